@@ -0,0 +1,2 @@
+ALTER TABLE users
+    ADD COLUMN IF NOT EXISTS group_results BOOLEAN NOT NULL DEFAULT TRUE;
@@ -0,0 +1,9 @@
+CREATE TABLE IF NOT EXISTS command_stats (
+    day   DATE NOT NULL,
+    kind  TEXT NOT NULL,
+    name  TEXT NOT NULL,
+    count INT8 NOT NULL DEFAULT 0,
+    PRIMARY KEY (day, kind, name)
+);
+
+CREATE INDEX ON command_stats(kind, day DESC);
@@ -0,0 +1,9 @@
+CREATE TABLE IF NOT EXISTS id_map (
+    media_type TEXT        NOT NULL,
+    mal_id     INT8        NOT NULL,
+    anilist_id INT8        NOT NULL,
+    updated_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    PRIMARY KEY (media_type, mal_id)
+);
+
+CREATE INDEX ON id_map(media_type, anilist_id);
@@ -0,0 +1,4 @@
+ALTER TABLE users
+    ADD COLUMN IF NOT EXISTS allow_adult BOOLEAN NOT NULL DEFAULT FALSE;
+ALTER TABLE groups
+    ADD COLUMN IF NOT EXISTS allow_adult BOOLEAN NOT NULL DEFAULT FALSE;
@@ -0,0 +1,4 @@
+ALTER TABLE users
+    ADD COLUMN IF NOT EXISTS title_language TEXT NOT NULL DEFAULT 'romaji';
+ALTER TABLE groups
+    ADD COLUMN IF NOT EXISTS title_language TEXT NOT NULL DEFAULT 'romaji';
@@ -0,0 +1,9 @@
+CREATE TABLE IF NOT EXISTS followed_anime (
+    user_id               INT8        NOT NULL,
+    anime_id              INT8        NOT NULL,
+    last_notified_episode INT4,
+    created_at            TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    PRIMARY KEY (user_id, anime_id)
+);
+
+CREATE INDEX ON followed_anime(anime_id);
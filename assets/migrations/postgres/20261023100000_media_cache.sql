@@ -0,0 +1,9 @@
+CREATE TABLE IF NOT EXISTS media_cache (
+    media_type TEXT        NOT NULL,
+    media_id   INT8        NOT NULL,
+    payload    JSONB       NOT NULL,
+    fetched_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    PRIMARY KEY (media_type, media_id)
+);
+
+CREATE INDEX ON media_cache(fetched_at);
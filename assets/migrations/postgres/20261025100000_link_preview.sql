@@ -0,0 +1,2 @@
+ALTER TABLE groups
+    ADD COLUMN IF NOT EXISTS link_preview BOOLEAN NOT NULL DEFAULT FALSE;
@@ -0,0 +1,6 @@
+CREATE TABLE IF NOT EXISTS banned_users (
+    user_id    INT8        PRIMARY KEY,
+    reason     TEXT,
+    banned_by  INT8        NOT NULL,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
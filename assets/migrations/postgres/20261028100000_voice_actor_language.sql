@@ -0,0 +1,4 @@
+ALTER TABLE users
+    ADD COLUMN IF NOT EXISTS voice_actor_language TEXT NOT NULL DEFAULT 'japanese';
+ALTER TABLE groups
+    ADD COLUMN IF NOT EXISTS voice_actor_language TEXT NOT NULL DEFAULT 'japanese';
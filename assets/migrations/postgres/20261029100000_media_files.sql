@@ -0,0 +1,5 @@
+CREATE TABLE IF NOT EXISTS media_files (
+    url         TEXT        PRIMARY KEY,
+    file_id     TEXT        NOT NULL,
+    uploaded_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
@@ -0,0 +1,13 @@
+CREATE TABLE IF NOT EXISTS view_history (
+    user_id    INT8        NOT NULL,
+    media_type TEXT        NOT NULL,
+    media_id   INT8        NOT NULL,
+    title      TEXT        NOT NULL,
+    viewed_at  TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    PRIMARY KEY (user_id, media_type, media_id)
+);
+
+CREATE INDEX ON view_history(user_id, viewed_at DESC);
+
+ALTER TABLE users
+    ADD COLUMN IF NOT EXISTS track_history BOOLEAN NOT NULL DEFAULT TRUE;
@@ -0,0 +1,8 @@
+CREATE TABLE IF NOT EXISTS favorites (
+    user_id    INT8        NOT NULL,
+    media_type TEXT        NOT NULL,
+    media_id   INT8        NOT NULL,
+    title      TEXT        NOT NULL,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    PRIMARY KEY (user_id, media_type, media_id)
+);
@@ -0,0 +1,10 @@
+CREATE TABLE IF NOT EXISTS manga_progress_snapshots (
+    user_id    INT8        NOT NULL,
+    manga_id   INT8        NOT NULL,
+    chapters   INT4        NOT NULL,
+    updated_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    PRIMARY KEY (user_id, manga_id)
+);
+
+ALTER TABLE users
+    ADD COLUMN IF NOT EXISTS chapter_notifications BOOLEAN NOT NULL DEFAULT FALSE;
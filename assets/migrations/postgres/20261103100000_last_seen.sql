@@ -0,0 +1,10 @@
+ALTER TABLE users
+    ADD COLUMN IF NOT EXISTS last_seen_at      TIMESTAMPTZ,
+    ADD COLUMN IF NOT EXISTS interaction_count INT8 NOT NULL DEFAULT 0;
+
+ALTER TABLE groups
+    ADD COLUMN IF NOT EXISTS last_seen_at      TIMESTAMPTZ,
+    ADD COLUMN IF NOT EXISTS interaction_count INT8 NOT NULL DEFAULT 0;
+
+CREATE INDEX IF NOT EXISTS users_last_seen_at_idx ON users(last_seen_at);
+CREATE INDEX IF NOT EXISTS groups_last_seen_at_idx ON groups(last_seen_at);
@@ -0,0 +1,2 @@
+ALTER TABLE groups
+    ADD COLUMN IF NOT EXISTS disabled_commands TEXT NOT NULL DEFAULT '';
@@ -0,0 +1,2 @@
+ALTER TABLE groups
+    ADD COLUMN IF NOT EXISTS clean_service_messages INT4 NOT NULL DEFAULT 0;
@@ -0,0 +1,5 @@
+ALTER TABLE users
+    ADD COLUMN IF NOT EXISTS packed_chat TEXT;
+
+ALTER TABLE groups
+    ADD COLUMN IF NOT EXISTS packed_chat TEXT;
@@ -0,0 +1,116 @@
+-- The timestamps are stored as RFC 3339 text, as sqlx encodes them, so they compare with
+-- the bound ones.
+
+CREATE TABLE IF NOT EXISTS users (
+    id                       INT8      PRIMARY KEY,
+    anilist_id               INT4      UNIQUE,
+    anilist_token            TEXT,
+    anilist_token_expires_at TIMESTAMP,
+    language_code            TEXT      NOT NULL DEFAULT 'pt',
+    group_results            BOOLEAN   NOT NULL DEFAULT TRUE,
+    allow_adult              BOOLEAN   NOT NULL DEFAULT FALSE,
+    title_language           TEXT      NOT NULL DEFAULT 'romaji',
+    voice_actor_language     TEXT      NOT NULL DEFAULT 'japanese',
+    track_history            BOOLEAN   NOT NULL DEFAULT TRUE,
+    chapter_notifications    BOOLEAN   NOT NULL DEFAULT FALSE,
+    blocked_at               TIMESTAMP,
+    created_at               TIMESTAMP NOT NULL DEFAULT (strftime('%Y-%m-%dT%H:%M:%f+00:00', 'now')),
+    updated_at               TIMESTAMP NOT NULL DEFAULT (strftime('%Y-%m-%dT%H:%M:%f+00:00', 'now'))
+);
+
+CREATE INDEX IF NOT EXISTS users_created_at_idx ON users(created_at DESC, updated_at DESC);
+
+CREATE TABLE IF NOT EXISTS groups (
+    id                   INT8      PRIMARY KEY,
+    language_code        TEXT      NOT NULL DEFAULT 'pt',
+    allow_adult          BOOLEAN   NOT NULL DEFAULT FALSE,
+    title_language       TEXT      NOT NULL DEFAULT 'romaji',
+    voice_actor_language TEXT      NOT NULL DEFAULT 'japanese',
+    birthdays            BOOLEAN   NOT NULL DEFAULT FALSE,
+    link_preview         BOOLEAN   NOT NULL DEFAULT FALSE,
+    created_at           TIMESTAMP NOT NULL DEFAULT (strftime('%Y-%m-%dT%H:%M:%f+00:00', 'now')),
+    updated_at           TIMESTAMP NOT NULL DEFAULT (strftime('%Y-%m-%dT%H:%M:%f+00:00', 'now'))
+);
+
+CREATE INDEX IF NOT EXISTS groups_created_at_idx ON groups(created_at DESC, updated_at DESC);
+
+CREATE TABLE IF NOT EXISTS command_stats (
+    day   DATE NOT NULL,
+    kind  TEXT NOT NULL,
+    name  TEXT NOT NULL,
+    count INT8 NOT NULL DEFAULT 0,
+    PRIMARY KEY (day, kind, name)
+);
+
+CREATE INDEX IF NOT EXISTS command_stats_kind_idx ON command_stats(kind, day DESC);
+
+CREATE TABLE IF NOT EXISTS id_map (
+    media_type TEXT      NOT NULL,
+    mal_id     INT8      NOT NULL,
+    anilist_id INT8      NOT NULL,
+    updated_at TIMESTAMP NOT NULL DEFAULT (strftime('%Y-%m-%dT%H:%M:%f+00:00', 'now')),
+    PRIMARY KEY (media_type, mal_id)
+);
+
+CREATE INDEX IF NOT EXISTS id_map_anilist_id_idx ON id_map(media_type, anilist_id);
+
+CREATE TABLE IF NOT EXISTS followed_anime (
+    user_id               INT8      NOT NULL,
+    anime_id              INT8      NOT NULL,
+    last_notified_episode INT4,
+    created_at            TIMESTAMP NOT NULL DEFAULT (strftime('%Y-%m-%dT%H:%M:%f+00:00', 'now')),
+    PRIMARY KEY (user_id, anime_id)
+);
+
+CREATE INDEX IF NOT EXISTS followed_anime_anime_id_idx ON followed_anime(anime_id);
+
+CREATE TABLE IF NOT EXISTS media_cache (
+    media_type TEXT      NOT NULL,
+    media_id   INT8      NOT NULL,
+    payload    TEXT      NOT NULL,
+    fetched_at TIMESTAMP NOT NULL DEFAULT (strftime('%Y-%m-%dT%H:%M:%f+00:00', 'now')),
+    PRIMARY KEY (media_type, media_id)
+);
+
+CREATE INDEX IF NOT EXISTS media_cache_fetched_at_idx ON media_cache(fetched_at);
+
+CREATE TABLE IF NOT EXISTS banned_users (
+    user_id    INT8      PRIMARY KEY,
+    reason     TEXT,
+    banned_by  INT8      NOT NULL,
+    created_at TIMESTAMP NOT NULL DEFAULT (strftime('%Y-%m-%dT%H:%M:%f+00:00', 'now'))
+);
+
+CREATE TABLE IF NOT EXISTS media_files (
+    url         TEXT      PRIMARY KEY,
+    file_id     TEXT      NOT NULL,
+    uploaded_at TIMESTAMP NOT NULL DEFAULT (strftime('%Y-%m-%dT%H:%M:%f+00:00', 'now'))
+);
+
+CREATE TABLE IF NOT EXISTS view_history (
+    user_id    INT8      NOT NULL,
+    media_type TEXT      NOT NULL,
+    media_id   INT8      NOT NULL,
+    title      TEXT      NOT NULL,
+    viewed_at  TIMESTAMP NOT NULL DEFAULT (strftime('%Y-%m-%dT%H:%M:%f+00:00', 'now')),
+    PRIMARY KEY (user_id, media_type, media_id)
+);
+
+CREATE INDEX IF NOT EXISTS view_history_viewed_at_idx ON view_history(user_id, viewed_at DESC);
+
+CREATE TABLE IF NOT EXISTS favorites (
+    user_id    INT8      NOT NULL,
+    media_type TEXT      NOT NULL,
+    media_id   INT8      NOT NULL,
+    title      TEXT      NOT NULL,
+    created_at TIMESTAMP NOT NULL DEFAULT (strftime('%Y-%m-%dT%H:%M:%f+00:00', 'now')),
+    PRIMARY KEY (user_id, media_type, media_id)
+);
+
+CREATE TABLE IF NOT EXISTS manga_progress_snapshots (
+    user_id    INT8      NOT NULL,
+    manga_id   INT8      NOT NULL,
+    chapters   INT4      NOT NULL,
+    updated_at TIMESTAMP NOT NULL DEFAULT (strftime('%Y-%m-%dT%H:%M:%f+00:00', 'now')),
+    PRIMARY KEY (user_id, manga_id)
+);
@@ -0,0 +1,8 @@
+ALTER TABLE users ADD COLUMN last_seen_at TIMESTAMP;
+ALTER TABLE users ADD COLUMN interaction_count INT8 NOT NULL DEFAULT 0;
+
+ALTER TABLE groups ADD COLUMN last_seen_at TIMESTAMP;
+ALTER TABLE groups ADD COLUMN interaction_count INT8 NOT NULL DEFAULT 0;
+
+CREATE INDEX IF NOT EXISTS users_last_seen_at_idx ON users(last_seen_at);
+CREATE INDEX IF NOT EXISTS groups_last_seen_at_idx ON groups(last_seen_at);
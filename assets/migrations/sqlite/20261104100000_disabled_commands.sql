@@ -0,0 +1 @@
+ALTER TABLE groups ADD COLUMN disabled_commands TEXT NOT NULL DEFAULT '';
@@ -0,0 +1 @@
+ALTER TABLE groups ADD COLUMN clean_service_messages INT4 NOT NULL DEFAULT 0;
@@ -0,0 +1,3 @@
+ALTER TABLE users ADD COLUMN packed_chat TEXT;
+
+ALTER TABLE groups ADD COLUMN packed_chat TEXT;
@@ -0,0 +1,28 @@
+// Copyright 2025 - Andriel Ferreira
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// https://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or https://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+//! The build script.
+
+/// Where the migrations embedded in the binary are located, one directory per backend.
+const MIGRATIONS_PATHS: &[&str] = &["assets/migrations/postgres", "assets/migrations/sqlite"];
+
+fn main() {
+    for path in MIGRATIONS_PATHS {
+        // The migrations are embedded by `sqlx::migrate!`, rebuild when they change.
+        println!("cargo:rerun-if-changed={}", path);
+
+        let migrations = std::fs::read_dir(path)
+            .expect("failed to read migrations directory")
+            .filter_map(|entry| entry.ok())
+            .filter(|entry| entry.path().extension().is_some_and(|ext| ext == "sql"))
+            .count();
+        if migrations == 0 {
+            panic!("no migrations found in {}", path);
+        }
+    }
+}
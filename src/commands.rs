@@ -0,0 +1,385 @@
+// Copyright 2025 - Andriel Ferreira
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// https://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or https://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+//! The command list shown by Telegram.
+
+use grammers_client::{Client, grammers_tl_types as tl};
+
+use crate::resources::I18n;
+
+/// A command shown in the Telegram command list.
+#[derive(Clone, Copy, Debug)]
+pub struct Command {
+    /// The command, without the slash.
+    pub name: &'static str,
+    /// The default description, overridden per locale by the `command_{name}` key.
+    pub description: &'static str,
+}
+
+/// The commands shown in the Telegram command list.
+pub const COMMANDS: &[Command] = &[
+    Command {
+        name: "start",
+        description: "Start the bot.",
+    },
+    Command {
+        name: "help",
+        description: "Show what the bot can do.",
+    },
+    Command {
+        name: "anime",
+        description: "Search for animes.",
+    },
+    Command {
+        name: "manga",
+        description: "Search for mangas.",
+    },
+    Command {
+        name: "char",
+        description: "Search for characters.",
+    },
+    Command {
+        name: "charimg",
+        description: "Send the image of a character.",
+    },
+    Command {
+        name: "user",
+        description: "Search for users.",
+    },
+    Command {
+        name: "me",
+        description: "Show your AniList profile.",
+    },
+    Command {
+        name: "staff",
+        description: "Search for staff and voice actors.",
+    },
+    Command {
+        name: "studio",
+        description: "Search for studios.",
+    },
+    Command {
+        name: "trending",
+        description: "Show the trending animes and mangas.",
+    },
+    Command {
+        name: "season",
+        description: "Browse the animes of a season.",
+    },
+    Command {
+        name: "random",
+        description: "Show a random anime or manga.",
+    },
+    Command {
+        name: "wp",
+        description: "Ask a group whether to watch an anime.",
+    },
+    Command {
+        name: "birthdays",
+        description: "List the characters whose birthday is today.",
+    },
+    Command {
+        name: "genre",
+        description: "Browse the animes and mangas of a genre.",
+    },
+    Command {
+        name: "top",
+        description: "Show the best scored animes and mangas.",
+    },
+    Command {
+        name: "follow",
+        description: "Get notified when an anime airs.",
+    },
+    Command {
+        name: "unfollow",
+        description: "Unfollow an anime.",
+    },
+    Command {
+        name: "following",
+        description: "List the animes you follow.",
+    },
+    Command {
+        name: "history",
+        description: "List the cards you opened recently.",
+    },
+    Command {
+        name: "favorites",
+        description: "List the entries you bookmarked.",
+    },
+    Command {
+        name: "export",
+        description: "Export the data the bot stores about you.",
+    },
+    Command {
+        name: "deleteme",
+        description: "Delete the data the bot stores about you.",
+    },
+    Command {
+        name: "id",
+        description: "Link AniList and MyAnimeList entries.",
+    },
+    Command {
+        name: "auth",
+        description: "Authenticate with AniList.",
+    },
+    Command {
+        name: "cancelauth",
+        description: "Cancel a pending authentication.",
+    },
+    Command {
+        name: "language",
+        description: "Change the bot language.",
+    },
+    Command {
+        name: "settings",
+        description: "Change the bot settings.",
+    },
+    Command {
+        name: "diagnose",
+        description: "Find out why the bot does not answer in a group.",
+    },
+    Command {
+        name: "about",
+        description: "About the bot.",
+    },
+    Command {
+        name: "ping",
+        description: "Ping the bot.",
+    },
+];
+
+/// A group of commands in `/help`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum HelpCategory {
+    /// Searching AniList.
+    Search,
+    /// Browsing rankings, seasons and genres.
+    Discover,
+    /// The user's own AniList account and follows.
+    Account,
+    /// The bot itself and its settings.
+    Bot,
+}
+
+impl HelpCategory {
+    /// All the categories, in the order they are listed.
+    pub const ALL: [Self; 4] = [Self::Search, Self::Discover, Self::Account, Self::Bot];
+
+    /// Gets the name of the category, for its `help_category_{name}` key.
+    pub fn name(&self) -> &'static str {
+        match self {
+            Self::Search => "search",
+            Self::Discover => "discover",
+            Self::Account => "account",
+            Self::Bot => "bot",
+        }
+    }
+}
+
+/// A command explained by `/help`.
+///
+/// Each plugin lists the entries of its commands in a `HELP` constant, collected by
+/// [`crate::plugins::help_entries`]. The details are translated by the `help_{command}` key.
+#[derive(Clone, Copy, Debug)]
+pub struct HelpEntry {
+    /// The command, without the slash, as in [`COMMANDS`].
+    pub command: &'static str,
+    /// The other names of the command.
+    pub aliases: &'static [&'static str],
+    /// The category of the command.
+    pub category: HelpCategory,
+    /// The prefix of the same search in the inline mode, e.g. `!a`.
+    pub inline: Option<&'static str>,
+}
+
+impl HelpEntry {
+    /// Creates a help entry without aliases nor inline mode.
+    ///
+    /// # Arguments
+    ///
+    /// * `command` - The command, without the slash.
+    /// * `category` - The category of the command.
+    pub const fn new(command: &'static str, category: HelpCategory) -> Self {
+        Self {
+            command,
+            aliases: &[],
+            category,
+            inline: None,
+        }
+    }
+
+    /// Sets the other names of the command.
+    ///
+    /// # Arguments
+    ///
+    /// * `aliases` - The other names, without the slash.
+    pub const fn aliases(mut self, aliases: &'static [&'static str]) -> Self {
+        self.aliases = aliases;
+        self
+    }
+
+    /// Sets the prefix of the same search in the inline mode.
+    ///
+    /// # Arguments
+    ///
+    /// * `prefix` - The prefix, e.g. `!a`.
+    pub const fn inline(mut self, prefix: &'static str) -> Self {
+        self.inline = Some(prefix);
+        self
+    }
+}
+
+/// The commands the groups can not turn off, the admins need them to turn the others back on.
+pub const ALWAYS_ENABLED: &[&str] = &["start", "help", "settings", "language", "diagnose"];
+
+/// Gets the commands the groups can turn off, in the order of `/help`.
+pub fn gateable_commands() -> Vec<HelpEntry> {
+    crate::plugins::help_entries()
+        .into_iter()
+        .filter(|entry| !ALWAYS_ENABLED.contains(&entry.command))
+        .collect()
+}
+
+/// Gets the command a name refers to, resolving the aliases.
+///
+/// Returns `None` if no command of `/help` has the name.
+///
+/// # Arguments
+///
+/// * `name` - The name, without the slash.
+pub fn resolve_command(name: &str) -> Option<&'static str> {
+    crate::plugins::help_entries()
+        .into_iter()
+        .find(|entry| entry.command == name || entry.aliases.contains(&name))
+        .map(|entry| entry.command)
+}
+
+/// Parses the name of the command a message starts with, e.g. `anime` for `/Anime@bot naruto`.
+///
+/// Returns `None` if the message is not a command, or is addressed to another bot.
+///
+/// # Arguments
+///
+/// * `text` - The text of the message.
+/// * `bot_username` - The username of the bot, if known.
+pub fn parse_command(text: &str, bot_username: Option<&str>) -> Option<String> {
+    let command = text.strip_prefix('/')?.split(char::is_whitespace).next()?;
+    let (name, mention) = match command.split_once('@') {
+        Some((name, mention)) => (name, Some(mention)),
+        None => (command, None),
+    };
+
+    if let (Some(mention), Some(username)) = (mention, bot_username) {
+        if !mention.eq_ignore_ascii_case(username) {
+            return None;
+        }
+    }
+    if name.is_empty() {
+        return None;
+    }
+
+    Some(name.to_lowercase())
+}
+
+/// The result of a command list sync.
+#[derive(Clone, Debug, Default)]
+pub struct SyncSummary {
+    /// The language code of each updated list (empty for the global one) and its size.
+    pub registered: Vec<(String, usize)>,
+    /// The language code of each list that failed to update and the error.
+    pub failed: Vec<(String, String)>,
+}
+
+/// Gets the command list of a locale.
+///
+/// Returns `None` for locales that do not translate any description, they use the global list.
+///
+/// # Arguments
+///
+/// * `i18n` - The i18n resource.
+/// * `locale` - The locale, `None` for the global list.
+pub fn command_list(i18n: &I18n, locale: Option<&str>) -> Option<Vec<(String, String)>> {
+    let translations = COMMANDS
+        .iter()
+        .map(|command| {
+            locale.and_then(|locale| i18n.lookup(format!("command_{}", command.name), locale))
+        })
+        .collect::<Vec<_>>();
+
+    if locale.is_some() && translations.iter().all(Option::is_none) {
+        return None;
+    }
+
+    Some(
+        COMMANDS
+            .iter()
+            .zip(translations)
+            .map(|(command, translation)| {
+                (
+                    command.name.to_string(),
+                    translation.unwrap_or(command.description.to_string()),
+                )
+            })
+            .collect(),
+    )
+}
+
+/// Updates the global command list and the list of each locale that translates it.
+///
+/// Failures are collected instead of stopping the sync.
+///
+/// # Arguments
+///
+/// * `client` - The Telegram client.
+/// * `i18n` - The i18n resource.
+pub async fn sync(client: &Client, i18n: &I18n) -> SyncSummary {
+    let mut summary = SyncSummary::default();
+
+    let mut locales = i18n.locales();
+    locales.sort();
+
+    let lists = std::iter::once((String::new(), command_list(i18n, None))).chain(
+        locales
+            .iter()
+            .map(|locale| (locale.clone(), command_list(i18n, Some(locale)))),
+    );
+
+    for (lang_code, list) in lists {
+        let Some(list) = list else {
+            continue;
+        };
+        let count = list.len();
+
+        let result = client
+            .invoke(&tl::functions::bots::SetBotCommands {
+                scope: tl::enums::BotCommandScope::Default,
+                lang_code: lang_code.clone(),
+                commands: list
+                    .into_iter()
+                    .map(|(command, description)| {
+                        tl::types::BotCommand {
+                            command,
+                            description,
+                        }
+                        .into()
+                    })
+                    .collect(),
+            })
+            .await;
+
+        match result {
+            Ok(_) => summary.registered.push((lang_code, count)),
+            Err(e) => {
+                log::error!("failed to set the commands of {:?}: {:?}", lang_code, e);
+                summary.failed.push((lang_code, e.to_string()));
+            }
+        }
+    }
+
+    summary
+}
@@ -0,0 +1,311 @@
+// Copyright 2025 - Andriel Ferreira
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// https://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or https://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+//! The bot.
+
+pub mod commands;
+pub mod config;
+pub mod middlewares;
+pub mod models;
+pub mod plugins;
+pub mod resources;
+pub mod tasks;
+pub mod utils;
+
+use std::time::Duration;
+
+pub use config::Config;
+use ferogram::{Client, Injector, Result};
+use grammers_client::{InputMessage, Update, types::inline};
+use resources::{
+    AniList, Bans, CallbackSigner, Cards, Correlations, Database, Debounce, Diagnostics,
+    DisabledCommands, Favorites, I18n, Maintenance, MediaFiles, Metrics, SearchStash, Seeds,
+    Sessions, Stats, Throttle, TokenCipher, callback_signer, correlations::UpdateKey, token_cipher,
+};
+use utils::ErrorClass;
+
+/// Loads the configuration, connects to Telegram and the database, and runs the bot
+/// until it is interrupted.
+///
+/// # Errors
+///
+/// Returns an error if the bot could not be started.
+pub fn run() -> Result<()> {
+    tokio_uring::start(async {
+        // Initialize the injector.
+        let mut injector = Injector::default();
+
+        // Load the configuration.
+        let config = Config::load()?;
+
+        // Register the config resource.
+        injector.insert(config.clone());
+
+        // Set the log level if it is not set.
+        if std::env::var("RUST_LOG").is_err() {
+            unsafe {
+                std::env::set_var(
+                    "RUST_LOG",
+                    format!("yamata_no_orochi={}", config.app.log_level),
+                );
+            }
+        }
+
+        // Initialize the logger.
+        env_logger::init();
+
+        // Encrypt the stored AniList tokens, if there is a key.
+        match config
+            .app
+            .token_encryption_key
+            .as_deref()
+            .and_then(TokenCipher::from_base64)
+        {
+            Some(cipher) => token_cipher::set_cipher(cipher),
+            None => log::warn!(
+                "no token_encryption_key set, the AniList tokens are stored in plain text"
+            ),
+        }
+
+        // Sign the callbacks of the buttons with a key derived from the bot token.
+        callback_signer::set_signer(CallbackSigner::from_bot_token(&config.telegram.bot_token));
+
+        // Initialize and register the i18n resource, the error handler needs it too.
+        let mut i18n = I18n::with_locale(&config.app.default_locale);
+        i18n.load()?;
+        i18n.ensure_default_locale();
+        injector.insert(i18n.clone());
+
+        // Initialize the client.
+        log::info!("connecting to the telegram server...");
+
+        // Allow at most one error reply per chat per minute.
+        let error_throttle = Throttle::<i64>::new(500, Duration::from_secs(60));
+
+        // Initialize and register the correlations resource, shared with the error handler.
+        let correlations = Correlations::new();
+        injector.insert(correlations.clone());
+
+        let error_i18n = i18n.clone();
+        let support_chat = config.telegram.support_chat.clone();
+        let client = Client::bot(config.telegram.bot_token.clone())
+            .api_id(config.telegram.api_id)
+            .api_hash(config.telegram.api_hash.clone())
+            .session_file(config.app.session_file.clone())
+            .catch_up(config.telegram.catch_up)
+            .flood_sleep_threshold(config.telegram.flood_sleep_threshold)
+            .on_err(move |_, update, err| {
+                let (error_throttle, correlations, i18n, support_chat) = (
+                    error_throttle.clone(),
+                    correlations.clone(),
+                    error_i18n.clone(),
+                    support_chat.clone(),
+                );
+
+                async move {
+                    let correlation = match UpdateKey::from_update(&update) {
+                        Some(key) => correlations.take(&key).await,
+                        None => None,
+                    }
+                    .unwrap_or_default();
+                    let id = correlation.id;
+                    log::error!("[{0}] An error occurred: {1:?}", id, err);
+
+                    // Answer in the language of the chat, once it was loaded.
+                    let i18n = match correlation.locale {
+                        Some(locale) => i18n.for_locale(locale),
+                        None => i18n,
+                    };
+                    let class = ErrorClass::of(&*err);
+
+                    match update {
+                        Update::NewMessage(message) | Update::MessageEdited(message) => {
+                            if !error_throttle.allow(message.chat().id()).await {
+                                log::debug!(
+                                    "[{0}] skipping the error reply to chat {1:?}, already replied recently",
+                                    id,
+                                    message.chat().id()
+                                );
+                                return Ok(());
+                            }
+
+                            let text = utils::gen_error_text(class, &id, &support_chat, &i18n);
+                            if let Err(e) = message.reply(InputMessage::html(text)).await {
+                                log::warn!("[{0}] failed to reply to an error: {1:?}", id, e);
+                            }
+                        }
+                        Update::CallbackQuery(query) => {
+                            if let Err(e) = query
+                                .answer()
+                                .alert(utils::gen_error_alert(class, &id, &support_chat, &i18n))
+                                .send()
+                                .await
+                            {
+                                log::warn!(
+                                    "[{0}] failed to answer a callback query with an error: {1:?}",
+                                    id,
+                                    e
+                                );
+                            }
+                        }
+                        Update::InlineQuery(query) => {
+                            let text = utils::gen_error_text(class, &id, &support_chat, &i18n);
+                            let article = inline::query::Article::new(
+                                i18n.translate("error_title"),
+                                InputMessage::html(text),
+                            )
+                            .description(utils::gen_error_alert(
+                                class,
+                                &id,
+                                &support_chat,
+                                &i18n,
+                            ));
+
+                            if let Err(e) = query
+                                .answer(vec![article])
+                                .switch_pm(i18n.translate("error_report_btn"), "error_report")
+                                .send()
+                                .await
+                            {
+                                log::warn!(
+                                    "[{0}] failed to answer an inline query with an error: {1:?}",
+                                    id,
+                                    e
+                                );
+                            }
+                        }
+                        _ => {
+                            log::debug!("[{0}] A update error was not handled: {1}\n{2:?}", id, err, update);
+                        },
+                    };
+
+                    Ok(())
+                }
+            })
+            .wait_for_ctrl_c()
+            .build_and_connect()
+            .await?;
+
+        log::info!("telegram server connected");
+
+        // Remember the username of the bot, for the deep links of the cards.
+        match client.inner().get_me().await {
+            Ok(me) => {
+                if let Some(username) = me.username() {
+                    utils::set_bot_username(username);
+                }
+            }
+            Err(e) => log::warn!("failed to get the username of the bot: {:?}", e),
+        }
+
+        // Register the command list with Telegram.
+        let summary = commands::sync(client.inner(), &i18n).await;
+        if !summary.failed.is_empty() {
+            log::warn!("some command lists failed to sync: {:?}", summary.failed);
+        }
+
+        // Initialize and register the search stash resource.
+        let search_stash = SearchStash::new();
+        injector.insert(search_stash);
+
+        // Initialize and register the seeds resource, reusing rolls for a few seconds.
+        let seeds = Seeds::new(Duration::from_secs(10));
+        injector.insert(seeds);
+
+        // Initialize and register the inline debounce, dropping the queries typed over quickly.
+        let inline_debounce = Debounce::<i64>::new(1000, plugins::inline::DEBOUNCE_DELAY);
+        injector.insert(inline_debounce);
+
+        // Initialize and register the sessions resource.
+        let sessions = Sessions::new();
+        injector.insert(sessions.clone());
+
+        // Initialize and register the diagnostics resource.
+        let diagnostics = Diagnostics::new();
+        injector.insert(diagnostics);
+
+        // Initialize and register the stats resource, starting the uptime.
+        let stats = Stats::new();
+        injector.insert(stats);
+
+        // Initialize and register the bans resource.
+        let bans = Bans::new();
+        injector.insert(bans);
+
+        // Initialize and register the disabled commands resource.
+        let disabled_commands = DisabledCommands::new();
+        injector.insert(disabled_commands);
+
+        // Initialize and register the maintenance resource, turned off.
+        let maintenance = Maintenance::new();
+        injector.insert(maintenance);
+
+        // Initialize and register the metrics resource.
+        let metrics = Metrics::new();
+        injector.insert(metrics.clone());
+
+        // Initialize and register the database resource.
+        let database =
+            Database::connect(&config.app.database_url, &config.app.pool_settings()).await?;
+        database
+            .migrate(config.app.migrations_path.as_deref())
+            .await?;
+
+        // Encrypt the tokens stored before the key was set.
+        match database.report(models::User::encrypt_plain_tokens(database.pool()).await) {
+            Ok(0) => {}
+            Ok(count) => log::info!("encrypted {} stored AniList tokens", count),
+            Err(e) => log::warn!("failed to encrypt the stored AniList tokens: {:?}", e),
+        }
+
+        // Initialize and register the AniList resource.
+        let anilist = AniList::new()
+            .with_rate_limit(config.anilist.requests_per_minute)
+            .with_media_cache_freshness(Duration::from_secs(
+                config.anilist.media_cache_hours * 60 * 60,
+            ))
+            .with_database(database.clone())
+            .with_metrics(metrics.clone());
+        injector.insert(anilist.clone());
+
+        // Initialize and register the favorites resource.
+        let favorites = Favorites::new(database.clone());
+        injector.insert(favorites.clone());
+
+        // Initialize and register the cards resource, remembering the images they upload and
+        // showing their star from the favorites.
+        let media_files = MediaFiles::new().with_database(database.clone());
+        injector.insert(Cards::new(database.clone(), media_files, favorites));
+
+        // Spawn the background tasks.
+        tasks::setup(
+            client.inner(),
+            &config,
+            &database,
+            &i18n,
+            &metrics,
+            &anilist,
+            &sessions,
+        );
+
+        injector.insert(database);
+
+        // Register the handlers and run the client.
+        let router_config = config.clone();
+        client
+            .dispatcher(|dp| {
+                dp.resources(|_| injector)
+                    .router(move |router| plugins::setup(router, &router_config))
+                    .middlewares(move |stack| middlewares::setup(stack, &config))
+            })
+            .run()
+            .await?;
+
+        Ok(())
+    })
+}
@@ -0,0 +1,39 @@
+// Copyright 2025 - Andriel Ferreira
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// https://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or https://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+//! Answer callbacks middleware.
+
+use async_trait::async_trait;
+use ferogram::{
+    Injector, Middleware,
+    flow::{self, Flow},
+};
+use grammers_client::{Client, InvocationError, Update};
+
+/// The middleware to answer the callback queries the handlers left unanswered.
+///
+/// Telegram keeps the button spinning until the query is answered, so a handler that returns
+/// early without answering it would leave the user waiting. Answering a query twice fails, the
+/// queries the handlers already answered are ignored.
+#[derive(Clone)]
+pub struct AnswerCallbacks;
+
+#[async_trait]
+impl Middleware for AnswerCallbacks {
+    async fn handle(&mut self, _: &Client, update: &Update, _: &mut Injector) -> Flow {
+        if let Update::CallbackQuery(query) = update {
+            match query.answer().send().await {
+                Ok(()) => log::trace!("answered a callback query left unanswered"),
+                Err(InvocationError::Rpc(rpc)) if rpc.name == "QUERY_ID_INVALID" => {}
+                Err(e) => log::debug!("failed to answer a callback query: {:?}", e),
+            }
+        }
+
+        flow::continue_now()
+    }
+}
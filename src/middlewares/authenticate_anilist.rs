@@ -18,24 +18,13 @@ use ferogram::{
 use grammers_client::{Client, Update};
 
 use crate::{
-    models::User,
-    resources::{AniList, Cache, Database},
+    models::{AniListAuth, User},
+    resources::{AniList, Database, Sessions},
 };
 
 /// The middleware to update the Anilist client token.
 #[derive(Clone)]
-pub struct AuthenticateAniList {
-    clients: Cache<i64, Arc<rust_anilist::Client>>,
-}
-
-impl AuthenticateAniList {
-    /// Creates a new instance of the middleware.
-    pub fn new() -> Self {
-        Self {
-            clients: Cache::with_capacity(50),
-        }
-    }
-}
+pub struct AuthenticateAniList;
 
 #[async_trait]
 impl Middleware for AuthenticateAniList {
@@ -44,23 +33,26 @@ impl Middleware for AuthenticateAniList {
 
         let db = injector.get::<Database>().unwrap();
         let ctx = injector.get::<Context>().unwrap();
+        let sessions = injector.get::<Sessions>().unwrap();
+
+        let mut auth = AniListAuth::Anonymous;
 
         let pool = db.pool();
         if let Some(sender) = ctx.sender() {
-            if let Ok(Some(user)) = User::get_by_id(pool, &sender.id()).await {
-                if let Some(client) = self.clients.get(&user.id) {
-                    ani.client = client.clone();
-                } else {
-                    log::debug!("creating a new Anilist client for user {:?}", user.id);
+            if let Ok(Some(user)) = db.report(User::get_by_id(pool, &sender.id()).await) {
+                auth = AniListAuth::of(Some(&user));
 
-                    let client = Arc::new(if let Some(token) = user.anilist_token {
-                        rust_anilist::Client::with_token(&token).timeout(Duration::from_secs(15))
-                    } else {
-                        rust_anilist::Client::with_timeout(Duration::from_secs(15))
-                    });
-
-                    self.clients.insert(user.id, Arc::clone(&client)).await;
+                if auth == AniListAuth::Expired {
+                    // The expired token would only fail, the handlers prompt to authenticate again.
+                    sessions.remove_client(user.id).await;
+                    ani.client =
+                        Arc::new(rust_anilist::Client::with_timeout(Duration::from_secs(15)));
+                } else if let Some(client) = sessions.client(user.id).await {
                     ani.client = client;
+                } else {
+                    ani.client = sessions
+                        .authenticate(user.id, user.token().as_deref())
+                        .await;
                 }
             }
         } else {
@@ -70,6 +62,7 @@ impl Middleware for AuthenticateAniList {
         }
 
         injector.insert(ani);
+        injector.insert(auth);
 
         flow::continue_now()
     }
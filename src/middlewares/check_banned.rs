@@ -0,0 +1,61 @@
+// Copyright 2025 - Andriel Ferreira
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// https://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or https://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+//! Check banned middleware.
+
+use async_trait::async_trait;
+use ferogram::{
+    Context, Injector, Middleware,
+    flow::{self, Flow},
+};
+use grammers_client::{Client, Update};
+
+use crate::resources::{Bans, Database, I18n};
+
+/// The middleware to drop the updates of the banned users.
+///
+/// It runs before the chat language is loaded, so the banned users cost no more queries.
+#[derive(Clone)]
+pub struct CheckBanned;
+
+#[async_trait]
+impl Middleware for CheckBanned {
+    async fn handle(&mut self, _: &Client, update: &Update, injector: &mut Injector) -> Flow {
+        let ctx = injector.get::<Context>().unwrap();
+        let Some(sender) = ctx.sender() else {
+            return flow::continue_now();
+        };
+
+        let bans = injector.get::<Bans>().unwrap();
+        let db = injector.get::<Database>().unwrap();
+        if !bans.is_banned(db, sender.id()).await {
+            return flow::continue_now();
+        }
+
+        log::trace!("dropping an update from the banned user {}", sender.id());
+
+        // Unanswered callback queries keep the button loading.
+        if let Update::CallbackQuery(query) = update {
+            let i18n = injector.get::<I18n>().unwrap();
+
+            if let Err(e) = query
+                .answer()
+                .alert(i18n.translate("banned_alert"))
+                .send()
+                .await
+            {
+                log::warn!(
+                    "failed to answer a callback query of a banned user: {:?}",
+                    e
+                );
+            }
+        }
+
+        flow::break_now()
+    }
+}
@@ -0,0 +1,73 @@
+// Copyright 2025 - Andriel Ferreira
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// https://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or https://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+//! Check maintenance middleware.
+
+use async_trait::async_trait;
+use ferogram::{
+    Context, Injector, Middleware,
+    flow::{self, Flow},
+};
+use grammers_client::{Client, InputMessage, Update};
+
+use crate::{
+    Config,
+    resources::{I18n, Maintenance},
+};
+
+/// The middleware to stop the updates of the non-sudoers while the bot is under maintenance.
+///
+/// It must run after the chat language is loaded, so the notice is translated.
+#[derive(Clone)]
+pub struct CheckMaintenance;
+
+#[async_trait]
+impl Middleware for CheckMaintenance {
+    async fn handle(&mut self, _: &Client, update: &Update, injector: &mut Injector) -> Flow {
+        let maintenance = injector.get::<Maintenance>().unwrap();
+        if !maintenance.is_enabled() {
+            return flow::continue_now();
+        }
+
+        let config = injector.get::<Config>().unwrap();
+        let ctx = injector.get::<Context>().unwrap();
+        if ctx
+            .sender()
+            .is_some_and(|sender| config.telegram.is_sudoer(sender.id()))
+        {
+            return flow::continue_now();
+        }
+
+        let i18n = injector.get::<I18n>().unwrap();
+
+        // Only the messages meant for the bot are answered, the groups would be flooded otherwise.
+        match update {
+            Update::NewMessage(message) if ctx.is_private() || message.text().starts_with('/') => {
+                if let Err(e) = message
+                    .reply(InputMessage::html(i18n.translate("under_maintenance")))
+                    .await
+                {
+                    log::warn!("failed to reply with the maintenance notice: {:?}", e);
+                }
+            }
+            Update::CallbackQuery(query) => {
+                if let Err(e) = query
+                    .answer()
+                    .alert(i18n.translate("under_maintenance_alert"))
+                    .send()
+                    .await
+                {
+                    log::warn!("failed to answer with the maintenance notice: {:?}", e);
+                }
+            }
+            _ => {}
+        }
+
+        flow::break_now()
+    }
+}
@@ -0,0 +1,98 @@
+// Copyright 2025 - Andriel Ferreira
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// https://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or https://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+//! Command gate middleware.
+
+use std::time::Duration;
+
+use async_trait::async_trait;
+use ferogram::{
+    Context, Injector, Middleware,
+    flow::{self, Flow},
+};
+use grammers_client::{Client, InputMessage, Update};
+use maplit::hashmap;
+
+use crate::{
+    Config, commands,
+    resources::{Database, DisabledCommands, I18n},
+    utils,
+};
+
+/// How long the notice of a disabled command stays before it is deleted.
+const NOTICE_LIFETIME: Duration = Duration::from_secs(10);
+
+/// The middleware to drop the commands a group turned off.
+///
+/// It must run after the chat language is loaded, so the notice is translated. Private chats
+/// are never gated.
+#[derive(Clone)]
+pub struct CommandGate;
+
+#[async_trait]
+impl Middleware for CommandGate {
+    async fn handle(&mut self, _: &Client, update: &Update, injector: &mut Injector) -> Flow {
+        let Update::NewMessage(message) = update else {
+            return flow::continue_now();
+        };
+
+        let ctx = injector.get::<Context>().unwrap();
+        if ctx.is_private() {
+            return flow::continue_now();
+        }
+
+        let Some(command) = commands::parse_command(message.text(), utils::bot_username())
+            .and_then(|name| commands::resolve_command(&name))
+        else {
+            return flow::continue_now();
+        };
+        if commands::ALWAYS_ENABLED.contains(&command) {
+            return flow::continue_now();
+        }
+
+        let db = injector.get::<Database>().unwrap();
+        let disabled_commands = injector.get::<DisabledCommands>().unwrap();
+        if !disabled_commands
+            .is_disabled(db, message.chat().id(), command)
+            .await
+        {
+            return flow::continue_now();
+        }
+
+        log::trace!(
+            "dropping the disabled command {} in {}",
+            command,
+            message.chat().id()
+        );
+
+        let config = injector.get::<Config>().unwrap();
+        if config.telegram.disabled_command_notice {
+            let i18n = injector.get::<I18n>().unwrap();
+
+            match message
+                .reply(InputMessage::html(i18n.translate_with_args(
+                    "command_disabled",
+                    hashmap! { "command" => command.to_string() },
+                )))
+                .await
+            {
+                Ok(notice) => {
+                    tokio::spawn(async move {
+                        tokio::time::sleep(NOTICE_LIFETIME).await;
+                        if let Err(e) = notice.delete().await {
+                            log::warn!("failed to delete a disabled command notice: {:?}", e);
+                        }
+                    });
+                }
+                Err(e) => log::warn!("failed to reply with the disabled command notice: {:?}", e),
+            }
+        }
+
+        flow::break_now()
+    }
+}
@@ -0,0 +1,95 @@
+// Copyright 2025 - Andriel Ferreira
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// https://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or https://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+//! Correlate updates middlewares.
+
+use async_trait::async_trait;
+use ferogram::{
+    Injector, Middleware,
+    flow::{self, Flow},
+};
+use grammers_client::{Client, Update};
+
+use crate::{
+    middlewares::time_updates::{UpdateTimer, update_kind},
+    resources::{
+        Correlations,
+        correlations::{CorrelationId, UpdateKey},
+    },
+};
+
+/// The middleware to give each update a correlation ID, logged with everything about it.
+///
+/// The texts written by the users are only logged at the `trace` level.
+#[derive(Clone)]
+pub struct CorrelateUpdates;
+
+#[async_trait]
+impl Middleware for CorrelateUpdates {
+    async fn handle(&mut self, _: &Client, update: &Update, injector: &mut Injector) -> Flow {
+        let correlations = injector.get::<Correlations>().unwrap();
+        let id = correlations.assign(UpdateKey::from_update(update)).await;
+
+        let (chat_id, sender_id, text) = match update {
+            Update::NewMessage(message) | Update::MessageEdited(message) => (
+                Some(message.chat().id()),
+                message.sender().map(|sender| sender.id()),
+                Some(message.text()),
+            ),
+            Update::CallbackQuery(query) => {
+                (Some(query.chat().id()), Some(query.sender().id()), None)
+            }
+            Update::InlineQuery(query) => (None, Some(query.sender().id()), Some(query.text())),
+            _ => (None, None, None),
+        };
+        log::debug!(
+            "[{0}] received {1} in chat {2:?} from {3:?}",
+            id,
+            update_kind(update),
+            chat_id,
+            sender_id
+        );
+        if let Some(text) = text {
+            log::trace!("[{0}] text: {1:?}", id, text);
+        }
+
+        injector.insert(id);
+
+        flow::continue_now()
+    }
+}
+
+/// The middleware to log how long the updates took, once handled.
+///
+/// Updates whose handler failed are logged by the error handler instead.
+#[derive(Clone)]
+pub struct LogHandledUpdates;
+
+#[async_trait]
+impl Middleware for LogHandledUpdates {
+    async fn handle(&mut self, _: &Client, update: &Update, injector: &mut Injector) -> Flow {
+        let correlations = injector.get::<Correlations>().unwrap();
+
+        // The ID is remembered for the error handler until the update is handled.
+        let remembered = match UpdateKey::from_update(update) {
+            Some(key) => correlations.take(&key).await,
+            None => None,
+        }
+        .map(|correlation| correlation.id);
+        let Some(id) = remembered.or_else(|| injector.get::<CorrelationId>().cloned()) else {
+            return flow::continue_now();
+        };
+
+        match injector.get::<UpdateTimer>() {
+            Some(timer) => log::debug!("[{0}] handled in {1:?}", id, timer.elapsed()),
+            None => log::debug!("[{0}] handled", id),
+        }
+
+        flow::continue_now()
+    }
+}
@@ -0,0 +1,40 @@
+// Copyright 2025 - Andriel Ferreira
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// https://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or https://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+//! Count callbacks middleware.
+
+use async_trait::async_trait;
+use ferogram::{
+    Injector, Middleware,
+    flow::{self, Flow},
+    utils::bytes_to_string,
+};
+use grammers_client::{Client, Update};
+
+use crate::resources::{Metrics, callback_signer};
+
+/// The middleware to count which callback buttons are pressed.
+///
+/// It must run after the banned users and the forged callbacks are stopped, so they are not
+/// counted.
+#[derive(Clone)]
+pub struct CountCallbacks;
+
+#[async_trait]
+impl Middleware for CountCallbacks {
+    async fn handle(&mut self, _: &Client, update: &Update, injector: &mut Injector) -> Flow {
+        if let Update::CallbackQuery(query) = update {
+            let metrics = injector.get::<Metrics>().unwrap();
+
+            let data = bytes_to_string(query.data());
+            metrics.record_callback(callback_signer::strip_token(&data));
+        }
+
+        flow::continue_now()
+    }
+}
@@ -0,0 +1,58 @@
+// Copyright 2025 - Andriel Ferreira
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// https://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or https://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+//! Load chat settings middleware.
+
+use async_trait::async_trait;
+use ferogram::{
+    Context, Injector, Middleware,
+    flow::{self, Flow},
+};
+use grammers_client::{Client, Update};
+
+use crate::{
+    models::{ChatSettings, Group, User},
+    resources::Database,
+};
+
+/// The middleware to load the settings of the chat an update comes from.
+#[derive(Clone)]
+pub struct LoadChatSettings;
+
+#[async_trait]
+impl Middleware for LoadChatSettings {
+    async fn handle(&mut self, _: &Client, update: &Update, injector: &mut Injector) -> Flow {
+        let db = injector.get::<Database>().unwrap();
+        let ctx = injector.get::<Context>().unwrap();
+
+        let mut settings = ChatSettings::default();
+
+        if db.is_healthy() {
+            let pool = db.pool();
+
+            // Inline queries do not tell which chat they come from, the user settings apply.
+            let from_user = ctx.is_private() || matches!(update, Update::InlineQuery(_));
+
+            if from_user {
+                if let Some(sender) = ctx.sender() {
+                    if let Ok(Some(user)) = db.report(User::get_by_id(pool, &sender.id()).await) {
+                        settings = ChatSettings::from(&user);
+                    }
+                }
+            } else if let Some(chat) = ctx.chat() {
+                if let Ok(Some(group)) = db.report(Group::get_by_id(pool, &chat.id()).await) {
+                    settings = ChatSettings::from(&group);
+                }
+            }
+        }
+
+        injector.insert(settings);
+
+        flow::continue_now()
+    }
+}
@@ -0,0 +1,75 @@
+// Copyright 2025 - Andriel Ferreira
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// https://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or https://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+//! Log updates middleware.
+
+use async_trait::async_trait;
+use ferogram::{
+    Injector, Middleware,
+    flow::{self, Flow},
+    utils::bytes_to_string,
+};
+use grammers_client::{Client, Update};
+
+/// The middleware to log every update, enabled by `app.debug_updates`.
+///
+/// The texts written by the users are redacted, only their length is logged.
+#[derive(Clone)]
+pub struct LogUpdates;
+
+#[async_trait]
+impl Middleware for LogUpdates {
+    async fn handle(&mut self, _: &Client, update: &Update, _: &mut Injector) -> Flow {
+        log::trace!("received {}", describe(update));
+
+        flow::continue_now()
+    }
+}
+
+/// Describes an update without the texts written by the users.
+///
+/// # Arguments
+///
+/// * `update` - The update.
+fn describe(update: &Update) -> String {
+    match update {
+        Update::NewMessage(message) | Update::MessageEdited(message) => format!(
+            "{0} {1} in chat {2} from {3:?}: {4}",
+            if matches!(update, Update::NewMessage(_)) {
+                "new message"
+            } else {
+                "edited message"
+            },
+            message.id(),
+            message.chat().id(),
+            message.sender().map(|sender| sender.id()),
+            redact(message.text())
+        ),
+        Update::CallbackQuery(query) => format!(
+            "callback query from {0} in chat {1}: {2:?}",
+            query.sender().id(),
+            query.chat().id(),
+            bytes_to_string(query.data())
+        ),
+        Update::InlineQuery(query) => format!(
+            "inline query from {0}: {1}",
+            query.sender().id(),
+            redact(query.text())
+        ),
+        _ => "an unhandled update".to_string(),
+    }
+}
+
+/// Replaces a text written by a user with its length.
+///
+/// # Arguments
+///
+/// * `text` - The text.
+fn redact(text: &str) -> String {
+    format!("<{} chars redacted>", text.chars().count())
+}
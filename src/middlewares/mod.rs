@@ -8,17 +8,63 @@
 
 //! Middlewares.
 
-mod authenticate_anilist;
-mod update_chat_lang;
+pub mod answer_callbacks;
+pub mod authenticate_anilist;
+pub mod check_banned;
+pub mod check_maintenance;
+pub mod command_gate;
+pub mod correlate_updates;
+pub mod count_callbacks;
+pub mod load_chat_settings;
+pub mod log_updates;
+pub mod time_updates;
+pub mod track_chat_activity;
+pub mod update_chat_lang;
+pub mod verify_callbacks;
 
-use authenticate_anilist::AuthenticateAniList;
-use update_chat_lang::UpdateChatLang;
+pub use answer_callbacks::AnswerCallbacks;
+pub use authenticate_anilist::AuthenticateAniList;
+pub use check_banned::CheckBanned;
+pub use check_maintenance::CheckMaintenance;
+pub use command_gate::CommandGate;
+pub use correlate_updates::{CorrelateUpdates, LogHandledUpdates};
+pub use count_callbacks::CountCallbacks;
+pub use load_chat_settings::LoadChatSettings;
+pub use log_updates::LogUpdates;
+pub use time_updates::{RecordUpdateDuration, TimeUpdates};
+pub use track_chat_activity::TrackChatActivity;
+pub use update_chat_lang::UpdateChatLang;
+pub use verify_callbacks::VerifyCallbacks;
 
 use ferogram::MiddlewareStack;
 
+use crate::Config;
+
 /// The middlewares setup.
-pub fn setup(stack: MiddlewareStack) -> MiddlewareStack {
+///
+/// # Arguments
+///
+/// * `stack` - The middleware stack.
+/// * `config` - The bot configuration, for the optional middlewares.
+pub fn setup(stack: MiddlewareStack, config: &Config) -> MiddlewareStack {
+    let stack = stack.before(TimeUpdates).before(CorrelateUpdates);
+    let stack = if config.app.debug_updates {
+        stack.before(LogUpdates)
+    } else {
+        stack
+    };
+
     stack
-        .before(UpdateChatLang)
-        .before(AuthenticateAniList::new())
+        .before(CheckBanned)
+        .before(TrackChatActivity)
+        .before(UpdateChatLang::new())
+        .before(CheckMaintenance)
+        .before(CommandGate)
+        .before(VerifyCallbacks)
+        .before(CountCallbacks)
+        .before(LoadChatSettings)
+        .before(AuthenticateAniList)
+        .after(AnswerCallbacks)
+        .after(LogHandledUpdates)
+        .after(RecordUpdateDuration)
 }
@@ -0,0 +1,82 @@
+// Copyright 2025 - Andriel Ferreira
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// https://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or https://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+//! Time updates middlewares.
+
+use std::time::Duration;
+
+use async_trait::async_trait;
+use ferogram::{
+    Injector, Middleware,
+    flow::{self, Flow},
+};
+use grammers_client::{Client, Update};
+use tokio::time::Instant;
+
+use crate::resources::Metrics;
+
+/// When the handling of an update started.
+#[derive(Clone, Copy)]
+pub struct UpdateTimer(Instant);
+
+impl UpdateTimer {
+    /// Gets how long ago the handling of the update started.
+    pub fn elapsed(&self) -> Duration {
+        self.0.elapsed()
+    }
+}
+
+/// The middleware to count the updates by kind and start timing them.
+///
+/// It must run before the other middlewares, so their time is counted too.
+#[derive(Clone)]
+pub struct TimeUpdates;
+
+#[async_trait]
+impl Middleware for TimeUpdates {
+    async fn handle(&mut self, _: &Client, update: &Update, injector: &mut Injector) -> Flow {
+        let metrics = injector.get::<Metrics>().unwrap();
+        metrics.record_update(update_kind(update));
+
+        injector.insert(UpdateTimer(Instant::now()));
+
+        flow::continue_now()
+    }
+}
+
+/// The middleware to record how long the updates took to handle, after [`TimeUpdates`].
+#[derive(Clone)]
+pub struct RecordUpdateDuration;
+
+#[async_trait]
+impl Middleware for RecordUpdateDuration {
+    async fn handle(&mut self, _: &Client, _: &Update, injector: &mut Injector) -> Flow {
+        if let Some(timer) = injector.take::<UpdateTimer>() {
+            let metrics = injector.get::<Metrics>().unwrap();
+
+            metrics.record_update_duration(timer.elapsed());
+        }
+
+        flow::continue_now()
+    }
+}
+
+/// Gets the kind of an update, as counted by the metrics.
+///
+/// # Arguments
+///
+/// * `update` - The update.
+pub fn update_kind(update: &Update) -> &'static str {
+    match update {
+        Update::NewMessage(_) => "new_message",
+        Update::MessageEdited(_) => "message_edited",
+        Update::CallbackQuery(_) => "callback_query",
+        Update::InlineQuery(_) => "inline_query",
+        _ => "other",
+    }
+}
@@ -0,0 +1,41 @@
+// Copyright 2025 - Andriel Ferreira
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// https://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or https://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+//! Track chat activity middleware.
+
+use async_trait::async_trait;
+use ferogram::{
+    Injector, Middleware,
+    flow::{self, Flow},
+};
+use grammers_client::{Client, Update, types::Chat};
+
+use crate::resources::Diagnostics;
+
+/// The middleware to record which kind of messages the bot receives from each group.
+///
+/// It must run after the banned users are stopped, so their messages are not counted.
+#[derive(Clone)]
+pub struct TrackChatActivity;
+
+#[async_trait]
+impl Middleware for TrackChatActivity {
+    async fn handle(&mut self, _: &Client, update: &Update, injector: &mut Injector) -> Flow {
+        if let Update::NewMessage(message) = update {
+            if let Chat::Group(group) = message.chat() {
+                let diagnostics = injector.get::<Diagnostics>().unwrap();
+
+                diagnostics
+                    .record_message(group.id(), message.text().starts_with('/'))
+                    .await;
+            }
+        }
+
+        flow::continue_now()
+    }
+}
@@ -8,6 +8,8 @@
 
 //! Update chat language middleware.
 
+use std::time::Duration;
+
 use async_trait::async_trait;
 use ferogram::{
     Context, Injector, Middleware,
@@ -17,84 +19,176 @@ use grammers_client::{Client, Update};
 
 use crate::{
     models::{Group, NewGroup, NewUser, User},
-    resources::{Database, I18n},
+    resources::{
+        Correlations, Database, I18n, Throttle, correlations::UpdateKey,
+        database::is_connection_error,
+    },
 };
 
+/// The minimum time between two records of the activity of a chat.
+const ACTIVITY_INTERVAL: Duration = Duration::from_secs(60);
+
 /// The middleware to update the language of the chat.
+///
+/// Also records when the chat was last seen, at most once per [`ACTIVITY_INTERVAL`].
 #[derive(Clone)]
-pub struct UpdateChatLang;
+pub struct UpdateChatLang {
+    /// When the activity of each chat was last recorded.
+    activity: Throttle<i64>,
+}
+
+impl Default for UpdateChatLang {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl UpdateChatLang {
+    /// Creates a new instance of the middleware.
+    pub fn new() -> Self {
+        Self {
+            activity: Throttle::new(10000, ACTIVITY_INTERVAL),
+        }
+    }
+}
 
 #[async_trait]
 impl Middleware for UpdateChatLang {
-    async fn handle(&mut self, _: &Client, _: &Update, injector: &mut Injector) -> Flow {
+    async fn handle(&mut self, _: &Client, update: &Update, injector: &mut Injector) -> Flow {
+        let i18n = injector.take::<I18n>().unwrap();
+
         let db = injector.get::<Database>().unwrap();
         let ctx = injector.get::<Context>().unwrap();
-        let i18n = injector.get::<I18n>().unwrap();
 
         let pool = db.pool();
 
-        if ctx.is_private() {
-            if let Some(sender) = ctx.sender() {
-                match User::get_by_id(pool, &sender.id()).await {
-                    Ok(Some(user)) => {
-                        i18n.set_locale(user.language_code);
-                    }
+        // The updates must keep flowing while the database is down, the health probe flips
+        // the flag back once it is reachable again.
+        let locale = if !db.is_healthy() {
+            None
+        } else if ctx.is_private() {
+            match ctx.sender() {
+                Some(sender) => match db.report(User::get_by_id(pool, &sender.id()).await) {
+                    Ok(Some(user)) => Some(user.language_code),
                     Ok(None) => {
                         let new_user = NewUser::new(sender.id(), "pt".to_string());
-                        match new_user.create(pool).await {
+                        match db.report(new_user.create(pool).await) {
                             Ok(user) => {
-                                log::debug!("created a new user: {:?}", user)
+                                log::debug!("created a new user: {:?}", user);
+
+                                Some(user.language_code)
                             }
                             Err(e) => {
-                                log::error!(
-                                    "failed to create a new user {:?} with error {:?}",
-                                    new_user,
-                                    e
-                                )
+                                if !is_connection_error(&e) {
+                                    log::error!(
+                                        "failed to create a new user {:?} with error {:?}",
+                                        new_user,
+                                        e
+                                    )
+                                }
+
+                                None
                             }
                         }
                     }
                     Err(e) => {
-                        log::error!(
-                            "failed to get user by id {:?} with error {:?}",
-                            sender.id(),
-                            e
-                        )
+                        if !is_connection_error(&e) {
+                            log::error!(
+                                "failed to get user by id {:?} with error {:?}",
+                                sender.id(),
+                                e
+                            )
+                        }
+
+                        None
                     }
-                }
+                },
+                None => None,
             }
         } else {
-            if let Some(chat) = ctx.chat() {
-                match Group::get_by_id(pool, &chat.id()).await {
-                    Ok(Some(group)) => {
-                        i18n.set_locale(group.language_code);
-                    }
+            match ctx.chat() {
+                Some(chat) => match db.report(Group::get_by_id(pool, &chat.id()).await) {
+                    Ok(Some(group)) => Some(group.language_code),
                     Ok(None) => {
                         let new_group = NewGroup::new(chat.id(), "pt".to_string());
-                        match new_group.create(pool).await {
+                        match db.report(new_group.create(pool).await) {
                             Ok(group) => {
-                                log::debug!("created a new group: {:?}", group)
+                                log::debug!("created a new group: {:?}", group);
+
+                                Some(group.language_code)
                             }
                             Err(e) => {
-                                log::error!(
-                                    "failed to create a new group {:?} with error {:?}",
-                                    new_group,
-                                    e
-                                )
+                                if !is_connection_error(&e) {
+                                    log::error!(
+                                        "failed to create a new group {:?} with error {:?}",
+                                        new_group,
+                                        e
+                                    )
+                                }
+
+                                None
                             }
                         }
                     }
                     Err(e) => {
-                        log::error!(
-                            "failed to get group by id {:?} with error {:?}",
-                            chat.id(),
-                            e
-                        )
+                        if !is_connection_error(&e) {
+                            log::error!(
+                                "failed to get group by id {:?} with error {:?}",
+                                chat.id(),
+                                e
+                            )
+                        }
+
+                        None
+                    }
+                },
+                None => None,
+            }
+        };
+
+        // The chat is registered once it has a locale, record that it was seen.
+        if locale.is_some() {
+            // The chat is packed with its access hash, so the tasks can send messages to it.
+            let active = if ctx.is_private() {
+                ctx.sender()
+                    .map(|sender| (sender.id(), sender.pack(), true))
+            } else {
+                ctx.chat().map(|chat| (chat.id(), chat.pack(), false))
+            };
+
+            if let Some((id, packed_chat, private)) = active {
+                if self.activity.allow(id).await {
+                    let packed_chat = packed_chat.to_hex();
+                    let result = if private {
+                        User::touch(pool, id, &packed_chat).await
+                    } else {
+                        Group::touch(pool, id, &packed_chat).await
+                    };
+
+                    if let Err(e) = db.report(result) {
+                        if !is_connection_error(&e) {
+                            log::warn!(
+                                "failed to record the activity of {} with error {:?}",
+                                id,
+                                e
+                            );
+                        }
                     }
                 }
             }
         }
 
+        // Each update gets its own view, the shared resource keeps the default locale.
+        let locale = locale.unwrap_or(i18n.default_locale().to_string());
+
+        // The error handler cannot reach the injector, it gets the locale with the correlation.
+        if let Some(key) = UpdateKey::from_update(update) {
+            let correlations = injector.get::<Correlations>().unwrap();
+            correlations.set_locale(&key, &locale).await;
+        }
+
+        injector.insert(i18n.for_locale(locale));
+
         flow::continue_now()
     }
 }
@@ -0,0 +1,62 @@
+// Copyright 2025 - Andriel Ferreira
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// https://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or https://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+//! Verify callbacks middleware.
+
+use std::time::Duration;
+
+use async_trait::async_trait;
+use ferogram::{
+    Injector, Middleware,
+    flow::{self, Flow},
+    utils::bytes_to_string,
+};
+use grammers_client::{Client, Update};
+
+use crate::{
+    resources::{I18n, callback_signer},
+    utils,
+};
+
+/// The middleware to stop the signed callbacks that expired or were forged.
+///
+/// It must run after the chat language is loaded, so the alert is translated.
+#[derive(Clone)]
+pub struct VerifyCallbacks;
+
+#[async_trait]
+impl Middleware for VerifyCallbacks {
+    async fn handle(&mut self, _: &Client, update: &Update, injector: &mut Injector) -> Flow {
+        let Update::CallbackQuery(query) = update else {
+            return flow::continue_now();
+        };
+
+        let data = bytes_to_string(query.data());
+        if !callback_signer::is_signed(&data) {
+            return flow::continue_now();
+        }
+
+        let Err(e) = utils::verify_callback(&data) else {
+            return flow::continue_now();
+        };
+        log::trace!("rejecting the callback {:?}: {:?}", data, e);
+
+        let i18n = injector.get::<I18n>().unwrap();
+        if let Err(e) = query
+            .answer()
+            .cache_time(Duration::from_secs(120))
+            .alert(i18n.translate("callback_expired"))
+            .send()
+            .await
+        {
+            log::warn!("failed to answer with the expired callback notice: {:?}", e);
+        }
+
+        flow::break_now()
+    }
+}
@@ -0,0 +1,38 @@
+// Copyright 2025 - Andriel Ferreira
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// https://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or https://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+//! How the sender of an update is authenticated with AniList.
+
+use crate::models::User;
+
+/// How the sender of an update is authenticated with AniList.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum AniListAuth {
+    /// The sender never connected an AniList account, or disconnected it.
+    #[default]
+    Anonymous,
+    /// The sender has a valid AniList token.
+    Authenticated,
+    /// The sender's AniList token expired, they have to authenticate again.
+    Expired,
+}
+
+impl AniListAuth {
+    /// Gets how a user is authenticated.
+    ///
+    /// # Arguments
+    ///
+    /// * `user` - The user, `None` if not registered.
+    pub fn of(user: Option<&User>) -> Self {
+        match user {
+            Some(user) if user.has_valid_token() => Self::Authenticated,
+            Some(user) if user.anilist_token.is_some() => Self::Expired,
+            _ => Self::Anonymous,
+        }
+    }
+}
@@ -0,0 +1,107 @@
+// Copyright 2025 - Andriel Ferreira
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// https://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or https://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+//! The banned user model.
+
+use chrono::{DateTime, Utc};
+use sqlx::FromRow;
+
+use crate::resources::database::{Pool, with_pool};
+
+/// The banned user model.
+///
+/// The bot ignores every update of a banned user.
+#[derive(Debug, FromRow, Clone)]
+pub struct BannedUser {
+    /// The user's ID.
+    pub user_id: i64,
+    /// Why the user was banned.
+    pub reason: Option<String>,
+    /// The ID of the sudoer that banned the user.
+    pub banned_by: i64,
+    /// When the user was banned.
+    pub created_at: DateTime<Utc>,
+}
+
+impl BannedUser {
+    /// Gets the ban of a user, if any.
+    ///
+    /// # Arguments
+    ///
+    /// * `pool` - The database pool.
+    /// * `user_id` - The user's ID.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the query fails.
+    pub async fn get(pool: &Pool, user_id: i64) -> sqlx::Result<Option<Self>> {
+        with_pool!(pool, |pool| {
+            sqlx::query_as("SELECT * FROM banned_users WHERE user_id = $1")
+                .bind(user_id)
+                .fetch_optional(pool)
+                .await
+        })
+    }
+
+    /// Bans a user, replacing the reason of a previous ban.
+    ///
+    /// # Arguments
+    ///
+    /// * `pool` - The database pool.
+    /// * `user_id` - The user's ID.
+    /// * `reason` - Why the user is banned.
+    /// * `banned_by` - The ID of the sudoer banning the user.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the query fails.
+    pub async fn ban(
+        pool: &Pool,
+        user_id: i64,
+        reason: Option<&str>,
+        banned_by: i64,
+    ) -> sqlx::Result<()> {
+        with_pool!(pool, |pool| {
+            sqlx::query(
+                "INSERT INTO banned_users (user_id, reason, banned_by) VALUES ($1, $2, $3)
+                 ON CONFLICT (user_id) DO UPDATE
+                 SET reason = EXCLUDED.reason, banned_by = EXCLUDED.banned_by",
+            )
+            .bind(user_id)
+            .bind(reason)
+            .bind(banned_by)
+            .execute(pool)
+            .await
+            .map(|_| ())
+        })
+    }
+
+    /// Unbans a user.
+    ///
+    /// Returns whether the user was banned.
+    ///
+    /// # Arguments
+    ///
+    /// * `pool` - The database pool.
+    /// * `user_id` - The user's ID.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the query fails.
+    pub async fn unban(pool: &Pool, user_id: i64) -> sqlx::Result<bool> {
+        let affected = with_pool!(pool, |pool| {
+            sqlx::query("DELETE FROM banned_users WHERE user_id = $1")
+                .bind(user_id)
+                .execute(pool)
+                .await
+                .map(|result| result.rows_affected())
+        })?;
+
+        Ok(affected > 0)
+    }
+}
@@ -0,0 +1,168 @@
+// Copyright 2025 - Andriel Ferreira
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// https://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or https://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+//! The settings of the chat an update comes from.
+
+use std::time::Duration;
+
+use crate::models::{Group, User};
+
+/// The language the titles are shown in.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum TitleLanguage {
+    /// The romanized title, e.g. `Shingeki no Kyojin`.
+    #[default]
+    Romaji,
+    /// The English title, e.g. `Attack on Titan`.
+    English,
+    /// The title in its native language, e.g. `進撃の巨人`.
+    Native,
+}
+
+impl TitleLanguage {
+    /// All the title languages, in the order the settings menu cycles through them.
+    pub const ALL: [Self; 3] = [Self::Romaji, Self::English, Self::Native];
+
+    /// Gets the name of the language as stored in the database.
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Romaji => "romaji",
+            Self::English => "english",
+            Self::Native => "native",
+        }
+    }
+
+    /// Parses a language from its stored name.
+    ///
+    /// # Arguments
+    ///
+    /// * `name` - The name of the language.
+    pub fn parse(name: &str) -> Option<Self> {
+        Self::ALL
+            .into_iter()
+            .find(|language| language.as_str() == name)
+    }
+
+    /// Gets the language that follows this one in the settings menu.
+    pub fn next(&self) -> Self {
+        let index = Self::ALL
+            .iter()
+            .position(|language| language == self)
+            .unwrap();
+
+        Self::ALL[(index + 1) % Self::ALL.len()]
+    }
+}
+
+/// The language of the voice actors listed with the characters.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub enum VoiceActorLanguage {
+    /// The original Japanese cast.
+    #[default]
+    Japanese,
+    /// The English dub.
+    English,
+    /// The Portuguese dub.
+    Portuguese,
+    /// The Spanish dub.
+    Spanish,
+}
+
+impl VoiceActorLanguage {
+    /// All the voice actor languages, in the order the settings menu cycles through them.
+    pub const ALL: [Self; 4] = [
+        Self::Japanese,
+        Self::English,
+        Self::Portuguese,
+        Self::Spanish,
+    ];
+
+    /// Gets the name of the language as stored in the database.
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Japanese => "japanese",
+            Self::English => "english",
+            Self::Portuguese => "portuguese",
+            Self::Spanish => "spanish",
+        }
+    }
+
+    /// Gets the name of the language as AniList expects it, e.g. `JAPANESE`.
+    pub fn as_anilist(&self) -> &'static str {
+        match self {
+            Self::Japanese => "JAPANESE",
+            Self::English => "ENGLISH",
+            Self::Portuguese => "PORTUGUESE",
+            Self::Spanish => "SPANISH",
+        }
+    }
+
+    /// Parses a language from its stored name.
+    ///
+    /// # Arguments
+    ///
+    /// * `name` - The name of the language.
+    pub fn parse(name: &str) -> Option<Self> {
+        Self::ALL
+            .into_iter()
+            .find(|language| language.as_str() == name)
+    }
+
+    /// Gets the language that follows this one in the settings menu.
+    pub fn next(&self) -> Self {
+        let index = Self::ALL
+            .iter()
+            .position(|language| language == self)
+            .unwrap();
+
+        Self::ALL[(index + 1) % Self::ALL.len()]
+    }
+}
+
+/// The settings of the chat an update comes from.
+///
+/// Read from the user row in private chats and inline queries, and from the group row
+/// otherwise. The defaults apply when the row is missing or the database is unreachable.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct ChatSettings {
+    /// Whether adult content is shown.
+    pub allow_adult: bool,
+    /// The language the titles are shown in.
+    pub title_language: TitleLanguage,
+    /// The language of the voice actors listed with the characters.
+    pub voice_actor_language: VoiceActorLanguage,
+    /// How long the usage and error replies stay before they are deleted, only in groups.
+    pub clean_service_messages: Option<Duration>,
+}
+
+impl From<&User> for ChatSettings {
+    fn from(user: &User) -> Self {
+        Self {
+            allow_adult: user.allow_adult,
+            title_language: TitleLanguage::parse(&user.title_language).unwrap_or_default(),
+            voice_actor_language: VoiceActorLanguage::parse(&user.voice_actor_language)
+                .unwrap_or_default(),
+            clean_service_messages: None,
+        }
+    }
+}
+
+impl From<&Group> for ChatSettings {
+    fn from(group: &Group) -> Self {
+        Self {
+            allow_adult: group.allow_adult,
+            title_language: TitleLanguage::parse(&group.title_language).unwrap_or_default(),
+            voice_actor_language: VoiceActorLanguage::parse(&group.voice_actor_language)
+                .unwrap_or_default(),
+            clean_service_messages: u64::try_from(group.clean_service_messages)
+                .ok()
+                .filter(|seconds| *seconds > 0)
+                .map(Duration::from_secs),
+        }
+    }
+}
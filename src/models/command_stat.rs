@@ -0,0 +1,113 @@
+// Copyright 2025 - Andriel Ferreira
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// https://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or https://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+//! The command stat model.
+
+use chrono::NaiveDate;
+use sqlx::FromRow;
+
+use crate::resources::database::{Pool, with_pool};
+
+/// The kind of a command stat.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum StatKind {
+    /// A callback query, named after its verb (e.g. `anime tags`).
+    Callback,
+}
+
+impl StatKind {
+    /// Gets the name of the kind as stored in the database.
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Callback => "callback",
+        }
+    }
+}
+
+/// The command stat model.
+///
+/// Counts how many times something was used in a day.
+#[derive(Debug, FromRow, Clone)]
+pub struct CommandStat {
+    /// The day of the count.
+    pub day: NaiveDate,
+    /// The kind of what was counted.
+    pub kind: String,
+    /// The name of what was counted.
+    pub name: String,
+    /// How many times it was used.
+    pub count: i64,
+}
+
+impl CommandStat {
+    /// Adds to the count of a day, creating it if needed.
+    ///
+    /// # Arguments
+    ///
+    /// * `pool` - The database pool.
+    /// * `day` - The day of the count.
+    /// * `kind` - The kind of what was counted.
+    /// * `name` - The name of what was counted.
+    /// * `count` - How much to add.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the query fails.
+    pub async fn increment(
+        pool: &Pool,
+        day: NaiveDate,
+        kind: StatKind,
+        name: &str,
+        count: i64,
+    ) -> sqlx::Result<()> {
+        with_pool!(pool, |pool| {
+            sqlx::query(
+                "INSERT INTO command_stats (day, kind, name, count) VALUES ($1, $2, $3, $4)
+                 ON CONFLICT (day, kind, name) DO UPDATE SET count = command_stats.count + EXCLUDED.count",
+            )
+            .bind(day)
+            .bind(kind.as_str())
+            .bind(name)
+            .bind(count)
+            .execute(pool)
+            .await
+            .map(|_| ())
+        })
+    }
+
+    /// Gets the most used names of a kind since a day, with their total count.
+    ///
+    /// # Arguments
+    ///
+    /// * `pool` - The database pool.
+    /// * `kind` - The kind to look for.
+    /// * `since` - The first day to count, inclusive.
+    /// * `limit` - The max number of names.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the query fails.
+    pub async fn top(
+        pool: &Pool,
+        kind: StatKind,
+        since: NaiveDate,
+        limit: i64,
+    ) -> sqlx::Result<Vec<(String, i64)>> {
+        with_pool!(pool, |pool| {
+            sqlx::query_as(
+                "SELECT name, CAST(SUM(count) AS INT8) FROM command_stats WHERE kind = $1 AND day >= $2
+                 GROUP BY name ORDER BY SUM(count) DESC, name LIMIT $3",
+            )
+            .bind(kind.as_str())
+            .bind(since)
+            .bind(limit)
+            .fetch_all(pool)
+            .await
+        })
+    }
+}
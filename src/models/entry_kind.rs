@@ -0,0 +1,75 @@
+// Copyright 2025 - Andriel Ferreira
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// https://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or https://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+//! The kind of the entries kept by the bot.
+
+use crate::utils;
+
+/// The kind of an entry the users keep in the bot, e.g. in their history.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum EntryKind {
+    /// An anime card.
+    Anime,
+    /// A manga card.
+    Manga,
+    /// A character card.
+    Character,
+}
+
+impl EntryKind {
+    /// All the kinds, in the order they are listed.
+    pub const ALL: [Self; 3] = [Self::Anime, Self::Manga, Self::Character];
+
+    /// Gets the name of the kind as stored in the database.
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Anime => "anime",
+            Self::Manga => "manga",
+            Self::Character => "character",
+        }
+    }
+
+    /// Parses a kind from its name in the database.
+    ///
+    /// # Arguments
+    ///
+    /// * `name` - The name of the kind, e.g. `manga`.
+    pub fn parse(name: &str) -> Option<Self> {
+        match name {
+            "anime" => Some(Self::Anime),
+            "manga" => Some(Self::Manga),
+            "character" => Some(Self::Character),
+            _ => None,
+        }
+    }
+
+    /// Gets the emoji the entries of the kind are shown with.
+    pub fn emoji(&self) -> &'static str {
+        match self {
+            Self::Anime => "📺",
+            Self::Manga => "📚",
+            Self::Character => "👤",
+        }
+    }
+
+    /// Generates the signed callback data that opens the card of an entry.
+    ///
+    /// # Arguments
+    ///
+    /// * `id` - The AniList ID of the entry.
+    /// * `user_id` - The ID of the user allowed to press the button.
+    pub fn callback_data(&self, id: i64, user_id: i64) -> String {
+        let verb = match self {
+            Self::Anime => "anime",
+            Self::Manga => "manga",
+            Self::Character => "char",
+        };
+
+        utils::sign_callback(&format!("{0} {1} {2}", verb, id, user_id))
+    }
+}
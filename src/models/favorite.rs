@@ -0,0 +1,186 @@
+// Copyright 2025 - Andriel Ferreira
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// https://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or https://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+//! The favorite model.
+
+use chrono::{DateTime, Utc};
+use sqlx::FromRow;
+
+use crate::{
+    models::EntryKind,
+    resources::database::{Pool, with_pool},
+};
+
+/// The favorite model.
+///
+/// An entry a user bookmarked in the bot, independently of their AniList lists.
+#[derive(Debug, FromRow, Clone)]
+pub struct Favorite {
+    /// The Telegram user ID.
+    pub user_id: i64,
+    /// The kind of the entry, see [`EntryKind`].
+    pub media_type: String,
+    /// The AniList ID of the entry.
+    pub media_id: i64,
+    /// The title of the entry when it was bookmarked.
+    pub title: String,
+    /// The favorite's created at date.
+    pub created_at: DateTime<Utc>,
+}
+
+impl Favorite {
+    /// Bookmarks an entry.
+    ///
+    /// Returns whether the entry was not bookmarked yet.
+    ///
+    /// # Arguments
+    ///
+    /// * `pool` - The database pool.
+    /// * `user_id` - The Telegram user ID.
+    /// * `kind` - The kind of the entry.
+    /// * `media_id` - The AniList ID of the entry.
+    /// * `title` - The title of the entry.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the query fails.
+    pub async fn add(
+        pool: &Pool,
+        user_id: i64,
+        kind: EntryKind,
+        media_id: i64,
+        title: &str,
+    ) -> sqlx::Result<bool> {
+        let affected = with_pool!(pool, |pool| {
+            sqlx::query(
+                "INSERT INTO favorites (user_id, media_type, media_id, title) VALUES ($1, $2, $3, $4)
+                 ON CONFLICT (user_id, media_type, media_id) DO NOTHING",
+            )
+            .bind(user_id)
+            .bind(kind.as_str())
+            .bind(media_id)
+            .bind(title)
+            .execute(pool)
+            .await
+            .map(|result| result.rows_affected())
+        })?;
+
+        Ok(affected > 0)
+    }
+
+    /// Removes a bookmark.
+    ///
+    /// Returns whether the entry was bookmarked.
+    ///
+    /// # Arguments
+    ///
+    /// * `pool` - The database pool.
+    /// * `user_id` - The Telegram user ID.
+    /// * `kind` - The kind of the entry.
+    /// * `media_id` - The AniList ID of the entry.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the query fails.
+    pub async fn remove(
+        pool: &Pool,
+        user_id: i64,
+        kind: EntryKind,
+        media_id: i64,
+    ) -> sqlx::Result<bool> {
+        let affected = with_pool!(pool, |pool| {
+            sqlx::query(
+                "DELETE FROM favorites WHERE user_id = $1 AND media_type = $2 AND media_id = $3",
+            )
+            .bind(user_id)
+            .bind(kind.as_str())
+            .bind(media_id)
+            .execute(pool)
+            .await
+            .map(|result| result.rows_affected())
+        })?;
+
+        Ok(affected > 0)
+    }
+
+    /// Checks whether a user bookmarked an entry.
+    ///
+    /// # Arguments
+    ///
+    /// * `pool` - The database pool.
+    /// * `user_id` - The Telegram user ID.
+    /// * `kind` - The kind of the entry.
+    /// * `media_id` - The AniList ID of the entry.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the query fails.
+    pub async fn exists(
+        pool: &Pool,
+        user_id: i64,
+        kind: EntryKind,
+        media_id: i64,
+    ) -> sqlx::Result<bool> {
+        with_pool!(pool, |pool| {
+            sqlx::query_scalar(
+                "SELECT EXISTS (SELECT 1 FROM favorites WHERE user_id = $1 AND media_type = $2 AND media_id = $3)",
+            )
+            .bind(user_id)
+            .bind(kind.as_str())
+            .bind(media_id)
+            .fetch_one(pool)
+            .await
+        })
+    }
+
+    /// Counts the bookmarks of a user.
+    ///
+    /// # Arguments
+    ///
+    /// * `pool` - The database pool.
+    /// * `user_id` - The Telegram user ID.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the query fails.
+    pub async fn count(pool: &Pool, user_id: i64) -> sqlx::Result<i64> {
+        with_pool!(pool, |pool| {
+            sqlx::query_scalar("SELECT COUNT(*) FROM favorites WHERE user_id = $1")
+                .bind(user_id)
+                .fetch_one(pool)
+                .await
+        })
+    }
+
+    /// Gets the bookmarks of a user, the oldest first.
+    ///
+    /// # Arguments
+    ///
+    /// * `pool` - The database pool.
+    /// * `user_id` - The Telegram user ID.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the query fails.
+    pub async fn get_by_user(pool: &Pool, user_id: i64) -> sqlx::Result<Vec<Self>> {
+        with_pool!(pool, |pool| {
+            sqlx::query_as(
+                "SELECT user_id, media_type, media_id, title, created_at FROM favorites
+                 WHERE user_id = $1 ORDER BY created_at",
+            )
+            .bind(user_id)
+            .fetch_all(pool)
+            .await
+        })
+    }
+
+    /// Gets the kind of the entry, `None` if it is unknown.
+    pub fn kind(&self) -> Option<EntryKind> {
+        EntryKind::parse(&self.media_type)
+    }
+}
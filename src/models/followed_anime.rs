@@ -0,0 +1,202 @@
+// Copyright 2025 - Andriel Ferreira
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// https://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or https://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+//! The followed anime model.
+
+use chrono::{DateTime, Utc};
+use sqlx::FromRow;
+
+use crate::resources::database::{Pool, with_pool};
+
+/// The followed anime model.
+///
+/// A user following an anime, to be notified when its episodes air.
+#[derive(Debug, FromRow, Clone)]
+pub struct FollowedAnime {
+    /// The Telegram user ID.
+    pub user_id: i64,
+    /// The AniList ID of the anime.
+    pub anime_id: i64,
+    /// The last episode the user was notified about.
+    pub last_notified_episode: Option<i32>,
+    /// The follow's created at date.
+    pub created_at: DateTime<Utc>,
+}
+
+/// A follower to notify about an episode.
+#[derive(Debug, FromRow, Clone)]
+pub struct Follower {
+    /// The Telegram user ID.
+    pub user_id: i64,
+    /// The user's language code, the default one if the user is not registered.
+    pub language_code: Option<String>,
+    /// The language the user's titles are shown in.
+    pub title_language: Option<String>,
+    /// The user's packed chat, as hex, if recorded.
+    pub packed_chat: Option<String>,
+}
+
+impl FollowedAnime {
+    /// Follows an anime.
+    ///
+    /// Returns whether the user was not following it yet.
+    ///
+    /// # Arguments
+    ///
+    /// * `pool` - The database pool.
+    /// * `user_id` - The Telegram user ID.
+    /// * `anime_id` - The AniList ID of the anime.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the query fails.
+    pub async fn follow(pool: &Pool, user_id: i64, anime_id: i64) -> sqlx::Result<bool> {
+        let affected = with_pool!(pool, |pool| {
+            sqlx::query(
+                "INSERT INTO followed_anime (user_id, anime_id) VALUES ($1, $2)
+                 ON CONFLICT (user_id, anime_id) DO NOTHING",
+            )
+            .bind(user_id)
+            .bind(anime_id)
+            .execute(pool)
+            .await
+            .map(|result| result.rows_affected())
+        })?;
+
+        Ok(affected > 0)
+    }
+
+    /// Unfollows an anime.
+    ///
+    /// Returns whether the user was following it.
+    ///
+    /// # Arguments
+    ///
+    /// * `pool` - The database pool.
+    /// * `user_id` - The Telegram user ID.
+    /// * `anime_id` - The AniList ID of the anime.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the query fails.
+    pub async fn unfollow(pool: &Pool, user_id: i64, anime_id: i64) -> sqlx::Result<bool> {
+        let affected = with_pool!(pool, |pool| {
+            sqlx::query("DELETE FROM followed_anime WHERE user_id = $1 AND anime_id = $2")
+                .bind(user_id)
+                .bind(anime_id)
+                .execute(pool)
+                .await
+                .map(|result| result.rows_affected())
+        })?;
+
+        Ok(affected > 0)
+    }
+
+    /// Gets the follows of a user, oldest first.
+    ///
+    /// # Arguments
+    ///
+    /// * `pool` - The database pool.
+    /// * `user_id` - The Telegram user ID.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the query fails.
+    pub async fn get_by_user(pool: &Pool, user_id: i64) -> sqlx::Result<Vec<Self>> {
+        with_pool!(pool, |pool| {
+            sqlx::query_as("SELECT * FROM followed_anime WHERE user_id = $1 ORDER BY created_at")
+                .bind(user_id)
+                .fetch_all(pool)
+                .await
+        })
+    }
+
+    /// Gets the IDs of every followed anime.
+    ///
+    /// # Arguments
+    ///
+    /// * `pool` - The database pool.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the query fails.
+    pub async fn followed_ids(pool: &Pool) -> sqlx::Result<Vec<i64>> {
+        with_pool!(pool, |pool| {
+            sqlx::query_scalar("SELECT DISTINCT anime_id FROM followed_anime ORDER BY anime_id")
+                .fetch_all(pool)
+                .await
+        })
+    }
+
+    /// Gets the followers of an anime not notified about an episode yet.
+    ///
+    /// Follows created after the episode aired are skipped.
+    ///
+    /// # Arguments
+    ///
+    /// * `pool` - The database pool.
+    /// * `anime_id` - The AniList ID of the anime.
+    /// * `episode` - The episode number.
+    /// * `aired_at` - When the episode aired.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the query fails.
+    pub async fn pending(
+        pool: &Pool,
+        anime_id: i64,
+        episode: i32,
+        aired_at: DateTime<Utc>,
+    ) -> sqlx::Result<Vec<Follower>> {
+        with_pool!(pool, |pool| {
+            sqlx::query_as(
+                "SELECT f.user_id, u.language_code, u.title_language, u.packed_chat
+                 FROM followed_anime f LEFT JOIN users u ON u.id = f.user_id
+                 WHERE f.anime_id = $1 AND f.created_at < $3
+                 AND (f.last_notified_episode IS NULL OR f.last_notified_episode < $2)",
+            )
+            .bind(anime_id)
+            .bind(episode)
+            .bind(aired_at)
+            .fetch_all(pool)
+            .await
+        })
+    }
+
+    /// Records that a user was notified about an episode.
+    ///
+    /// # Arguments
+    ///
+    /// * `pool` - The database pool.
+    /// * `user_id` - The Telegram user ID.
+    /// * `anime_id` - The AniList ID of the anime.
+    /// * `episode` - The episode number.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the query fails.
+    pub async fn mark_notified(
+        pool: &Pool,
+        user_id: i64,
+        anime_id: i64,
+        episode: i32,
+    ) -> sqlx::Result<()> {
+        with_pool!(pool, |pool| {
+            sqlx::query(
+                "UPDATE followed_anime SET last_notified_episode = $3
+                 WHERE user_id = $1 AND anime_id = $2",
+            )
+            .bind(user_id)
+            .bind(anime_id)
+            .bind(episode)
+            .execute(pool)
+            .await
+            .map(|_| ())
+        })
+    }
+}
@@ -8,28 +8,213 @@
 
 //! The group model.
 
-use chrono::{DateTime, Utc};
-use sqlx::{FromRow, Row};
-use tiny_orm::Table;
+use chrono::{DateTime, NaiveDate, Utc};
+use grammers_client::session::PackedChat;
+use sqlx::FromRow;
+
+use crate::{
+    resources::database::{Backend, Pool, with_pool},
+    utils,
+};
 
 /// The group model.
-#[derive(Debug, FromRow, Table, Clone)]
-#[tiny_orm(table_name = "groups")]
+#[derive(Debug, FromRow, Clone)]
 pub struct Group {
     /// The group's ID.
-    #[tiny_orm(primary_key)]
     pub id: i64,
     /// The group's langauge code.
     pub language_code: String,
+    /// Whether the group allows adult content.
+    pub allow_adult: bool,
+    /// The language the group's titles are shown in.
+    pub title_language: String,
+    /// The language of the voice actors listed with the characters.
+    pub voice_actor_language: String,
+    /// Whether the group gets the character birthdays every day.
+    pub birthdays: bool,
+    /// Whether the group gets a card for the AniList links sent to it.
+    pub link_preview: bool,
+    /// The commands turned off in the group, comma-separated.
+    pub disabled_commands: String,
+    /// How many seconds the usage and error replies stay before they are deleted, 0 to keep
+    /// them.
+    pub clean_service_messages: i32,
+    /// When the group last interacted with the bot, recorded at most once a minute.
+    pub last_seen_at: Option<DateTime<Utc>>,
+    /// How many times the group interacted with the bot, counted at most once a minute.
+    pub interaction_count: i64,
+    /// The group's chat packed with its access hash, as hex, recorded with the activity.
+    pub packed_chat: Option<String>,
     /// The group's created at date.
     pub created_at: DateTime<Utc>,
     /// The group's updated at date.
     pub updated_at: DateTime<Utc>,
 }
 
+impl Group {
+    /// Gets a group by its ID.
+    ///
+    /// # Arguments
+    ///
+    /// * `pool` - The database pool.
+    /// * `id` - The group's ID.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the query fails.
+    pub async fn get_by_id(pool: &Pool, id: &i64) -> sqlx::Result<Option<Self>> {
+        with_pool!(pool, |pool| {
+            sqlx::query_as("SELECT * FROM groups WHERE id = $1")
+                .bind(id)
+                .fetch_optional(pool)
+                .await
+        })
+    }
+
+    /// Records that the group interacted with the bot, bumping its interaction count.
+    ///
+    /// # Arguments
+    ///
+    /// * `pool` - The database pool.
+    /// * `id` - The group's ID.
+    /// * `packed_chat` - The group's packed chat, as hex.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the query fails.
+    pub async fn touch(pool: &Pool, id: i64, packed_chat: &str) -> sqlx::Result<()> {
+        with_pool!(pool, |pool| {
+            sqlx::query(
+                "UPDATE groups SET last_seen_at = $2, interaction_count = interaction_count + 1,
+                 packed_chat = $3 WHERE id = $1",
+            )
+            .bind(id)
+            .bind(Utc::now())
+            .bind(packed_chat)
+            .execute(pool)
+            .await
+            .map(|_| ())
+        })
+    }
+
+    /// Counts the groups that interacted with the bot since a moment.
+    ///
+    /// # Arguments
+    ///
+    /// * `pool` - The database pool.
+    /// * `since` - The moment to count from.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the query fails.
+    pub async fn active_since(pool: &Pool, since: DateTime<Utc>) -> sqlx::Result<i64> {
+        with_pool!(pool, |pool| {
+            sqlx::query_scalar("SELECT COUNT(*) FROM groups WHERE last_seen_at >= $1")
+                .bind(since)
+                .fetch_one(pool)
+                .await
+        })
+    }
+
+    /// Counts the groups.
+    ///
+    /// # Arguments
+    ///
+    /// * `pool` - The database pool.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the query fails.
+    pub async fn count(pool: &Pool) -> sqlx::Result<i64> {
+        with_pool!(pool, |pool| {
+            sqlx::query_scalar("SELECT COUNT(*) FROM groups")
+                .fetch_one(pool)
+                .await
+        })
+    }
+
+    /// Counts the groups by their language code.
+    ///
+    /// # Arguments
+    ///
+    /// * `pool` - The database pool.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the query fails.
+    pub async fn count_by_language(pool: &Pool) -> sqlx::Result<Vec<(String, i64)>> {
+        with_pool!(pool, |pool| {
+            sqlx::query_as(
+                "SELECT language_code, COUNT(*) FROM groups GROUP BY language_code ORDER BY COUNT(*) DESC",
+            )
+            .fetch_all(pool)
+            .await
+        })
+    }
+
+    /// Counts the groups added on each day since a moment, skipping the days without any.
+    ///
+    /// # Arguments
+    ///
+    /// * `pool` - The database pool.
+    /// * `since` - The moment to count from.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the query fails.
+    pub async fn count_by_day(
+        pool: &Pool,
+        since: DateTime<Utc>,
+    ) -> sqlx::Result<Vec<(NaiveDate, i64)>> {
+        let query = match pool.backend() {
+            Backend::Postgres => {
+                "SELECT (created_at AT TIME ZONE 'UTC')::date AS day, COUNT(*) FROM groups WHERE created_at >= $1 GROUP BY day ORDER BY day"
+            }
+            Backend::Sqlite => {
+                "SELECT date(created_at) AS day, COUNT(*) FROM groups WHERE created_at >= $1 GROUP BY day ORDER BY day"
+            }
+        };
+
+        with_pool!(pool, |pool| sqlx::query_as(query)
+            .bind(since)
+            .fetch_all(pool)
+            .await)
+    }
+
+    /// Gets the groups that get the character birthdays every day.
+    ///
+    /// # Arguments
+    ///
+    /// * `pool` - The database pool.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the query fails.
+    pub async fn with_birthdays(pool: &Pool) -> sqlx::Result<Vec<Self>> {
+        with_pool!(pool, |pool| {
+            sqlx::query_as("SELECT * FROM groups WHERE birthdays ORDER BY id")
+                .fetch_all(pool)
+                .await
+        })
+    }
+
+    /// Gets the commands turned off in the group.
+    pub fn disabled_command_list(&self) -> Vec<String> {
+        self.disabled_commands
+            .split(',')
+            .filter(|command| !command.is_empty())
+            .map(String::from)
+            .collect()
+    }
+
+    /// Gets the group's chat to send messages to, `None` if it was not recorded yet.
+    pub fn chat(&self) -> Option<PackedChat> {
+        self.packed_chat.as_deref().and_then(utils::unpack_chat)
+    }
+}
+
 /// The new group model.
-#[derive(Debug, FromRow, Table, Clone)]
-#[tiny_orm(table_name = "groups")]
+#[derive(Debug, FromRow, Clone)]
 pub struct NewGroup {
     /// The group's ID.
     pub id: i64,
@@ -47,16 +232,93 @@ impl NewGroup {
     pub fn new(id: i64, language_code: String) -> Self {
         Self { id, language_code }
     }
+
+    /// Inserts the group, returning it with the defaults of the other columns.
+    ///
+    /// # Arguments
+    ///
+    /// * `pool` - The database pool.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the query fails, or the group already exists.
+    pub async fn create(&self, pool: &Pool) -> sqlx::Result<Group> {
+        with_pool!(pool, |pool| {
+            sqlx::query_as("INSERT INTO groups (id, language_code) VALUES ($1, $2) RETURNING *")
+                .bind(self.id)
+                .bind(&self.language_code)
+                .fetch_one(pool)
+                .await
+        })
+    }
 }
 
 /// The update group model.
-#[derive(Debug, FromRow, Table, Clone)]
-#[tiny_orm(table_name = "groups")]
+#[derive(Debug, FromRow, Clone)]
 pub struct UpdateGroup {
     /// The group's ID.
     pub id: i64,
     /// The group's langauge code.
     pub language_code: String,
+    /// Whether the group allows adult content.
+    pub allow_adult: bool,
+    /// The language the group's titles are shown in.
+    pub title_language: String,
+    /// The language of the voice actors listed with the characters.
+    pub voice_actor_language: String,
+    /// Whether the group gets the character birthdays every day.
+    pub birthdays: bool,
+    /// Whether the group gets a card for the AniList links sent to it.
+    pub link_preview: bool,
+    /// The commands turned off in the group, comma-separated.
+    pub disabled_commands: String,
+    /// How many seconds the usage and error replies stay before they are deleted, 0 to keep
+    /// them.
+    pub clean_service_messages: i32,
+}
+
+impl UpdateGroup {
+    /// Sets the commands turned off in the group.
+    ///
+    /// # Arguments
+    ///
+    /// * `commands` - The commands, without the slash.
+    pub fn set_disabled_commands(&mut self, commands: &[String]) {
+        self.disabled_commands = commands.join(",");
+    }
+
+    /// Saves the group's columns, bumping its updated at date.
+    ///
+    /// # Arguments
+    ///
+    /// * `pool` - The database pool.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the query fails.
+    pub async fn update(&self, pool: &Pool) -> sqlx::Result<()> {
+        with_pool!(pool, |pool| {
+            sqlx::query(
+                "UPDATE groups SET language_code = $2, allow_adult = $3, title_language = $4,
+                 voice_actor_language = $5, birthdays = $6, link_preview = $7,
+                 disabled_commands = $8, clean_service_messages = $9, updated_at = $10
+                 WHERE id = $1",
+            )
+            .bind(self.id)
+            .bind(&self.language_code)
+            .bind(self.allow_adult)
+            .bind(&self.title_language)
+            .bind(&self.voice_actor_language)
+            .bind(self.birthdays)
+            .bind(self.link_preview)
+            .bind(&self.disabled_commands)
+            .bind(self.clean_service_messages)
+            .bind(Utc::now())
+            .execute(pool)
+            .await
+            .map(|_| ())
+        })
+    }
 }
 
 impl From<Group> for UpdateGroup {
@@ -64,6 +326,13 @@ impl From<Group> for UpdateGroup {
         Self {
             id: group.id,
             language_code: group.language_code,
+            allow_adult: group.allow_adult,
+            title_language: group.title_language,
+            voice_actor_language: group.voice_actor_language,
+            birthdays: group.birthdays,
+            link_preview: group.link_preview,
+            disabled_commands: group.disabled_commands,
+            clean_service_messages: group.clean_service_messages,
         }
     }
 }
@@ -0,0 +1,138 @@
+// Copyright 2025 - Andriel Ferreira
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// https://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or https://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+//! The ID map model.
+
+use chrono::{DateTime, Utc};
+use sqlx::FromRow;
+
+use crate::resources::database::{Pool, with_pool};
+
+/// The type of a media.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum MediaType {
+    /// An anime.
+    Anime,
+    /// A manga.
+    Manga,
+}
+
+impl MediaType {
+    /// Gets the name of the type as used by AniList and stored in the database.
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Anime => "ANIME",
+            Self::Manga => "MANGA",
+        }
+    }
+}
+
+/// The ID map model.
+///
+/// Maps a MyAnimeList ID to its AniList ID.
+#[derive(Debug, FromRow, Clone)]
+pub struct IdMap {
+    /// The media type, `ANIME` or `MANGA`.
+    pub media_type: String,
+    /// The MyAnimeList ID.
+    pub mal_id: i64,
+    /// The AniList ID.
+    pub anilist_id: i64,
+    /// The mapping's updated at date.
+    pub updated_at: DateTime<Utc>,
+}
+
+impl IdMap {
+    /// Stores a mapping, replacing the AniList ID previously mapped to the MyAnimeList ID.
+    ///
+    /// # Arguments
+    ///
+    /// * `pool` - The database pool.
+    /// * `media_type` - The media type.
+    /// * `anilist_id` - The AniList ID.
+    /// * `mal_id` - The MyAnimeList ID.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the query fails.
+    pub async fn upsert(
+        pool: &Pool,
+        media_type: MediaType,
+        anilist_id: i64,
+        mal_id: i64,
+    ) -> sqlx::Result<()> {
+        with_pool!(pool, |pool| {
+            sqlx::query(
+                "INSERT INTO id_map (media_type, mal_id, anilist_id) VALUES ($1, $2, $3)
+                 ON CONFLICT (media_type, mal_id) DO UPDATE
+                 SET anilist_id = EXCLUDED.anilist_id, updated_at = $4",
+            )
+            .bind(media_type.as_str())
+            .bind(mal_id)
+            .bind(anilist_id)
+            .bind(Utc::now())
+            .execute(pool)
+            .await
+            .map(|_| ())
+        })
+    }
+
+    /// Gets the AniList ID mapped to a MyAnimeList ID.
+    ///
+    /// # Arguments
+    ///
+    /// * `pool` - The database pool.
+    /// * `media_type` - The media type.
+    /// * `mal_id` - The MyAnimeList ID.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the query fails.
+    pub async fn anilist_id(
+        pool: &Pool,
+        media_type: MediaType,
+        mal_id: i64,
+    ) -> sqlx::Result<Option<i64>> {
+        with_pool!(pool, |pool| {
+            sqlx::query_scalar(
+                "SELECT anilist_id FROM id_map WHERE media_type = $1 AND mal_id = $2",
+            )
+            .bind(media_type.as_str())
+            .bind(mal_id)
+            .fetch_optional(pool)
+            .await
+        })
+    }
+
+    /// Gets the MyAnimeList ID mapped to an AniList ID.
+    ///
+    /// # Arguments
+    ///
+    /// * `pool` - The database pool.
+    /// * `media_type` - The media type.
+    /// * `anilist_id` - The AniList ID.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the query fails.
+    pub async fn mal_id(
+        pool: &Pool,
+        media_type: MediaType,
+        anilist_id: i64,
+    ) -> sqlx::Result<Option<i64>> {
+        with_pool!(pool, |pool| {
+            sqlx::query_scalar(
+                "SELECT mal_id FROM id_map WHERE media_type = $1 AND anilist_id = $2 LIMIT 1",
+            )
+            .bind(media_type.as_str())
+            .bind(anilist_id)
+            .fetch_optional(pool)
+            .await
+        })
+    }
+}
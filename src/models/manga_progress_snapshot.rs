@@ -0,0 +1,120 @@
+// Copyright 2025 - Andriel Ferreira
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// https://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or https://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+//! The manga progress snapshot model.
+
+use chrono::{DateTime, Utc};
+use sqlx::FromRow;
+
+use crate::resources::database::{Pool, with_pool};
+
+/// The manga progress snapshot model.
+///
+/// The number of chapters a manga of a user's reading list had when it was last checked.
+#[derive(Debug, FromRow, Clone)]
+pub struct MangaProgressSnapshot {
+    /// The Telegram user ID.
+    pub user_id: i64,
+    /// The AniList ID of the manga.
+    pub manga_id: i64,
+    /// The number of chapters of the manga.
+    pub chapters: i32,
+    /// When the manga was last checked.
+    pub updated_at: DateTime<Utc>,
+}
+
+impl MangaProgressSnapshot {
+    /// Gets the snapshots of a user.
+    ///
+    /// # Arguments
+    ///
+    /// * `pool` - The database pool.
+    /// * `user_id` - The Telegram user ID.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the query fails.
+    pub async fn get_by_user(pool: &Pool, user_id: i64) -> sqlx::Result<Vec<Self>> {
+        with_pool!(pool, |pool| {
+            sqlx::query_as(
+                "SELECT user_id, manga_id, chapters, updated_at FROM manga_progress_snapshots
+                 WHERE user_id = $1",
+            )
+            .bind(user_id)
+            .fetch_all(pool)
+            .await
+        })
+    }
+
+    /// Replaces the snapshots of a user, forgetting the mangas they are no longer reading.
+    ///
+    /// # Arguments
+    ///
+    /// * `pool` - The database pool.
+    /// * `user_id` - The Telegram user ID.
+    /// * `chapters` - The number of chapters of each manga, by its AniList ID.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if a query fails.
+    pub async fn replace(pool: &Pool, user_id: i64, chapters: &[(i64, i32)]) -> sqlx::Result<()> {
+        let now = Utc::now();
+
+        match pool {
+            Pool::Postgres(pool) => {
+                let (ids, counts): (Vec<i64>, Vec<i32>) = chapters.iter().copied().unzip();
+
+                let mut tx = pool.begin().await?;
+                sqlx::query(
+                    "DELETE FROM manga_progress_snapshots WHERE user_id = $1 AND manga_id <> ALL($2)",
+                )
+                .bind(user_id)
+                .bind(&ids)
+                .execute(&mut *tx)
+                .await?;
+                sqlx::query(
+                    "INSERT INTO manga_progress_snapshots (user_id, manga_id, chapters, updated_at)
+                     SELECT $1, manga_id, chapters, $4 FROM UNNEST($2::INT8[], $3::INT4[])
+                     AS snapshots (manga_id, chapters)
+                     ON CONFLICT (user_id, manga_id) DO UPDATE
+                     SET chapters = EXCLUDED.chapters, updated_at = EXCLUDED.updated_at",
+                )
+                .bind(user_id)
+                .bind(&ids)
+                .bind(&counts)
+                .bind(now)
+                .execute(&mut *tx)
+                .await?;
+
+                tx.commit().await
+            }
+            // SQLite has no arrays, the snapshots are written one by one.
+            Pool::Sqlite(pool) => {
+                let mut tx = pool.begin().await?;
+                sqlx::query("DELETE FROM manga_progress_snapshots WHERE user_id = $1")
+                    .bind(user_id)
+                    .execute(&mut *tx)
+                    .await?;
+                for (manga_id, count) in chapters {
+                    sqlx::query(
+                        "INSERT INTO manga_progress_snapshots (user_id, manga_id, chapters, updated_at)
+                         VALUES ($1, $2, $3, $4)",
+                    )
+                    .bind(user_id)
+                    .bind(manga_id)
+                    .bind(count)
+                    .bind(now)
+                    .execute(&mut *tx)
+                    .await?;
+                }
+
+                tx.commit().await
+            }
+        }
+    }
+}
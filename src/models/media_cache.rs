@@ -0,0 +1,130 @@
+// Copyright 2025 - Andriel Ferreira
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// https://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or https://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+//! The media cache model.
+
+use chrono::{DateTime, Utc};
+use sqlx::FromRow;
+
+use crate::{
+    models::MediaType,
+    resources::database::{Backend, Pool, with_pool},
+};
+
+/// The media cache model.
+///
+/// A media as last fetched from AniList, so it survives restarts.
+#[derive(Debug, FromRow, Clone)]
+pub struct MediaCache {
+    /// The media type, `ANIME` or `MANGA`.
+    pub media_type: String,
+    /// The AniList ID.
+    pub media_id: i64,
+    /// The media, serialized as JSON.
+    pub payload: String,
+    /// When the media was fetched.
+    pub fetched_at: DateTime<Utc>,
+}
+
+impl MediaCache {
+    /// Gets a cached media.
+    ///
+    /// # Arguments
+    ///
+    /// * `pool` - The database pool.
+    /// * `media_type` - The media type.
+    /// * `media_id` - The AniList ID.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the query fails.
+    pub async fn get(
+        pool: &Pool,
+        media_type: MediaType,
+        media_id: i64,
+    ) -> sqlx::Result<Option<Self>> {
+        with_pool!(pool, |pool| {
+            sqlx::query_as(
+                "SELECT media_type, media_id, CAST(payload AS TEXT) AS payload, fetched_at FROM media_cache
+                 WHERE media_type = $1 AND media_id = $2",
+            )
+            .bind(media_type.as_str())
+            .bind(media_id)
+            .fetch_optional(pool)
+            .await
+        })
+    }
+
+    /// Stores a media, replacing the one previously cached.
+    ///
+    /// # Arguments
+    ///
+    /// * `pool` - The database pool.
+    /// * `media_type` - The media type.
+    /// * `media_id` - The AniList ID.
+    /// * `payload` - The media, serialized as JSON.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the query fails.
+    pub async fn upsert(
+        pool: &Pool,
+        media_type: MediaType,
+        media_id: i64,
+        payload: &str,
+    ) -> sqlx::Result<()> {
+        // The payload is stored as JSONB by Postgres, as text by SQLite.
+        let sql = match pool.backend() {
+            Backend::Postgres => {
+                "INSERT INTO media_cache (media_type, media_id, payload) VALUES ($1, $2, $3::JSONB)
+                 ON CONFLICT (media_type, media_id) DO UPDATE
+                 SET payload = EXCLUDED.payload, fetched_at = $4"
+            }
+            Backend::Sqlite => {
+                "INSERT INTO media_cache (media_type, media_id, payload) VALUES ($1, $2, $3)
+                 ON CONFLICT (media_type, media_id) DO UPDATE
+                 SET payload = EXCLUDED.payload, fetched_at = $4"
+            }
+        };
+
+        with_pool!(pool, |pool| {
+            sqlx::query(sql)
+                .bind(media_type.as_str())
+                .bind(media_id)
+                .bind(payload)
+                .bind(Utc::now())
+                .execute(pool)
+                .await
+                .map(|_| ())
+        })
+    }
+
+    /// Deletes the media fetched before a date.
+    ///
+    /// Returns how many were deleted.
+    ///
+    /// # Arguments
+    ///
+    /// * `pool` - The database pool.
+    /// * `before` - The date the media must have been fetched after to be kept.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the query fails.
+    pub async fn delete_older_than(pool: &Pool, before: DateTime<Utc>) -> sqlx::Result<u64> {
+        let affected = with_pool!(pool, |pool| {
+            sqlx::query("DELETE FROM media_cache WHERE fetched_at < $1")
+                .bind(before)
+                .execute(pool)
+                .await
+                .map(|result| result.rows_affected())
+        })?;
+
+        Ok(affected)
+    }
+}
@@ -0,0 +1,94 @@
+// Copyright 2025 - Andriel Ferreira
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// https://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or https://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+//! The media file model.
+
+use chrono::{DateTime, Utc};
+use sqlx::FromRow;
+
+use crate::resources::database::{Pool, with_pool};
+
+/// The media file model.
+///
+/// An image already uploaded to Telegram, so it is sent again without uploading it.
+#[derive(Debug, FromRow, Clone)]
+pub struct MediaFile {
+    /// The link the image was uploaded from.
+    pub url: String,
+    /// The file ID of the uploaded image.
+    pub file_id: String,
+    /// When the image was uploaded.
+    pub uploaded_at: DateTime<Utc>,
+}
+
+impl MediaFile {
+    /// Gets the uploaded image of a link.
+    ///
+    /// # Arguments
+    ///
+    /// * `pool` - The database pool.
+    /// * `url` - The link of the image.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the query fails.
+    pub async fn get(pool: &Pool, url: &str) -> sqlx::Result<Option<Self>> {
+        with_pool!(pool, |pool| {
+            sqlx::query_as("SELECT url, file_id, uploaded_at FROM media_files WHERE url = $1")
+                .bind(url)
+                .fetch_optional(pool)
+                .await
+        })
+    }
+
+    /// Stores the uploaded image of a link, replacing the one previously stored.
+    ///
+    /// # Arguments
+    ///
+    /// * `pool` - The database pool.
+    /// * `url` - The link of the image.
+    /// * `file_id` - The file ID of the uploaded image.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the query fails.
+    pub async fn upsert(pool: &Pool, url: &str, file_id: &str) -> sqlx::Result<()> {
+        with_pool!(pool, |pool| {
+            sqlx::query(
+                "INSERT INTO media_files (url, file_id) VALUES ($1, $2)
+                 ON CONFLICT (url) DO UPDATE SET file_id = EXCLUDED.file_id, uploaded_at = $3",
+            )
+            .bind(url)
+            .bind(file_id)
+            .bind(Utc::now())
+            .execute(pool)
+            .await
+            .map(|_| ())
+        })
+    }
+
+    /// Deletes the uploaded image of a link.
+    ///
+    /// # Arguments
+    ///
+    /// * `pool` - The database pool.
+    /// * `url` - The link of the image.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the query fails.
+    pub async fn delete(pool: &Pool, url: &str) -> sqlx::Result<()> {
+        with_pool!(pool, |pool| {
+            sqlx::query("DELETE FROM media_files WHERE url = $1")
+                .bind(url)
+                .execute(pool)
+                .await
+                .map(|_| ())
+        })
+    }
+}
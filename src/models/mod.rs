@@ -8,8 +8,34 @@
 
 //! Database models.
 
+pub mod anilist_auth;
+pub mod banned_user;
+pub mod chat_settings;
+pub mod command_stat;
+pub mod entry_kind;
+pub mod favorite;
+pub mod followed_anime;
 pub mod group;
+pub mod id_map;
+pub mod manga_progress_snapshot;
+pub mod media_cache;
+pub mod media_file;
 pub mod user;
+pub mod user_data;
+pub mod view_history;
 
+pub use anilist_auth::AniListAuth;
+pub use banned_user::BannedUser;
+pub use chat_settings::{ChatSettings, TitleLanguage, VoiceActorLanguage};
+pub use command_stat::{CommandStat, StatKind};
+pub use entry_kind::EntryKind;
+pub use favorite::Favorite;
+pub use followed_anime::{FollowedAnime, Follower};
 pub use group::{Group, NewGroup, UpdateGroup};
+pub use id_map::{IdMap, MediaType};
+pub use manga_progress_snapshot::MangaProgressSnapshot;
+pub use media_cache::MediaCache;
+pub use media_file::MediaFile;
 pub use user::{NewUser, UpdateUser, User};
+pub use user_data::UserData;
+pub use view_history::ViewHistory;
@@ -8,32 +8,337 @@
 
 //! The user model.
 
-use chrono::{DateTime, Utc};
-use sqlx::{FromRow, Row};
-use tiny_orm::Table;
+use chrono::{DateTime, NaiveDate, Utc};
+use grammers_client::session::PackedChat;
+use sqlx::FromRow;
+
+use crate::{
+    resources::{
+        database::{Backend, Pool, with_pool},
+        sessions::token_expiry,
+        token_cipher,
+    },
+    utils,
+};
 
 /// The user model.
-#[derive(Debug, FromRow, Table, Clone)]
-#[tiny_orm(table_name = "users")]
+#[derive(Debug, FromRow, Clone)]
 pub struct User {
     /// The user's ID.
-    #[tiny_orm(primary_key)]
     pub id: i64,
     /// The user's Anilist ID.
     pub anilist_id: Option<i32>,
-    /// The user's Anilist token.
+    /// The user's Anilist token, as stored: encrypted when a key is configured.
     pub anilist_token: Option<String>,
+    /// When the user's Anilist token expires, if known.
+    pub anilist_token_expires_at: Option<DateTime<Utc>>,
     /// The user's langauge code.
     pub language_code: String,
+    /// Whether the user wants search results grouped by franchise.
+    pub group_results: bool,
+    /// Whether the user allows adult content.
+    pub allow_adult: bool,
+    /// The language the user's titles are shown in.
+    pub title_language: String,
+    /// The language of the voice actors listed with the characters.
+    pub voice_actor_language: String,
+    /// Whether the cards the user opens are recorded in their history.
+    pub track_history: bool,
+    /// Whether the user is notified of the new chapters of the mangas they are reading.
+    pub chapter_notifications: bool,
+    /// When the user last interacted with the bot, recorded at most once a minute.
+    pub last_seen_at: Option<DateTime<Utc>>,
+    /// How many times the user interacted with the bot, counted at most once a minute.
+    pub interaction_count: i64,
+    /// The user's chat packed with their access hash, as hex, recorded with the activity.
+    pub packed_chat: Option<String>,
     /// The user's created at date.
     pub created_at: DateTime<Utc>,
     /// The user's updated at date.
     pub updated_at: DateTime<Utc>,
 }
 
+impl User {
+    /// Gets a user by their ID.
+    ///
+    /// # Arguments
+    ///
+    /// * `pool` - The database pool.
+    /// * `id` - The user's ID.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the query fails.
+    pub async fn get_by_id(pool: &Pool, id: &i64) -> sqlx::Result<Option<Self>> {
+        with_pool!(pool, |pool| {
+            sqlx::query_as("SELECT * FROM users WHERE id = $1")
+                .bind(id)
+                .fetch_optional(pool)
+                .await
+        })
+    }
+
+    /// Records that the user interacted with the bot, bumping their interaction count.
+    ///
+    /// # Arguments
+    ///
+    /// * `pool` - The database pool.
+    /// * `id` - The user's ID.
+    /// * `packed_chat` - The user's packed chat, as hex.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the query fails.
+    pub async fn touch(pool: &Pool, id: i64, packed_chat: &str) -> sqlx::Result<()> {
+        with_pool!(pool, |pool| {
+            sqlx::query(
+                "UPDATE users SET last_seen_at = $2, interaction_count = interaction_count + 1,
+                 packed_chat = $3 WHERE id = $1",
+            )
+            .bind(id)
+            .bind(Utc::now())
+            .bind(packed_chat)
+            .execute(pool)
+            .await
+            .map(|_| ())
+        })
+    }
+
+    /// Counts the users that interacted with the bot since a moment.
+    ///
+    /// # Arguments
+    ///
+    /// * `pool` - The database pool.
+    /// * `since` - The moment to count from.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the query fails.
+    pub async fn active_since(pool: &Pool, since: DateTime<Utc>) -> sqlx::Result<i64> {
+        with_pool!(pool, |pool| {
+            sqlx::query_scalar("SELECT COUNT(*) FROM users WHERE last_seen_at >= $1")
+                .bind(since)
+                .fetch_one(pool)
+                .await
+        })
+    }
+
+    /// Counts the users.
+    ///
+    /// # Arguments
+    ///
+    /// * `pool` - The database pool.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the query fails.
+    pub async fn count(pool: &Pool) -> sqlx::Result<i64> {
+        with_pool!(pool, |pool| {
+            sqlx::query_scalar("SELECT COUNT(*) FROM users")
+                .fetch_one(pool)
+                .await
+        })
+    }
+
+    /// Counts the users by their language code.
+    ///
+    /// # Arguments
+    ///
+    /// * `pool` - The database pool.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the query fails.
+    pub async fn count_by_language(pool: &Pool) -> sqlx::Result<Vec<(String, i64)>> {
+        with_pool!(pool, |pool| {
+            sqlx::query_as(
+                "SELECT language_code, COUNT(*) FROM users GROUP BY language_code ORDER BY COUNT(*) DESC",
+            )
+            .fetch_all(pool)
+            .await
+        })
+    }
+
+    /// Counts the users registered on each day since a moment, skipping the days without any.
+    ///
+    /// # Arguments
+    ///
+    /// * `pool` - The database pool.
+    /// * `since` - The moment to count from.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the query fails.
+    pub async fn count_by_day(
+        pool: &Pool,
+        since: DateTime<Utc>,
+    ) -> sqlx::Result<Vec<(NaiveDate, i64)>> {
+        let query = match pool.backend() {
+            Backend::Postgres => {
+                "SELECT (created_at AT TIME ZONE 'UTC')::date AS day, COUNT(*) FROM users WHERE created_at >= $1 GROUP BY day ORDER BY day"
+            }
+            Backend::Sqlite => {
+                "SELECT date(created_at) AS day, COUNT(*) FROM users WHERE created_at >= $1 GROUP BY day ORDER BY day"
+            }
+        };
+
+        with_pool!(pool, |pool| sqlx::query_as(query)
+            .bind(since)
+            .fetch_all(pool)
+            .await)
+    }
+
+    /// Gets the IDs and packed chats, as hex, of the users that did not block the bot.
+    ///
+    /// # Arguments
+    ///
+    /// * `pool` - The database pool.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the query fails.
+    pub async fn reachable(pool: &Pool) -> sqlx::Result<Vec<(i64, Option<String>)>> {
+        with_pool!(pool, |pool| {
+            sqlx::query_as("SELECT id, packed_chat FROM users WHERE blocked_at IS NULL ORDER BY id")
+                .fetch_all(pool)
+                .await
+        })
+    }
+
+    /// Gets the users to notify of new chapters: those who opted in, authenticated with
+    /// AniList and did not block the bot.
+    ///
+    /// # Arguments
+    ///
+    /// * `pool` - The database pool.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the query fails.
+    pub async fn chapter_subscribers(pool: &Pool) -> sqlx::Result<Vec<Self>> {
+        with_pool!(pool, |pool| {
+            sqlx::query_as(
+                "SELECT * FROM users WHERE chapter_notifications AND anilist_id IS NOT NULL
+                 AND anilist_token IS NOT NULL AND blocked_at IS NULL ORDER BY id",
+            )
+            .fetch_all(pool)
+            .await
+        })
+    }
+
+    /// Records whether a user blocked the bot, so the broadcasts skip them.
+    ///
+    /// # Arguments
+    ///
+    /// * `pool` - The database pool.
+    /// * `id` - The user's ID.
+    /// * `blocked` - Whether the user blocked the bot.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the query fails.
+    pub async fn set_blocked(pool: &Pool, id: i64, blocked: bool) -> sqlx::Result<()> {
+        with_pool!(pool, |pool| {
+            sqlx::query(
+                "UPDATE users SET blocked_at = CASE WHEN $2 THEN $3 END WHERE id = $1 AND (blocked_at IS NULL) = $2",
+            )
+            .bind(id)
+            .bind(blocked)
+            .bind(Utc::now())
+            .execute(pool)
+            .await
+            .map(|_| ())
+        })
+    }
+
+    /// Encrypts the AniList tokens stored in plain text, before a key was configured.
+    ///
+    /// Returns how many tokens were encrypted, none if no key is configured.
+    ///
+    /// # Arguments
+    ///
+    /// * `pool` - The database pool.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if a query fails.
+    pub async fn encrypt_plain_tokens(pool: &Pool) -> sqlx::Result<u64> {
+        let Some(cipher) = token_cipher::cipher() else {
+            return Ok(0);
+        };
+
+        let rows: Vec<(i64, String)> = with_pool!(pool, |pool| {
+            sqlx::query_as(
+                "SELECT id, anilist_token FROM users WHERE anilist_token IS NOT NULL AND anilist_token NOT LIKE $1 || '%'",
+            )
+            .bind(token_cipher::ENCRYPTED_PREFIX)
+            .fetch_all(pool)
+            .await
+        })?;
+
+        let mut encrypted = 0;
+        for (id, token) in rows {
+            // The token may have changed since it was read, it is left to the next startup then.
+            encrypted += with_pool!(pool, |pool| {
+                sqlx::query(
+                    "UPDATE users SET anilist_token = $3 WHERE id = $1 AND anilist_token = $2",
+                )
+                .bind(id)
+                .bind(&token)
+                .bind(cipher.encrypt(&token))
+                .execute(pool)
+                .await
+                .map(|result| result.rows_affected())
+            })?;
+        }
+
+        Ok(encrypted)
+    }
+
+    /// Gets the user's AniList token, decrypted.
+    ///
+    /// Returns `None` if the user has no token, or it could not be decrypted.
+    pub fn token(&self) -> Option<String> {
+        self.anilist_token.as_deref().and_then(token_cipher::open)
+    }
+
+    /// Whether the user's AniList token expired.
+    ///
+    /// Tokens stored before their expiry was tracked are checked against their claims.
+    pub fn token_expired(&self) -> bool {
+        self.anilist_token_expires_at
+            .or_else(|| self.token().as_deref().and_then(token_expiry))
+            .is_some_and(|expires_at| expires_at <= Utc::now())
+    }
+
+    /// Whether the user has an AniList token that did not expire.
+    pub fn has_valid_token(&self) -> bool {
+        self.token().is_some() && !self.token_expired()
+    }
+
+    /// Whether a user wants search results grouped by franchise.
+    ///
+    /// Defaults to `true` when the user is not registered or the query fails.
+    ///
+    /// # Arguments
+    ///
+    /// * `pool` - The database pool.
+    /// * `id` - The user's ID.
+    pub async fn wants_grouped_results(pool: &Pool, id: i64) -> bool {
+        match Self::get_by_id(pool, &id).await {
+            Ok(user) => user.is_none_or(|user| user.group_results),
+            Err(_) => true,
+        }
+    }
+
+    /// Gets the user's chat to send messages to, `None` if it was not recorded yet.
+    pub fn chat(&self) -> Option<PackedChat> {
+        self.packed_chat.as_deref().and_then(utils::unpack_chat)
+    }
+}
+
 /// The new user model.
-#[derive(Debug, FromRow, Table, Clone)]
-#[tiny_orm(table_name = "users")]
+#[derive(Debug, FromRow, Clone)]
 pub struct NewUser {
     /// The user's ID.
     pub id: i64,
@@ -51,20 +356,98 @@ impl NewUser {
     pub fn new(id: i64, language_code: String) -> Self {
         Self { id, language_code }
     }
+
+    /// Inserts the user, returning it with the defaults of the other columns.
+    ///
+    /// # Arguments
+    ///
+    /// * `pool` - The database pool.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the query fails, or the user already exists.
+    pub async fn create(&self, pool: &Pool) -> sqlx::Result<User> {
+        with_pool!(pool, |pool| {
+            sqlx::query_as("INSERT INTO users (id, language_code) VALUES ($1, $2) RETURNING *")
+                .bind(self.id)
+                .bind(&self.language_code)
+                .fetch_one(pool)
+                .await
+        })
+    }
 }
 
 /// The update user model.
-#[derive(Debug, FromRow, Table, Clone)]
-#[tiny_orm(table_name = "users")]
+#[derive(Debug, FromRow, Clone)]
 pub struct UpdateUser {
     /// The user's ID.
     pub id: i64,
     /// The user's Anilist ID.
     pub anilist_id: Option<i32>,
-    /// The user's Anilist token.
+    /// The user's Anilist token, as stored: encrypted when a key is configured.
     pub anilist_token: Option<String>,
+    /// When the user's Anilist token expires, if known.
+    pub anilist_token_expires_at: Option<DateTime<Utc>>,
     /// The user's langauge code.
     pub language_code: String,
+    /// Whether the user wants search results grouped by franchise.
+    pub group_results: bool,
+    /// Whether the user allows adult content.
+    pub allow_adult: bool,
+    /// The language the user's titles are shown in.
+    pub title_language: String,
+    /// The language of the voice actors listed with the characters.
+    pub voice_actor_language: String,
+    /// Whether the cards the user opens are recorded in their history.
+    pub track_history: bool,
+    /// Whether the user is notified of the new chapters of the mangas they are reading.
+    pub chapter_notifications: bool,
+}
+
+impl UpdateUser {
+    /// Sets the user's AniList token, encrypting it when a key is configured.
+    ///
+    /// # Arguments
+    ///
+    /// * `token` - The AniList token, `None` to remove it.
+    pub fn set_token(&mut self, token: Option<&str>) {
+        self.anilist_token = token.map(token_cipher::seal);
+    }
+
+    /// Saves the user's columns, bumping their updated at date.
+    ///
+    /// # Arguments
+    ///
+    /// * `pool` - The database pool.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the query fails.
+    pub async fn update(&self, pool: &Pool) -> sqlx::Result<()> {
+        with_pool!(pool, |pool| {
+            sqlx::query(
+                "UPDATE users SET anilist_id = $2, anilist_token = $3, anilist_token_expires_at = $4,
+                 language_code = $5, group_results = $6, allow_adult = $7, title_language = $8,
+                 voice_actor_language = $9, track_history = $10, chapter_notifications = $11,
+                 updated_at = $12 WHERE id = $1",
+            )
+            .bind(self.id)
+            .bind(self.anilist_id)
+            .bind(&self.anilist_token)
+            .bind(self.anilist_token_expires_at)
+            .bind(&self.language_code)
+            .bind(self.group_results)
+            .bind(self.allow_adult)
+            .bind(&self.title_language)
+            .bind(&self.voice_actor_language)
+            .bind(self.track_history)
+            .bind(self.chapter_notifications)
+            .bind(Utc::now())
+            .execute(pool)
+            .await
+            .map(|_| ())
+        })
+    }
 }
 
 impl From<User> for UpdateUser {
@@ -73,7 +456,14 @@ impl From<User> for UpdateUser {
             id: user.id,
             anilist_id: user.anilist_id,
             anilist_token: user.anilist_token,
+            anilist_token_expires_at: user.anilist_token_expires_at,
             language_code: user.language_code,
+            group_results: user.group_results,
+            allow_adult: user.allow_adult,
+            title_language: user.title_language,
+            voice_actor_language: user.voice_actor_language,
+            track_history: user.track_history,
+            chapter_notifications: user.chapter_notifications,
         }
     }
 }
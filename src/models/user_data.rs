@@ -0,0 +1,156 @@
+// Copyright 2025 - Andriel Ferreira
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// https://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or https://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+//! Everything the bot stores about a user.
+
+use chrono::{DateTime, Utc};
+use serde_json::{Value, json};
+
+use crate::{
+    models::{
+        BannedUser, Favorite, FollowedAnime, MangaProgressSnapshot, User, ViewHistory,
+        view_history::HISTORY_LIMIT,
+    },
+    resources::database::{Pool, with_pool},
+};
+
+/// The tables whose rows belong to a user, by their `user_id` column.
+///
+/// The bans are not listed: they are kept so deleting an account does not lift a ban.
+pub const USER_TABLES: [&str; 4] = [
+    "view_history",
+    "favorites",
+    "followed_anime",
+    "manga_progress_snapshots",
+];
+
+/// Everything the bot stores about a user.
+///
+/// The bot does not store which groups its users are in, only the settings of the groups.
+#[derive(Debug, Clone, Default)]
+pub struct UserData {
+    /// The user's row, `None` if not registered.
+    pub user: Option<User>,
+    /// The animes the user follows.
+    pub follows: Vec<FollowedAnime>,
+    /// The entries the user bookmarked.
+    pub favorites: Vec<Favorite>,
+    /// The cards the user opened.
+    pub history: Vec<ViewHistory>,
+    /// The chapters of the mangas the user is reading, at the last check.
+    pub manga_progress: Vec<MangaProgressSnapshot>,
+    /// The user's ban, if banned.
+    pub ban: Option<BannedUser>,
+}
+
+impl UserData {
+    /// Gathers everything the bot stores about a user.
+    ///
+    /// # Arguments
+    ///
+    /// * `pool` - The database pool.
+    /// * `user_id` - The Telegram user ID.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if a query fails.
+    pub async fn collect(pool: &Pool, user_id: i64) -> sqlx::Result<Self> {
+        Ok(Self {
+            user: User::get_by_id(pool, &user_id).await?,
+            follows: FollowedAnime::get_by_user(pool, user_id).await?,
+            favorites: Favorite::get_by_user(pool, user_id).await?,
+            history: ViewHistory::recent(pool, user_id, HISTORY_LIMIT).await?,
+            manga_progress: MangaProgressSnapshot::get_by_user(pool, user_id).await?,
+            ban: BannedUser::get(pool, user_id).await?,
+        })
+    }
+
+    /// Deletes everything the bot stores about a user, except their ban, in a transaction.
+    ///
+    /// The user's AniList token goes with their row.
+    ///
+    /// # Arguments
+    ///
+    /// * `pool` - The database pool.
+    /// * `user_id` - The Telegram user ID.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if a query fails, nothing is deleted then.
+    pub async fn delete(pool: &Pool, user_id: i64) -> sqlx::Result<()> {
+        with_pool!(pool, |pool| {
+            let mut tx = pool.begin().await?;
+            for table in USER_TABLES {
+                sqlx::query(&format!("DELETE FROM {} WHERE user_id = $1", table))
+                    .bind(user_id)
+                    .execute(&mut *tx)
+                    .await?;
+            }
+            sqlx::query("DELETE FROM users WHERE id = $1")
+                .bind(user_id)
+                .execute(&mut *tx)
+                .await?;
+
+            tx.commit().await
+        })
+    }
+
+    /// Converts the data into a JSON document.
+    ///
+    /// The AniList token is left out, only whether there is one and when it expires are
+    /// kept.
+    pub fn to_json(&self) -> Value {
+        let date = |date: &DateTime<Utc>| date.to_rfc3339();
+
+        json!({
+            "user": self.user.as_ref().map(|user| json!({
+                "id": user.id,
+                "anilist_id": user.anilist_id,
+                "anilist_authenticated": user.anilist_token.is_some(),
+                "anilist_token_expires_at": user.anilist_token_expires_at.as_ref().map(date),
+                "language_code": user.language_code,
+                "group_results": user.group_results,
+                "allow_adult": user.allow_adult,
+                "title_language": user.title_language,
+                "voice_actor_language": user.voice_actor_language,
+                "track_history": user.track_history,
+                "chapter_notifications": user.chapter_notifications,
+                "last_seen_at": user.last_seen_at.as_ref().map(date),
+                "interaction_count": user.interaction_count,
+                "created_at": date(&user.created_at),
+                "updated_at": date(&user.updated_at),
+            })),
+            "follows": self.follows.iter().map(|follow| json!({
+                "anime_id": follow.anime_id,
+                "last_notified_episode": follow.last_notified_episode,
+                "created_at": date(&follow.created_at),
+            })).collect::<Vec<_>>(),
+            "favorites": self.favorites.iter().map(|favorite| json!({
+                "type": favorite.media_type,
+                "id": favorite.media_id,
+                "title": favorite.title,
+                "created_at": date(&favorite.created_at),
+            })).collect::<Vec<_>>(),
+            "history": self.history.iter().map(|entry| json!({
+                "type": entry.media_type,
+                "id": entry.media_id,
+                "title": entry.title,
+                "viewed_at": date(&entry.viewed_at),
+            })).collect::<Vec<_>>(),
+            "manga_progress": self.manga_progress.iter().map(|snapshot| json!({
+                "manga_id": snapshot.manga_id,
+                "chapters": snapshot.chapters,
+                "updated_at": date(&snapshot.updated_at),
+            })).collect::<Vec<_>>(),
+            "ban": self.ban.as_ref().map(|ban| json!({
+                "reason": ban.reason,
+                "created_at": date(&ban.created_at),
+            })),
+        })
+    }
+}
@@ -0,0 +1,150 @@
+// Copyright 2025 - Andriel Ferreira
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// https://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or https://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+//! The view history model.
+
+use chrono::{DateTime, Utc};
+use sqlx::FromRow;
+
+use crate::{
+    models::EntryKind,
+    resources::database::{Pool, with_pool},
+};
+
+/// How many entries the history of a user keeps, the oldest ones are deleted.
+pub const HISTORY_LIMIT: i64 = 50;
+
+/// The view history model.
+///
+/// A card a user opened, the last time they opened it.
+#[derive(Debug, FromRow, Clone)]
+pub struct ViewHistory {
+    /// The Telegram user ID.
+    pub user_id: i64,
+    /// The kind of the entry, see [`EntryKind`].
+    pub media_type: String,
+    /// The AniList ID of the entry.
+    pub media_id: i64,
+    /// The title of the entry when it was viewed.
+    pub title: String,
+    /// When the entry was last viewed.
+    pub viewed_at: DateTime<Utc>,
+}
+
+impl ViewHistory {
+    /// Records a view, moving the entry to the top of the history.
+    ///
+    /// Users who turned the history off are skipped, and the entries past
+    /// [`HISTORY_LIMIT`] are deleted.
+    ///
+    /// # Arguments
+    ///
+    /// * `pool` - The database pool.
+    /// * `user_id` - The Telegram user ID.
+    /// * `kind` - The kind of the entry.
+    /// * `media_id` - The AniList ID of the entry.
+    /// * `title` - The title of the entry.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the query fails.
+    pub async fn record(
+        pool: &Pool,
+        user_id: i64,
+        kind: EntryKind,
+        media_id: i64,
+        title: &str,
+    ) -> sqlx::Result<()> {
+        let affected = with_pool!(pool, |pool| {
+            sqlx::query(
+                "INSERT INTO view_history (user_id, media_type, media_id, title, viewed_at)
+                 SELECT $1, $2, $3, $4, $5
+                 WHERE NOT EXISTS (SELECT 1 FROM users WHERE id = $1 AND NOT track_history)
+                 ON CONFLICT (user_id, media_type, media_id) DO UPDATE
+                 SET title = EXCLUDED.title, viewed_at = EXCLUDED.viewed_at",
+            )
+            .bind(user_id)
+            .bind(kind.as_str())
+            .bind(media_id)
+            .bind(title)
+            .bind(Utc::now())
+            .execute(pool)
+            .await
+            .map(|result| result.rows_affected())
+        })?;
+
+        if affected > 0 {
+            with_pool!(pool, |pool| {
+                sqlx::query(
+                    "DELETE FROM view_history WHERE user_id = $1 AND viewed_at < (
+                         SELECT MIN(viewed_at) FROM (
+                             SELECT viewed_at FROM view_history WHERE user_id = $1
+                             ORDER BY viewed_at DESC LIMIT $2
+                         ) AS kept
+                     )",
+                )
+                .bind(user_id)
+                .bind(HISTORY_LIMIT)
+                .execute(pool)
+                .await
+                .map(|_| ())
+            })?;
+        }
+
+        Ok(())
+    }
+
+    /// Gets the last entries a user viewed, the most recent first.
+    ///
+    /// # Arguments
+    ///
+    /// * `pool` - The database pool.
+    /// * `user_id` - The Telegram user ID.
+    /// * `limit` - How many entries to get.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the query fails.
+    pub async fn recent(pool: &Pool, user_id: i64, limit: i64) -> sqlx::Result<Vec<Self>> {
+        with_pool!(pool, |pool| {
+            sqlx::query_as(
+                "SELECT user_id, media_type, media_id, title, viewed_at FROM view_history
+                 WHERE user_id = $1 ORDER BY viewed_at DESC LIMIT $2",
+            )
+            .bind(user_id)
+            .bind(limit)
+            .fetch_all(pool)
+            .await
+        })
+    }
+
+    /// Deletes the history of a user.
+    ///
+    /// # Arguments
+    ///
+    /// * `pool` - The database pool.
+    /// * `user_id` - The Telegram user ID.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the query fails.
+    pub async fn clear(pool: &Pool, user_id: i64) -> sqlx::Result<()> {
+        with_pool!(pool, |pool| {
+            sqlx::query("DELETE FROM view_history WHERE user_id = $1")
+                .bind(user_id)
+                .execute(pool)
+                .await
+                .map(|_| ())
+        })
+    }
+
+    /// Gets the kind of the entry, `None` if it is unknown.
+    pub fn kind(&self) -> Option<EntryKind> {
+        EntryKind::parse(&self.media_type)
+    }
+}
@@ -0,0 +1,63 @@
+// Copyright 2025 - Andriel Ferreira
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// https://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or https://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+//! The about plugin.
+
+use ferogram::{Result, Router, filter, handler};
+use grammers_client::{InputMessage, types::Message};
+use maplit::hashmap;
+
+use crate::{
+    Config,
+    commands::{HelpCategory, HelpEntry},
+    resources::{AniList, Database, I18n, Stats},
+    utils,
+};
+
+/// The commands of the plugin, for `/help`.
+pub const HELP: &[HelpEntry] = &[HelpEntry::new("about", HelpCategory::Bot)];
+
+/// The plugin setup.
+pub fn setup(router: Router) -> Router {
+    router.register(
+        handler::new_message(filter::command("about").description("About the bot.")).then(about),
+    )
+}
+
+/// The about command handler.
+///
+/// Shows the effective settings, so misconfigurations are visible, and the runtime statistics.
+async fn about(
+    message: Message,
+    config: Config,
+    db: Database,
+    i18n: I18n,
+    ani: AniList,
+    stats: Stats,
+) -> Result<()> {
+    let t_a = |key: &str, args| i18n.translate_with_args(key, args);
+
+    let snapshot = stats.collect(&db, &ani).await;
+
+    message
+        .reply(InputMessage::html(format!(
+            "{0}\n\n{1}",
+            t_a(
+                "about",
+                hashmap! {
+                    "version" => snapshot.version.to_string(),
+                    "log_level" => config.app.log_level.clone(),
+                    "default_locale" => i18n.default_locale().to_string(),
+                },
+            ),
+            utils::gen_about_stats(&snapshot, &i18n)
+        )))
+        .await?;
+
+    Ok(())
+}
@@ -10,23 +10,42 @@
 
 use std::time::Duration;
 
-use ferogram::{
-    Context, Result, Router, filter, handler,
-    utils::{bytes_to_string, split_btns_into_columns},
-};
+use async_trait::async_trait;
+use chrono::Utc;
+use ferogram::{Context, Result, Router, filter, handler, utils::split_btns_into_columns};
 use grammers_client::{
-    InputMessage, button, reply_markup,
-    types::{CallbackQuery, InlineQuery, inline},
+    InputMessage,
+    button::{self, Inline},
+    grammers_tl_types as tl, reply_markup,
+    types::{CallbackQuery, InlineQuery, Message, inline},
 };
 use maplit::hashmap;
 use rust_anilist::models::{Anime, Format, RelationType};
 
 use crate::{
-    resources::{AniList, I18n},
-    utils::{self, gen_char_list, gen_pagination_buttons, remove_html, shorten_text},
+    Config,
+    commands::{HelpCategory, HelpEntry},
+    models::{ChatSettings, EntryKind, MediaType, User},
+    plugins::{favorites, genre, history, inline::should_search, list, settings},
+    resources::{
+        AniList, Cards, Database, Debounce, I18n, SearchStash,
+        anilist::{FetchError, characters},
+        search_stash::{SearchResult, StashedSearch},
+    },
+    utils::{
+        self, CardResources, Resolver, SearchQuery, SearchSort, StartPayload, gen_char_list,
+        gen_episode_list, gen_links_list, gen_recommendation_list, gen_signed_pagination_buttons,
+        gen_studio_list, next_episode_countdown, remove_html, shorten_text,
+    },
 };
 
-const ANILIST_BANNER_URL: &str = "https://img.anili.st/media/";
+/// The link of the AniList banners of the animes, followed by their ID.
+pub const ANILIST_BANNER_URL: &str = "https://img.anili.st/media/";
+
+/// The commands of the plugin, for `/help`.
+pub const HELP: &[HelpEntry] = &[HelpEntry::new("anime", HelpCategory::Search)
+    .aliases(&["a"])
+    .inline("!a")];
 
 /// The plugin setup.
 pub fn setup(router: Router) -> Router {
@@ -37,113 +56,373 @@ pub fn setup(router: Router) -> Router {
             )
             .then(anime),
         )
-        .register(handler::callback_query(filter::regex(r"^anime (\d+) (\d+)")).then(anime))
+        .register(handler::callback_query(filter::regex(r"^anime (\d+) (\d+)")).then(anime_result))
         .register(
             handler::callback_query(filter::regex(
-                r"^anime (studios|episodes|staff|chars|tags|links) (\d+) (\d+)",
+                r"^anime (studios|episodes|staff|chars|tags|links|recs) (\d+) (\d+)",
             ))
             .then(anime_info),
         )
+        .register(
+            handler::callback_query(filter::regex(r"^anime sort (\w+) (\w+) (\d+) \S+$"))
+                .then(anime_sort),
+        )
+        .register(
+            handler::callback_query(filter::regex(r"^anime group (\w+) (\d+) (\d+) \S+$"))
+                .then(anime_group),
+        )
         .register(handler::inline_query(filter::regex(r"^[\.!]?a (.+)")).then(anime_inline))
 }
 
 /// The anime command handler.
-async fn anime(ctx: Context, i18n: I18n, ani: AniList) -> Result<()> {
-    let t = |key: &str| i18n.translate(key);
-    let t_a = |key: &str, args| i18n.translate_with_args(key, args);
+async fn anime(
+    ctx: Context,
+    message: Message,
+    i18n: I18n,
+    ani: AniList,
+    cards: Cards,
+    stash: SearchStash,
+    settings: ChatSettings,
+) -> Result<()> {
+    let resources = CardResources {
+        cards: &cards,
+        i18n: &i18n,
+        ani: &ani,
+    };
 
-    let text = if ctx.is_callback_query() {
-        ctx.query()
-    } else {
-        ctx.text()
+    find_anime(ctx, Some(&message), resources, &stash, &settings).await
+}
+
+/// The anime callback handler, for the buttons of the search results.
+async fn anime_result(
+    ctx: Context,
+    i18n: I18n,
+    ani: AniList,
+    cards: Cards,
+    stash: SearchStash,
+    settings: ChatSettings,
+) -> Result<()> {
+    let resources = CardResources {
+        cards: &cards,
+        i18n: &i18n,
+        ani: &ani,
+    };
+
+    find_anime(ctx, None, resources, &stash, &settings).await
+}
+
+/// Finds the animes of a command or of a button of its results.
+async fn find_anime(
+    ctx: Context,
+    command: Option<&Message>,
+    resources: CardResources<'_>,
+    stash: &SearchStash,
+    settings: &ChatSettings,
+) -> Result<()> {
+    let CardResources { i18n, ani, .. } = resources;
+    let resolver = AnimeResolver {
+        resources,
+        stash,
+        settings,
+    };
+
+    utils::resolve_or_disambiguate(
+        ctx,
+        command,
+        &resolver,
+        ani,
+        i18n,
+        settings.clean_service_messages,
+    )
+    .await
+}
+
+/// Finds the animes of the command, see [`utils::resolve_or_disambiguate`].
+struct AnimeResolver<'a> {
+    /// The resources the cards are sent with, the database for the viewer and their
+    /// preferences.
+    resources: CardResources<'a>,
+    /// The stash of the searches, for the sort buttons.
+    stash: &'a SearchStash,
+    /// The settings of the chat.
+    settings: &'a ChatSettings,
+}
+
+#[async_trait]
+impl Resolver for AnimeResolver<'_> {
+    type Entry = Anime;
+
+    fn usage_key(&self) -> &'static str {
+        "anime_usage"
     }
-    .unwrap();
-    let mut args = text.split_whitespace().skip(1).collect::<Vec<_>>();
 
-    let sender = ctx.sender().unwrap();
+    fn inline_query(&self) -> &'static str {
+        "!a"
+    }
+
+    async fn fetch(&self, arg: &str) -> Option<std::result::Result<Anime, FetchError>> {
+        let reference = utils::parse_media_reference(arg, MediaType::Anime)?;
+
+        Some(self.resources.ani.get_anime_by_reference(reference).await)
+    }
+
+    async fn refetch(&self, anime: Anime) -> Anime {
+        self.resources
+            .ani
+            .get_anime(anime.id)
+            .await
+            .unwrap_or(anime)
+    }
+
+    async fn search(&self, query: &SearchQuery) -> Option<Vec<Anime>> {
+        self.resources
+            .ani
+            .search_anime_sorted(&query.terms, query.sort, 1, 6)
+            .await
+            .map(|mut result| {
+                result.retain(|anime| self.settings.allow_adult || !anime.is_adult);
+                result
+            })
+    }
+
+    async fn send(&self, anime: Anime, ctx: Context) -> Result<()> {
+        let viewer = list::get_viewer(self.resources.cards.db(), ctx.sender().unwrap().id()).await;
+
+        send_anime_info(anime, ctx, self.resources, self.settings, viewer.as_ref()).await
+    }
 
-    if let Some(query) = ctx.callback_query() {
-        let sender_id = args.pop().unwrap().parse::<i64>().unwrap();
+    async fn gen_result_buttons(
+        &self,
+        result: Vec<Anime>,
+        query: &SearchQuery,
+        sender_id: i64,
+    ) -> Vec<Vec<Inline>> {
+        let results = result
+            .iter()
+            .map(|anime| search_result(anime, self.settings))
+            .collect::<Vec<_>>();
+        let stash_id = self
+            .stash
+            .insert(StashedSearch {
+                query: query.clone(),
+                results: results.clone(),
+            })
+            .await;
+        let group = !self.resources.cards.db().is_healthy()
+            || User::wants_grouped_results(self.resources.cards.db().pool(), sender_id).await;
+
+        utils::gen_search_buttons(
+            "anime",
+            &results,
+            &stash_id,
+            query.sort,
+            sender_id,
+            group,
+            self.resources.i18n,
+        )
+    }
+}
+
+/// The anime search sort callback handler.
+async fn anime_sort(
+    query: CallbackQuery,
+    db: Database,
+    i18n: I18n,
+    ani: AniList,
+    stash: SearchStash,
+    settings: ChatSettings,
+) -> Result<()> {
+    let t = |key: &str| i18n.translate(key);
+    let t_a = |key: &str, args| i18n.translate_with_args(key, args);
+
+    let data = utils::callback_data(&query);
+    let args = data.split_whitespace().skip(2).collect::<Vec<_>>();
+
+    let stash_id = args[0];
+    let sort = SearchSort::parse(args[1]).unwrap_or_default();
+    let sender_id = args[2].parse::<i64>().unwrap();
 
-        if sender.id() != sender_id {
+    if query.sender().id() != sender_id {
+        utils::send_with_retry(|| {
             query
                 .answer()
                 .cache_time(Duration::from_secs(120))
                 .alert(t("not_allowed"))
                 .send()
-                .await?;
-            return Ok(());
+        })
+        .await?;
+        return Ok(());
+    }
+
+    let Some(mut search) = stash.get(stash_id).await else {
+        utils::send_with_retry(|| query.answer().alert(t("search_expired")).send()).await?;
+        return Ok(());
+    };
+    search.query.sort = sort;
+
+    match ani
+        .search_anime_sorted(&search.query.terms, search.query.sort, 1, 6)
+        .await
+        .map(|mut result| {
+            result.retain(|anime| settings.allow_adult || !anime.is_adult);
+            result
+        }) {
+        Some(result) if !result.is_empty() => {
+            search.results = result
+                .iter()
+                .map(|anime| search_result(anime, &settings))
+                .collect();
+            stash.replace(stash_id, search.clone()).await;
+
+            let group = !db.is_healthy() || User::wants_grouped_results(db.pool(), sender_id).await;
+            let buttons = utils::gen_search_buttons(
+                "anime",
+                &search.results,
+                stash_id,
+                search.query.sort,
+                sender_id,
+                group,
+                &i18n,
+            );
+
+            let message = InputMessage::html(t_a(
+                "search_results",
+                hashmap! { "search" => utils::escape_html(search.query.terms) },
+            ))
+            .reply_markup(&reply_markup::inline(buttons));
+            utils::send_with_retry(|| query.answer().edit(message.clone())).await?;
+        }
+        _ => {
+            utils::send_with_retry(|| query.answer().alert(t("no_results")).send()).await?;
         }
     }
 
-    if args.is_empty() {
-        ctx.reply(
-            InputMessage::html(t("anime_usage")).reply_markup(&reply_markup::inline(vec![vec![
-                button::switch_inline(t("search_btn"), "!a "),
-            ]])),
-        )
+    Ok(())
+}
+
+/// The anime search franchise group callback handler.
+async fn anime_group(query: CallbackQuery, i18n: I18n, stash: SearchStash) -> Result<()> {
+    let t = |key: &str| i18n.translate(key);
+    let t_a = |key: &str, args| i18n.translate_with_args(key, args);
+
+    let data = utils::callback_data(&query);
+    let args = data.split_whitespace().skip(2).collect::<Vec<_>>();
+
+    let stash_id = args[0];
+    let index = args[1].parse::<usize>().unwrap();
+    let sender_id = args[2].parse::<i64>().unwrap();
+
+    if query.sender().id() != sender_id {
+        utils::send_with_retry(|| {
+            query
+                .answer()
+                .cache_time(Duration::from_secs(120))
+                .alert(t("not_allowed"))
+                .send()
+        })
         .await?;
-    } else {
-        if let Ok(id) = args[0].parse::<i64>() {
-            if let Ok(anime) = ani.get_anime(id).await {
-                send_anime_info(anime, ctx, &i18n).await?;
-            } else {
-                ctx.reply(InputMessage::html(t("not_found"))).await?;
-            }
-        } else {
-            let title = args.join(" ");
+        return Ok(());
+    }
 
-            if let Some(result) = ani.search_anime(&title, 1, 6).await {
-                if result.is_empty() {
-                    ctx.reply(InputMessage::html(t("no_results_text")).reply_markup(
-                        &reply_markup::inline(vec![vec![button::switch_inline(
-                            t("search_again_btn"),
-                            format!("!a {}", title),
-                        )]]),
-                    ))
-                    .await?;
-                    return Ok(());
-                } else if result.len() == 1 {
-                    let anime = ani.get_anime(result[0].id).await.unwrap_or_default();
-                    return send_anime_info(anime, ctx, &i18n).await;
-                }
-
-                let buttons = result
-                    .into_iter()
-                    .map(|anime| {
-                        vec![button::inline(
-                            if anime.is_adult { "🔞 " } else { "" }.to_string()
-                                + &anime.title.romaji(),
-                            format!("anime {0} {1}", anime.id, sender.id()),
-                        )]
-                    })
-                    .collect::<Vec<_>>();
+    let expanded = stash.get(stash_id).await.and_then(|search| {
+        utils::gen_franchise_buttons("anime", &search, stash_id, index, sender_id, &i18n)
+    });
 
-                ctx.reply(
-                    InputMessage::html(t_a("search_results", hashmap! { "search" => title }))
-                        .reply_markup(&reply_markup::inline(buttons)),
-                )
-                .await?;
-            } else {
-                ctx.reply(InputMessage::html(t("no_results_text")).reply_markup(
-                    &reply_markup::inline(vec![vec![button::switch_inline(
-                        t("search_again_btn"),
-                        format!("!a {}", title),
-                    )]]),
-                ))
-                .await?;
-            }
-        }
+    if let Some((title, buttons)) = expanded {
+        let message = InputMessage::html(t_a(
+            "franchise_results",
+            hashmap! { "title" => utils::escape_html(title) },
+        ))
+        .reply_markup(&reply_markup::inline(buttons));
+        utils::send_with_retry(|| query.answer().edit(message.clone())).await?;
+    } else {
+        utils::send_with_retry(|| query.answer().alert(t("search_expired")).send()).await?;
     }
 
     Ok(())
 }
 
+/// Converts an anime into a stashable search result.
+fn search_result(anime: &Anime, settings: &ChatSettings) -> SearchResult {
+    SearchResult {
+        id: anime.id,
+        title: utils::preferred_title(&anime.title, settings),
+        is_adult: anime.is_adult,
+    }
+}
+
 /// Sends the anime info to the user.
-async fn send_anime_info(anime: Anime, ctx: Context, i18n: &I18n) -> Result<()> {
+///
+/// Adult animes are replaced with a notice unless the chat allows adult content, and
+/// authenticated viewers get their list status and buttons to update it.
+///
+/// # Arguments
+///
+/// * `anime` - The anime to send.
+/// * `ctx` - The context of the update.
+/// * `resources` - The resources of the card, the AniList one for the viewer's list status.
+/// * `settings` - The settings of the chat.
+/// * `viewer` - The database row of the sender, if registered.
+pub async fn send_anime_info(
+    anime: Anime,
+    ctx: Context,
+    resources: CardResources<'_>,
+    settings: &ChatSettings,
+    viewer: Option<&User>,
+) -> Result<()> {
+    send_anime_info_with(anime, ctx, resources, settings, viewer, Vec::new()).await
+}
+
+/// Sends the anime info to the user, with an extra row of buttons, see [`send_anime_info`].
+///
+/// # Arguments
+///
+/// * `anime` - The anime to send.
+/// * `ctx` - The context of the update.
+/// * `resources` - The resources of the card, the AniList one for the viewer's list status.
+/// * `settings` - The settings of the chat.
+/// * `viewer` - The database row of the sender, if registered.
+/// * `extra` - The extra row of buttons, shown last unless it is empty.
+pub async fn send_anime_info_with(
+    anime: Anime,
+    ctx: Context,
+    resources: CardResources<'_>,
+    settings: &ChatSettings,
+    viewer: Option<&User>,
+    extra: Vec<Inline>,
+) -> Result<()> {
+    let CardResources { cards, i18n, ani } = resources;
     let t = |key: &str| i18n.translate(key);
 
-    let text = utils::gen_anime_info(&anime, i18n);
+    if anime.is_adult && !settings.allow_adult {
+        return settings::send_adult_content_hidden(ctx, i18n).await;
+    }
+
+    let title = utils::preferred_title(&anime.title, settings);
+    history::record_view(&ctx, cards.db(), EntryKind::Anime, anime.id, &title).await;
+
+    let mut text = utils::gen_anime_info(&anime, i18n, settings);
+    if let Some(status) = list::gen_viewer_status(
+        ani,
+        viewer,
+        MediaType::Anime,
+        anime.id,
+        anime.episodes.map(i64::from),
+        i18n,
+    )
+    .await
+    {
+        text.push_str(&status);
+    }
+    if let Some(fetched_at) = ani.stale_since(MediaType::Anime, anime.id).await {
+        text.push_str(&format!(
+            "\n{}\n",
+            i18n.translate_with_args(
+                "stale_data",
+                hashmap! { "time" => utils::humanize_ago(fetched_at, i18n, &Utc) },
+            )
+        ));
+    }
     let image_url = ANILIST_BANNER_URL.to_owned() + &anime.id.to_string();
     let mut buttons = Vec::new();
 
@@ -152,52 +431,73 @@ async fn send_anime_info(anime: Anime, ctx: Context, i18n: &I18n) -> Result<()>
     if anime.studios.is_some() {
         buttons.push(button::inline(
             t("studios_btn"),
-            format!("anime studios {0} {1}", anime.id, sender.id()),
+            utils::sign_callback(&format!("anime studios {0} {1}", anime.id, sender.id())),
         ));
     }
 
     if matches!(anime.format, Format::Movie) {
         buttons.push(button::inline(
             t("watch_btn"),
-            format!("anime episodes {0} {1}", anime.id, sender.id()),
+            utils::sign_callback(&format!("anime episodes {0} {1}", anime.id, sender.id())),
         ));
     } else if anime.episodes.is_some() {
         buttons.push(button::inline(
             t("episodes_btn"),
-            format!("anime episodes {0} {1}", anime.id, sender.id()),
+            utils::sign_callback(&format!("anime episodes {0} {1}", anime.id, sender.id())),
+        ));
+    }
+
+    if anime.next_airing_episode.is_some() {
+        buttons.push(button::inline(
+            t("follow_btn"),
+            format!("follow {0} {1}", anime.id, sender.id()),
         ));
     }
 
     if anime.staff.is_some() {
         buttons.push(button::inline(
             t("staff_btn"),
-            format!("anime staff {0} {1}", anime.id, sender.id()),
+            utils::sign_callback(&format!("anime staff {0} {1}", anime.id, sender.id())),
         ));
     }
 
     if anime.characters().is_ok() {
         buttons.push(button::inline(
             t("characters_btn"),
-            format!("anime chars {0} {1}", anime.id, sender.id()),
+            utils::sign_callback(&format!("anime chars {0} {1}", anime.id, sender.id())),
         ));
     }
 
     if anime.tags.as_ref().is_some_and(|tags| !tags.is_empty()) {
         buttons.push(button::inline(
             t("tags_btn"),
-            format!("anime tags {0} {1}", anime.id, sender.id()),
+            utils::sign_callback(&format!("anime tags {0} {1}", anime.id, sender.id())),
         ));
     }
 
     if anime.external_links.is_some() {
         buttons.push(button::inline(
             t("links_btn"),
-            format!("anime links {0} {1}", anime.id, sender.id()),
+            utils::sign_callback(&format!("anime links {0} {1}", anime.id, sender.id())),
         ));
     }
 
+    buttons.push(button::inline(
+        t("recommendations_btn"),
+        utils::sign_callback(&format!("anime recs {0} {1}", anime.id, sender.id())),
+    ));
+
     let mut buttons = split_btns_into_columns(buttons, 2);
 
+    if viewer.is_some_and(|viewer| viewer.anilist_token.is_some()) {
+        buttons.push(list::gen_list_buttons(
+            MediaType::Anime,
+            anime.id,
+            sender.id(),
+            i18n,
+        ));
+    }
+
     if let Ok(relations) = anime.relations() {
         let mut relations_buttons = Vec::new();
 
@@ -213,13 +513,13 @@ async fn send_anime_info(anime: Anime, ctx: Context, i18n: &I18n) -> Result<()>
         if let Some(prequel) = prequel {
             relations_buttons.push(button::inline(
                 t("previous_btn"),
-                format!("anime {0} {1}", prequel.media().id(), sender.id()),
+                utils::sign_callback(&format!("anime {0} {1}", prequel.media().id(), sender.id())),
             ));
         }
         if let Some(sequel) = sequel {
             relations_buttons.push(button::inline(
                 t("next_btn"),
-                format!("anime {0} {1}", sequel.media().id(), sender.id()),
+                utils::sign_callback(&format!("anime {0} {1}", sequel.media().id(), sender.id())),
             ));
         }
 
@@ -228,34 +528,34 @@ async fn send_anime_info(anime: Anime, ctx: Context, i18n: &I18n) -> Result<()>
         }
     }
 
-    let markup = reply_markup::inline(buttons);
+    let favorite =
+        favorites::gen_favorite_button(&ctx, cards.favorites(), EntryKind::Anime, anime.id, i18n)
+            .await;
+    let share = utils::gen_share_button(StartPayload::Anime(anime.id), i18n);
+    let row = favorite.into_iter().chain(share).collect::<Vec<_>>();
+    if !row.is_empty() {
+        buttons.push(row);
+    }
 
-    if ctx.is_callback_query() {
-        ctx.edit(
-            InputMessage::html(format!("<a href=\"{}\">⁠</a>", image_url) + &text)
-                .link_preview(true)
-                .photo_url(image_url)
-                .reply_markup(&markup),
-        )
-        .await?;
-    } else {
-        ctx.reply(
-            InputMessage::html(text)
-                .photo_url(image_url)
-                .reply_markup(&markup),
-        )
-        .await?;
+    if !extra.is_empty() {
+        buttons.push(extra);
     }
 
+    utils::send_card(&ctx, cards.files(), &text, Some(&image_url), buttons, true).await?;
+
     Ok(())
 }
 
 /// The anime info handler.
-async fn anime_info(query: CallbackQuery, i18n: I18n, ani: AniList) -> Result<()> {
+async fn anime_info(
+    query: CallbackQuery,
+    i18n: I18n,
+    ani: AniList,
+    settings: ChatSettings,
+) -> Result<()> {
     let t = |key: &str| i18n.translate(key);
 
-    let data = query.data();
-    let args = bytes_to_string(data)
+    let args = utils::callback_data(&query)
         .split_whitespace()
         .skip(1)
         .map(String::from)
@@ -268,139 +568,346 @@ async fn anime_info(query: CallbackQuery, i18n: I18n, ani: AniList) -> Result<()
     let sender = query.sender();
 
     if sender.id() != sender_id {
-        query
-            .answer()
-            .cache_time(Duration::from_secs(120))
-            .alert(t("not_allowed"))
-            .send()
-            .await?;
+        utils::send_with_retry(|| {
+            query
+                .answer()
+                .cache_time(Duration::from_secs(120))
+                .alert(t("not_allowed"))
+                .send()
+        })
+        .await?;
         return Ok(());
     }
 
-    if let Ok(mut anime) = ani.get_anime(anime_id).await {
-        let mut text = format!(
-            "<code>{0}</code> | <b>{1}</b>\n\n",
-            anime.id,
-            anime.title.romaji()
-        );
-
-        match info {
-            "studios" => {}
-            "episodes" => {}
-            "staff" => {}
-            "chars" => {
-                let page = args
-                    .get(3)
-                    .unwrap_or(&1.to_string())
-                    .parse::<usize>()
-                    .unwrap();
-                let characters = anime.characters().unwrap_or_default();
-
-                let per_page = 10;
-                let max_pages = (characters.len() as f32 / 15f32).round() as usize + 1;
-
-                if characters.is_empty() {
-                    query.answer().alert(t("not_available")).send().await?;
-                    return Ok(());
-                }
-
-                text.push_str(&gen_char_list(&characters, page, per_page, &i18n));
-                let buttons = gen_pagination_buttons(
-                    &format!("anime chars {0} {1}", anime_id, sender_id),
+    let overview = ani.get_anime_overview(anime_id).await;
+    if let Err(FetchError::RateLimited(retry_after)) = overview {
+        utils::send_with_retry(|| {
+            query
+                .answer()
+                .alert(remove_html(utils::rate_limited_text(retry_after, &i18n)))
+                .send()
+        })
+        .await?;
+        return Ok(());
+    }
+
+    let Ok(overview) = overview else {
+        utils::send_with_retry(|| query.answer().alert(t("entry_unavailable")).send()).await?;
+        return Ok(());
+    };
+
+    let mut text = format!(
+        "<code>{0}</code> | <b>{1}</b>\n\n",
+        overview.id,
+        utils::escape_html(utils::preferred_title(&overview.title, &settings))
+    );
+
+    match info {
+        "studios" => {
+            let page = args
+                .get(3)
+                .unwrap_or(&1.to_string())
+                .parse::<usize>()
+                .unwrap();
+            let studios = ani
+                .get_anime(anime_id)
+                .await
+                .ok()
+                .and_then(|anime| anime.studios)
+                .unwrap_or_default();
+
+            let per_page = 15;
+            let max_pages = studios.len().div_ceil(per_page);
+
+            if studios.is_empty() {
+                utils::send_with_retry(|| {
+                    query
+                        .answer()
+                        .cache_time(Duration::from_secs(120))
+                        .alert(t("not_available"))
+                        .send()
+                })
+                .await?;
+                return Ok(());
+            }
+
+            text.push_str(&gen_studio_list(&studios, page, per_page, &i18n));
+
+            let buttons = studios
+                .iter()
+                .skip((page - 1) * per_page)
+                .take(per_page)
+                .map(|studio| {
+                    button::inline(
+                        studio.name.clone(),
+                        format!("studio {0} {1}", studio.id, sender_id),
+                    )
+                })
+                .collect::<Vec<_>>();
+            let mut buttons = split_btns_into_columns(buttons, 2);
+            if max_pages > 1 {
+                buttons.push(gen_signed_pagination_buttons(
+                    &format!("anime studios {0} {1}", anime_id, sender_id),
                     page,
                     max_pages,
-                );
+                ));
+            }
+            buttons.push(vec![button::inline(
+                t("back_btn"),
+                utils::sign_callback(&format!("anime {0} {1}", anime_id, sender_id)),
+            )]);
 
-                query
-                    .answer()
-                    .edit(
-                        InputMessage::html(text).reply_markup(&reply_markup::inline(vec![
-                            buttons,
-                            vec![button::inline(
-                                t("back_btn"),
-                                format!("anime {0} {1}", anime_id, sender_id),
-                            )],
-                        ])),
-                    )
-                    .await?;
+            utils::edit_card_page(&query, &text, buttons).await?;
+        }
+        "episodes" => {
+            let page = args
+                .get(3)
+                .unwrap_or(&1.to_string())
+                .parse::<usize>()
+                .unwrap();
+            let episodes = ani.get_episodes(anime_id).await.unwrap_or_default();
+
+            let per_page = 15;
+            let max_pages = episodes.len().div_ceil(per_page);
+
+            if episodes.is_empty() {
+                utils::send_with_retry(|| {
+                    query
+                        .answer()
+                        .cache_time(Duration::from_secs(120))
+                        .alert(t("not_available"))
+                        .send()
+                })
+                .await?;
+                return Ok(());
             }
-            "tags" => {
-                if let Some(tags) = anime.tags.as_mut().take_if(|tags| !tags.is_empty()) {
-                    let tags = tags
-                        .iter()
-                        .map(|tag| {
-                            if tag.is_adult {
-                                format!("<s>{}</s>", tag.name)
-                            } else if tag.is_general_spoiler || tag.is_media_spoiler {
-                                format!("<details>{}</details>", tag.name)
-                            } else {
-                                tag.name.clone()
-                            }
-                        })
-                        .collect::<Vec<_>>()
-                        .join(", ");
-                    text.push_str(&format!("🏷 | <b>{0}</b>: <i>{1}</i>", t("tags"), tags));
 
+            let next = ani
+                .get_anime(anime_id)
+                .await
+                .ok()
+                .and_then(|anime| next_episode_countdown(&anime, &i18n));
+            text.push_str(&gen_episode_list(
+                &episodes,
+                next.as_ref(),
+                page,
+                per_page,
+                &i18n,
+            ));
+
+            let mut buttons = Vec::new();
+            if max_pages > 1 {
+                buttons.push(gen_signed_pagination_buttons(
+                    &format!("anime episodes {0} {1}", anime_id, sender_id),
+                    page,
+                    max_pages,
+                ));
+            }
+            buttons.push(vec![button::inline(
+                t("back_btn"),
+                utils::sign_callback(&format!("anime {0} {1}", anime_id, sender_id)),
+            )]);
+
+            utils::edit_card_page(&query, &text, buttons).await?;
+        }
+        "staff" => {}
+        "chars" => {
+            let page = args
+                .get(3)
+                .unwrap_or(&1.to_string())
+                .parse::<u16>()
+                .unwrap();
+            let characters = ani
+                .get_media_characters(
+                    anime_id,
+                    MediaType::Anime,
+                    page,
+                    characters::PER_PAGE,
+                    settings.voice_actor_language,
+                )
+                .await
+                .filter(|characters| !characters.characters.is_empty());
+
+            let Some(characters) = characters else {
+                utils::send_with_retry(|| {
                     query
                         .answer()
-                        .edit(
-                            InputMessage::html(text).reply_markup(&reply_markup::inline(vec![
-                                vec![button::inline(
-                                    t("back_btn"),
-                                    format!("anime {0} {1}", anime_id, sender_id),
-                                )],
-                            ])),
-                        )
-                        .await?;
-                } else {
+                        .cache_time(Duration::from_secs(120))
+                        .alert(t(if page > 1 {
+                            "page_not_found"
+                        } else {
+                            "not_available"
+                        }))
+                        .send()
+                })
+                .await?;
+                return Ok(());
+            };
+
+            text.push_str(&gen_char_list(&characters.characters, &i18n));
+
+            let mut buttons = Vec::new();
+            if characters.last_page > 1 {
+                buttons.push(gen_signed_pagination_buttons(
+                    &format!("anime chars {0} {1}", anime_id, sender_id),
+                    page as usize,
+                    characters.last_page,
+                ));
+            }
+            buttons.push(vec![button::inline(
+                t("back_btn"),
+                utils::sign_callback(&format!("anime {0} {1}", anime_id, sender_id)),
+            )]);
+
+            utils::edit_card_page(&query, &text, buttons).await?;
+        }
+        "tags" => {
+            let mut anime = ani.get_anime(anime_id).await.unwrap_or_default();
+            if let Some(tags) = anime.tags.as_mut().take_if(|tags| !tags.is_empty()) {
+                let tags = tags
+                    .iter()
+                    .map(|tag| {
+                        if tag.is_adult {
+                            format!("<s>{}</s>", tag.name)
+                        } else if tag.is_general_spoiler || tag.is_media_spoiler {
+                            format!("<details>{}</details>", tag.name)
+                        } else {
+                            tag.name.clone()
+                        }
+                    })
+                    .collect::<Vec<_>>()
+                    .join(", ");
+                text.push_str(&format!("🏷 | <b>{0}</b>: <i>{1}</i>", t("tags"), tags));
+
+                // Only the tags safe to show become buttons.
+                let mut buttons = genre::gen_tag_buttons(
+                    anime
+                        .tags
+                        .iter()
+                        .flatten()
+                        .filter(|tag| {
+                            !tag.is_adult && !tag.is_general_spoiler && !tag.is_media_spoiler
+                        })
+                        .map(|tag| tag.name.as_str()),
+                    MediaType::Anime,
+                    sender_id,
+                );
+                buttons.push(vec![button::inline(
+                    t("back_btn"),
+                    utils::sign_callback(&format!("anime {0} {1}", anime_id, sender_id)),
+                )]);
+
+                utils::edit_card_page(&query, &text, buttons).await?;
+            } else {
+                utils::send_with_retry(|| {
                     query
                         .answer()
                         .cache_time(Duration::from_secs(120))
                         .alert(t("not_available"))
                         .send()
-                        .await?;
-                }
+                })
+                .await?;
             }
-            "links" => {
-                text.push_str(&format!("🖇 <b>{}</b>:\n", t("links")));
-
-                if let Some(links) = anime.external_links.as_ref() {
-                    for link in links.iter().filter(|l| l.is_disabled.is_none()) {
-                        text.push_str(&format!(
-                            "🔗 | <a href=\"{}\">{}</a>\n",
-                            link.url, link.site
-                        ));
-                    }
-                }
-
-                text.push_str(&format!("🔗 | <a href=\"{}\">AniList</a>\n", anime.url));
-                if let Some(id) = anime.id_mal {
-                    text.push_str(&format!(
-                        "🔗 | <a href=\"https://myanimelist.net/manga/{}\">MyAnimeList</a>",
-                        id
-                    ));
-                }
+        }
+        "recs" => {
+            let page = args
+                .get(3)
+                .unwrap_or(&1.to_string())
+                .parse::<u16>()
+                .unwrap();
+            let recommendations = ani
+                .get_recommendations(anime_id, page)
+                .await
+                .filter(|recommendations| !recommendations.recommendations.is_empty());
 
-                query
-                    .answer()
-                    .edit(
-                        InputMessage::html(text).reply_markup(&reply_markup::inline(vec![vec![
-                            button::inline(
-                                t("back_btn"),
-                                format!("anime {0} {1}", anime_id, sender_id),
-                            ),
-                        ]])),
+            let Some(recommendations) = recommendations else {
+                utils::send_with_retry(|| {
+                    query
+                        .answer()
+                        .cache_time(Duration::from_secs(120))
+                        .alert(t(if page > 1 {
+                            "page_not_found"
+                        } else {
+                            "not_available"
+                        }))
+                        .send()
+                })
+                .await?;
+                return Ok(());
+            };
+
+            text.push_str(&gen_recommendation_list(
+                &recommendations.recommendations,
+                &i18n,
+            ));
+
+            let buttons = recommendations
+                .recommendations
+                .iter()
+                .map(|recommendation| {
+                    button::inline(
+                        shorten_text(&recommendation.title, 40),
+                        utils::sign_callback(&format!(
+                            "{0} {1} {2}",
+                            match recommendation.media_type {
+                                MediaType::Anime => "anime",
+                                MediaType::Manga => "manga",
+                            },
+                            recommendation.id,
+                            sender_id
+                        )),
                     )
-                    .await?;
+                })
+                .collect::<Vec<_>>();
+            let mut buttons = split_btns_into_columns(buttons, 2);
+            if recommendations.last_page > 1 {
+                buttons.push(gen_signed_pagination_buttons(
+                    &format!("anime recs {0} {1}", anime_id, sender_id),
+                    page as usize,
+                    recommendations.last_page,
+                ));
             }
-            _ => {
+            buttons.push(vec![button::inline(
+                t("back_btn"),
+                utils::sign_callback(&format!("anime {0} {1}", anime_id, sender_id)),
+            )]);
+
+            utils::edit_card_page(&query, &text, buttons).await?;
+        }
+        "links" => {
+            let Some(links) = ani.get_media_links(anime_id).await else {
+                utils::send_with_retry(|| {
+                    query
+                        .answer()
+                        .cache_time(Duration::from_secs(120))
+                        .alert(t("not_available"))
+                        .send()
+                })
+                .await?;
+                return Ok(());
+            };
+
+            text.push_str(&gen_links_list(&links, MediaType::Anime, &i18n));
+
+            utils::edit_card_page(
+                &query,
+                &text,
+                vec![vec![button::inline(
+                    t("back_btn"),
+                    utils::sign_callback(&format!("anime {0} {1}", anime_id, sender_id)),
+                )]],
+            )
+            .await?;
+        }
+        _ => {
+            utils::send_with_retry(|| {
                 query
                     .answer()
                     .cache_time(Duration::from_secs(120))
                     .alert(t("not_implemented"))
                     .send()
-                    .await?
-            }
+            })
+            .await?;
         }
     }
 
@@ -408,7 +915,14 @@ async fn anime_info(query: CallbackQuery, i18n: I18n, ani: AniList) -> Result<()
 }
 
 /// The anime inline query handler.
-async fn anime_inline(query: InlineQuery, i18n: I18n, ani: AniList) -> Result<()> {
+async fn anime_inline(
+    query: InlineQuery,
+    i18n: I18n,
+    ani: AniList,
+    settings: ChatSettings,
+    config: Config,
+    debounce: Debounce<i64>,
+) -> Result<()> {
     let t = |key: &str| i18n.translate(key);
 
     let arg = query
@@ -417,12 +931,28 @@ async fn anime_inline(query: InlineQuery, i18n: I18n, ani: AniList) -> Result<()
         .skip(1)
         .collect::<Vec<_>>()
         .join(" ");
+
+    if !should_search(&query, &arg, &config, &debounce, &i18n).await? {
+        return Ok(());
+    }
+
     let offset = query.offset().parse::<u16>().unwrap_or(1);
     let mut results = Vec::new();
 
-    if let Some(result) = ani.search_anime(&arg, offset, 10).await {
+    let search = utils::parse_search_query(&arg);
+    if let Some((MediaType::Anime, mal_id)) = utils::parse_mal_reference(&arg, MediaType::Anime) {
+        // A MyAnimeList reference has a single result, so there is no next page.
+        let anime = match offset {
+            1 => ani.get_anime_by_mal_id(mal_id).await.ok(),
+            _ => None,
+        };
+        results.extend(anime.map(|anime| gen_anime_article(&query, anime, &i18n, &settings)));
+    } else if let Some(result) = ani
+        .search_anime_sorted(&search.terms, search.sort, offset, 10)
+        .await
+    {
         for anime in result {
-            let article = gen_anime_article(&query, anime, &i18n);
+            let article = gen_anime_article(&query, anime, &i18n, &settings);
             results.push(article);
         }
     }
@@ -439,7 +969,8 @@ async fn anime_inline(query: InlineQuery, i18n: I18n, ani: AniList) -> Result<()
                         )]],
                     )),
                 )
-                .description(t("click_for_more_info")),
+                .description(t("click_for_more_info"))
+                .into(),
             );
         } else {
             results.push(
@@ -452,7 +983,8 @@ async fn anime_inline(query: InlineQuery, i18n: I18n, ani: AniList) -> Result<()
                         )]]),
                     ),
                 )
-                .description(t("click_for_more_info")),
+                .description(t("click_for_more_info"))
+                .into(),
             );
         }
     }
@@ -460,6 +992,7 @@ async fn anime_inline(query: InlineQuery, i18n: I18n, ani: AniList) -> Result<()
     query
         .answer(results)
         .cache_time(120)
+        .private()
         .next_offset((offset + 1).to_string())
         .send()
         .await?;
@@ -467,36 +1000,53 @@ async fn anime_inline(query: InlineQuery, i18n: I18n, ani: AniList) -> Result<()
     Ok(())
 }
 
-/// Generates an inline query article for an anime.
-fn gen_anime_article(query: &InlineQuery, anime: Anime, i18n: &I18n) -> inline::query::Article {
+/// Generates an inline query result for an anime, see [`utils::gen_card_result`].
+pub fn gen_anime_article(
+    query: &InlineQuery,
+    anime: Anime,
+    i18n: &I18n,
+    settings: &ChatSettings,
+) -> tl::enums::InputBotInlineResult {
+    let title = if anime.is_adult { "🔞 " } else { "" }.to_string()
+        + &utils::preferred_title(&anime.title, settings);
+
+    gen_anime_article_with_title(query, anime, title, i18n, settings)
+}
+
+/// Generates an inline query result for an anime, with a custom title.
+///
+/// Adult animes get a notice article instead unless the chat allows adult content.
+pub fn gen_anime_article_with_title(
+    query: &InlineQuery,
+    anime: Anime,
+    title: String,
+    i18n: &I18n,
+    settings: &ChatSettings,
+) -> tl::enums::InputBotInlineResult {
     let t = |key: &str| i18n.translate(key);
 
-    let text = utils::gen_anime_info(&anime, &i18n);
+    if anime.is_adult && !settings.allow_adult {
+        return settings::gen_adult_content_hidden_article(i18n).into();
+    }
+
+    let text = utils::gen_anime_info(&anime, i18n, settings);
     let image_url = ANILIST_BANNER_URL.to_owned() + &anime.id.to_string();
+    let thumb_url = anime
+        .banner
+        .clone()
+        .or(anime.cover.largest().map(String::from));
 
     let sender = query.sender();
 
-    let mut article = inline::query::Article::new(
-        if anime.is_adult { "🔞 " } else { "" }.to_string() + &anime.title.romaji(),
-        InputMessage::html(format!("<a href=\"{}\">⁠</a>", image_url) + &text)
-            .link_preview(true)
-            .reply_markup(&reply_markup::inline(vec![vec![button::inline(
-                t("load_more_btn"),
-                format!("anime {0} {1}", anime.id, sender.id()),
-            )]])),
+    utils::gen_card_result(
+        title,
+        shorten_text(remove_html(anime.description), 150),
+        &text,
+        Some(&image_url),
+        thumb_url.as_deref(),
+        (
+            t("load_more_btn"),
+            utils::sign_callback(&format!("anime {0} {1}", anime.id, sender.id())),
+        ),
     )
-    .description(shorten_text(remove_html(anime.description), 150));
-
-    let image_url = anime.banner.unwrap_or(
-        anime
-            .cover
-            .largest()
-            .map(String::from)
-            .unwrap_or(String::new()),
-    );
-    if !image_url.is_empty() {
-        article = article.thumb_url(image_url);
-    }
-
-    article
 }
@@ -9,20 +9,35 @@
 //! The auth plugin.
 
 use base64::Engine;
-use ferogram::{Result, Router, filter, handler};
+use ferogram::{Context, Result, Router, filter, handler};
 use grammers_client::{
-    InputMessage, Update, button, reply_markup,
-    types::{Chat, Message},
+    InputMessage, button, reply_markup,
+    types::{CallbackQuery, Chat, Message},
 };
 use maplit::hashmap;
 use serde::{Deserialize, Serialize};
 
 use crate::{
     Config,
+    commands::{HelpCategory, HelpEntry},
     models::{UpdateUser, User},
-    resources::{Database, I18n},
+    resources::{
+        AniList, Cards, Database, I18n, Sessions,
+        database::is_connection_error,
+        sessions::{AttemptError, AuthAttempt, token_expiry},
+    },
+    utils::{self, CardResources},
 };
 
+/// The URI AniList redirects to after the user authorizes the bot.
+const REDIRECT_URI: &str = "https://yamata-no-orochi.vercel.app/auth";
+
+/// The commands of the plugin, for `/help`.
+pub const HELP: &[HelpEntry] = &[
+    HelpEntry::new("auth", HelpCategory::Account),
+    HelpEntry::new("cancelauth", HelpCategory::Account),
+];
+
 /// The plugin setup.
 pub fn setup(router: Router) -> Router {
     router
@@ -31,16 +46,26 @@ pub fn setup(router: Router) -> Router {
                 .then(auth),
         )
         .register(
-            handler::new_update(filter::always).then(|update: Update| async move {
-                println!("{:?}", update);
-
-                Ok(())
-            }),
+            handler::new_message(
+                filter::command("cancelauth").description("Cancel a pending authentication."),
+            )
+            .then(cancel_auth),
         )
+        .register(handler::callback_query(filter::regex("^auth revoke$")).then(auth_revoke))
 }
 
 /// The auth handler.
-async fn auth(message: Message, db: Database, i18n: I18n, config: Config) -> Result<()> {
+#[allow(clippy::too_many_arguments)]
+async fn auth(
+    ctx: Context,
+    message: Message,
+    db: Database,
+    ani: AniList,
+    cards: Cards,
+    i18n: I18n,
+    config: Config,
+    sessions: Sessions,
+) -> Result<()> {
     let t = |key: &str| i18n.translate(key);
     let t_a = |key: &str, args| i18n.translate_with_args(key, args);
     let pool = db.pool();
@@ -53,45 +78,90 @@ async fn auth(message: Message, db: Database, i18n: I18n, config: Config) -> Res
     let sender = message.sender();
 
     if let Some(Chat::User(u)) = sender {
-        if let Some(user) = User::get_by_id(pool, &u.id()).await? {
-            if user.anilist_token.is_some() {
+        let user = match db.report(User::get_by_id(pool, &u.id()).await) {
+            Ok(user) => user,
+            Err(e) if is_connection_error(&e) => {
+                message
+                    .reply(InputMessage::html(t("database_unavailable")))
+                    .await?;
+                return Ok(());
+            }
+            Err(e) => return Err(e.into()),
+        };
+
+        if let Some(user) = user {
+            if args.first() == Some(&"revoke") {
+                let text = if user.anilist_token.is_some() {
+                    match db.report(revoke(&db, &sessions, user).await) {
+                        Ok(()) => t("authentication_revoked"),
+                        Err(e) if is_connection_error(&e) => t("database_unavailable"),
+                        Err(e) => return Err(e.into()),
+                    }
+                } else {
+                    t("not_authenticated")
+                };
+
+                message.reply(InputMessage::html(text)).await?;
+            } else if user.has_valid_token() {
                 message
                     .reply(InputMessage::html(t("already_authenticated")).reply_markup(
                         &reply_markup::inline(vec![vec![
                             button::inline(t("disconnect_btn"), "auth revoke"),
                             button::inline(
                                 t("profile_btn"),
-                                format!("user {}", user.anilist_id.unwrap_or(0)),
+                                utils::sign_callback(&format!(
+                                    "user {0} {1}",
+                                    user.anilist_id.unwrap_or(0),
+                                    u.id()
+                                )),
                             ),
                         ]]),
                     ))
                     .await?;
             } else {
                 if args.is_empty() {
-                    message.reply(InputMessage::html(t("authenticate")).reply_markup(
-                        &reply_markup::inline(vec![vec![button::webview(
-                            t("authenticate_btn"),
-                            format!("https://anilist.co/api/v2/oauth/authorize?client_id={0}&response_type=code&redirect_uri=https://yamata-no-orochi.vercel.app/auth", config.anilist.client_id),
-                        )]]),
-                    ))
-                    .await?;
+                    let text = if sessions.attempt(u.id()).await.is_some() {
+                        t("authentication_pending")
+                    } else if user.token_expired() {
+                        t("authentication_token_expired")
+                    } else {
+                        t("authenticate")
+                    };
+                    let attempt = sessions.start_attempt(u.id()).await;
+
+                    message
+                        .reply(
+                            InputMessage::html(text)
+                                .reply_markup(&authorize_markup(&config, &i18n, &attempt)),
+                        )
+                        .await?;
                 } else {
                     message.delete().await?;
 
                     let code = args[0];
-                    let mut response = surf::post("https://anilist.co/api/v2/oauth/token")
-                        .header("content-type", "application/json")
-                        .header("accept", "application/json")
-                        .body_json(&Body {
-                            grant_type: "authorization_code".to_string(),
-                            client_id: config.anilist.client_id.clone(),
-                            client_secret: config.anilist.client_secret.clone(),
-                            redirect_uri: "https://yamata-no-orochi.vercel.app/auth".to_string(),
-                            code: code.to_string(),
-                        })?
-                        .await?;
+                    let state = args.get(1).copied().unwrap_or_default();
+
+                    let attempt = match sessions.verify_attempt(u.id(), state).await {
+                        Ok(attempt) => attempt,
+                        Err(e) => {
+                            let attempt = sessions.start_attempt(u.id()).await;
+
+                            message
+                                .reply(
+                                    InputMessage::html(match e {
+                                        AttemptError::Expired => t("authentication_expired"),
+                                        AttemptError::StateMismatch => {
+                                            t("authentication_state_mismatch")
+                                        }
+                                    })
+                                    .reply_markup(&authorize_markup(&config, &i18n, &attempt)),
+                                )
+                                .await?;
+                            return Ok(());
+                        }
+                    };
 
-                    let ani_res = response.body_json::<Response>().await?;
+                    let (response, ani_res) = exchange_code(&config, code, &attempt).await?;
 
                     if response.status().is_success() {
                         if let Some(token) = ani_res.access_token {
@@ -109,21 +179,28 @@ async fn auth(message: Message, db: Database, i18n: I18n, config: Config) -> Res
                                 .parse::<i32>()
                                 .expect("failed to parse user's AniList ID");
 
-                            message
-                                .reply(
-                                    InputMessage::html(t("authentication_success")).reply_markup(
-                                        &reply_markup::inline(vec![vec![button::inline(
-                                            t("profile_btn"),
-                                            format!("user {}", ani_id),
-                                        )]]),
-                                    ),
-                                )
-                                .await?;
-
                             let mut update_user: UpdateUser = user.into();
                             update_user.anilist_id = Some(ani_id);
-                            update_user.anilist_token = Some(token);
-                            update_user.update(pool).await?;
+                            update_user.set_token(Some(&token));
+                            update_user.anilist_token_expires_at = token_expiry(&token);
+                            match db.report(update_user.update(pool).await) {
+                                Ok(_) => {}
+                                Err(e) if is_connection_error(&e) => {
+                                    message
+                                        .reply(InputMessage::html(t("database_unavailable")))
+                                        .await?;
+                                    return Ok(());
+                                }
+                                Err(e) => return Err(e.into()),
+                            }
+
+                            let client = sessions.authenticate(u.id(), Some(&token)).await;
+                            let resources = CardResources {
+                                cards: &cards,
+                                i18n: &i18n,
+                                ani: &ani,
+                            };
+                            send_profile(&ctx, &message, &client, resources, ani_id).await?;
                         } else {
                             message
                                 .reply(InputMessage::html(t_a("authentication_failed", hashmap! { "error" => "No token received from AniList".to_string()})))
@@ -146,6 +223,239 @@ async fn auth(message: Message, db: Database, i18n: I18n, config: Config) -> Res
     Ok(())
 }
 
+/// The auth revoke callback handler.
+///
+/// Disconnects the AniList account of whoever taps the disconnect button.
+async fn auth_revoke(
+    query: CallbackQuery,
+    db: Database,
+    i18n: I18n,
+    sessions: Sessions,
+) -> Result<()> {
+    let t = |key: &str| i18n.translate(key);
+
+    let result = match db.report(User::get_by_id(db.pool(), &query.sender().id()).await) {
+        Ok(Some(user)) if user.anilist_token.is_some() => {
+            db.report(revoke(&db, &sessions, user).await).map(|_| true)
+        }
+        Ok(_) => Ok(false),
+        Err(e) => Err(e),
+    };
+
+    match result {
+        Ok(true) => {
+            query
+                .answer()
+                .edit(InputMessage::html(t("authentication_revoked")))
+                .await?;
+        }
+        Ok(false) => {
+            query
+                .answer()
+                .alert(utils::remove_html(t("not_authenticated")))
+                .send()
+                .await?;
+        }
+        Err(e) if is_connection_error(&e) => {
+            query
+                .answer()
+                .alert(t("database_unavailable"))
+                .send()
+                .await?;
+        }
+        Err(e) => return Err(e.into()),
+    }
+
+    Ok(())
+}
+
+/// Disconnects the AniList account of a user.
+///
+/// # Arguments
+///
+/// * `db` - The database resource.
+/// * `sessions` - The sessions resource.
+/// * `user` - The user to disconnect.
+///
+/// # Errors
+///
+/// Returns an error if the user could not be updated.
+async fn revoke(db: &Database, sessions: &Sessions, user: User) -> sqlx::Result<()> {
+    let user_id = user.id;
+
+    let mut update_user: UpdateUser = user.into();
+    update_user.anilist_id = None;
+    update_user.set_token(None);
+    update_user.anilist_token_expires_at = None;
+    update_user.update(db.pool()).await?;
+
+    sessions.remove_client(user_id).await;
+
+    Ok(())
+}
+
+/// The cancel auth handler.
+async fn cancel_auth(message: Message, i18n: I18n, sessions: Sessions) -> Result<()> {
+    let t = |key: &str| i18n.translate(key);
+
+    if let Some(Chat::User(u)) = message.sender() {
+        if sessions.cancel_attempt(u.id()).await {
+            message
+                .reply(InputMessage::html(t("authentication_cancelled")))
+                .await?;
+        } else {
+            message
+                .reply(InputMessage::html(t("no_pending_authentication")))
+                .await?;
+        }
+    } else {
+        message
+            .reply(InputMessage::html(t("only_user_command")))
+            .await?;
+    }
+
+    Ok(())
+}
+
+/// Sends the freshly authenticated user's AniList profile, with a summary of their statistics.
+///
+/// Falls back to a plain confirmation when the profile could not be fetched.
+///
+/// # Arguments
+///
+/// * `ctx` - The context of the command.
+/// * `message` - The message that completed the authentication.
+/// * `client` - The user's authenticated AniList client.
+/// * `resources` - The resources of the card, the AniList one for the statistics.
+/// * `ani_id` - The user's AniList ID.
+async fn send_profile(
+    ctx: &Context,
+    message: &Message,
+    client: &rust_anilist::Client,
+    resources: CardResources<'_>,
+    ani_id: i32,
+) -> Result<()> {
+    let CardResources { cards, i18n, ani } = resources;
+    let t = |key: &str| i18n.translate(key);
+
+    let sender_id = message.sender().map(|sender| sender.id()).unwrap_or(0);
+
+    match client.get_user(ani_id).await {
+        Ok(profile) => {
+            let mut text = format!(
+                "{0}\n\n{1}",
+                t("authentication_success"),
+                utils::gen_user_info(&profile)
+            );
+            if let Some(summary) = ani
+                .get_user_stats(ani_id)
+                .await
+                .map(|stats| utils::gen_user_stats_summary(&stats, i18n))
+                .filter(|summary| !summary.is_empty())
+            {
+                text.push_str(&format!("\n\n{}", summary));
+            }
+
+            let image_url = format!("https://img.anili.st/user/{}", profile.id);
+            let buttons = vec![
+                vec![
+                    button::inline(
+                        t("my_list_btn"),
+                        utils::sign_callback(&format!(
+                            "user list {0} anime CURRENT 1 {1}",
+                            ani_id, sender_id
+                        )),
+                    ),
+                    button::inline(
+                        t("favourites_btn"),
+                        utils::sign_callback(&format!(
+                            "user favourites {0} {1}",
+                            ani_id, sender_id
+                        )),
+                    ),
+                ],
+                vec![button::inline(t("disconnect_btn"), "auth revoke")],
+            ];
+            // The image shows the stats, so an uploaded copy would get outdated.
+            utils::send_card(ctx, cards.files(), &text, Some(&image_url), buttons, false).await?;
+        }
+        Err(e) => {
+            log::warn!("failed to fetch the profile of {:?}: {:?}", ani_id, e);
+
+            message
+                .reply(
+                    InputMessage::html(t("authentication_success")).reply_markup(
+                        &reply_markup::inline(vec![vec![button::inline(
+                            t("profile_btn"),
+                            utils::sign_callback(&format!("user {0} {1}", ani_id, sender_id)),
+                        )]]),
+                    ),
+                )
+                .await?;
+        }
+    }
+
+    Ok(())
+}
+
+/// Generates the markup with the button that opens the AniList authorization page.
+///
+/// # Arguments
+///
+/// * `config` - The bot configuration.
+/// * `i18n` - The translations.
+/// * `attempt` - The pending authentication attempt.
+pub fn authorize_markup(
+    config: &Config,
+    i18n: &I18n,
+    attempt: &AuthAttempt,
+) -> reply_markup::Inline {
+    reply_markup::inline(vec![vec![button::webview(
+        i18n.translate("authenticate_btn"),
+        format!(
+            "https://anilist.co/api/v2/oauth/authorize?client_id={0}&response_type=code&redirect_uri={1}&state={2}",
+            config.anilist.client_id, REDIRECT_URI, attempt.state
+        ),
+    )]])
+}
+
+/// Exchanges an authorization code for an access token.
+///
+/// # Arguments
+///
+/// * `config` - The bot configuration.
+/// * `code` - The authorization code received from AniList.
+/// * `attempt` - The verified authentication attempt the code belongs to.
+///
+/// # Errors
+///
+/// Returns an error if the request to AniList fails.
+async fn exchange_code(
+    config: &Config,
+    code: &str,
+    attempt: &AuthAttempt,
+) -> Result<(surf::Response, Response)> {
+    log::debug!(
+        "exchanging an AniList code for an attempt started {:?} ago",
+        attempt.started_at.elapsed()
+    );
+
+    let mut response = surf::post("https://anilist.co/api/v2/oauth/token")
+        .header("content-type", "application/json")
+        .header("accept", "application/json")
+        .body_json(&Body {
+            grant_type: "authorization_code".to_string(),
+            client_id: config.anilist.client_id,
+            client_secret: config.anilist.client_secret.clone(),
+            redirect_uri: REDIRECT_URI.to_string(),
+            code: code.to_string(),
+        })?
+        .await?;
+    let ani_res = response.body_json::<Response>().await?;
+
+    Ok((response, ani_res))
+}
+
 /// The body of the request to the AniList API.
 #[derive(Serialize)]
 struct Body {
@@ -0,0 +1,123 @@
+// Copyright 2025 - Andriel Ferreira
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// https://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or https://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+//! The birthdays plugin.
+
+use std::time::Duration;
+
+use ferogram::{Context, Result, Router, filter, handler};
+use grammers_client::{InputMessage, button, reply_markup};
+
+use crate::{
+    commands::{HelpCategory, HelpEntry},
+    resources::{AniList, I18n},
+    utils::{self, gen_birthday_list, gen_pagination_buttons_with, shorten_text},
+};
+
+/// The commands of the plugin, for `/help`.
+pub const HELP: &[HelpEntry] = &[HelpEntry::new("birthdays", HelpCategory::Discover)];
+
+/// The plugin setup.
+pub fn setup(router: Router) -> Router {
+    router
+        .register(
+            handler::new_message(
+                filter::command("birthdays")
+                    .description("List the characters whose birthday is today."),
+            )
+            .then(birthdays),
+        )
+        .register(
+            handler::callback_query(filter::regex(r"^birthdays (\d+) (\d+)$")).then(birthdays),
+        )
+}
+
+/// The birthdays handler.
+async fn birthdays(ctx: Context, i18n: I18n, ani: AniList) -> Result<()> {
+    let t = |key: &str| i18n.translate(key);
+
+    let text = if ctx.is_callback_query() {
+        ctx.query()
+    } else {
+        ctx.text()
+    }
+    .unwrap();
+    let mut args = text.split_whitespace().skip(1).collect::<Vec<_>>();
+
+    let sender = ctx.sender().unwrap();
+
+    let mut page = 1;
+    if let Some(query) = ctx.callback_query() {
+        let sender_id = args.pop().unwrap().parse::<i64>().unwrap();
+
+        if sender.id() != sender_id {
+            query
+                .answer()
+                .cache_time(Duration::from_secs(120))
+                .alert(t("not_allowed"))
+                .send()
+                .await?;
+            return Ok(());
+        }
+
+        let Some(requested) = args.first().and_then(|page| page.parse::<u16>().ok()) else {
+            query
+                .answer()
+                .cache_time(Duration::from_secs(120))
+                .alert(t("page_not_found"))
+                .send()
+                .await?;
+            return Ok(());
+        };
+        page = requested.max(1);
+    }
+
+    let characters = ani
+        .get_birthday_characters(page)
+        .await
+        .filter(|characters| !characters.characters.is_empty());
+    let Some(characters) = characters else {
+        if let Some(query) = ctx.callback_query() {
+            query
+                .answer()
+                .cache_time(Duration::from_secs(120))
+                .alert(t("page_not_found"))
+                .send()
+                .await?;
+        } else {
+            ctx.reply(InputMessage::html(t("no_birthdays"))).await?;
+        }
+        return Ok(());
+    };
+
+    let mut buttons = characters
+        .characters
+        .iter()
+        .map(|character| {
+            vec![button::inline(
+                shorten_text(&character.name, 50),
+                utils::sign_callback(&format!("char {0} {1}", character.id, sender.id())),
+            )]
+        })
+        .collect::<Vec<_>>();
+    if characters.last_page > 1 {
+        buttons.push(gen_pagination_buttons_with(
+            page as usize,
+            characters.last_page,
+            |page| format!("birthdays {0} {1}", page, sender.id()),
+        ));
+    }
+
+    ctx.edit_or_reply(
+        InputMessage::html(gen_birthday_list(&characters.characters, &i18n))
+            .reply_markup(&reply_markup::inline(buttons)),
+    )
+    .await?;
+
+    Ok(())
+}
@@ -0,0 +1,61 @@
+// Copyright 2025 - Andriel Ferreira
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// https://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or https://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+//! The cancel plugin, closing the search lists.
+
+use std::time::Duration;
+
+use ferogram::{Result, Router, filter, handler, utils::bytes_to_string};
+use grammers_client::{InputMessage, types::CallbackQuery};
+
+use crate::{resources::I18n, utils};
+
+/// The plugin setup.
+pub fn setup(router: Router) -> Router {
+    router.register(handler::callback_query(filter::regex(r"^cancel (\d+)$")).then(cancel))
+}
+
+/// The cancel callback handler.
+///
+/// Deletes the message, or closes it when it is too old to be deleted.
+async fn cancel(query: CallbackQuery, i18n: I18n) -> Result<()> {
+    let t = |key: &str| i18n.translate(key);
+
+    let data = bytes_to_string(query.data());
+    let sender_id = data
+        .split_whitespace()
+        .nth(1)
+        .unwrap()
+        .parse::<i64>()
+        .unwrap();
+
+    if query.sender().id() != sender_id {
+        utils::send_with_retry(|| {
+            query
+                .answer()
+                .cache_time(Duration::from_secs(120))
+                .alert(t("not_allowed"))
+                .send()
+        })
+        .await?;
+        return Ok(());
+    }
+
+    let message = query.load_message().await?;
+    if let Err(e) = message.delete().await {
+        log::debug!(
+            "failed to delete a search list, closing it instead: {:?}",
+            e
+        );
+
+        utils::send_with_retry(|| query.answer().edit(InputMessage::html(t("search_closed"))))
+            .await?;
+    }
+
+    Ok(())
+}
@@ -10,19 +10,82 @@
 
 use std::time::Duration;
 
+use async_trait::async_trait;
 use ferogram::{Context, Result, Router, filter, handler, utils::split_btns_into_columns};
 use grammers_client::{
-    InputMessage, button, reply_markup,
-    types::{InlineQuery, inline},
+    InputMessage,
+    button::{self, Inline},
+    grammers_tl_types as tl, reply_markup,
+    types::{CallbackQuery, InlineQuery, Message, inline},
 };
-use maplit::hashmap;
 use rust_anilist::models::Character;
 
 use crate::{
-    resources::{AniList, I18n},
-    utils::{self, remove_html, shorten_text},
+    Config,
+    commands::{HelpCategory, HelpEntry},
+    models::{ChatSettings, EntryKind, MediaType},
+    plugins::{favorites, history, inline::should_search},
+    resources::{AniList, Cards, Debounce, I18n, anilist::FetchError},
+    utils::{
+        self, CardResources, Resolver, SearchQuery, StartPayload, gen_appearance_list,
+        gen_signed_pagination_buttons, gen_voice_actor_list, remove_html, shorten_text,
+    },
 };
 
+/// The commands of the plugin, for `/help`.
+pub const HELP: &[HelpEntry] = &[
+    HelpEntry::new("char", HelpCategory::Search)
+        .aliases(&["c", "p", "perso"])
+        .inline("!c"),
+    HelpEntry::new("charimg", HelpCategory::Search),
+];
+
+/// How a character is shown once found.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum CharacterView {
+    /// The card, with the character info.
+    Card,
+    /// Only the image, with the name as its caption.
+    Image,
+}
+
+impl CharacterView {
+    /// Gets the prefix of the callbacks that show a character this way.
+    fn callback_prefix(&self) -> &'static str {
+        match self {
+            Self::Card => "char",
+            Self::Image => "charimg",
+        }
+    }
+
+    /// Gets the key of the usage of the command.
+    fn usage_key(&self) -> &'static str {
+        match self {
+            Self::Card => "character_usage",
+            Self::Image => "character_image_usage",
+        }
+    }
+
+    /// Sends a character this way.
+    ///
+    /// # Arguments
+    ///
+    /// * `char` - The character to send.
+    /// * `ctx` - The context of the update.
+    /// * `resources` - The resources of the card.
+    async fn send(
+        &self,
+        char: Character,
+        ctx: Context,
+        resources: CardResources<'_>,
+    ) -> Result<()> {
+        match self {
+            Self::Card => send_char_info(char, ctx, resources).await,
+            Self::Image => send_char_image(char, ctx, resources).await,
+        }
+    }
+}
+
 /// The plugin setup.
 pub fn setup(router: Router) -> Router {
     router
@@ -33,104 +96,221 @@ pub fn setup(router: Router) -> Router {
             )
             .then(character),
         )
-        .register(handler::callback_query(filter::regex(r"^char (\d+) (\d+)")).then(character))
+        .register(
+            handler::callback_query(filter::regex(r"^char (\d+) (\d+)")).then(character_result),
+        )
+        .register(
+            handler::new_message(
+                filter::command("charimg").description("Send the image of a character."),
+            )
+            .then(character_image),
+        )
+        .register(
+            handler::callback_query(filter::regex(r"^charimg (\d+) (\d+) \S+$"))
+                .then(character_image_result),
+        )
+        .register(
+            handler::callback_query(filter::regex(r"^char (voice_actors|medias) (\d+) (\d+)"))
+                .then(char_info),
+        )
         .register(handler::inline_query(filter::regex(r"^[\.!]?(c|p) (.+)")).then(character_inline))
 }
 
 /// The character handler.
-async fn character(ctx: Context, i18n: I18n, ani: AniList) -> Result<()> {
-    let t = |key: &str| i18n.translate(key);
-    let t_a = |key: &str, args| i18n.translate_with_args(key, args);
+async fn character(
+    ctx: Context,
+    message: Message,
+    i18n: I18n,
+    ani: AniList,
+    cards: Cards,
+    settings: ChatSettings,
+) -> Result<()> {
+    let resources = CardResources {
+        cards: &cards,
+        i18n: &i18n,
+        ani: &ani,
+    };
+
+    find_character(
+        ctx,
+        Some(&message),
+        resources,
+        &settings,
+        CharacterView::Card,
+    )
+    .await
+}
 
-    let text = if ctx.is_callback_query() {
-        ctx.query()
-    } else {
-        ctx.text()
-    }
-    .unwrap();
-    let mut args = text.split_whitespace().skip(1).collect::<Vec<_>>();
+/// The character callback handler, for the buttons of the search results.
+async fn character_result(
+    ctx: Context,
+    i18n: I18n,
+    ani: AniList,
+    cards: Cards,
+    settings: ChatSettings,
+) -> Result<()> {
+    let resources = CardResources {
+        cards: &cards,
+        i18n: &i18n,
+        ani: &ani,
+    };
+
+    find_character(ctx, None, resources, &settings, CharacterView::Card).await
+}
 
-    let sender = ctx.sender().unwrap();
+/// The character image handler.
+///
+/// Sends only the image of a character, e.g. to react with it in groups.
+async fn character_image(
+    ctx: Context,
+    message: Message,
+    i18n: I18n,
+    ani: AniList,
+    cards: Cards,
+    settings: ChatSettings,
+) -> Result<()> {
+    let resources = CardResources {
+        cards: &cards,
+        i18n: &i18n,
+        ani: &ani,
+    };
+
+    find_character(
+        ctx,
+        Some(&message),
+        resources,
+        &settings,
+        CharacterView::Image,
+    )
+    .await
+}
 
-    if let Some(query) = ctx.callback_query() {
-        let sender_id = args.pop().unwrap().parse::<i64>().unwrap();
+/// The character image callback handler, for the buttons of the search results.
+async fn character_image_result(
+    ctx: Context,
+    i18n: I18n,
+    ani: AniList,
+    cards: Cards,
+    settings: ChatSettings,
+) -> Result<()> {
+    let resources = CardResources {
+        cards: &cards,
+        i18n: &i18n,
+        ani: &ani,
+    };
+
+    find_character(ctx, None, resources, &settings, CharacterView::Image).await
+}
 
-        if sender.id() != sender_id {
-            query
-                .answer()
-                .cache_time(Duration::from_secs(120))
-                .alert(t("not_allowed"))
-                .send()
-                .await?;
-            return Ok(());
-        }
+/// Finds the characters of a command or of a button of its results.
+async fn find_character(
+    ctx: Context,
+    command: Option<&Message>,
+    resources: CardResources<'_>,
+    settings: &ChatSettings,
+    view: CharacterView,
+) -> Result<()> {
+    let CardResources { i18n, ani, .. } = resources;
+    let resolver = CharacterResolver { resources, view };
+
+    utils::resolve_or_disambiguate(
+        ctx,
+        command,
+        &resolver,
+        ani,
+        i18n,
+        settings.clean_service_messages,
+    )
+    .await
+}
+
+/// Finds the characters of the commands, see [`utils::resolve_or_disambiguate`].
+///
+/// The results are listed as buttons that send the character the same way.
+struct CharacterResolver<'a> {
+    /// The resources the cards are sent with.
+    resources: CardResources<'a>,
+    /// How the character is sent.
+    view: CharacterView,
+}
+
+#[async_trait]
+impl Resolver for CharacterResolver<'_> {
+    type Entry = Character;
+
+    fn usage_key(&self) -> &'static str {
+        self.view.usage_key()
     }
 
-    if args.is_empty() {
-        ctx.reply(
-            InputMessage::html(t("character_usage")).reply_markup(&reply_markup::inline(vec![
-                vec![button::switch_inline(t("search_btn"), "!c ")],
-            ])),
-        )
-        .await?;
-    } else {
-        if let Ok(id) = args[0].parse::<i64>() {
-            if let Ok(char) = ani.get_char(id).await {
-                send_char_info(char, ctx, &i18n).await?;
-            } else {
-                ctx.reply(InputMessage::html(t("not_found"))).await?;
-            }
-        } else {
-            let title = args.join(" ");
+    fn inline_query(&self) -> &'static str {
+        "!c"
+    }
 
-            if let Some(result) = ani.search_char(&title, 1, 6).await {
-                if result.is_empty() {
-                    ctx.reply(InputMessage::html(t("no_results_text")).reply_markup(
-                        &reply_markup::inline(vec![vec![button::switch_inline(
-                            t("search_again_btn"),
-                            format!("!c {}", title),
-                        )]]),
-                    ))
-                    .await?;
-                    return Ok(());
-                } else if result.len() == 1 {
-                    return send_char_info(result[0].clone(), ctx, &i18n).await;
-                }
+    async fn fetch(&self, arg: &str) -> Option<std::result::Result<Character, FetchError>> {
+        let id = arg.parse::<i64>().ok()?;
 
-                let buttons = result
-                    .into_iter()
-                    .map(|char| {
-                        vec![button::inline(
-                            char.name.full(),
-                            format!("char {0} {1}", char.id, sender.id()),
-                        )]
-                    })
-                    .collect::<Vec<_>>();
+        Some(self.resources.ani.get_char(id).await)
+    }
 
-                ctx.reply(
-                    InputMessage::html(t_a("search_results", hashmap! { "search" => title }))
-                        .reply_markup(&reply_markup::inline(buttons)),
-                )
-                .await?;
-            } else {
-                ctx.reply(InputMessage::html(t("no_results_text")).reply_markup(
-                    &reply_markup::inline(vec![vec![button::switch_inline(
-                        t("search_again_btn"),
-                        format!("!c {}", title),
-                    )]]),
-                ))
-                .await?;
-            }
-        }
+    async fn refetch(&self, char: Character) -> Character {
+        self.resources.ani.get_char(char.id).await.unwrap_or(char)
     }
 
-    Ok(())
+    async fn search(&self, query: &SearchQuery) -> Option<Vec<Character>> {
+        self.resources.ani.search_char(&query.terms, 1, 6).await
+    }
+
+    async fn send(&self, char: Character, ctx: Context) -> Result<()> {
+        self.view.send(char, ctx, self.resources).await
+    }
+
+    async fn gen_result_buttons(
+        &self,
+        result: Vec<Character>,
+        _: &SearchQuery,
+        sender_id: i64,
+    ) -> Vec<Vec<Inline>> {
+        let mut buttons = result
+            .into_iter()
+            .map(|char| {
+                vec![button::inline(
+                    char.name.full(),
+                    utils::sign_callback(&format!(
+                        "{0} {1} {2}",
+                        self.view.callback_prefix(),
+                        char.id,
+                        sender_id
+                    )),
+                )]
+            })
+            .collect::<Vec<_>>();
+        buttons.push(vec![utils::gen_cancel_button(
+            sender_id,
+            self.resources.i18n,
+        )]);
+
+        buttons
+    }
 }
 
 /// Sends the char info to the user.
-async fn send_char_info(char: Character, ctx: Context, i18n: &I18n) -> Result<()> {
+pub async fn send_char_info(
+    char: Character,
+    ctx: Context,
+    resources: CardResources<'_>,
+) -> Result<()> {
+    let CardResources { cards, i18n, .. } = resources;
     let t = |key: &str| i18n.translate(key);
 
+    history::record_view(
+        &ctx,
+        cards.db(),
+        EntryKind::Character,
+        char.id,
+        &char.name.full(),
+    )
+    .await;
+
     let text = utils::gen_char_info(&char, i18n);
     let image_url = char.image.largest();
     let mut buttons = Vec::new();
@@ -140,40 +320,247 @@ async fn send_char_info(char: Character, ctx: Context, i18n: &I18n) -> Result<()
     if char.voice_actors.is_some() {
         buttons.push(button::inline(
             t("voice_actors_btn"),
-            format!("char voice_actors {} {}", char.id, sender.id()),
+            utils::sign_callback(&format!("char voice_actors {} {}", char.id, sender.id())),
         ));
     }
 
     let mut buttons = split_btns_into_columns(buttons, 2);
     buttons.push(vec![button::inline(
         t("medias_btn"),
-        format!("char medias {} {}", char.id, sender.id()),
+        utils::sign_callback(&format!("char medias {} {}", char.id, sender.id())),
     )]);
+    let favorite = favorites::gen_favorite_button(
+        &ctx,
+        cards.favorites(),
+        EntryKind::Character,
+        char.id,
+        i18n,
+    )
+    .await;
+    let share = utils::gen_share_button(StartPayload::Character(char.id), i18n);
+    let row = favorite.into_iter().chain(share).collect::<Vec<_>>();
+    if !row.is_empty() {
+        buttons.push(row);
+    }
 
-    let markup = reply_markup::inline(buttons);
+    let image_url = Some(image_url).filter(|image_url| !image_url.is_empty());
+    utils::send_card(&ctx, cards.files(), &text, image_url, buttons, true).await?;
 
-    if ctx.is_callback_query() {
-        ctx.edit(
-            InputMessage::html(format!("<a href=\"{}\">⁠</a>", image_url) + &text)
-                .link_preview(true)
-                .photo_url(image_url)
-                .reply_markup(&markup),
-        )
+    Ok(())
+}
+
+/// Sends only the image of a character, with its name and ID as the caption and a button
+/// to its card.
+///
+/// Characters without an image get their card instead.
+///
+/// # Arguments
+///
+/// * `char` - The character to send.
+/// * `ctx` - The context of the update.
+/// * `resources` - The resources of the card.
+pub async fn send_char_image(
+    char: Character,
+    ctx: Context,
+    resources: CardResources<'_>,
+) -> Result<()> {
+    let image_url = char.image.largest().to_string();
+    if image_url.is_empty() {
+        return send_char_info(char, ctx, resources).await;
+    }
+
+    let sender = ctx.sender().unwrap();
+
+    let caption = utils::gen_char_image_caption(&char);
+    let buttons = vec![vec![button::inline(
+        resources.i18n.translate("more_info_btn"),
+        utils::sign_callback(&format!("char {0} {1}", char.id, sender.id())),
+    )]];
+    utils::send_card(
+        &ctx,
+        resources.cards.files(),
+        &caption,
+        Some(&image_url),
+        buttons,
+        true,
+    )
+    .await?;
+
+    Ok(())
+}
+
+/// The character info handler.
+async fn char_info(query: CallbackQuery, i18n: I18n, ani: AniList) -> Result<()> {
+    let t = |key: &str| i18n.translate(key);
+
+    let args = utils::callback_data(&query)
+        .split_whitespace()
+        .skip(1)
+        .map(String::from)
+        .collect::<Vec<_>>();
+
+    let info = args[0].as_str();
+    let char_id = args[1].parse::<i64>().unwrap();
+    let sender_id = args[2].parse::<i64>().unwrap();
+
+    let sender = query.sender();
+
+    if sender.id() != sender_id {
+        utils::send_with_retry(|| {
+            query
+                .answer()
+                .cache_time(Duration::from_secs(120))
+                .alert(t("not_allowed"))
+                .send()
+        })
         .await?;
-    } else {
-        ctx.reply(
-            InputMessage::html(text)
-                .photo_url(image_url)
-                .reply_markup(&markup),
-        )
+        return Ok(());
+    }
+
+    let char = ani.get_char(char_id).await;
+    if let Err(FetchError::RateLimited(retry_after)) = char {
+        utils::send_with_retry(|| {
+            query
+                .answer()
+                .alert(remove_html(utils::rate_limited_text(retry_after, &i18n)))
+                .send()
+        })
         .await?;
+        return Ok(());
+    }
+
+    if let Ok(char) = char {
+        let mut text = format!(
+            "<code>{0}</code> | <b>{1}</b>\n\n",
+            char.id,
+            utils::escape_html(char.name.full())
+        );
+
+        match info {
+            "voice_actors" => {
+                let page = args
+                    .get(3)
+                    .unwrap_or(&1.to_string())
+                    .parse::<usize>()
+                    .unwrap();
+                let actors = ani.get_voice_actors(char_id).await.unwrap_or_default();
+
+                let per_page = 10;
+                let max_pages = actors.len().div_ceil(per_page);
+
+                if actors.is_empty() {
+                    utils::send_with_retry(|| {
+                        query
+                            .answer()
+                            .cache_time(Duration::from_secs(120))
+                            .alert(t("not_available"))
+                            .send()
+                    })
+                    .await?;
+                    return Ok(());
+                }
+
+                text.push_str(&gen_voice_actor_list(&actors, page, per_page, &i18n));
+
+                let mut buttons = Vec::new();
+                if max_pages > 1 {
+                    buttons.push(gen_signed_pagination_buttons(
+                        &format!("char voice_actors {0} {1}", char_id, sender_id),
+                        page,
+                        max_pages,
+                    ));
+                }
+                buttons.push(vec![button::inline(
+                    t("back_btn"),
+                    utils::sign_callback(&format!("char {0} {1}", char_id, sender_id)),
+                )]);
+
+                utils::edit_card_page(&query, &text, buttons).await?;
+            }
+            "medias" => {
+                let page = args
+                    .get(3)
+                    .unwrap_or(&1.to_string())
+                    .parse::<usize>()
+                    .unwrap();
+                let appearances = ani.get_appearances(char_id).await.unwrap_or_default();
+
+                let per_page = 10;
+                let max_pages = appearances.len().div_ceil(per_page);
+
+                if appearances.is_empty() {
+                    utils::send_with_retry(|| {
+                        query
+                            .answer()
+                            .cache_time(Duration::from_secs(120))
+                            .alert(t("not_available"))
+                            .send()
+                    })
+                    .await?;
+                    return Ok(());
+                }
+
+                text.push_str(&gen_appearance_list(&appearances, page, per_page, &i18n));
+
+                let mut buttons = appearances
+                    .iter()
+                    .skip((page - 1) * per_page)
+                    .take(3)
+                    .map(|appearance| {
+                        vec![button::inline(
+                            shorten_text(&appearance.title, 40),
+                            utils::sign_callback(&format!(
+                                "{0} {1} {2}",
+                                match appearance.media_type {
+                                    MediaType::Anime => "anime",
+                                    MediaType::Manga => "manga",
+                                },
+                                appearance.id,
+                                sender_id
+                            )),
+                        )]
+                    })
+                    .collect::<Vec<_>>();
+                if max_pages > 1 {
+                    buttons.push(gen_signed_pagination_buttons(
+                        &format!("char medias {0} {1}", char_id, sender_id),
+                        page,
+                        max_pages,
+                    ));
+                }
+                buttons.push(vec![button::inline(
+                    t("back_btn"),
+                    utils::sign_callback(&format!("char {0} {1}", char_id, sender_id)),
+                )]);
+
+                utils::edit_card_page(&query, &text, buttons).await?;
+            }
+            _ => {
+                utils::send_with_retry(|| {
+                    query
+                        .answer()
+                        .cache_time(Duration::from_secs(120))
+                        .alert(t("not_implemented"))
+                        .send()
+                })
+                .await?;
+            }
+        }
+    } else {
+        utils::send_with_retry(|| query.answer().alert(t("entry_unavailable")).send()).await?;
     }
 
     Ok(())
 }
 
 /// The character inline query handler.
-async fn character_inline(query: InlineQuery, i18n: I18n, ani: AniList) -> Result<()> {
+async fn character_inline(
+    query: InlineQuery,
+    i18n: I18n,
+    ani: AniList,
+    config: Config,
+    debounce: Debounce<i64>,
+) -> Result<()> {
     let t = |key: &str| i18n.translate(key);
 
     let arg = query
@@ -182,6 +569,11 @@ async fn character_inline(query: InlineQuery, i18n: I18n, ani: AniList) -> Resul
         .skip(1)
         .collect::<Vec<_>>()
         .join(" ");
+
+    if !should_search(&query, &arg, &config, &debounce, &i18n).await? {
+        return Ok(());
+    }
+
     let offset = query.offset().parse::<u16>().unwrap_or(1);
     let mut results = Vec::new();
 
@@ -204,7 +596,8 @@ async fn character_inline(query: InlineQuery, i18n: I18n, ani: AniList) -> Resul
                         )]],
                     )),
                 )
-                .description(t("click_for_more_info")),
+                .description(t("click_for_more_info"))
+                .into(),
             );
         } else {
             results.push(
@@ -217,7 +610,8 @@ async fn character_inline(query: InlineQuery, i18n: I18n, ani: AniList) -> Resul
                         )]]),
                     ),
                 )
-                .description(t("click_for_more_info")),
+                .description(t("click_for_more_info"))
+                .into(),
             );
         }
     }
@@ -232,29 +626,28 @@ async fn character_inline(query: InlineQuery, i18n: I18n, ani: AniList) -> Resul
     Ok(())
 }
 
-/// Generates an inline query article for a character.
-fn gen_char_article(query: &InlineQuery, char: Character, i18n: &I18n) -> inline::query::Article {
+/// Generates an inline query result for a character, see [`utils::gen_card_result`].
+pub fn gen_char_article(
+    query: &InlineQuery,
+    char: Character,
+    i18n: &I18n,
+) -> tl::enums::InputBotInlineResult {
     let t = |key: &str| i18n.translate(key);
 
     let text = utils::gen_char_info(&char, &i18n);
-    let image_url = char.image.largest();
+    let image_url = Some(char.image.largest()).filter(|image_url| !image_url.is_empty());
 
     let sender = query.sender();
 
-    let mut article = inline::query::Article::new(
-        char.name.full(),
-        InputMessage::html(format!("<a href=\"{}\">⁠</a>", image_url) + &text)
-            .link_preview(true)
-            .reply_markup(&reply_markup::inline(vec![vec![button::inline(
-                t("load_more_btn"),
-                format!("char {0} {1}", char.id, sender.id()),
-            )]])),
+    utils::gen_card_result(
+        char.name.full().to_string(),
+        shorten_text(remove_html(char.description), 150),
+        &text,
+        image_url,
+        image_url,
+        (
+            t("load_more_btn"),
+            utils::sign_callback(&format!("char {0} {1}", char.id, sender.id())),
+        ),
     )
-    .description(shorten_text(remove_html(char.description), 150));
-
-    if !image_url.is_empty() {
-        article = article.thumb_url(image_url);
-    }
-
-    article
 }
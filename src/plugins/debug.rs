@@ -0,0 +1,263 @@
+// Copyright 2025 - Andriel Ferreira
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// https://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or https://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+//! The debug plugin.
+
+use std::{fmt::Debug, hash::Hash, time::Duration};
+
+use ferogram::{Result, Router, filter, handler};
+use grammers_client::{InputMessage, types::Message};
+
+use crate::{
+    Config,
+    models::{Group, User},
+    plugins::owner::sudoer,
+    resources::{
+        AniList, Cache, Database, Sessions,
+        sessions::{token_expiry, token_fingerprint},
+    },
+    utils,
+};
+
+/// The usage of the debug command.
+const USAGE: &str = "/debug caches
+/debug cache <anime|manga|user|char|studio|staff> <id>
+/debug session <user_id>
+/debug settings <chat_id>";
+
+/// The plugin setup.
+///
+/// # Arguments
+///
+/// * `router` - The router.
+/// * `config` - The bot configuration, for the sudoers.
+pub fn setup(router: Router, config: &Config) -> Router {
+    router.register(handler::new_message(filter::command("debug").and(sudoer(config))).then(debug))
+}
+
+/// The debug command handler.
+///
+/// Only answers the sudoers, see [`sudoer`].
+async fn debug(message: Message, db: Database, ani: AniList, sessions: Sessions) -> Result<()> {
+    let text = message.text();
+    let args = text.split_whitespace().skip(1).collect::<Vec<_>>();
+
+    let output = match args.as_slice() {
+        ["caches"] => [
+            summarize_cache("anime", ani.anime_cache()).await,
+            summarize_cache("manga", ani.manga_cache()).await,
+            summarize_cache("user", ani.user_cache()).await,
+            summarize_cache("char", ani.char_cache()).await,
+            summarize_cache("studio", ani.studio_cache()).await,
+            summarize_cache("staff", ani.staff_cache()).await,
+            summarize_cache("search anime", ani.anime_search_cache()).await,
+            summarize_cache("search manga", ani.manga_search_cache()).await,
+            summarize_cache("search user", ani.user_search_cache()).await,
+        ]
+        .join("\n"),
+        ["cache", kind, id] => match (*kind, id.parse::<i64>()) {
+            ("anime", Ok(id)) => inspect_cache("anime", ani.anime_cache(), &id).await,
+            ("manga", Ok(id)) => inspect_cache("manga", ani.manga_cache(), &id).await,
+            ("char", Ok(id)) => inspect_cache("char", ani.char_cache(), &id).await,
+            ("studio", Ok(id)) => inspect_cache("studio", ani.studio_cache(), &id).await,
+            ("staff", Ok(id)) => inspect_cache("staff", ani.staff_cache(), &id).await,
+            ("user", Ok(id)) => inspect_cache("user", ani.user_cache(), &(id as i32)).await,
+            _ => USAGE.to_string(),
+        },
+        ["session", user_id] => match user_id.parse::<i64>() {
+            Ok(user_id) => inspect_session(&db, &sessions, user_id).await,
+            Err(_) => USAGE.to_string(),
+        },
+        ["settings", chat_id] => match chat_id.parse::<i64>() {
+            Ok(chat_id) => inspect_settings(&db, chat_id).await,
+            Err(_) => USAGE.to_string(),
+        },
+        _ => USAGE.to_string(),
+    };
+
+    message
+        .reply(InputMessage::html(format!(
+            "<pre>{}</pre>",
+            utils::escape_html(output)
+        )))
+        .await?;
+
+    Ok(())
+}
+
+/// Summarizes the size and the hit rate of a cache in a line.
+///
+/// # Arguments
+///
+/// * `name` - The name of the cache.
+/// * `cache` - The cache.
+async fn summarize_cache<K, V>(name: &str, cache: &Cache<K, V>) -> String
+where
+    K: Eq + Hash + Clone,
+    V: Clone,
+{
+    let (hits, misses) = (cache.hits(), cache.misses());
+    let rate = if hits + misses > 0 {
+        hits * 100 / (hits + misses)
+    } else {
+        0
+    };
+
+    format!(
+        "{0:<13} {1:>3}/{2:<3} hits: {3} misses: {4} ({5}%)",
+        name,
+        cache.len().await,
+        cache.capacity(),
+        hits,
+        misses,
+        rate
+    )
+}
+
+/// Describes a cache entry.
+///
+/// # Arguments
+///
+/// * `name` - The name of the cache.
+/// * `cache` - The cache.
+/// * `key` - The key of the entry.
+async fn inspect_cache<K, V>(name: &str, cache: &Cache<K, V>, key: &K) -> String
+where
+    K: Eq + Hash + Clone + Debug,
+    V: Clone + Debug,
+{
+    let mut output = format!(
+        "cache:   {0}\nentries: {1}/{2}\nkey:     {3:?}\n",
+        name,
+        cache.len().await,
+        cache.capacity(),
+        key
+    );
+
+    match (cache.age_of(key).await, cache.get(key).await) {
+        (Some(age), Some(value)) => {
+            output.push_str(&format!(
+                "cached:  yes\nage:     {0}\nsize:    ~{1} bytes",
+                format_duration(age),
+                utils::format_thousands(format!("{:?}", value).len() as i64)
+            ));
+        }
+        _ => output.push_str("cached:  no"),
+    }
+
+    output
+}
+
+/// Describes the AniList session of a user, without revealing the token.
+///
+/// # Arguments
+///
+/// * `db` - The database resource.
+/// * `sessions` - The sessions resource.
+/// * `user_id` - The Telegram user ID.
+async fn inspect_session(db: &Database, sessions: &Sessions, user_id: i64) -> String {
+    let mut output = format!("user:    {}\n", user_id);
+
+    output.push_str(&match sessions.client_age(user_id).await {
+        Some(age) => format!("client:  cached {} ago\n", format_duration(age)),
+        None => "client:  none\n".to_string(),
+    });
+    output.push_str(&match sessions.attempt(user_id).await {
+        Some(attempt) => format!(
+            "auth:    pending for {}\n",
+            format_duration(attempt.started_at.elapsed())
+        ),
+        None => "auth:    none pending\n".to_string(),
+    });
+
+    match db.report(User::get_by_id(db.pool(), &user_id).await) {
+        Ok(Some(user)) => match user.token().as_deref() {
+            Some(token) => output.push_str(&format!(
+                "token:   {0} (expires {1})",
+                token_fingerprint(token),
+                token_expiry(token)
+                    .map(|expiry| expiry.to_rfc3339())
+                    .unwrap_or("unknown".to_string())
+            )),
+            None => output.push_str("token:   none"),
+        },
+        Ok(None) => output.push_str("token:   user not registered"),
+        Err(e) => output.push_str(&format!("token:   database error: {}", e)),
+    }
+
+    output
+}
+
+/// Describes the settings of a chat as stored in the database.
+///
+/// # Arguments
+///
+/// * `db` - The database resource.
+/// * `chat_id` - The chat ID, positive for users and negative for groups.
+async fn inspect_settings(db: &Database, chat_id: i64) -> String {
+    // The settings are not cached, every update reads them from the database.
+    let mut output = format!("chat:    {}\ncached:  none\n", chat_id);
+
+    if chat_id > 0 {
+        match db.report(User::get_by_id(db.pool(), &chat_id).await) {
+            Ok(Some(user)) => output.push_str(&format!(
+                "db:      users row\n  anilist_id:    {0:?}\n  anilist_token: {1}\n  language_code: {2}\n  group_results: {3}\n  allow_adult:   {4}\n  title_lang:    {5}\n  va_lang:       {6}\n  track_history: {7}\n  chapter_notif: {8}\n  created_at:    {9}\n  updated_at:    {10}",
+                user.anilist_id,
+                user.token()
+                    .as_deref()
+                    .map(token_fingerprint)
+                    .unwrap_or("none".to_string()),
+                user.language_code,
+                user.group_results,
+                user.allow_adult,
+                user.title_language,
+                user.voice_actor_language,
+                user.track_history,
+                user.chapter_notifications,
+                user.created_at.to_rfc3339(),
+                user.updated_at.to_rfc3339()
+            )),
+            Ok(None) => output.push_str("db:      no users row"),
+            Err(e) => output.push_str(&format!("db:      error: {}", e)),
+        }
+    } else {
+        match db.report(Group::get_by_id(db.pool(), &chat_id).await) {
+            Ok(Some(group)) => output.push_str(&format!(
+                "db:      groups row\n  language_code: {0}\n  allow_adult:   {1}\n  title_lang:    {2}\n  va_lang:       {3}\n  created_at:    {4}\n  updated_at:    {5}",
+                group.language_code,
+                group.allow_adult,
+                group.title_language,
+                group.voice_actor_language,
+                group.created_at.to_rfc3339(),
+                group.updated_at.to_rfc3339()
+            )),
+            Ok(None) => output.push_str("db:      no groups row"),
+            Err(e) => output.push_str(&format!("db:      error: {}", e)),
+        }
+    }
+
+    output
+}
+
+/// Formats a duration as e.g. `1h 2m 3s`.
+///
+/// # Arguments
+///
+/// * `duration` - The duration to format.
+fn format_duration(duration: Duration) -> String {
+    let seconds = duration.as_secs();
+    let (hours, minutes, seconds) = (seconds / 3600, seconds / 60 % 60, seconds % 60);
+
+    if hours > 0 {
+        format!("{}h {}m {}s", hours, minutes, seconds)
+    } else if minutes > 0 {
+        format!("{}m {}s", minutes, seconds)
+    } else {
+        format!("{}s", seconds)
+    }
+}
@@ -0,0 +1,243 @@
+// Copyright 2025 - Andriel Ferreira
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// https://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or https://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+//! The diagnose plugin.
+
+use ferogram::{Filter, Result, Router, filter, handler};
+use grammers_client::{
+    Client, InputMessage, grammers_tl_types as tl,
+    types::{Chat, Message},
+};
+use maplit::hashmap;
+
+use crate::{
+    commands::{HelpCategory, HelpEntry},
+    models::Group,
+    resources::{
+        Database, Diagnostics, I18n,
+        diagnostics::{BotPermissions, ChatActivity},
+    },
+};
+
+/// The commands of the plugin, for `/help`.
+pub const HELP: &[HelpEntry] = &[HelpEntry::new("diagnose", HelpCategory::Bot)];
+
+/// The plugin setup.
+pub fn setup(router: Router) -> Router {
+    router.register(
+        handler::new_message(
+            filter::command("diagnose")
+                .description("Find out why the bot does not answer in a group.")
+                .and(filter::administrator),
+        )
+        .then(diagnose),
+    )
+}
+
+/// The diagnose command handler.
+async fn diagnose(
+    client: Client,
+    message: Message,
+    db: Database,
+    i18n: I18n,
+    diagnostics: Diagnostics,
+) -> Result<()> {
+    let t = |key: &str| i18n.translate(key);
+
+    let chat = message.chat();
+    if !matches!(chat, Chat::Group(_)) {
+        message
+            .reply(InputMessage::html(t("only_group_command")))
+            .await?;
+        return Ok(());
+    }
+
+    let permissions = match diagnostics.permissions(chat.id()).await {
+        Some(permissions) => permissions,
+        None => {
+            let permissions = probe_permissions(&client, &chat).await;
+            diagnostics.store_permissions(chat.id(), permissions).await;
+
+            permissions
+        }
+    };
+
+    let language = if db.is_healthy() {
+        db.report(Group::get_by_id(db.pool(), &chat.id()).await)
+            .ok()
+            .flatten()
+            .map(|group| group.language_code)
+    } else {
+        None
+    };
+
+    message
+        .reply(InputMessage::html(gen_report(
+            diagnostics.activity(chat.id()).await,
+            permissions,
+            language,
+            &i18n,
+        )))
+        .await?;
+
+    Ok(())
+}
+
+/// Probes what the bot is allowed to do in a group.
+///
+/// Probes that fail are reported as unknown instead of failing the whole diagnosis.
+///
+/// # Arguments
+///
+/// * `client` - The Telegram client.
+/// * `chat` - The group.
+async fn probe_permissions(client: &Client, chat: &Chat) -> BotPermissions {
+    let Chat::Group(group) = chat else {
+        return BotPermissions::default();
+    };
+
+    let default_rights = match &group.raw {
+        tl::enums::Chat::Channel(channel) => channel.default_banned_rights.clone(),
+        tl::enums::Chat::Chat(chat) => chat.default_banned_rights.clone(),
+        _ => None,
+    };
+
+    // Basic groups have no way to get a single participant, only the defaults apply.
+    let Some(channel) = chat.pack().try_to_input_channel() else {
+        return permissions_from(default_rights.as_ref());
+    };
+
+    match client
+        .invoke(&tl::functions::channels::GetParticipant {
+            channel,
+            participant: tl::enums::InputPeer::PeerSelf,
+        })
+        .await
+    {
+        Ok(tl::enums::channels::ChannelParticipant::Participant(participant)) => {
+            match participant.participant {
+                tl::enums::ChannelParticipant::Admin(_)
+                | tl::enums::ChannelParticipant::Creator(_) => BotPermissions {
+                    send_messages: Some(true),
+                    send_media: Some(true),
+                    embed_links: Some(true),
+                },
+                tl::enums::ChannelParticipant::Banned(banned) => {
+                    permissions_from(Some(&banned.banned_rights))
+                }
+                _ => permissions_from(default_rights.as_ref()),
+            }
+        }
+        Err(e) => {
+            log::warn!(
+                "failed to probe the permissions in {:?}: {:?}",
+                chat.id(),
+                e
+            );
+
+            BotPermissions::default()
+        }
+    }
+}
+
+/// Converts the banned rights of a chat into the permissions of the bot.
+///
+/// # Arguments
+///
+/// * `rights` - The banned rights, `None` if nothing is banned.
+fn permissions_from(rights: Option<&tl::enums::ChatBannedRights>) -> BotPermissions {
+    let Some(tl::enums::ChatBannedRights::Rights(rights)) = rights else {
+        return BotPermissions {
+            send_messages: Some(true),
+            send_media: Some(true),
+            embed_links: Some(true),
+        };
+    };
+
+    BotPermissions {
+        send_messages: Some(!rights.send_messages),
+        send_media: Some(!(rights.send_media || rights.send_photos)),
+        embed_links: Some(!rights.embed_links),
+    }
+}
+
+/// Generates the diagnosis text, with the fixes for the problems found.
+///
+/// # Arguments
+///
+/// * `activity` - The messages received from the group.
+/// * `permissions` - The permissions of the bot in the group.
+/// * `language` - The stored language of the group, `None` if unknown.
+/// * `i18n` - The i18n resource.
+pub fn gen_report(
+    activity: ChatActivity,
+    permissions: BotPermissions,
+    language: Option<String>,
+    i18n: &I18n,
+) -> String {
+    let t = |key: &str| i18n.translate(key);
+    let status = |value: Option<bool>| match value {
+        Some(true) => "✅",
+        Some(false) => "❌",
+        None => "❔",
+    };
+
+    let mut text = format!("🩺 <b>{}</b>\n\n", t("diagnose_title"));
+    let mut fixes = Vec::new();
+
+    let privacy_mode = activity.privacy_mode();
+    text.push_str(&format!(
+        "{0} {1}\n",
+        status(privacy_mode.map(|privacy_mode| !privacy_mode)),
+        match privacy_mode {
+            Some(true) => t("diagnose_privacy_on"),
+            Some(false) => t("diagnose_privacy_off"),
+            None => t("diagnose_privacy_unknown"),
+        }
+    ));
+    if privacy_mode == Some(true) {
+        fixes.push(t("diagnose_fix_privacy"));
+    }
+
+    for (key, value) in [
+        ("send_messages", permissions.send_messages),
+        ("send_media", permissions.send_media),
+        ("embed_links", permissions.embed_links),
+    ] {
+        text.push_str(&format!(
+            "{0} {1}\n",
+            status(value),
+            t(&format!("diagnose_{}", key))
+        ));
+        if value == Some(false) {
+            fixes.push(t(&format!("diagnose_fix_{}", key)));
+        }
+    }
+
+    text.push_str(&format!(
+        "\n{}\n",
+        match language {
+            Some(language) => i18n.translate_with_args(
+                "diagnose_language",
+                hashmap! { "language" => i18n.translate_from_locale("_NAME", &language) },
+            ),
+            None => t("diagnose_language_unknown"),
+        }
+    ));
+
+    if fixes.is_empty() {
+        text.push_str(&format!("\n{}", t("diagnose_all_good")));
+    } else {
+        text.push_str(&format!("\n<b>{}</b>\n", t("diagnose_fixes")));
+        for fix in fixes {
+            text.push_str(&format!("• {}\n", fix));
+        }
+    }
+
+    text
+}
@@ -0,0 +1,401 @@
+// Copyright 2025 - Andriel Ferreira
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// https://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or https://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+//! The favorites plugin.
+
+use std::time::Duration;
+
+use ferogram::{Context, Result, Router, filter, handler, utils::bytes_to_string};
+use grammers_client::{
+    InputMessage,
+    button::{self, Inline},
+    reply_markup,
+};
+use maplit::hashmap;
+
+use crate::{
+    commands::{HelpCategory, HelpEntry},
+    models::{ChatSettings, EntryKind, Favorite},
+    plugins::{anime, character, list, manga},
+    resources::{
+        AniList, Cards, Database, Favorites, I18n,
+        database::is_connection_error,
+        favorites::{MAX_FAVORITES, Toggled},
+    },
+    utils::{self, CardResources, gen_pagination_buttons_with, remove_html, shorten_text},
+};
+
+/// How many favorites are listed per page.
+const PER_PAGE: usize = 10;
+
+/// The commands of the plugin, for `/help`.
+pub const HELP: &[HelpEntry] = &[HelpEntry::new("favorites", HelpCategory::Account)];
+
+/// The plugin setup.
+pub fn setup(router: Router) -> Router {
+    router
+        .register(
+            handler::new_message(
+                filter::command("favorites").description("List the entries you bookmarked."),
+            )
+            .then(favorites),
+        )
+        .register(
+            handler::callback_query(filter::regex(r"^favorites (\d+) (\d+)$")).then(favorites),
+        )
+        .register(
+            handler::callback_query(filter::regex(
+                r"^favorites remove (anime|manga|character) (\d+) (\d+) (\d+)$",
+            ))
+            .then(favorites_remove),
+        )
+        .register(
+            handler::callback_query(filter::regex(
+                r"^favorite (anime|manga|character) (\d+) (\d+)$",
+            ))
+            .then(favorite_toggle),
+        )
+}
+
+/// Generates the star button of a card, showing whether the sender bookmarked the entry.
+///
+/// Returns `None` for updates without a sender.
+///
+/// # Arguments
+///
+/// * `ctx` - The context of the update.
+/// * `favorites` - The favorites resource.
+/// * `kind` - The kind of the card.
+/// * `id` - The AniList ID of the entry.
+/// * `i18n` - The i18n resource.
+pub async fn gen_favorite_button(
+    ctx: &Context,
+    favorites: &Favorites,
+    kind: EntryKind,
+    id: i64,
+    i18n: &I18n,
+) -> Option<Inline> {
+    let sender = ctx.sender()?;
+
+    let key = if favorites.contains(sender.id(), kind, id).await {
+        "favorite_remove_btn"
+    } else {
+        "favorite_add_btn"
+    };
+
+    Some(button::inline(
+        i18n.translate(key),
+        format!("favorite {0} {1} {2}", kind.as_str(), id, sender.id()),
+    ))
+}
+
+/// The favorite toggle callback handler.
+///
+/// Bookmarks the entry of a card, or removes its bookmark, and sends the card again so its
+/// star follows.
+async fn favorite_toggle(
+    ctx: Context,
+    db: Database,
+    i18n: I18n,
+    ani: AniList,
+    cards: Cards,
+    favorites: Favorites,
+    settings: ChatSettings,
+) -> Result<()> {
+    let t = |key: &str| i18n.translate(key);
+
+    let query = ctx.callback_query().unwrap();
+
+    let data = bytes_to_string(query.data());
+    let args = data.split_whitespace().skip(1).collect::<Vec<_>>();
+
+    let kind = EntryKind::parse(args[0]).unwrap();
+    let id = args[1].parse::<i64>().unwrap();
+    let sender_id = args[2].parse::<i64>().unwrap();
+
+    if query.sender().id() != sender_id {
+        query
+            .answer()
+            .cache_time(Duration::from_secs(120))
+            .alert(t("not_allowed"))
+            .send()
+            .await?;
+        return Ok(());
+    }
+
+    let viewer = list::get_viewer(&db, sender_id).await;
+    let resources = CardResources {
+        cards: &cards,
+        i18n: &i18n,
+        ani: &ani,
+    };
+
+    match kind {
+        EntryKind::Anime => {
+            let Ok(anime) = ani.get_anime(id).await else {
+                return respond(&ctx, t("entry_unavailable")).await;
+            };
+
+            let title = utils::preferred_title(&anime.title, &settings);
+            if !toggle(&ctx, &favorites, sender_id, kind, id, &title, &i18n).await? {
+                return Ok(());
+            }
+
+            anime::send_anime_info(anime, ctx, resources, &settings, viewer.as_ref()).await
+        }
+        EntryKind::Manga => {
+            let Ok(manga) = ani.get_manga(id).await else {
+                return respond(&ctx, t("entry_unavailable")).await;
+            };
+
+            let title = utils::preferred_title(&manga.title, &settings);
+            if !toggle(&ctx, &favorites, sender_id, kind, id, &title, &i18n).await? {
+                return Ok(());
+            }
+
+            manga::send_manga_info(manga, ctx, resources, &settings, viewer.as_ref()).await
+        }
+        EntryKind::Character => {
+            let Ok(char) = ani.get_char(id).await else {
+                return respond(&ctx, t("entry_unavailable")).await;
+            };
+
+            let title = char.name.full().to_string();
+            if !toggle(&ctx, &favorites, sender_id, kind, id, &title, &i18n).await? {
+                return Ok(());
+            }
+
+            character::send_char_info(char, ctx, resources).await
+        }
+    }
+}
+
+/// Toggles a bookmark, answering the callback if it could not be toggled.
+///
+/// Returns whether the bookmark was toggled.
+///
+/// # Arguments
+///
+/// * `ctx` - The context of the update.
+/// * `favorites` - The favorites resource.
+/// * `user_id` - The Telegram user ID.
+/// * `kind` - The kind of the entry.
+/// * `id` - The AniList ID of the entry.
+/// * `title` - The title of the entry.
+/// * `i18n` - The i18n resource.
+async fn toggle(
+    ctx: &Context,
+    favorites: &Favorites,
+    user_id: i64,
+    kind: EntryKind,
+    id: i64,
+    title: &str,
+    i18n: &I18n,
+) -> Result<bool> {
+    let t = |key: &str| i18n.translate(key);
+    let t_a = |key: &str, args| i18n.translate_with_args(key, args);
+
+    match favorites.toggle(user_id, kind, id, title).await {
+        Ok(Toggled::Added | Toggled::Removed) => Ok(true),
+        Ok(Toggled::LimitReached) => {
+            respond(
+                ctx,
+                t_a(
+                    "favorite_limit",
+                    hashmap! { "max" => MAX_FAVORITES.to_string() },
+                ),
+            )
+            .await?;
+            Ok(false)
+        }
+        Err(e) if is_connection_error(&e) => {
+            respond(ctx, t("database_unavailable")).await?;
+            Ok(false)
+        }
+        Err(e) => Err(e.into()),
+    }
+}
+
+/// The favorites handler.
+///
+/// Lists the entries the user bookmarked, grouped by kind, with a button to remove each.
+async fn favorites(ctx: Context, i18n: I18n, favorites: Favorites) -> Result<()> {
+    let t = |key: &str| i18n.translate(key);
+
+    let text = if ctx.is_callback_query() {
+        ctx.query()
+    } else {
+        ctx.text()
+    }
+    .unwrap();
+    let mut args = text.split_whitespace().skip(1).collect::<Vec<_>>();
+
+    let sender = ctx.sender().unwrap();
+
+    if let Some(query) = ctx.callback_query() {
+        let sender_id = args.pop().unwrap().parse::<i64>().unwrap();
+
+        if sender.id() != sender_id {
+            query
+                .answer()
+                .cache_time(Duration::from_secs(120))
+                .alert(t("not_allowed"))
+                .send()
+                .await?;
+            return Ok(());
+        }
+    }
+
+    let page = args
+        .first()
+        .and_then(|page| page.parse::<usize>().ok())
+        .unwrap_or(1);
+
+    send_favorites(ctx, &i18n, &favorites, sender.id(), page).await
+}
+
+/// The favorites remove callback handler.
+async fn favorites_remove(ctx: Context, i18n: I18n, favorites: Favorites) -> Result<()> {
+    let t = |key: &str| i18n.translate(key);
+
+    let query = ctx.callback_query().unwrap();
+
+    let text = ctx.query().unwrap();
+    let args = text.split_whitespace().skip(2).collect::<Vec<_>>();
+
+    let kind = EntryKind::parse(args[0]).unwrap();
+    let id = args[1].parse::<i64>().unwrap();
+    let page = args[2].parse::<usize>().unwrap();
+    let sender_id = args[3].parse::<i64>().unwrap();
+
+    if query.sender().id() != sender_id {
+        query
+            .answer()
+            .cache_time(Duration::from_secs(120))
+            .alert(t("not_allowed"))
+            .send()
+            .await?;
+        return Ok(());
+    }
+
+    match favorites.remove(sender_id, kind, id).await {
+        Ok(()) => send_favorites(ctx, &i18n, &favorites, sender_id, page).await,
+        Err(e) if is_connection_error(&e) => respond(&ctx, t("database_unavailable")).await,
+        Err(e) => Err(e.into()),
+    }
+}
+
+/// Sends a page of the entries a user bookmarked.
+///
+/// # Arguments
+///
+/// * `ctx` - The context of the update.
+/// * `i18n` - The i18n resource.
+/// * `favorites` - The favorites resource.
+/// * `user_id` - The Telegram user ID.
+/// * `page` - The page number.
+async fn send_favorites(
+    ctx: Context,
+    i18n: &I18n,
+    favorites: &Favorites,
+    user_id: i64,
+    page: usize,
+) -> Result<()> {
+    let t = |key: &str| i18n.translate(key);
+
+    let entries = match favorites.list(user_id).await {
+        Ok(entries) => group_favorites(entries),
+        Err(e) if is_connection_error(&e) => {
+            return respond(&ctx, t("database_unavailable")).await;
+        }
+        Err(e) => return Err(e.into()),
+    };
+
+    if entries.is_empty() {
+        ctx.edit_or_reply(InputMessage::html(t("favorites_empty")))
+            .await?;
+        return Ok(());
+    }
+
+    let max_pages = entries.len().div_ceil(PER_PAGE);
+    let page = page.clamp(1, max_pages);
+
+    let mut text = format!("⭐ <b>{}</b>\n", t("favorites"));
+    let mut buttons = Vec::new();
+    let mut last_kind = None;
+
+    for (kind, entry) in entries.iter().skip((page - 1) * PER_PAGE).take(PER_PAGE) {
+        if last_kind != Some(*kind) {
+            last_kind = Some(*kind);
+            text.push_str(&format!(
+                "\n{0} <b>{1}</b>\n",
+                kind.emoji(),
+                t(&format!("favorites_{}", kind.as_str()))
+            ));
+        }
+
+        text.push_str(&format!(
+            "• <code>{0}</code> | {1}\n",
+            entry.media_id,
+            utils::escape_html(&entry.title)
+        ));
+        buttons.push(vec![button::inline(
+            format!("🗑 {}", shorten_text(&entry.title, 40)),
+            format!(
+                "favorites remove {0} {1} {2} {3}",
+                kind.as_str(),
+                entry.media_id,
+                page,
+                user_id
+            ),
+        )]);
+    }
+
+    if max_pages > 1 {
+        buttons.push(gen_pagination_buttons_with(page, max_pages, |i| {
+            format!("favorites {0} {1}", i, user_id)
+        }));
+    }
+
+    ctx.edit_or_reply(InputMessage::html(text).reply_markup(&reply_markup::inline(buttons)))
+        .await?;
+
+    Ok(())
+}
+
+/// Groups the favorites by kind, in the order of [`EntryKind::ALL`], keeping the order of
+/// each kind. Favorites of unknown kinds are skipped.
+///
+/// # Arguments
+///
+/// * `favorites` - The favorites of a user.
+pub fn group_favorites(favorites: Vec<Favorite>) -> Vec<(EntryKind, Favorite)> {
+    let mut grouped = favorites
+        .into_iter()
+        .filter_map(|favorite| Some((favorite.kind()?, favorite)))
+        .collect::<Vec<_>>();
+    grouped.sort_by_key(|(kind, _)| EntryKind::ALL.iter().position(|k| k == kind));
+
+    grouped
+}
+
+/// Answers a favorites action, with an alert for callbacks and a reply for commands.
+///
+/// # Arguments
+///
+/// * `ctx` - The context of the update.
+/// * `text` - The HTML text of the answer.
+async fn respond(ctx: &Context, text: String) -> Result<()> {
+    if let Some(query) = ctx.callback_query() {
+        query.answer().alert(remove_html(text)).send().await?;
+    } else {
+        ctx.reply(InputMessage::html(text)).await?;
+    }
+
+    Ok(())
+}
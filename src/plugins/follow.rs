@@ -0,0 +1,350 @@
+// Copyright 2025 - Andriel Ferreira
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// https://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or https://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+//! The follow plugin.
+
+use std::time::Duration;
+
+use ferogram::{Context, Result, Router, filter, handler};
+use grammers_client::{InputMessage, button, reply_markup};
+use maplit::hashmap;
+
+use crate::{
+    commands::{HelpCategory, HelpEntry},
+    models::{ChatSettings, FollowedAnime},
+    resources::{AniList, Database, I18n, database::is_connection_error},
+    utils::{self, gen_pagination_buttons_with, remove_html, shorten_text},
+};
+
+/// How many animes a user can follow.
+const MAX_FOLLOWS: usize = 100;
+
+/// How many follows are listed per page.
+const PER_PAGE: usize = 10;
+
+/// The commands of the plugin, for `/help`.
+pub const HELP: &[HelpEntry] = &[
+    HelpEntry::new("follow", HelpCategory::Account),
+    HelpEntry::new("unfollow", HelpCategory::Account),
+    HelpEntry::new("following", HelpCategory::Account),
+];
+
+/// The plugin setup.
+pub fn setup(router: Router) -> Router {
+    router
+        .register(
+            handler::new_message(
+                filter::command("follow").description("Get notified when an anime airs."),
+            )
+            .then(follow),
+        )
+        .register(handler::callback_query(filter::regex(r"^follow (\d+) (\d+)$")).then(follow))
+        .register(
+            handler::new_message(filter::command("unfollow").description("Unfollow an anime."))
+                .then(unfollow),
+        )
+        .register(handler::callback_query(filter::regex(r"^unfollow (\d+) (\d+)$")).then(unfollow))
+        .register(
+            handler::new_message(
+                filter::command("following").description("List the animes you follow."),
+            )
+            .then(following),
+        )
+        .register(
+            handler::callback_query(filter::regex(r"^following (\d+) (\d+)$")).then(following),
+        )
+        .register(
+            handler::callback_query(filter::regex(r"^following remove (\d+) (\d+) (\d+)$"))
+                .then(following_remove),
+        )
+}
+
+/// The follow handler.
+///
+/// Accepts `/follow <anime id>` and the follow button of the anime cards.
+async fn follow(
+    ctx: Context,
+    db: Database,
+    i18n: I18n,
+    ani: AniList,
+    settings: ChatSettings,
+) -> Result<()> {
+    let t = |key: &str| i18n.translate(key);
+    let t_a = |key: &str, args| i18n.translate_with_args(key, args);
+
+    let Some(anime_id) = parse_args(&ctx, &i18n).await? else {
+        return Ok(());
+    };
+    let Some(anime_id) = anime_id else {
+        ctx.reply(InputMessage::html(t("follow_usage"))).await?;
+        return Ok(());
+    };
+
+    let sender = ctx.sender().unwrap();
+
+    let Ok(anime) = ani.get_anime(anime_id).await else {
+        return respond(&ctx, t("not_found")).await;
+    };
+    if anime.next_airing_episode.is_none() {
+        return respond(&ctx, t("follow_not_airing")).await;
+    }
+
+    let pool = db.pool();
+    let result = match db.report(FollowedAnime::get_by_user(pool, sender.id()).await) {
+        Ok(follows) if follows.len() >= MAX_FOLLOWS => {
+            return respond(
+                &ctx,
+                t_a(
+                    "follow_limit",
+                    hashmap! { "max" => MAX_FOLLOWS.to_string() },
+                ),
+            )
+            .await;
+        }
+        Ok(_) => db.report(FollowedAnime::follow(pool, sender.id(), anime_id).await),
+        Err(e) => Err(e),
+    };
+
+    let title = utils::escape_html(utils::preferred_title(&anime.title, &settings));
+    match result {
+        Ok(true) => respond(&ctx, t_a("follow_added", hashmap! { "title" => title })).await,
+        Ok(false) => respond(&ctx, t_a("follow_already", hashmap! { "title" => title })).await,
+        Err(e) if is_connection_error(&e) => respond(&ctx, t("database_unavailable")).await,
+        Err(e) => Err(e.into()),
+    }
+}
+
+/// The unfollow handler.
+///
+/// Accepts `/unfollow <anime id>` and the unfollow button of the notifications.
+async fn unfollow(ctx: Context, db: Database, i18n: I18n) -> Result<()> {
+    let t = |key: &str| i18n.translate(key);
+
+    let Some(anime_id) = parse_args(&ctx, &i18n).await? else {
+        return Ok(());
+    };
+    let Some(anime_id) = anime_id else {
+        ctx.reply(InputMessage::html(t("unfollow_usage"))).await?;
+        return Ok(());
+    };
+
+    let sender = ctx.sender().unwrap();
+
+    match db.report(FollowedAnime::unfollow(db.pool(), sender.id(), anime_id).await) {
+        Ok(true) => respond(&ctx, t("follow_removed")).await,
+        Ok(false) => respond(&ctx, t("follow_not_following")).await,
+        Err(e) if is_connection_error(&e) => respond(&ctx, t("database_unavailable")).await,
+        Err(e) => Err(e.into()),
+    }
+}
+
+/// The following handler.
+///
+/// Lists the animes the user follows, with a button to unfollow each.
+async fn following(
+    ctx: Context,
+    db: Database,
+    i18n: I18n,
+    ani: AniList,
+    settings: ChatSettings,
+) -> Result<()> {
+    let t = |key: &str| i18n.translate(key);
+
+    let text = if ctx.is_callback_query() {
+        ctx.query()
+    } else {
+        ctx.text()
+    }
+    .unwrap();
+    let mut args = text.split_whitespace().skip(1).collect::<Vec<_>>();
+
+    let sender = ctx.sender().unwrap();
+
+    if let Some(query) = ctx.callback_query() {
+        let sender_id = args.pop().unwrap().parse::<i64>().unwrap();
+
+        if sender.id() != sender_id {
+            query
+                .answer()
+                .cache_time(Duration::from_secs(120))
+                .alert(t("not_allowed"))
+                .send()
+                .await?;
+            return Ok(());
+        }
+    }
+
+    let page = args
+        .first()
+        .and_then(|page| page.parse::<usize>().ok())
+        .unwrap_or(1);
+
+    send_following(ctx, db, &i18n, ani, &settings, sender.id(), page).await
+}
+
+/// The following remove callback handler.
+async fn following_remove(
+    ctx: Context,
+    db: Database,
+    i18n: I18n,
+    ani: AniList,
+    settings: ChatSettings,
+) -> Result<()> {
+    let t = |key: &str| i18n.translate(key);
+
+    let query = ctx.callback_query().unwrap();
+
+    let text = ctx.query().unwrap();
+    let args = text.split_whitespace().skip(2).collect::<Vec<_>>();
+
+    let anime_id = args[0].parse::<i64>().unwrap();
+    let page = args[1].parse::<usize>().unwrap();
+    let sender_id = args[2].parse::<i64>().unwrap();
+
+    if query.sender().id() != sender_id {
+        query
+            .answer()
+            .cache_time(Duration::from_secs(120))
+            .alert(t("not_allowed"))
+            .send()
+            .await?;
+        return Ok(());
+    }
+
+    let result = db.report(FollowedAnime::unfollow(db.pool(), sender_id, anime_id).await);
+    match result {
+        Ok(_) => send_following(ctx, db, &i18n, ani, &settings, sender_id, page).await,
+        Err(e) if is_connection_error(&e) => respond(&ctx, t("database_unavailable")).await,
+        Err(e) => Err(e.into()),
+    }
+}
+
+/// Sends a page of the animes a user follows.
+///
+/// # Arguments
+///
+/// * `ctx` - The context of the update.
+/// * `db` - The database resource.
+/// * `i18n` - The i18n resource.
+/// * `ani` - The AniList resource.
+/// * `settings` - The settings of the chat, for the title language.
+/// * `user_id` - The Telegram user ID.
+/// * `page` - The page number.
+async fn send_following(
+    ctx: Context,
+    db: Database,
+    i18n: &I18n,
+    ani: AniList,
+    settings: &ChatSettings,
+    user_id: i64,
+    page: usize,
+) -> Result<()> {
+    let t = |key: &str| i18n.translate(key);
+
+    let follows = match db.report(FollowedAnime::get_by_user(db.pool(), user_id).await) {
+        Ok(follows) => follows,
+        Err(e) if is_connection_error(&e) => {
+            return respond(&ctx, t("database_unavailable")).await;
+        }
+        Err(e) => return Err(e.into()),
+    };
+
+    if follows.is_empty() {
+        ctx.edit_or_reply(InputMessage::html(t("following_empty")))
+            .await?;
+        return Ok(());
+    }
+
+    let max_pages = follows.len().div_ceil(PER_PAGE);
+    let page = page.clamp(1, max_pages);
+
+    let mut text = format!("🔔 <b>{}</b>\n\n", t("following"));
+    let mut buttons = Vec::new();
+
+    for follow in follows.iter().skip((page - 1) * PER_PAGE).take(PER_PAGE) {
+        let title = match ani.get_anime(follow.anime_id).await {
+            Ok(anime) => utils::preferred_title(&anime.title, settings),
+            Err(_) => follow.anime_id.to_string(),
+        };
+
+        text.push_str(&format!(
+            "• <code>{0}</code> | {1}\n",
+            follow.anime_id,
+            utils::escape_html(&title)
+        ));
+        buttons.push(vec![button::inline(
+            format!("🔕 {}", shorten_text(title, 40)),
+            format!(
+                "following remove {0} {1} {2}",
+                follow.anime_id, page, user_id
+            ),
+        )]);
+    }
+
+    if max_pages > 1 {
+        buttons.push(gen_pagination_buttons_with(page, max_pages, |i| {
+            format!("following {0} {1}", i, user_id)
+        }));
+    }
+
+    ctx.edit_or_reply(InputMessage::html(text).reply_markup(&reply_markup::inline(buttons)))
+        .await?;
+
+    Ok(())
+}
+
+/// Parses the anime ID of a follow or unfollow, from the command or the callback.
+///
+/// Returns `None` if the callback is not from its sender, after answering it, and
+/// `Some(None)` if no valid anime ID was given.
+///
+/// # Arguments
+///
+/// * `ctx` - The context of the update.
+/// * `i18n` - The i18n resource.
+async fn parse_args(ctx: &Context, i18n: &I18n) -> Result<Option<Option<i64>>> {
+    let text = if ctx.is_callback_query() {
+        ctx.query()
+    } else {
+        ctx.text()
+    }
+    .unwrap();
+    let mut args = text.split_whitespace().skip(1).collect::<Vec<_>>();
+
+    if let Some(query) = ctx.callback_query() {
+        let sender_id = args.pop().unwrap().parse::<i64>().unwrap();
+
+        if ctx.sender().unwrap().id() != sender_id {
+            query
+                .answer()
+                .cache_time(Duration::from_secs(120))
+                .alert(i18n.translate("not_allowed"))
+                .send()
+                .await?;
+            return Ok(None);
+        }
+    }
+
+    Ok(Some(args.first().and_then(|id| id.parse::<i64>().ok())))
+}
+
+/// Answers a follow action, with an alert for callbacks and a reply for commands.
+///
+/// # Arguments
+///
+/// * `ctx` - The context of the update.
+/// * `text` - The HTML text of the answer.
+async fn respond(ctx: &Context, text: String) -> Result<()> {
+    if let Some(query) = ctx.callback_query() {
+        query.answer().alert(remove_html(text)).send().await?;
+    } else {
+        ctx.reply(InputMessage::html(text)).await?;
+    }
+
+    Ok(())
+}
@@ -0,0 +1,282 @@
+// Copyright 2025 - Andriel Ferreira
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// https://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or https://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+//! The genre plugin.
+
+use std::time::Duration;
+
+use ferogram::{Context, Result, Router, filter, handler, utils::split_btns_into_columns};
+use grammers_client::{
+    InputMessage,
+    button::{self, Inline},
+    reply_markup,
+};
+use maplit::hashmap;
+
+use crate::{
+    commands::{HelpCategory, HelpEntry},
+    models::MediaType,
+    resources::{AniList, I18n, anilist::genres},
+    utils::{
+        self, gen_genre_list, gen_pagination_buttons_with, remove_html, shorten_text,
+        translate_enum,
+    },
+};
+
+/// The commands of the plugin, for `/help`.
+pub const HELP: &[HelpEntry] = &[HelpEntry::new("genre", HelpCategory::Discover)];
+
+/// The plugin setup.
+pub fn setup(router: Router) -> Router {
+    router
+        .register(
+            handler::new_message(
+                filter::command("genre").description("Browse the animes and mangas of a genre."),
+            )
+            .then(genre),
+        )
+        .register(
+            handler::callback_query(filter::regex(r"^genre (\S+) (anime|manga) (\d+) (\d+)$"))
+                .then(genre),
+        )
+}
+
+/// The genre command and callback handler.
+///
+/// Lists the genres with `/genre`, and the most popular media of a genre or tag with
+/// `/genre romance` or `/genre manga slice of life`. In callbacks the name is sent with
+/// underscores instead of spaces, or `-` for the list of genres.
+async fn genre(ctx: Context, i18n: I18n, ani: AniList) -> Result<()> {
+    let t = |key: &str| i18n.translate(key);
+
+    let text = if ctx.is_callback_query() {
+        ctx.query()
+    } else {
+        ctx.text()
+    }
+    .unwrap();
+    let mut args = text.split_whitespace().skip(1).collect::<Vec<_>>();
+
+    let sender = ctx.sender().unwrap();
+
+    let (genre, media_type, page) = if let Some(query) = ctx.callback_query() {
+        let sender_id = args.pop().unwrap().parse::<i64>().unwrap();
+
+        if sender.id() != sender_id {
+            query
+                .answer()
+                .cache_time(Duration::from_secs(120))
+                .alert(t("not_allowed"))
+                .send()
+                .await?;
+            return Ok(());
+        }
+
+        let Some(page) = args.get(2).and_then(|page| page.parse::<u16>().ok()) else {
+            return respond(&ctx, t("page_not_found")).await;
+        };
+
+        (
+            (args[0] != "-").then(|| genres::decode(args[0])),
+            parse_media_type(args[1]),
+            page.max(1),
+        )
+    } else {
+        let media_type = args
+            .first()
+            .filter(|arg| matches!(arg.to_lowercase().as_str(), "anime" | "a" | "manga" | "m"))
+            .map(|arg| parse_media_type(&arg.to_lowercase()));
+        if media_type.is_some() {
+            args.remove(0);
+        }
+
+        let genre = args.join(" ");
+        (
+            (!genre.is_empty()).then(|| utils::normalize_genre(&genre)),
+            media_type.unwrap_or(MediaType::Anime),
+            1,
+        )
+    };
+
+    let type_name = |media_type: MediaType| match media_type {
+        MediaType::Anime => "anime",
+        MediaType::Manga => "manga",
+    };
+    let other_type = match media_type {
+        MediaType::Anime => MediaType::Manga,
+        MediaType::Manga => MediaType::Anime,
+    };
+
+    let Some(genre) = genre else {
+        let Some(genres) = ani.get_genres().await.filter(|genres| !genres.is_empty()) else {
+            return respond(&ctx, t("not_available")).await;
+        };
+
+        let buttons = genres
+            .iter()
+            .map(|genre| {
+                button::inline(
+                    translate_enum("genre", genre, &i18n),
+                    format!(
+                        "genre {0} {1} 1 {2}",
+                        genres::encode(genre),
+                        type_name(media_type),
+                        sender.id()
+                    ),
+                )
+            })
+            .collect::<Vec<_>>();
+        let mut buttons = split_btns_into_columns(buttons, 3);
+        buttons.push(vec![button::inline(
+            t(&format!("{}_btn", type_name(other_type))),
+            format!("genre - {0} 1 {1}", type_name(other_type), sender.id()),
+        )]);
+
+        ctx.edit_or_reply(
+            InputMessage::html(t(&format!("genres_choose_{}", type_name(media_type))))
+                .reply_markup(&reply_markup::inline(buttons)),
+        )
+        .await?;
+
+        return Ok(());
+    };
+
+    let entries = ani
+        .search_by_genre(&genre, media_type, page)
+        .await
+        .filter(|entries| !entries.media.is_empty());
+    let Some(entries) = entries else {
+        let text = if page > 1 {
+            t("page_not_found")
+        } else {
+            i18n.translate_with_args(
+                "genres_no_results",
+                hashmap! { "genre" => utils::escape_html(translate_enum("genre", &genre, &i18n)) },
+            )
+        };
+        return respond(&ctx, text).await;
+    };
+
+    let mut buttons = entries
+        .media
+        .iter()
+        .map(|entry| {
+            vec![button::inline(
+                shorten_text(&entry.title, 50),
+                utils::sign_callback(&format!(
+                    "{0} {1} {2}",
+                    type_name(media_type),
+                    entry.id,
+                    sender.id()
+                )),
+            )]
+        })
+        .collect::<Vec<_>>();
+    if entries.last_page > 1 {
+        buttons.push(gen_pagination_buttons_with(
+            page as usize,
+            entries.last_page,
+            |page| {
+                format!(
+                    "genre {0} {1} {2} {3}",
+                    genres::encode(&genre),
+                    type_name(media_type),
+                    page,
+                    sender.id()
+                )
+            },
+        ));
+    }
+    buttons.push(vec![
+        button::inline(
+            t(&format!("{}_btn", type_name(other_type))),
+            format!(
+                "genre {0} {1} 1 {2}",
+                genres::encode(&genre),
+                type_name(other_type),
+                sender.id()
+            ),
+        ),
+        button::inline(
+            t("genres_btn"),
+            format!("genre - {0} 1 {1}", type_name(media_type), sender.id()),
+        ),
+    ]);
+
+    ctx.edit_or_reply(
+        InputMessage::html(gen_genre_list(&genre, &entries.media, &i18n))
+            .reply_markup(&reply_markup::inline(buttons)),
+    )
+    .await?;
+
+    Ok(())
+}
+
+/// Generates the buttons that browse the media of the tags of a card.
+///
+/// Tags too long to fit in a callback are skipped.
+///
+/// # Arguments
+///
+/// * `tags` - The names of the tags.
+/// * `media_type` - The media type of the card.
+/// * `sender_id` - The ID of the user who opened the card.
+pub fn gen_tag_buttons<'a>(
+    tags: impl IntoIterator<Item = &'a str>,
+    media_type: MediaType,
+    sender_id: i64,
+) -> Vec<Vec<Inline>> {
+    /// The maximum length of a callback, in bytes.
+    const MAX_CALLBACK_LEN: usize = 64;
+
+    let buttons = tags
+        .into_iter()
+        .map(|tag| {
+            (
+                tag,
+                format!(
+                    "genre {0} {1} 1 {2}",
+                    genres::encode(tag),
+                    match media_type {
+                        MediaType::Anime => "anime",
+                        MediaType::Manga => "manga",
+                    },
+                    sender_id
+                ),
+            )
+        })
+        .filter(|(_, data)| data.len() <= MAX_CALLBACK_LEN)
+        .map(|(tag, data)| button::inline(tag, data))
+        .collect::<Vec<_>>();
+
+    split_btns_into_columns(buttons, 2)
+}
+
+/// Parses the media type of the command or callback, anime unless it is a manga.
+fn parse_media_type(arg: &str) -> MediaType {
+    match arg {
+        "manga" | "m" => MediaType::Manga,
+        _ => MediaType::Anime,
+    }
+}
+
+/// Answers a failed browse, with an alert for callbacks and a reply for commands.
+///
+/// # Arguments
+///
+/// * `ctx` - The context of the update.
+/// * `text` - The HTML text of the answer.
+async fn respond(ctx: &Context, text: String) -> Result<()> {
+    if let Some(query) = ctx.callback_query() {
+        query.answer().alert(remove_html(text)).send().await?;
+    } else {
+        ctx.reply(InputMessage::html(text)).await?;
+    }
+
+    Ok(())
+}
@@ -0,0 +1,93 @@
+// Copyright 2025 - Andriel Ferreira
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// https://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or https://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+//! The help plugin.
+
+use ferogram::{Context, Result, Router, filter, handler, utils::split_btns_into_columns};
+use grammers_client::{InputMessage, button, reply_markup};
+
+use crate::{
+    commands::{HelpCategory, HelpEntry},
+    plugins,
+    resources::I18n,
+    utils::{gen_help_entry, gen_help_list},
+};
+
+/// The commands of the plugin, for `/help`.
+pub const HELP: &[HelpEntry] = &[HelpEntry::new("help", HelpCategory::Bot)];
+
+/// The plugin setup.
+pub fn setup(router: Router) -> Router {
+    router
+        .register(
+            handler::new_message(filter::command("help").description("Show what the bot can do."))
+                .then(help),
+        )
+        .register(handler::callback_query(filter::regex(r"^help( \w+)?$")).then(help))
+}
+
+/// The help handler.
+///
+/// Accepts `/help`, for the list of commands, or `/help <command>`, for its details.
+async fn help(ctx: Context, i18n: I18n) -> Result<()> {
+    let t = |key: &str| i18n.translate(key);
+
+    let text = if ctx.is_callback_query() {
+        ctx.query()
+    } else {
+        ctx.text()
+    }
+    .unwrap();
+    let name = text
+        .split_whitespace()
+        .nth(1)
+        .map(|name| name.trim_start_matches('/').to_lowercase());
+
+    let entries = plugins::help_entries();
+    let entry = name.and_then(|name| {
+        entries
+            .iter()
+            .find(|entry| entry.command == name || entry.aliases.contains(&name.as_str()))
+    });
+
+    let message = if let Some(entry) = entry {
+        let mut buttons = Vec::new();
+        if let Some(prefix) = entry.inline {
+            buttons.push(vec![button::switch_inline(
+                t("search_btn"),
+                format!("{} ", prefix),
+            )]);
+        }
+        buttons.push(vec![button::inline(t("back_btn"), "help")]);
+
+        InputMessage::html(gen_help_entry(entry, &i18n))
+            .reply_markup(&reply_markup::inline(buttons))
+    } else {
+        let buttons = HelpCategory::ALL
+            .iter()
+            .flat_map(|category| {
+                entries
+                    .iter()
+                    .filter(move |entry| entry.category == *category)
+            })
+            .map(|entry| {
+                button::inline(
+                    format!("/{}", entry.command),
+                    format!("help {}", entry.command),
+                )
+            })
+            .collect::<Vec<_>>();
+
+        InputMessage::html(gen_help_list(&entries, &i18n))
+            .reply_markup(&reply_markup::inline(split_btns_into_columns(buttons, 3)))
+    };
+
+    ctx.edit_or_reply(message).await?;
+
+    Ok(())
+}
@@ -0,0 +1,200 @@
+// Copyright 2025 - Andriel Ferreira
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// https://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or https://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+//! The history plugin.
+
+use std::time::Duration;
+
+use ferogram::{Context, Result, Router, filter, handler};
+use grammers_client::{InputMessage, button, reply_markup};
+
+use crate::{
+    commands::{HelpCategory, HelpEntry},
+    models::{EntryKind, User, ViewHistory},
+    resources::{Database, I18n, database::is_connection_error},
+    utils::{remove_html, shorten_text},
+};
+
+/// How many entries `/history` lists.
+const SHOWN_ENTRIES: i64 = 15;
+
+/// The commands of the plugin, for `/help`.
+pub const HELP: &[HelpEntry] = &[HelpEntry::new("history", HelpCategory::Account)];
+
+/// The plugin setup.
+pub fn setup(router: Router) -> Router {
+    router
+        .register(
+            handler::new_message(
+                filter::command("history").description("List the cards you opened recently."),
+            )
+            .then(history),
+        )
+        .register(
+            handler::callback_query(filter::regex(r"^history clear (\d+)$")).then(history_clear),
+        )
+}
+
+/// Records that the sender opened a card, for their `/history`.
+///
+/// It is best-effort: failures are logged and never stop the card.
+///
+/// # Arguments
+///
+/// * `ctx` - The context of the update.
+/// * `db` - The database resource.
+/// * `kind` - The kind of the card.
+/// * `id` - The AniList ID of the entry.
+/// * `title` - The title of the entry.
+pub async fn record_view(ctx: &Context, db: &Database, kind: EntryKind, id: i64, title: &str) {
+    let Some(sender) = ctx.sender() else {
+        return;
+    };
+    if !db.is_healthy() {
+        return;
+    }
+
+    let result = ViewHistory::record(db.pool(), sender.id(), kind, id, title).await;
+    if let Err(e) = db.report(result) {
+        log::warn!(
+            "failed to record that {} viewed {} {}: {:?}",
+            sender.id(),
+            kind.as_str(),
+            id,
+            e
+        );
+    }
+}
+
+/// The history handler.
+///
+/// Lists the last cards the user opened, each button opening its card again.
+async fn history(ctx: Context, db: Database, i18n: I18n) -> Result<()> {
+    let sender = ctx.sender().unwrap();
+
+    send_history(ctx, db, &i18n, sender.id()).await
+}
+
+/// The history clear callback handler.
+async fn history_clear(ctx: Context, db: Database, i18n: I18n) -> Result<()> {
+    let t = |key: &str| i18n.translate(key);
+
+    let sender = ctx.sender().unwrap();
+
+    if !check_sender(&ctx, sender.id(), &i18n).await? {
+        return Ok(());
+    }
+
+    match db.report(ViewHistory::clear(db.pool(), sender.id()).await) {
+        Ok(()) => send_history(ctx, db, &i18n, sender.id()).await,
+        Err(e) if is_connection_error(&e) => respond(&ctx, t("database_unavailable")).await,
+        Err(e) => Err(e.into()),
+    }
+}
+
+/// Sends the last cards a user opened.
+///
+/// # Arguments
+///
+/// * `ctx` - The context of the update.
+/// * `db` - The database resource.
+/// * `i18n` - The i18n resource.
+/// * `user_id` - The Telegram user ID.
+async fn send_history(ctx: Context, db: Database, i18n: &I18n, user_id: i64) -> Result<()> {
+    let t = |key: &str| i18n.translate(key);
+
+    let pool = db.pool();
+    let entries = match db.report(ViewHistory::recent(pool, user_id, SHOWN_ENTRIES).await) {
+        Ok(entries) => entries,
+        Err(e) if is_connection_error(&e) => {
+            return respond(&ctx, t("database_unavailable")).await;
+        }
+        Err(e) => return Err(e.into()),
+    };
+    let tracking = match User::get_by_id(pool, &user_id).await {
+        Ok(user) => user.is_none_or(|user| user.track_history),
+        Err(_) => true,
+    };
+
+    let mut text = format!("🕘 <b>{}</b>\n\n", t("history"));
+    if entries.is_empty() {
+        text.push_str(&t("history_empty"));
+    }
+    if !tracking {
+        text.push_str(&format!("\n\n<i>{}</i>", t("history_disabled")));
+    }
+
+    let mut buttons = entries
+        .iter()
+        .filter_map(|entry| {
+            let kind = entry.kind()?;
+
+            Some(vec![button::inline(
+                format!("{0} {1}", kind.emoji(), shorten_text(&entry.title, 40)),
+                kind.callback_data(entry.media_id, user_id),
+            )])
+        })
+        .collect::<Vec<_>>();
+    if !entries.is_empty() {
+        buttons.push(vec![button::inline(
+            t("history_clear_btn"),
+            format!("history clear {}", user_id),
+        )]);
+    }
+
+    ctx.edit_or_reply(InputMessage::html(text).reply_markup(&reply_markup::inline(buttons)))
+        .await?;
+
+    Ok(())
+}
+
+/// Checks whether the sender may press the buttons of a callback, answering it if not.
+///
+/// # Arguments
+///
+/// * `ctx` - The context of the update.
+/// * `sender_id` - The ID of the sender.
+/// * `i18n` - The i18n resource.
+async fn check_sender(ctx: &Context, sender_id: i64, i18n: &I18n) -> Result<bool> {
+    let Some(query) = ctx.callback_query() else {
+        return Ok(true);
+    };
+
+    let text = ctx.query().unwrap();
+    let owner_id = text
+        .split_whitespace()
+        .last()
+        .and_then(|id| id.parse::<i64>().ok());
+    if owner_id != Some(sender_id) {
+        query
+            .answer()
+            .cache_time(Duration::from_secs(120))
+            .alert(i18n.translate("not_allowed"))
+            .send()
+            .await?;
+        return Ok(false);
+    }
+
+    Ok(true)
+}
+
+/// Answers a history action, with an alert for callbacks and a reply for commands.
+///
+/// # Arguments
+///
+/// * `ctx` - The context of the update.
+/// * `text` - The HTML text of the answer.
+async fn respond(ctx: &Context, text: String) -> Result<()> {
+    if let Some(query) = ctx.callback_query() {
+        query.answer().alert(remove_html(text)).send().await?;
+    } else {
+        ctx.reply(InputMessage::html(text)).await?;
+    }
+
+    Ok(())
+}
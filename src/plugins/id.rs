@@ -0,0 +1,98 @@
+// Copyright 2025 - Andriel Ferreira
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// https://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or https://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+//! The ID plugin.
+
+use ferogram::{Result, Router, filter, handler};
+use grammers_client::{InputMessage, types::Message};
+use maplit::hashmap;
+
+use crate::{
+    commands::{HelpCategory, HelpEntry},
+    models::MediaType,
+    resources::{AniList, I18n},
+    utils,
+};
+
+/// The commands of the plugin, for `/help`.
+pub const HELP: &[HelpEntry] = &[HelpEntry::new("id", HelpCategory::Search)];
+
+/// The plugin setup.
+pub fn setup(router: Router) -> Router {
+    router.register(
+        handler::new_message(
+            filter::command("id").description("Link AniList and MyAnimeList entries."),
+        )
+        .then(id),
+    )
+}
+
+/// The ID command handler.
+///
+/// Converts a MyAnimeList link into its AniList entry, and the other way around.
+async fn id(message: Message, i18n: I18n, ani: AniList) -> Result<()> {
+    let t = |key: &str| i18n.translate(key);
+    let t_a = |key: &str, args| i18n.translate_with_args(key, args);
+
+    let text = message.text();
+    let Some(link) = text.split_whitespace().nth(1) else {
+        message.reply(InputMessage::html(t("id_usage"))).await?;
+        return Ok(());
+    };
+
+    let resolved = if let Some((media_type, mal_id)) = utils::parse_mal_url(link) {
+        ani.resolve_mal(mal_id, media_type)
+            .await
+            .map(|anilist_id| (media_type, anilist_id))
+    } else {
+        utils::parse_anilist_url(link)
+    };
+    let Some((media_type, anilist_id)) = resolved else {
+        message.reply(InputMessage::html(t("not_found"))).await?;
+        return Ok(());
+    };
+
+    let media = match media_type {
+        MediaType::Anime => ani
+            .get_anime(anilist_id)
+            .await
+            .ok()
+            .map(|anime| (anime.title.romaji().to_string(), anime.id_mal)),
+        MediaType::Manga => ani
+            .get_manga(anilist_id)
+            .await
+            .ok()
+            .map(|manga| (manga.title.romaji().to_string(), manga.id_mal)),
+    };
+    let Some((title, mal_id)) = media else {
+        message.reply(InputMessage::html(t("not_found"))).await?;
+        return Ok(());
+    };
+
+    let path = media_type.as_str().to_lowercase();
+    let mal = match mal_id {
+        Some(mal_id) => format!(
+            "<a href=\"https://myanimelist.net/{0}/{1}\">{1}</a>",
+            path, mal_id
+        ),
+        None => "—".to_string(),
+    };
+
+    message
+        .reply(InputMessage::html(t_a(
+            "id_result",
+            hashmap! {
+                "title" => utils::escape_html(title),
+                "anilist" => format!("<a href=\"https://anilist.co/{0}/{1}\">{1}</a>", path, anilist_id),
+                "mal" => mal,
+            },
+        )))
+        .await?;
+
+    Ok(())
+}
@@ -6,35 +6,254 @@
 // option. This file may not be copied, modified, or distributed
 // except according to those terms.
 
-//! The start plugin.
+//! The inline plugin.
+
+use std::time::Duration;
 
 use ferogram::{Result, Router, filter, handler};
 use grammers_client::{
-    InputMessage,
+    InputMessage, button, reply_markup,
     types::{InlineQuery, inline},
 };
+use maplit::hashmap;
+
+use crate::{
+    Config,
+    models::ChatSettings,
+    plugins::{anime, manga},
+    resources::{AniList, Debounce, I18n, Seeds},
+    utils,
+};
+
+/// How long an inline search waits for a newer query from the same user before running.
+pub const DEBOUNCE_DELAY: Duration = Duration::from_millis(300);
+
+/// How many animes a random roll picks.
+const RANDOM_COUNT: usize = 3;
 
-use crate::resources::I18n;
+/// The minimum average score of a random pick.
+const RANDOM_MIN_SCORE: u8 = 70;
+
+/// How many animes and how many mangas a search without a prefix shows per page.
+const MIXED_COUNT: u16 = 5;
 
 /// The plugin setup.
 pub fn setup(router: Router) -> Router {
-    router.register(handler::inline_query(filter::always).then(inline))
+    router
+        .register(handler::inline_query(filter::regex(r"^[\.!]r(\s|$)")).then(random_inline))
+        .register(handler::inline_query(filter::always).then(inline))
+}
+
+/// Checks whether an inline search should run, to spare AniList the queries sent while the
+/// user is still typing.
+///
+/// Queries shorter than the configured minimum are asked to keep typing, and the first page
+/// of a query is dropped if a newer one from the same user arrives within [`DEBOUNCE_DELAY`].
+///
+/// # Arguments
+///
+/// * `query` - The inline query.
+/// * `arg` - The search terms of the query.
+/// * `config` - The bot configuration.
+/// * `debounce` - The inline debounce.
+/// * `i18n` - The i18n resource.
+pub async fn should_search(
+    query: &InlineQuery,
+    arg: &str,
+    config: &Config,
+    debounce: &Debounce<i64>,
+    i18n: &I18n,
+) -> Result<bool> {
+    let t = |key: &str| i18n.translate(key);
+    let t_a = |key: &str, args| i18n.translate_with_args(key, args);
+
+    if utils::is_inline_query_too_short(arg, config.telegram.inline_min_query_length) {
+        query
+            .answer(vec![
+                inline::query::Article::new(
+                    t("keep_typing"),
+                    InputMessage::html(t("how_to_use_inline_text")),
+                )
+                .description(t_a(
+                    "keep_typing_description",
+                    hashmap! { "count" => config.telegram.inline_min_query_length.to_string() },
+                )),
+            ])
+            .cache_time(0)
+            .private()
+            .send()
+            .await?;
+
+        return Ok(false);
+    }
+
+    if query.offset().is_empty() && !debounce.settle(query.sender().id()).await {
+        log::trace!(
+            "dropping the inline query of {}, a newer one arrived",
+            query.sender().id()
+        );
+        return Ok(false);
+    }
+
+    Ok(true)
+}
+
+/// The random inline handler.
+///
+/// Rolls a few random animes, optionally of a genre, e.g. `!r action`.
+async fn random_inline(
+    query: InlineQuery,
+    i18n: I18n,
+    ani: AniList,
+    seeds: Seeds,
+    settings: ChatSettings,
+) -> Result<()> {
+    let t = |key: &str| i18n.translate(key);
+    let t_a = |key: &str, args| i18n.translate_with_args(key, args);
+
+    let genre = query
+        .text()
+        .split_whitespace()
+        .skip(1)
+        .collect::<Vec<_>>()
+        .join(" ");
+    let genre = (!genre.is_empty()).then(|| utils::normalize_genre(&genre));
+
+    let seed = seeds
+        .seed(
+            query.sender().id(),
+            &format!("random {}", genre.as_deref().unwrap_or_default()),
+        )
+        .await;
+
+    let mut results = Vec::new();
+    if let Some(picks) = ani
+        .random_anime(genre.as_deref(), RANDOM_MIN_SCORE, RANDOM_COUNT, seed)
+        .await
+    {
+        for (i, pick) in picks.into_iter().enumerate() {
+            let title = t_a(
+                "random_pick",
+                hashmap! { "number" => (i + 1).to_string(), "title" => utils::preferred_title(&pick.title, &settings) },
+            );
+            results.push(anime::gen_anime_article_with_title(
+                &query, pick, title, &i18n, &settings,
+            ));
+        }
+    }
+
+    if results.is_empty() {
+        results.push(
+            inline::query::Article::new(
+                t("no_results"),
+                InputMessage::html(t("no_results_text")).reply_markup(&reply_markup::inline(vec![
+                    vec![button::switch_inline(t("search_again_btn"), "!r ")],
+                ])),
+            )
+            .description(t("click_for_more_info"))
+            .into(),
+        );
+    }
+
+    query.answer(results).cache_time(5).private().send().await?;
+
+    Ok(())
 }
 
 /// The inline handler.
-async fn inline(query: InlineQuery, i18n: I18n) -> Result<()> {
+///
+/// Searches animes and mangas at once when the query has no prefix, e.g. `Frieren`, and
+/// explains how to use the inline mode when it is empty.
+async fn inline(
+    query: InlineQuery,
+    i18n: I18n,
+    ani: AniList,
+    settings: ChatSettings,
+    config: Config,
+    debounce: Debounce<i64>,
+) -> Result<()> {
     let t = |key: &str| i18n.translate(key);
 
-    query
-        .answer(vec![
+    let arg = query.text().trim().to_string();
+    if arg.is_empty() {
+        query
+            .answer(vec![
+                inline::query::Article::new(
+                    t("how_to_use_inline"),
+                    InputMessage::html(t("how_to_use_inline_text")),
+                )
+                .description(t("click_for_more_info")),
+            ])
+            .cache_time(60)
+            .private()
+            .send()
+            .await?;
+
+        return Ok(());
+    }
+
+    if !should_search(&query, &arg, &config, &debounce, &i18n).await? {
+        return Ok(());
+    }
+
+    // Both searches fetch the same page, so the offset pages them together.
+    let offset = query.offset().parse::<u16>().unwrap_or(1);
+    let search = utils::parse_search_query(&arg);
+    let (animes, mangas) = tokio::join!(
+        ani.search_anime_sorted(&search.terms, search.sort, offset, MIXED_COUNT),
+        ani.search_manga_sorted(&search.terms, search.sort, offset, MIXED_COUNT),
+    );
+
+    let animes = animes
+        .unwrap_or_default()
+        .into_iter()
+        .map(|anime| {
+            let title = format!(
+                "📺 {0}{1}",
+                if anime.is_adult { "🔞 " } else { "" },
+                utils::preferred_title(&anime.title, &settings)
+            );
+            anime::gen_anime_article_with_title(&query, anime, title, &i18n, &settings)
+        })
+        .collect();
+    let mangas = mangas
+        .unwrap_or_default()
+        .into_iter()
+        .map(|manga| {
+            let title = format!(
+                "📚 {0}{1}",
+                if manga.is_adult { "🔞 " } else { "" },
+                utils::preferred_title(&manga.title, &settings)
+            );
+            manga::gen_manga_article_with_title(&query, manga, title, &i18n, &settings)
+        })
+        .collect();
+    let mut results = utils::interleave(animes, mangas);
+
+    if results.is_empty() {
+        let (title, text) = if offset == 1 {
+            ("no_results", "no_results_text")
+        } else {
+            ("no_more_results", "no_more_results_text")
+        };
+
+        results.push(
             inline::query::Article::new(
-                t("how_to_use_inline"),
-                InputMessage::html(t("how_to_use_inline_text")),
+                t(title),
+                InputMessage::html(t(text)).reply_markup(&reply_markup::inline(vec![vec![
+                    button::switch_inline(t("search_again_btn"), arg.clone()),
+                ]])),
             )
-            .description(t("click_for_more_info")),
-        ])
-        .cache_time(60)
+            .description(t("click_for_more_info"))
+            .into(),
+        );
+    }
+
+    query
+        .answer(results)
+        .cache_time(120)
         .private()
+        .next_offset((offset + 1).to_string())
         .send()
         .await?;
 
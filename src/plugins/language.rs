@@ -8,18 +8,23 @@
 
 //! Language plugin.
 
-use ferogram::{
-    Context, Filter, Result, Router, filter, handler,
-    utils::{bytes_to_string, split_btns_into_columns},
-};
+use ferogram::{Context, Filter, Result, Router, filter, handler, utils::split_btns_into_columns};
 use grammers_client::{InputMessage, button, reply_markup, types::Chat};
 use maplit::hashmap;
 
 use crate::{
+    commands::{HelpCategory, HelpEntry},
     models::{Group, UpdateUser, User, group::UpdateGroup},
-    resources::{Database, I18n},
+    resources::{
+        Database, I18n,
+        database::{Pool, is_connection_error},
+    },
+    utils,
 };
 
+/// The commands of the plugin, for `/help`.
+pub const HELP: &[HelpEntry] = &[HelpEntry::new("language", HelpCategory::Bot).aliases(&["lang"])];
+
 /// Language plugin setup.
 pub fn setup(router: Router) -> Router {
     router
@@ -32,12 +37,12 @@ pub fn setup(router: Router) -> Router {
             .then(language),
         )
         .register(
-            handler::callback_query(filter::regex("^language$").and(filter::administrator))
+            handler::callback_query(filter::regex(r"^language \S+$").and(filter::administrator))
                 .then(language),
         )
         .register(
             handler::callback_query(
-                filter::regex(r"^language set (\w+)$").and(filter::administrator),
+                filter::regex(r"^language set (\w+) \S+$").and(filter::administrator),
             )
             .then(language_set),
         )
@@ -59,16 +64,15 @@ async fn language(ctx: Context, i18n: I18n) -> Result<()> {
                     i18n.translate_from_locale("_NAME", locale),
                     if *locale == i18n.locale() { "✔" } else { "" },
                 ),
-                format!("language set {}", locale),
+                utils::sign_callback(&format!("language set {}", locale)),
             )
         })
         .collect::<Vec<_>>();
-    let buttons = split_btns_into_columns(buttons, 2);
+    let mut buttons = split_btns_into_columns(buttons, 2);
+    buttons.push(vec![button::inline(t("back_btn"), "settings")]);
 
-    ctx.edit_or_reply(
-        InputMessage::html(t("language")).reply_markup(&reply_markup::inline(buttons)),
-    )
-    .await?;
+    let message = InputMessage::html(t("language")).reply_markup(&reply_markup::inline(buttons));
+    utils::send_with_retry(|| ctx.edit_or_reply(message.clone())).await?;
 
     Ok(())
 }
@@ -82,54 +86,99 @@ async fn language_set(ctx: Context, db: Database, i18n: I18n) -> Result<()> {
     let query = ctx.callback_query().unwrap();
 
     let chat = query.chat();
-    let data = bytes_to_string(query.data());
+    let data = utils::callback_data(&query);
     let args = data.split_whitespace().skip(2).collect::<Vec<_>>();
 
     let language_code = args[0];
     if language_code == i18n.locale() {
-        query
-            .answer()
-            .alert(t_a(
-                "already_language",
-                hashmap! { "language" => i18n.translate_from_locale("_NAME", language_code) },
-            ))
-            .send()
-            .await?;
+        utils::send_with_retry(|| {
+            query
+                .answer()
+                .alert(t_a(
+                    "already_language",
+                    hashmap! { "language" => i18n.translate_from_locale("_NAME", language_code) },
+                ))
+                .send()
+        })
+        .await?;
         return Ok(());
     }
 
-    let mut success = false;
-    if let Chat::User(_) = chat {
-        if let Some(user) = User::get_by_id(pool, &chat.id()).await? {
-            let mut update_user: UpdateUser = user.into();
-            update_user.language_code = language_code.to_string();
-            update_user.update(pool).await?;
-
-            success = true;
-        } else {
-            log::warn!("user not found: {}", chat.id());
-        }
+    let result = if let Chat::User(_) = chat {
+        set_user_language(pool, chat.id(), language_code).await
     } else {
-        if let Some(group) = Group::get_by_id(pool, &chat.id()).await? {
-            let mut update_group: UpdateGroup = group.into();
-            update_group.language_code = language_code.to_string();
-            update_group.update(pool).await?;
-
-            success = true;
-        } else {
-            log::warn!("group not found: {}", chat.id());
+        set_group_language(pool, chat.id(), language_code).await
+    };
+
+    let success = match db.report(result) {
+        Ok(success) => success,
+        Err(e) if is_connection_error(&e) => {
+            utils::send_with_retry(|| query.answer().alert(t("database_unavailable")).send())
+                .await?;
+            return Ok(());
         }
-    }
+        Err(e) => return Err(e.into()),
+    };
 
     if success {
-        query
-            .answer()
-            .edit(InputMessage::html(t_a(
-                "new_language",
-                hashmap! { "new_language" => i18n.translate_from_locale("_NAME", language_code) },
-            )).reply_markup(&reply_markup::inline(vec![vec![button::inline(t("back_btn"), "language")]])))
-            .await?;
+        let message = InputMessage::html(t_a(
+            "new_language",
+            hashmap! { "new_language" => i18n.translate_from_locale("_NAME", language_code) },
+        ))
+        .reply_markup(&reply_markup::inline(vec![vec![button::inline(
+            t("back_btn"),
+            utils::sign_callback("language"),
+        )]]));
+        utils::send_with_retry(|| query.answer().edit(message.clone())).await?;
+    } else {
+        utils::send_with_retry(|| query.answer().alert(t("language_not_saved")).send()).await?;
     }
 
     Ok(())
 }
+
+/// Sets the language of a user.
+///
+/// Returns whether the user exists.
+///
+/// # Arguments
+///
+/// * `pool` - The database pool.
+/// * `id` - The user's ID.
+/// * `language_code` - The new language code.
+async fn set_user_language(pool: &Pool, id: i64, language_code: &str) -> sqlx::Result<bool> {
+    if let Some(user) = User::get_by_id(pool, &id).await? {
+        let mut update_user: UpdateUser = user.into();
+        update_user.language_code = language_code.to_string();
+        update_user.update(pool).await?;
+
+        Ok(true)
+    } else {
+        log::warn!("user not found: {}", id);
+
+        Ok(false)
+    }
+}
+
+/// Sets the language of a group.
+///
+/// Returns whether the group exists.
+///
+/// # Arguments
+///
+/// * `pool` - The database pool.
+/// * `id` - The group's ID.
+/// * `language_code` - The new language code.
+async fn set_group_language(pool: &Pool, id: i64, language_code: &str) -> sqlx::Result<bool> {
+    if let Some(group) = Group::get_by_id(pool, &id).await? {
+        let mut update_group: UpdateGroup = group.into();
+        update_group.language_code = language_code.to_string();
+        update_group.update(pool).await?;
+
+        Ok(true)
+    } else {
+        log::warn!("group not found: {}", id);
+
+        Ok(false)
+    }
+}
@@ -0,0 +1,113 @@
+// Copyright 2025 - Andriel Ferreira
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// https://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or https://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+//! The links plugin.
+
+use ferogram::{Context, Result, Router, filter, handler};
+
+use crate::{
+    models::{ChatSettings, Group},
+    plugins::{anime, character, list, manga, user},
+    resources::{AniList, Cards, Database, I18n},
+    utils::{self, AniListLink, CardResources},
+};
+
+/// The plugin setup.
+pub fn setup(router: Router) -> Router {
+    router.register(
+        handler::new_message(filter::regex(r"anilist\.co/(anime|manga|character|user)/"))
+            .then(link),
+    )
+}
+
+/// The AniList link handler.
+///
+/// Replies with the card of the first AniList link of a message. It always answers in private
+/// chats, but only in the groups that opted in. Links that could not be retrieved are ignored,
+/// the message was not meant for the bot after all.
+async fn link(
+    ctx: Context,
+    db: Database,
+    i18n: I18n,
+    ani: AniList,
+    cards: Cards,
+    settings: ChatSettings,
+) -> Result<()> {
+    let text = ctx.text().unwrap();
+
+    // The commands answer the links given to them themselves.
+    if text.starts_with('/') || !previews_links(&ctx, &db).await {
+        return Ok(());
+    }
+
+    let Some(link) = utils::find_anilist_link(&text) else {
+        return Ok(());
+    };
+
+    let sender = ctx.sender().unwrap();
+    let resources = CardResources {
+        cards: &cards,
+        i18n: &i18n,
+        ani: &ani,
+    };
+
+    match link {
+        AniListLink::Anime(id) => {
+            if let Ok(anime) = ani.get_anime(id).await {
+                let viewer = list::get_viewer(&db, sender.id()).await;
+                anime::send_anime_info(anime, ctx, resources, &settings, viewer.as_ref()).await?;
+            }
+        }
+        AniListLink::Manga(id) => {
+            if let Ok(manga) = ani.get_manga(id).await {
+                let viewer = list::get_viewer(&db, sender.id()).await;
+                manga::send_manga_info(manga, ctx, resources, &settings, viewer.as_ref()).await?;
+            }
+        }
+        AniListLink::Character(id) => {
+            if let Ok(char) = ani.get_char(id).await {
+                character::send_char_info(char, ctx, resources).await?;
+            }
+        }
+        AniListLink::User(name) => {
+            let found = ani.search_user(&name, 1, 6).await.and_then(|result| {
+                result
+                    .into_iter()
+                    .find(|user| user.name.eq_ignore_ascii_case(&name))
+            });
+            if let Some(found) = found {
+                user::send_user_info(&found, ctx, resources).await?;
+            }
+        }
+    }
+
+    Ok(())
+}
+
+/// Whether the chat gets a card for the AniList links sent to it.
+///
+/// # Arguments
+///
+/// * `ctx` - The context of the update.
+/// * `db` - The database resource.
+async fn previews_links(ctx: &Context, db: &Database) -> bool {
+    if ctx.is_private() {
+        return true;
+    }
+    if !db.is_healthy() {
+        return false;
+    }
+
+    let Some(chat) = ctx.chat() else {
+        return false;
+    };
+    match Group::get_by_id(db.pool(), &chat.id()).await {
+        Ok(group) => group.is_some_and(|group| group.link_preview),
+        Err(_) => false,
+    }
+}
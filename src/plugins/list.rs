@@ -0,0 +1,295 @@
+// Copyright 2025 - Andriel Ferreira
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// https://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or https://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+//! The AniList list plugin.
+
+use std::time::Duration;
+
+use ferogram::{Result, Router, filter, handler, utils::bytes_to_string};
+use grammers_client::{
+    button::{self, Inline},
+    types::CallbackQuery,
+};
+use maplit::hashmap;
+
+use crate::{
+    models::{AniListAuth, MediaType, User},
+    resources::{
+        AniList, Database, I18n,
+        anilist::{
+            graphql::QueryError,
+            lists::{ListEntry, ListStatus},
+        },
+        database::is_connection_error,
+    },
+    utils::{self, remove_html},
+};
+
+/// The plugin setup.
+pub fn setup(router: Router) -> Router {
+    router.register(
+        handler::callback_query(filter::regex(r"^list (anime|manga) (\w+) (\d+) (\d+)$"))
+            .then(list_set),
+    )
+}
+
+/// The list set callback handler.
+///
+/// Saves the media to the sender's AniList list with the chosen status.
+async fn list_set(
+    query: CallbackQuery,
+    db: Database,
+    i18n: I18n,
+    ani: AniList,
+    auth: AniListAuth,
+) -> Result<()> {
+    let t = |key: &str| i18n.translate(key);
+    let t_a = |key: &str, args| i18n.translate_with_args(key, args);
+
+    let data = bytes_to_string(query.data());
+    let args = data.split_whitespace().skip(1).collect::<Vec<_>>();
+
+    let media_type = match args[0] {
+        "anime" => MediaType::Anime,
+        _ => MediaType::Manga,
+    };
+    let media_id = args[2].parse::<i64>().unwrap();
+    let sender_id = args[3].parse::<i64>().unwrap();
+
+    if query.sender().id() != sender_id {
+        query
+            .answer()
+            .cache_time(Duration::from_secs(120))
+            .alert(t("not_allowed"))
+            .send()
+            .await?;
+        return Ok(());
+    }
+
+    let Some(status) = ListStatus::parse(args[1]) else {
+        query.answer().alert(t("not_implemented")).send().await?;
+        return Ok(());
+    };
+
+    if auth == AniListAuth::Expired {
+        query
+            .answer()
+            .alert(remove_html(t("list_token_expired")))
+            .send()
+            .await?;
+        return Ok(());
+    }
+
+    let token = match db.report(User::get_by_id(db.pool(), &sender_id).await) {
+        Ok(user) => user.and_then(|user| user.token()),
+        Err(e) if is_connection_error(&e) => {
+            query
+                .answer()
+                .alert(t("database_unavailable"))
+                .send()
+                .await?;
+            return Ok(());
+        }
+        Err(e) => return Err(e.into()),
+    };
+    let Some(token) = token else {
+        query
+            .answer()
+            .alert(remove_html(t("list_not_authenticated")))
+            .send()
+            .await?;
+        return Ok(());
+    };
+
+    match ani
+        .save_media_list_entry(&token, media_id, status, None, None)
+        .await
+    {
+        Ok(status) => {
+            query
+                .answer()
+                .text(t_a(
+                    "list_saved",
+                    hashmap! { "status" => status_label(status, media_type, &i18n) },
+                ))
+                .send()
+                .await?;
+        }
+        Err(QueryError::Unauthorized) => {
+            query
+                .answer()
+                .alert(remove_html(t("list_token_expired")))
+                .send()
+                .await?;
+        }
+        Err(QueryError::RateLimited(retry_after)) => {
+            query
+                .answer()
+                .alert(remove_html(utils::rate_limited_text(retry_after, &i18n)))
+                .send()
+                .await?;
+        }
+        Err(e) => {
+            log::warn!("failed to save the list entry of {}: {:?}", media_id, e);
+
+            query.answer().alert(t("list_save_failed")).send().await?;
+        }
+    }
+
+    Ok(())
+}
+
+/// Gets the database row of the user viewing a card.
+///
+/// Returns `None` if the user is not registered or the database is unavailable.
+///
+/// # Arguments
+///
+/// * `db` - The database resource.
+/// * `user_id` - The Telegram user ID.
+pub async fn get_viewer(db: &Database, user_id: i64) -> Option<User> {
+    if !db.is_healthy() {
+        return None;
+    }
+
+    db.report(User::get_by_id(db.pool(), &user_id).await)
+        .ok()
+        .flatten()
+}
+
+/// Generates the line of a card with the viewer's list status.
+///
+/// Returns `None` if the viewer is not authenticated, the media is not on their list
+/// or the entry could not be fetched.
+///
+/// # Arguments
+///
+/// * `ani` - The AniList resource.
+/// * `viewer` - The database row of the viewer, if registered.
+/// * `media_type` - The type of the media.
+/// * `media_id` - The AniList ID of the media.
+/// * `total` - The episodes or chapters of the media, if known.
+/// * `i18n` - The i18n resource.
+pub async fn gen_viewer_status(
+    ani: &AniList,
+    viewer: Option<&User>,
+    media_type: MediaType,
+    media_id: i64,
+    total: Option<i64>,
+    i18n: &I18n,
+) -> Option<String> {
+    let viewer = viewer.filter(|viewer| viewer.has_valid_token())?;
+    let (Some(anilist_id), Some(token)) = (viewer.anilist_id, viewer.token()) else {
+        return None;
+    };
+
+    let entry = ani
+        .get_media_list_entry(&token, anilist_id, media_id)
+        .await?;
+    gen_list_status(&entry, media_type, total, i18n)
+}
+
+/// Formats a list entry, like `Watching — 7/12, score 8`.
+///
+/// Returns `None` if the entry has no status.
+///
+/// # Arguments
+///
+/// * `entry` - The list entry.
+/// * `media_type` - The type of the media.
+/// * `total` - The episodes or chapters of the media, if known.
+/// * `i18n` - The i18n resource.
+pub fn gen_list_status(
+    entry: &ListEntry,
+    media_type: MediaType,
+    total: Option<i64>,
+    i18n: &I18n,
+) -> Option<String> {
+    let mut text = status_label(entry.status?, media_type, i18n);
+
+    if let Some(progress) = entry.progress.filter(|progress| *progress > 0) {
+        match total {
+            Some(total) => text.push_str(&format!(" — {0}/{1}", progress, total)),
+            None => text.push_str(&format!(" — {}", progress)),
+        }
+    }
+    if let Some(score) = entry.score.filter(|score| *score > 0.0) {
+        text.push_str(", ");
+        text.push_str(
+            &i18n.translate_with_args("list_score", hashmap! { "score" => score.to_string() }),
+        );
+    }
+
+    Some(format!(
+        "📝 | <b>{0}</b>: <i>{1}</i>\n",
+        i18n.translate("list_your_status"),
+        text
+    ))
+}
+
+/// Generates the buttons that save a media to the viewer's list.
+///
+/// # Arguments
+///
+/// * `media_type` - The type of the media.
+/// * `media_id` - The AniList ID of the media.
+/// * `sender_id` - The Telegram user ID of the viewer.
+/// * `i18n` - The i18n resource.
+pub fn gen_list_buttons(
+    media_type: MediaType,
+    media_id: i64,
+    sender_id: i64,
+    i18n: &I18n,
+) -> Vec<Inline> {
+    let t = |key: &str| i18n.translate(key);
+    let prefix = media_type.as_str().to_lowercase();
+
+    [
+        (ListStatus::Planning, "list_planning_btn"),
+        (
+            ListStatus::Current,
+            match media_type {
+                MediaType::Anime => "list_watching_btn",
+                MediaType::Manga => "list_reading_btn",
+            },
+        ),
+        (ListStatus::Completed, "list_completed_btn"),
+    ]
+    .into_iter()
+    .map(|(status, key)| {
+        button::inline(
+            t(key),
+            format!(
+                "list {0} {1} {2} {3}",
+                prefix,
+                status.as_str(),
+                media_id,
+                sender_id
+            ),
+        )
+    })
+    .collect()
+}
+
+/// Gets the translated name of a list status.
+///
+/// # Arguments
+///
+/// * `status` - The list status.
+/// * `media_type` - The type of the media, the current statuses are named after it.
+/// * `i18n` - The i18n resource.
+pub fn status_label(status: ListStatus, media_type: MediaType, i18n: &I18n) -> String {
+    let key = format!("list_status_{}", status.as_str().to_lowercase());
+
+    match status {
+        ListStatus::Current | ListStatus::Repeating => {
+            i18n.translate(&format!("{0}_{1}", key, media_type.as_str().to_lowercase()))
+        }
+        _ => i18n.translate(&key),
+    }
+}
@@ -10,22 +10,40 @@
 
 use std::time::Duration;
 
-use ferogram::{
-    Context, Result, Router, filter, handler,
-    utils::{bytes_to_string, split_btns_into_columns},
-};
+use async_trait::async_trait;
+use chrono::Utc;
+use ferogram::{Context, Result, Router, filter, handler, utils::split_btns_into_columns};
 use grammers_client::{
-    InputMessage, button, reply_markup,
-    types::{CallbackQuery, InlineQuery, inline},
+    InputMessage,
+    button::{self, Inline},
+    grammers_tl_types as tl, reply_markup,
+    types::{CallbackQuery, InlineQuery, Message, inline},
 };
 use maplit::hashmap;
 use rust_anilist::models::{Manga, RelationType};
 
 use crate::{
-    resources::{AniList, I18n},
-    utils::{self, gen_char_list, gen_pagination_buttons, remove_html, shorten_text},
+    Config,
+    commands::{HelpCategory, HelpEntry},
+    models::{ChatSettings, EntryKind, MediaType, User},
+    plugins::{favorites, genre, history, inline::should_search, list, settings},
+    resources::{
+        AniList, Cards, Database, Debounce, I18n, SearchStash,
+        anilist::{FetchError, characters},
+        search_stash::{SearchResult, StashedSearch},
+    },
+    utils::{
+        self, CardResources, Resolver, SearchQuery, SearchSort, StartPayload, gen_char_list,
+        gen_links_list, gen_recommendation_list, gen_signed_pagination_buttons, remove_html,
+        shorten_text,
+    },
 };
 
+/// The commands of the plugin, for `/help`.
+pub const HELP: &[HelpEntry] = &[HelpEntry::new("manga", HelpCategory::Search)
+    .aliases(&["m"])
+    .inline("!m")];
+
 /// The plugin setup.
 pub fn setup(router: Router) -> Router {
     router
@@ -35,113 +53,373 @@ pub fn setup(router: Router) -> Router {
             )
             .then(manga),
         )
-        .register(handler::callback_query(filter::regex(r"^manga (\d+)")).then(manga))
+        .register(handler::callback_query(filter::regex(r"^manga (\d+)")).then(manga_result))
         .register(
             handler::callback_query(filter::regex(
-                r"^manga (studios|chapters|staff|chars|tags|links) (\d+) (\d+)",
+                r"^manga (studios|chapters|staff|chars|tags|links|recs) (\d+) (\d+)",
             ))
             .then(manga_info),
         )
+        .register(
+            handler::callback_query(filter::regex(r"^manga sort (\w+) (\w+) (\d+) \S+$"))
+                .then(manga_sort),
+        )
+        .register(
+            handler::callback_query(filter::regex(r"^manga group (\w+) (\d+) (\d+) \S+$"))
+                .then(manga_group),
+        )
         .register(handler::inline_query(filter::regex(r"^[\.!]?m (.+)")).then(manga_inline))
 }
 
 /// The manga command handler.
-async fn manga(ctx: Context, i18n: I18n, ani: AniList) -> Result<()> {
-    let t = |key: &str| i18n.translate(key);
-    let t_a = |key: &str, args| i18n.translate_with_args(key, args);
+async fn manga(
+    ctx: Context,
+    message: Message,
+    i18n: I18n,
+    ani: AniList,
+    cards: Cards,
+    stash: SearchStash,
+    settings: ChatSettings,
+) -> Result<()> {
+    let resources = CardResources {
+        cards: &cards,
+        i18n: &i18n,
+        ani: &ani,
+    };
+
+    find_manga(ctx, Some(&message), resources, &stash, &settings).await
+}
 
-    let text = if ctx.is_callback_query() {
-        ctx.query()
-    } else {
-        ctx.text()
+/// The manga callback handler, for the buttons of the search results.
+async fn manga_result(
+    ctx: Context,
+    i18n: I18n,
+    ani: AniList,
+    cards: Cards,
+    stash: SearchStash,
+    settings: ChatSettings,
+) -> Result<()> {
+    let resources = CardResources {
+        cards: &cards,
+        i18n: &i18n,
+        ani: &ani,
+    };
+
+    find_manga(ctx, None, resources, &stash, &settings).await
+}
+
+/// Finds the mangas of a command or of a button of its results.
+async fn find_manga(
+    ctx: Context,
+    command: Option<&Message>,
+    resources: CardResources<'_>,
+    stash: &SearchStash,
+    settings: &ChatSettings,
+) -> Result<()> {
+    let CardResources { i18n, ani, .. } = resources;
+    let resolver = MangaResolver {
+        resources,
+        stash,
+        settings,
+    };
+
+    utils::resolve_or_disambiguate(
+        ctx,
+        command,
+        &resolver,
+        ani,
+        i18n,
+        settings.clean_service_messages,
+    )
+    .await
+}
+
+/// Finds the mangas of the command, see [`utils::resolve_or_disambiguate`].
+struct MangaResolver<'a> {
+    /// The resources the cards are sent with, the database for the viewer and their
+    /// preferences.
+    resources: CardResources<'a>,
+    /// The stash of the searches, for the sort buttons.
+    stash: &'a SearchStash,
+    /// The settings of the chat.
+    settings: &'a ChatSettings,
+}
+
+#[async_trait]
+impl Resolver for MangaResolver<'_> {
+    type Entry = Manga;
+
+    fn usage_key(&self) -> &'static str {
+        "manga_usage"
     }
-    .unwrap();
-    let mut args = text.split_whitespace().skip(1).collect::<Vec<_>>();
 
-    let sender = ctx.sender().unwrap();
+    fn inline_query(&self) -> &'static str {
+        "!m"
+    }
+
+    async fn fetch(&self, arg: &str) -> Option<std::result::Result<Manga, FetchError>> {
+        let reference = utils::parse_media_reference(arg, MediaType::Manga)?;
 
-    if let Some(query) = ctx.callback_query() {
-        let sender_id = args.pop().unwrap().parse::<i64>().unwrap();
+        Some(self.resources.ani.get_manga_by_reference(reference).await)
+    }
+
+    async fn refetch(&self, manga: Manga) -> Manga {
+        self.resources
+            .ani
+            .get_manga(manga.id)
+            .await
+            .unwrap_or(manga)
+    }
 
-        if sender.id() != sender_id {
+    async fn search(&self, query: &SearchQuery) -> Option<Vec<Manga>> {
+        self.resources
+            .ani
+            .search_manga_sorted(&query.terms, query.sort, 1, 6)
+            .await
+            .map(|mut result| {
+                result.retain(|manga| self.settings.allow_adult || !manga.is_adult);
+                result
+            })
+    }
+
+    async fn send(&self, manga: Manga, ctx: Context) -> Result<()> {
+        let viewer = list::get_viewer(self.resources.cards.db(), ctx.sender().unwrap().id()).await;
+
+        send_manga_info(manga, ctx, self.resources, self.settings, viewer.as_ref()).await
+    }
+
+    async fn gen_result_buttons(
+        &self,
+        result: Vec<Manga>,
+        query: &SearchQuery,
+        sender_id: i64,
+    ) -> Vec<Vec<Inline>> {
+        let results = result
+            .iter()
+            .map(|manga| search_result(manga, self.settings))
+            .collect::<Vec<_>>();
+        let stash_id = self
+            .stash
+            .insert(StashedSearch {
+                query: query.clone(),
+                results: results.clone(),
+            })
+            .await;
+        let group = !self.resources.cards.db().is_healthy()
+            || User::wants_grouped_results(self.resources.cards.db().pool(), sender_id).await;
+
+        utils::gen_search_buttons(
+            "manga",
+            &results,
+            &stash_id,
+            query.sort,
+            sender_id,
+            group,
+            self.resources.i18n,
+        )
+    }
+}
+
+/// The manga search sort callback handler.
+async fn manga_sort(
+    query: CallbackQuery,
+    db: Database,
+    i18n: I18n,
+    ani: AniList,
+    stash: SearchStash,
+    settings: ChatSettings,
+) -> Result<()> {
+    let t = |key: &str| i18n.translate(key);
+    let t_a = |key: &str, args| i18n.translate_with_args(key, args);
+
+    let data = utils::callback_data(&query);
+    let args = data.split_whitespace().skip(2).collect::<Vec<_>>();
+
+    let stash_id = args[0];
+    let sort = SearchSort::parse(args[1]).unwrap_or_default();
+    let sender_id = args[2].parse::<i64>().unwrap();
+
+    if query.sender().id() != sender_id {
+        utils::send_with_retry(|| {
             query
                 .answer()
                 .cache_time(Duration::from_secs(120))
                 .alert(t("not_allowed"))
                 .send()
-                .await?;
-            return Ok(());
+        })
+        .await?;
+        return Ok(());
+    }
+
+    let Some(mut search) = stash.get(stash_id).await else {
+        utils::send_with_retry(|| query.answer().alert(t("search_expired")).send()).await?;
+        return Ok(());
+    };
+    search.query.sort = sort;
+
+    match ani
+        .search_manga_sorted(&search.query.terms, search.query.sort, 1, 6)
+        .await
+        .map(|mut result| {
+            result.retain(|manga| settings.allow_adult || !manga.is_adult);
+            result
+        }) {
+        Some(result) if !result.is_empty() => {
+            search.results = result
+                .iter()
+                .map(|manga| search_result(manga, &settings))
+                .collect();
+            stash.replace(stash_id, search.clone()).await;
+
+            let group = !db.is_healthy() || User::wants_grouped_results(db.pool(), sender_id).await;
+            let buttons = utils::gen_search_buttons(
+                "manga",
+                &search.results,
+                stash_id,
+                search.query.sort,
+                sender_id,
+                group,
+                &i18n,
+            );
+
+            let message = InputMessage::html(t_a(
+                "search_results",
+                hashmap! { "search" => utils::escape_html(search.query.terms) },
+            ))
+            .reply_markup(&reply_markup::inline(buttons));
+            utils::send_with_retry(|| query.answer().edit(message.clone())).await?;
+        }
+        _ => {
+            utils::send_with_retry(|| query.answer().alert(t("no_results")).send()).await?;
         }
     }
 
-    if args.is_empty() {
-        ctx.reply(
-            InputMessage::html(t("manga_usage")).reply_markup(&reply_markup::inline(vec![vec![
-                button::switch_inline(t("search_btn"), "!m "),
-            ]])),
-        )
-        .await?;
-    } else {
-        if let Ok(id) = args[0].parse::<i64>() {
-            if let Ok(manga) = ani.get_manga(id).await {
-                send_manga_info(manga, ctx, &i18n).await?;
-            } else {
-                ctx.reply(InputMessage::html(t("not_found"))).await?;
-            }
-        } else {
-            let title = args.join(" ");
+    Ok(())
+}
 
-            if let Some(result) = ani.search_manga(&title, 1, 6).await {
-                if result.is_empty() {
-                    ctx.reply(InputMessage::html(t("no_results_text")).reply_markup(
-                        &reply_markup::inline(vec![vec![button::switch_inline(
-                            t("search_again_btn"),
-                            format!("!m {}", title),
-                        )]]),
-                    ))
-                    .await?;
-                    return Ok(());
-                } else if result.len() == 1 {
-                    let manga = ani.get_manga(result[0].id).await.unwrap_or_default();
-                    return send_manga_info(manga, ctx, &i18n).await;
-                }
-
-                let buttons = result
-                    .into_iter()
-                    .map(|manga| {
-                        vec![button::inline(
-                            if manga.is_adult { "🔞 " } else { "" }.to_string()
-                                + &manga.title.romaji(),
-                            format!("manga {0} {1}", manga.id, sender.id()),
-                        )]
-                    })
-                    .collect::<Vec<_>>();
+/// The manga search franchise group callback handler.
+async fn manga_group(query: CallbackQuery, i18n: I18n, stash: SearchStash) -> Result<()> {
+    let t = |key: &str| i18n.translate(key);
+    let t_a = |key: &str, args| i18n.translate_with_args(key, args);
 
-                ctx.reply(
-                    InputMessage::html(t_a("search_results", hashmap! { "search" => title }))
-                        .reply_markup(&reply_markup::inline(buttons)),
-                )
-                .await?;
-            } else {
-                ctx.reply(InputMessage::html(t("no_results_text")).reply_markup(
-                    &reply_markup::inline(vec![vec![button::switch_inline(
-                        t("search_again_btn"),
-                        format!("!m {}", title),
-                    )]]),
-                ))
-                .await?;
-            }
-        }
+    let data = utils::callback_data(&query);
+    let args = data.split_whitespace().skip(2).collect::<Vec<_>>();
+
+    let stash_id = args[0];
+    let index = args[1].parse::<usize>().unwrap();
+    let sender_id = args[2].parse::<i64>().unwrap();
+
+    if query.sender().id() != sender_id {
+        utils::send_with_retry(|| {
+            query
+                .answer()
+                .cache_time(Duration::from_secs(120))
+                .alert(t("not_allowed"))
+                .send()
+        })
+        .await?;
+        return Ok(());
+    }
+
+    let expanded = stash.get(stash_id).await.and_then(|search| {
+        utils::gen_franchise_buttons("manga", &search, stash_id, index, sender_id, &i18n)
+    });
+
+    if let Some((title, buttons)) = expanded {
+        let message = InputMessage::html(t_a(
+            "franchise_results",
+            hashmap! { "title" => utils::escape_html(title) },
+        ))
+        .reply_markup(&reply_markup::inline(buttons));
+        utils::send_with_retry(|| query.answer().edit(message.clone())).await?;
+    } else {
+        utils::send_with_retry(|| query.answer().alert(t("search_expired")).send()).await?;
     }
 
     Ok(())
 }
 
+/// Converts a manga into a stashable search result.
+fn search_result(manga: &Manga, settings: &ChatSettings) -> SearchResult {
+    SearchResult {
+        id: manga.id,
+        title: utils::preferred_title(&manga.title, settings),
+        is_adult: manga.is_adult,
+    }
+}
+
 /// Sends the manga info to the user.
-async fn send_manga_info(manga: Manga, ctx: Context, i18n: &I18n) -> Result<()> {
+///
+/// Adult mangas are replaced with a notice unless the chat allows adult content, and
+/// authenticated viewers get their list status and buttons to update it.
+///
+/// # Arguments
+///
+/// * `manga` - The manga to send.
+/// * `ctx` - The context of the update.
+/// * `resources` - The resources of the card, the AniList one for the viewer's list status.
+/// * `settings` - The settings of the chat.
+/// * `viewer` - The database row of the sender, if registered.
+pub async fn send_manga_info(
+    manga: Manga,
+    ctx: Context,
+    resources: CardResources<'_>,
+    settings: &ChatSettings,
+    viewer: Option<&User>,
+) -> Result<()> {
+    send_manga_info_with(manga, ctx, resources, settings, viewer, Vec::new()).await
+}
+
+/// Sends the manga info to the user, with an extra row of buttons, see [`send_manga_info`].
+///
+/// # Arguments
+///
+/// * `manga` - The manga to send.
+/// * `ctx` - The context of the update.
+/// * `resources` - The resources of the card, the AniList one for the viewer's list status.
+/// * `settings` - The settings of the chat.
+/// * `viewer` - The database row of the sender, if registered.
+/// * `extra` - The extra row of buttons, shown last unless it is empty.
+pub async fn send_manga_info_with(
+    manga: Manga,
+    ctx: Context,
+    resources: CardResources<'_>,
+    settings: &ChatSettings,
+    viewer: Option<&User>,
+    extra: Vec<Inline>,
+) -> Result<()> {
+    let CardResources { cards, i18n, ani } = resources;
     let t = |key: &str| i18n.translate(key);
 
-    let mut text = utils::gen_manga_info(&manga, i18n);
+    if manga.is_adult && !settings.allow_adult {
+        return settings::send_adult_content_hidden(ctx, i18n).await;
+    }
+
+    let title = utils::preferred_title(&manga.title, settings);
+    history::record_view(&ctx, cards.db(), EntryKind::Manga, manga.id, &title).await;
+
+    let mut text = utils::gen_manga_info(&manga, i18n, settings);
+    if let Some(status) = list::gen_viewer_status(
+        ani,
+        viewer,
+        MediaType::Manga,
+        manga.id,
+        manga.chapters.map(i64::from),
+        i18n,
+    )
+    .await
+    {
+        text.push_str(&status);
+    }
+    if let Some(fetched_at) = ani.stale_since(MediaType::Manga, manga.id).await {
+        text.push_str(&format!(
+            "\n{}\n",
+            i18n.translate_with_args(
+                "stale_data",
+                hashmap! { "time" => utils::humanize_ago(fetched_at, i18n, &Utc) },
+            )
+        ));
+    }
     let image_url = manga
         .banner
         .clone()
@@ -153,47 +431,61 @@ async fn send_manga_info(manga: Manga, ctx: Context, i18n: &I18n) -> Result<()>
     if manga.studios.is_some() {
         buttons.push(button::inline(
             t("studios_btn"),
-            format!("manga studios {0} {1}", manga.id, sender.id()),
+            utils::sign_callback(&format!("manga studios {0} {1}", manga.id, sender.id())),
         ));
     }
 
     if manga.chapters.is_some() {
         buttons.push(button::inline(
             t("chapters_btn"),
-            format!("manga chapters {0} {1}", manga.id, sender.id()),
+            utils::sign_callback(&format!("manga chapters {0} {1}", manga.id, sender.id())),
         ));
     }
 
     if manga.staff.is_some() {
         buttons.push(button::inline(
             t("staff_btn"),
-            format!("manga staff {0} {1}", manga.id, sender.id()),
+            utils::sign_callback(&format!("manga staff {0} {1}", manga.id, sender.id())),
         ));
     }
 
     if manga.characters().is_ok() {
         buttons.push(button::inline(
             t("characters_btn"),
-            format!("manga chars {0} {1}", manga.id, sender.id()),
+            utils::sign_callback(&format!("manga chars {0} {1}", manga.id, sender.id())),
         ));
     }
 
     if manga.tags.as_ref().is_some_and(|tags| !tags.is_empty()) {
         buttons.push(button::inline(
             t("tags_btn"),
-            format!("manga tags {0} {1}", manga.id, sender.id()),
+            utils::sign_callback(&format!("manga tags {0} {1}", manga.id, sender.id())),
         ));
     }
 
     if manga.external_links.is_some() {
         buttons.push(button::inline(
             t("links_btn"),
-            format!("manga links {0} {1}", manga.id, sender.id()),
+            utils::sign_callback(&format!("manga links {0} {1}", manga.id, sender.id())),
         ));
     }
 
+    buttons.push(button::inline(
+        t("recommendations_btn"),
+        utils::sign_callback(&format!("manga recs {0} {1}", manga.id, sender.id())),
+    ));
+
     let mut buttons = split_btns_into_columns(buttons, 2);
 
+    if viewer.is_some_and(|viewer| viewer.anilist_token.is_some()) {
+        buttons.push(list::gen_list_buttons(
+            MediaType::Manga,
+            manga.id,
+            sender.id(),
+            i18n,
+        ));
+    }
+
     if let Ok(relations) = manga.relations() {
         let mut relations_buttons = Vec::new();
 
@@ -209,13 +501,13 @@ async fn send_manga_info(manga: Manga, ctx: Context, i18n: &I18n) -> Result<()>
         if let Some(prequel) = prequel {
             relations_buttons.push(button::inline(
                 t("previous_btn"),
-                format!("manga {0} {1}", prequel.media().id(), sender.id()),
+                utils::sign_callback(&format!("manga {0} {1}", prequel.media().id(), sender.id())),
             ));
         }
         if let Some(sequel) = sequel {
             relations_buttons.push(button::inline(
                 t("next_btn"),
-                format!("manga {0} {1}", sequel.media().id(), sender.id()),
+                utils::sign_callback(&format!("manga {0} {1}", sequel.media().id(), sender.id())),
             ));
         }
 
@@ -224,47 +516,43 @@ async fn send_manga_info(manga: Manga, ctx: Context, i18n: &I18n) -> Result<()>
         }
     }
 
-    let markup = reply_markup::inline(buttons);
-
-    if ctx.is_callback_query() {
-        if let Some(image_url) = image_url.as_ref() {
-            text = format!("<a href=\"{}\">⁠</a>", image_url) + &text;
-        }
+    let favorite =
+        favorites::gen_favorite_button(&ctx, cards.favorites(), EntryKind::Manga, manga.id, i18n)
+            .await;
+    let share = utils::gen_share_button(StartPayload::Manga(manga.id), i18n);
+    let row = favorite.into_iter().chain(share).collect::<Vec<_>>();
+    if !row.is_empty() {
+        buttons.push(row);
+    }
 
-        if let Some(image_url) = image_url {
-            ctx.edit(
-                InputMessage::html(text)
-                    .link_preview(true)
-                    .photo_url(image_url)
-                    .reply_markup(&markup),
-            )
-            .await?;
-        } else {
-            ctx.edit(
-                InputMessage::html(text)
-                    .link_preview(true)
-                    .reply_markup(&markup),
-            )
-            .await?;
-        }
-    } else {
-        ctx.reply(
-            InputMessage::html(text)
-                .photo_url(image_url.unwrap_or_default())
-                .reply_markup(&markup),
-        )
-        .await?;
+    if !extra.is_empty() {
+        buttons.push(extra);
     }
 
+    utils::send_card(
+        &ctx,
+        cards.files(),
+        &text,
+        image_url.as_deref(),
+        buttons,
+        true,
+    )
+    .await?;
+
     Ok(())
 }
 
 /// The manga info handler.
-async fn manga_info(query: CallbackQuery, i18n: I18n, ani: AniList) -> Result<()> {
+async fn manga_info(
+    query: CallbackQuery,
+    i18n: I18n,
+    ani: AniList,
+    settings: ChatSettings,
+) -> Result<()> {
     let t = |key: &str| i18n.translate(key);
+    let t_a = |key: &str, args| i18n.translate_with_args(key, args);
 
-    let data = query.data();
-    let args = bytes_to_string(data)
+    let args = utils::callback_data(&query)
         .split_whitespace()
         .skip(1)
         .map(String::from)
@@ -277,139 +565,317 @@ async fn manga_info(query: CallbackQuery, i18n: I18n, ani: AniList) -> Result<()
     let sender = query.sender();
 
     if sender.id() != sender_id {
-        query
-            .answer()
-            .cache_time(Duration::from_secs(120))
-            .alert(t("not_allowed"))
-            .send()
-            .await?;
+        utils::send_with_retry(|| {
+            query
+                .answer()
+                .cache_time(Duration::from_secs(120))
+                .alert(t("not_allowed"))
+                .send()
+        })
+        .await?;
         return Ok(());
     }
 
-    if let Ok(mut manga) = ani.get_manga(manga_id).await {
-        let mut text = format!(
-            "<code>{0}</code> | <b>{1}</b>\n\n",
-            manga.id,
-            manga.title.romaji()
-        );
-
-        match info {
-            "studios" => {}
-            "chapters" => {}
-            "staff" => {}
-            "chars" => {
-                let page = args
-                    .get(3)
-                    .unwrap_or(&1.to_string())
-                    .parse::<usize>()
-                    .unwrap();
-                let characters = manga.characters().unwrap_or_default();
-
-                let per_page = 10;
-                let max_pages = (characters.len() as f32 / 15f32).round() as usize + 1;
-
-                if characters.is_empty() {
-                    query.answer().alert(t("not_available")).send().await?;
-                    return Ok(());
-                }
-
-                text.push_str(&gen_char_list(&characters, page, per_page, &i18n));
-                let buttons = gen_pagination_buttons(
-                    &format!("manga chars {0} {1}", manga_id, sender_id),
-                    page,
-                    max_pages,
-                );
+    let overview = ani.get_manga_overview(manga_id).await;
+    if let Err(FetchError::RateLimited(retry_after)) = overview {
+        utils::send_with_retry(|| {
+            query
+                .answer()
+                .alert(remove_html(utils::rate_limited_text(retry_after, &i18n)))
+                .send()
+        })
+        .await?;
+        return Ok(());
+    }
 
-                query
-                    .answer()
-                    .edit(
-                        InputMessage::html(text).reply_markup(&reply_markup::inline(vec![
-                            buttons,
-                            vec![button::inline(
-                                t("back_btn"),
-                                format!("manga {0} {1}", manga_id, sender_id),
-                            )],
-                        ])),
+    let Ok(overview) = overview else {
+        utils::send_with_retry(|| query.answer().alert(t("entry_unavailable")).send()).await?;
+        return Ok(());
+    };
+
+    let mut text = format!(
+        "<code>{0}</code> | <b>{1}</b>\n\n",
+        overview.id,
+        utils::escape_html(utils::preferred_title(&overview.title, &settings))
+    );
+
+    match info {
+        "studios" => {}
+        "chapters" => {
+            let Some(data) = ani.get_chapters(manga_id).await else {
+                utils::send_with_retry(|| {
+                    query
+                        .answer()
+                        .cache_time(Duration::from_secs(120))
+                        .alert(t("not_available"))
+                        .send()
+                })
+                .await?;
+                return Ok(());
+            };
+
+            let (chapters, volumes) = (data.chapters, data.volumes);
+            let links = data.reading_links();
+
+            if links.is_empty() {
+                let alert = match chapters {
+                    Some(chapters) => t_a(
+                        "chapters_count",
+                        hashmap! { "chapters" => chapters.to_string() },
+                    ),
+                    None => t("not_available"),
+                };
+                utils::send_with_retry(|| {
+                    query
+                        .answer()
+                        .cache_time(Duration::from_secs(120))
+                        .alert(alert.clone())
+                        .send()
+                })
+                .await?;
+                return Ok(());
+            }
+
+            if let Some(chapters) = chapters {
+                text.push_str(&format!(
+                    "📄 | <b>{0}</b>: <i>{1}</i>\n",
+                    t("chapters"),
+                    chapters
+                ));
+            }
+            if let Some(volumes) = volumes {
+                text.push_str(&format!(
+                    "📚 | <b>{0}</b>: <i>{1}</i>\n",
+                    t("volumes"),
+                    volumes
+                ));
+            }
+            if let (Some(chapters), Some(volumes @ 1..)) = (chapters, volumes) {
+                text.push_str(&format!(
+                    "📐 | <i>{}</i>\n",
+                    t_a(
+                        "chapters_per_volume",
+                        hashmap! { "average" => format!("{:.1}", chapters as f64 / volumes as f64) },
                     )
-                    .await?;
+                ));
             }
-            "tags" => {
-                if let Some(tags) = manga.tags.as_mut().take_if(|tags| !tags.is_empty()) {
-                    let tags = tags
-                        .iter()
-                        .map(|tag| {
-                            if tag.is_adult {
-                                format!("<s>{}</s>", tag.name)
-                            } else if tag.is_general_spoiler || tag.is_media_spoiler {
-                                format!("<details>{}</details>", tag.name)
-                            } else {
-                                tag.name.clone()
-                            }
-                        })
-                        .collect::<Vec<_>>()
-                        .join(", ");
-                    text.push_str(&format!("🏷 | <b>{0}</b>: <i>{1}</i>", t("tags"), tags));
 
+            text.push_str(&format!("\n📖 <b>{}</b>:\n", t("read_at")));
+            for link in links {
+                text.push_str(&format!(
+                    "🔗 | <a href=\"{}\">{}</a>\n",
+                    link.url, link.site
+                ));
+            }
+
+            utils::edit_card_page(
+                &query,
+                &text,
+                vec![vec![button::inline(
+                    t("back_btn"),
+                    utils::sign_callback(&format!("manga {0} {1}", manga_id, sender_id)),
+                )]],
+            )
+            .await?;
+        }
+        "staff" => {}
+        "chars" => {
+            let page = args
+                .get(3)
+                .unwrap_or(&1.to_string())
+                .parse::<u16>()
+                .unwrap();
+            let characters = ani
+                .get_media_characters(
+                    manga_id,
+                    MediaType::Manga,
+                    page,
+                    characters::PER_PAGE,
+                    settings.voice_actor_language,
+                )
+                .await
+                .filter(|characters| !characters.characters.is_empty());
+
+            let Some(characters) = characters else {
+                utils::send_with_retry(|| {
                     query
                         .answer()
-                        .edit(
-                            InputMessage::html(text).reply_markup(&reply_markup::inline(vec![
-                                vec![button::inline(
-                                    t("back_btn"),
-                                    format!("manga {0} {1}", manga_id, sender_id),
-                                )],
-                            ])),
-                        )
-                        .await?;
-                } else {
+                        .cache_time(Duration::from_secs(120))
+                        .alert(t(if page > 1 {
+                            "page_not_found"
+                        } else {
+                            "not_available"
+                        }))
+                        .send()
+                })
+                .await?;
+                return Ok(());
+            };
+
+            text.push_str(&gen_char_list(&characters.characters, &i18n));
+
+            let mut buttons = Vec::new();
+            if characters.last_page > 1 {
+                buttons.push(gen_signed_pagination_buttons(
+                    &format!("manga chars {0} {1}", manga_id, sender_id),
+                    page as usize,
+                    characters.last_page,
+                ));
+            }
+            buttons.push(vec![button::inline(
+                t("back_btn"),
+                utils::sign_callback(&format!("manga {0} {1}", manga_id, sender_id)),
+            )]);
+
+            utils::edit_card_page(&query, &text, buttons).await?;
+        }
+        "tags" => {
+            let mut manga = ani.get_manga(manga_id).await.unwrap_or_default();
+            if let Some(tags) = manga.tags.as_mut().take_if(|tags| !tags.is_empty()) {
+                let tags = tags
+                    .iter()
+                    .map(|tag| {
+                        if tag.is_adult {
+                            format!("<s>{}</s>", tag.name)
+                        } else if tag.is_general_spoiler || tag.is_media_spoiler {
+                            format!("<details>{}</details>", tag.name)
+                        } else {
+                            tag.name.clone()
+                        }
+                    })
+                    .collect::<Vec<_>>()
+                    .join(", ");
+                text.push_str(&format!("🏷 | <b>{0}</b>: <i>{1}</i>", t("tags"), tags));
+
+                // Only the tags safe to show become buttons.
+                let mut buttons = genre::gen_tag_buttons(
+                    manga
+                        .tags
+                        .iter()
+                        .flatten()
+                        .filter(|tag| {
+                            !tag.is_adult && !tag.is_general_spoiler && !tag.is_media_spoiler
+                        })
+                        .map(|tag| tag.name.as_str()),
+                    MediaType::Manga,
+                    sender_id,
+                );
+                buttons.push(vec![button::inline(
+                    t("back_btn"),
+                    utils::sign_callback(&format!("manga {0} {1}", manga_id, sender_id)),
+                )]);
+
+                utils::edit_card_page(&query, &text, buttons).await?;
+            } else {
+                utils::send_with_retry(|| {
                     query
                         .answer()
                         .cache_time(Duration::from_secs(120))
                         .alert(t("not_available"))
                         .send()
-                        .await?;
-                }
+                })
+                .await?;
             }
-            "links" => {
-                text.push_str(&format!("🖇 <b>{}</b>:\n", t("links")));
-
-                if let Some(links) = manga.external_links.as_ref() {
-                    for link in links.iter().filter(|l| l.is_disabled.is_none()) {
-                        text.push_str(&format!(
-                            "🔗 | <a href=\"{}\">{}</a>\n",
-                            link.url, link.site
-                        ));
-                    }
-                }
-
-                text.push_str(&format!("🔗 | <a href=\"{}\">AniList</a>\n", manga.url));
-                if let Some(id) = manga.id_mal {
-                    text.push_str(&format!(
-                        "🔗 | <a href=\"https://mymangalist.net/manga/{}\">MyAnimeList</a>",
-                        id
-                    ));
-                }
+        }
+        "recs" => {
+            let page = args
+                .get(3)
+                .unwrap_or(&1.to_string())
+                .parse::<u16>()
+                .unwrap();
+            let recommendations = ani
+                .get_recommendations(manga_id, page)
+                .await
+                .filter(|recommendations| !recommendations.recommendations.is_empty());
+
+            let Some(recommendations) = recommendations else {
+                utils::send_with_retry(|| {
+                    query
+                        .answer()
+                        .cache_time(Duration::from_secs(120))
+                        .alert(t(if page > 1 {
+                            "page_not_found"
+                        } else {
+                            "not_available"
+                        }))
+                        .send()
+                })
+                .await?;
+                return Ok(());
+            };
 
-                query
-                    .answer()
-                    .edit(
-                        InputMessage::html(text).reply_markup(&reply_markup::inline(vec![vec![
-                            button::inline(
-                                t("back_btn"),
-                                format!("manga {0} {1}", manga_id, sender_id),
-                            ),
-                        ]])),
+            text.push_str(&gen_recommendation_list(
+                &recommendations.recommendations,
+                &i18n,
+            ));
+
+            let buttons = recommendations
+                .recommendations
+                .iter()
+                .map(|recommendation| {
+                    button::inline(
+                        shorten_text(&recommendation.title, 40),
+                        utils::sign_callback(&format!(
+                            "{0} {1} {2}",
+                            match recommendation.media_type {
+                                MediaType::Anime => "anime",
+                                MediaType::Manga => "manga",
+                            },
+                            recommendation.id,
+                            sender_id
+                        )),
                     )
-                    .await?;
+                })
+                .collect::<Vec<_>>();
+            let mut buttons = split_btns_into_columns(buttons, 2);
+            if recommendations.last_page > 1 {
+                buttons.push(gen_signed_pagination_buttons(
+                    &format!("manga recs {0} {1}", manga_id, sender_id),
+                    page as usize,
+                    recommendations.last_page,
+                ));
             }
-            _ => {
+            buttons.push(vec![button::inline(
+                t("back_btn"),
+                utils::sign_callback(&format!("manga {0} {1}", manga_id, sender_id)),
+            )]);
+
+            utils::edit_card_page(&query, &text, buttons).await?;
+        }
+        "links" => {
+            let Some(links) = ani.get_media_links(manga_id).await else {
+                utils::send_with_retry(|| {
+                    query
+                        .answer()
+                        .cache_time(Duration::from_secs(120))
+                        .alert(t("not_available"))
+                        .send()
+                })
+                .await?;
+                return Ok(());
+            };
+
+            text.push_str(&gen_links_list(&links, MediaType::Manga, &i18n));
+
+            utils::edit_card_page(
+                &query,
+                &text,
+                vec![vec![button::inline(
+                    t("back_btn"),
+                    utils::sign_callback(&format!("manga {0} {1}", manga_id, sender_id)),
+                )]],
+            )
+            .await?;
+        }
+        _ => {
+            utils::send_with_retry(|| {
                 query
                     .answer()
                     .cache_time(Duration::from_secs(120))
                     .alert(t("not_implemented"))
                     .send()
-                    .await?
-            }
+            })
+            .await?;
         }
     }
 
@@ -417,7 +883,14 @@ async fn manga_info(query: CallbackQuery, i18n: I18n, ani: AniList) -> Result<()
 }
 
 /// The manga inline query handler.
-async fn manga_inline(query: InlineQuery, i18n: I18n, ani: AniList) -> Result<()> {
+async fn manga_inline(
+    query: InlineQuery,
+    i18n: I18n,
+    ani: AniList,
+    settings: ChatSettings,
+    config: Config,
+    debounce: Debounce<i64>,
+) -> Result<()> {
     let t = |key: &str| i18n.translate(key);
 
     let arg = query
@@ -426,12 +899,28 @@ async fn manga_inline(query: InlineQuery, i18n: I18n, ani: AniList) -> Result<()
         .skip(1)
         .collect::<Vec<_>>()
         .join(" ");
+
+    if !should_search(&query, &arg, &config, &debounce, &i18n).await? {
+        return Ok(());
+    }
+
     let offset = query.offset().parse::<u16>().unwrap_or(1);
     let mut results = Vec::new();
 
-    if let Some(result) = ani.search_manga(&arg, offset, 10).await {
+    let search = utils::parse_search_query(&arg);
+    if let Some((MediaType::Manga, mal_id)) = utils::parse_mal_reference(&arg, MediaType::Manga) {
+        // A MyAnimeList reference has a single result, so there is no next page.
+        let manga = match offset {
+            1 => ani.get_manga_by_mal_id(mal_id).await.ok(),
+            _ => None,
+        };
+        results.extend(manga.map(|manga| gen_manga_article(&query, manga, &i18n, &settings)));
+    } else if let Some(result) = ani
+        .search_manga_sorted(&search.terms, search.sort, offset, 10)
+        .await
+    {
         for manga in result {
-            let article = gen_manga_article(&query, manga, &i18n);
+            let article = gen_manga_article(&query, manga, &i18n, &settings);
             results.push(article);
         }
     }
@@ -448,7 +937,8 @@ async fn manga_inline(query: InlineQuery, i18n: I18n, ani: AniList) -> Result<()
                         )]],
                     )),
                 )
-                .description(t("click_for_more_info")),
+                .description(t("click_for_more_info"))
+                .into(),
             );
         } else {
             results.push(
@@ -461,7 +951,8 @@ async fn manga_inline(query: InlineQuery, i18n: I18n, ani: AniList) -> Result<()
                         )]]),
                     ),
                 )
-                .description(t("click_for_more_info")),
+                .description(t("click_for_more_info"))
+                .into(),
             );
         }
     }
@@ -469,6 +960,7 @@ async fn manga_inline(query: InlineQuery, i18n: I18n, ani: AniList) -> Result<()
     query
         .answer(results)
         .cache_time(120)
+        .private()
         .next_offset((offset + 1).to_string())
         .send()
         .await?;
@@ -476,33 +968,52 @@ async fn manga_inline(query: InlineQuery, i18n: I18n, ani: AniList) -> Result<()
     Ok(())
 }
 
-/// Generates an inline query article for a manga.
-fn gen_manga_article(query: &InlineQuery, manga: Manga, i18n: &I18n) -> inline::query::Article {
-    let t = |key: &str| i18n.translate(key);
-
-    let mut text = utils::gen_manga_info(&manga, &i18n);
-    let image_url = manga.banner.or(manga.cover.largest().map(String::from));
+/// Generates an inline query result for a manga, see [`utils::gen_card_result`].
+pub fn gen_manga_article(
+    query: &InlineQuery,
+    manga: Manga,
+    i18n: &I18n,
+    settings: &ChatSettings,
+) -> tl::enums::InputBotInlineResult {
+    let title = if manga.is_adult { "🔞 " } else { "" }.to_string()
+        + &utils::preferred_title(&manga.title, settings);
+
+    gen_manga_article_with_title(query, manga, title, i18n, settings)
+}
 
-    let sender = query.sender();
+/// Generates an inline query result for a manga, with a custom title.
+///
+/// Adult mangas get a notice article instead unless the chat allows adult content.
+pub fn gen_manga_article_with_title(
+    query: &InlineQuery,
+    manga: Manga,
+    title: String,
+    i18n: &I18n,
+    settings: &ChatSettings,
+) -> tl::enums::InputBotInlineResult {
+    let t = |key: &str| i18n.translate(key);
 
-    if let Some(image_url) = image_url.as_ref() {
-        text = format!("<a href=\"{}\">⁠</a>", image_url) + &text;
+    if manga.is_adult && !settings.allow_adult {
+        return settings::gen_adult_content_hidden_article(i18n).into();
     }
 
-    let mut article = inline::query::Article::new(
-        if manga.is_adult { "🔞 " } else { "" }.to_string() + &manga.title.romaji(),
-        InputMessage::html(text)
-            .link_preview(true)
-            .reply_markup(&reply_markup::inline(vec![vec![button::inline(
-                t("load_more_btn"),
-                format!("manga {0} {1}", manga.id, sender.id()),
-            )]])),
-    )
-    .description(shorten_text(remove_html(manga.description), 150));
+    let text = utils::gen_manga_info(&manga, i18n, settings);
+    let image_url = manga
+        .banner
+        .clone()
+        .or(manga.cover.largest().map(String::from));
 
-    if let Some(image_url) = image_url {
-        article = article.thumb_url(image_url);
-    }
+    let sender = query.sender();
 
-    article
+    utils::gen_card_result(
+        title,
+        shorten_text(remove_html(manga.description), 150),
+        &text,
+        image_url.as_deref(),
+        image_url.as_deref(),
+        (
+            t("load_more_btn"),
+            utils::sign_callback(&format!("manga {0} {1}", manga.id, sender.id())),
+        ),
+    )
 }
@@ -10,26 +10,117 @@
 
 use ferogram::Router;
 
-mod anime;
-mod auth;
-mod character;
-mod inline;
-mod language;
-mod manga;
-mod ping;
-mod start;
-mod user;
+use crate::{Config, commands::HelpEntry};
+
+pub mod about;
+pub mod anime;
+pub mod auth;
+pub mod birthdays;
+pub mod cancel;
+pub mod character;
+pub mod debug;
+pub mod diagnose;
+pub mod favorites;
+pub mod follow;
+pub mod genre;
+pub mod help;
+pub mod history;
+pub mod id;
+pub mod inline;
+pub mod language;
+pub mod links;
+pub mod list;
+pub mod manga;
+pub mod owner;
+pub mod ping;
+pub mod privacy;
+pub mod random;
+pub mod season;
+pub mod settings;
+pub mod staff;
+pub mod start;
+pub mod studio;
+pub mod sync_commands;
+pub mod top;
+pub mod trending;
+pub mod usage;
+pub mod user;
+pub mod watchparty;
 
 /// The plugins setup.
-pub fn setup(router: Router) -> Router {
-    router
+///
+/// # Arguments
+///
+/// * `router` - The router.
+/// * `config` - The bot configuration, for the admin commands.
+pub fn setup(router: Router, config: &Config) -> Router {
+    let router = router
         .extend(ping::setup)
         .extend(start::setup)
+        .extend(help::setup)
+        .extend(about::setup)
         .extend(language::setup)
+        .extend(settings::setup)
+        .extend(cancel::setup)
+        .extend(diagnose::setup)
         .extend(anime::setup)
         .extend(manga::setup)
         .extend(user::setup)
         .extend(character::setup)
+        .extend(trending::setup)
+        .extend(season::setup)
+        .extend(random::setup)
+        .extend(watchparty::setup)
+        .extend(birthdays::setup)
+        .extend(genre::setup)
+        .extend(top::setup)
+        .extend(follow::setup)
+        .extend(history::setup)
+        .extend(favorites::setup)
+        .extend(privacy::setup)
+        .extend(list::setup)
+        .extend(studio::setup)
+        .extend(staff::setup)
+        .extend(id::setup)
+        .extend(links::setup)
         .extend(inline::setup)
-        .extend(auth::setup)
+        .extend(auth::setup);
+    let router = usage::setup(router, config);
+    let router = debug::setup(router, config);
+    let router = owner::setup(router, config);
+
+    sync_commands::setup(router, config)
+}
+
+/// Gets the help entries of the plugins, in the order they are listed by `/help`.
+pub fn help_entries() -> Vec<HelpEntry> {
+    [
+        start::HELP,
+        help::HELP,
+        anime::HELP,
+        manga::HELP,
+        character::HELP,
+        user::HELP,
+        staff::HELP,
+        studio::HELP,
+        id::HELP,
+        trending::HELP,
+        season::HELP,
+        top::HELP,
+        genre::HELP,
+        random::HELP,
+        watchparty::HELP,
+        birthdays::HELP,
+        follow::HELP,
+        history::HELP,
+        favorites::HELP,
+        privacy::HELP,
+        auth::HELP,
+        language::HELP,
+        settings::HELP,
+        diagnose::HELP,
+        about::HELP,
+        ping::HELP,
+    ]
+    .concat()
 }
@@ -0,0 +1,482 @@
+// Copyright 2025 - Andriel Ferreira
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// https://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or https://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+//! The owner plugin.
+
+use std::time::Duration;
+
+use chrono::{Days, NaiveDate, Utc};
+use ferogram::{Filter, Result, Router, filter, handler};
+use grammers_client::{Client, InputMessage, InvocationError, Update, types::Message};
+use maplit::hashmap;
+use tokio::time::Instant;
+
+use crate::{
+    Config,
+    models::{Group, User},
+    resources::{Bans, Database, I18n, Maintenance, database::is_connection_error},
+    utils,
+};
+
+/// How many days `/gstats` covers.
+const GROWTH_DAYS: u64 = 7;
+
+/// The delay between two broadcast messages, keeping well under the flood limits.
+const SEND_DELAY: Duration = Duration::from_millis(50);
+
+/// How often the broadcast status message is updated.
+const PROGRESS_INTERVAL: Duration = Duration::from_secs(5);
+
+/// The errors Telegram answers with when a user can no longer receive messages from the bot.
+const BLOCKED_ERRORS: &[&str] = &[
+    "USER_IS_BLOCKED",
+    "INPUT_USER_DEACTIVATED",
+    "USER_DEACTIVATED",
+];
+
+/// The plugin setup.
+///
+/// # Arguments
+///
+/// * `router` - The router.
+/// * `config` - The bot configuration, for the sudoers.
+pub fn setup(router: Router, config: &Config) -> Router {
+    router
+        .register(
+            handler::new_message(filter::command("broadcast").and(sudoer(config))).then(broadcast),
+        )
+        .register(handler::new_message(filter::command("gstats").and(sudoer(config))).then(gstats))
+        .register(
+            handler::new_message(filter::command("maintenance").and(sudoer(config)))
+                .then(maintenance),
+        )
+        .register(handler::new_message(filter::command("ban").and(sudoer(config))).then(ban))
+        .register(handler::new_message(filter::command("unban").and(sudoer(config))).then(unban))
+}
+
+/// Filters the messages sent by the sudoers of the bot.
+///
+/// # Arguments
+///
+/// * `config` - The bot configuration.
+pub fn sudoer(config: &Config) -> impl Filter {
+    let telegram = config.telegram.clone();
+
+    move |_: Client, update: Update| {
+        let telegram = telegram.clone();
+
+        async move {
+            match update {
+                Update::NewMessage(message) => message
+                    .sender()
+                    .is_some_and(|sender| telegram.is_sudoer(sender.id())),
+                _ => false,
+            }
+        }
+    }
+}
+
+/// The broadcast command handler.
+///
+/// Sends the text after the command, as HTML, to every user that did not block the bot.
+async fn broadcast(client: Client, message: Message, db: Database, i18n: I18n) -> Result<()> {
+    let t = |key: &str| i18n.translate(key);
+
+    let text = message.text();
+    let content = text
+        .split_once(char::is_whitespace)
+        .map(|(_, content)| content.trim())
+        .unwrap_or_default();
+    if content.is_empty() {
+        message
+            .reply(InputMessage::html(t("broadcast_usage")))
+            .await?;
+        return Ok(());
+    }
+
+    let pool = db.pool();
+    let users = match db.report(User::reachable(pool).await) {
+        Ok(users) => users,
+        Err(e) if is_connection_error(&e) => {
+            message
+                .reply(InputMessage::html(t("database_unavailable")))
+                .await?;
+            return Ok(());
+        }
+        Err(e) => return Err(e.into()),
+    };
+
+    let mut progress = BroadcastProgress {
+        total: users.len(),
+        ..Default::default()
+    };
+    let status = message
+        .reply(InputMessage::html(gen_broadcast_progress(&progress, &i18n)))
+        .await?;
+    let mut last_update = Instant::now();
+
+    for (id, packed_chat) in users {
+        let Some(chat) = packed_chat.as_deref().and_then(utils::unpack_chat) else {
+            progress.failed += 1;
+            continue;
+        };
+
+        match client.send_message(chat, InputMessage::html(content)).await {
+            Ok(_) => progress.sent += 1,
+            Err(InvocationError::Rpc(e)) if is_blocked_error(&e.name) => {
+                progress.blocked += 1;
+
+                if let Err(e) = db.report(User::set_blocked(pool, id, true).await) {
+                    log::warn!("failed to record that {} blocked the bot: {:?}", id, e);
+                }
+            }
+            Err(e) => {
+                progress.failed += 1;
+
+                log::warn!("failed to broadcast to {}: {:?}", id, e);
+            }
+        }
+
+        if last_update.elapsed() >= PROGRESS_INTERVAL {
+            if let Err(e) = status
+                .edit(InputMessage::html(gen_broadcast_progress(&progress, &i18n)))
+                .await
+            {
+                log::warn!("failed to update the broadcast status: {:?}", e);
+            }
+            last_update = Instant::now();
+        }
+
+        tokio::time::sleep(SEND_DELAY).await;
+    }
+
+    status
+        .edit(InputMessage::html(gen_broadcast_report(&progress, &i18n)))
+        .await?;
+
+    Ok(())
+}
+
+/// The gstats command handler.
+///
+/// Reports how many users and groups the bot has, and how many joined in the last days.
+async fn gstats(message: Message, db: Database, i18n: I18n) -> Result<()> {
+    let t = |key: &str| i18n.translate(key);
+
+    let today = Utc::now().date_naive();
+    let since = (today - Days::new(GROWTH_DAYS - 1))
+        .and_hms_opt(0, 0, 0)
+        .unwrap()
+        .and_utc();
+
+    let pool = db.pool();
+    let growth = async {
+        let users = Growth {
+            total: User::count(pool).await?,
+            daily: User::count_by_day(pool, since).await?,
+            active: User::active_since(pool, since).await?,
+        };
+        let groups = Growth {
+            total: Group::count(pool).await?,
+            daily: Group::count_by_day(pool, since).await?,
+            active: Group::active_since(pool, since).await?,
+        };
+
+        Ok::<_, sqlx::Error>((users, groups))
+    };
+
+    let (users, groups) = match db.report(growth.await) {
+        Ok(growth) => growth,
+        Err(e) if is_connection_error(&e) => {
+            message
+                .reply(InputMessage::html(t("database_unavailable")))
+                .await?;
+            return Ok(());
+        }
+        Err(e) => return Err(e.into()),
+    };
+
+    message
+        .reply(InputMessage::html(gen_growth_report(
+            &users, &groups, today, &i18n,
+        )))
+        .await?;
+
+    Ok(())
+}
+
+/// The maintenance command handler.
+///
+/// Accepts `/maintenance on` and `/maintenance off`, and shows the current mode otherwise.
+async fn maintenance(message: Message, maintenance: Maintenance, i18n: I18n) -> Result<()> {
+    let t = |key: &str| i18n.translate(key);
+
+    let text = message.text();
+    let state = |enabled: bool| {
+        if enabled {
+            t("maintenance_on")
+        } else {
+            t("maintenance_off")
+        }
+    };
+
+    match text.split_whitespace().nth(1) {
+        Some("on") => {
+            maintenance.set(true);
+            log::info!("maintenance mode turned on");
+        }
+        Some("off") => {
+            maintenance.set(false);
+            log::info!("maintenance mode turned off");
+        }
+        _ => {
+            message
+                .reply(InputMessage::html(format!(
+                    "{0}\n\n{1}",
+                    state(maintenance.is_enabled()),
+                    t("maintenance_usage")
+                )))
+                .await?;
+            return Ok(());
+        }
+    }
+
+    message
+        .reply(InputMessage::html(state(maintenance.is_enabled())))
+        .await?;
+
+    Ok(())
+}
+
+/// The ban command handler.
+///
+/// Accepts `/ban <id> [reason]`, the sudoers cannot be banned.
+async fn ban(message: Message, config: Config, db: Database, bans: Bans, i18n: I18n) -> Result<()> {
+    let t = |key: &str| i18n.translate(key);
+
+    let text = message.text();
+    let Some((user_id, reason)) = parse_ban_args(text) else {
+        message.reply(InputMessage::html(t("ban_usage"))).await?;
+        return Ok(());
+    };
+    if config.telegram.is_sudoer(user_id) {
+        message.reply(InputMessage::html(t("ban_sudoer"))).await?;
+        return Ok(());
+    }
+
+    let sender = message.sender().unwrap();
+    match bans.ban(&db, user_id, reason, sender.id()).await {
+        Ok(()) => {
+            log::info!("{} banned {} for {:?}", sender.id(), user_id, reason);
+
+            message
+                .reply(InputMessage::html(i18n.translate_with_args(
+                    "user_banned",
+                    hashmap! { "id" => user_id.to_string() },
+                )))
+                .await?;
+        }
+        Err(e) if is_connection_error(&e) => {
+            message
+                .reply(InputMessage::html(t("database_unavailable")))
+                .await?;
+        }
+        Err(e) => return Err(e.into()),
+    }
+
+    Ok(())
+}
+
+/// The unban command handler.
+///
+/// Accepts `/unban <id>`.
+async fn unban(message: Message, db: Database, bans: Bans, i18n: I18n) -> Result<()> {
+    let t = |key: &str| i18n.translate(key);
+
+    let text = message.text();
+    let Some((user_id, _)) = parse_ban_args(text) else {
+        message.reply(InputMessage::html(t("unban_usage"))).await?;
+        return Ok(());
+    };
+
+    let key = match bans.unban(&db, user_id).await {
+        Ok(true) => {
+            log::info!("{} unbanned {}", message.sender().unwrap().id(), user_id);
+
+            "user_unbanned"
+        }
+        Ok(false) => "user_not_banned",
+        Err(e) if is_connection_error(&e) => "database_unavailable",
+        Err(e) => return Err(e.into()),
+    };
+    message
+        .reply(InputMessage::html(i18n.translate_with_args(
+            key,
+            hashmap! { "id" => user_id.to_string() },
+        )))
+        .await?;
+
+    Ok(())
+}
+
+/// Parses the arguments of `/ban` and `/unban`, the user ID and an optional reason.
+///
+/// # Arguments
+///
+/// * `text` - The text of the command, e.g. `/ban 123456 spam`.
+pub fn parse_ban_args(text: &str) -> Option<(i64, Option<&str>)> {
+    let mut args = text.trim().splitn(3, char::is_whitespace).skip(1);
+
+    let user_id = args.next()?.parse::<i64>().ok().filter(|id| *id > 0)?;
+    let reason = args
+        .next()
+        .map(str::trim)
+        .filter(|reason| !reason.is_empty());
+
+    Some((user_id, reason))
+}
+
+/// Whether an error answered by Telegram means the user can no longer receive messages.
+///
+/// # Arguments
+///
+/// * `name` - The name of the error, e.g. `USER_IS_BLOCKED`.
+pub fn is_blocked_error(name: &str) -> bool {
+    BLOCKED_ERRORS.contains(&name)
+}
+
+/// How far a broadcast went.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct BroadcastProgress {
+    /// How many users the broadcast is sent to.
+    pub total: usize,
+    /// How many users got the message.
+    pub sent: usize,
+    /// How many users blocked the bot.
+    pub blocked: usize,
+    /// How many users could not get the message for another reason.
+    pub failed: usize,
+}
+
+impl BroadcastProgress {
+    /// How many users were tried so far.
+    pub fn done(&self) -> usize {
+        self.sent + self.blocked + self.failed
+    }
+}
+
+/// Generates the status of a running broadcast.
+///
+/// # Arguments
+///
+/// * `progress` - How far the broadcast went.
+/// * `i18n` - The i18n resource.
+pub fn gen_broadcast_progress(progress: &BroadcastProgress, i18n: &I18n) -> String {
+    i18n.translate_with_args(
+        "broadcast_progress",
+        hashmap! {
+            "done" => utils::format_thousands(progress.done() as i64),
+            "total" => utils::format_thousands(progress.total as i64),
+        },
+    )
+}
+
+/// Generates the report of a finished broadcast.
+///
+/// # Arguments
+///
+/// * `progress` - How far the broadcast went.
+/// * `i18n` - The i18n resource.
+pub fn gen_broadcast_report(progress: &BroadcastProgress, i18n: &I18n) -> String {
+    i18n.translate_with_args(
+        "broadcast_report",
+        hashmap! {
+            "sent" => utils::format_thousands(progress.sent as i64),
+            "blocked" => utils::format_thousands(progress.blocked as i64),
+            "failed" => utils::format_thousands(progress.failed as i64),
+        },
+    )
+}
+
+/// How many rows a table has, how many were added on each of the last days and how many
+/// were active in them.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct Growth {
+    /// How many rows the table has.
+    pub total: i64,
+    /// How many rows were added on each day, skipping the days without any.
+    pub daily: Vec<(NaiveDate, i64)>,
+    /// How many rows interacted with the bot in the days.
+    pub active: i64,
+}
+
+impl Growth {
+    /// How many rows were added on a day.
+    ///
+    /// # Arguments
+    ///
+    /// * `day` - The day.
+    pub fn added_on(&self, day: NaiveDate) -> i64 {
+        self.daily
+            .iter()
+            .find(|(date, _)| *date == day)
+            .map_or(0, |(_, count)| *count)
+    }
+
+    /// How many rows were added in all the days.
+    pub fn added(&self) -> i64 {
+        self.daily.iter().map(|(_, count)| count).sum()
+    }
+}
+
+/// Generates the growth report text.
+///
+/// # Arguments
+///
+/// * `users` - The growth of the users.
+/// * `groups` - The growth of the groups.
+/// * `today` - The last day of the report.
+/// * `i18n` - The i18n resource.
+pub fn gen_growth_report(users: &Growth, groups: &Growth, today: NaiveDate, i18n: &I18n) -> String {
+    let mut text = format!(
+        "📈 <b>{0}</b>\n\n{1}\n{2}\n\n",
+        i18n.translate_with_args("gstats", hashmap! { "days" => GROWTH_DAYS.to_string() }),
+        i18n.translate_with_args(
+            "gstats_total",
+            hashmap! {
+                "users" => utils::format_thousands(users.total),
+                "new_users" => utils::format_thousands(users.added()),
+                "groups" => utils::format_thousands(groups.total),
+                "new_groups" => utils::format_thousands(groups.added()),
+            }
+        ),
+        i18n.translate_with_args(
+            "gstats_active",
+            hashmap! {
+                "users" => utils::format_thousands(users.active),
+                "groups" => utils::format_thousands(groups.active),
+            }
+        ),
+    );
+
+    for offset in (0..GROWTH_DAYS).rev() {
+        let day = today - Days::new(offset);
+
+        text.push_str(&i18n.translate_with_args(
+            "gstats_day",
+            hashmap! {
+                "day" => day.format("%Y-%m-%d").to_string(),
+                "users" => utils::format_thousands(users.added_on(day)),
+                "groups" => utils::format_thousands(groups.added_on(day)),
+            },
+        ));
+        text.push('\n');
+    }
+
+    text
+}
@@ -13,6 +13,14 @@ use std::time::Instant;
 use ferogram::{Result, Router, filter, handler};
 use grammers_client::{Client, InputMessage, grammers_tl_types as tl, types::Message};
 
+use crate::{
+    commands::{HelpCategory, HelpEntry},
+    resources::Database,
+};
+
+/// The commands of the plugin, for `/help`.
+pub const HELP: &[HelpEntry] = &[HelpEntry::new("ping", HelpCategory::Bot)];
+
 /// The plugin setup.
 pub fn setup(router: Router) -> Router {
     router.register(
@@ -21,7 +29,7 @@ pub fn setup(router: Router) -> Router {
 }
 
 /// The ping command handler.
-async fn ping(client: Client, message: Message) -> Result<()> {
+async fn ping(client: Client, message: Message, db: Database) -> Result<()> {
     let sent = message.reply(InputMessage::html("<b>Ping</b>...")).await?;
 
     let start = Instant::now();
@@ -32,9 +40,15 @@ async fn ping(client: Client, message: Message) -> Result<()> {
         .await?;
     let elapsed = start.elapsed().as_millis();
 
+    let database = if db.is_healthy() || db.health_check().await.is_some() {
+        "online"
+    } else {
+        "offline"
+    };
+
     sent.edit(InputMessage::html(format!(
-        "<b>Ping</b>... <b>Pong</b>! <code>{}</code>ms.",
-        elapsed
+        "<b>Ping</b>... <b>Pong</b>! <code>{0}</code>ms.\n<b>Database</b>: <code>{1}</code>.",
+        elapsed, database
     )))
     .await?;
 
@@ -0,0 +1,182 @@
+// Copyright 2025 - Andriel Ferreira
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// https://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or https://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+//! The privacy plugin.
+
+use std::{io::Cursor, time::Duration};
+
+use ferogram::{Context, Result, Router, filter, handler, utils::bytes_to_string};
+use grammers_client::{Client, InputMessage, button, reply_markup};
+
+use crate::{
+    commands::{HelpCategory, HelpEntry},
+    models::UserData,
+    resources::{Database, Favorites, I18n, Sessions, database::is_connection_error},
+    utils::remove_html,
+};
+
+/// The commands of the plugin, for `/help`.
+pub const HELP: &[HelpEntry] = &[
+    HelpEntry::new("export", HelpCategory::Account),
+    HelpEntry::new("deleteme", HelpCategory::Account),
+];
+
+/// The plugin setup.
+pub fn setup(router: Router) -> Router {
+    router
+        .register(
+            handler::new_message(
+                filter::command("export").description("Export the data the bot stores about you."),
+            )
+            .then(export),
+        )
+        .register(
+            handler::new_message(
+                filter::command("deleteme")
+                    .description("Delete the data the bot stores about you."),
+            )
+            .then(delete_me),
+        )
+        .register(
+            handler::callback_query(filter::regex(r"^deleteme (confirm|cancel) (\d+)$"))
+                .then(delete_me_answer),
+        )
+}
+
+/// The export handler.
+///
+/// Sends everything the bot stores about the sender as a JSON file.
+async fn export(ctx: Context, client: Client, db: Database, i18n: I18n) -> Result<()> {
+    let t = |key: &str| i18n.translate(key);
+
+    if !ctx.is_private() {
+        ctx.reply(InputMessage::html(t("only_private_command")))
+            .await?;
+        return Ok(());
+    }
+
+    let sender = ctx.sender().unwrap();
+
+    let data = match db.report(UserData::collect(db.pool(), sender.id()).await) {
+        Ok(data) => data,
+        Err(e) if is_connection_error(&e) => {
+            ctx.reply(InputMessage::html(t("database_unavailable")))
+                .await?;
+            return Ok(());
+        }
+        Err(e) => return Err(e.into()),
+    };
+
+    let document = serde_json::to_vec_pretty(&data.to_json())?;
+    let size = document.len();
+    let file = client
+        .upload_stream(
+            &mut Cursor::new(document),
+            size,
+            format!("export-{}.json", sender.id()),
+        )
+        .await?;
+
+    ctx.reply(InputMessage::html(t("export_caption")).document(file))
+        .await?;
+
+    Ok(())
+}
+
+/// The delete me handler.
+///
+/// Asks the sender to confirm the deletion of their data.
+async fn delete_me(ctx: Context, i18n: I18n) -> Result<()> {
+    let t = |key: &str| i18n.translate(key);
+
+    if !ctx.is_private() {
+        ctx.reply(InputMessage::html(t("only_private_command")))
+            .await?;
+        return Ok(());
+    }
+
+    let sender = ctx.sender().unwrap();
+
+    ctx.reply(
+        InputMessage::html(t("deleteme_confirm")).reply_markup(&reply_markup::inline(vec![vec![
+            button::inline(
+                t("deleteme_confirm_btn"),
+                format!("deleteme confirm {}", sender.id()),
+            ),
+            button::inline(
+                t("deleteme_cancel_btn"),
+                format!("deleteme cancel {}", sender.id()),
+            ),
+        ]])),
+    )
+    .await?;
+
+    Ok(())
+}
+
+/// The delete me confirmation callback handler.
+///
+/// Deletes the sender's data, including their AniList token, and forgets what is known of
+/// them from memory.
+async fn delete_me_answer(
+    ctx: Context,
+    db: Database,
+    i18n: I18n,
+    sessions: Sessions,
+    favorites: Favorites,
+) -> Result<()> {
+    let t = |key: &str| i18n.translate(key);
+
+    let query = ctx.callback_query().unwrap();
+
+    let data = bytes_to_string(query.data());
+    let args = data.split_whitespace().skip(1).collect::<Vec<_>>();
+
+    let sender_id = args[1].parse::<i64>().unwrap();
+
+    if query.sender().id() != sender_id {
+        query
+            .answer()
+            .cache_time(Duration::from_secs(120))
+            .alert(t("not_allowed"))
+            .send()
+            .await?;
+        return Ok(());
+    }
+
+    if args[0] == "cancel" {
+        query
+            .answer()
+            .edit(InputMessage::html(t("deleteme_cancelled")))
+            .await?;
+        return Ok(());
+    }
+
+    match db.report(UserData::delete(db.pool(), sender_id).await) {
+        Ok(()) => {
+            sessions.remove_client(sender_id).await;
+            sessions.cancel_attempt(sender_id).await;
+            favorites.forget_user(sender_id).await;
+
+            query
+                .answer()
+                .edit(InputMessage::html(t("deleteme_done")))
+                .await?;
+        }
+        Err(e) if is_connection_error(&e) => {
+            query
+                .answer()
+                .alert(remove_html(t("database_unavailable")))
+                .send()
+                .await?;
+        }
+        Err(e) => return Err(e.into()),
+    }
+
+    Ok(())
+}
@@ -0,0 +1,197 @@
+// Copyright 2025 - Andriel Ferreira
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// https://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or https://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+//! The random plugin.
+
+use std::time::Duration;
+
+use ferogram::{Context, Result, Router, filter, handler};
+use grammers_client::{InputMessage, button};
+use maplit::hashmap;
+
+use crate::{
+    commands::{HelpCategory, HelpEntry},
+    models::{ChatSettings, MediaType},
+    plugins::{anime, list, manga},
+    resources::{AniList, Cards, Database, I18n, anilist::FetchError},
+    utils::{self, CardResources, remove_html},
+};
+
+/// The commands of the plugin, for `/help`.
+pub const HELP: &[HelpEntry] = &[HelpEntry::new("random", HelpCategory::Discover).inline("!r")];
+
+/// The plugin setup.
+pub fn setup(router: Router) -> Router {
+    router
+        .register(
+            handler::new_message(
+                filter::command("random").description("Show a random anime or manga."),
+            )
+            .then(random),
+        )
+        .register(
+            handler::callback_query(filter::regex(r"^random (anime|manga) (\S+) (\d+)$"))
+                .then(random),
+        )
+}
+
+/// The random command and callback handler.
+///
+/// Shows a random well-rated anime, or manga with `/random manga`, optionally of a genre,
+/// e.g. `/random anime slice of life`. The "another one" button redraws it in place.
+async fn random(
+    ctx: Context,
+    db: Database,
+    i18n: I18n,
+    ani: AniList,
+    cards: Cards,
+    settings: ChatSettings,
+) -> Result<()> {
+    let t = |key: &str| i18n.translate(key);
+
+    let text = if ctx.is_callback_query() {
+        ctx.query()
+    } else {
+        ctx.text()
+    }
+    .unwrap();
+    let mut args = text.split_whitespace().skip(1).collect::<Vec<_>>();
+
+    let sender = ctx.sender().unwrap();
+
+    let (media_type, genre) = if let Some(query) = ctx.callback_query() {
+        let sender_id = args.pop().unwrap().parse::<i64>().unwrap();
+
+        if sender.id() != sender_id {
+            query
+                .answer()
+                .cache_time(Duration::from_secs(120))
+                .alert(t("not_allowed"))
+                .send()
+                .await?;
+            return Ok(());
+        }
+
+        // The genre is sent with underscores instead of spaces, or `-` for any genre.
+        let genre = (args[1] != "-").then(|| args[1].replace('_', " "));
+        (parse_media_type(args[0]), genre)
+    } else {
+        let media_type = args
+            .first()
+            .filter(|arg| matches!(arg.to_lowercase().as_str(), "anime" | "a" | "manga" | "m"))
+            .map(|arg| parse_media_type(&arg.to_lowercase()));
+        if media_type.is_some() {
+            args.remove(0);
+        }
+
+        let genre = args.join(" ");
+        (
+            media_type.unwrap_or(MediaType::Anime),
+            (!genre.is_empty()).then(|| utils::normalize_genre(&genre)),
+        )
+    };
+
+    let id = match ani.get_random_media(media_type, genre.as_deref()).await {
+        Ok(Some(id)) => id,
+        Ok(None) => {
+            let text = match genre.as_deref() {
+                Some(genre) => i18n.translate_with_args(
+                    "random_no_results",
+                    hashmap! { "genre" => utils::escape_html(genre) },
+                ),
+                None => t("no_results"),
+            };
+            return respond(&ctx, text).await;
+        }
+        Err(e) => return respond(&ctx, fetch_error_text(e, &i18n)).await,
+    };
+
+    let another = vec![button::inline(
+        t("random_btn"),
+        format!(
+            "random {0} {1} {2}",
+            match media_type {
+                MediaType::Anime => "anime",
+                MediaType::Manga => "manga",
+            },
+            genre
+                .as_deref()
+                .map_or("-".to_string(), |genre| genre.replace(' ', "_")),
+            sender.id()
+        ),
+    )];
+    let viewer = list::get_viewer(&db, sender.id()).await;
+    let resources = CardResources {
+        cards: &cards,
+        i18n: &i18n,
+        ani: &ani,
+    };
+
+    match media_type {
+        MediaType::Anime => match ani.get_anime(id).await {
+            Ok(anime) => {
+                anime::send_anime_info_with(
+                    anime,
+                    ctx,
+                    resources,
+                    &settings,
+                    viewer.as_ref(),
+                    another,
+                )
+                .await
+            }
+            Err(e) => respond(&ctx, fetch_error_text(e, &i18n)).await,
+        },
+        MediaType::Manga => match ani.get_manga(id).await {
+            Ok(manga) => {
+                manga::send_manga_info_with(
+                    manga,
+                    ctx,
+                    resources,
+                    &settings,
+                    viewer.as_ref(),
+                    another,
+                )
+                .await
+            }
+            Err(e) => respond(&ctx, fetch_error_text(e, &i18n)).await,
+        },
+    }
+}
+
+/// Gets the text of a failed request.
+fn fetch_error_text(error: FetchError, i18n: &I18n) -> String {
+    match error {
+        FetchError::RateLimited(retry_after) => utils::rate_limited_text(retry_after, i18n),
+        FetchError::NotFound => i18n.translate("not_found"),
+    }
+}
+
+/// Parses the media type of the command or callback, anime unless it is a manga.
+fn parse_media_type(arg: &str) -> MediaType {
+    match arg {
+        "manga" | "m" => MediaType::Manga,
+        _ => MediaType::Anime,
+    }
+}
+
+/// Answers a random roll that failed, with an alert for callbacks and a reply for commands.
+///
+/// # Arguments
+///
+/// * `ctx` - The context of the update.
+/// * `text` - The HTML text of the answer.
+async fn respond(ctx: &Context, text: String) -> Result<()> {
+    if let Some(query) = ctx.callback_query() {
+        query.answer().alert(remove_html(text)).send().await?;
+    } else {
+        ctx.reply(InputMessage::html(text)).await?;
+    }
+
+    Ok(())
+}
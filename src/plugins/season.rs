@@ -0,0 +1,161 @@
+// Copyright 2025 - Andriel Ferreira
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// https://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or https://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+//! The season plugin.
+
+use std::time::Duration;
+
+use chrono::Utc;
+use ferogram::{Context, Result, Router, filter, handler};
+use grammers_client::{InputMessage, button, reply_markup};
+use maplit::hashmap;
+
+use crate::{
+    commands::{HelpCategory, HelpEntry},
+    resources::{AniList, I18n},
+    utils::{
+        self, AnimeSeason, escape_html, gen_pagination_buttons_with, shorten_text, translate_enum,
+    },
+};
+
+/// How many animes are listed per page.
+const PER_PAGE: u16 = 10;
+
+/// The commands of the plugin, for `/help`.
+pub const HELP: &[HelpEntry] = &[HelpEntry::new("season", HelpCategory::Discover)];
+
+/// The plugin setup.
+pub fn setup(router: Router) -> Router {
+    router
+        .register(
+            handler::new_message(
+                filter::command("season").description("Browse the animes of a season."),
+            )
+            .then(season),
+        )
+        .register(
+            handler::callback_query(filter::regex(r"^season (\w+) (\d+) (\d+) (\d+)$"))
+                .then(season),
+        )
+}
+
+/// The season handler.
+///
+/// Accepts `/season`, for the current season, or `/season <season> <year>`.
+async fn season(ctx: Context, i18n: I18n, ani: AniList) -> Result<()> {
+    let t = |key: &str| i18n.translate(key);
+    let t_a = |key: &str, args| i18n.translate_with_args(key, args);
+
+    let text = if ctx.is_callback_query() {
+        ctx.query()
+    } else {
+        ctx.text()
+    }
+    .unwrap();
+    let mut args = text.split_whitespace().skip(1).collect::<Vec<_>>();
+
+    let sender = ctx.sender().unwrap();
+
+    if let Some(query) = ctx.callback_query() {
+        let sender_id = args.pop().unwrap().parse::<i64>().unwrap();
+
+        if sender.id() != sender_id {
+            query
+                .answer()
+                .cache_time(Duration::from_secs(120))
+                .alert(t("not_allowed"))
+                .send()
+                .await?;
+            return Ok(());
+        }
+    }
+
+    let (current_season, current_year) = AnimeSeason::of(&Utc::now());
+    let season = match args.first() {
+        Some(name) => AnimeSeason::parse(name),
+        None => Some(current_season),
+    };
+    let year = match args.get(1) {
+        Some(year) => year.parse::<i32>().ok(),
+        None => Some(current_year),
+    };
+    let page = args
+        .get(2)
+        .and_then(|page| page.parse::<u16>().ok())
+        .unwrap_or(1)
+        .max(1);
+
+    let (Some(season), Some(year)) = (season, year) else {
+        ctx.reply(InputMessage::html(t_a(
+            "season_usage",
+            hashmap! { "seasons" => AnimeSeason::KEYWORDS.map(|keyword| format!("<code>{}</code>", keyword)).join(", ") },
+        )))
+        .await?;
+        return Ok(());
+    };
+
+    let Some((animes, last_page)) = ani.get_season(season, year, page, PER_PAGE).await else {
+        ctx.reply(InputMessage::html(t("no_results"))).await?;
+        return Ok(());
+    };
+    if animes.is_empty() {
+        ctx.reply(InputMessage::html(t("no_results"))).await?;
+        return Ok(());
+    }
+
+    let mut text = format!(
+        "📅 <b>{}</b>:\n\n",
+        t_a(
+            "season_title",
+            hashmap! {
+                "season" => t(&format!("season_{}", season.name())),
+                "year" => year.to_string(),
+            },
+        )
+    );
+    let mut buttons = Vec::new();
+
+    for anime in animes.iter() {
+        text.push_str(&format!(
+            "<code>{0}</code> | <b>{1}</b>\n<i>{2}</i>",
+            anime.id,
+            escape_html(anime.title.romaji()),
+            translate_enum("format", &anime.format, &i18n)
+        ));
+        if let Some(score) = anime.average_score {
+            text.push_str(&format!(" · 🌟 <i>{:02}%</i>", score));
+        }
+        text.push('\n');
+
+        buttons.push(vec![button::inline(
+            shorten_text(anime.title.romaji(), 50),
+            utils::sign_callback(&format!("anime {0} {1}", anime.id, sender.id())),
+        )]);
+    }
+
+    if last_page > 1 {
+        buttons.push(gen_pagination_buttons_with(
+            page as usize,
+            last_page as usize,
+            |page| {
+                format!(
+                    "season {0} {1} {2} {3}",
+                    season.name(),
+                    year,
+                    page,
+                    sender.id()
+                )
+            },
+        ));
+    }
+
+    ctx.edit_or_reply(InputMessage::html(text).reply_markup(&reply_markup::inline(buttons)))
+        .await?;
+
+    Ok(())
+}
@@ -0,0 +1,623 @@
+// Copyright 2025 - Andriel Ferreira
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// https://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or https://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+//! Settings plugin.
+
+use std::time::Duration;
+
+use ferogram::{
+    Context, Filter, Result, Router, filter, handler,
+    utils::{bytes_to_string, split_btns_into_columns},
+};
+use grammers_client::{
+    InputMessage, button, reply_markup,
+    types::{Chat, inline},
+};
+use maplit::hashmap;
+
+use crate::{
+    commands::{self, HelpCategory, HelpEntry},
+    models::{
+        ChatSettings, Group, TitleLanguage, UpdateGroup, UpdateUser, User, VoiceActorLanguage,
+    },
+    resources::{
+        Database, DisabledCommands, I18n,
+        database::{Pool, is_connection_error},
+    },
+    utils,
+};
+
+/// The commands of the plugin, for `/help`.
+pub const HELP: &[HelpEntry] = &[HelpEntry::new("settings", HelpCategory::Bot)];
+
+/// How many seconds the usage and error replies of a group can stay, in the order the settings
+/// menu cycles through them. 0 keeps them.
+const CLEAN_SERVICE_MESSAGES: [i32; 4] = [0, 60, 5 * 60, 15 * 60];
+
+/// Settings plugin setup.
+pub fn setup(router: Router) -> Router {
+    router
+        .register(
+            handler::new_message(
+                filter::command("settings")
+                    .description("Change the bot settings.")
+                    .and(filter::administrator),
+            )
+            .then(settings),
+        )
+        .register(
+            handler::callback_query(filter::regex("^settings$").and(filter::administrator))
+                .then(settings),
+        )
+        .register(
+            handler::callback_query(
+                filter::regex(r"^settings set (\w+) (\w+)$").and(filter::administrator),
+            )
+            .then(settings_set),
+        )
+        .register(
+            handler::callback_query(
+                filter::regex("^settings commands$").and(filter::administrator),
+            )
+            .then(settings_commands),
+        )
+        .register(
+            handler::callback_query(
+                filter::regex(r"^settings command (\w+) (true|false)$").and(filter::administrator),
+            )
+            .then(settings_command_set),
+        )
+}
+
+/// The settings command handler.
+///
+/// `/settings commands` opens the commands of the group instead.
+async fn settings(
+    ctx: Context,
+    db: Database,
+    i18n: I18n,
+    settings: ChatSettings,
+    disabled_commands: DisabledCommands,
+) -> Result<()> {
+    let wants_commands = !ctx.is_callback_query()
+        && ctx
+            .text()
+            .is_some_and(|text| text.split_whitespace().nth(1) == Some("commands"));
+    if wants_commands && !ctx.is_private() {
+        return settings_commands(ctx, db, i18n, disabled_commands).await;
+    }
+
+    let user_toggles = user_toggles(&ctx, &db).await;
+    let toggles = group_toggles(&ctx, &db).await;
+
+    ctx.edit_or_reply(gen_settings_menu(&settings, user_toggles, toggles, &i18n))
+        .await?;
+
+    Ok(())
+}
+
+/// The settings set callback handler.
+async fn settings_set(
+    ctx: Context,
+    db: Database,
+    i18n: I18n,
+    mut settings: ChatSettings,
+) -> Result<()> {
+    let t = |key: &str| i18n.translate(key);
+    let pool = db.pool();
+
+    let query = ctx.callback_query().unwrap();
+
+    let chat = query.chat();
+    let data = bytes_to_string(query.data());
+    let args = data.split_whitespace().skip(2).collect::<Vec<_>>();
+
+    let mut user_toggles = user_toggles(&ctx, &db).await;
+    let mut toggles = group_toggles(&ctx, &db).await;
+
+    let updated = match args[0] {
+        "allow_adult" => args[1]
+            .parse::<bool>()
+            .map(|value| settings.allow_adult = value)
+            .is_ok(),
+        "title_language" => TitleLanguage::parse(args[1])
+            .map(|value| settings.title_language = value)
+            .is_some(),
+        "voice_actor_language" => VoiceActorLanguage::parse(args[1])
+            .map(|value| settings.voice_actor_language = value)
+            .is_some(),
+        // Only users group their search results, record their history and get the chapters.
+        "group_results" | "track_history" | "chapter_notifications" => {
+            match (user_toggles.as_mut(), args[1].parse::<bool>()) {
+                (Some(user_toggles), Ok(value)) => {
+                    match args[0] {
+                        "group_results" => user_toggles.group_results = value,
+                        "track_history" => user_toggles.track_history = value,
+                        _ => user_toggles.chapter_notifications = value,
+                    }
+                    true
+                }
+                _ => false,
+            }
+        }
+        // Only groups get the birthdays and the link previews, and clean the service messages.
+        "birthdays" | "link_preview" => match (toggles.as_mut(), args[1].parse::<bool>()) {
+            (Some(toggles), Ok(value)) => {
+                if args[0] == "birthdays" {
+                    toggles.birthdays = value;
+                } else {
+                    toggles.link_preview = value;
+                }
+                true
+            }
+            _ => false,
+        },
+        "clean_service_messages" => match (toggles.as_mut(), args[1].parse::<i32>()) {
+            (Some(toggles), Ok(value)) if CLEAN_SERVICE_MESSAGES.contains(&value) => {
+                toggles.clean_service_messages = value;
+                true
+            }
+            _ => false,
+        },
+        _ => false,
+    };
+    if !updated {
+        query.answer().alert(t("not_implemented")).send().await?;
+        return Ok(());
+    }
+
+    let result = if let Chat::User(_) = chat {
+        set_user_settings(pool, chat.id(), &settings, user_toggles).await
+    } else {
+        set_group_settings(pool, chat.id(), &settings, toggles).await
+    };
+
+    let success = match db.report(result) {
+        Ok(success) => success,
+        Err(e) if is_connection_error(&e) => {
+            query
+                .answer()
+                .alert(t("database_unavailable"))
+                .send()
+                .await?;
+            return Ok(());
+        }
+        Err(e) => return Err(e.into()),
+    };
+
+    if success {
+        query
+            .answer()
+            .edit(gen_settings_menu(&settings, user_toggles, toggles, &i18n))
+            .await?;
+    }
+
+    Ok(())
+}
+
+/// The settings commands handler.
+///
+/// Lists the commands the group can turn off, each with its toggle.
+async fn settings_commands(
+    ctx: Context,
+    db: Database,
+    i18n: I18n,
+    disabled_commands: DisabledCommands,
+) -> Result<()> {
+    let chat = ctx.chat().unwrap();
+    let disabled = disabled_commands.get(&db, chat.id()).await;
+
+    ctx.edit_or_reply(gen_commands_menu(&disabled, &i18n))
+        .await?;
+
+    Ok(())
+}
+
+/// The settings command set callback handler.
+async fn settings_command_set(
+    ctx: Context,
+    db: Database,
+    i18n: I18n,
+    disabled_commands: DisabledCommands,
+) -> Result<()> {
+    let t = |key: &str| i18n.translate(key);
+
+    let query = ctx.callback_query().unwrap();
+
+    let chat = query.chat();
+    let data = bytes_to_string(query.data());
+    let args = data.split_whitespace().skip(2).collect::<Vec<_>>();
+
+    let command = args[0];
+    let enabled = args[1] == "true";
+    if ctx.is_private()
+        || !commands::gateable_commands()
+            .iter()
+            .any(|entry| entry.command == command)
+    {
+        query.answer().alert(t("not_implemented")).send().await?;
+        return Ok(());
+    }
+
+    match disabled_commands
+        .set(&db, chat.id(), command, enabled)
+        .await
+    {
+        Ok(Some(disabled)) => {
+            query
+                .answer()
+                .edit(gen_commands_menu(&disabled, &i18n))
+                .await?;
+        }
+        Ok(None) => {
+            query.answer().alert(t("not_implemented")).send().await?;
+        }
+        Err(e) if is_connection_error(&e) => {
+            query
+                .answer()
+                .alert(t("database_unavailable"))
+                .send()
+                .await?;
+        }
+        Err(e) => return Err(e.into()),
+    }
+
+    Ok(())
+}
+
+/// Generates the menu of the commands a group can turn off.
+///
+/// # Arguments
+///
+/// * `disabled` - The commands turned off in the group.
+/// * `i18n` - The i18n resource.
+fn gen_commands_menu(disabled: &[String], i18n: &I18n) -> InputMessage {
+    let t = |key: &str| i18n.translate(key);
+
+    let buttons = commands::gateable_commands()
+        .into_iter()
+        .map(|entry| {
+            let is_disabled = disabled.iter().any(|command| command == entry.command);
+
+            button::inline(
+                i18n.translate_with_args(
+                    "settings_command_btn",
+                    hashmap! {
+                        "command" => entry.command.to_string(),
+                        "value" => t(if is_disabled { "off" } else { "on" }),
+                    },
+                ),
+                format!("settings command {} {}", entry.command, is_disabled),
+            )
+        })
+        .collect::<Vec<_>>();
+    let mut buttons = split_btns_into_columns(buttons, 2);
+    buttons.push(vec![button::inline(t("back_btn"), "settings")]);
+
+    InputMessage::html(t("settings_commands")).reply_markup(&reply_markup::inline(buttons))
+}
+
+/// The settings only users have.
+#[derive(Clone, Copy, Debug)]
+struct UserToggles {
+    /// Whether the user wants search results grouped by franchise.
+    group_results: bool,
+    /// Whether the cards the user opens are recorded in their history.
+    track_history: bool,
+    /// Whether the user is notified of the new chapters of the mangas they are reading.
+    chapter_notifications: bool,
+}
+
+impl Default for UserToggles {
+    fn default() -> Self {
+        Self {
+            group_results: true,
+            track_history: true,
+            chapter_notifications: false,
+        }
+    }
+}
+
+/// Gets the settings only users have, `None` outside private chats.
+///
+/// Users that are not registered, or whose settings could not be read, get the defaults.
+///
+/// # Arguments
+///
+/// * `ctx` - The context of the update.
+/// * `db` - The database resource.
+async fn user_toggles(ctx: &Context, db: &Database) -> Option<UserToggles> {
+    if !ctx.is_private() {
+        return None;
+    }
+
+    let sender = ctx.sender()?;
+    if !db.is_healthy() {
+        return Some(UserToggles::default());
+    }
+
+    let user = User::get_by_id(db.pool(), &sender.id())
+        .await
+        .ok()
+        .flatten();
+    Some(user.map_or_else(UserToggles::default, |user| UserToggles {
+        group_results: user.group_results,
+        track_history: user.track_history,
+        chapter_notifications: user.chapter_notifications,
+    }))
+}
+
+/// The settings only groups have.
+#[derive(Clone, Copy, Debug)]
+struct GroupToggles {
+    /// Whether the group gets the character birthdays every day.
+    birthdays: bool,
+    /// Whether the group gets a card for the AniList links sent to it.
+    link_preview: bool,
+    /// How many seconds the usage and error replies stay, 0 to keep them.
+    clean_service_messages: i32,
+}
+
+/// Gets the settings only groups have, `None` in private chats.
+///
+/// # Arguments
+///
+/// * `ctx` - The context of the update.
+/// * `db` - The database resource.
+async fn group_toggles(ctx: &Context, db: &Database) -> Option<GroupToggles> {
+    if ctx.is_private() || !db.is_healthy() {
+        return None;
+    }
+
+    let chat = ctx.chat()?;
+    match Group::get_by_id(db.pool(), &chat.id()).await {
+        Ok(group) => group.map(|group| GroupToggles {
+            birthdays: group.birthdays,
+            link_preview: group.link_preview,
+            clean_service_messages: group.clean_service_messages,
+        }),
+        Err(_) => None,
+    }
+}
+
+/// Gets the time the usage and error replies stay that follows this one in the settings menu.
+///
+/// # Arguments
+///
+/// * `seconds` - The current time, in seconds.
+fn next_clean_service_messages(seconds: i32) -> i32 {
+    let index = CLEAN_SERVICE_MESSAGES
+        .iter()
+        .position(|choice| *choice == seconds)
+        .map_or(0, |index| index + 1);
+
+    CLEAN_SERVICE_MESSAGES[index % CLEAN_SERVICE_MESSAGES.len()]
+}
+
+/// Generates the settings menu.
+///
+/// Each row shows a setting and its current value, tapping it cycles the value.
+///
+/// # Arguments
+///
+/// * `settings` - The current chat settings.
+/// * `user_toggles` - The settings only users have, `None` in groups.
+/// * `toggles` - The settings only groups have, `None` in private chats.
+/// * `i18n` - The i18n resource.
+fn gen_settings_menu(
+    settings: &ChatSettings,
+    user_toggles: Option<UserToggles>,
+    toggles: Option<GroupToggles>,
+    i18n: &I18n,
+) -> InputMessage {
+    let t = |key: &str| i18n.translate(key);
+    let t_a = |key: &str, args| i18n.translate_with_args(key, args);
+    let on_off = |value: bool| t(if value { "on" } else { "off" });
+
+    let mut buttons = vec![
+        vec![button::inline(
+            t_a("settings_language_btn", hashmap! { "value" => t("_NAME") }),
+            utils::sign_callback("language"),
+        )],
+        vec![button::inline(
+            t_a(
+                "settings_allow_adult_btn",
+                hashmap! { "value" => on_off(settings.allow_adult) },
+            ),
+            format!("settings set allow_adult {}", !settings.allow_adult),
+        )],
+        vec![button::inline(
+            t_a(
+                "settings_title_language_btn",
+                hashmap! { "value" => t(&format!("title_{}", settings.title_language.as_str())) },
+            ),
+            format!(
+                "settings set title_language {}",
+                settings.title_language.next().as_str()
+            ),
+        )],
+        vec![button::inline(
+            t_a(
+                "settings_voice_actor_language_btn",
+                hashmap! { "value" => t(&format!("voice_actor_{}", settings.voice_actor_language.as_str())) },
+            ),
+            format!(
+                "settings set voice_actor_language {}",
+                settings.voice_actor_language.next().as_str()
+            ),
+        )],
+    ];
+
+    if let Some(user_toggles) = user_toggles {
+        buttons.push(vec![button::inline(
+            t_a(
+                "settings_group_results_btn",
+                hashmap! { "value" => on_off(user_toggles.group_results) },
+            ),
+            format!("settings set group_results {}", !user_toggles.group_results),
+        )]);
+        buttons.push(vec![button::inline(
+            t_a(
+                "settings_track_history_btn",
+                hashmap! { "value" => on_off(user_toggles.track_history) },
+            ),
+            format!("settings set track_history {}", !user_toggles.track_history),
+        )]);
+        buttons.push(vec![button::inline(
+            t_a(
+                "settings_chapter_notifications_btn",
+                hashmap! { "value" => on_off(user_toggles.chapter_notifications) },
+            ),
+            format!(
+                "settings set chapter_notifications {}",
+                !user_toggles.chapter_notifications
+            ),
+        )]);
+    }
+
+    if let Some(toggles) = toggles {
+        buttons.push(vec![button::inline(
+            t_a(
+                "settings_birthdays_btn",
+                hashmap! { "value" => on_off(toggles.birthdays) },
+            ),
+            format!("settings set birthdays {}", !toggles.birthdays),
+        )]);
+        buttons.push(vec![button::inline(
+            t_a(
+                "settings_link_preview_btn",
+                hashmap! { "value" => on_off(toggles.link_preview) },
+            ),
+            format!("settings set link_preview {}", !toggles.link_preview),
+        )]);
+        let clean_after = match toggles.clean_service_messages {
+            0 => t("off"),
+            seconds => utils::format_uptime(Duration::from_secs(seconds as u64)),
+        };
+        buttons.push(vec![button::inline(
+            t_a(
+                "settings_clean_service_messages_btn",
+                hashmap! { "value" => clean_after },
+            ),
+            format!(
+                "settings set clean_service_messages {}",
+                next_clean_service_messages(toggles.clean_service_messages)
+            ),
+        )]);
+        buttons.push(vec![button::inline(
+            t("settings_commands_btn"),
+            "settings commands",
+        )]);
+    }
+
+    InputMessage::html(t("settings")).reply_markup(&reply_markup::inline(buttons))
+}
+
+/// Tells the user an adult entry was hidden, pointing to the settings.
+///
+/// # Arguments
+///
+/// * `ctx` - The context of the update.
+/// * `i18n` - The i18n resource.
+pub async fn send_adult_content_hidden(ctx: Context, i18n: &I18n) -> Result<()> {
+    let t = |key: &str| i18n.translate(key);
+
+    ctx.edit_or_reply(InputMessage::html(t("adult_content_hidden")).reply_markup(
+        &reply_markup::inline(vec![vec![button::inline(t("settings_btn"), "settings")]]),
+    ))
+    .await?;
+
+    Ok(())
+}
+
+/// Generates the inline query article that replaces a hidden adult entry.
+///
+/// # Arguments
+///
+/// * `i18n` - The i18n resource.
+pub fn gen_adult_content_hidden_article(i18n: &I18n) -> inline::query::Article {
+    let t = |key: &str| i18n.translate(key);
+
+    inline::query::Article::new(
+        t("adult_content_hidden_title"),
+        InputMessage::html(t("adult_content_hidden")),
+    )
+    .description(t("adult_content_hidden_description"))
+}
+
+/// Sets the settings of a user.
+///
+/// Returns whether the user exists.
+///
+/// # Arguments
+///
+/// * `pool` - The database pool.
+/// * `id` - The user's ID.
+/// * `settings` - The new settings.
+/// * `user_toggles` - The settings only users have, if known.
+async fn set_user_settings(
+    pool: &Pool,
+    id: i64,
+    settings: &ChatSettings,
+    user_toggles: Option<UserToggles>,
+) -> sqlx::Result<bool> {
+    if let Some(user) = User::get_by_id(pool, &id).await? {
+        let mut update_user: UpdateUser = user.into();
+        update_user.allow_adult = settings.allow_adult;
+        update_user.title_language = settings.title_language.as_str().to_string();
+        update_user.voice_actor_language = settings.voice_actor_language.as_str().to_string();
+        if let Some(user_toggles) = user_toggles {
+            update_user.group_results = user_toggles.group_results;
+            update_user.track_history = user_toggles.track_history;
+            update_user.chapter_notifications = user_toggles.chapter_notifications;
+        }
+        update_user.update(pool).await?;
+
+        Ok(true)
+    } else {
+        log::warn!("user not found: {}", id);
+
+        Ok(false)
+    }
+}
+
+/// Sets the settings of a group.
+///
+/// Returns whether the group exists.
+///
+/// # Arguments
+///
+/// * `pool` - The database pool.
+/// * `id` - The group's ID.
+/// * `settings` - The new settings.
+/// * `toggles` - The settings only groups have, if known.
+async fn set_group_settings(
+    pool: &Pool,
+    id: i64,
+    settings: &ChatSettings,
+    toggles: Option<GroupToggles>,
+) -> sqlx::Result<bool> {
+    if let Some(group) = Group::get_by_id(pool, &id).await? {
+        let mut update_group: UpdateGroup = group.into();
+        update_group.allow_adult = settings.allow_adult;
+        update_group.title_language = settings.title_language.as_str().to_string();
+        update_group.voice_actor_language = settings.voice_actor_language.as_str().to_string();
+        if let Some(toggles) = toggles {
+            update_group.birthdays = toggles.birthdays;
+            update_group.link_preview = toggles.link_preview;
+            update_group.clean_service_messages = toggles.clean_service_messages;
+        }
+        update_group.update(pool).await?;
+
+        Ok(true)
+    } else {
+        log::warn!("group not found: {}", id);
+
+        Ok(false)
+    }
+}
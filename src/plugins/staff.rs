@@ -0,0 +1,363 @@
+// Copyright 2025 - Andriel Ferreira
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// https://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or https://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+//! The staff plugin.
+
+use std::time::Duration;
+
+use ferogram::{
+    Context, Result, Router, filter, handler,
+    utils::{bytes_to_string, split_btns_into_columns},
+};
+use grammers_client::{
+    InputMessage, button, reply_markup,
+    types::{CallbackQuery, InlineQuery, inline},
+};
+use maplit::hashmap;
+
+use crate::{
+    commands::{HelpCategory, HelpEntry},
+    resources::{AniList, I18n, anilist::staff::Staff},
+    utils::{
+        escape_html, gen_pagination_buttons, gen_staff_info, gen_voiced_list, gen_work_list,
+        remove_html, shorten_text,
+    },
+};
+
+/// How many roles are listed per page.
+const PER_PAGE: usize = 10;
+
+/// The commands of the plugin, for `/help`.
+pub const HELP: &[HelpEntry] = &[HelpEntry::new("staff", HelpCategory::Search).inline("!s")];
+
+/// The plugin setup.
+pub fn setup(router: Router) -> Router {
+    router
+        .register(
+            handler::new_message(
+                filter::command("staff").description("Search for staff and voice actors."),
+            )
+            .then(staff),
+        )
+        .register(handler::callback_query(filter::regex(r"^staff (\d+) (\d+)")).then(staff))
+        .register(
+            handler::callback_query(filter::regex(r"^staff (characters|works) (\d+) (\d+)"))
+                .then(staff_info),
+        )
+        .register(handler::inline_query(filter::regex(r"^[\.!]?s (.+)")).then(staff_inline))
+}
+
+/// The staff handler.
+async fn staff(ctx: Context, i18n: I18n, ani: AniList) -> Result<()> {
+    let t = |key: &str| i18n.translate(key);
+    let t_a = |key: &str, args| i18n.translate_with_args(key, args);
+
+    let text = if ctx.is_callback_query() {
+        ctx.query()
+    } else {
+        ctx.text()
+    }
+    .unwrap();
+    let mut args = text.split_whitespace().skip(1).collect::<Vec<_>>();
+
+    let sender = ctx.sender().unwrap();
+
+    if let Some(query) = ctx.callback_query() {
+        let sender_id = args.pop().unwrap().parse::<i64>().unwrap();
+
+        if sender.id() != sender_id {
+            query
+                .answer()
+                .cache_time(Duration::from_secs(120))
+                .alert(t("not_allowed"))
+                .send()
+                .await?;
+            return Ok(());
+        }
+    }
+
+    if args.is_empty() {
+        ctx.reply(
+            InputMessage::html(t("staff_usage")).reply_markup(&reply_markup::inline(vec![vec![
+                button::switch_inline(t("search_btn"), "!s "),
+            ]])),
+        )
+        .await?;
+    } else if let Ok(id) = args[0].parse::<i64>() {
+        if let Some(staff) = ani.get_staff(id).await {
+            send_staff_info(staff, ctx, &i18n).await?;
+        } else {
+            ctx.reply(InputMessage::html(t("not_found"))).await?;
+        }
+    } else {
+        let name = args.join(" ");
+        let result = ani.search_staff(&name, 1, 6).await.unwrap_or_default();
+
+        if result.is_empty() {
+            ctx.reply(InputMessage::html(t("no_results_text")).reply_markup(
+                &reply_markup::inline(vec![vec![button::switch_inline(
+                    t("search_again_btn"),
+                    format!("!s {}", name),
+                )]]),
+            ))
+            .await?;
+            return Ok(());
+        } else if result.len() == 1 {
+            return send_staff_info(result[0].clone(), ctx, &i18n).await;
+        }
+
+        let buttons = result
+            .into_iter()
+            .map(|staff| {
+                vec![button::inline(
+                    staff.name.full,
+                    format!("staff {0} {1}", staff.id, sender.id()),
+                )]
+            })
+            .collect::<Vec<_>>();
+
+        ctx.reply(
+            InputMessage::html(t_a(
+                "search_results",
+                hashmap! { "search" => escape_html(&name) },
+            ))
+            .reply_markup(&reply_markup::inline(buttons)),
+        )
+        .await?;
+    }
+
+    Ok(())
+}
+
+/// Sends the staff info to the user.
+pub async fn send_staff_info(staff: Staff, ctx: Context, i18n: &I18n) -> Result<()> {
+    let t = |key: &str| i18n.translate(key);
+
+    let text = gen_staff_info(&staff, i18n);
+    let image_url = staff.image_url();
+
+    let sender = ctx.sender().unwrap();
+
+    let mut buttons = Vec::new();
+    if !staff.characters.nodes.is_empty() {
+        buttons.push(button::inline(
+            t("characters_voiced_btn"),
+            format!("staff characters {} {}", staff.id, sender.id()),
+        ));
+    }
+    if !staff.staff_media.edges.is_empty() {
+        buttons.push(button::inline(
+            t("works_btn"),
+            format!("staff works {} {}", staff.id, sender.id()),
+        ));
+    }
+
+    let markup = reply_markup::inline(split_btns_into_columns(buttons, 2));
+
+    if ctx.is_callback_query() {
+        ctx.edit(
+            InputMessage::html(format!("<a href=\"{}\">⁠</a>", image_url) + &text)
+                .link_preview(true)
+                .reply_markup(&markup),
+        )
+        .await?;
+    } else if image_url.is_empty() {
+        ctx.reply(InputMessage::html(text).reply_markup(&markup))
+            .await?;
+    } else {
+        ctx.reply(
+            InputMessage::html(text)
+                .photo_url(image_url)
+                .reply_markup(&markup),
+        )
+        .await?;
+    }
+
+    Ok(())
+}
+
+/// The staff info handler.
+async fn staff_info(query: CallbackQuery, i18n: I18n, ani: AniList) -> Result<()> {
+    let t = |key: &str| i18n.translate(key);
+
+    let data = query.data();
+    let args = bytes_to_string(data)
+        .split_whitespace()
+        .skip(1)
+        .map(String::from)
+        .collect::<Vec<_>>();
+
+    let info = args[0].as_str();
+    let staff_id = args[1].parse::<i64>().unwrap();
+    let sender_id = args[2].parse::<i64>().unwrap();
+    let page = args
+        .get(3)
+        .and_then(|page| page.parse::<usize>().ok())
+        .unwrap_or(1)
+        .max(1);
+
+    let sender = query.sender();
+
+    if sender.id() != sender_id {
+        query
+            .answer()
+            .cache_time(Duration::from_secs(120))
+            .alert(t("not_allowed"))
+            .send()
+            .await?;
+        return Ok(());
+    }
+
+    let Some(staff) = ani.get_staff(staff_id).await else {
+        query
+            .answer()
+            .cache_time(Duration::from_secs(120))
+            .alert(t("not_available"))
+            .send()
+            .await?;
+        return Ok(());
+    };
+
+    let mut text = format!(
+        "<code>{0}</code> | <b>{1}</b>\n\n",
+        staff.id,
+        escape_html(&staff.name.full)
+    );
+
+    let count = match info {
+        "characters" => {
+            text.push_str(&gen_voiced_list(
+                &staff.characters.nodes,
+                page,
+                PER_PAGE,
+                &i18n,
+            ));
+            staff.characters.nodes.len()
+        }
+        _ => {
+            text.push_str(&gen_work_list(
+                &staff.staff_media.edges,
+                page,
+                PER_PAGE,
+                &i18n,
+            ));
+            staff.staff_media.edges.len()
+        }
+    };
+
+    if count == 0 {
+        query
+            .answer()
+            .cache_time(Duration::from_secs(120))
+            .alert(t("not_available"))
+            .send()
+            .await?;
+        return Ok(());
+    }
+
+    let mut buttons = Vec::new();
+    let max_pages = count.div_ceil(PER_PAGE);
+    if max_pages > 1 {
+        buttons.push(gen_pagination_buttons(
+            &format!("staff {0} {1} {2}", info, staff_id, sender_id),
+            page,
+            max_pages,
+        ));
+    }
+    buttons.push(vec![button::inline(
+        t("back_btn"),
+        format!("staff {0} {1}", staff_id, sender_id),
+    )]);
+
+    query
+        .answer()
+        .edit(InputMessage::html(text).reply_markup(&reply_markup::inline(buttons)))
+        .await?;
+
+    Ok(())
+}
+
+/// The staff inline query handler.
+async fn staff_inline(query: InlineQuery, i18n: I18n, ani: AniList) -> Result<()> {
+    let t = |key: &str| i18n.translate(key);
+
+    let arg = query
+        .text()
+        .split_whitespace()
+        .skip(1)
+        .collect::<Vec<_>>()
+        .join(" ");
+    let offset = query.offset().parse::<u16>().unwrap_or(1);
+    let mut results = Vec::new();
+
+    if let Some(result) = ani.search_staff(&arg, offset, 10).await {
+        for staff in result {
+            results.push(gen_staff_article(&query, staff, &i18n));
+        }
+    }
+
+    if results.is_empty() {
+        results.push(
+            inline::query::Article::new(
+                t(if offset == 1 {
+                    "no_results"
+                } else {
+                    "no_more_results"
+                }),
+                InputMessage::html(t(if offset == 1 {
+                    "no_results_text"
+                } else {
+                    "no_more_results_text"
+                }))
+                .reply_markup(&reply_markup::inline(vec![vec![
+                    button::switch_inline(t("search_again_btn"), format!("!s {}", arg)),
+                ]])),
+            )
+            .description(t("click_for_more_info")),
+        );
+    }
+
+    query
+        .answer(results)
+        .cache_time(120)
+        .next_offset((offset + 1).to_string())
+        .send()
+        .await?;
+
+    Ok(())
+}
+
+/// Generates an inline query article for a staff member.
+pub fn gen_staff_article(query: &InlineQuery, staff: Staff, i18n: &I18n) -> inline::query::Article {
+    let t = |key: &str| i18n.translate(key);
+
+    let text = gen_staff_info(&staff, i18n);
+    let image_url = staff.image_url();
+
+    let sender = query.sender();
+
+    let mut article = inline::query::Article::new(
+        staff.name.full.clone(),
+        InputMessage::html(format!("<a href=\"{}\">⁠</a>", image_url) + &text)
+            .link_preview(true)
+            .reply_markup(&reply_markup::inline(vec![vec![button::inline(
+                t("load_more_btn"),
+                format!("staff {0} {1}", staff.id, sender.id()),
+            )]])),
+    )
+    .description(shorten_text(
+        remove_html(staff.description.clone().unwrap_or_default()),
+        150,
+    ));
+
+    if !image_url.is_empty() {
+        article = article.thumb_url(image_url);
+    }
+
+    article
+}
@@ -8,10 +8,19 @@
 
 //! The start plugin.
 
-use ferogram::{Result, Router, filter, handler};
-use grammers_client::{InputMessage, types::Message};
+use ferogram::{Context, Result, Router, filter, handler};
+use grammers_client::InputMessage;
 
-use crate::resources::I18n;
+use crate::{
+    commands::{HelpCategory, HelpEntry},
+    models::{ChatSettings, User},
+    plugins::{anime, character, list, manga, user},
+    resources::{AniList, Cards, Database, I18n},
+    utils::{CardResources, StartPayload},
+};
+
+/// The commands of the plugin, for `/help`.
+pub const HELP: &[HelpEntry] = &[HelpEntry::new("start", HelpCategory::Bot)];
 
 /// The plugin setup.
 pub fn setup(router: Router) -> Router {
@@ -21,10 +30,70 @@ pub fn setup(router: Router) -> Router {
 }
 
 /// The start command handler.
-async fn start(message: Message, i18n: I18n) -> Result<()> {
+///
+/// Opens the card of a deep link, e.g. `/start anime_1735`, and greets the user otherwise.
+async fn start(
+    ctx: Context,
+    db: Database,
+    i18n: I18n,
+    ani: AniList,
+    cards: Cards,
+    settings: ChatSettings,
+) -> Result<()> {
     let t = |key: &str| i18n.translate(key);
 
-    message.reply(InputMessage::html(t("start"))).await?;
+    let text = ctx.text().unwrap();
+    let payload = text.split_whitespace().nth(1).and_then(StartPayload::parse);
+
+    let sender = ctx.sender().unwrap();
+
+    // Unblocking the bot offers to restart it, so the user gets the broadcasts again.
+    if ctx.is_private() && db.is_healthy() {
+        if let Err(e) = db.report(User::set_blocked(db.pool(), sender.id(), false).await) {
+            log::warn!(
+                "failed to record that {} unblocked the bot: {:?}",
+                sender.id(),
+                e
+            );
+        }
+    }
+
+    let resources = CardResources {
+        cards: &cards,
+        i18n: &i18n,
+        ani: &ani,
+    };
+
+    // Cards that could not be retrieved fall back to the greeting.
+    match payload {
+        Some(StartPayload::Anime(id)) => {
+            if let Ok(anime) = ani.get_anime(id).await {
+                let viewer = list::get_viewer(&db, sender.id()).await;
+                return anime::send_anime_info(anime, ctx, resources, &settings, viewer.as_ref())
+                    .await;
+            }
+        }
+        Some(StartPayload::Manga(id)) => {
+            if let Ok(manga) = ani.get_manga(id).await {
+                let viewer = list::get_viewer(&db, sender.id()).await;
+                return manga::send_manga_info(manga, ctx, resources, &settings, viewer.as_ref())
+                    .await;
+            }
+        }
+        Some(StartPayload::Character(id)) => {
+            if let Ok(char) = ani.get_char(id).await {
+                return character::send_char_info(char, ctx, resources).await;
+            }
+        }
+        Some(StartPayload::User(id)) => {
+            if let Ok(found) = ani.get_user(id).await {
+                return user::send_user_info(&found, ctx, resources).await;
+            }
+        }
+        None => {}
+    }
+
+    ctx.reply(InputMessage::html(t("start"))).await?;
 
     Ok(())
 }
@@ -0,0 +1,174 @@
+// Copyright 2025 - Andriel Ferreira
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// https://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or https://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+//! The studio plugin.
+
+use std::time::Duration;
+
+use ferogram::{Context, Result, Router, filter, handler};
+use grammers_client::{InputMessage, button, reply_markup};
+use maplit::hashmap;
+
+use crate::{
+    commands::{HelpCategory, HelpEntry},
+    resources::{AniList, I18n, anilist::studios::Studio},
+    utils::{self, escape_html, gen_pagination_buttons, gen_studio_info, shorten_text},
+};
+
+/// How many productions are listed per page.
+const PER_PAGE: usize = 10;
+
+/// The commands of the plugin, for `/help`.
+pub const HELP: &[HelpEntry] = &[HelpEntry::new("studio", HelpCategory::Search)];
+
+/// The plugin setup.
+pub fn setup(router: Router) -> Router {
+    router
+        .register(
+            handler::new_message(filter::command("studio").description("Search for studios."))
+                .then(studio),
+        )
+        .register(handler::callback_query(filter::regex(r"^studio (\d+) (\d+)")).then(studio))
+}
+
+/// The studio handler.
+async fn studio(ctx: Context, i18n: I18n, ani: AniList) -> Result<()> {
+    let t = |key: &str| i18n.translate(key);
+    let t_a = |key: &str, args| i18n.translate_with_args(key, args);
+
+    let text = if ctx.is_callback_query() {
+        ctx.query()
+    } else {
+        ctx.text()
+    }
+    .unwrap();
+    let args = text.split_whitespace().skip(1).collect::<Vec<_>>();
+
+    let sender = ctx.sender().unwrap();
+
+    if let Some(query) = ctx.callback_query() {
+        let studio_id = args[0].parse::<i64>().unwrap();
+        let sender_id = args[1].parse::<i64>().unwrap();
+        let page = args
+            .get(2)
+            .and_then(|page| page.parse::<usize>().ok())
+            .unwrap_or(1)
+            .max(1);
+
+        if sender.id() != sender_id {
+            query
+                .answer()
+                .cache_time(Duration::from_secs(120))
+                .alert(t("not_allowed"))
+                .send()
+                .await?;
+            return Ok(());
+        }
+
+        if let Some(studio) = ani.get_studio(studio_id).await {
+            return send_studio_info(studio, page, ctx, &i18n).await;
+        }
+
+        query
+            .answer()
+            .cache_time(Duration::from_secs(120))
+            .alert(t("not_available"))
+            .send()
+            .await?;
+        return Ok(());
+    }
+
+    if args.is_empty() {
+        ctx.reply(InputMessage::html(t("studio_usage"))).await?;
+        return Ok(());
+    }
+
+    if let Ok(id) = args[0].parse::<i64>() {
+        if let Some(studio) = ani.get_studio(id).await {
+            send_studio_info(studio, 1, ctx, &i18n).await?;
+        } else {
+            ctx.reply(InputMessage::html(t("not_found"))).await?;
+        }
+        return Ok(());
+    }
+
+    let name = args.join(" ");
+    let result = ani.search_studio(&name, 1, 6).await.unwrap_or_default();
+
+    if result.is_empty() {
+        ctx.reply(InputMessage::html(t("no_results"))).await?;
+        return Ok(());
+    } else if result.len() == 1 {
+        return send_studio_info(result[0].clone(), 1, ctx, &i18n).await;
+    }
+
+    let buttons = result
+        .into_iter()
+        .map(|studio| {
+            vec![button::inline(
+                studio.name,
+                format!("studio {0} {1}", studio.id, sender.id()),
+            )]
+        })
+        .collect::<Vec<_>>();
+
+    ctx.reply(
+        InputMessage::html(t_a(
+            "search_results",
+            hashmap! { "search" => escape_html(&name) },
+        ))
+        .reply_markup(&reply_markup::inline(buttons)),
+    )
+    .await?;
+
+    Ok(())
+}
+
+/// Sends the studio info, with a page of its productions, to the user.
+async fn send_studio_info(studio: Studio, page: usize, ctx: Context, i18n: &I18n) -> Result<()> {
+    let sender = ctx.sender().unwrap();
+
+    let text = gen_studio_info(&studio, page, PER_PAGE, i18n);
+
+    let mut buttons = studio
+        .media
+        .nodes
+        .iter()
+        .skip((page - 1) * PER_PAGE)
+        .take(PER_PAGE)
+        .map(|production| {
+            vec![button::inline(
+                shorten_text(production.title.romaji.clone().unwrap_or_default(), 50),
+                utils::sign_callback(&format!(
+                    "{0} {1} {2}",
+                    if production.media_type.as_deref() == Some("MANGA") {
+                        "manga"
+                    } else {
+                        "anime"
+                    },
+                    production.id,
+                    sender.id()
+                )),
+            )]
+        })
+        .collect::<Vec<_>>();
+
+    let max_pages = studio.media.nodes.len().div_ceil(PER_PAGE);
+    if max_pages > 1 {
+        buttons.push(gen_pagination_buttons(
+            &format!("studio {0} {1}", studio.id, sender.id()),
+            page,
+            max_pages,
+        ));
+    }
+
+    ctx.edit_or_reply(InputMessage::html(text).reply_markup(&reply_markup::inline(buttons)))
+        .await?;
+
+    Ok(())
+}
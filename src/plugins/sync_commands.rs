@@ -0,0 +1,68 @@
+// Copyright 2025 - Andriel Ferreira
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// https://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or https://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+//! The sync commands plugin.
+
+use ferogram::{Result, Router, filter, handler};
+use grammers_client::{Client, InputMessage, types::Message};
+use maplit::hashmap;
+
+use crate::{Config, commands, plugins::owner::sudoer, resources::I18n, utils};
+
+/// The plugin setup.
+///
+/// # Arguments
+///
+/// * `router` - The router.
+/// * `config` - The bot configuration, for the sudoers.
+pub fn setup(router: Router, config: &Config) -> Router {
+    router.register(
+        handler::new_message(filter::command("synccommands").and(sudoer(config)))
+            .then(sync_commands),
+    )
+}
+
+/// The sync commands command handler.
+///
+/// Only answers the sudoers, see [`sudoer`].
+async fn sync_commands(client: Client, message: Message, i18n: I18n) -> Result<()> {
+    let t = |key: &str| i18n.translate(key);
+
+    let summary = commands::sync(&client, &i18n).await;
+
+    let scope = |lang_code: &str| {
+        if lang_code.is_empty() {
+            t("sync_commands_global")
+        } else {
+            lang_code.to_string()
+        }
+    };
+
+    let mut text = format!("<b>{}</b>\n", t("sync_commands"));
+    for (lang_code, count) in summary.registered.iter() {
+        let line = i18n.translate_with_args(
+            "sync_commands_registered",
+            hashmap! {
+                "scope" => scope(lang_code),
+                "count" => count.to_string(),
+            },
+        );
+        text.push_str(&format!("\n✅ {}", line));
+    }
+    for (lang_code, error) in summary.failed.iter() {
+        text.push_str(&format!(
+            "\n❌ <code>{}</code>: {}",
+            scope(lang_code),
+            utils::escape_html(error)
+        ));
+    }
+
+    message.reply(InputMessage::html(text)).await?;
+
+    Ok(())
+}
@@ -0,0 +1,150 @@
+// Copyright 2025 - Andriel Ferreira
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// https://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or https://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+//! The top plugin.
+
+use std::time::Duration;
+
+use ferogram::{Context, Result, Router, filter, handler};
+use grammers_client::{InputMessage, button, reply_markup};
+use maplit::hashmap;
+
+use crate::{
+    commands::{HelpCategory, HelpEntry},
+    models::MediaType,
+    resources::{AniList, I18n, anilist::top},
+    utils::{self, TopKind, gen_pagination_buttons_with, gen_top_list, shorten_text},
+};
+
+/// How many of the best ranked media of a page get a button.
+const BUTTONS: usize = 3;
+
+/// The commands of the plugin, for `/help`.
+pub const HELP: &[HelpEntry] = &[HelpEntry::new("top", HelpCategory::Discover)];
+
+/// The plugin setup.
+pub fn setup(router: Router) -> Router {
+    router
+        .register(
+            handler::new_message(
+                filter::command("top").description("Show the best scored animes and mangas."),
+            )
+            .then(top),
+        )
+        .register(handler::callback_query(filter::regex(r"^top (\w+) (\d+) (\d+)$")).then(top))
+}
+
+/// The top handler.
+///
+/// Accepts `/top`, for the best scored animes, or `/top <kind>`.
+async fn top(ctx: Context, i18n: I18n, ani: AniList) -> Result<()> {
+    let t = |key: &str| i18n.translate(key);
+    let t_a = |key: &str, args| i18n.translate_with_args(key, args);
+
+    let text = if ctx.is_callback_query() {
+        ctx.query()
+    } else {
+        ctx.text()
+    }
+    .unwrap();
+    let mut args = text.split_whitespace().skip(1).collect::<Vec<_>>();
+
+    let sender = ctx.sender().unwrap();
+
+    if let Some(query) = ctx.callback_query() {
+        let sender_id = args.pop().unwrap().parse::<i64>().unwrap();
+
+        if sender.id() != sender_id {
+            query
+                .answer()
+                .cache_time(Duration::from_secs(120))
+                .alert(t("not_allowed"))
+                .send()
+                .await?;
+            return Ok(());
+        }
+    }
+
+    let kind = match args.first() {
+        Some(name) => TopKind::parse(name),
+        None => Some(TopKind::Anime),
+    };
+    let page = args
+        .get(1)
+        .and_then(|page| page.parse::<u16>().ok())
+        .unwrap_or(1)
+        .max(1);
+
+    let Some(kind) = kind else {
+        ctx.reply(InputMessage::html(t_a(
+            "top_usage",
+            hashmap! { "kinds" => TopKind::KEYWORDS.map(|keyword| format!("<code>{}</code>", keyword)).join(", ") },
+        )))
+        .await?;
+        return Ok(());
+    };
+
+    let entries = ani
+        .get_top_media(kind, page)
+        .await
+        .filter(|entries| !entries.media.is_empty());
+    let Some(entries) = entries else {
+        if let Some(query) = ctx.callback_query() {
+            query
+                .answer()
+                .cache_time(Duration::from_secs(120))
+                .alert(t(if page > 1 {
+                    "page_not_found"
+                } else {
+                    "not_available"
+                }))
+                .send()
+                .await?;
+        } else {
+            ctx.reply(InputMessage::html(t("no_results"))).await?;
+        }
+        return Ok(());
+    };
+
+    let offset = (page as usize - 1) * top::PER_PAGE as usize;
+    let mut buttons = entries
+        .media
+        .iter()
+        .take(BUTTONS)
+        .enumerate()
+        .map(|(i, entry)| {
+            vec![button::inline(
+                format!("{0}. {1}", offset + i + 1, shorten_text(&entry.title, 45)),
+                utils::sign_callback(&format!(
+                    "{0} {1} {2}",
+                    match kind.media_type() {
+                        MediaType::Anime => "anime",
+                        MediaType::Manga => "manga",
+                    },
+                    entry.id,
+                    sender.id()
+                )),
+            )]
+        })
+        .collect::<Vec<_>>();
+    if entries.last_page > 1 {
+        buttons.push(gen_pagination_buttons_with(
+            page as usize,
+            entries.last_page,
+            |page| format!("top {0} {1} {2}", kind.name(), page, sender.id()),
+        ));
+    }
+
+    ctx.edit_or_reply(
+        InputMessage::html(gen_top_list(kind, &entries.media, offset, &i18n))
+            .reply_markup(&reply_markup::inline(buttons)),
+    )
+    .await?;
+
+    Ok(())
+}
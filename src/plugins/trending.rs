@@ -0,0 +1,98 @@
+// Copyright 2025 - Andriel Ferreira
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// https://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or https://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+//! The trending plugin.
+
+use ferogram::{Result, Router, filter, handler};
+use grammers_client::{InputMessage, button, reply_markup, types::Message};
+
+use crate::{
+    commands::{HelpCategory, HelpEntry},
+    resources::{AniList, I18n},
+    utils::{self, shorten_text},
+};
+
+/// How many trending media are listed.
+const LIMIT: u16 = 10;
+
+/// The commands of the plugin, for `/help`.
+pub const HELP: &[HelpEntry] = &[HelpEntry::new("trending", HelpCategory::Discover)];
+
+/// The plugin setup.
+pub fn setup(router: Router) -> Router {
+    router.register(
+        handler::new_message(
+            filter::command("trending").description("Show the trending animes and mangas."),
+        )
+        .then(trending),
+    )
+}
+
+/// The trending command handler.
+///
+/// Lists the trending animes, or mangas with `/trending manga`.
+async fn trending(message: Message, i18n: I18n, ani: AniList) -> Result<()> {
+    let t = |key: &str| i18n.translate(key);
+
+    let Some(sender) = message.sender() else {
+        return Ok(());
+    };
+
+    let text = message.text().to_lowercase();
+    let is_manga = text
+        .split_whitespace()
+        .nth(1)
+        .is_some_and(|arg| matches!(arg, "manga" | "m"));
+
+    let entries = if is_manga {
+        ani.get_trending_manga(1, LIMIT).await.map(|mangas| {
+            mangas
+                .into_iter()
+                .map(|manga| (manga.id, manga.title.romaji()))
+                .collect::<Vec<_>>()
+        })
+    } else {
+        ani.get_trending_anime(1, LIMIT).await.map(|animes| {
+            animes
+                .into_iter()
+                .map(|anime| (anime.id, anime.title.romaji()))
+                .collect::<Vec<_>>()
+        })
+    }
+    .unwrap_or_default();
+
+    if entries.is_empty() {
+        message.reply(InputMessage::html(t("no_results"))).await?;
+        return Ok(());
+    }
+
+    let callback = if is_manga { "manga" } else { "anime" };
+    let buttons = entries
+        .into_iter()
+        .enumerate()
+        .map(|(index, (id, title))| {
+            vec![button::inline(
+                format!("{0}. {1}", index + 1, shorten_text(title, 50)),
+                utils::sign_callback(&format!("{0} {1} {2}", callback, id, sender.id())),
+            )]
+        })
+        .collect::<Vec<_>>();
+
+    message
+        .reply(
+            InputMessage::html(t(if is_manga {
+                "trending_manga"
+            } else {
+                "trending_anime"
+            }))
+            .reply_markup(&reply_markup::inline(buttons)),
+        )
+        .await?;
+
+    Ok(())
+}
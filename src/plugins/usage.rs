@@ -0,0 +1,96 @@
+// Copyright 2025 - Andriel Ferreira
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// https://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or https://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+//! The usage plugin.
+
+use chrono::{Days, Utc};
+use ferogram::{Result, Router, filter, handler};
+use grammers_client::{InputMessage, types::Message};
+use maplit::hashmap;
+
+use crate::{
+    Config,
+    models::{CommandStat, StatKind},
+    plugins::owner::sudoer,
+    resources::{Database, I18n, database::is_connection_error},
+    utils,
+};
+
+/// How many days the report covers.
+const REPORT_DAYS: u64 = 7;
+
+/// How many entries each section of the report lists.
+const REPORT_LIMIT: i64 = 10;
+
+/// The plugin setup.
+///
+/// # Arguments
+///
+/// * `router` - The router.
+/// * `config` - The bot configuration, for the sudoers.
+pub fn setup(router: Router, config: &Config) -> Router {
+    router.register(handler::new_message(filter::command("usage").and(sudoer(config))).then(usage))
+}
+
+/// The usage command handler.
+///
+/// Only answers the sudoers, see [`sudoer`].
+async fn usage(message: Message, db: Database, i18n: I18n) -> Result<()> {
+    let t = |key: &str| i18n.translate(key);
+
+    let since = Utc::now().date_naive() - Days::new(REPORT_DAYS - 1);
+    let callbacks = match db
+        .report(CommandStat::top(db.pool(), StatKind::Callback, since, REPORT_LIMIT).await)
+    {
+        Ok(callbacks) => callbacks,
+        Err(e) if is_connection_error(&e) => {
+            message
+                .reply(InputMessage::html(t("database_unavailable")))
+                .await?;
+            return Ok(());
+        }
+        Err(e) => return Err(e.into()),
+    };
+
+    message
+        .reply(InputMessage::html(gen_report(&callbacks, &i18n)))
+        .await?;
+
+    Ok(())
+}
+
+/// Generates the usage report text.
+///
+/// # Arguments
+///
+/// * `callbacks` - The most pressed callback views, with their count.
+/// * `i18n` - The i18n resource.
+pub fn gen_report(callbacks: &[(String, i64)], i18n: &I18n) -> String {
+    let mut text = format!(
+        "📊 <b>{0}</b>\n\n<b>{1}</b>\n",
+        i18n.translate_with_args(
+            "usage_report",
+            hashmap! { "days" => REPORT_DAYS.to_string() }
+        ),
+        i18n.translate("usage_report_callbacks"),
+    );
+
+    if callbacks.is_empty() {
+        text.push_str(&i18n.translate("usage_report_empty"));
+    }
+    for (position, (verb, count)) in callbacks.iter().enumerate() {
+        text.push_str(&format!(
+            "{0}. <code>{1}</code>: {2}\n",
+            position + 1,
+            verb,
+            utils::format_thousands(*count)
+        ));
+    }
+
+    text
+}
@@ -8,19 +8,43 @@
 
 //! The user plugin.
 
-use ferogram::{Context, Result, Router, filter, handler};
+use std::time::Duration;
+
+use async_trait::async_trait;
+use ferogram::{Context, Result, Router, filter, handler, utils::split_btns_into_columns};
 use grammers_client::{
-    InputMessage, button, reply_markup,
-    types::{InlineQuery, inline},
+    InputMessage,
+    button::{self, Inline},
+    reply_markup,
+    types::{InlineQuery, Message, inline},
 };
-use maplit::hashmap;
 use rust_anilist::models::User;
 
 use crate::{
-    resources::{AniList, I18n},
-    utils,
+    Config,
+    commands::{HelpCategory, HelpEntry},
+    models::{self, ChatSettings, MediaType},
+    plugins::{auth, inline::should_search, list},
+    resources::{
+        AniList, Cards, Database, Debounce, I18n, Sessions,
+        anilist::{
+            FetchError,
+            graphql::QueryError,
+            lists::{self, ListStatus},
+        },
+        database::is_connection_error,
+    },
+    utils::{self, CardResources, Resolver, SearchQuery, StartPayload},
 };
 
+/// The commands of the plugin, for `/help`.
+pub const HELP: &[HelpEntry] = &[
+    HelpEntry::new("user", HelpCategory::Search)
+        .aliases(&["u"])
+        .inline("!u"),
+    HelpEntry::new("me", HelpCategory::Account),
+];
+
 /// The plugin setup.
 pub fn setup(router: Router) -> Router {
     router
@@ -28,101 +52,514 @@ pub fn setup(router: Router) -> Router {
             handler::new_message(filter::commands(&["u", "user"]).description("Search for users."))
                 .then(user),
         )
-        .register(handler::callback_query(filter::regex(r"^user (\d+)")).then(user))
+        .register(handler::callback_query(filter::regex(r"^user (\d+) (\d+)")).then(user_result))
+        .register(
+            handler::callback_query(filter::regex(r"^user (stats|favourites) (\d+) (\d+) \S+$"))
+                .then(user_section),
+        )
+        .register(
+            handler::callback_query(filter::regex(
+                r"^user list (\d+) (anime|manga) (\w+) (\d+) (\d+) \S+$",
+            ))
+            .then(user_list),
+        )
+        .register(
+            handler::new_message(filter::command("me").description("Show your AniList profile."))
+                .then(me),
+        )
+        .register(handler::callback_query(filter::regex(r"^me (\d+)$")).then(me))
         .register(handler::inline_query(filter::regex(r"^[\.!]?u (.+)")).then(user_inline))
 }
 
 /// The user handler.
-async fn user(ctx: Context, i18n: I18n, ani: AniList) -> Result<()> {
+async fn user(
+    ctx: Context,
+    message: Message,
+    i18n: I18n,
+    ani: AniList,
+    cards: Cards,
+    settings: ChatSettings,
+) -> Result<()> {
+    let resources = CardResources {
+        cards: &cards,
+        i18n: &i18n,
+        ani: &ani,
+    };
+
+    find_user(ctx, Some(&message), resources, &settings).await
+}
+
+/// The user callback handler, for the buttons of the search results.
+async fn user_result(
+    ctx: Context,
+    i18n: I18n,
+    ani: AniList,
+    cards: Cards,
+    settings: ChatSettings,
+) -> Result<()> {
+    let resources = CardResources {
+        cards: &cards,
+        i18n: &i18n,
+        ani: &ani,
+    };
+
+    find_user(ctx, None, resources, &settings).await
+}
+
+/// Finds the users of a command or of a button of its results.
+async fn find_user(
+    ctx: Context,
+    command: Option<&Message>,
+    resources: CardResources<'_>,
+    settings: &ChatSettings,
+) -> Result<()> {
+    let CardResources { i18n, ani, .. } = resources;
+    let resolver = UserResolver { resources };
+
+    utils::resolve_or_disambiguate(
+        ctx,
+        command,
+        &resolver,
+        ani,
+        i18n,
+        settings.clean_service_messages,
+    )
+    .await
+}
+
+/// Finds the users of the command, see [`utils::resolve_or_disambiguate`].
+struct UserResolver<'a> {
+    /// The resources the cards are sent with.
+    resources: CardResources<'a>,
+}
+
+#[async_trait]
+impl Resolver for UserResolver<'_> {
+    type Entry = User;
+
+    fn usage_key(&self) -> &'static str {
+        "user_usage"
+    }
+
+    fn inline_query(&self) -> &'static str {
+        "!u"
+    }
+
+    async fn fetch(&self, arg: &str) -> Option<std::result::Result<User, FetchError>> {
+        let id = arg.parse::<i32>().ok()?;
+
+        Some(self.resources.ani.get_user(id).await)
+    }
+
+    async fn refetch(&self, user: User) -> User {
+        self.resources.ani.get_user(user.id).await.unwrap_or(user)
+    }
+
+    async fn search(&self, query: &SearchQuery) -> Option<Vec<User>> {
+        self.resources.ani.search_user(&query.terms, 1, 6).await
+    }
+
+    async fn send(&self, user: User, ctx: Context) -> Result<()> {
+        send_user_info(&user, ctx, self.resources).await
+    }
+
+    async fn gen_result_buttons(
+        &self,
+        result: Vec<User>,
+        _: &SearchQuery,
+        sender_id: i64,
+    ) -> Vec<Vec<Inline>> {
+        let mut buttons = result
+            .into_iter()
+            .map(|user| {
+                vec![button::inline(
+                    user.name,
+                    utils::sign_callback(&format!("user {0} {1}", user.id, sender_id)),
+                )]
+            })
+            .collect::<Vec<_>>();
+        buttons.push(vec![utils::gen_cancel_button(
+            sender_id,
+            self.resources.i18n,
+        )]);
+
+        buttons
+    }
+}
+
+/// The me handler.
+///
+/// Shows the AniList profile of the sender, if they are authenticated.
+async fn me(
+    ctx: Context,
+    db: Database,
+    i18n: I18n,
+    ani: AniList,
+    cards: Cards,
+    config: Config,
+    sessions: Sessions,
+) -> Result<()> {
     let t = |key: &str| i18n.translate(key);
-    let t_a = |key: &str, args| i18n.translate_with_args(key, args);
 
-    let text = if ctx.is_callback_query() {
-        ctx.query()
-    } else {
-        ctx.text()
+    let sender = ctx.sender().unwrap();
+
+    if let Some(query) = ctx.callback_query() {
+        let sender_id = ctx
+            .query()
+            .unwrap()
+            .split_whitespace()
+            .nth(1)
+            .unwrap()
+            .parse::<i64>()
+            .unwrap();
+
+        if sender.id() != sender_id {
+            utils::send_with_retry(|| {
+                query
+                    .answer()
+                    .cache_time(Duration::from_secs(120))
+                    .alert(t("not_allowed"))
+                    .send()
+            })
+            .await?;
+            return Ok(());
+        }
     }
-    .unwrap();
-    let args = text.split_whitespace().skip(1).collect::<Vec<&str>>();
 
-    if args.is_empty() {
-        ctx.reply(
-            InputMessage::html(t("user_usage")).reply_markup(&reply_markup::inline(vec![vec![
-                button::switch_inline(t("search_btn"), "!u "),
-            ]])),
-        )
-        .await?;
-    } else {
-        if let Ok(id) = args[0].parse::<i32>() {
-            if let Ok(user) = ani.get_user(id).await {
-                send_user_info(&user, ctx).await?;
+    let anilist_id = match db.report(models::User::get_by_id(db.pool(), &sender.id()).await) {
+        Ok(user) => user.and_then(|user| user.anilist_id),
+        Err(e) if is_connection_error(&e) => {
+            if let Some(query) = ctx.callback_query() {
+                utils::send_with_retry(|| query.answer().alert(t("database_unavailable")).send())
+                    .await?;
             } else {
-                ctx.reply(InputMessage::html(t("not_found"))).await?;
+                let message = InputMessage::html(t("database_unavailable"));
+                utils::send_with_retry(|| ctx.reply(message.clone())).await?;
             }
+            return Ok(());
+        }
+        Err(e) => return Err(e.into()),
+    };
+
+    let Some(anilist_id) = anilist_id else {
+        if let Some(query) = ctx.callback_query() {
+            utils::send_with_retry(|| {
+                query
+                    .answer()
+                    .alert(utils::remove_html(t("not_authenticated")))
+                    .send()
+            })
+            .await?;
+        } else if ctx.is_private() {
+            // The authorization button only works in private chats.
+            let attempt = sessions.start_attempt(sender.id()).await;
+
+            let message = InputMessage::html(t("not_authenticated"))
+                .reply_markup(&auth::authorize_markup(&config, &i18n, &attempt));
+            utils::send_with_retry(|| ctx.reply(message.clone())).await?;
         } else {
-            let name = args.join(" ");
+            let message = InputMessage::html(t("not_authenticated_private"));
+            utils::send_with_retry(|| ctx.reply(message.clone())).await?;
+        }
+        return Ok(());
+    };
 
-            if let Some(result) = ani.search_user(&name, 1, 6).await {
-                if result.is_empty() {
-                    ctx.reply(InputMessage::html(t("no_results_text")).reply_markup(
-                        &reply_markup::inline(vec![vec![button::switch_inline(
-                            t("search_again_btn"),
-                            format!("!u {}", name),
-                        )]]),
-                    ))
-                    .await?;
-                    return Ok(());
-                } else if result.len() == 1 {
-                    return send_user_info(&result[0], ctx).await;
+    match ani.get_user(anilist_id).await {
+        Ok(user) => {
+            let resources = CardResources {
+                cards: &cards,
+                i18n: &i18n,
+                ani: &ani,
+            };
+            send_user_info(&user, ctx, resources).await?
+        }
+        Err(FetchError::RateLimited(retry_after)) => {
+            let message = InputMessage::html(utils::rate_limited_text(retry_after, &i18n));
+            utils::send_with_retry(|| ctx.reply(message.clone())).await?;
+        }
+        Err(FetchError::NotFound) => {
+            let message = InputMessage::html(t("not_found"));
+            utils::send_with_retry(|| ctx.reply(message.clone())).await?;
+        }
+    }
+
+    Ok(())
+}
+
+/// The user stats and favourites callback handler.
+async fn user_section(ctx: Context, i18n: I18n, ani: AniList) -> Result<()> {
+    let t = |key: &str| i18n.translate(key);
+
+    let query = ctx.callback_query().unwrap();
+    let text = utils::callback_data(&query);
+    let args = text.split_whitespace().skip(1).collect::<Vec<_>>();
+
+    let sender = ctx.sender().unwrap();
+    let user_id = args[1].parse::<i32>().unwrap();
+    let sender_id = args[2].parse::<i64>().unwrap();
+
+    if sender.id() != sender_id {
+        utils::send_with_retry(|| {
+            query
+                .answer()
+                .cache_time(Duration::from_secs(120))
+                .alert(t("not_allowed"))
+                .send()
+        })
+        .await?;
+        return Ok(());
+    }
+
+    let mut buttons = Vec::new();
+    let text = match args[0] {
+        "stats" => ani
+            .get_user_stats(user_id)
+            .await
+            .filter(|stats| stats.anime.count > 0 || stats.manga.count > 0)
+            .map(|stats| utils::gen_user_stats(&stats, &i18n)),
+        _ => ani
+            .get_user_favourites(user_id)
+            .await
+            .filter(|favourites| !favourites.is_empty())
+            .map(|favourites| {
+                for (callback, favourites) in [
+                    ("anime", &favourites.anime),
+                    ("manga", &favourites.manga),
+                    ("char", &favourites.characters),
+                ] {
+                    let row = favourites
+                        .iter()
+                        .map(|favourite| {
+                            button::inline(
+                                utils::shorten_text(&favourite.name, 20),
+                                utils::sign_callback(&format!(
+                                    "{0} {1} {2}",
+                                    callback, favourite.id, sender_id
+                                )),
+                            )
+                        })
+                        .collect::<Vec<_>>();
+                    buttons.extend(split_btns_into_columns(row, 3));
                 }
 
-                let buttons = result
-                    .into_iter()
-                    .map(|user| vec![button::inline(user.name, format!("user {}", user.id))])
-                    .collect::<Vec<_>>();
+                utils::gen_user_favourites(&favourites, &i18n)
+            }),
+    };
 
-                ctx.reply(
-                    InputMessage::html(t_a("search_results", hashmap! { "search" => name }))
-                        .reply_markup(&reply_markup::inline(buttons)),
-                )
-                .await?;
-            } else {
-                ctx.reply(InputMessage::html(t("no_results_text")).reply_markup(
-                    &reply_markup::inline(vec![vec![button::switch_inline(
-                        t("search_again_btn"),
-                        format!("!u {}", name),
-                    )]]),
-                ))
+    let Some(text) = text else {
+        utils::send_with_retry(|| {
+            query
+                .answer()
+                .cache_time(Duration::from_secs(120))
+                .alert(t("not_available"))
+                .send()
+        })
+        .await?;
+        return Ok(());
+    };
+
+    buttons.push(vec![button::inline(
+        t("back_btn"),
+        utils::sign_callback(&format!("user {0} {1}", user_id, sender_id)),
+    )]);
+
+    utils::edit_card_page(&query, &text, buttons).await?;
+
+    Ok(())
+}
+
+/// The user list callback handler.
+///
+/// Pages through a user's public list of a media type, one status at a time.
+async fn user_list(ctx: Context, i18n: I18n, ani: AniList) -> Result<()> {
+    /// The statuses the list can be switched to.
+    const STATUSES: [ListStatus; 4] = [
+        ListStatus::Current,
+        ListStatus::Completed,
+        ListStatus::Planning,
+        ListStatus::Dropped,
+    ];
+
+    let t = |key: &str| i18n.translate(key);
+
+    let query = ctx.callback_query().unwrap();
+    let text = utils::callback_data(&query);
+    let args = text.split_whitespace().skip(2).collect::<Vec<_>>();
+
+    let sender = ctx.sender().unwrap();
+    let user_id = args[0].parse::<i32>().unwrap();
+    let media_type = match args[1] {
+        "manga" => MediaType::Manga,
+        _ => MediaType::Anime,
+    };
+    let status = ListStatus::parse(args[2]).unwrap_or(ListStatus::Current);
+    let Some(page) = args.get(3).and_then(|page| page.parse::<u16>().ok()) else {
+        utils::send_with_retry(|| {
+            query
+                .answer()
+                .cache_time(Duration::from_secs(120))
+                .alert(t("page_not_found"))
+                .send()
+        })
+        .await?;
+        return Ok(());
+    };
+    let page = page.max(1);
+    let sender_id = args[4].parse::<i64>().unwrap();
+
+    if sender.id() != sender_id {
+        utils::send_with_retry(|| {
+            query
+                .answer()
+                .cache_time(Duration::from_secs(120))
+                .alert(t("not_allowed"))
+                .send()
+        })
+        .await?;
+        return Ok(());
+    }
+
+    let entries = match ani
+        .get_user_media_list(user_id, media_type, status, page)
+        .await
+    {
+        Ok(entries) => entries,
+        Err(e) => {
+            let text = match e {
+                QueryError::RateLimited(retry_after) => {
+                    utils::rate_limited_text(retry_after, &i18n)
+                }
+                e if lists::is_private(&e) => t("user_list_private"),
+                _ => t("not_available"),
+            };
+            utils::send_with_retry(|| query.answer().alert(utils::remove_html(&text)).send())
                 .await?;
-            }
+            return Ok(());
         }
+    };
+    if entries.entries.is_empty() && page > 1 {
+        utils::send_with_retry(|| {
+            query
+                .answer()
+                .cache_time(Duration::from_secs(120))
+                .alert(t("page_not_found"))
+                .send()
+        })
+        .await?;
+        return Ok(());
     }
 
+    let callback = |status: ListStatus, page: usize| {
+        utils::sign_callback(&format!(
+            "user list {0} {1} {2} {3} {4}",
+            user_id,
+            args[1],
+            status.as_str(),
+            page,
+            sender_id
+        ))
+    };
+
+    let title = list::status_label(status, media_type, &i18n);
+    let text = if entries.entries.is_empty() {
+        format!("📋 <b>{0}</b>:\n\n{1}", title, t("user_list_empty"))
+    } else {
+        utils::gen_user_list(&title, &entries.entries)
+    };
+
+    let mut buttons = vec![
+        STATUSES
+            .into_iter()
+            .map(|other| {
+                let label = list::status_label(other, media_type, &i18n);
+                button::inline(
+                    if other == status {
+                        format!("• {} •", label)
+                    } else {
+                        label
+                    },
+                    callback(other, 1),
+                )
+            })
+            .collect::<Vec<_>>(),
+    ];
+    if entries.last_page > 1 {
+        buttons.push(utils::gen_pagination_buttons_with(
+            page as usize,
+            entries.last_page,
+            |page| callback(status, page),
+        ));
+    }
+    buttons.push(vec![button::inline(
+        t("back_btn"),
+        utils::sign_callback(&format!("user {0} {1}", user_id, sender_id)),
+    )]);
+
+    utils::edit_card_page(&query, &text, buttons).await?;
+
     Ok(())
 }
 
 /// Sends the user info to the user.
-async fn send_user_info(user: &User, ctx: Context) -> Result<()> {
+pub async fn send_user_info(user: &User, ctx: Context, resources: CardResources<'_>) -> Result<()> {
+    let CardResources { cards, i18n, .. } = resources;
+    let t = |key: &str| i18n.translate(key);
+
     let text = utils::gen_user_info(&user);
-    let mut image_url = format!("https://img.anili.st/user/{}", user.id);
+    // The image shows the stats, so its link changes for Telegram to fetch it again.
+    let image_url = format!(
+        "https://img.anili.st/user/{0}?u={1}",
+        user.id,
+        rand::random::<u32>()
+    );
 
-    if ctx.is_callback_query() {
-        ctx.edit(
-            InputMessage::html(format!("<a href=\"{}\">⁠</a>", image_url) + &text)
-                .link_preview(true),
-        )
-        .await?;
-    } else {
-        image_url.push_str(&format!("?u={}", rand::random::<u32>()));
-        ctx.reply(InputMessage::html(text).photo_url(image_url))
-            .await?;
+    let sender = ctx.sender().unwrap();
+    let mut buttons = vec![
+        vec![
+            button::inline(
+                t("anime_list_btn"),
+                utils::sign_callback(&format!(
+                    "user list {0} anime CURRENT 1 {1}",
+                    user.id,
+                    sender.id()
+                )),
+            ),
+            button::inline(
+                t("manga_list_btn"),
+                utils::sign_callback(&format!(
+                    "user list {0} manga CURRENT 1 {1}",
+                    user.id,
+                    sender.id()
+                )),
+            ),
+        ],
+        vec![
+            button::inline(
+                t("stats_btn"),
+                utils::sign_callback(&format!("user stats {0} {1}", user.id, sender.id())),
+            ),
+            button::inline(
+                t("favourites_btn"),
+                utils::sign_callback(&format!("user favourites {0} {1}", user.id, sender.id())),
+            ),
+        ],
+    ];
+    if let Some(share) = utils::gen_share_button(StartPayload::User(user.id), i18n) {
+        buttons.push(vec![share]);
     }
+    utils::send_card(&ctx, cards.files(), &text, Some(&image_url), buttons, false).await?;
 
     Ok(())
 }
 
 /// Generates an inline query article for a user.
-async fn user_inline(query: InlineQuery, i18n: I18n, ani: AniList) -> Result<()> {
+async fn user_inline(
+    query: InlineQuery,
+    i18n: I18n,
+    ani: AniList,
+    config: Config,
+    debounce: Debounce<i64>,
+) -> Result<()> {
     let t = |key: &str| i18n.translate(key);
 
     let arg = query
@@ -131,6 +568,11 @@ async fn user_inline(query: InlineQuery, i18n: I18n, ani: AniList) -> Result<()>
         .skip(1)
         .collect::<Vec<_>>()
         .join(" ");
+
+    if !should_search(&query, &arg, &config, &debounce, &i18n).await? {
+        return Ok(());
+    }
+
     let offset = query.offset().parse::<u16>().unwrap_or(1);
     let mut results = Vec::new();
 
@@ -182,7 +624,7 @@ async fn user_inline(query: InlineQuery, i18n: I18n, ani: AniList) -> Result<()>
 }
 
 /// Generates an inline query article for a user.
-fn gen_user_article(user: User) -> inline::query::Article {
+pub fn gen_user_article(user: User) -> inline::query::Article {
     let text = utils::gen_user_info(&user);
     let image_url = format!("https://img.anili.st/user/{}", user.id);
 
@@ -0,0 +1,442 @@
+// Copyright 2025 - Andriel Ferreira
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// https://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or https://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+//! The watch party plugin.
+
+use std::time::Duration;
+
+use async_trait::async_trait;
+use ferogram::{Context, Result, Router, filter, handler, utils::bytes_to_string};
+use grammers_client::{
+    Client, InputMessage,
+    button::{self, Inline},
+    grammers_tl_types as tl, reply_markup,
+    types::{Chat, Media, Message},
+};
+use maplit::hashmap;
+use rust_anilist::models::Anime;
+
+use crate::{
+    commands::{HelpCategory, HelpEntry},
+    models::{ChatSettings, MediaType},
+    plugins::{anime::ANILIST_BANNER_URL, settings},
+    resources::{AniList, I18n, anilist::FetchError},
+    utils::{self, Resolver, SearchQuery},
+};
+
+/// The option of the poll answer that votes for watching.
+const YES_OPTION: &[u8] = b"0";
+
+/// The option of the poll answer that votes against watching.
+const NO_OPTION: &[u8] = b"1";
+
+/// The commands of the plugin, for `/help`.
+pub const HELP: &[HelpEntry] = &[HelpEntry::new("wp", HelpCategory::Discover)];
+
+/// The plugin setup.
+pub fn setup(router: Router) -> Router {
+    router
+        .register(
+            handler::new_message(
+                filter::command("wp").description("Ask a group whether to watch an anime."),
+            )
+            .then(watch_party),
+        )
+        .register(
+            handler::callback_query(filter::regex(r"^wp (\d+) (\d+)")).then(watch_party_result),
+        )
+        .register(
+            handler::callback_query(filter::regex(r"^wp finish (\d+) (\d+) (\d+)$"))
+                .then(watch_party_finish),
+        )
+}
+
+/// The watch party command handler.
+///
+/// Sends the card of an anime followed by a poll asking the group whether to watch it.
+async fn watch_party(
+    ctx: Context,
+    message: Message,
+    i18n: I18n,
+    ani: AniList,
+    settings: ChatSettings,
+) -> Result<()> {
+    if !matches!(ctx.chat(), Some(Chat::Group(_))) {
+        let message = InputMessage::html(i18n.translate("only_group_command"));
+        utils::send_with_retry(|| ctx.reply(message.clone())).await?;
+        return Ok(());
+    }
+
+    find_anime(ctx, Some(&message), &i18n, &ani, &settings).await
+}
+
+/// The watch party callback handler, for the buttons of the search results.
+async fn watch_party_result(
+    ctx: Context,
+    i18n: I18n,
+    ani: AniList,
+    settings: ChatSettings,
+) -> Result<()> {
+    find_anime(ctx, None, &i18n, &ani, &settings).await
+}
+
+/// Finds the anime of a watch party, from its command or from a button of its results.
+async fn find_anime(
+    ctx: Context,
+    command: Option<&Message>,
+    i18n: &I18n,
+    ani: &AniList,
+    settings: &ChatSettings,
+) -> Result<()> {
+    let resolver = WatchPartyResolver {
+        i18n,
+        ani,
+        settings,
+    };
+
+    utils::resolve_or_disambiguate(
+        ctx,
+        command,
+        &resolver,
+        ani,
+        i18n,
+        settings.clean_service_messages,
+    )
+    .await
+}
+
+/// Finds the anime of the watch party, see [`utils::resolve_or_disambiguate`].
+struct WatchPartyResolver<'a> {
+    /// The i18n resource.
+    i18n: &'a I18n,
+    /// The AniList resource.
+    ani: &'a AniList,
+    /// The settings of the chat.
+    settings: &'a ChatSettings,
+}
+
+#[async_trait]
+impl Resolver for WatchPartyResolver<'_> {
+    type Entry = Anime;
+
+    fn usage_key(&self) -> &'static str {
+        "watch_party_usage"
+    }
+
+    fn inline_query(&self) -> &'static str {
+        "!a"
+    }
+
+    async fn fetch(&self, arg: &str) -> Option<std::result::Result<Anime, FetchError>> {
+        let reference = utils::parse_media_reference(arg, MediaType::Anime)?;
+
+        Some(self.ani.get_anime_by_reference(reference).await)
+    }
+
+    async fn refetch(&self, anime: Anime) -> Anime {
+        self.ani.get_anime(anime.id).await.unwrap_or(anime)
+    }
+
+    async fn search(&self, query: &SearchQuery) -> Option<Vec<Anime>> {
+        self.ani
+            .search_anime_sorted(&query.terms, query.sort, 1, 6)
+            .await
+            .map(|mut result| {
+                result.retain(|anime| self.settings.allow_adult || !anime.is_adult);
+                result
+            })
+    }
+
+    async fn send(&self, anime: Anime, ctx: Context) -> Result<()> {
+        let t = |key: &str| self.i18n.translate(key);
+
+        if anime.is_adult && !self.settings.allow_adult {
+            return settings::send_adult_content_hidden(ctx, self.i18n).await;
+        }
+
+        let text = utils::gen_anime_info(&anime, self.i18n, self.settings);
+        let image_url = ANILIST_BANNER_URL.to_owned() + &anime.id.to_string();
+        let message = utils::gen_card_message(&text, Some(&image_url));
+        // The results of a search become the card.
+        let card = match ctx.callback_query() {
+            Some(query) => {
+                utils::send_with_retry(|| query.answer().edit(message.clone())).await?;
+                query.load_message().await?
+            }
+            None => {
+                let Some(card) = utils::send_with_retry(|| ctx.reply(message.clone())).await?
+                else {
+                    return Ok(());
+                };
+                card
+            }
+        };
+
+        let sender = ctx.sender().unwrap();
+        let Some(poll) = gen_poll(self.i18n) else {
+            return Ok(());
+        };
+        let message = InputMessage::text("")
+            .copy_media(&poll)
+            .reply_markup(&reply_markup::inline(vec![vec![button::inline(
+                t("watch_party_finish_btn"),
+                format!("wp finish {0} {1} {2}", anime.id, card.id(), sender.id()),
+            )]]));
+        utils::send_with_retry(|| card.reply(message.clone())).await?;
+
+        Ok(())
+    }
+
+    async fn gen_result_buttons(
+        &self,
+        result: Vec<Anime>,
+        _: &SearchQuery,
+        sender_id: i64,
+    ) -> Vec<Vec<Inline>> {
+        let mut buttons = result
+            .into_iter()
+            .map(|anime| {
+                vec![button::inline(
+                    utils::preferred_title(&anime.title, self.settings),
+                    utils::sign_callback(&format!("wp {0} {1}", anime.id, sender_id)),
+                )]
+            })
+            .collect::<Vec<_>>();
+        buttons.push(vec![utils::gen_cancel_button(sender_id, self.i18n)]);
+
+        buttons
+    }
+}
+
+/// The watch party finish callback handler.
+///
+/// Closes the poll and edits its results into the card. Only the member who started the
+/// watch party and the group admins can finish it.
+async fn watch_party_finish(
+    ctx: Context,
+    client: Client,
+    i18n: I18n,
+    ani: AniList,
+    settings: ChatSettings,
+) -> Result<()> {
+    let t = |key: &str| i18n.translate(key);
+    let t_a = |key: &str, args| i18n.translate_with_args(key, args);
+
+    let query = ctx.callback_query().unwrap();
+
+    let data = bytes_to_string(query.data());
+    let args = data.split_whitespace().skip(2).collect::<Vec<_>>();
+
+    let anime_id = args[0].parse::<i64>().unwrap();
+    let card_id = args[1].parse::<i32>().unwrap();
+    let sender_id = args[2].parse::<i64>().unwrap();
+
+    let chat = query.chat();
+    let presser_id = query.sender().id();
+    if presser_id != sender_id && !is_admin(&client, &chat, query.sender()).await {
+        utils::send_with_retry(|| {
+            query
+                .answer()
+                .cache_time(Duration::from_secs(120))
+                .alert(t("not_allowed"))
+                .send()
+        })
+        .await?;
+        return Ok(());
+    }
+
+    let message = query.load_message().await?;
+    let Some(Media::Poll(poll)) = message.media() else {
+        utils::send_with_retry(|| query.answer().send()).await?;
+        return Ok(());
+    };
+
+    let (yes, no) = tally_votes(&poll.raw_results);
+    // Telegram closes a poll when the same poll is sent back closed.
+    let mut closed = poll.raw.clone();
+    closed.closed = true;
+    let closed = Media::from_raw(tl::enums::MessageMedia::Poll(tl::types::MessageMediaPoll {
+        poll: closed.into(),
+        results: poll.raw_results.clone().into(),
+    }));
+    if let Some(closed) = closed {
+        utils::send_with_retry(|| message.edit(InputMessage::text("").copy_media(&closed))).await?;
+    }
+
+    let results = t_a(
+        "watch_party_results",
+        hashmap! {
+            "yes" => yes.to_string(),
+            "no" => no.to_string(),
+            "verdict" => t(if yes > no { "watch_party_watch" } else { "watch_party_skip" }),
+        },
+    );
+    let card = match ani.get_anime(anime_id).await {
+        Ok(anime) => {
+            let text = utils::gen_anime_info(&anime, &i18n, &settings) + "\n" + &results;
+            let image_url = ANILIST_BANNER_URL.to_owned() + &anime.id.to_string();
+
+            client
+                .edit_message(
+                    chat.pack(),
+                    card_id,
+                    utils::gen_card_message(&text, Some(&image_url)),
+                )
+                .await
+                .map_err(|e| e.to_string())
+        }
+        Err(e) => Err(e.to_string()),
+    };
+
+    // The card may be gone, or its results may not fit in it, the poll gets them then.
+    if let Err(e) = card {
+        log::debug!(
+            "failed to edit the results into the card {}: {}",
+            card_id,
+            e
+        );
+        let results = InputMessage::html(results);
+        utils::send_with_retry(|| message.reply(results.clone())).await?;
+    }
+    utils::send_with_retry(|| query.answer().send()).await?;
+
+    Ok(())
+}
+
+/// Generates the poll of a new watch party.
+///
+/// # Arguments
+///
+/// * `i18n` - The i18n resource.
+fn gen_poll(i18n: &I18n) -> Option<Media> {
+    let t = |key: &str| i18n.translate(key);
+    let text = |text: String| {
+        tl::enums::TextWithEntities::Entities(tl::types::TextWithEntities {
+            text,
+            entities: Vec::new(),
+        })
+    };
+    let answer = |text: tl::enums::TextWithEntities, option: &[u8]| {
+        tl::enums::PollAnswer::Answer(tl::types::PollAnswer {
+            text,
+            option: option.to_vec(),
+        })
+    };
+
+    Media::from_raw(tl::enums::MessageMedia::Poll(tl::types::MessageMediaPoll {
+        poll: tl::enums::Poll::Poll(tl::types::Poll {
+            id: rand::random(),
+            closed: false,
+            public_voters: false,
+            multiple_choice: false,
+            quiz: false,
+            question: text(t("watch_party_question")),
+            answers: vec![
+                answer(text(t("watch_party_yes")), YES_OPTION),
+                answer(text(t("watch_party_no")), NO_OPTION),
+            ],
+            close_period: None,
+            close_date: None,
+        }),
+        results: tl::enums::PollResults::Results(tl::types::PollResults {
+            min: false,
+            results: None,
+            total_voters: None,
+            recent_voters: None,
+            solution: None,
+            solution_entities: None,
+        }),
+    }))
+}
+
+/// Counts the votes of a watch party poll.
+///
+/// Returns the votes for and against watching.
+///
+/// # Arguments
+///
+/// * `results` - The results of the poll.
+pub fn tally_votes(results: &tl::types::PollResults) -> (i32, i32) {
+    results
+        .results
+        .iter()
+        .flatten()
+        .fold((0, 0), |(yes, no), voters| {
+            let tl::enums::PollAnswerVoters::Voters(voters) = voters;
+
+            match voters.option.as_slice() {
+                YES_OPTION => (yes + voters.voters, no),
+                NO_OPTION => (yes, no + voters.voters),
+                _ => (yes, no),
+            }
+        })
+}
+
+/// Whether a member is an admin of a group.
+///
+/// Members whose rights could not be checked are not admins.
+///
+/// # Arguments
+///
+/// * `client` - The Telegram client.
+/// * `chat` - The group.
+/// * `member` - The member.
+async fn is_admin(client: &Client, chat: &Chat, member: &Chat) -> bool {
+    let result = match chat.pack().try_to_input_channel() {
+        Some(channel) => client
+            .invoke(&tl::functions::channels::GetParticipant {
+                channel,
+                participant: member.pack().to_input_peer(),
+            })
+            .await
+            .map(|participant| {
+                let tl::enums::channels::ChannelParticipant::Participant(participant) =
+                    participant;
+
+                matches!(
+                    participant.participant,
+                    tl::enums::ChannelParticipant::Admin(_)
+                        | tl::enums::ChannelParticipant::Creator(_)
+                )
+            }),
+        // Basic groups have no way to get a single participant, all of them are listed.
+        None => client
+            .invoke(&tl::functions::messages::GetFullChat { chat_id: chat.id() })
+            .await
+            .map(|full| {
+                let tl::enums::messages::ChatFull::Full(full) = full;
+                let tl::enums::ChatFull::Full(full) = full.full_chat else {
+                    return false;
+                };
+                let tl::enums::ChatParticipants::Participants(participants) = full.participants
+                else {
+                    return false;
+                };
+
+                participants.participants.iter().any(|participant| {
+                    matches!(
+                        participant,
+                        tl::enums::ChatParticipant::Admin(admin) if admin.user_id == member.id()
+                    ) || matches!(
+                        participant,
+                        tl::enums::ChatParticipant::Creator(creator) if creator.user_id == member.id()
+                    )
+                })
+            }),
+    };
+
+    result.unwrap_or_else(|e| {
+        log::warn!(
+            "failed to check whether {} is an admin of {}: {:?}",
+            member.id(),
+            chat.id(),
+            e
+        );
+        false
+    })
+}
@@ -0,0 +1,73 @@
+// Copyright 2025 - Andriel Ferreira
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// https://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or https://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+//! The episodes that aired in a time window.
+
+use rust_anilist::models::Title;
+use serde::Deserialize;
+
+/// The query of the episodes of some animes that aired in a time window.
+pub const QUERY: &str = "
+    query ($ids: [Int], $from: Int, $to: Int, $page: Int) {
+        Page(page: $page, perPage: 50) {
+            pageInfo { hasNextPage }
+            airingSchedules(mediaId_in: $ids, airingAt_greater: $from, airingAt_lesser: $to, sort: TIME) {
+                mediaId
+                episode
+                airingAt
+                media { title { romaji english native userPreferred } }
+            }
+        }
+    }
+";
+
+/// How many animes are asked about in a single query.
+pub const IDS_PER_QUERY: usize = 100;
+
+/// How many pages of each query are fetched at most.
+pub const MAX_PAGES: u16 = 10;
+
+/// A page of aired episodes.
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AiredPage {
+    /// The pagination info.
+    pub page_info: PageInfo,
+    /// The aired episodes.
+    #[serde(default)]
+    pub airing_schedules: Vec<AiredEpisode>,
+}
+
+/// The pagination info of a page.
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PageInfo {
+    /// Whether there is a next page.
+    pub has_next_page: bool,
+}
+
+/// An episode that aired.
+#[derive(Clone, Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AiredEpisode {
+    /// The AniList ID of the anime.
+    pub media_id: i64,
+    /// The episode number.
+    pub episode: i32,
+    /// When the episode aired, as a Unix timestamp.
+    pub airing_at: i64,
+    /// The anime.
+    pub media: AiredMedia,
+}
+
+/// The anime of an aired episode.
+#[derive(Clone, Debug, Deserialize)]
+pub struct AiredMedia {
+    /// The title.
+    pub title: Title,
+}
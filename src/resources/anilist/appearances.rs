@@ -0,0 +1,119 @@
+// Copyright 2025 - Andriel Ferreira
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// https://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or https://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+//! The media a character appears in.
+
+use serde::Deserialize;
+
+use crate::models::MediaType;
+
+/// The query of the media appearances, the most popular first.
+pub const QUERY: &str = "
+    query ($id: Int) {
+        Character(id: $id) {
+            media(perPage: 50, sort: POPULARITY_DESC) {
+                edges {
+                    characterRole
+                    node { id type title { romaji } }
+                }
+            }
+        }
+    }
+";
+
+/// A media a character appears in.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Appearance {
+    /// The AniList ID of the media.
+    pub id: i64,
+    /// The media type.
+    pub media_type: MediaType,
+    /// The romaji title of the media.
+    pub title: String,
+    /// The role of the character, e.g. `MAIN`.
+    pub role: Option<String>,
+}
+
+/// The media appearances of a character.
+#[derive(Deserialize)]
+pub struct CharacterAppearances {
+    /// The character.
+    #[serde(rename = "Character")]
+    pub character: CharacterMedia,
+}
+
+/// The media of a character.
+#[derive(Deserialize)]
+pub struct CharacterMedia {
+    /// The media connection.
+    pub media: MediaConnection,
+}
+
+/// A media connection.
+#[derive(Deserialize)]
+pub struct MediaConnection {
+    /// The edges of the connection.
+    #[serde(default)]
+    pub edges: Vec<MediaEdge>,
+}
+
+/// A media edge.
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct MediaEdge {
+    /// The role of the character in the media.
+    pub character_role: Option<String>,
+    /// The media.
+    pub node: MediaNode,
+}
+
+/// A media node.
+#[derive(Deserialize)]
+pub struct MediaNode {
+    /// The AniList ID.
+    pub id: i64,
+    /// The media type, `ANIME` or `MANGA`.
+    #[serde(rename = "type")]
+    pub media_type: Option<String>,
+    /// The title.
+    pub title: MediaTitle,
+}
+
+/// The title of a media.
+#[derive(Deserialize)]
+pub struct MediaTitle {
+    /// The romaji title.
+    pub romaji: Option<String>,
+}
+
+/// Converts the media edges into appearances.
+///
+/// Edges with an unknown media type are skipped.
+///
+/// # Arguments
+///
+/// * `edges` - The media edges.
+pub fn collect(edges: Vec<MediaEdge>) -> Vec<Appearance> {
+    edges
+        .into_iter()
+        .filter_map(|edge| {
+            let media_type = match edge.node.media_type.as_deref() {
+                Some("ANIME") => MediaType::Anime,
+                Some("MANGA") => MediaType::Manga,
+                _ => return None,
+            };
+
+            Some(Appearance {
+                id: edge.node.id,
+                media_type,
+                title: edge.node.title.romaji.unwrap_or_default(),
+                role: edge.character_role,
+            })
+        })
+        .collect()
+}
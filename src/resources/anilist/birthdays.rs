@@ -0,0 +1,181 @@
+// Copyright 2025 - Andriel Ferreira
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// https://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or https://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+//! The characters whose birthday is today.
+
+use serde::Deserialize;
+
+use super::graphql::PageInfo;
+use crate::models::MediaType;
+
+/// How many characters are listed per page.
+pub const PER_PAGE: u16 = 10;
+
+/// The query of a page of birthday characters, the most favourited first.
+pub const QUERY: &str = "
+    query ($page: Int, $perPage: Int) {
+        Page(page: $page, perPage: $perPage) {
+            pageInfo { lastPage }
+            characters(isBirthday: true, sort: [FAVOURITES_DESC, ID]) {
+                id
+                name { full }
+                age
+                media(perPage: 1, sort: POPULARITY_DESC) {
+                    nodes { id type title { romaji } }
+                }
+            }
+        }
+    }
+";
+
+/// A character whose birthday is today.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct BirthdayCharacter {
+    /// The AniList ID of the character.
+    pub id: i64,
+    /// The full name of the character.
+    pub name: String,
+    /// The age of the character, free text on AniList, e.g. `17` or `17-19`.
+    pub age: Option<String>,
+    /// The media the character is best known for, i.e. its most popular one.
+    pub known_for: Option<KnownFor>,
+}
+
+/// The media a character is best known for.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct KnownFor {
+    /// The AniList ID of the media.
+    pub id: i64,
+    /// The media type.
+    pub media_type: MediaType,
+    /// The romaji title of the media.
+    pub title: String,
+}
+
+/// A page of birthday characters.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct BirthdayPage {
+    /// The characters of the page.
+    pub characters: Vec<BirthdayCharacter>,
+    /// The number of the last page.
+    pub last_page: usize,
+}
+
+/// A page of characters.
+#[derive(Deserialize)]
+pub struct CharacterPage {
+    /// The page.
+    #[serde(rename = "Page")]
+    pub page: Characters,
+}
+
+/// The characters of a page.
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Characters {
+    /// The pagination of the page.
+    pub page_info: PageInfo,
+    /// The characters.
+    #[serde(default)]
+    pub characters: Vec<CharacterNode>,
+}
+
+/// A character node.
+#[derive(Deserialize)]
+pub struct CharacterNode {
+    /// The AniList ID.
+    pub id: i64,
+    /// The name.
+    pub name: CharacterName,
+    /// The age.
+    pub age: Option<String>,
+    /// The most popular media of the character.
+    pub media: Option<MediaConnection>,
+}
+
+/// The name of a character.
+#[derive(Deserialize)]
+pub struct CharacterName {
+    /// The full name.
+    pub full: Option<String>,
+}
+
+/// A media connection.
+#[derive(Deserialize)]
+pub struct MediaConnection {
+    /// The nodes of the connection.
+    #[serde(default)]
+    pub nodes: Vec<MediaNode>,
+}
+
+/// A media node.
+#[derive(Deserialize)]
+pub struct MediaNode {
+    /// The AniList ID.
+    pub id: i64,
+    /// The media type, `ANIME` or `MANGA`.
+    #[serde(rename = "type")]
+    pub media_type: Option<String>,
+    /// The title.
+    pub title: MediaTitle,
+}
+
+/// The title of a media.
+#[derive(Deserialize)]
+pub struct MediaTitle {
+    /// The romaji title.
+    pub romaji: Option<String>,
+}
+
+/// Converts a page of characters into a page of birthday characters.
+///
+/// Characters without a name are skipped, and blank ages are dropped.
+///
+/// # Arguments
+///
+/// * `page` - The page of characters.
+pub fn collect(page: Characters) -> BirthdayPage {
+    let characters = page
+        .characters
+        .into_iter()
+        .filter_map(|character| {
+            let name = character.name.full.filter(|name| !name.trim().is_empty())?;
+            let known_for = character
+                .media
+                .and_then(|media| media.nodes.into_iter().next())
+                .and_then(|media| {
+                    let media_type = match media.media_type.as_deref() {
+                        Some("ANIME") => MediaType::Anime,
+                        Some("MANGA") => MediaType::Manga,
+                        _ => return None,
+                    };
+
+                    Some(KnownFor {
+                        id: media.id,
+                        media_type,
+                        title: media.title.romaji.unwrap_or_default(),
+                    })
+                });
+
+            Some(BirthdayCharacter {
+                id: character.id,
+                name,
+                age: character
+                    .age
+                    .map(|age| age.trim().to_string())
+                    .filter(|age| !age.is_empty()),
+                known_for,
+            })
+        })
+        .collect();
+
+    BirthdayPage {
+        characters,
+        last_page: page.page_info.last_page.unwrap_or(1).max(1) as usize,
+    }
+}
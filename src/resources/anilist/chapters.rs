@@ -0,0 +1,74 @@
+// Copyright 2025 - Andriel Ferreira
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// https://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or https://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+//! The chapter data of a manga.
+//!
+//! AniList only knows the chapter and volume counts, so the reading links are fetched along
+//! with them.
+
+use serde::Deserialize;
+
+/// The query of the chapter data.
+pub const QUERY: &str = "
+    query ($id: Int) {
+        Media(id: $id, type: MANGA) {
+            chapters
+            volumes
+            externalLinks { site url type isDisabled }
+        }
+    }
+";
+
+/// The chapter data of a manga.
+#[derive(Deserialize)]
+pub struct MediaChapters {
+    /// The media.
+    #[serde(rename = "Media")]
+    pub media: ChapterData,
+}
+
+/// The chapter data of a media.
+#[derive(Clone, Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ChapterData {
+    /// The number of chapters, if known.
+    pub chapters: Option<i64>,
+    /// The number of volumes, if known.
+    pub volumes: Option<i64>,
+    /// The external links.
+    #[serde(default)]
+    pub external_links: Vec<ExternalLink>,
+}
+
+/// An external link of a media.
+#[derive(Clone, Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ExternalLink {
+    /// The site name.
+    pub site: String,
+    /// The link.
+    pub url: String,
+    /// The link type, `STREAMING` for the reading sites of a manga.
+    #[serde(rename = "type")]
+    pub link_type: Option<String>,
+    /// The language of the site, e.g. `Japanese`.
+    pub language: Option<String>,
+    /// Whether the link is disabled.
+    pub is_disabled: Option<bool>,
+}
+
+impl ChapterData {
+    /// Gets the links of the sites where the manga can be read.
+    pub fn reading_links(&self) -> Vec<&ExternalLink> {
+        self.external_links
+            .iter()
+            .filter(|link| link.is_disabled != Some(true))
+            .filter(|link| link.link_type.as_deref() == Some("STREAMING"))
+            .collect()
+    }
+}
@@ -0,0 +1,171 @@
+// Copyright 2025 - Andriel Ferreira
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// https://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or https://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+//! The characters of a media, paged by AniList.
+
+use serde::Deserialize;
+
+use super::graphql::PageInfo;
+
+/// How many characters are listed per page by default.
+pub const PER_PAGE: u16 = 10;
+
+/// The query of a page of characters, the main ones first, with their voice actors in a
+/// language.
+pub const QUERY: &str = "
+    query ($id: Int, $type: MediaType, $page: Int, $perPage: Int, $language: StaffLanguage) {
+        Media(id: $id, type: $type) {
+            characters(page: $page, perPage: $perPage, sort: [ROLE, RELEVANCE, ID]) {
+                pageInfo { lastPage total }
+                edges {
+                    role
+                    node { id gender name { full } }
+                    voiceActors(language: $language, sort: [RELEVANCE, ID]) { id name { full } }
+                }
+            }
+        }
+    }
+";
+
+/// A character of a media.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct MediaCharacter {
+    /// The AniList ID of the character.
+    pub id: i64,
+    /// The full name of the character.
+    pub name: String,
+    /// The gender of the character, e.g. `Female`.
+    pub gender: Option<String>,
+    /// The role of the character in the media, e.g. `MAIN`.
+    pub role: Option<String>,
+    /// The main voice actor of the character, only anime have them.
+    pub voice_actor: Option<CharacterVoiceActor>,
+}
+
+/// The voice actor of a character.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct CharacterVoiceActor {
+    /// The AniList ID of the staff.
+    pub id: i64,
+    /// The full name of the staff.
+    pub name: String,
+}
+
+/// A page of characters.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct CharacterPage {
+    /// The characters of the page.
+    pub characters: Vec<MediaCharacter>,
+    /// The number of the last page.
+    pub last_page: usize,
+    /// The number of characters, across all pages.
+    pub total: usize,
+}
+
+/// The characters of a media.
+#[derive(Deserialize)]
+pub struct MediaCharacters {
+    /// The media.
+    #[serde(rename = "Media")]
+    pub media: CharacterMedia,
+}
+
+/// The media of the characters.
+#[derive(Deserialize)]
+pub struct CharacterMedia {
+    /// The character connection.
+    pub characters: CharacterConnection,
+}
+
+/// A character connection.
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CharacterConnection {
+    /// The pagination of the connection.
+    pub page_info: PageInfo,
+    /// The edges of the connection.
+    #[serde(default)]
+    pub edges: Vec<CharacterEdge>,
+}
+
+/// A character edge.
+#[derive(Deserialize)]
+pub struct CharacterEdge {
+    /// The role of the character in the media.
+    pub role: Option<String>,
+    /// The character, missing if it was deleted.
+    pub node: Option<CharacterNode>,
+    /// The voice actors of the character, the most relevant first.
+    #[serde(default, rename = "voiceActors")]
+    pub voice_actors: Option<Vec<VoiceActorNode>>,
+}
+
+/// A character node.
+#[derive(Deserialize)]
+pub struct CharacterNode {
+    /// The AniList ID.
+    pub id: i64,
+    /// The gender.
+    pub gender: Option<String>,
+    /// The name.
+    pub name: CharacterName,
+}
+
+/// A voice actor node.
+#[derive(Deserialize)]
+pub struct VoiceActorNode {
+    /// The AniList ID.
+    pub id: i64,
+    /// The name.
+    pub name: CharacterName,
+}
+
+/// The name of a character or a voice actor.
+#[derive(Deserialize)]
+pub struct CharacterName {
+    /// The full name.
+    pub full: Option<String>,
+}
+
+/// Converts a character connection into a page of characters.
+///
+/// Deleted characters are skipped, and only the most relevant voice actor is kept.
+///
+/// # Arguments
+///
+/// * `connection` - The character connection.
+pub fn collect(connection: CharacterConnection) -> CharacterPage {
+    let characters =
+        connection
+            .edges
+            .into_iter()
+            .filter_map(|edge| {
+                let node = edge.node?;
+                let voice_actor = edge.voice_actors.into_iter().flatten().next().map(|staff| {
+                    CharacterVoiceActor {
+                        id: staff.id,
+                        name: staff.name.full.unwrap_or_default(),
+                    }
+                });
+
+                Some(MediaCharacter {
+                    id: node.id,
+                    name: node.name.full.unwrap_or_default(),
+                    gender: node.gender,
+                    role: edge.role,
+                    voice_actor,
+                })
+            })
+            .collect();
+
+    CharacterPage {
+        characters,
+        last_page: connection.page_info.last_page.unwrap_or(1).max(1) as usize,
+        total: connection.page_info.total.unwrap_or_default() as usize,
+    }
+}
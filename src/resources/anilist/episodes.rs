@@ -0,0 +1,182 @@
+// Copyright 2025 - Andriel Ferreira
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// https://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or https://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+//! The episode list of an anime.
+
+use std::collections::BTreeMap;
+
+use chrono::{DateTime, Utc};
+use serde::Deserialize;
+
+/// The query of the episode data, one page of the airing schedule at a time.
+pub const QUERY: &str = "
+    query ($id: Int, $page: Int) {
+        Media(id: $id, type: ANIME) {
+            episodes
+            streamingEpisodes { title }
+            airingSchedule(page: $page, perPage: 50) {
+                pageInfo { hasNextPage }
+                nodes { episode airingAt }
+            }
+        }
+    }
+";
+
+/// How many pages of the airing schedule are fetched at most.
+pub const MAX_SCHEDULE_PAGES: u16 = 20;
+
+/// An episode of an anime.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Episode {
+    /// The episode number.
+    pub number: i64,
+    /// The episode title, when a streaming site provides it.
+    pub title: Option<String>,
+    /// When the episode aired, or will air.
+    pub airing_at: Option<DateTime<Utc>>,
+}
+
+/// The episode data of an anime.
+#[derive(Deserialize)]
+pub struct MediaEpisodes {
+    /// The media.
+    #[serde(rename = "Media")]
+    pub media: EpisodeData,
+}
+
+/// The episode data of a media.
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct EpisodeData {
+    /// The number of episodes, if known.
+    pub episodes: Option<i64>,
+    /// The episodes listed by the streaming sites.
+    #[serde(default)]
+    pub streaming_episodes: Vec<StreamingEpisode>,
+    /// A page of the airing schedule.
+    pub airing_schedule: Option<AiringSchedule>,
+}
+
+/// An episode listed by a streaming site.
+#[derive(Deserialize)]
+pub struct StreamingEpisode {
+    /// The title, usually `Episode N - Title`.
+    pub title: Option<String>,
+}
+
+/// A page of the airing schedule.
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AiringSchedule {
+    /// The pagination info.
+    pub page_info: PageInfo,
+    /// The scheduled episodes.
+    #[serde(default)]
+    pub nodes: Vec<AiringEpisode>,
+}
+
+/// The pagination info of a page.
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PageInfo {
+    /// Whether there is a next page.
+    pub has_next_page: bool,
+}
+
+/// A scheduled episode.
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AiringEpisode {
+    /// The episode number.
+    pub episode: i64,
+    /// When the episode airs, as a unix timestamp.
+    pub airing_at: i64,
+}
+
+/// Splits a streaming episode title like `Episode 3 - The Title` into its number and title.
+///
+/// Returns `None` for the number when the title does not start with one.
+///
+/// # Arguments
+///
+/// * `title` - The streaming episode title.
+pub fn parse_streaming_title(title: &str) -> (Option<i64>, String) {
+    let title = title.trim();
+
+    let Some(rest) = title
+        .strip_prefix("Episode ")
+        .or_else(|| title.strip_prefix("Ep. "))
+    else {
+        return (None, title.to_string());
+    };
+
+    let digits = rest
+        .chars()
+        .take_while(char::is_ascii_digit)
+        .collect::<String>();
+    let Ok(number) = digits.parse::<i64>() else {
+        return (None, title.to_string());
+    };
+
+    let name = rest[digits.len()..]
+        .trim_start_matches([' ', '-', ':', '–', '—'])
+        .trim()
+        .to_string();
+
+    (Some(number), name)
+}
+
+/// Merges the streaming titles and the airing schedule into a single episode list.
+///
+/// Streaming titles without a number are numbered by their position. Episodes neither source
+/// knows about, up to `total`, are listed without a title or date.
+///
+/// # Arguments
+///
+/// * `total` - The number of episodes, if known.
+/// * `streaming` - The streaming episode titles.
+/// * `schedule` - The scheduled episodes.
+pub fn merge(
+    total: Option<i64>,
+    streaming: &[StreamingEpisode],
+    schedule: &[AiringEpisode],
+) -> Vec<Episode> {
+    let mut episodes = BTreeMap::<i64, Episode>::new();
+
+    for scheduled in schedule {
+        entry(&mut episodes, scheduled.episode).airing_at =
+            DateTime::from_timestamp(scheduled.airing_at, 0);
+    }
+
+    for (index, streaming) in streaming.iter().enumerate() {
+        let Some(title) = streaming.title.as_deref() else {
+            continue;
+        };
+
+        let (number, name) = parse_streaming_title(title);
+        let episode = entry(&mut episodes, number.unwrap_or(index as i64 + 1));
+        if !name.is_empty() {
+            episode.title = Some(name);
+        }
+    }
+
+    for number in 1..=total.unwrap_or_default() {
+        entry(&mut episodes, number);
+    }
+
+    episodes.into_values().collect()
+}
+
+/// Gets an episode of the list, adding it if it is not there yet.
+fn entry(episodes: &mut BTreeMap<i64, Episode>, number: i64) -> &mut Episode {
+    episodes.entry(number).or_insert(Episode {
+        number,
+        title: None,
+        airing_at: None,
+    })
+}
@@ -0,0 +1,137 @@
+// Copyright 2025 - Andriel Ferreira
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// https://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or https://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+//! The media of a genre or tag.
+
+use serde::Deserialize;
+
+use super::graphql::PageInfo;
+
+/// How many media are listed per page.
+pub const PER_PAGE: u16 = 10;
+
+/// The query of the genre collection.
+pub const GENRES_QUERY: &str = "query { GenreCollection }";
+
+/// The query of a page of the media of a genre or tag, the most popular first.
+pub const QUERY: &str = "
+    query ($type: MediaType, $genre: String, $tag: String, $page: Int, $perPage: Int) {
+        Page(page: $page, perPage: $perPage) {
+            pageInfo { lastPage }
+            media(type: $type, isAdult: false, genre: $genre, tag: $tag, sort: [POPULARITY_DESC, ID]) {
+                id
+                title { romaji }
+                averageScore
+            }
+        }
+    }
+";
+
+/// A media of a genre or tag.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct GenreEntry {
+    /// The AniList ID of the media.
+    pub id: i64,
+    /// The romaji title of the media.
+    pub title: String,
+    /// The average score of the media, if it was scored.
+    pub average_score: Option<u8>,
+}
+
+/// A page of the media of a genre or tag.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct GenrePage {
+    /// The media of the page.
+    pub media: Vec<GenreEntry>,
+    /// The number of the last page.
+    pub last_page: usize,
+}
+
+/// The genre collection.
+#[derive(Deserialize)]
+pub struct GenreCollection {
+    /// The genres.
+    #[serde(rename = "GenreCollection", default)]
+    pub genres: Vec<String>,
+}
+
+/// A page of media.
+#[derive(Deserialize)]
+pub struct MediaPage {
+    /// The page.
+    #[serde(rename = "Page")]
+    pub page: Media,
+}
+
+/// The media of a page.
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Media {
+    /// The pagination of the page.
+    pub page_info: PageInfo,
+    /// The media.
+    #[serde(default)]
+    pub media: Vec<MediaNode>,
+}
+
+/// A media node.
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct MediaNode {
+    /// The AniList ID.
+    pub id: i64,
+    /// The title.
+    pub title: MediaTitle,
+    /// The average score.
+    pub average_score: Option<u8>,
+}
+
+/// The title of a media.
+#[derive(Deserialize)]
+pub struct MediaTitle {
+    /// The romaji title.
+    pub romaji: Option<String>,
+}
+
+/// Converts a page of media into a page of genre entries.
+///
+/// # Arguments
+///
+/// * `page` - The page of media.
+pub fn collect(page: Media) -> GenrePage {
+    GenrePage {
+        media: page
+            .media
+            .into_iter()
+            .map(|media| GenreEntry {
+                id: media.id,
+                title: media.title.romaji.unwrap_or_default(),
+                average_score: media.average_score,
+            })
+            .collect(),
+        last_page: page.page_info.last_page.unwrap_or(1).max(1) as usize,
+    }
+}
+
+/// Encodes a genre or tag for a callback, with underscores instead of spaces.
+///
+/// # Arguments
+///
+/// * `name` - The name of the genre or tag.
+pub fn encode(name: &str) -> String {
+    name.replace(' ', "_")
+}
+
+/// Decodes a genre or tag from a callback.
+///
+/// # Arguments
+///
+/// * `name` - The encoded name of the genre or tag.
+pub fn decode(name: &str) -> String {
+    name.replace('_', " ")
+}
@@ -0,0 +1,250 @@
+// Copyright 2025 - Andriel Ferreira
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// https://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or https://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+//! Raw GraphQL requests to the AniList API.
+//!
+//! Used for the queries `rust_anilist` does not expose.
+
+use std::{fmt, sync::LazyLock, time::Duration};
+
+use serde::{Deserialize, de::DeserializeOwned};
+use serde_json::{Value, json};
+
+/// The AniList GraphQL endpoint.
+pub const API_URL: &str = "https://graphql.anilist.co";
+
+/// The timeout of the requests.
+const TIMEOUT: Duration = Duration::from_secs(15);
+/// How long to wait when AniList rate limits a request without a `Retry-After` header.
+pub const DEFAULT_RETRY_AFTER: Duration = Duration::from_secs(60);
+
+/// The HTTP client used for the requests.
+static CLIENT: LazyLock<surf::Client> = LazyLock::new(|| {
+    surf::Config::new()
+        .set_timeout(Some(TIMEOUT))
+        .try_into()
+        .expect("failed to build the HTTP client")
+});
+
+/// The media fields requested by the queries that return full media objects.
+pub const MEDIA_FIELDS: &str = "
+    id
+    idMal
+    type
+    title { romaji english native userPreferred }
+    format
+    status
+    description(asHtml: true)
+    startDate { year month day }
+    endDate { year month day }
+    season
+    seasonYear
+    episodes
+    duration
+    chapters
+    volumes
+    countryOfOrigin
+    source
+    coverImage { extraLarge large medium color }
+    bannerImage
+    genres
+    synonyms
+    averageScore
+    meanScore
+    popularity
+    favourites
+    isAdult
+    nextAiringEpisode { airingAt timeUntilAiring episode }
+    siteUrl
+";
+
+/// The response of a GraphQL request.
+#[derive(Deserialize)]
+struct Response<T> {
+    /// The data of the response.
+    data: Option<T>,
+    /// The errors of the response.
+    #[serde(default)]
+    errors: Vec<ResponseError>,
+}
+
+/// An error of a GraphQL response.
+#[derive(Deserialize)]
+struct ResponseError {
+    /// The error message.
+    message: String,
+    /// The HTTP status AniList attached to the error, if any.
+    #[serde(default)]
+    status: Option<u16>,
+}
+
+/// Why a GraphQL request failed.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum QueryError {
+    /// The request could not be sent or its response could not be read.
+    Request,
+    /// The token was rejected, usually because it expired or was revoked.
+    Unauthorized,
+    /// Too many requests were sent, retry after the duration.
+    RateLimited(Duration),
+    /// AniList answered with an error.
+    Api(String),
+}
+
+impl fmt::Display for QueryError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Request => write!(f, "the AniList request failed"),
+            Self::Unauthorized => write!(f, "the AniList token was rejected"),
+            Self::RateLimited(retry_after) => {
+                write!(f, "AniList is rate limiting the bot for {:?}", retry_after)
+            }
+            Self::Api(message) => write!(f, "AniList answered with an error: {}", message),
+        }
+    }
+}
+
+impl std::error::Error for QueryError {}
+
+/// A page of results.
+#[derive(Deserialize)]
+pub struct Page<T> {
+    /// The page of results.
+    #[serde(rename = "Page")]
+    pub page: T,
+}
+
+/// A page of media.
+#[derive(Deserialize)]
+pub struct MediaPage<T> {
+    /// The media of the page.
+    pub media: Vec<T>,
+    /// The pagination info, when requested.
+    #[serde(default, rename = "pageInfo")]
+    pub page_info: Option<PageInfo>,
+}
+
+/// The pagination info of a page.
+#[derive(Deserialize)]
+pub struct PageInfo {
+    /// The number of the last page.
+    #[serde(rename = "lastPage")]
+    pub last_page: Option<u16>,
+    /// The number of results, across all pages.
+    pub total: Option<u32>,
+}
+
+/// A single media, looked up by one of its IDs.
+#[derive(Deserialize)]
+pub struct MediaById {
+    /// The media.
+    #[serde(rename = "Media")]
+    pub media: MediaId,
+}
+
+/// The ID of a media.
+#[derive(Deserialize)]
+pub struct MediaId {
+    /// The AniList ID.
+    pub id: i64,
+}
+
+/// Sends a GraphQL query to AniList and deserializes the `data` of the response.
+///
+/// Returns `None` if the request fails or AniList answers with errors.
+///
+/// # Arguments
+///
+/// * `query` - The GraphQL query.
+/// * `variables` - The variables of the query.
+/// * `token` - The user's AniList token, for queries that need authentication.
+pub async fn query<T: DeserializeOwned>(
+    query: &str,
+    variables: Value,
+    token: Option<&str>,
+) -> Option<T> {
+    try_query(query, variables, token).await.ok()
+}
+
+/// Sends a GraphQL query to AniList and deserializes the `data` of the response.
+///
+/// # Arguments
+///
+/// * `query` - The GraphQL query.
+/// * `variables` - The variables of the query.
+/// * `token` - The user's AniList token, for queries that need authentication.
+///
+/// # Errors
+///
+/// Returns [`QueryError::Unauthorized`] if AniList rejects the token,
+/// [`QueryError::RateLimited`] if too many requests were sent, and another [`QueryError`] if
+/// the request fails or AniList answers with errors.
+pub async fn try_query<T: DeserializeOwned>(
+    query: &str,
+    variables: Value,
+    token: Option<&str>,
+) -> Result<T, QueryError> {
+    let mut request = surf::post(API_URL)
+        .header("content-type", "application/json")
+        .header("accept", "application/json")
+        .body_json(&json!({ "query": query, "variables": variables }))
+        .map_err(|_| QueryError::Request)?;
+    if let Some(token) = token {
+        request = request.header("authorization", format!("Bearer {}", token));
+    }
+
+    let mut response = match CLIENT.send(request).await {
+        Ok(response) => response,
+        Err(e) => {
+            log::warn!("AniList request failed: {:?}", e);
+            return Err(QueryError::Request);
+        }
+    };
+    if response.status() == surf::StatusCode::TooManyRequests {
+        let retry_after = response
+            .header("retry-after")
+            .and_then(|value| value.as_str().trim().parse::<u64>().ok())
+            .map(Duration::from_secs)
+            .unwrap_or(DEFAULT_RETRY_AFTER);
+        log::warn!("AniList rate limited the bot for {:?}", retry_after);
+
+        return Err(QueryError::RateLimited(retry_after));
+    }
+    let unauthorized = response.status() == surf::StatusCode::Unauthorized;
+
+    match response.body_json::<Response<T>>().await {
+        Ok(body) => {
+            for error in body.errors.iter() {
+                log::warn!("AniList answered with an error: {}", error.message);
+            }
+
+            if unauthorized || body.errors.iter().any(|error| error.status == Some(401)) {
+                Err(QueryError::Unauthorized)
+            } else if let Some(data) = body.data {
+                Ok(data)
+            } else if let Some(error) = body.errors.into_iter().next() {
+                Err(QueryError::Api(error.message))
+            } else {
+                Err(QueryError::Request)
+            }
+        }
+        Err(e) => {
+            log::warn!(
+                "failed to parse the AniList response ({}): {:?}",
+                response.status(),
+                e
+            );
+
+            Err(if unauthorized {
+                QueryError::Unauthorized
+            } else {
+                QueryError::Request
+            })
+        }
+    }
+}
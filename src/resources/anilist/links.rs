@@ -0,0 +1,56 @@
+// Copyright 2025 - Andriel Ferreira
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// https://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or https://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+//! The links of a media.
+
+use serde::Deserialize;
+
+use super::chapters::ExternalLink;
+
+/// The query of the links of a media.
+pub const QUERY: &str = "
+    query ($id: Int) {
+        Media(id: $id) {
+            idMal
+            siteUrl
+            externalLinks { site url type language isDisabled }
+        }
+    }
+";
+
+/// The links of a media, looked up by its ID.
+#[derive(Deserialize)]
+pub struct LinksById {
+    /// The media.
+    #[serde(rename = "Media")]
+    pub media: MediaLinks,
+}
+
+/// The links of a media.
+#[derive(Clone, Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct MediaLinks {
+    /// The MyAnimeList ID.
+    pub id_mal: Option<i64>,
+    /// The AniList page.
+    pub site_url: String,
+    /// The external links.
+    #[serde(default)]
+    pub external_links: Vec<ExternalLink>,
+}
+
+impl MediaLinks {
+    /// Gets the external links that are not disabled.
+    ///
+    /// AniList marks them with `isDisabled`, links without it are enabled.
+    pub fn enabled_links(&self) -> impl Iterator<Item = &ExternalLink> {
+        self.external_links
+            .iter()
+            .filter(|link| link.is_disabled != Some(true))
+    }
+}
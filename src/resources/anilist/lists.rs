@@ -0,0 +1,263 @@
+// Copyright 2025 - Andriel Ferreira
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// https://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or https://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+//! The entries of the user's AniList lists.
+
+use serde::Deserialize;
+
+use super::graphql::{PageInfo, QueryError};
+
+/// The mutation that creates or updates an entry of the viewer's list.
+pub const SAVE_MUTATION: &str = "
+    mutation ($mediaId: Int, $status: MediaListStatus, $progress: Int, $score: Float) {
+        SaveMediaListEntry(mediaId: $mediaId, status: $status, progress: $progress, score: $score) {
+            id
+            userId
+            status
+            progress
+            score(format: POINT_10_DECIMAL)
+        }
+    }
+";
+
+/// The query of an entry of a user's list.
+pub const ENTRY_QUERY: &str = "
+    query ($userId: Int, $mediaId: Int) {
+        MediaList(userId: $userId, mediaId: $mediaId) {
+            id
+            userId
+            status
+            progress
+            score(format: POINT_10_DECIMAL)
+        }
+    }
+";
+
+/// How many entries of a user's list are shown per page.
+pub const PER_PAGE: u16 = 15;
+
+/// How many entries of a user's reading list are fetched per page, when checking for new
+/// chapters.
+pub const READING_PER_PAGE: u16 = 50;
+
+/// How many pages of a user's reading list are fetched at most, when checking for new
+/// chapters.
+pub const MAX_READING_PAGES: u16 = 4;
+
+/// The query of a page of a user's list, the last updated first.
+pub const LIST_QUERY: &str = "
+    query ($userId: Int, $type: MediaType, $status: MediaListStatus, $page: Int, $perPage: Int) {
+        Page(page: $page, perPage: $perPage) {
+            pageInfo { lastPage }
+            mediaList(userId: $userId, type: $type, status: $status, sort: [UPDATED_TIME_DESC, MEDIA_ID]) {
+                progress
+                score(format: POINT_10_DECIMAL)
+                media { id title { romaji } episodes chapters }
+            }
+        }
+    }
+";
+
+/// The status of a list entry.
+#[derive(Clone, Copy, Debug, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+pub enum ListStatus {
+    /// Planning to watch or read.
+    Planning,
+    /// Currently watching or reading.
+    Current,
+    /// Finished watching or reading.
+    Completed,
+    /// Stopped watching or reading.
+    Dropped,
+    /// Paused watching or reading.
+    Paused,
+    /// Rewatching or rereading.
+    Repeating,
+}
+
+impl ListStatus {
+    /// Every list status.
+    pub const ALL: [Self; 6] = [
+        Self::Planning,
+        Self::Current,
+        Self::Completed,
+        Self::Dropped,
+        Self::Paused,
+        Self::Repeating,
+    ];
+
+    /// Gets the AniList name of the status.
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Planning => "PLANNING",
+            Self::Current => "CURRENT",
+            Self::Completed => "COMPLETED",
+            Self::Dropped => "DROPPED",
+            Self::Paused => "PAUSED",
+            Self::Repeating => "REPEATING",
+        }
+    }
+
+    /// Parses the AniList name of a status.
+    ///
+    /// # Arguments
+    ///
+    /// * `value` - The AniList name of the status.
+    pub fn parse(value: &str) -> Option<Self> {
+        Self::ALL
+            .into_iter()
+            .find(|status| status.as_str().eq_ignore_ascii_case(value))
+    }
+}
+
+/// The response of the save mutation.
+#[derive(Deserialize)]
+pub struct SavedEntry {
+    /// The saved entry.
+    #[serde(rename = "SaveMediaListEntry")]
+    pub entry: ListEntry,
+}
+
+/// The response of the entry query.
+#[derive(Deserialize)]
+pub struct MediaListEntry {
+    /// The entry, `None` if the media is not on the list.
+    #[serde(rename = "MediaList")]
+    pub entry: Option<ListEntry>,
+}
+
+/// An entry of a list.
+#[derive(Clone, Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ListEntry {
+    /// The ID of the entry.
+    pub id: i64,
+    /// The AniList ID of the list owner.
+    #[serde(default)]
+    pub user_id: Option<i32>,
+    /// The status of the entry.
+    pub status: Option<ListStatus>,
+    /// The watched episodes or read chapters.
+    #[serde(default)]
+    pub progress: Option<i32>,
+    /// The score, from 0 to 10, where 0 means not scored.
+    #[serde(default)]
+    pub score: Option<f64>,
+}
+
+/// An entry of a user's list, with its media.
+#[derive(Clone, Debug, PartialEq)]
+pub struct UserListEntry {
+    /// The AniList ID of the media.
+    pub media_id: i64,
+    /// The romaji title of the media.
+    pub title: String,
+    /// The watched episodes or read chapters.
+    pub progress: Option<i32>,
+    /// The number of episodes or chapters of the media, if known.
+    pub total: Option<i32>,
+    /// The score, from 0 to 10, where 0 means not scored.
+    pub score: Option<f64>,
+}
+
+/// A page of a user's list.
+#[derive(Clone, Debug, PartialEq)]
+pub struct UserListPage {
+    /// The entries of the page.
+    pub entries: Vec<UserListEntry>,
+    /// The number of the last page.
+    pub last_page: usize,
+}
+
+/// A page of list entries.
+#[derive(Deserialize)]
+pub struct MediaListPage {
+    /// The page.
+    #[serde(rename = "Page")]
+    pub page: MediaLists,
+}
+
+/// The list entries of a page.
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct MediaLists {
+    /// The pagination of the page.
+    pub page_info: PageInfo,
+    /// The list entries.
+    #[serde(default)]
+    pub media_list: Vec<MediaListNode>,
+}
+
+/// A list entry node.
+#[derive(Deserialize)]
+pub struct MediaListNode {
+    /// The watched episodes or read chapters.
+    pub progress: Option<i32>,
+    /// The score.
+    pub score: Option<f64>,
+    /// The media, missing if it was deleted.
+    pub media: Option<MediaNode>,
+}
+
+/// A media node.
+#[derive(Deserialize)]
+pub struct MediaNode {
+    /// The AniList ID.
+    pub id: i64,
+    /// The title.
+    pub title: MediaTitle,
+    /// The number of episodes, for animes.
+    pub episodes: Option<i32>,
+    /// The number of chapters, for mangas.
+    pub chapters: Option<i32>,
+}
+
+/// The title of a media.
+#[derive(Deserialize)]
+pub struct MediaTitle {
+    /// The romaji title.
+    pub romaji: Option<String>,
+}
+
+/// Converts a page of list entries into a page of a user's list.
+///
+/// Entries of deleted media are skipped.
+///
+/// # Arguments
+///
+/// * `page` - The page of list entries.
+pub fn collect(page: MediaLists) -> UserListPage {
+    UserListPage {
+        entries: page
+            .media_list
+            .into_iter()
+            .filter_map(|entry| {
+                let media = entry.media?;
+
+                Some(UserListEntry {
+                    media_id: media.id,
+                    title: media.title.romaji.unwrap_or_default(),
+                    progress: entry.progress,
+                    total: media.episodes.or(media.chapters),
+                    score: entry.score,
+                })
+            })
+            .collect(),
+        last_page: page.page_info.last_page.unwrap_or(1).max(1) as usize,
+    }
+}
+
+/// Whether AniList refused a query because the user's lists are private.
+///
+/// # Arguments
+///
+/// * `error` - The error of the query.
+pub fn is_private(error: &QueryError) -> bool {
+    matches!(error, QueryError::Api(message) if message.to_lowercase().contains("private"))
+}
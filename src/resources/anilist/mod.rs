@@ -0,0 +1,2051 @@
+// Copyright 2025 - Andriel Ferreira
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// https://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or https://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+//! The AniList resource.
+
+pub mod airing;
+pub mod appearances;
+pub mod birthdays;
+pub mod chapters;
+pub mod characters;
+pub mod episodes;
+pub mod genres;
+pub mod graphql;
+pub mod links;
+pub mod lists;
+pub mod overview;
+pub mod recommendations;
+pub mod staff;
+pub mod studios;
+pub mod top;
+pub mod user_favourites;
+pub mod user_stats;
+pub mod voice_actors;
+
+use std::{fmt, sync::Arc, time::Duration};
+
+use chrono::{DateTime, NaiveDate, Utc};
+
+use rand::{Rng, SeedableRng, rngs::StdRng, seq::SliceRandom};
+use rust_anilist::{
+    Client, Error,
+    models::{Anime, Character, Manga, User},
+};
+use serde::{Serialize, de::DeserializeOwned};
+use serde_json::{Value, json};
+
+use crate::{
+    models::{IdMap, MediaCache, MediaType, VoiceActorLanguage},
+    resources::{Cache, Database, Metrics, RateLimiter, SingleFlight},
+    utils::{self, AnimeSeason, MediaReference, SearchSort, TopKind},
+};
+use airing::AiredEpisode;
+use appearances::Appearance;
+use birthdays::BirthdayPage;
+use chapters::ChapterData;
+use characters::CharacterPage;
+use episodes::Episode;
+use genres::GenrePage;
+use graphql::{MediaPage, Page, QueryError};
+use links::MediaLinks;
+use lists::{ListEntry, ListStatus, UserListEntry, UserListPage};
+use overview::MediaOverview;
+use recommendations::RecommendationPage;
+use staff::Staff;
+use studios::Studio;
+use top::TopPage;
+use user_favourites::Favourites;
+use user_stats::UserStats;
+use voice_actors::VoiceActor;
+
+/// How many pages of popular media the random picks are drawn from.
+const RANDOM_PAGES: u16 = 20;
+/// The minimum average score of a random media, so the picks are worth watching.
+const RANDOM_MIN_SCORE: u8 = 70;
+/// How long the media, users and people are cached, so scores and airing info refresh.
+const CACHE_TTL: Duration = Duration::from_secs(15 * 60);
+/// How long the trending media are cached, they barely change.
+const TRENDING_TTL: Duration = Duration::from_secs(30 * 60);
+/// How long the birthday characters are cached, they are also keyed by the day.
+const BIRTHDAY_TTL: Duration = Duration::from_secs(24 * 60 * 60);
+/// How long the genre collection is cached, AniList rarely adds a genre.
+const GENRES_TTL: Duration = Duration::from_secs(24 * 60 * 60);
+/// How long the rankings are cached, scores move slowly.
+const TOP_TTL: Duration = Duration::from_secs(60 * 60);
+/// How long the list entries are cached, they change whenever the user updates them.
+const LIST_ENTRY_TTL: Duration = Duration::from_secs(60);
+/// How long the search results are cached, inline queries repeat them on every keystroke.
+const SEARCH_TTL: Duration = Duration::from_secs(5 * 60);
+/// How many searches of each kind are cached, so unique queries cannot grow it unboundedly.
+const SEARCH_CAPACITY: usize = 200;
+/// How many requests are sent per minute by default, AniList allows 90.
+const REQUESTS_PER_MINUTE: u32 = 80;
+/// How long the media stored in the database are fresh by default.
+const MEDIA_CACHE_FRESHNESS: Duration = Duration::from_secs(6 * 60 * 60);
+
+/// The key of a cached search: the normalized terms, the order, the page and the limit.
+pub type SearchKey = (String, SearchSort, u16, u16);
+
+/// AniList module.
+#[derive(Clone, Debug)]
+pub struct AniList {
+    /// The AniList client.
+    pub client: Arc<rust_anilist::Client>,
+    /// The cache for anime.
+    cache_anime: Cache<i64, Anime>,
+    /// The cache for manga.
+    cache_manga: Cache<i64, Manga>,
+    /// The cache for users.
+    cache_user: Cache<i32, User>,
+    /// The cache for characters.
+    cache_char: Cache<i64, Character>,
+    /// The cache for studios.
+    cache_studio: Cache<i64, Studio>,
+    /// The cache for staff.
+    cache_staff: Cache<i64, Staff>,
+    /// The cache for episode lists.
+    cache_episodes: Cache<i64, Vec<Episode>>,
+    /// The cache for the character pages of a media, by media, page, limit and voice actor
+    /// language.
+    cache_characters: Cache<(MediaType, i64, u16, u16, VoiceActorLanguage), CharacterPage>,
+    /// The cache for trending anime, by page and limit.
+    cache_trending_anime: Cache<(u16, u16), Vec<Anime>>,
+    /// The cache for trending manga, by page and limit.
+    cache_trending_manga: Cache<(u16, u16), Vec<Manga>>,
+    /// The cache for birthday characters, by day and page.
+    cache_birthdays: Cache<(NaiveDate, u16), BirthdayPage>,
+    /// The cache for the genre collection.
+    cache_genres: Cache<(), Vec<String>>,
+    /// The cache for rankings, by kind and page.
+    cache_top: Cache<(TopKind, u16), TopPage>,
+    /// The cache for list entries, by the AniList ID of the user and the media ID.
+    cache_list_entries: Cache<(i32, i64), Option<ListEntry>>,
+    /// The cache for anime searches.
+    cache_search_anime: Cache<SearchKey, Vec<Anime>>,
+    /// The cache for manga searches.
+    cache_search_manga: Cache<SearchKey, Vec<Manga>>,
+    /// The cache for user searches.
+    cache_search_user: Cache<SearchKey, Vec<User>>,
+    /// The anime requests in flight.
+    flights_anime: SingleFlight<i64, Result<Anime, FetchError>>,
+    /// The manga requests in flight.
+    flights_manga: SingleFlight<i64, Result<Manga, FetchError>>,
+    /// The user requests in flight.
+    flights_user: SingleFlight<i32, Result<User, FetchError>>,
+    /// The character requests in flight.
+    flights_char: SingleFlight<i64, Result<Character, FetchError>>,
+    /// The anime searches in flight.
+    flights_search_anime: SingleFlight<SearchKey, Option<Vec<Anime>>>,
+    /// The manga searches in flight.
+    flights_search_manga: SingleFlight<SearchKey, Option<Vec<Manga>>>,
+    /// The user searches in flight.
+    flights_search_user: SingleFlight<SearchKey, Option<Vec<User>>>,
+    /// The limiter of the requests, shared by every client.
+    limiter: RateLimiter,
+    /// When the stale media served while AniList was unreachable were fetched.
+    stale_media: Cache<(MediaType, i64), DateTime<Utc>>,
+    /// How long the media stored in the database are fresh.
+    media_cache_freshness: Duration,
+    /// The database where the MyAnimeList ID mappings and the media are stored.
+    db: Option<Database>,
+    /// Where the requests are counted.
+    metrics: Option<Metrics>,
+}
+
+/// Why a media, user or character could not be retrieved.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum FetchError {
+    /// It does not exist, or the request failed.
+    NotFound,
+    /// AniList is rate limiting the bot, retry after the duration.
+    RateLimited(Duration),
+}
+
+impl fmt::Display for FetchError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::NotFound => write!(f, "the AniList resource could not be retrieved"),
+            Self::RateLimited(retry_after) => {
+                write!(f, "AniList is rate limiting the bot for {:?}", retry_after)
+            }
+        }
+    }
+}
+
+impl std::error::Error for FetchError {}
+
+/// Where a resolved ID came from.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Resolution {
+    /// The ID was already stored.
+    Stored(i64),
+    /// The ID was fetched from the API.
+    Fetched(i64),
+}
+
+impl Resolution {
+    /// Gets the resolved ID.
+    pub fn id(&self) -> i64 {
+        match self {
+            Self::Stored(id) | Self::Fetched(id) => *id,
+        }
+    }
+}
+
+impl Default for AniList {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl AniList {
+    /// Creates a new instance of the AniList resource.
+    pub fn new() -> Self {
+        Self {
+            client: Arc::new(Client::with_timeout(Duration::from_secs(15))),
+            cache_anime: Cache::with_capacity_and_ttl(50, CACHE_TTL),
+            cache_manga: Cache::with_capacity_and_ttl(50, CACHE_TTL),
+            cache_user: Cache::with_capacity_and_ttl(50, CACHE_TTL),
+            cache_char: Cache::with_capacity_and_ttl(50, CACHE_TTL),
+            cache_studio: Cache::with_capacity_and_ttl(50, CACHE_TTL),
+            cache_staff: Cache::with_capacity_and_ttl(50, CACHE_TTL),
+            cache_episodes: Cache::with_capacity_and_ttl(50, CACHE_TTL),
+            cache_characters: Cache::with_capacity_and_ttl(100, CACHE_TTL),
+            cache_trending_anime: Cache::with_capacity(10),
+            cache_trending_manga: Cache::with_capacity(10),
+            cache_birthdays: Cache::with_capacity_and_ttl(10, BIRTHDAY_TTL),
+            cache_genres: Cache::with_capacity_and_ttl(1, GENRES_TTL),
+            cache_top: Cache::with_capacity_and_ttl(40, TOP_TTL),
+            cache_list_entries: Cache::with_capacity(100),
+            cache_search_anime: Cache::with_capacity_and_ttl(SEARCH_CAPACITY, SEARCH_TTL),
+            cache_search_manga: Cache::with_capacity_and_ttl(SEARCH_CAPACITY, SEARCH_TTL),
+            cache_search_user: Cache::with_capacity_and_ttl(SEARCH_CAPACITY, SEARCH_TTL),
+            flights_anime: SingleFlight::new(),
+            flights_manga: SingleFlight::new(),
+            flights_user: SingleFlight::new(),
+            flights_char: SingleFlight::new(),
+            flights_search_anime: SingleFlight::new(),
+            flights_search_manga: SingleFlight::new(),
+            flights_search_user: SingleFlight::new(),
+            limiter: RateLimiter::new(REQUESTS_PER_MINUTE),
+            stale_media: Cache::with_capacity_and_ttl(100, CACHE_TTL),
+            media_cache_freshness: MEDIA_CACHE_FRESHNESS,
+            db: None,
+            metrics: None,
+        }
+    }
+
+    /// Removes the expired values from the caches, returning how many were removed.
+    pub async fn purge_expired(&self) -> usize {
+        self.cache_anime.purge_expired().await
+            + self.cache_manga.purge_expired().await
+            + self.cache_user.purge_expired().await
+            + self.cache_char.purge_expired().await
+            + self.cache_studio.purge_expired().await
+            + self.cache_staff.purge_expired().await
+            + self.cache_episodes.purge_expired().await
+            + self.cache_characters.purge_expired().await
+            + self.cache_birthdays.purge_expired().await
+            + self.cache_genres.purge_expired().await
+            + self.cache_top.purge_expired().await
+            + self.cache_search_anime.purge_expired().await
+            + self.cache_search_manga.purge_expired().await
+            + self.cache_search_user.purge_expired().await
+            + self.stale_media.purge_expired().await
+    }
+
+    /// Gets how many lookups found a value and how many found nothing, over all the caches.
+    pub fn cache_counts(&self) -> (u64, u64) {
+        let counts = [
+            (self.cache_anime.hits(), self.cache_anime.misses()),
+            (self.cache_manga.hits(), self.cache_manga.misses()),
+            (self.cache_user.hits(), self.cache_user.misses()),
+            (self.cache_char.hits(), self.cache_char.misses()),
+            (self.cache_studio.hits(), self.cache_studio.misses()),
+            (self.cache_staff.hits(), self.cache_staff.misses()),
+            (self.cache_episodes.hits(), self.cache_episodes.misses()),
+            (self.cache_characters.hits(), self.cache_characters.misses()),
+            (
+                self.cache_trending_anime.hits(),
+                self.cache_trending_anime.misses(),
+            ),
+            (
+                self.cache_trending_manga.hits(),
+                self.cache_trending_manga.misses(),
+            ),
+            (self.cache_birthdays.hits(), self.cache_birthdays.misses()),
+            (self.cache_genres.hits(), self.cache_genres.misses()),
+            (self.cache_top.hits(), self.cache_top.misses()),
+            (
+                self.cache_list_entries.hits(),
+                self.cache_list_entries.misses(),
+            ),
+            (
+                self.cache_search_anime.hits(),
+                self.cache_search_anime.misses(),
+            ),
+            (
+                self.cache_search_manga.hits(),
+                self.cache_search_manga.misses(),
+            ),
+            (
+                self.cache_search_user.hits(),
+                self.cache_search_user.misses(),
+            ),
+        ];
+
+        counts.iter().fold((0, 0), |(hits, misses), count| {
+            (hits + count.0, misses + count.1)
+        })
+    }
+
+    /// Gets the cache for anime.
+    pub fn anime_cache(&self) -> &Cache<i64, Anime> {
+        &self.cache_anime
+    }
+
+    /// Gets the cache for manga.
+    pub fn manga_cache(&self) -> &Cache<i64, Manga> {
+        &self.cache_manga
+    }
+
+    /// Gets the cache for users.
+    pub fn user_cache(&self) -> &Cache<i32, User> {
+        &self.cache_user
+    }
+
+    /// Gets the cache for characters.
+    pub fn char_cache(&self) -> &Cache<i64, Character> {
+        &self.cache_char
+    }
+
+    /// Gets the cache for studios.
+    pub fn studio_cache(&self) -> &Cache<i64, Studio> {
+        &self.cache_studio
+    }
+
+    /// Gets the cache for staff.
+    pub fn staff_cache(&self) -> &Cache<i64, Staff> {
+        &self.cache_staff
+    }
+
+    /// Gets the cache for anime searches.
+    pub fn anime_search_cache(&self) -> &Cache<SearchKey, Vec<Anime>> {
+        &self.cache_search_anime
+    }
+
+    /// Gets the cache for manga searches.
+    pub fn manga_search_cache(&self) -> &Cache<SearchKey, Vec<Manga>> {
+        &self.cache_search_manga
+    }
+
+    /// Gets the cache for user searches.
+    pub fn user_search_cache(&self) -> &Cache<SearchKey, Vec<User>> {
+        &self.cache_search_user
+    }
+
+    /// Sets how many requests are sent to AniList per minute.
+    ///
+    /// # Arguments
+    ///
+    /// * `per_minute` - How many requests are allowed per minute.
+    pub fn with_rate_limit(mut self, per_minute: u32) -> Self {
+        self.limiter = RateLimiter::new(per_minute);
+        self
+    }
+
+    /// Gets how long AniList is still rate limiting the bot for, if it is.
+    pub fn retry_after(&self) -> Option<Duration> {
+        self.limiter.retry_after()
+    }
+
+    /// Sets how long the media stored in the database are fresh.
+    ///
+    /// Older media are only served when AniList is unreachable.
+    ///
+    /// # Arguments
+    ///
+    /// * `freshness` - How long the stored media are fresh.
+    pub fn with_media_cache_freshness(mut self, freshness: Duration) -> Self {
+        self.media_cache_freshness = freshness;
+        self
+    }
+
+    /// Gets when a media was fetched, if it is a stale copy served while AniList was
+    /// unreachable.
+    ///
+    /// # Arguments
+    ///
+    /// * `media_type` - The media type.
+    /// * `id` - The AniList ID.
+    pub async fn stale_since(&self, media_type: MediaType, id: i64) -> Option<DateTime<Utc>> {
+        self.stale_media.get(&(media_type, id)).await
+    }
+
+    /// Sets the database used to store and look up the MyAnimeList ID mappings and the media.
+    ///
+    /// # Arguments
+    ///
+    /// * `db` - The database resource.
+    pub fn with_database(mut self, db: Database) -> Self {
+        self.db = Some(db);
+        self
+    }
+
+    /// Sets where the requests and the cache lookups are counted.
+    ///
+    /// # Arguments
+    ///
+    /// * `metrics` - The metrics resource.
+    pub fn with_metrics(mut self, metrics: Metrics) -> Self {
+        self.cache_anime = self.cache_anime.with_metrics(metrics.clone(), "anime");
+        self.cache_manga = self.cache_manga.with_metrics(metrics.clone(), "manga");
+        self.cache_user = self.cache_user.with_metrics(metrics.clone(), "user");
+        self.cache_char = self.cache_char.with_metrics(metrics.clone(), "char");
+        self.cache_studio = self.cache_studio.with_metrics(metrics.clone(), "studio");
+        self.cache_staff = self.cache_staff.with_metrics(metrics.clone(), "staff");
+        self.cache_episodes = self
+            .cache_episodes
+            .with_metrics(metrics.clone(), "episodes");
+        self.cache_characters = self
+            .cache_characters
+            .with_metrics(metrics.clone(), "characters");
+        self.cache_trending_anime = self
+            .cache_trending_anime
+            .with_metrics(metrics.clone(), "trending_anime");
+        self.cache_trending_manga = self
+            .cache_trending_manga
+            .with_metrics(metrics.clone(), "trending_manga");
+        self.cache_birthdays = self
+            .cache_birthdays
+            .with_metrics(metrics.clone(), "birthdays");
+        self.cache_genres = self.cache_genres.with_metrics(metrics.clone(), "genres");
+        self.cache_top = self.cache_top.with_metrics(metrics.clone(), "top");
+        self.cache_list_entries = self
+            .cache_list_entries
+            .with_metrics(metrics.clone(), "list_entries");
+        self.cache_search_anime = self
+            .cache_search_anime
+            .with_metrics(metrics.clone(), "search_anime");
+        self.cache_search_manga = self
+            .cache_search_manga
+            .with_metrics(metrics.clone(), "search_manga");
+        self.cache_search_user = self
+            .cache_search_user
+            .with_metrics(metrics.clone(), "search_user");
+        self.metrics = Some(metrics);
+        self
+    }
+
+    /// Counts a request sent to AniList, if the metrics are set.
+    ///
+    /// # Arguments
+    ///
+    /// * `outcome` - How the request went, `ok`, `rate_limited` or `failed`.
+    fn record_request(&self, outcome: &'static str) {
+        if let Some(metrics) = &self.metrics {
+            metrics.record_anilist_request(outcome);
+        }
+    }
+
+    /// Gets an anime by its ID.
+    ///
+    /// Looks the anime up in memory, then in the database, then on AniList. Concurrent calls
+    /// for the same anime share a single request, and a stale copy is served when AniList is
+    /// unreachable, see [`AniList::stale_since`].
+    ///
+    /// # Arguments
+    ///
+    /// * `id` - The anime ID.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`FetchError::RateLimited`] if AniList is rate limiting the bot, and
+    /// [`FetchError::NotFound`] if the anime could not be retrieved otherwise.
+    pub async fn get_anime(&self, id: i64) -> Result<Anime, FetchError> {
+        if let Some(anime) = self.cache_anime.get(&id).await {
+            return Ok(anime);
+        }
+
+        self.flights_anime
+            .run(id, || async {
+                let anime = self
+                    .fetch_media(MediaType::Anime, id, &self.cache_anime, || {
+                        self.client.get_anime(id)
+                    })
+                    .await?;
+                self.remember_mal(MediaType::Anime, anime.id, anime.id_mal);
+
+                Ok(anime)
+            })
+            .await
+    }
+
+    /// Gets a manga by its ID.
+    ///
+    /// Looks the manga up in memory, then in the database, then on AniList. Concurrent calls
+    /// for the same manga share a single request, and a stale copy is served when AniList is
+    /// unreachable, see [`AniList::stale_since`].
+    ///
+    /// # Arguments
+    ///
+    /// * `id` - The manga ID.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`FetchError::RateLimited`] if AniList is rate limiting the bot, and
+    /// [`FetchError::NotFound`] if the manga could not be retrieved otherwise.
+    pub async fn get_manga(&self, id: i64) -> Result<Manga, FetchError> {
+        if let Some(manga) = self.cache_manga.get(&id).await {
+            return Ok(manga);
+        }
+
+        self.flights_manga
+            .run(id, || async {
+                let manga = self
+                    .fetch_media(MediaType::Manga, id, &self.cache_manga, || {
+                        self.client.get_manga(id)
+                    })
+                    .await?;
+                self.remember_mal(MediaType::Manga, manga.id, manga.id_mal);
+
+                Ok(manga)
+            })
+            .await
+    }
+
+    /// Gets an anime by its MyAnimeList ID.
+    ///
+    /// # Arguments
+    ///
+    /// * `mal_id` - The MyAnimeList ID.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`FetchError::RateLimited`] if AniList is rate limiting the bot, and
+    /// [`FetchError::NotFound`] if the anime could not be retrieved otherwise.
+    pub async fn get_anime_by_mal_id(&self, mal_id: i64) -> Result<Anime, FetchError> {
+        match self.resolve_mal(mal_id, MediaType::Anime).await {
+            Some(id) => self.get_anime(id).await,
+            None => Err(self
+                .retry_after()
+                .map_or(FetchError::NotFound, FetchError::RateLimited)),
+        }
+    }
+
+    /// Gets a manga by its MyAnimeList ID.
+    ///
+    /// # Arguments
+    ///
+    /// * `mal_id` - The MyAnimeList ID.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`FetchError::RateLimited`] if AniList is rate limiting the bot, and
+    /// [`FetchError::NotFound`] if the manga could not be retrieved otherwise.
+    pub async fn get_manga_by_mal_id(&self, mal_id: i64) -> Result<Manga, FetchError> {
+        match self.resolve_mal(mal_id, MediaType::Manga).await {
+            Some(id) => self.get_manga(id).await,
+            None => Err(self
+                .retry_after()
+                .map_or(FetchError::NotFound, FetchError::RateLimited)),
+        }
+    }
+
+    /// Gets an anime by its AniList or MyAnimeList ID.
+    ///
+    /// # Arguments
+    ///
+    /// * `reference` - The ID of the anime.
+    pub async fn get_anime_by_reference(
+        &self,
+        reference: MediaReference,
+    ) -> Result<Anime, FetchError> {
+        match reference {
+            MediaReference::AniList(id) => self.get_anime(id).await,
+            MediaReference::MyAnimeList(mal_id) => self.get_anime_by_mal_id(mal_id).await,
+        }
+    }
+
+    /// Gets a manga by its AniList or MyAnimeList ID.
+    ///
+    /// # Arguments
+    ///
+    /// * `reference` - The ID of the manga.
+    pub async fn get_manga_by_reference(
+        &self,
+        reference: MediaReference,
+    ) -> Result<Manga, FetchError> {
+        match reference {
+            MediaReference::AniList(id) => self.get_manga(id).await,
+            MediaReference::MyAnimeList(mal_id) => self.get_manga_by_mal_id(mal_id).await,
+        }
+    }
+
+    /// Gets the overview of an anime, its ID and title.
+    ///
+    /// Served from the anime cache when the full anime is there, only the overview is
+    /// requested otherwise.
+    ///
+    /// # Arguments
+    ///
+    /// * `id` - The anime ID.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`FetchError::RateLimited`] if AniList is rate limiting the bot, and
+    /// [`FetchError::NotFound`] if the anime could not be retrieved otherwise.
+    pub async fn get_anime_overview(&self, id: i64) -> Result<MediaOverview, FetchError> {
+        match self.cache_anime.get(&id).await {
+            Some(anime) => Ok(MediaOverview::from(&anime)),
+            None => self.fetch_overview(MediaType::Anime, id).await,
+        }
+    }
+
+    /// Gets the overview of a manga, its ID and title.
+    ///
+    /// Served from the manga cache when the full manga is there, only the overview is
+    /// requested otherwise.
+    ///
+    /// # Arguments
+    ///
+    /// * `id` - The manga ID.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`FetchError::RateLimited`] if AniList is rate limiting the bot, and
+    /// [`FetchError::NotFound`] if the manga could not be retrieved otherwise.
+    pub async fn get_manga_overview(&self, id: i64) -> Result<MediaOverview, FetchError> {
+        match self.cache_manga.get(&id).await {
+            Some(manga) => Ok(MediaOverview::from(&manga)),
+            None => self.fetch_overview(MediaType::Manga, id).await,
+        }
+    }
+
+    /// Requests the overview of a media.
+    ///
+    /// # Arguments
+    ///
+    /// * `media_type` - The media type.
+    /// * `id` - The AniList ID.
+    async fn fetch_overview(
+        &self,
+        media_type: MediaType,
+        id: i64,
+    ) -> Result<MediaOverview, FetchError> {
+        self.try_query::<overview::MediaOverviewById>(
+            overview::QUERY,
+            json!({ "id": id, "type": media_type.as_str() }),
+            None,
+        )
+        .await
+        .map(|data| data.media)
+        .map_err(|e| match e {
+            QueryError::RateLimited(retry_after) => FetchError::RateLimited(retry_after),
+            _ => FetchError::NotFound,
+        })
+    }
+
+    /// Gets the links of a media, to AniList, MyAnimeList and the external sites.
+    ///
+    /// # Arguments
+    ///
+    /// * `id` - The media ID.
+    pub async fn get_media_links(&self, id: i64) -> Option<MediaLinks> {
+        self.query::<links::LinksById>(links::QUERY, json!({ "id": id }), None)
+            .await
+            .map(|data| data.media)
+    }
+
+    /// Gets a page of the characters of a media, the main ones first.
+    ///
+    /// AniList pages the characters itself, so only the requested page is fetched and cached.
+    ///
+    /// # Arguments
+    ///
+    /// * `id` - The media ID.
+    /// * `media_type` - The media type.
+    /// * `page` - The page number.
+    /// * `per_page` - How many characters per page.
+    /// * `language` - The language of the voice actors.
+    pub async fn get_media_characters(
+        &self,
+        id: i64,
+        media_type: MediaType,
+        page: u16,
+        per_page: u16,
+        language: VoiceActorLanguage,
+    ) -> Option<CharacterPage> {
+        let key = (media_type, id, page, per_page, language);
+        if let Some(characters) = self.cache_characters.get(&key).await {
+            return Some(characters);
+        }
+
+        let characters = self
+            .query::<characters::MediaCharacters>(
+                characters::QUERY,
+                json!({
+                    "id": id,
+                    "type": media_type.as_str(),
+                    "page": page,
+                    "perPage": per_page,
+                    "language": language.as_anilist(),
+                }),
+                None,
+            )
+            .await
+            .map(|data| characters::collect(data.media.characters))?;
+        self.cache_characters.insert(key, characters.clone()).await;
+
+        Some(characters)
+    }
+
+    /// Gets a studio by its ID, with its most popular productions.
+    ///
+    /// # Arguments
+    ///
+    /// * `id` - The studio ID.
+    pub async fn get_studio(&self, id: i64) -> Option<Studio> {
+        if let Some(studio) = self.cache_studio.get(&id).await {
+            return Some(studio);
+        }
+
+        let studio = self
+            .query::<studios::StudioById>(&studios::by_id_query(), json!({ "id": id }), None)
+            .await?
+            .studio;
+        self.cache_studio.insert(id, studio.clone()).await;
+
+        Some(studio)
+    }
+
+    /// Searches for studios by its name.
+    ///
+    /// # Arguments
+    ///
+    /// * `name` - The studio name.
+    /// * `page` - The page number.
+    /// * `limit` - The number of results per page.
+    pub async fn search_studio(&self, name: &str, page: u16, limit: u16) -> Option<Vec<Studio>> {
+        let studios = self
+            .query::<Page<studios::StudioPage>>(
+                &studios::search_query(),
+                json!({ "search": name, "page": page, "perPage": limit }),
+                None,
+            )
+            .await?
+            .page
+            .studios;
+
+        for studio in studios.iter() {
+            self.cache_studio.insert(studio.id, studio.clone()).await;
+        }
+
+        Some(studios)
+    }
+
+    /// Gets a staff member by its ID, with the characters they voiced and their works.
+    ///
+    /// # Arguments
+    ///
+    /// * `id` - The staff ID.
+    pub async fn get_staff(&self, id: i64) -> Option<Staff> {
+        if let Some(staff) = self.cache_staff.get(&id).await {
+            return Some(staff);
+        }
+
+        let staff = self
+            .query::<staff::StaffById>(&staff::by_id_query(), json!({ "id": id }), None)
+            .await?
+            .staff;
+        self.cache_staff.insert(id, staff.clone()).await;
+
+        Some(staff)
+    }
+
+    /// Searches for staff by its name.
+    ///
+    /// # Arguments
+    ///
+    /// * `name` - The staff name.
+    /// * `page` - The page number.
+    /// * `limit` - The number of results per page.
+    pub async fn search_staff(&self, name: &str, page: u16, limit: u16) -> Option<Vec<Staff>> {
+        let staff = self
+            .query::<Page<staff::StaffPage>>(
+                &staff::search_query(),
+                json!({ "search": name, "page": page, "perPage": limit }),
+                None,
+            )
+            .await?
+            .page
+            .staff;
+
+        for member in staff.iter() {
+            self.cache_staff.insert(member.id, member.clone()).await;
+        }
+
+        Some(staff)
+    }
+
+    /// Gets the episode list of an anime.
+    ///
+    /// Combines the streaming episode titles with the airing schedule, see [`episodes::merge`].
+    ///
+    /// # Arguments
+    ///
+    /// * `id` - The anime ID.
+    pub async fn get_episodes(&self, id: i64) -> Option<Vec<Episode>> {
+        if let Some(episodes) = self.cache_episodes.get(&id).await {
+            return Some(episodes);
+        }
+
+        let mut total = None;
+        let mut streaming = Vec::new();
+        let mut schedule = Vec::new();
+
+        for page in 1..=episodes::MAX_SCHEDULE_PAGES {
+            let data = self
+                .query::<episodes::MediaEpisodes>(
+                    episodes::QUERY,
+                    json!({ "id": id, "page": page }),
+                    None,
+                )
+                .await?
+                .media;
+
+            if page == 1 {
+                total = data.episodes;
+                streaming = data.streaming_episodes;
+            }
+
+            let Some(airing_schedule) = data.airing_schedule else {
+                break;
+            };
+            schedule.extend(airing_schedule.nodes);
+
+            if !airing_schedule.page_info.has_next_page {
+                break;
+            }
+        }
+
+        let episodes = episodes::merge(total, &streaming, &schedule);
+        self.cache_episodes.insert(id, episodes.clone()).await;
+
+        Some(episodes)
+    }
+
+    /// Gets the chapter data of a manga, with its reading links.
+    ///
+    /// # Arguments
+    ///
+    /// * `id` - The manga ID.
+    pub async fn get_chapters(&self, id: i64) -> Option<ChapterData> {
+        self.query::<chapters::MediaChapters>(chapters::QUERY, json!({ "id": id }), None)
+            .await
+            .map(|data| data.media)
+    }
+
+    /// Gets the voice actors of a character.
+    ///
+    /// # Arguments
+    ///
+    /// * `id` - The character ID.
+    pub async fn get_voice_actors(&self, id: i64) -> Option<Vec<VoiceActor>> {
+        self.query::<voice_actors::CharacterVoiceActors>(
+            voice_actors::QUERY,
+            json!({ "id": id }),
+            None,
+        )
+        .await
+        .map(|data| voice_actors::collect(data.character.media.edges))
+    }
+
+    /// Gets the media a character appears in.
+    ///
+    /// # Arguments
+    ///
+    /// * `id` - The character ID.
+    pub async fn get_appearances(&self, id: i64) -> Option<Vec<Appearance>> {
+        self.query::<appearances::CharacterAppearances>(
+            appearances::QUERY,
+            json!({ "id": id }),
+            None,
+        )
+        .await
+        .map(|data| appearances::collect(data.character.media.edges))
+    }
+
+    /// Gets a page of the recommendations of a media, the best rated first.
+    ///
+    /// # Arguments
+    ///
+    /// * `id` - The media ID.
+    /// * `page` - The page number.
+    pub async fn get_recommendations(&self, id: i64, page: u16) -> Option<RecommendationPage> {
+        self.query::<recommendations::MediaRecommendations>(
+            recommendations::QUERY,
+            json!({ "id": id, "page": page, "perPage": recommendations::PER_PAGE }),
+            None,
+        )
+        .await
+        .map(|data| recommendations::collect(data.media.recommendations))
+    }
+
+    /// Gets a page of the characters whose birthday is today, the most favourited first.
+    ///
+    /// The pages are cached per calendar day, in UTC.
+    ///
+    /// # Arguments
+    ///
+    /// * `page` - The page number.
+    pub async fn get_birthday_characters(&self, page: u16) -> Option<BirthdayPage> {
+        let key = (Utc::now().date_naive(), page);
+        if let Some(characters) = self.cache_birthdays.get(&key).await {
+            return Some(characters);
+        }
+
+        let characters = self
+            .query::<birthdays::CharacterPage>(
+                birthdays::QUERY,
+                json!({ "page": page, "perPage": birthdays::PER_PAGE }),
+                None,
+            )
+            .await
+            .map(|data| birthdays::collect(data.page))?;
+        self.cache_birthdays.insert(key, characters.clone()).await;
+
+        Some(characters)
+    }
+
+    /// Gets the genre collection of AniList.
+    ///
+    /// The genres are cached for a day.
+    pub async fn get_genres(&self) -> Option<Vec<String>> {
+        if let Some(genres) = self.cache_genres.get(&()).await {
+            return Some(genres);
+        }
+
+        let genres = self
+            .query::<genres::GenreCollection>(genres::GENRES_QUERY, json!({}), None)
+            .await?
+            .genres;
+        if !genres.is_empty() {
+            self.cache_genres.insert((), genres.clone()).await;
+        }
+
+        Some(genres)
+    }
+
+    /// Gets a page of the media of a genre or tag, the most popular first.
+    ///
+    /// Names missing from the genre collection are searched as tags.
+    ///
+    /// # Arguments
+    ///
+    /// * `genre` - The name of the genre or tag, as AniList writes it.
+    /// * `media_type` - The media type.
+    /// * `page` - The page number.
+    pub async fn search_by_genre(
+        &self,
+        genre: &str,
+        media_type: MediaType,
+        page: u16,
+    ) -> Option<GenrePage> {
+        let is_genre = self
+            .get_genres()
+            .await
+            .is_some_and(|genres| genres.iter().any(|name| name == genre));
+        let (genre, tag) = if is_genre {
+            (Some(genre), None)
+        } else {
+            (None, Some(genre))
+        };
+
+        self.query::<genres::MediaPage>(
+            genres::QUERY,
+            json!({
+                "type": media_type.as_str(),
+                "genre": genre,
+                "tag": tag,
+                "page": page,
+                "perPage": genres::PER_PAGE,
+            }),
+            None,
+        )
+        .await
+        .map(|data| genres::collect(data.page))
+    }
+
+    /// Gets a page of a ranking of the best scored media.
+    ///
+    /// The pages are cached for an hour.
+    ///
+    /// # Arguments
+    ///
+    /// * `kind` - The ranking, with its media type and format or status filter.
+    /// * `page` - The page number.
+    pub async fn get_top_media(&self, kind: TopKind, page: u16) -> Option<TopPage> {
+        if let Some(top) = self.cache_top.get(&(kind, page)).await {
+            return Some(top);
+        }
+
+        let top = self
+            .query::<top::MediaPage>(
+                top::QUERY,
+                json!({
+                    "type": kind.media_type().as_str(),
+                    "format": kind.format(),
+                    "status": kind.status(),
+                    "page": page,
+                    "perPage": top::PER_PAGE,
+                }),
+                None,
+            )
+            .await
+            .map(|data| top::collect(data.page))?;
+        self.cache_top.insert((kind, page), top.clone()).await;
+
+        Some(top)
+    }
+
+    /// Resolves the AniList ID of a media from its MyAnimeList ID.
+    ///
+    /// The stored mappings are looked up first, AniList is only queried when the mapping is
+    /// unknown, and the answer is stored for the next time.
+    ///
+    /// # Arguments
+    ///
+    /// * `mal_id` - The MyAnimeList ID.
+    /// * `media_type` - The media type.
+    pub async fn resolve_mal(&self, mal_id: i64, media_type: MediaType) -> Option<i64> {
+        let resolution = resolve_with(
+            || async move {
+                let db = self.db.as_ref().filter(|db| db.is_healthy())?;
+
+                db.report(IdMap::anilist_id(db.pool(), media_type, mal_id).await)
+                    .ok()
+                    .flatten()
+            },
+            || async move {
+                self.query::<graphql::MediaById>(
+                    "query ($idMal: Int, $type: MediaType) { Media(idMal: $idMal, type: $type) { id } }",
+                    json!({ "idMal": mal_id, "type": media_type.as_str() }),
+                    None,
+                )
+                .await
+                .map(|media| media.media.id)
+            },
+        )
+        .await?;
+
+        if let Resolution::Fetched(id) = resolution {
+            self.remember_mal(media_type, id, Some(mal_id));
+        }
+
+        Some(resolution.id())
+    }
+
+    /// Gets a media from the database if it is fresh, or from AniList otherwise, caching it
+    /// in memory.
+    ///
+    /// Fetched media are stored in the database. When AniList fails, the stored media is
+    /// served however old it is, and marked as stale.
+    ///
+    /// # Arguments
+    ///
+    /// * `media_type` - The media type.
+    /// * `id` - The AniList ID.
+    /// * `cache` - The memory cache of the media type.
+    /// * `request` - Fetches the media from AniList.
+    ///
+    /// # Errors
+    ///
+    /// Returns the error of the request if the media is not stored either.
+    async fn fetch_media<T, F, Fut>(
+        &self,
+        media_type: MediaType,
+        id: i64,
+        cache: &Cache<i64, T>,
+        request: F,
+    ) -> Result<T, FetchError>
+    where
+        T: Clone + Serialize + DeserializeOwned,
+        F: FnOnce() -> Fut,
+        Fut: Future<Output = Result<T, Error>>,
+    {
+        let stored = self.load_media::<T>(media_type, id).await;
+        if let Some((media, fetched_at)) = stored.as_ref() {
+            let age = (Utc::now() - *fetched_at).to_std().unwrap_or_default();
+            if age < self.media_cache_freshness {
+                cache.insert(id, media.clone()).await;
+                return Ok(media.clone());
+            }
+        }
+
+        match self.call(request()).await {
+            Ok(media) => {
+                cache.insert(id, media.clone()).await;
+                self.stale_media.remove(&(media_type, id)).await;
+                self.store_media(media_type, id, &media);
+
+                Ok(media)
+            }
+            Err(e) => {
+                let Some((media, fetched_at)) = stored else {
+                    return Err(e);
+                };
+                log::warn!(
+                    "serving the {:?} {} fetched at {} after AniList failed: {:?}",
+                    media_type,
+                    id,
+                    fetched_at,
+                    e
+                );
+
+                cache.insert(id, media.clone()).await;
+                self.stale_media.insert((media_type, id), fetched_at).await;
+
+                Ok(media)
+            }
+        }
+    }
+
+    /// Loads a media stored in the database, with when it was fetched.
+    ///
+    /// # Arguments
+    ///
+    /// * `media_type` - The media type.
+    /// * `id` - The AniList ID.
+    async fn load_media<T: DeserializeOwned>(
+        &self,
+        media_type: MediaType,
+        id: i64,
+    ) -> Option<(T, DateTime<Utc>)> {
+        let db = self.db.as_ref().filter(|db| db.is_healthy())?;
+        let row = db
+            .report(MediaCache::get(db.pool(), media_type, id).await)
+            .ok()
+            .flatten()?;
+
+        match serde_json::from_str(&row.payload) {
+            Ok(media) => Some((media, row.fetched_at)),
+            Err(e) => {
+                log::warn!(
+                    "failed to parse the stored {:?} {}: {:?}",
+                    media_type,
+                    id,
+                    e
+                );
+                None
+            }
+        }
+    }
+
+    /// Stores a media in the database in the background.
+    ///
+    /// # Arguments
+    ///
+    /// * `media_type` - The media type.
+    /// * `id` - The AniList ID.
+    /// * `media` - The media.
+    fn store_media<T: Serialize>(&self, media_type: MediaType, id: i64, media: &T) {
+        let Some(db) = self.db.clone().filter(|db| db.is_healthy()) else {
+            return;
+        };
+        let payload = match serde_json::to_string(media) {
+            Ok(payload) => payload,
+            Err(e) => {
+                log::warn!("failed to serialize the {:?} {}: {:?}", media_type, id, e);
+                return;
+            }
+        };
+
+        tokio::spawn(async move {
+            if let Err(e) = db.report(MediaCache::upsert(db.pool(), media_type, id, &payload).await)
+            {
+                log::warn!("failed to store the {:?} {}: {:?}", media_type, id, e);
+            }
+        });
+    }
+
+    /// Stores the MyAnimeList ID of a media in the background, if it has one.
+    ///
+    /// # Arguments
+    ///
+    /// * `media_type` - The media type.
+    /// * `anilist_id` - The AniList ID.
+    /// * `mal_id` - The MyAnimeList ID.
+    fn remember_mal(&self, media_type: MediaType, anilist_id: i64, mal_id: Option<i64>) {
+        let (Some(db), Some(mal_id)) = (self.db.clone(), mal_id) else {
+            return;
+        };
+        if !db.is_healthy() {
+            return;
+        }
+
+        tokio::spawn(async move {
+            if let Err(e) =
+                db.report(IdMap::upsert(db.pool(), media_type, anilist_id, mal_id).await)
+            {
+                log::warn!(
+                    "failed to store the MyAnimeList ID {:?} of {:?}: {:?}",
+                    mal_id,
+                    anilist_id,
+                    e
+                );
+            }
+        });
+    }
+
+    /// Gets a user by its ID.
+    ///
+    /// Concurrent calls for the same user share a single request.
+    ///
+    /// # Arguments
+    ///
+    /// * `id` - The user ID.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`FetchError::RateLimited`] if AniList is rate limiting the bot, and
+    /// [`FetchError::NotFound`] if the user could not be retrieved otherwise.
+    pub async fn get_user(&self, id: i32) -> Result<User, FetchError> {
+        if let Some(user) = self.cache_user.get(&id).await {
+            return Ok(user);
+        }
+
+        self.flights_user
+            .run(id, || async {
+                let user = self.call(self.client.get_user(id)).await?;
+                self.cache_user.insert(id, user.clone()).await;
+
+                Ok(user)
+            })
+            .await
+    }
+
+    /// Gets a page of a user's public list of a media type, the last updated first.
+    ///
+    /// # Arguments
+    ///
+    /// * `user_id` - The AniList ID of the user.
+    /// * `media_type` - The media type of the list.
+    /// * `status` - The status of the entries.
+    /// * `page` - The page number.
+    ///
+    /// # Errors
+    ///
+    /// Returns the [`QueryError`] of the request, see [`lists::is_private`] for private lists.
+    pub async fn get_user_media_list(
+        &self,
+        user_id: i32,
+        media_type: MediaType,
+        status: ListStatus,
+        page: u16,
+    ) -> Result<UserListPage, QueryError> {
+        self.try_query::<lists::MediaListPage>(
+            lists::LIST_QUERY,
+            json!({
+                "userId": user_id,
+                "type": media_type.as_str(),
+                "status": status.as_str(),
+                "page": page,
+                "perPage": lists::PER_PAGE,
+            }),
+            None,
+        )
+        .await
+        .map(|data| lists::collect(data.page))
+    }
+
+    /// Gets the mangas a user is currently reading, as the user, so private lists are seen.
+    ///
+    /// Only the first [`lists::MAX_READING_PAGES`] pages are fetched, the last updated first.
+    ///
+    /// # Arguments
+    ///
+    /// * `user_id` - The AniList user ID.
+    /// * `token` - The user's AniList token.
+    ///
+    /// # Errors
+    ///
+    /// Returns the error of the first page that failed.
+    pub async fn get_reading_list(
+        &self,
+        user_id: i32,
+        token: &str,
+    ) -> Result<Vec<UserListEntry>, QueryError> {
+        let mut entries = Vec::new();
+
+        for page in 1..=lists::MAX_READING_PAGES {
+            let list = self
+                .try_query::<lists::MediaListPage>(
+                    lists::LIST_QUERY,
+                    json!({
+                        "userId": user_id,
+                        "type": MediaType::Manga.as_str(),
+                        "status": ListStatus::Current.as_str(),
+                        "page": page,
+                        "perPage": lists::READING_PER_PAGE,
+                    }),
+                    Some(token),
+                )
+                .await
+                .map(|data| lists::collect(data.page))?;
+
+            entries.extend(list.entries);
+            if page as usize >= list.last_page {
+                break;
+            }
+        }
+
+        Ok(entries)
+    }
+
+    /// Gets the anime and manga statistics of a user.
+    ///
+    /// # Arguments
+    ///
+    /// * `id` - The user ID.
+    pub async fn get_user_stats(&self, id: i32) -> Option<UserStats> {
+        self.query::<user_stats::UserStatistics>(
+            user_stats::QUERY,
+            json!({ "id": id, "genres": user_stats::GENRES }),
+            None,
+        )
+        .await
+        .map(|data| data.user.statistics)
+    }
+
+    /// Gets the favourite animes, mangas and characters of a user.
+    ///
+    /// # Arguments
+    ///
+    /// * `id` - The user ID.
+    pub async fn get_user_favourites(&self, id: i32) -> Option<Favourites> {
+        self.query::<user_favourites::UserFavourites>(
+            user_favourites::QUERY,
+            json!({ "id": id, "perPage": user_favourites::PER_KIND }),
+            None,
+        )
+        .await
+        .map(|data| user_favourites::collect(data.user.favourites))
+    }
+
+    /// Gets a character by its ID.
+    ///
+    /// Concurrent calls for the same character share a single request.
+    ///
+    /// # Arguments
+    ///
+    /// * `id` - The character ID.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`FetchError::RateLimited`] if AniList is rate limiting the bot, and
+    /// [`FetchError::NotFound`] if the character could not be retrieved otherwise.
+    pub async fn get_char(&self, id: i64) -> Result<Character, FetchError> {
+        if let Some(char) = self.cache_char.get(&id).await {
+            return Ok(char);
+        }
+
+        self.flights_char
+            .run(id, || async {
+                let char = self.call(self.client.get_char(id)).await?;
+                self.cache_char.insert(id, char.clone()).await;
+
+                Ok(char)
+            })
+            .await
+    }
+
+    /// Searches for animes by its title, by relevance.
+    ///
+    /// # Arguments
+    ///
+    /// * `title` - The anime title.
+    /// * `page` - The page number.
+    /// * `limit` - The number of results per page.
+    pub async fn search_anime(&self, title: &str, page: u16, limit: u16) -> Option<Vec<Anime>> {
+        self.search_anime_sorted(title, SearchSort::Relevance, page, limit)
+            .await
+    }
+
+    /// Searches for mangas by its title, by relevance.
+    ///
+    /// # Arguments
+    ///
+    /// * `title` - The manga title.
+    /// * `page` - The page number.
+    /// * `limit` - The number of results per page.
+    pub async fn search_manga(&self, title: &str, page: u16, limit: u16) -> Option<Vec<Manga>> {
+        self.search_manga_sorted(title, SearchSort::Relevance, page, limit)
+            .await
+    }
+
+    /// Searches for animes by its title, in the given order.
+    ///
+    /// The results are cached for 5 minutes. They lack the relations, characters and staff
+    /// of the anime, so they are not cached as full animes.
+    ///
+    /// # Arguments
+    ///
+    /// * `title` - The anime title.
+    /// * `sort` - The order of the results.
+    /// * `page` - The page number.
+    /// * `limit` - The number of results per page.
+    pub async fn search_anime_sorted(
+        &self,
+        title: &str,
+        sort: SearchSort,
+        page: u16,
+        limit: u16,
+    ) -> Option<Vec<Anime>> {
+        let key = (utils::normalize_search_terms(title), sort, page, limit);
+        if let Some(anime) = self.cache_search_anime.get(&key).await {
+            return Some(anime);
+        }
+
+        self.flights_search_anime
+            .run(key.clone(), || async {
+                let anime = self
+                    .search_media::<Anime>("ANIME", &key.0, sort, page, limit)
+                    .await?;
+                self.cache_search_anime
+                    .insert(key.clone(), anime.clone())
+                    .await;
+
+                Some(anime)
+            })
+            .await
+    }
+
+    /// Searches for mangas by its title, in the given order.
+    ///
+    /// The results are cached for 5 minutes. They lack the relations, characters and staff
+    /// of the manga, so they are not cached as full mangas.
+    ///
+    /// # Arguments
+    ///
+    /// * `title` - The manga title.
+    /// * `sort` - The order of the results.
+    /// * `page` - The page number.
+    /// * `limit` - The number of results per page.
+    pub async fn search_manga_sorted(
+        &self,
+        title: &str,
+        sort: SearchSort,
+        page: u16,
+        limit: u16,
+    ) -> Option<Vec<Manga>> {
+        let key = (utils::normalize_search_terms(title), sort, page, limit);
+        if let Some(manga) = self.cache_search_manga.get(&key).await {
+            return Some(manga);
+        }
+
+        self.flights_search_manga
+            .run(key.clone(), || async {
+                let manga = self
+                    .search_media::<Manga>("MANGA", &key.0, sort, page, limit)
+                    .await?;
+                self.cache_search_manga
+                    .insert(key.clone(), manga.clone())
+                    .await;
+
+                Some(manga)
+            })
+            .await
+    }
+
+    /// Picks random animes among the popular ones with a minimum score.
+    ///
+    /// The same seed gives the same picks, as long as AniList's results do not change.
+    ///
+    /// # Arguments
+    ///
+    /// * `genre` - The genre the animes must have, if any.
+    /// * `min_score` - The minimum average score, from 0 to 100.
+    /// * `count` - How many animes to pick.
+    /// * `seed` - The seed of the picks.
+    pub async fn random_anime(
+        &self,
+        genre: Option<&str>,
+        min_score: u8,
+        count: usize,
+        seed: u64,
+    ) -> Option<Vec<Anime>> {
+        let query = format!(
+            "query ($page: Int, $genre: String, $score: Int) {{
+                Page(page: $page, perPage: 50) {{
+                    media(type: ANIME, isAdult: false, genre: $genre, averageScore_greater: $score, sort: POPULARITY_DESC) {{ {} }}
+                }}
+            }}",
+            graphql::MEDIA_FIELDS
+        );
+        let fetch = |page: u16| {
+            self.query::<Page<MediaPage<Anime>>>(
+                &query,
+                json!({ "page": page, "genre": genre, "score": min_score }),
+                None,
+            )
+        };
+
+        let mut rng = StdRng::seed_from_u64(seed);
+        let page = rng.random_range(1..=RANDOM_PAGES);
+
+        let mut media = fetch(page).await?.page.media;
+        // Small genres do not have that many pages.
+        if media.is_empty() && page > 1 {
+            media = fetch(1).await?.page.media;
+        }
+
+        media.shuffle(&mut rng);
+        media.truncate(count);
+
+        Some(media)
+    }
+
+    /// Picks a random well-rated media, optionally of a genre.
+    ///
+    /// Counts the matching media, then fetches the one at a random position.
+    ///
+    /// # Arguments
+    ///
+    /// * `media_type` - The media type.
+    /// * `genre` - The genre the media must have, if any.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`FetchError::RateLimited`] if AniList is rate limiting the bot, and
+    /// [`FetchError::NotFound`] if the request failed. No media matching is `Ok(None)`.
+    pub async fn get_random_media(
+        &self,
+        media_type: MediaType,
+        genre: Option<&str>,
+    ) -> Result<Option<i64>, FetchError> {
+        let query = "query ($page: Int, $type: MediaType, $genre: String, $score: Int) {
+            Page(page: $page, perPage: 1) {
+                pageInfo { total }
+                media(type: $type, isAdult: false, genre: $genre, averageScore_greater: $score, sort: ID) { id }
+            }
+        }";
+        let fetch = |page: u32| async move {
+            self.try_query::<Page<MediaPage<graphql::MediaId>>>(
+                query,
+                json!({
+                    "page": page,
+                    "type": media_type.as_str(),
+                    "genre": genre,
+                    "score": RANDOM_MIN_SCORE,
+                }),
+                None,
+            )
+            .await
+            .map(|page| page.page)
+            .map_err(|e| match e {
+                QueryError::RateLimited(retry_after) => FetchError::RateLimited(retry_after),
+                _ => FetchError::NotFound,
+            })
+        };
+
+        let first = fetch(1).await?;
+        let total = first
+            .page_info
+            .and_then(|page_info| page_info.total)
+            .unwrap_or(first.media.len() as u32);
+        if total == 0 {
+            return Ok(None);
+        }
+
+        let page = rand::rng().random_range(1..=total);
+        if page == 1 {
+            return Ok(first.media.first().map(|media| media.id));
+        }
+
+        // The total is an estimate, fall back to the first media past the end.
+        let media = fetch(page).await?.media;
+        Ok(media.first().or(first.media.first()).map(|media| media.id))
+    }
+
+    /// Gets the trending animes.
+    ///
+    /// The results are cached for 30 minutes.
+    ///
+    /// # Arguments
+    ///
+    /// * `page` - The page number.
+    /// * `limit` - The number of results per page.
+    pub async fn get_trending_anime(&self, page: u16, limit: u16) -> Option<Vec<Anime>> {
+        if let Some(anime) = self
+            .cache_trending_anime
+            .get_fresh(&(page, limit), TRENDING_TTL)
+            .await
+        {
+            return Some(anime);
+        }
+
+        let anime = self.get_trending_media("ANIME", page, limit).await?;
+        self.cache_trending_anime
+            .insert((page, limit), anime.clone())
+            .await;
+
+        Some(anime)
+    }
+
+    /// Gets the trending mangas.
+    ///
+    /// The results are cached for 30 minutes.
+    ///
+    /// # Arguments
+    ///
+    /// * `page` - The page number.
+    /// * `limit` - The number of results per page.
+    pub async fn get_trending_manga(&self, page: u16, limit: u16) -> Option<Vec<Manga>> {
+        if let Some(manga) = self
+            .cache_trending_manga
+            .get_fresh(&(page, limit), TRENDING_TTL)
+            .await
+        {
+            return Some(manga);
+        }
+
+        let manga = self.get_trending_media("MANGA", page, limit).await?;
+        self.cache_trending_manga
+            .insert((page, limit), manga.clone())
+            .await;
+
+        Some(manga)
+    }
+
+    /// Gets the animes of a season, the most popular first.
+    ///
+    /// Returns the animes of the page and the number of pages.
+    ///
+    /// # Arguments
+    ///
+    /// * `season` - The season.
+    /// * `year` - The year of the season.
+    /// * `page` - The page number.
+    /// * `limit` - The number of results per page.
+    pub async fn get_season(
+        &self,
+        season: AnimeSeason,
+        year: i32,
+        page: u16,
+        limit: u16,
+    ) -> Option<(Vec<Anime>, u16)> {
+        let query = format!(
+            "query ($season: MediaSeason, $year: Int, $page: Int, $perPage: Int) {{
+                Page(page: $page, perPage: $perPage) {{
+                    pageInfo {{ lastPage }}
+                    media(type: ANIME, isAdult: false, season: $season, seasonYear: $year, sort: POPULARITY_DESC) {{ {} }}
+                }}
+            }}",
+            graphql::MEDIA_FIELDS
+        );
+
+        let page = self
+            .query::<Page<MediaPage<Anime>>>(
+                &query,
+                json!({
+                    "season": season.as_anilist(),
+                    "year": year,
+                    "page": page,
+                    "perPage": limit,
+                }),
+                None,
+            )
+            .await?
+            .page;
+        let last_page = page.page_info.and_then(|info| info.last_page).unwrap_or(1);
+
+        Some((page.media, last_page))
+    }
+
+    /// Creates or updates an entry of the user's AniList list.
+    ///
+    /// Returns the status the entry was saved with.
+    ///
+    /// # Arguments
+    ///
+    /// * `token` - The user's AniList token.
+    /// * `media_id` - The AniList ID of the media.
+    /// * `status` - The new status of the entry.
+    /// * `progress` - The new progress of the entry, if it changes.
+    /// * `score` - The new score of the entry, if it changes.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`QueryError::Unauthorized`] if the token expired or was revoked, and
+    /// another [`QueryError`] if the request fails.
+    pub async fn save_media_list_entry(
+        &self,
+        token: &str,
+        media_id: i64,
+        status: ListStatus,
+        progress: Option<i32>,
+        score: Option<f64>,
+    ) -> Result<ListStatus, QueryError> {
+        // Fields left out keep their current value, an explicit `null` would not.
+        let mut variables = json!({ "mediaId": media_id, "status": status.as_str() });
+        if let Some(progress) = progress {
+            variables["progress"] = json!(progress);
+        }
+        if let Some(score) = score {
+            variables["score"] = json!(score);
+        }
+
+        let saved = self
+            .try_query::<lists::SavedEntry>(lists::SAVE_MUTATION, variables, Some(token))
+            .await?;
+        if let Some(user_id) = saved.entry.user_id {
+            self.cache_list_entries
+                .insert((user_id, media_id), Some(saved.entry.clone()))
+                .await;
+        }
+
+        Ok(saved.entry.status.unwrap_or(status))
+    }
+
+    /// Gets an entry of a user's AniList list.
+    ///
+    /// Returns `None` if the media is not on the list or the request fails. The entries
+    /// are cached for a minute, so switching between the tabs of a card does not refetch.
+    ///
+    /// # Arguments
+    ///
+    /// * `token` - The user's AniList token, needed for private lists.
+    /// * `user_id` - The AniList ID of the user.
+    /// * `media_id` - The AniList ID of the media.
+    pub async fn get_media_list_entry(
+        &self,
+        token: &str,
+        user_id: i32,
+        media_id: i64,
+    ) -> Option<ListEntry> {
+        if let Some(entry) = self
+            .cache_list_entries
+            .get_fresh(&(user_id, media_id), LIST_ENTRY_TTL)
+            .await
+        {
+            return entry;
+        }
+
+        let entry = self
+            .try_query::<lists::MediaListEntry>(
+                lists::ENTRY_QUERY,
+                json!({ "userId": user_id, "mediaId": media_id }),
+                Some(token),
+            )
+            .await
+            .ok()?
+            .entry;
+        self.cache_list_entries
+            .insert((user_id, media_id), entry.clone())
+            .await;
+
+        entry
+    }
+
+    /// Gets the episodes of some animes that aired in a time window, oldest first.
+    ///
+    /// Returns `None` if any request fails, so no episode is skipped.
+    ///
+    /// # Arguments
+    ///
+    /// * `ids` - The AniList IDs of the animes.
+    /// * `from` - The start of the window, as a Unix timestamp, exclusive.
+    /// * `to` - The end of the window, as a Unix timestamp, exclusive.
+    pub async fn get_aired_episodes(
+        &self,
+        ids: &[i64],
+        from: i64,
+        to: i64,
+    ) -> Option<Vec<AiredEpisode>> {
+        let mut episodes = Vec::new();
+
+        for ids in ids.chunks(airing::IDS_PER_QUERY) {
+            for page in 1..=airing::MAX_PAGES {
+                let data = self
+                    .query::<Page<airing::AiredPage>>(
+                        airing::QUERY,
+                        json!({ "ids": ids, "from": from, "to": to, "page": page }),
+                        None,
+                    )
+                    .await?
+                    .page;
+                episodes.extend(data.airing_schedules);
+
+                if !data.page_info.has_next_page {
+                    break;
+                }
+            }
+        }
+
+        episodes.sort_by_key(|episode| episode.airing_at);
+
+        Some(episodes)
+    }
+
+    /// Gets the trending media of a type, without caching.
+    ///
+    /// # Arguments
+    ///
+    /// * `media_type` - The AniList media type, `ANIME` or `MANGA`.
+    /// * `page` - The page number.
+    /// * `limit` - The number of results per page.
+    pub async fn get_trending_media<T: DeserializeOwned>(
+        &self,
+        media_type: &str,
+        page: u16,
+        limit: u16,
+    ) -> Option<Vec<T>> {
+        let query = format!(
+            "query ($type: MediaType, $page: Int, $perPage: Int) {{
+                Page(page: $page, perPage: $perPage) {{
+                    media(type: $type, isAdult: false, sort: TRENDING_DESC) {{ {} }}
+                }}
+            }}",
+            graphql::MEDIA_FIELDS
+        );
+
+        self.query::<Page<MediaPage<T>>>(
+            &query,
+            json!({ "type": media_type, "page": page, "perPage": limit }),
+            None,
+        )
+        .await
+        .map(|page| page.page.media)
+    }
+
+    /// Searches for media of a type by its title, in the given order.
+    ///
+    /// # Arguments
+    ///
+    /// * `media_type` - The AniList media type, `ANIME` or `MANGA`.
+    /// * `title` - The media title.
+    /// * `sort` - The order of the results.
+    /// * `page` - The page number.
+    /// * `limit` - The number of results per page.
+    async fn search_media<T: DeserializeOwned>(
+        &self,
+        media_type: &str,
+        title: &str,
+        sort: SearchSort,
+        page: u16,
+        limit: u16,
+    ) -> Option<Vec<T>> {
+        let query = format!(
+            "query ($search: String, $type: MediaType, $sort: [MediaSort], $page: Int, $perPage: Int) {{
+                Page(page: $page, perPage: $perPage) {{
+                    media(search: $search, type: $type, sort: $sort) {{ {} }}
+                }}
+            }}",
+            graphql::MEDIA_FIELDS
+        );
+
+        self.query::<Page<MediaPage<T>>>(
+            &query,
+            json!({
+                "search": title,
+                "type": media_type,
+                "sort": sort.as_anilist(),
+                "page": page,
+                "perPage": limit,
+            }),
+            None,
+        )
+        .await
+        .map(|page| page.page.media)
+    }
+
+    /// Sends a GraphQL query to AniList once the rate limiter allows it.
+    ///
+    /// Returns `None` if the request fails, see [`AniList::try_query`].
+    ///
+    /// # Arguments
+    ///
+    /// * `query` - The GraphQL query.
+    /// * `variables` - The variables of the query.
+    /// * `token` - The user's AniList token, for queries that need authentication.
+    async fn query<T: DeserializeOwned>(
+        &self,
+        query: &str,
+        variables: Value,
+        token: Option<&str>,
+    ) -> Option<T> {
+        self.try_query(query, variables, token).await.ok()
+    }
+
+    /// Sends a GraphQL query to AniList once the rate limiter allows it.
+    ///
+    /// When AniList answers that the limit was exceeded, the next requests are blocked until
+    /// its `Retry-After`.
+    ///
+    /// # Arguments
+    ///
+    /// * `query` - The GraphQL query.
+    /// * `variables` - The variables of the query.
+    /// * `token` - The user's AniList token, for queries that need authentication.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`QueryError::RateLimited`] if the limiter is blocked, and the error of
+    /// [`graphql::try_query`] otherwise.
+    async fn try_query<T: DeserializeOwned>(
+        &self,
+        query: &str,
+        variables: Value,
+        token: Option<&str>,
+    ) -> Result<T, QueryError> {
+        self.limiter
+            .acquire()
+            .await
+            .map_err(QueryError::RateLimited)?;
+
+        let result = graphql::try_query(query, variables, token).await;
+        match result {
+            Ok(_) => self.record_request("ok"),
+            Err(QueryError::RateLimited(retry_after)) => {
+                self.limiter.block_for(retry_after);
+                self.record_request("rate_limited");
+            }
+            Err(_) => self.record_request("failed"),
+        }
+
+        result
+    }
+
+    /// Sends a request through the AniList client once the rate limiter allows it.
+    ///
+    /// # Arguments
+    ///
+    /// * `request` - The request.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`FetchError::RateLimited`] if the limiter is blocked or AniList rate limited
+    /// the request, and [`FetchError::NotFound`] if it fails otherwise.
+    async fn call<T>(
+        &self,
+        request: impl Future<Output = Result<T, Error>>,
+    ) -> Result<T, FetchError> {
+        self.limiter
+            .acquire()
+            .await
+            .map_err(FetchError::RateLimited)?;
+
+        let result = request.await.map_err(|e| {
+            if is_rate_limit_error(&e) {
+                self.limiter.block_for(graphql::DEFAULT_RETRY_AFTER);
+                FetchError::RateLimited(graphql::DEFAULT_RETRY_AFTER)
+            } else {
+                FetchError::NotFound
+            }
+        });
+        self.record_request(match result {
+            Ok(_) => "ok",
+            Err(FetchError::RateLimited(_)) => "rate_limited",
+            Err(FetchError::NotFound) => "failed",
+        });
+
+        result
+    }
+
+    /// Searches for users by its name.
+    ///
+    /// The results are cached for 5 minutes.
+    ///
+    /// # Arguments
+    ///
+    /// * `name` - The user name.
+    /// * `page` - The page number.
+    /// * `limit` - The number of results per page.
+    pub async fn search_user(&self, name: &str, page: u16, limit: u16) -> Option<Vec<User>> {
+        let key = (
+            utils::normalize_search_terms(name),
+            SearchSort::Relevance,
+            page,
+            limit,
+        );
+        if let Some(users) = self.cache_search_user.get(&key).await {
+            return Some(users);
+        }
+
+        self.flights_search_user
+            .run(key.clone(), || async {
+                self.limiter.acquire().await.ok()?;
+                let users = self.client.search_user(&key.0, page, limit).await;
+                self.record_request(if users.is_some() { "ok" } else { "failed" });
+
+                let users = users?;
+                self.cache_search_user
+                    .insert(key.clone(), users.clone())
+                    .await;
+
+                Some(users)
+            })
+            .await
+    }
+
+    /// Searches for characters by its name.
+    ///
+    /// # Arguments
+    ///
+    /// * `name` - The character name.
+    /// * `page` - The page number.
+    /// * `limit` - The number of results per page.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the character could not be retrieved.
+    pub async fn search_char(
+        &self,
+        _name: &str,
+        _page: u16,
+        _limit: u16,
+    ) -> Option<Vec<Character>> {
+        // self.client.search_char(name, page, limit).await
+        None
+    }
+}
+
+/// Whether an error of the AniList client is a rate limit.
+///
+/// The client exposes neither the status nor the headers of the response, only its message.
+///
+/// # Arguments
+///
+/// * `error` - The error of the client.
+fn is_rate_limit_error(error: &Error) -> bool {
+    let message = format!("{:?}", error).to_lowercase();
+    message.contains("429") || message.contains("too many requests")
+}
+
+/// Resolves an ID from the stored mappings first, falling back to fetching it.
+///
+/// `fetch` is only called when `stored` does not know the ID.
+///
+/// # Arguments
+///
+/// * `stored` - Looks the ID up in the stored mappings.
+/// * `fetch` - Fetches the ID from the API.
+pub async fn resolve_with<S, SF, F, FF>(stored: S, fetch: F) -> Option<Resolution>
+where
+    S: FnOnce() -> SF,
+    SF: Future<Output = Option<i64>>,
+    F: FnOnce() -> FF,
+    FF: Future<Output = Option<i64>>,
+{
+    if let Some(id) = stored().await {
+        return Some(Resolution::Stored(id));
+    }
+
+    fetch().await.map(Resolution::Fetched)
+}
@@ -0,0 +1,64 @@
+// Copyright 2025 - Andriel Ferreira
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// https://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or https://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+//! The overview of a media, the little the tabs of its card show above their content.
+
+use rust_anilist::models::{Anime, Manga, Title};
+use serde::Deserialize;
+
+/// The query of the overview of a media.
+pub const QUERY: &str = "
+    query ($id: Int, $type: MediaType) {
+        Media(id: $id, type: $type) {
+            id
+            title { romaji english native userPreferred }
+            isAdult
+        }
+    }
+";
+
+/// The overview of a media, looked up by its ID.
+#[derive(Deserialize)]
+pub struct MediaOverviewById {
+    /// The media.
+    #[serde(rename = "Media")]
+    pub media: MediaOverview,
+}
+
+/// The overview of a media.
+#[derive(Clone, Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct MediaOverview {
+    /// The AniList ID.
+    pub id: i64,
+    /// The title.
+    pub title: Title,
+    /// Whether the media is for adults.
+    #[serde(default)]
+    pub is_adult: bool,
+}
+
+impl From<&Anime> for MediaOverview {
+    fn from(anime: &Anime) -> Self {
+        Self {
+            id: anime.id,
+            title: anime.title.clone(),
+            is_adult: anime.is_adult,
+        }
+    }
+}
+
+impl From<&Manga> for MediaOverview {
+    fn from(manga: &Manga) -> Self {
+        Self {
+            id: manga.id,
+            title: manga.title.clone(),
+            is_adult: manga.is_adult,
+        }
+    }
+}
@@ -0,0 +1,143 @@
+// Copyright 2025 - Andriel Ferreira
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// https://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or https://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+//! The recommendations of a media, i.e. "users who liked X also liked Y".
+
+use serde::Deserialize;
+
+use super::graphql::PageInfo;
+use crate::models::MediaType;
+
+/// How many recommendations are listed per page.
+pub const PER_PAGE: u16 = 10;
+
+/// The query of a page of recommendations, the best rated first.
+pub const QUERY: &str = "
+    query ($id: Int, $page: Int, $perPage: Int) {
+        Media(id: $id) {
+            recommendations(page: $page, perPage: $perPage, sort: [RATING_DESC, ID]) {
+                pageInfo { lastPage }
+                nodes {
+                    rating
+                    mediaRecommendation { id type title { romaji } }
+                }
+            }
+        }
+    }
+";
+
+/// A recommended media.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Recommendation {
+    /// The AniList ID of the media.
+    pub id: i64,
+    /// The media type.
+    pub media_type: MediaType,
+    /// The romaji title of the media.
+    pub title: String,
+    /// The rating of the recommendation, i.e. the upvotes minus the downvotes.
+    pub rating: i64,
+}
+
+/// A page of recommendations.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct RecommendationPage {
+    /// The recommendations of the page.
+    pub recommendations: Vec<Recommendation>,
+    /// The number of the last page.
+    pub last_page: usize,
+}
+
+/// The recommendations of a media.
+#[derive(Deserialize)]
+pub struct MediaRecommendations {
+    /// The media.
+    #[serde(rename = "Media")]
+    pub media: RecommendedMedia,
+}
+
+/// The media being recommended from.
+#[derive(Deserialize)]
+pub struct RecommendedMedia {
+    /// The recommendation connection.
+    pub recommendations: RecommendationConnection,
+}
+
+/// A recommendation connection.
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RecommendationConnection {
+    /// The pagination of the connection.
+    pub page_info: PageInfo,
+    /// The nodes of the connection.
+    #[serde(default)]
+    pub nodes: Vec<RecommendationNode>,
+}
+
+/// A recommendation node.
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RecommendationNode {
+    /// The rating of the recommendation.
+    pub rating: Option<i64>,
+    /// The recommended media, missing if it was deleted.
+    pub media_recommendation: Option<MediaNode>,
+}
+
+/// A media node.
+#[derive(Deserialize)]
+pub struct MediaNode {
+    /// The AniList ID.
+    pub id: i64,
+    /// The media type, `ANIME` or `MANGA`.
+    #[serde(rename = "type")]
+    pub media_type: Option<String>,
+    /// The title.
+    pub title: MediaTitle,
+}
+
+/// The title of a media.
+#[derive(Deserialize)]
+pub struct MediaTitle {
+    /// The romaji title.
+    pub romaji: Option<String>,
+}
+
+/// Converts a recommendation connection into a page of recommendations.
+///
+/// Deleted media and media with an unknown type are skipped.
+///
+/// # Arguments
+///
+/// * `connection` - The recommendation connection.
+pub fn collect(connection: RecommendationConnection) -> RecommendationPage {
+    let recommendations = connection
+        .nodes
+        .into_iter()
+        .filter_map(|node| {
+            let media = node.media_recommendation?;
+            let media_type = match media.media_type.as_deref() {
+                Some("ANIME") => MediaType::Anime,
+                Some("MANGA") => MediaType::Manga,
+                _ => return None,
+            };
+
+            Some(Recommendation {
+                id: media.id,
+                media_type,
+                title: media.title.romaji.unwrap_or_default(),
+                rating: node.rating.unwrap_or_default(),
+            })
+        })
+        .collect();
+
+    RecommendationPage {
+        recommendations,
+        last_page: connection.page_info.last_page.unwrap_or(1).max(1) as usize,
+    }
+}
@@ -0,0 +1,175 @@
+// Copyright 2025 - Andriel Ferreira
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// https://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or https://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+//! The AniList staff, e.g. voice actors, directors and mangakas.
+
+use serde::Deserialize;
+
+/// The staff fields requested by the queries.
+const STAFF_FIELDS: &str = "
+    id
+    name { full native }
+    languageV2
+    age
+    homeTown
+    primaryOccupations
+    description
+    siteUrl
+    image { large }
+    characters(perPage: 50, sort: FAVOURITES_DESC) {
+        nodes { id name { full } }
+    }
+    staffMedia(perPage: 50, sort: POPULARITY_DESC) {
+        edges { staffRole node { id type title { romaji } } }
+    }
+";
+
+/// The query of a staff by its ID.
+pub fn by_id_query() -> String {
+    format!(
+        "query ($id: Int) {{ Staff(id: $id) {{ {} }} }}",
+        STAFF_FIELDS
+    )
+}
+
+/// The query of the staff matching a name.
+pub fn search_query() -> String {
+    format!(
+        "query ($search: String, $page: Int, $perPage: Int) {{
+            Page(page: $page, perPage: $perPage) {{ staff(search: $search) {{ {} }} }}
+        }}",
+        STAFF_FIELDS
+    )
+}
+
+/// A staff member.
+#[derive(Clone, Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Staff {
+    /// The AniList ID.
+    pub id: i64,
+    /// The name.
+    pub name: StaffName,
+    /// The primary language.
+    #[serde(rename = "languageV2")]
+    pub language: Option<String>,
+    /// The age.
+    pub age: Option<i64>,
+    /// The home town.
+    pub home_town: Option<String>,
+    /// The primary occupations, e.g. `Voice Actor`.
+    #[serde(default)]
+    pub primary_occupations: Vec<String>,
+    /// The description, in markdown.
+    pub description: Option<String>,
+    /// The AniList page.
+    pub site_url: String,
+    /// The image.
+    pub image: Option<StaffImage>,
+    /// The characters voiced, the most favourited first.
+    #[serde(default)]
+    pub characters: VoicedCharacters,
+    /// The works, the most popular first.
+    #[serde(default)]
+    pub staff_media: Works,
+}
+
+/// The name of a staff member.
+#[derive(Clone, Debug, Deserialize)]
+pub struct StaffName {
+    /// The full name.
+    pub full: String,
+    /// The name in the native language.
+    pub native: Option<String>,
+}
+
+/// The image of a staff member.
+#[derive(Clone, Debug, Deserialize)]
+pub struct StaffImage {
+    /// The large image.
+    pub large: Option<String>,
+}
+
+/// The characters voiced by a staff member.
+#[derive(Clone, Debug, Default, Deserialize)]
+pub struct VoicedCharacters {
+    /// The characters.
+    #[serde(default)]
+    pub nodes: Vec<VoicedCharacter>,
+}
+
+/// A character voiced by a staff member.
+#[derive(Clone, Debug, Deserialize)]
+pub struct VoicedCharacter {
+    /// The AniList ID.
+    pub id: i64,
+    /// The name.
+    pub name: StaffName,
+}
+
+/// The works of a staff member.
+#[derive(Clone, Debug, Default, Deserialize)]
+pub struct Works {
+    /// The works.
+    #[serde(default)]
+    pub edges: Vec<Work>,
+}
+
+/// A work of a staff member.
+#[derive(Clone, Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Work {
+    /// The role in the work, e.g. `Director`.
+    pub staff_role: Option<String>,
+    /// The media.
+    pub node: WorkMedia,
+}
+
+/// The media of a work.
+#[derive(Clone, Debug, Deserialize)]
+pub struct WorkMedia {
+    /// The AniList ID.
+    pub id: i64,
+    /// The media type, `ANIME` or `MANGA`.
+    #[serde(rename = "type")]
+    pub media_type: Option<String>,
+    /// The title.
+    pub title: WorkTitle,
+}
+
+/// The title of a work.
+#[derive(Clone, Debug, Deserialize)]
+pub struct WorkTitle {
+    /// The romaji title.
+    pub romaji: Option<String>,
+}
+
+impl Staff {
+    /// Gets the URL of the largest image, or an empty string.
+    pub fn image_url(&self) -> String {
+        self.image
+            .as_ref()
+            .and_then(|image| image.large.clone())
+            .unwrap_or_default()
+    }
+}
+
+/// A single staff member.
+#[derive(Deserialize)]
+pub struct StaffById {
+    /// The staff member.
+    #[serde(rename = "Staff")]
+    pub staff: Staff,
+}
+
+/// A page of staff.
+#[derive(Deserialize)]
+pub struct StaffPage {
+    /// The staff of the page.
+    pub staff: Vec<Staff>,
+}
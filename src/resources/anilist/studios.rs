@@ -0,0 +1,107 @@
+// Copyright 2025 - Andriel Ferreira
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// https://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or https://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+//! The AniList studios.
+
+use serde::Deserialize;
+
+/// The studio fields requested by the queries.
+const STUDIO_FIELDS: &str = "
+    id
+    name
+    isAnimationStudio
+    siteUrl
+    favourites
+    media(sort: POPULARITY_DESC, isMain: true, perPage: 50) {
+        nodes { id type format averageScore title { romaji } }
+    }
+";
+
+/// The query of a studio by its ID.
+pub fn by_id_query() -> String {
+    format!(
+        "query ($id: Int) {{ Studio(id: $id) {{ {} }} }}",
+        STUDIO_FIELDS
+    )
+}
+
+/// The query of the studios matching a name.
+pub fn search_query() -> String {
+    format!(
+        "query ($search: String, $page: Int, $perPage: Int) {{
+            Page(page: $page, perPage: $perPage) {{ studios(search: $search) {{ {} }} }}
+        }}",
+        STUDIO_FIELDS
+    )
+}
+
+/// A studio.
+#[derive(Clone, Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Studio {
+    /// The AniList ID.
+    pub id: i64,
+    /// The name.
+    pub name: String,
+    /// Whether the studio animates, rather than only producing.
+    pub is_animation_studio: bool,
+    /// The AniList page.
+    pub site_url: String,
+    /// How many users favourited the studio.
+    pub favourites: Option<i64>,
+    /// The most popular productions.
+    #[serde(default)]
+    pub media: Productions,
+}
+
+/// The productions of a studio.
+#[derive(Clone, Debug, Default, Deserialize)]
+pub struct Productions {
+    /// The productions.
+    #[serde(default)]
+    pub nodes: Vec<Production>,
+}
+
+/// A production of a studio.
+#[derive(Clone, Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Production {
+    /// The AniList ID.
+    pub id: i64,
+    /// The media type, `ANIME` or `MANGA`.
+    #[serde(rename = "type")]
+    pub media_type: Option<String>,
+    /// The media format, e.g. `TV`.
+    pub format: Option<String>,
+    /// The average score, from 0 to 100.
+    pub average_score: Option<i64>,
+    /// The title.
+    pub title: ProductionTitle,
+}
+
+/// The title of a production.
+#[derive(Clone, Debug, Deserialize)]
+pub struct ProductionTitle {
+    /// The romaji title.
+    pub romaji: Option<String>,
+}
+
+/// A single studio.
+#[derive(Deserialize)]
+pub struct StudioById {
+    /// The studio.
+    #[serde(rename = "Studio")]
+    pub studio: Studio,
+}
+
+/// A page of studios.
+#[derive(Deserialize)]
+pub struct StudioPage {
+    /// The studios of the page.
+    pub studios: Vec<Studio>,
+}
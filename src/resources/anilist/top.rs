@@ -0,0 +1,108 @@
+// Copyright 2025 - Andriel Ferreira
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// https://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or https://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+//! The rankings of the best scored media.
+
+use serde::Deserialize;
+
+use super::graphql::PageInfo;
+
+/// How many media are listed per page.
+pub const PER_PAGE: u16 = 10;
+
+/// The query of a page of a ranking, the best scored first.
+pub const QUERY: &str = "
+    query ($type: MediaType, $format: MediaFormat, $status: MediaStatus, $page: Int, $perPage: Int) {
+        Page(page: $page, perPage: $perPage) {
+            pageInfo { lastPage }
+            media(type: $type, format: $format, status: $status, isAdult: false, sort: [SCORE_DESC, ID]) {
+                id
+                title { romaji }
+                averageScore
+            }
+        }
+    }
+";
+
+/// A ranked media.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct TopEntry {
+    /// The AniList ID of the media.
+    pub id: i64,
+    /// The romaji title of the media.
+    pub title: String,
+    /// The average score of the media, if it was scored.
+    pub average_score: Option<u8>,
+}
+
+/// A page of a ranking.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct TopPage {
+    /// The media of the page, the best scored first.
+    pub media: Vec<TopEntry>,
+    /// The number of the last page.
+    pub last_page: usize,
+}
+
+/// A page of media.
+#[derive(Deserialize)]
+pub struct MediaPage {
+    /// The page.
+    #[serde(rename = "Page")]
+    pub page: Media,
+}
+
+/// The media of a page.
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Media {
+    /// The pagination of the page.
+    pub page_info: PageInfo,
+    /// The media.
+    #[serde(default)]
+    pub media: Vec<MediaNode>,
+}
+
+/// A media node.
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct MediaNode {
+    /// The AniList ID.
+    pub id: i64,
+    /// The title.
+    pub title: MediaTitle,
+    /// The average score.
+    pub average_score: Option<u8>,
+}
+
+/// The title of a media.
+#[derive(Deserialize)]
+pub struct MediaTitle {
+    /// The romaji title.
+    pub romaji: Option<String>,
+}
+
+/// Converts a page of media into a page of a ranking.
+///
+/// # Arguments
+///
+/// * `page` - The page of media.
+pub fn collect(page: Media) -> TopPage {
+    TopPage {
+        media: page
+            .media
+            .into_iter()
+            .map(|media| TopEntry {
+                id: media.id,
+                title: media.title.romaji.unwrap_or_default(),
+                average_score: media.average_score,
+            })
+            .collect(),
+        last_page: page.page_info.last_page.unwrap_or(1).max(1) as usize,
+    }
+}
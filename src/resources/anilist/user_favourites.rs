@@ -0,0 +1,137 @@
+// Copyright 2025 - Andriel Ferreira
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// https://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or https://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+//! The favourites of a user.
+
+use serde::Deserialize;
+
+/// How many favourites of each kind are listed.
+pub const PER_KIND: u8 = 5;
+
+/// The query of the favourites of a user.
+pub const QUERY: &str = "
+    query ($id: Int, $perPage: Int) {
+        User(id: $id) {
+            favourites {
+                anime(perPage: $perPage) { nodes { id title { romaji } } }
+                manga(perPage: $perPage) { nodes { id title { romaji } } }
+                characters(perPage: $perPage) { nodes { id name { full } } }
+            }
+        }
+    }
+";
+
+/// A favourite anime, manga or character.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Favourite {
+    /// The AniList ID.
+    pub id: i64,
+    /// The romaji title of the media, or the full name of the character.
+    pub name: String,
+}
+
+/// The favourites of a user.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct Favourites {
+    /// The favourite animes.
+    pub anime: Vec<Favourite>,
+    /// The favourite mangas.
+    pub manga: Vec<Favourite>,
+    /// The favourite characters.
+    pub characters: Vec<Favourite>,
+}
+
+impl Favourites {
+    /// Whether the user has no favourites.
+    pub fn is_empty(&self) -> bool {
+        self.anime.is_empty() && self.manga.is_empty() && self.characters.is_empty()
+    }
+}
+
+/// The favourites of a user, as returned by AniList.
+#[derive(Deserialize)]
+pub struct UserFavourites {
+    /// The user.
+    #[serde(rename = "User")]
+    pub user: FavouritesUser,
+}
+
+/// The user of the favourites.
+#[derive(Deserialize)]
+pub struct FavouritesUser {
+    /// The favourites of the user.
+    pub favourites: FavouriteConnections,
+}
+
+/// The favourite connections of a user.
+#[derive(Deserialize)]
+pub struct FavouriteConnections {
+    /// The anime connection.
+    pub anime: Option<Connection>,
+    /// The manga connection.
+    pub manga: Option<Connection>,
+    /// The character connection.
+    pub characters: Option<Connection>,
+}
+
+/// A connection of favourites.
+#[derive(Deserialize)]
+pub struct Connection {
+    /// The nodes of the connection.
+    #[serde(default)]
+    pub nodes: Vec<Node>,
+}
+
+/// A media or character node.
+#[derive(Deserialize)]
+pub struct Node {
+    /// The AniList ID.
+    pub id: i64,
+    /// The title, for media.
+    pub title: Option<Name>,
+    /// The name, for characters.
+    pub name: Option<Name>,
+}
+
+/// The title of a media or the name of a character.
+#[derive(Deserialize)]
+pub struct Name {
+    /// The romaji title.
+    pub romaji: Option<String>,
+    /// The full name.
+    pub full: Option<String>,
+}
+
+/// Converts the favourite connections into the favourites of a user.
+///
+/// # Arguments
+///
+/// * `connections` - The favourite connections.
+pub fn collect(connections: FavouriteConnections) -> Favourites {
+    let nodes = |connection: Option<Connection>| {
+        connection
+            .map(|connection| connection.nodes)
+            .unwrap_or_default()
+            .into_iter()
+            .map(|node| Favourite {
+                id: node.id,
+                name: node
+                    .title
+                    .or(node.name)
+                    .and_then(|name| name.romaji.or(name.full))
+                    .unwrap_or_default(),
+            })
+            .collect()
+    };
+
+    Favourites {
+        anime: nodes(connections.anime),
+        manga: nodes(connections.manga),
+        characters: nodes(connections.characters),
+    }
+}
@@ -0,0 +1,92 @@
+// Copyright 2025 - Andriel Ferreira
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// https://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or https://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+//! The statistics of a user.
+
+use serde::Deserialize;
+
+/// How many genres are listed per media type.
+pub const GENRES: u8 = 5;
+
+/// The query of the statistics of a user.
+pub const QUERY: &str = "
+    query ($id: Int, $genres: Int) {
+        User(id: $id) {
+            statistics {
+                anime {
+                    count meanScore minutesWatched episodesWatched
+                    genres(limit: $genres, sort: COUNT_DESC) { genre count }
+                    statuses(sort: COUNT_DESC) { status count }
+                }
+                manga {
+                    count meanScore chaptersRead volumesRead
+                    genres(limit: $genres, sort: COUNT_DESC) { genre count }
+                    statuses(sort: COUNT_DESC) { status count }
+                }
+            }
+        }
+    }
+";
+
+/// The statistics of a user.
+#[derive(Deserialize)]
+pub struct UserStatistics {
+    /// The user.
+    #[serde(rename = "User")]
+    pub user: StatisticsUser,
+}
+
+/// The user of the statistics.
+#[derive(Deserialize)]
+pub struct StatisticsUser {
+    /// The statistics of the user.
+    pub statistics: UserStats,
+}
+
+/// The statistics of a user, per media type.
+#[derive(Clone, Debug, Default, Deserialize, PartialEq)]
+pub struct UserStats {
+    /// The anime statistics.
+    #[serde(default)]
+    pub anime: MediaStats,
+    /// The manga statistics.
+    #[serde(default)]
+    pub manga: MediaStats,
+}
+
+/// The statistics of a media type.
+#[derive(Clone, Debug, Default, Deserialize, PartialEq)]
+#[serde(rename_all = "camelCase", default)]
+pub struct MediaStats {
+    /// How many entries are on the list.
+    pub count: u32,
+    /// The mean score given, out of 100.
+    pub mean_score: f32,
+    /// How many minutes of anime were watched.
+    pub minutes_watched: u32,
+    /// How many episodes were watched.
+    pub episodes_watched: u32,
+    /// How many chapters were read.
+    pub chapters_read: u32,
+    /// How many volumes were read.
+    pub volumes_read: u32,
+    /// The most listed genres, the most listed first.
+    pub genres: Vec<StatCount>,
+    /// How many entries have each status, the most common first.
+    pub statuses: Vec<StatCount>,
+}
+
+/// How many entries have a genre or status.
+#[derive(Clone, Debug, Deserialize, PartialEq, Eq)]
+pub struct StatCount {
+    /// The genre or status, as AniList names it.
+    #[serde(alias = "genre", alias = "status")]
+    pub name: String,
+    /// How many entries have it.
+    pub count: u32,
+}
@@ -0,0 +1,97 @@
+// Copyright 2025 - Andriel Ferreira
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// https://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or https://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+//! The voice actors of a character.
+
+use serde::Deserialize;
+
+/// The query of the voice actors, taken from the most popular media of the character.
+pub const QUERY: &str = "
+    query ($id: Int) {
+        Character(id: $id) {
+            media(perPage: 25, sort: POPULARITY_DESC) {
+                edges {
+                    voiceActors { id name { full } languageV2 siteUrl }
+                }
+            }
+        }
+    }
+";
+
+/// The voice actors of a character.
+#[derive(Deserialize)]
+pub struct CharacterVoiceActors {
+    /// The character.
+    #[serde(rename = "Character")]
+    pub character: CharacterMedia,
+}
+
+/// The media of a character.
+#[derive(Deserialize)]
+pub struct CharacterMedia {
+    /// The media connection.
+    pub media: MediaConnection,
+}
+
+/// A media connection.
+#[derive(Deserialize)]
+pub struct MediaConnection {
+    /// The edges of the connection.
+    #[serde(default)]
+    pub edges: Vec<MediaEdge>,
+}
+
+/// A media edge, holding the voice actors of the character in that media.
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct MediaEdge {
+    /// The voice actors.
+    #[serde(default)]
+    pub voice_actors: Vec<VoiceActor>,
+}
+
+/// A voice actor.
+#[derive(Clone, Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct VoiceActor {
+    /// The AniList ID.
+    pub id: i64,
+    /// The name.
+    pub name: VoiceActorName,
+    /// The language the voice actor dubs in.
+    #[serde(rename = "languageV2")]
+    pub language: Option<String>,
+    /// The AniList page.
+    pub site_url: String,
+}
+
+/// The name of a voice actor.
+#[derive(Clone, Debug, Deserialize)]
+pub struct VoiceActorName {
+    /// The full name.
+    pub full: String,
+}
+
+/// Collects the voice actors of every media, without repeating them.
+///
+/// The actors keep the order they first appear in, so the most popular media come first.
+///
+/// # Arguments
+///
+/// * `edges` - The media edges.
+pub fn collect(edges: Vec<MediaEdge>) -> Vec<VoiceActor> {
+    let mut actors = Vec::<VoiceActor>::new();
+
+    for actor in edges.into_iter().flat_map(|edge| edge.voice_actors) {
+        if !actors.iter().any(|known| known.id == actor.id) {
+            actors.push(actor);
+        }
+    }
+
+    actors
+}
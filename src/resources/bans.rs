@@ -0,0 +1,118 @@
+// Copyright 2025 - Andriel Ferreira
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// https://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or https://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+//! The bans resource.
+
+use std::time::Duration;
+
+use crate::{
+    models::BannedUser,
+    resources::{Cache, Database},
+};
+
+/// How long whether a user is banned is reused before asking the database again.
+pub const BAN_TTL: Duration = Duration::from_secs(10 * 60);
+
+/// Bans module.
+///
+/// Tells whether a user is banned, caching the answer so every update does not query the
+/// database.
+#[derive(Clone, Debug)]
+pub struct Bans {
+    /// Whether each user is banned.
+    cache: Cache<i64, bool>,
+}
+
+impl Default for Bans {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Bans {
+    /// Creates a new instance of the bans.
+    pub fn new() -> Self {
+        Self {
+            cache: Cache::with_capacity_and_ttl(10000, BAN_TTL),
+        }
+    }
+
+    /// Whether a user is banned.
+    ///
+    /// Users are not considered banned while the database is unavailable.
+    ///
+    /// # Arguments
+    ///
+    /// * `db` - The database resource.
+    /// * `user_id` - The user's ID.
+    pub async fn is_banned(&self, db: &Database, user_id: i64) -> bool {
+        if let Some(banned) = self.cache.get(&user_id).await {
+            return banned;
+        }
+        if !db.is_healthy() {
+            return false;
+        }
+
+        match db.report(BannedUser::get(db.pool(), user_id).await) {
+            Ok(ban) => {
+                self.cache.insert(user_id, ban.is_some()).await;
+
+                ban.is_some()
+            }
+            Err(e) => {
+                log::warn!("failed to check whether {} is banned: {:?}", user_id, e);
+
+                false
+            }
+        }
+    }
+
+    /// Bans a user.
+    ///
+    /// # Arguments
+    ///
+    /// * `db` - The database resource.
+    /// * `user_id` - The user's ID.
+    /// * `reason` - Why the user is banned.
+    /// * `banned_by` - The ID of the sudoer banning the user.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the query fails.
+    pub async fn ban(
+        &self,
+        db: &Database,
+        user_id: i64,
+        reason: Option<&str>,
+        banned_by: i64,
+    ) -> sqlx::Result<()> {
+        db.report(BannedUser::ban(db.pool(), user_id, reason, banned_by).await)?;
+        self.cache.insert(user_id, true).await;
+
+        Ok(())
+    }
+
+    /// Unbans a user.
+    ///
+    /// Returns whether the user was banned.
+    ///
+    /// # Arguments
+    ///
+    /// * `db` - The database resource.
+    /// * `user_id` - The user's ID.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the query fails.
+    pub async fn unban(&self, db: &Database, user_id: i64) -> sqlx::Result<bool> {
+        let banned = db.report(BannedUser::unban(db.pool(), user_id).await)?;
+        self.cache.insert(user_id, false).await;
+
+        Ok(banned)
+    }
+}
@@ -8,17 +8,93 @@
 
 //! The cache resource.
 
-use std::{collections::HashMap, hash::Hash, sync::Arc};
+use std::{
+    collections::{BTreeMap, HashMap},
+    hash::Hash,
+    sync::{
+        Arc,
+        atomic::{AtomicU64, Ordering},
+    },
+    time::Duration,
+};
 
-use tokio::sync::RwLock;
+use tokio::{sync::RwLock, time::Instant};
+
+use crate::resources::Metrics;
 
 /// Cache module.
+///
+/// Once full, inserting evicts the least recently used value. Caches with a TTL also
+/// treat the values older than it as missing.
 #[derive(Clone, Debug)]
 pub struct Cache<K, V> {
-    /// The underlying map storing the cached values.
-    map: Arc<RwLock<HashMap<K, V>>>,
+    /// The cached values and their recency.
+    inner: Arc<RwLock<Inner<K, V>>>,
     /// The maximum size of the cache.
     capacity: usize,
+    /// How long the values live, forever if `None`.
+    ttl: Option<Duration>,
+    /// How many lookups found a value.
+    hits: Arc<AtomicU64>,
+    /// How many lookups found nothing.
+    misses: Arc<AtomicU64>,
+    /// Where the lookups are also counted, with the name of the cache.
+    metrics: Option<(Metrics, &'static str)>,
+}
+
+/// The cached values and their recency.
+#[derive(Debug)]
+struct Inner<K, V> {
+    /// The cached values, by key.
+    map: HashMap<K, Entry<V>>,
+    /// The keys, by when they were last used, least recently used first.
+    order: BTreeMap<u64, K>,
+    /// The counter the uses are numbered with.
+    tick: u64,
+}
+
+/// A cached value.
+#[derive(Debug)]
+struct Entry<V> {
+    /// The value.
+    value: V,
+    /// When the value was inserted.
+    inserted_at: Instant,
+    /// When the value was last used, from the counter of the cache.
+    used_at: u64,
+}
+
+impl<K, V> Inner<K, V>
+where
+    K: Eq + Hash + Clone,
+{
+    /// Marks a value as the most recently used, returning it.
+    ///
+    /// # Arguments
+    ///
+    /// * `key` - The key associated with the value.
+    fn touch(&mut self, key: &K) -> Option<&Entry<V>> {
+        let entry = self.map.get_mut(key)?;
+
+        self.order.remove(&entry.used_at);
+        self.tick += 1;
+        entry.used_at = self.tick;
+        self.order.insert(self.tick, key.clone());
+
+        Some(entry)
+    }
+
+    /// Removes a value.
+    ///
+    /// # Arguments
+    ///
+    /// * `key` - The key associated with the value.
+    fn remove(&mut self, key: &K) -> Option<Entry<V>> {
+        let entry = self.map.remove(key)?;
+        self.order.remove(&entry.used_at);
+
+        Some(entry)
+    }
 }
 
 #[allow(dead_code)]
@@ -34,19 +110,162 @@ where
     /// * `capacity` - The max size of the cache.
     pub fn with_capacity(capacity: usize) -> Self {
         Self {
-            map: Arc::new(RwLock::new(HashMap::new())),
+            inner: Arc::new(RwLock::new(Inner {
+                map: HashMap::new(),
+                order: BTreeMap::new(),
+                tick: 0,
+            })),
             capacity,
+            ttl: None,
+            hits: Arc::new(AtomicU64::new(0)),
+            misses: Arc::new(AtomicU64::new(0)),
+            metrics: None,
+        }
+    }
+
+    /// Creates a new instance of the cache whose values expire.
+    ///
+    /// # Arguments
+    ///
+    /// * `capacity` - The max size of the cache.
+    /// * `ttl` - How long the values live.
+    pub fn with_capacity_and_ttl(capacity: usize, ttl: Duration) -> Self {
+        Self {
+            ttl: Some(ttl),
+            ..Self::with_capacity(capacity)
+        }
+    }
+
+    /// Counts the lookups of the cache in the metrics too.
+    ///
+    /// # Arguments
+    ///
+    /// * `metrics` - The metrics resource.
+    /// * `name` - The name of the cache, e.g. `anime`.
+    pub fn with_metrics(mut self, metrics: Metrics, name: &'static str) -> Self {
+        self.metrics = Some((metrics, name));
+        self
+    }
+
+    /// Checks whether a value outlived the TTL of the cache.
+    ///
+    /// # Arguments
+    ///
+    /// * `entry` - The cached value.
+    fn is_expired(&self, entry: &Entry<V>) -> bool {
+        self.ttl
+            .is_some_and(|ttl| entry.inserted_at.elapsed() >= ttl)
+    }
+
+    /// Retrieves a cloned value from the cache, marking it as recently used.
+    ///
+    /// Expired values are removed and treated as missing.
+    ///
+    /// # Arguments
+    ///
+    /// * `key` - The key associated with the value to be retrieved.
+    pub async fn get(&self, key: &K) -> Option<V> {
+        self.get_fresh(key, Duration::MAX).await
+    }
+
+    /// Retrieves a cloned value from the cache, if it was cached less than `max_age` ago,
+    /// marking it as recently used.
+    ///
+    /// # Arguments
+    ///
+    /// * `key` - The key associated with the value to be retrieved.
+    /// * `max_age` - How long ago the value may have been cached.
+    pub async fn get_fresh(&self, key: &K, max_age: Duration) -> Option<V> {
+        let value = self.lookup(key, max_age).await;
+
+        if value.is_some() {
+            self.hits.fetch_add(1, Ordering::Relaxed);
+        } else {
+            self.misses.fetch_add(1, Ordering::Relaxed);
+        }
+        if let Some((metrics, name)) = &self.metrics {
+            metrics.record_cache_lookup(name, value.is_some());
         }
+
+        value
     }
 
-    /// Retrieves a cloned value from the cache.
+    /// Retrieves a cloned value from the cache, without counting the lookup.
     ///
     /// # Arguments
     ///
     /// * `key` - The key associated with the value to be retrieved.
-    pub fn get(&self, key: &K) -> Option<V> {
-        let map = self.map.try_read().expect("failed to lock the cache.");
-        map.get(key).cloned()
+    /// * `max_age` - How long ago the value may have been cached.
+    async fn lookup(&self, key: &K, max_age: Duration) -> Option<V> {
+        let mut inner = self.inner.write().await;
+
+        let entry = inner.map.get(key)?;
+        if self.is_expired(entry) {
+            inner.remove(key);
+            return None;
+        } else if entry.inserted_at.elapsed() >= max_age {
+            return None;
+        }
+
+        inner.touch(key).map(|entry| entry.value.clone())
+    }
+
+    /// Checks whether a value is cached.
+    ///
+    /// # Arguments
+    ///
+    /// * `key` - The key associated with the value.
+    pub async fn contains(&self, key: &K) -> bool {
+        let inner = self.inner.read().await;
+        inner
+            .map
+            .get(key)
+            .is_some_and(|entry| !self.is_expired(entry))
+    }
+
+    /// Gets how long ago a value was cached.
+    ///
+    /// # Arguments
+    ///
+    /// * `key` - The key associated with the value.
+    pub async fn age_of(&self, key: &K) -> Option<Duration> {
+        let inner = self.inner.read().await;
+        inner
+            .map
+            .get(key)
+            .filter(|entry| !self.is_expired(entry))
+            .map(|entry| entry.inserted_at.elapsed())
+    }
+
+    /// Gets the number of cached values, counting the expired ones not purged yet.
+    pub async fn len(&self) -> usize {
+        let inner = self.inner.read().await;
+        inner.map.len()
+    }
+
+    /// Checks whether the cache is empty.
+    pub async fn is_empty(&self) -> bool {
+        self.len().await == 0
+    }
+
+    /// Gets the max size of the cache.
+    pub fn capacity(&self) -> usize {
+        self.capacity
+    }
+
+    /// Gets how long the values live, `None` if they never expire.
+    pub fn ttl(&self) -> Option<Duration> {
+        self.ttl
+    }
+
+    /// Gets how many lookups found a value.
+    pub fn hits(&self) -> u64 {
+        self.hits.load(Ordering::Relaxed)
+    }
+
+    /// Gets how many lookups found nothing, or only a stale value.
+    pub fn misses(&self) -> u64 {
+        self.misses.load(Ordering::Relaxed)
     }
 
     /// Retrieves a value from the cache and removes it.
@@ -54,25 +273,42 @@ where
     /// # Arguments
     ///
     /// * `key` - The key associated with the value to be retrieved.
-    pub fn take(&self, key: &K) -> Option<V> {
-        let mut map = self.map.try_write().expect("failed to lock the cache.");
-        map.remove(key)
+    pub async fn take(&self, key: &K) -> Option<V> {
+        let mut inner = self.inner.write().await;
+        inner
+            .remove(key)
+            .filter(|entry| !self.is_expired(entry))
+            .map(|entry| entry.value)
     }
 
     /// Inserts a value into the cache.
     ///
+    /// Evicts the least recently used value if the cache is full.
+    ///
     /// # Arguments
     ///
     /// * `key` - The key associated with the value to be inserted.
     /// * `value` - The value to be inserted into the cache.
     pub async fn insert(&self, key: K, value: V) {
-        let mut map = self.map.write().await;
+        let mut inner = self.inner.write().await;
 
-        if map.len() >= self.capacity {
-            map.clear();
+        if inner.remove(&key).is_none() && inner.map.len() >= self.capacity {
+            if let Some((_, oldest)) = inner.order.pop_first() {
+                inner.map.remove(&oldest);
+            }
         }
 
-        map.insert(key, value);
+        inner.tick += 1;
+        let used_at = inner.tick;
+        inner.order.insert(used_at, key.clone());
+        inner.map.insert(
+            key,
+            Entry {
+                value,
+                inserted_at: Instant::now(),
+                used_at,
+            },
+        );
     }
 
     /// Removes a value from the cache.
@@ -81,7 +317,47 @@ where
     ///
     /// * `key` - The key associated with the value to be removed.
     pub async fn remove(&self, key: &K) {
-        let mut map = self.map.write().await;
-        map.remove(key);
+        let mut inner = self.inner.write().await;
+        inner.remove(key);
+    }
+
+    /// Removes the expired values, returning how many were removed.
+    pub async fn purge_expired(&self) -> usize {
+        if self.ttl.is_none() {
+            return 0;
+        }
+
+        let mut inner = self.inner.write().await;
+        let expired = inner
+            .map
+            .iter()
+            .filter(|(_, entry)| self.is_expired(entry))
+            .map(|(key, _)| key.clone())
+            .collect::<Vec<_>>();
+        for key in expired.iter() {
+            inner.remove(key);
+        }
+
+        expired.len()
+    }
+
+    /// Removes the values whose key does not match, returning how many were removed.
+    ///
+    /// # Arguments
+    ///
+    /// * `keep` - Whether the value of a key is kept.
+    pub async fn retain(&self, keep: impl Fn(&K) -> bool) -> usize {
+        let mut inner = self.inner.write().await;
+        let removed = inner
+            .map
+            .keys()
+            .filter(|key| !keep(key))
+            .cloned()
+            .collect::<Vec<_>>();
+        for key in removed.iter() {
+            inner.remove(key);
+        }
+
+        removed.len()
     }
 }
@@ -0,0 +1,195 @@
+// Copyright 2025 - Andriel Ferreira
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// https://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or https://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+//! The signatures of the callback data of the buttons.
+//!
+//! A signed callback is its data followed by a space and a token, the base64 of the time it
+//! was issued and a truncated HMAC-SHA256 of that time and the data. The token is only 13
+//! bytes with its space, so the data still fits in Telegram's 64 bytes.
+
+use std::{sync::OnceLock, time::Duration};
+
+use base64::{Engine, engine::general_purpose::URL_SAFE_NO_PAD};
+use chrono::{DateTime, Utc};
+use openssl::{
+    hash::{MessageDigest, hash},
+    memcmp,
+    pkey::{PKey, Private},
+    sign::Signer,
+};
+
+/// The first words of the callbacks that must be signed.
+pub const SIGNED_CALLBACKS: &[&str] = &["anime", "manga", "char", "charimg", "user", "language"];
+
+/// How long a signed callback is accepted after it was issued.
+pub const CALLBACK_LIFETIME: Duration = Duration::from_secs(48 * 60 * 60);
+
+/// The length of the tokens, in base64.
+pub const TOKEN_LENGTH: usize = 12;
+
+/// The length of the truncated HMACs.
+const MAC_LENGTH: usize = 5;
+
+/// The signer of the callbacks, known once the config is loaded.
+static SIGNER: OnceLock<CallbackSigner> = OnceLock::new();
+
+/// Sets the signer of the callbacks. Only the first call has an effect.
+///
+/// # Arguments
+///
+/// * `signer` - The callback signer.
+pub fn set_signer(signer: CallbackSigner) {
+    let _ = SIGNER.set(signer);
+}
+
+/// Gets the signer of the callbacks.
+///
+/// Without a signer set, a random key is used, so the buttons expire when the bot restarts.
+pub fn signer() -> &'static CallbackSigner {
+    SIGNER.get_or_init(CallbackSigner::random)
+}
+
+/// Whether the callbacks with this data must be signed.
+///
+/// # Arguments
+///
+/// * `data` - The callback data.
+pub fn is_signed(data: &str) -> bool {
+    data.split(' ')
+        .next()
+        .is_some_and(|verb| SIGNED_CALLBACKS.contains(&verb))
+}
+
+/// Removes the token of a signed callback, without checking it.
+///
+/// Returns the data unchanged if it has no token.
+///
+/// # Arguments
+///
+/// * `data` - The callback data.
+pub fn strip_token(data: &str) -> &str {
+    match data.rsplit_once(' ') {
+        Some((parts, token)) if is_token(token) => parts,
+        _ => data,
+    }
+}
+
+/// Whether a word looks like a token.
+fn is_token(word: &str) -> bool {
+    word.len() == TOKEN_LENGTH
+        && word
+            .bytes()
+            .all(|byte| byte.is_ascii_alphanumeric() || byte == b'-' || byte == b'_')
+}
+
+/// Why a callback was rejected.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum CallbackError {
+    /// The callback has no token, it was issued before the callbacks were signed.
+    Unsigned,
+    /// The token does not match the data, or was made with another key.
+    BadSignature,
+    /// The callback is older than [`CALLBACK_LIFETIME`].
+    Expired,
+}
+
+/// Signs the callbacks with HMAC-SHA256.
+pub struct CallbackSigner {
+    /// The HMAC key.
+    key: PKey<Private>,
+}
+
+impl CallbackSigner {
+    /// Creates a signer with a key derived from the bot token.
+    ///
+    /// # Arguments
+    ///
+    /// * `bot_token` - The token of the bot.
+    pub fn from_bot_token(bot_token: &str) -> Self {
+        let key = hash(
+            MessageDigest::sha256(),
+            format!("callbacks:{}", bot_token).as_bytes(),
+        )
+        .expect("failed to derive the callback key");
+
+        Self::from_key(&key)
+    }
+
+    /// Creates a signer with a random key.
+    pub fn random() -> Self {
+        Self::from_key(&rand::random::<[u8; 32]>())
+    }
+
+    /// Creates a signer with a raw key.
+    fn from_key(key: &[u8]) -> Self {
+        Self {
+            key: PKey::hmac(key).expect("failed to load the callback key"),
+        }
+    }
+
+    /// Signs the data of a callback, issued at the given time.
+    ///
+    /// # Arguments
+    ///
+    /// * `parts` - The callback data, its parts separated by spaces.
+    /// * `issued_at` - When the callback was issued.
+    pub fn sign(&self, parts: &str, issued_at: DateTime<Utc>) -> String {
+        let timestamp = (issued_at.timestamp().max(0) as u32).to_be_bytes();
+
+        let mut payload = timestamp.to_vec();
+        payload.extend(self.mac(&timestamp, parts));
+
+        format!("{0} {1}", parts, URL_SAFE_NO_PAD.encode(payload))
+    }
+
+    /// Verifies a callback signed by [`CallbackSigner::sign`].
+    ///
+    /// Returns the data without its token.
+    ///
+    /// # Arguments
+    ///
+    /// * `data` - The signed callback data.
+    /// * `now` - The current time.
+    pub fn verify<'a>(&self, data: &'a str, now: DateTime<Utc>) -> Result<&'a str, CallbackError> {
+        let (parts, token) = data
+            .rsplit_once(' ')
+            .filter(|(_, token)| is_token(token))
+            .ok_or(CallbackError::Unsigned)?;
+        let payload = URL_SAFE_NO_PAD
+            .decode(token)
+            .map_err(|_| CallbackError::Unsigned)?;
+
+        let (timestamp, mac) = payload.split_at(4);
+        if !memcmp::eq(mac, &self.mac(timestamp, parts)) {
+            return Err(CallbackError::BadSignature);
+        }
+
+        let issued_at = u32::from_be_bytes(timestamp.try_into().unwrap());
+        let age = now.timestamp() - i64::from(issued_at);
+        if age > CALLBACK_LIFETIME.as_secs() as i64 {
+            return Err(CallbackError::Expired);
+        }
+
+        Ok(parts)
+    }
+
+    /// Computes the truncated HMAC of a timestamp and the callback data.
+    fn mac(&self, timestamp: &[u8], parts: &str) -> Vec<u8> {
+        let mut signer =
+            Signer::new(MessageDigest::sha256(), &self.key).expect("failed to sign a callback");
+        signer
+            .update(timestamp)
+            .and_then(|_| signer.update(parts.as_bytes()))
+            .expect("failed to sign a callback");
+
+        let mut mac = signer.sign_to_vec().expect("failed to sign a callback");
+        mac.truncate(MAC_LENGTH);
+
+        mac
+    }
+}
@@ -0,0 +1,57 @@
+// Copyright 2025 - Andriel Ferreira
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// https://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or https://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+//! The cards resource.
+
+use crate::resources::{Database, Favorites, MediaFiles};
+
+/// Cards module.
+///
+/// Groups the resources the cards are sent with, so the handlers that send cards take them
+/// at once.
+#[derive(Clone, Debug)]
+pub struct Cards {
+    /// The database, for the history of the viewers.
+    db: Database,
+    /// Where the images uploaded by the cards are remembered.
+    files: MediaFiles,
+    /// The favorites the cards show their star from.
+    favorites: Favorites,
+}
+
+impl Cards {
+    /// Creates a new instance of the cards.
+    ///
+    /// # Arguments
+    ///
+    /// * `db` - The database resource.
+    /// * `files` - The media files resource.
+    /// * `favorites` - The favorites resource.
+    pub fn new(db: Database, files: MediaFiles, favorites: Favorites) -> Self {
+        Self {
+            db,
+            files,
+            favorites,
+        }
+    }
+
+    /// Gets the database, for the history of the viewers.
+    pub fn db(&self) -> &Database {
+        &self.db
+    }
+
+    /// Gets where the images uploaded by the cards are remembered.
+    pub fn files(&self) -> &MediaFiles {
+        &self.files
+    }
+
+    /// Gets the favorites the cards show their star from.
+    pub fn favorites(&self) -> &Favorites {
+        &self.favorites
+    }
+}
@@ -0,0 +1,178 @@
+// Copyright 2025 - Andriel Ferreira
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// https://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or https://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+//! The correlations resource.
+
+use std::{fmt, time::Duration};
+
+use grammers_client::Update;
+
+use crate::resources::Cache;
+
+/// How long the ID of an update is kept for the error handler.
+pub const CORRELATION_TTL: Duration = Duration::from_secs(10 * 60);
+
+/// A short ID grouping the log lines of an update, e.g. `ab12cd`.
+///
+/// It is stored in the injector, so the handlers can take it to tag their own log lines.
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct CorrelationId(pub String);
+
+impl Default for CorrelationId {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl CorrelationId {
+    /// Creates a new random ID.
+    pub fn new() -> Self {
+        Self(format!("{:06x}", rand::random::<u32>() & 0xff_ffff))
+    }
+}
+
+impl fmt::Display for CorrelationId {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.0)
+    }
+}
+
+/// What the error handler knows of an update being handled.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct Correlation {
+    /// The ID of the update.
+    pub id: CorrelationId,
+    /// The locale of the chat, once it is loaded.
+    pub locale: Option<String>,
+}
+
+/// What tells an update apart from the others, as seen by the middlewares and the error
+/// handler alike.
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub enum UpdateKey {
+    /// A new or edited message.
+    Message {
+        /// The ID of the chat.
+        chat_id: i64,
+        /// The ID of the message.
+        message_id: i32,
+        /// Whether the message was edited.
+        edited: bool,
+    },
+    /// A callback query.
+    CallbackQuery {
+        /// The ID of the chat.
+        chat_id: i64,
+        /// The ID of the user that pressed the button.
+        sender_id: i64,
+        /// The data of the button.
+        data: Vec<u8>,
+    },
+    /// An inline query.
+    InlineQuery {
+        /// The ID of the user that sent the query.
+        sender_id: i64,
+        /// The text of the query.
+        text: String,
+        /// The offset of the query.
+        offset: String,
+    },
+}
+
+impl UpdateKey {
+    /// Gets the key of an update, `None` for the kinds the bot does not handle.
+    ///
+    /// # Arguments
+    ///
+    /// * `update` - The update.
+    pub fn from_update(update: &Update) -> Option<Self> {
+        match update {
+            Update::NewMessage(message) | Update::MessageEdited(message) => Some(Self::Message {
+                chat_id: message.chat().id(),
+                message_id: message.id(),
+                edited: matches!(update, Update::MessageEdited(_)),
+            }),
+            Update::CallbackQuery(query) => Some(Self::CallbackQuery {
+                chat_id: query.chat().id(),
+                sender_id: query.sender().id(),
+                data: query.data().to_vec(),
+            }),
+            Update::InlineQuery(query) => Some(Self::InlineQuery {
+                sender_id: query.sender().id(),
+                text: query.text().to_string(),
+                offset: query.offset().to_string(),
+            }),
+            _ => None,
+        }
+    }
+}
+
+/// Correlations module.
+///
+/// Remembers the ID and the chat locale of each update being handled, for the error handler,
+/// which cannot reach the injector.
+#[derive(Clone, Debug)]
+pub struct Correlations {
+    /// The correlation of each update being handled.
+    correlations: Cache<UpdateKey, Correlation>,
+}
+
+impl Default for Correlations {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Correlations {
+    /// Creates a new instance of the correlations.
+    pub fn new() -> Self {
+        Self {
+            correlations: Cache::with_capacity_and_ttl(1000, CORRELATION_TTL),
+        }
+    }
+
+    /// Assigns a new ID to an update.
+    ///
+    /// # Arguments
+    ///
+    /// * `key` - The key of the update, the ID is not remembered without it.
+    pub async fn assign(&self, key: Option<UpdateKey>) -> CorrelationId {
+        let id = CorrelationId::new();
+        if let Some(key) = key {
+            let correlation = Correlation {
+                id: id.clone(),
+                locale: None,
+            };
+            self.correlations.insert(key, correlation).await;
+        }
+
+        id
+    }
+
+    /// Remembers the locale of the chat of an update.
+    ///
+    /// # Arguments
+    ///
+    /// * `key` - The key of the update.
+    /// * `locale` - The locale of the chat.
+    pub async fn set_locale(&self, key: &UpdateKey, locale: &str) {
+        if let Some(mut correlation) = self.correlations.get(key).await {
+            correlation.locale = Some(locale.to_string());
+            self.correlations.insert(key.clone(), correlation).await;
+        }
+    }
+
+    /// Takes the correlation of an update, once it was handled.
+    ///
+    /// # Arguments
+    ///
+    /// * `key` - The key of the update.
+    pub async fn take(&self, key: &UpdateKey) -> Option<Correlation> {
+        self.correlations.take(key).await
+    }
+}
@@ -8,89 +8,434 @@
 
 //! The database resource.
 
-use std::path::Path;
+use std::{
+    sync::{
+        Arc, Mutex,
+        atomic::{AtomicBool, Ordering},
+    },
+    time::{Duration, Instant},
+};
 
 use ferogram::Result;
-use sqlx::{PgPool, migrate::Migrator};
-use tokio::fs::read_dir;
+use sqlx::{
+    PgPool, SqlitePool,
+    migrate::Migrator,
+    postgres::PgPoolOptions,
+    sqlite::{SqliteConnectOptions, SqliteJournalMode, SqlitePoolOptions},
+};
 
-/// Where the migrations are located.
-const MIGRATIONS_PATH: &str = "./assets/migrations/";
+/// The Postgres migrations, embedded at compile time from `assets/migrations/postgres`.
+pub static POSTGRES_MIGRATOR: Migrator = sqlx::migrate!("./assets/migrations/postgres");
+
+/// The SQLite migrations, embedded at compile time from `assets/migrations/sqlite`.
+pub static SQLITE_MIGRATOR: Migrator = sqlx::migrate!("./assets/migrations/sqlite");
+
+/// The minimum interval between two "database is down" warnings.
+const WARNING_INTERVAL: Duration = Duration::from_secs(60);
+
+/// The longest wait between two connection attempts at startup.
+pub const MAX_RETRY_DELAY: Duration = Duration::from_secs(30);
+
+/// How the database pool connects.
+#[derive(Clone, Debug)]
+pub struct PoolSettings {
+    /// The most connections the pool keeps open.
+    pub max_connections: u32,
+    /// How long to wait for a connection before giving up.
+    ///
+    /// Kept short so updates don't hang while the database is down.
+    pub acquire_timeout: Duration,
+    /// For how long the first connection is retried before giving up.
+    pub connect_retry: Duration,
+}
+
+/// The backend of a database.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Backend {
+    /// A Postgres server, for `postgres://` URLs.
+    Postgres,
+    /// A SQLite file, for `sqlite:` URLs, e.g. `sqlite://bot.db` or `sqlite::memory:`.
+    Sqlite,
+}
+
+impl Backend {
+    /// Gets the backend of a database from the scheme of its URL.
+    ///
+    /// Returns `None` if the scheme is not supported.
+    ///
+    /// # Arguments
+    ///
+    /// * `database_url` - The connection string.
+    pub fn from_url(database_url: &str) -> Option<Self> {
+        let (scheme, _) = database_url.split_once(':')?;
+
+        match scheme {
+            "postgres" | "postgresql" => Some(Self::Postgres),
+            "sqlite" => Some(Self::Sqlite),
+            _ => None,
+        }
+    }
+
+    /// Gets the migrations embedded for the backend.
+    pub fn migrator(&self) -> &'static Migrator {
+        match self {
+            Self::Postgres => &POSTGRES_MIGRATOR,
+            Self::Sqlite => &SQLITE_MIGRATOR,
+        }
+    }
+}
+
+/// The pool of a database, of either backend.
+///
+/// The queries run on it through [`with_pool!`], or match on it when they are not portable.
+#[derive(Clone, Debug)]
+pub enum Pool {
+    /// A Postgres pool.
+    Postgres(PgPool),
+    /// A SQLite pool.
+    Sqlite(SqlitePool),
+}
+
+impl Pool {
+    /// Connects a pool to a database, without retrying.
+    ///
+    /// # Arguments
+    ///
+    /// * `database_url` - The connection string.
+    /// * `settings` - How the pool connects.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the URL is not supported or the connection fails.
+    pub async fn connect(database_url: &str, settings: &PoolSettings) -> sqlx::Result<Self> {
+        match Backend::from_url(database_url) {
+            Some(Backend::Postgres) => PgPoolOptions::new()
+                .max_connections(settings.max_connections)
+                .acquire_timeout(settings.acquire_timeout)
+                .connect(database_url)
+                .await
+                .map(Self::Postgres),
+            Some(Backend::Sqlite) => {
+                let options = database_url
+                    .parse::<SqliteConnectOptions>()?
+                    .create_if_missing(true);
+
+                // An in-memory database is gone once its last connection closes.
+                let pool = if database_url.contains(":memory:") {
+                    SqlitePoolOptions::new()
+                        .max_connections(1)
+                        .idle_timeout(None)
+                        .max_lifetime(None)
+                } else {
+                    SqlitePoolOptions::new().max_connections(settings.max_connections)
+                };
+
+                pool.acquire_timeout(settings.acquire_timeout)
+                    .connect_with(options.journal_mode(SqliteJournalMode::Wal))
+                    .await
+                    .map(Self::Sqlite)
+            }
+            None => Err(sqlx::Error::Configuration(
+                format!(
+                    "unsupported database URL scheme, expected postgres:// or sqlite: in {:?}",
+                    database_url
+                        .split_once(':')
+                        .map_or(database_url, |(scheme, _)| scheme)
+                )
+                .into(),
+            )),
+        }
+    }
+
+    /// Gets the backend of the pool.
+    pub fn backend(&self) -> Backend {
+        match self {
+            Self::Postgres(_) => Backend::Postgres,
+            Self::Sqlite(_) => Backend::Sqlite,
+        }
+    }
+}
+
+/// Runs an expression on the inner pool of a [`Pool`], whatever its backend.
+///
+/// The expression is compiled once per backend, so its queries must be valid in both.
+///
+/// ```ignore
+/// with_pool!(pool, |pool| sqlx::query("SELECT 1").execute(pool).await.map(|_| ()))
+/// ```
+macro_rules! with_pool {
+    ($pool:expr, |$inner:ident| $body:expr) => {
+        match $pool {
+            $crate::resources::database::Pool::Postgres($inner) => $body,
+            $crate::resources::database::Pool::Sqlite($inner) => $body,
+        }
+    };
+}
+
+pub(crate) use with_pool;
 
 /// Database module.
-#[derive(Clone)]
+#[derive(Clone, Debug)]
 pub struct Database {
     /// The database pool.
-    pool: PgPool,
+    pool: Pool,
+    /// Whether the last query reached the database.
+    healthy: Arc<AtomicBool>,
+    /// When the last "database is down" warning was logged.
+    last_warning: Arc<Mutex<Option<Instant>>>,
 }
 
 impl Database {
-    /// Connects to the database.
+    /// Connects to the database, retrying with an exponential backoff while it is
+    /// unreachable.
     ///
     /// # Arguments
     ///
     /// * `database_url` - The connection string.
-    pub async fn connect(database_url: &str) -> Self {
+    /// * `settings` - How the pool connects.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the database was still unreachable after `settings.connect_retry`,
+    /// or refused the connection, e.g. for wrong credentials.
+    pub async fn connect(database_url: &str, settings: &PoolSettings) -> sqlx::Result<Self> {
         log::info!("connecting to the database...");
 
-        let pool = PgPool::connect(database_url)
-            .await
-            .expect("failed to connect to the database.");
+        let started_at = Instant::now();
+        let mut attempt = 0;
+        let pool = loop {
+            match Pool::connect(database_url, settings).await {
+                Ok(pool) => break pool,
+                Err(e)
+                    if is_connection_error(&e)
+                        && started_at.elapsed() + retry_delay(attempt) < settings.connect_retry =>
+                {
+                    let delay = retry_delay(attempt);
+                    log::warn!(
+                        "failed to connect to the database, retrying in {:?}: {}",
+                        delay,
+                        e
+                    );
+
+                    tokio::time::sleep(delay).await;
+                    attempt += 1;
+                }
+                Err(e) => {
+                    log::error!(
+                        "giving up connecting to the database after {} attempts in {:?}: {}",
+                        attempt + 1,
+                        started_at.elapsed(),
+                        e
+                    );
+                    return Err(e);
+                }
+            }
+        };
 
-        log::info!("database connected");
+        log::info!("{:?} database connected", pool.backend());
 
-        Self { pool }
+        Ok(Self {
+            pool,
+            healthy: Arc::new(AtomicBool::new(true)),
+            last_warning: Arc::new(Mutex::new(None)),
+        })
     }
 
     /// Gets the database pool.
-    pub fn pool(&self) -> &PgPool {
+    pub fn pool(&self) -> &Pool {
         &self.pool
     }
 
+    /// Whether the database was reachable the last time it was queried.
+    pub fn is_healthy(&self) -> bool {
+        self.healthy.load(Ordering::Relaxed)
+    }
+
+    /// Updates the health flag from the result of a query and passes the result through.
+    ///
+    /// Connection errors mark the database as down and are logged at most once per minute,
+    /// any other result marks it as up again.
+    ///
+    /// # Arguments
+    ///
+    /// * `result` - The result of the query.
+    pub fn report<T>(&self, result: sqlx::Result<T>) -> sqlx::Result<T> {
+        match &result {
+            Err(e) if is_connection_error(e) => {
+                self.healthy.store(false, Ordering::Relaxed);
+
+                let mut last_warning = self.last_warning.lock().unwrap();
+                if last_warning.is_none_or(|instant| instant.elapsed() >= WARNING_INTERVAL) {
+                    *last_warning = Some(Instant::now());
+                    log::error!("the database is unavailable: {:?}", e);
+                }
+            }
+            _ => {
+                if !self.healthy.swap(true, Ordering::Relaxed) {
+                    log::info!("the database is available again");
+                }
+            }
+        }
+
+        result
+    }
+
+    /// Checks whether the database is reachable, updating the health flag.
+    ///
+    /// Returns how long the database took to answer, `None` if it is unreachable.
+    pub async fn health_check(&self) -> Option<Duration> {
+        let started_at = Instant::now();
+
+        let result = with_pool!(&self.pool, |pool| {
+            sqlx::query("SELECT 1").execute(pool).await.map(|_| ())
+        });
+
+        self.report(result).ok().map(|_| started_at.elapsed())
+    }
+
     /// Migrates the database.
     ///
-    /// Search for migrations in the `assets/migrations` folder.
+    /// Runs the migrations embedded in the binary for the backend, or the ones in `path` if it
+    /// is set.
+    ///
+    /// # Arguments
+    ///
+    /// * `path` - The folder to read the migrations from instead, for development.
     ///
     /// # Errors
     ///
-    /// Returns an error if the migration fails.
-    pub async fn migrate(&self) -> Result<()> {
-        log::debug!("searching migrations from: {:?}", MIGRATIONS_PATH);
+    /// Returns an error if the migrations could not be read or the migration fails.
+    pub async fn migrate(&self, path: Option<&str>) -> Result<()> {
+        let loaded;
+        let migrator = match path {
+            Some(path) => {
+                let path = std::path::absolute(path)?;
+                log::debug!("searching migrations from: {:?}", path);
 
-        let mut dir = read_dir(MIGRATIONS_PATH)
-            .await
-            .expect("failed to read migrations directory");
-        let mut files = Vec::new();
+                loaded = Migrator::new(path.as_path()).await?;
+                if loaded.iter().next().is_none() {
+                    log::warn!("no migrations found in {:?}", path);
+                }
 
-        while let Some(child) = dir.next_entry().await? {
-            files.push(child);
-        }
+                &loaded
+            }
+            None => self.pool.backend().migrator(),
+        };
 
-        if files.is_empty() {
-            log::warn!("no migrations found");
-            return Ok(());
-        } else {
-            log::debug!(
-                "found migrations: {}",
-                files
-                    .into_iter()
-                    .map(|entry| entry
-                        .file_name()
-                        .into_string()
-                        .expect("failed to parse file name"))
-                    .collect::<Vec<_>>()
-                    .join(", ")
-            );
-        }
+        log::debug!(
+            "found migrations: {}",
+            migrator
+                .iter()
+                .map(|migration| format!("{}_{}", migration.version, migration.description))
+                .collect::<Vec<_>>()
+                .join(", ")
+        );
+
+        self.rename_legacy_versions(migrator).await?;
 
         log::debug!("migrating the database...");
 
-        let migrator = Migrator::new(Path::new(MIGRATIONS_PATH)).await?;
-        let result = migrator.run(&self.pool).await.map_err(Into::into);
+        let result = with_pool!(&self.pool, |pool| migrator.run(pool).await).map_err(Into::into);
         if result.is_ok() {
             log::debug!("database migrated");
         }
 
         result
     }
+
+    /// Renames the versions of the migrations applied before they were named
+    /// `YYYYMMDDHHMMSS`, see [`legacy_version`], so sqlx finds them applied.
+    ///
+    /// # Arguments
+    ///
+    /// * `migrator` - The migrations about to run.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if a query fails.
+    async fn rename_legacy_versions(&self, migrator: &Migrator) -> sqlx::Result<()> {
+        let query = match self.pool.backend() {
+            Backend::Postgres => "SELECT to_regclass('_sqlx_migrations') IS NOT NULL",
+            Backend::Sqlite => {
+                "SELECT EXISTS (SELECT 1 FROM sqlite_master WHERE type = 'table' AND name = '_sqlx_migrations')"
+            }
+        };
+
+        let migrated: bool = with_pool!(&self.pool, |pool| sqlx::query_scalar(query)
+            .fetch_one(pool)
+            .await)?;
+        if !migrated {
+            return Ok(());
+        }
+
+        for migration in migrator.iter() {
+            let Some(legacy) = legacy_version(migration.version) else {
+                continue;
+            };
+
+            let renamed = with_pool!(&self.pool, |pool| {
+                sqlx::query("UPDATE _sqlx_migrations SET version = $1 WHERE version = $2")
+                    .bind(migration.version)
+                    .bind(legacy)
+                    .execute(pool)
+                    .await
+                    .map(|result| result.rows_affected())
+            })?;
+            if renamed > 0 {
+                log::info!(
+                    "renamed the applied migration {} to {}",
+                    legacy,
+                    migration.version
+                );
+            }
+        }
+
+        Ok(())
+    }
+}
+
+/// Gets the version a migration was applied with when it was still named after its day, as
+/// `DDMMYYYY_HHMMSS`.
+///
+/// sqlx only reads the digits before the first underscore, so those names ran out of order.
+/// Returns `None` for versions that are not `YYYYMMDDHHMMSS`.
+///
+/// # Arguments
+///
+/// * `version` - The version of the migration, e.g. `20261101100000`.
+pub fn legacy_version(version: i64) -> Option<i64> {
+    if !(10_000_000_000_000..100_000_000_000_000).contains(&version) {
+        return None;
+    }
+
+    let date = version / 1_000_000;
+    let (year, month, day) = (date / 10_000, date / 100 % 100, date % 100);
+
+    Some(day * 1_000_000 + month * 10_000 + year)
+}
+
+/// Gets how long to wait before the next attempt to connect, doubling from one second up
+/// to [`MAX_RETRY_DELAY`].
+///
+/// # Arguments
+///
+/// * `attempt` - The number of the attempt that failed, from zero.
+pub fn retry_delay(attempt: u32) -> Duration {
+    Duration::from_secs(1u64 << attempt.min(16)).min(MAX_RETRY_DELAY)
+}
+
+/// Whether an error means the database could not be reached, as opposed to a failed query.
+///
+/// # Arguments
+///
+/// * `error` - The error to check.
+pub fn is_connection_error(error: &sqlx::Error) -> bool {
+    matches!(
+        error,
+        sqlx::Error::Io(_)
+            | sqlx::Error::Tls(_)
+            | sqlx::Error::PoolTimedOut
+            | sqlx::Error::PoolClosed
+            | sqlx::Error::WorkerCrashed
+    )
 }
@@ -0,0 +1,67 @@
+// Copyright 2025 - Andriel Ferreira
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// https://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or https://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+//! The debounce resource.
+
+use std::{
+    hash::Hash,
+    sync::{
+        Arc,
+        atomic::{AtomicU64, Ordering},
+    },
+    time::Duration,
+};
+
+use crate::resources::Cache;
+
+/// Debounce module.
+///
+/// Lets only the last of a burst of actions for the same key go through, e.g. the inline
+/// queries sent while a user is still typing.
+#[derive(Clone, Debug)]
+pub struct Debounce<K> {
+    /// The ticket of the last action of each key.
+    latest: Cache<K, u64>,
+    /// The counter the actions are numbered with.
+    tickets: Arc<AtomicU64>,
+    /// How long to wait for a newer action before letting one go through.
+    delay: Duration,
+}
+
+impl<K> Debounce<K>
+where
+    K: Eq + Hash + Clone,
+{
+    /// Creates a new instance of the debounce.
+    ///
+    /// # Arguments
+    ///
+    /// * `capacity` - The max number of keys to remember.
+    /// * `delay` - How long to wait for a newer action before letting one go through.
+    pub fn new(capacity: usize, delay: Duration) -> Self {
+        Self {
+            latest: Cache::with_capacity(capacity),
+            tickets: Arc::new(AtomicU64::new(0)),
+            delay,
+        }
+    }
+
+    /// Waits for the delay, returning whether no newer action arrived for the key meanwhile.
+    ///
+    /// # Arguments
+    ///
+    /// * `key` - The key of the action.
+    pub async fn settle(&self, key: K) -> bool {
+        let ticket = self.tickets.fetch_add(1, Ordering::Relaxed);
+        self.latest.insert(key.clone(), ticket).await;
+
+        tokio::time::sleep(self.delay).await;
+
+        self.latest.get(&key).await == Some(ticket)
+    }
+}
@@ -0,0 +1,133 @@
+// Copyright 2025 - Andriel Ferreira
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// https://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or https://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+//! The diagnostics resource.
+
+use std::time::Duration;
+
+use tokio::time::Instant;
+
+use crate::resources::Cache;
+
+/// How long the permission probes are reused.
+pub const PROBE_TTL: Duration = Duration::from_secs(60);
+
+/// The messages the bot received from a chat.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct ChatActivity {
+    /// How many commands were received.
+    pub commands: u64,
+    /// How many messages that are not commands were received.
+    pub plain_messages: u64,
+}
+
+impl ChatActivity {
+    /// Whether the bot looks like it only receives commands, i.e. privacy mode is on.
+    ///
+    /// Returns `None` while too few messages were received to tell.
+    pub fn privacy_mode(&self) -> Option<bool> {
+        if self.plain_messages > 0 {
+            Some(false)
+        } else if self.commands >= 3 {
+            Some(true)
+        } else {
+            None
+        }
+    }
+}
+
+/// What the bot is allowed to do in a chat.
+///
+/// Each field is `None` when it could not be probed.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct BotPermissions {
+    /// Whether the bot can send messages, and so inline keyboards.
+    pub send_messages: Option<bool>,
+    /// Whether the bot can send photos and other media.
+    pub send_media: Option<bool>,
+    /// Whether the bot can send link previews.
+    pub embed_links: Option<bool>,
+}
+
+/// Diagnostics module.
+///
+/// Remembers what the bot sees of each chat, to help admins find why it does not answer.
+#[derive(Clone, Debug)]
+pub struct Diagnostics {
+    /// The messages received from each chat.
+    activity: Cache<i64, ChatActivity>,
+    /// The last permission probe of each chat, and when it was made.
+    permissions: Cache<i64, (Instant, BotPermissions)>,
+}
+
+impl Default for Diagnostics {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Diagnostics {
+    /// Creates a new instance of the diagnostics.
+    pub fn new() -> Self {
+        Self {
+            activity: Cache::with_capacity(5000),
+            permissions: Cache::with_capacity(500),
+        }
+    }
+
+    /// Records a message received from a chat.
+    ///
+    /// # Arguments
+    ///
+    /// * `chat_id` - The chat ID.
+    /// * `is_command` - Whether the message is a command.
+    pub async fn record_message(&self, chat_id: i64, is_command: bool) {
+        let mut activity = self.activity.get(&chat_id).await.unwrap_or_default();
+        if is_command {
+            activity.commands += 1;
+        } else {
+            activity.plain_messages += 1;
+        }
+
+        self.activity.insert(chat_id, activity).await;
+    }
+
+    /// Gets the messages received from a chat.
+    ///
+    /// # Arguments
+    ///
+    /// * `chat_id` - The chat ID.
+    pub async fn activity(&self, chat_id: i64) -> ChatActivity {
+        self.activity.get(&chat_id).await.unwrap_or_default()
+    }
+
+    /// Gets the last permission probe of a chat, if it is recent enough.
+    ///
+    /// # Arguments
+    ///
+    /// * `chat_id` - The chat ID.
+    pub async fn permissions(&self, chat_id: i64) -> Option<BotPermissions> {
+        self.permissions
+            .get(&chat_id)
+            .await
+            .filter(|(probed_at, _)| probed_at.elapsed() < PROBE_TTL)
+            .map(|(_, permissions)| permissions)
+    }
+
+    /// Stores a permission probe of a chat.
+    ///
+    /// # Arguments
+    ///
+    /// * `chat_id` - The chat ID.
+    /// * `permissions` - The probed permissions.
+    pub async fn store_permissions(&self, chat_id: i64, permissions: BotPermissions) {
+        self.permissions
+            .insert(chat_id, (Instant::now(), permissions))
+            .await;
+    }
+}
@@ -0,0 +1,144 @@
+// Copyright 2025 - Andriel Ferreira
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// https://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or https://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+//! The disabled commands resource.
+
+use std::time::Duration;
+
+use crate::{
+    models::{Group, UpdateGroup},
+    resources::{Cache, Database},
+};
+
+/// How long the disabled commands of a group are reused before asking the database again.
+pub const DISABLED_COMMANDS_TTL: Duration = Duration::from_secs(10 * 60);
+
+/// Disabled commands module.
+///
+/// Tells which commands each group turned off, caching them so every command does not
+/// query the database.
+#[derive(Clone, Debug)]
+pub struct DisabledCommands {
+    /// The commands turned off in each group.
+    cache: Cache<i64, Vec<String>>,
+}
+
+impl Default for DisabledCommands {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl DisabledCommands {
+    /// Creates a new instance of the disabled commands.
+    pub fn new() -> Self {
+        Self {
+            cache: Cache::with_capacity_and_ttl(10000, DISABLED_COMMANDS_TTL),
+        }
+    }
+
+    /// Gets the commands turned off in a group.
+    ///
+    /// No command is considered turned off while the database is unavailable.
+    ///
+    /// # Arguments
+    ///
+    /// * `db` - The database resource.
+    /// * `chat_id` - The group's ID.
+    pub async fn get(&self, db: &Database, chat_id: i64) -> Vec<String> {
+        if let Some(commands) = self.cache.get(&chat_id).await {
+            return commands;
+        }
+        if !db.is_healthy() {
+            return Vec::new();
+        }
+
+        match db.report(Group::get_by_id(db.pool(), &chat_id).await) {
+            Ok(group) => {
+                let commands = group.map_or_else(Vec::new, |group| group.disabled_command_list());
+                self.cache.insert(chat_id, commands.clone()).await;
+
+                commands
+            }
+            Err(e) => {
+                log::warn!(
+                    "failed to get the disabled commands of {}: {:?}",
+                    chat_id,
+                    e
+                );
+
+                Vec::new()
+            }
+        }
+    }
+
+    /// Whether a command is turned off in a group.
+    ///
+    /// # Arguments
+    ///
+    /// * `db` - The database resource.
+    /// * `chat_id` - The group's ID.
+    /// * `command` - The command, without the slash.
+    pub async fn is_disabled(&self, db: &Database, chat_id: i64, command: &str) -> bool {
+        self.get(db, chat_id)
+            .await
+            .iter()
+            .any(|disabled| disabled == command)
+    }
+
+    /// Turns a command on or off in a group.
+    ///
+    /// Returns the commands turned off in the group, `None` if the group is not registered.
+    ///
+    /// # Arguments
+    ///
+    /// * `db` - The database resource.
+    /// * `chat_id` - The group's ID.
+    /// * `command` - The command, without the slash.
+    /// * `enabled` - Whether the command is turned on.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if a query fails.
+    pub async fn set(
+        &self,
+        db: &Database,
+        chat_id: i64,
+        command: &str,
+        enabled: bool,
+    ) -> sqlx::Result<Option<Vec<String>>> {
+        let Some(group) = db.report(Group::get_by_id(db.pool(), &chat_id).await)? else {
+            self.invalidate(chat_id).await;
+            return Ok(None);
+        };
+
+        let mut commands = group.disabled_command_list();
+        commands.retain(|disabled| disabled != command);
+        if !enabled {
+            commands.push(command.to_string());
+            commands.sort();
+        }
+
+        let mut update_group: UpdateGroup = group.into();
+        update_group.set_disabled_commands(&commands);
+        db.report(update_group.update(db.pool()).await)?;
+
+        self.cache.insert(chat_id, commands.clone()).await;
+
+        Ok(Some(commands))
+    }
+
+    /// Forgets the commands cached for a group, they are read again on the next command.
+    ///
+    /// # Arguments
+    ///
+    /// * `chat_id` - The group's ID.
+    pub async fn invalidate(&self, chat_id: i64) {
+        self.cache.remove(&chat_id).await;
+    }
+}
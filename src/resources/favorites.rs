@@ -0,0 +1,180 @@
+// Copyright 2025 - Andriel Ferreira
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// https://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or https://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+//! The favorites resource.
+
+use std::time::Duration;
+
+use crate::{
+    models::{EntryKind, Favorite},
+    resources::{Cache, Database},
+};
+
+/// How many entries a user can bookmark.
+pub const MAX_FAVORITES: i64 = 200;
+
+/// How long whether an entry is bookmarked is remembered.
+pub const STATE_TTL: Duration = Duration::from_secs(10 * 60);
+
+/// What toggling a bookmark did.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Toggled {
+    /// The entry was bookmarked.
+    Added,
+    /// The bookmark was removed.
+    Removed,
+    /// The user already has [`MAX_FAVORITES`] bookmarks.
+    LimitReached,
+}
+
+/// Favorites module.
+///
+/// Keeps the entries the users bookmarked in the bot, remembering which ones are bookmarked
+/// so the cards show their star without a query each time.
+#[derive(Clone, Debug)]
+pub struct Favorites {
+    /// Whether each entry is bookmarked, by user, kind and ID.
+    states: Cache<(i64, EntryKind, i64), bool>,
+    /// The database where the bookmarks are stored.
+    db: Database,
+}
+
+impl Favorites {
+    /// Creates a new instance of the favorites.
+    ///
+    /// # Arguments
+    ///
+    /// * `db` - The database resource.
+    pub fn new(db: Database) -> Self {
+        Self {
+            states: Cache::with_capacity_and_ttl(5000, STATE_TTL),
+            db,
+        }
+    }
+
+    /// Checks whether a user bookmarked an entry, `false` if the database is unavailable.
+    ///
+    /// # Arguments
+    ///
+    /// * `user_id` - The Telegram user ID.
+    /// * `kind` - The kind of the entry.
+    /// * `id` - The AniList ID of the entry.
+    pub async fn contains(&self, user_id: i64, kind: EntryKind, id: i64) -> bool {
+        let key = (user_id, kind, id);
+        if let Some(state) = self.states.get(&key).await {
+            return state;
+        }
+
+        if !self.db.is_healthy() {
+            return false;
+        }
+
+        match self
+            .db
+            .report(Favorite::exists(self.db.pool(), user_id, kind, id).await)
+        {
+            Ok(state) => {
+                self.states.insert(key, state).await;
+                state
+            }
+            Err(e) => {
+                log::warn!(
+                    "failed to check whether {} bookmarked {} {}: {:?}",
+                    user_id,
+                    kind.as_str(),
+                    id,
+                    e
+                );
+                false
+            }
+        }
+    }
+
+    /// Bookmarks an entry, or removes its bookmark if it was already bookmarked.
+    ///
+    /// # Arguments
+    ///
+    /// * `user_id` - The Telegram user ID.
+    /// * `kind` - The kind of the entry.
+    /// * `id` - The AniList ID of the entry.
+    /// * `title` - The title of the entry, shown by `/favorites`.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if a query fails.
+    pub async fn toggle(
+        &self,
+        user_id: i64,
+        kind: EntryKind,
+        id: i64,
+        title: &str,
+    ) -> sqlx::Result<Toggled> {
+        let pool = self.db.pool();
+
+        let toggled = if self
+            .db
+            .report(Favorite::remove(pool, user_id, kind, id).await)?
+        {
+            Toggled::Removed
+        } else if self.db.report(Favorite::count(pool, user_id).await)? >= MAX_FAVORITES {
+            Toggled::LimitReached
+        } else {
+            self.db
+                .report(Favorite::add(pool, user_id, kind, id, title).await)?;
+            Toggled::Added
+        };
+
+        self.states
+            .insert((user_id, kind, id), toggled == Toggled::Added)
+            .await;
+
+        Ok(toggled)
+    }
+
+    /// Removes a bookmark.
+    ///
+    /// # Arguments
+    ///
+    /// * `user_id` - The Telegram user ID.
+    /// * `kind` - The kind of the entry.
+    /// * `id` - The AniList ID of the entry.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the query fails.
+    pub async fn remove(&self, user_id: i64, kind: EntryKind, id: i64) -> sqlx::Result<()> {
+        self.db
+            .report(Favorite::remove(self.db.pool(), user_id, kind, id).await)?;
+        self.states.insert((user_id, kind, id), false).await;
+
+        Ok(())
+    }
+
+    /// Gets the bookmarks of a user, the oldest first.
+    ///
+    /// # Arguments
+    ///
+    /// * `user_id` - The Telegram user ID.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the query fails.
+    pub async fn list(&self, user_id: i64) -> sqlx::Result<Vec<Favorite>> {
+        self.db
+            .report(Favorite::get_by_user(self.db.pool(), user_id).await)
+    }
+
+    /// Forgets the bookmarks of a user known from memory, e.g. after their data was deleted.
+    ///
+    /// # Arguments
+    ///
+    /// * `user_id` - The Telegram user ID.
+    pub async fn forget_user(&self, user_id: i64) {
+        self.states.retain(|(id, _, _)| *id != user_id).await;
+    }
+}
@@ -8,25 +8,33 @@
 
 //! The internationalization (i18n) resource.
 
-use std::{collections::HashMap, sync::Arc};
+use std::{
+    collections::{HashMap, HashSet},
+    path::Path,
+    sync::{Arc, Mutex},
+};
 
 use ferogram::Result;
 use serde_json::Value;
-use tokio::sync::Mutex;
 
 /// The path to the locales directory.
 const PATH: &str = "./assets/locales/";
 
 /// Internationalization module.
+///
+/// Cloning is cheap, the locales are shared. Each update gets its own view through
+/// [`I18n::for_locale`], so concurrent updates never see each other's locale.
 #[derive(Clone)]
 pub struct I18n {
     /// The current locale.
-    current_locale: Arc<Mutex<String>>,
+    current_locale: String,
     /// The default locale.
     default_locale: String,
 
     /// The locales.
-    locales: HashMap<String, Value>,
+    locales: Arc<HashMap<String, Value>>,
+    /// The keys already reported as missing, by locale.
+    reported: Arc<Mutex<HashSet<(String, String)>>>,
 }
 
 #[allow(dead_code)]
@@ -40,10 +48,11 @@ impl I18n {
         let default_locale = locale.to_string();
 
         Self {
-            current_locale: Arc::new(Mutex::new(default_locale.clone())),
+            current_locale: default_locale.clone(),
             default_locale,
 
-            locales: HashMap::new(),
+            locales: Arc::new(HashMap::new()),
+            reported: Arc::new(Mutex::new(HashSet::new())),
         }
     }
 
@@ -53,9 +62,23 @@ impl I18n {
     ///
     /// Returns an error if the locales could not be loaded.
     pub fn load(&mut self) -> Result<()> {
-        log::debug!("loading locales from: {:?}", PATH);
+        self.load_from(PATH)
+    }
+
+    /// Loads the locales from a directory.
+    ///
+    /// # Arguments
+    ///
+    /// * `path` - The directory with the locale files.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the locales could not be loaded.
+    pub fn load_from<P: AsRef<Path>>(&mut self, path: P) -> Result<()> {
+        let path = path.as_ref();
+        log::debug!("loading locales from: {:?}", path);
 
-        let locales = std::fs::read_dir(PATH)?
+        let locales = std::fs::read_dir(path)?
             .map(|entry| entry.expect("failed to read entry"))
             .map(|entry| {
                 let path = entry.path();
@@ -73,7 +96,7 @@ impl I18n {
                 (locale, value)
             })
             .collect::<HashMap<String, Value>>();
-        self.locales = locales;
+        self.locales = Arc::new(locales);
 
         log::info!(
             "locales loaded: {}",
@@ -87,17 +110,44 @@ impl I18n {
         Ok(())
     }
 
+    /// Makes sure the default locale was loaded.
+    ///
+    /// Falls back to `en`, or the first available locale, with a warning when it was not.
+    /// Returns the effective default locale.
+    pub fn ensure_default_locale(&mut self) -> &str {
+        if !self.locales.contains_key(&self.default_locale) {
+            if let Some(fallback) = fallback_locale(&self.locales()) {
+                log::warn!(
+                    "default locale {:?} not found, falling back to {:?}",
+                    self.default_locale,
+                    fallback
+                );
+
+                self.set_locale(&fallback);
+                self.default_locale = fallback;
+            } else {
+                log::warn!("no locales loaded, translations will be missing");
+            }
+        }
+
+        &self.default_locale
+    }
+
     /// Gets the current locale.
+    pub fn locale(&self) -> String {
+        self.current_locale.clone()
+    }
+
+    /// Gets a view of the resource using a locale.
     ///
-    /// # Errors
+    /// # Arguments
     ///
-    /// Returns an error if the current locale could not be retrieved.
-    pub fn locale(&self) -> String {
-        let current_locale = self
-            .current_locale
-            .try_lock()
-            .expect("failed to lock mutex");
-        current_locale.clone()
+    /// * `locale` - The locale to use.
+    pub fn for_locale<L: ToString>(&self, locale: L) -> Self {
+        let mut i18n = self.clone();
+        i18n.set_locale(locale);
+
+        i18n
     }
 
     /// Gets the available locales.
@@ -105,21 +155,44 @@ impl I18n {
         self.locales.keys().cloned().collect()
     }
 
-    /// Sets the current locale.
+    /// Gets the default locale.
+    pub fn default_locale(&self) -> &str {
+        &self.default_locale
+    }
+
+    /// Gets how much of a locale is translated, compared to the default locale.
+    ///
+    /// Returns a percentage between `0.0` and `100.0`, or `None` if the locale is not loaded.
     ///
     /// # Arguments
     ///
-    /// * `locale` - The locale to set.
+    /// * `locale` - The locale to check.
+    pub fn completeness(&self, locale: &str) -> Option<f32> {
+        let object = self.locales.get(locale)?.as_object()?;
+        let default = self
+            .locales
+            .get(&self.default_locale)
+            .and_then(Value::as_object)?;
+
+        if default.is_empty() {
+            return Some(100.0);
+        }
+
+        let translated = default
+            .keys()
+            .filter(|key| object.contains_key(key.as_str()))
+            .count();
+
+        Some(translated as f32 * 100.0 / default.len() as f32)
+    }
+
+    /// Sets the current locale.
     ///
-    /// # Errors
+    /// # Arguments
     ///
-    /// Returns an error if the locale could not be set.
-    pub fn set_locale<L: ToString>(&self, locale: L) {
-        let mut current_locale = self
-            .current_locale
-            .try_lock()
-            .expect("failed to lock mutex");
-        *current_locale = locale.to_string();
+    /// * `locale` - The locale to set.
+    pub fn set_locale<L: ToString>(&mut self, locale: L) {
+        self.current_locale = locale.to_string();
     }
 
     /// Translates a key.
@@ -157,34 +230,86 @@ impl I18n {
 
     /// Translates a key from a locale.
     ///
+    /// Keys missing from the locale fall back to the default locale, and then to a
+    /// `[[missing: key]]` placeholder. Each missing key is logged once.
+    ///
     /// # Arguments
     ///
     /// * `key` - The key to translate.
     /// * `locale` - The locale to translate from.
-    ///
-    /// # Errors
-    ///
-    /// Returns an error if the key could not be translated.
     pub fn translate_from_locale<L: ToString, K: ToString>(&self, key: K, locale: L) -> String {
         let key = key.to_string();
         let locale = locale.to_string();
 
-        let object = self
+        if let Some(value) = self.lookup(&key, &locale) {
+            return value;
+        }
+
+        // Unknown locales silently use the default one, only missing keys are reported.
+        if self.locales.contains_key(&locale) {
+            self.report_missing(&key, &locale);
+        }
+
+        if let Some(value) = self.lookup(&key, &self.default_locale) {
+            return value;
+        }
+        self.report_missing(&key, &self.default_locale);
+
+        format!("[[missing: {}]]", key)
+    }
+
+    /// Logs a missing key, once per key and locale.
+    ///
+    /// # Arguments
+    ///
+    /// * `key` - The missing key.
+    /// * `locale` - The locale missing it.
+    fn report_missing(&self, key: &str, locale: &str) {
+        let mut reported = self.reported.lock().expect("failed to lock mutex");
+
+        if reported.insert((locale.to_string(), key.to_string())) {
+            log::warn!("missing translation for {:?} in locale {:?}", key, locale);
+        }
+    }
+
+    /// Gets the keys of the default locale that a locale does not translate, sorted.
+    ///
+    /// Every key is missing from a locale that is not loaded.
+    ///
+    /// # Arguments
+    ///
+    /// * `locale` - The locale to check.
+    pub fn missing_keys(&self, locale: &str) -> Vec<String> {
+        let Some(default) = self
             .locales
-            .get(&locale)
-            .or_else(|| {
-                Some(
-                    self.locales
-                        .get(&self.default_locale)
-                        .expect("default locale not found"),
-                )
-            })
-            .unwrap();
-        let value = object.get(&key).map_or("KEY_NOT_FOUND", |value| {
-            value.as_str().expect("value not found")
-        });
+            .get(&self.default_locale)
+            .and_then(Value::as_object)
+        else {
+            return Vec::new();
+        };
+
+        let mut keys = default
+            .keys()
+            .filter(|key| self.lookup(key.as_str(), locale).is_none())
+            .cloned()
+            .collect::<Vec<_>>();
+        keys.sort();
 
-        value.to_string()
+        keys
+    }
+
+    /// Gets the translation of a key in a locale, without falling back to the default locale.
+    ///
+    /// # Arguments
+    ///
+    /// * `key` - The key to look up.
+    /// * `locale` - The locale to look in.
+    pub fn lookup<K: ToString>(&self, key: K, locale: &str) -> Option<String> {
+        self.locales
+            .get(locale)?
+            .get(key.to_string())?
+            .as_str()
+            .map(String::from)
     }
 
     /// Translates a key from a locale with arguments.
@@ -213,3 +338,18 @@ impl I18n {
         result
     }
 }
+
+/// Picks the locale to fall back to when the default one is not available.
+///
+/// Prefers `en`, then the first locale in alphabetical order.
+///
+/// # Arguments
+///
+/// * `locales` - The available locales.
+pub fn fallback_locale(locales: &[String]) -> Option<String> {
+    if locales.iter().any(|locale| locale == "en") {
+        return Some("en".to_string());
+    }
+
+    locales.iter().min().cloned()
+}
@@ -0,0 +1,44 @@
+// Copyright 2025 - Andriel Ferreira
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// https://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or https://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+//! The maintenance resource.
+
+use std::sync::{
+    Arc,
+    atomic::{AtomicBool, Ordering},
+};
+
+/// Maintenance module.
+///
+/// Whether the bot is under maintenance, answering only its sudoers.
+#[derive(Clone, Debug, Default)]
+pub struct Maintenance {
+    /// Whether the maintenance mode is on.
+    enabled: Arc<AtomicBool>,
+}
+
+impl Maintenance {
+    /// Creates a new instance of the maintenance, turned off.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Whether the maintenance mode is on.
+    pub fn is_enabled(&self) -> bool {
+        self.enabled.load(Ordering::Relaxed)
+    }
+
+    /// Turns the maintenance mode on or off.
+    ///
+    /// # Arguments
+    ///
+    /// * `enabled` - Whether the maintenance mode is on.
+    pub fn set(&self, enabled: bool) {
+        self.enabled.store(enabled, Ordering::Relaxed);
+    }
+}
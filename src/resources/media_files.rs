@@ -0,0 +1,204 @@
+// Copyright 2025 - Andriel Ferreira
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// https://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or https://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+//! The media files resource.
+
+use std::time::Duration;
+
+use base64::Engine;
+use grammers_client::{grammers_tl_types as tl, types::Media};
+
+use crate::{
+    models::MediaFile,
+    resources::{Cache, Database},
+};
+
+/// How long an uploaded image is reused from memory before asking the database again.
+pub const FILE_TTL: Duration = Duration::from_secs(60 * 60);
+
+/// A photo already uploaded to Telegram.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct PhotoFile {
+    /// The ID of the photo.
+    pub id: i64,
+    /// The access hash of the photo.
+    pub access_hash: i64,
+    /// The file reference of the photo, Telegram expires it from time to time.
+    pub file_reference: Vec<u8>,
+    /// The datacenter the photo is stored in.
+    pub dc_id: i32,
+}
+
+impl PhotoFile {
+    /// Gets the photo of a sent message, if it has one.
+    ///
+    /// # Arguments
+    ///
+    /// * `media` - The media of the message.
+    pub fn from_media(media: &Media) -> Option<Self> {
+        let Media::Photo(photo) = media else {
+            return None;
+        };
+
+        match photo.raw.photo.as_ref()? {
+            tl::enums::Photo::Photo(photo) => Some(Self {
+                id: photo.id,
+                access_hash: photo.access_hash,
+                file_reference: photo.file_reference.clone(),
+                dc_id: photo.dc_id,
+            }),
+            tl::enums::Photo::Empty(_) => None,
+        }
+    }
+
+    /// Converts the photo into a media, to send it again.
+    pub fn to_media(&self) -> Option<Media> {
+        Media::from_raw(tl::enums::MessageMedia::Photo(
+            tl::types::MessageMediaPhoto {
+                spoiler: false,
+                photo: Some(tl::enums::Photo::Photo(tl::types::Photo {
+                    has_stickers: false,
+                    id: self.id,
+                    access_hash: self.access_hash,
+                    file_reference: self.file_reference.clone(),
+                    date: 0,
+                    sizes: Vec::new(),
+                    video_sizes: None,
+                    dc_id: self.dc_id,
+                })),
+                ttl_seconds: None,
+            },
+        ))
+    }
+
+    /// Encodes the photo as a file ID, e.g. `5042:-77:2:AQID`.
+    pub fn to_file_id(&self) -> String {
+        format!(
+            "{0}:{1}:{2}:{3}",
+            self.id,
+            self.access_hash,
+            self.dc_id,
+            base64::engine::general_purpose::URL_SAFE_NO_PAD.encode(&self.file_reference)
+        )
+    }
+
+    /// Decodes a photo from its file ID.
+    ///
+    /// # Arguments
+    ///
+    /// * `file_id` - The file ID, see [`PhotoFile::to_file_id`].
+    pub fn parse(file_id: &str) -> Option<Self> {
+        let mut parts = file_id.split(':');
+
+        let photo = Self {
+            id: parts.next()?.parse().ok()?,
+            access_hash: parts.next()?.parse().ok()?,
+            dc_id: parts.next()?.parse().ok()?,
+            file_reference: base64::engine::general_purpose::URL_SAFE_NO_PAD
+                .decode(parts.next()?)
+                .ok()?,
+        };
+
+        parts.next().is_none().then_some(photo)
+    }
+}
+
+/// Media files module.
+///
+/// Remembers the images uploaded to Telegram by their link, so the cards send them again
+/// without Telegram downloading them from AniList every time.
+#[derive(Clone, Debug)]
+pub struct MediaFiles {
+    /// The uploaded images, by their link.
+    cache: Cache<String, PhotoFile>,
+    /// The database where the uploaded images are stored.
+    db: Option<Database>,
+}
+
+impl Default for MediaFiles {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl MediaFiles {
+    /// Creates a new instance of the media files.
+    pub fn new() -> Self {
+        Self {
+            cache: Cache::with_capacity_and_ttl(500, FILE_TTL),
+            db: None,
+        }
+    }
+
+    /// Sets the database used to store and look up the uploaded images.
+    ///
+    /// # Arguments
+    ///
+    /// * `db` - The database resource.
+    pub fn with_database(mut self, db: Database) -> Self {
+        self.db = Some(db);
+        self
+    }
+
+    /// Gets the uploaded image of a link.
+    ///
+    /// # Arguments
+    ///
+    /// * `url` - The link of the image.
+    pub async fn get(&self, url: &str) -> Option<PhotoFile> {
+        if let Some(photo) = self.cache.get(&url.to_string()).await {
+            return Some(photo);
+        }
+
+        let db = self.db.as_ref().filter(|db| db.is_healthy())?;
+        let row = match db.report(MediaFile::get(db.pool(), url).await) {
+            Ok(row) => row?,
+            Err(e) => {
+                log::warn!("failed to get the uploaded image of {}: {:?}", url, e);
+                return None;
+            }
+        };
+
+        let photo = PhotoFile::parse(&row.file_id)?;
+        self.cache.insert(url.to_string(), photo.clone()).await;
+
+        Some(photo)
+    }
+
+    /// Remembers the uploaded image of a link.
+    ///
+    /// # Arguments
+    ///
+    /// * `url` - The link of the image.
+    /// * `photo` - The uploaded image.
+    pub async fn store(&self, url: &str, photo: PhotoFile) {
+        self.cache.insert(url.to_string(), photo.clone()).await;
+
+        if let Some(db) = self.db.as_ref().filter(|db| db.is_healthy()) {
+            let result = MediaFile::upsert(db.pool(), url, &photo.to_file_id()).await;
+            if let Err(e) = db.report(result) {
+                log::warn!("failed to store the uploaded image of {}: {:?}", url, e);
+            }
+        }
+    }
+
+    /// Forgets the uploaded image of a link, e.g. after Telegram rejected it.
+    ///
+    /// # Arguments
+    ///
+    /// * `url` - The link of the image.
+    pub async fn forget(&self, url: &str) {
+        self.cache.remove(&url.to_string()).await;
+
+        if let Some(db) = self.db.as_ref().filter(|db| db.is_healthy()) {
+            if let Err(e) = db.report(MediaFile::delete(db.pool(), url).await) {
+                log::warn!("failed to forget the uploaded image of {}: {:?}", url, e);
+            }
+        }
+    }
+}
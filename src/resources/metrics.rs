@@ -0,0 +1,267 @@
+// Copyright 2025 - Andriel Ferreira
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// https://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or https://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+//! The metrics resource.
+
+use std::{
+    collections::{BTreeMap, HashMap},
+    fmt::Write,
+    sync::{Arc, Mutex},
+    time::Duration,
+};
+
+/// The upper bounds, in seconds, of the buckets of the update durations.
+pub const DURATION_BUCKETS: &[f64] = &[
+    0.005, 0.01, 0.025, 0.05, 0.1, 0.25, 0.5, 1.0, 2.5, 5.0, 10.0,
+];
+
+/// Metrics module.
+///
+/// Counts usage in memory, the callback counts until they are flushed to the database and
+/// the rest for the Prometheus endpoint.
+#[derive(Clone, Debug, Default)]
+pub struct Metrics {
+    /// How many times each callback verb was pressed since the last flush.
+    callbacks: Arc<Mutex<HashMap<String, i64>>>,
+    /// How many updates of each kind were received.
+    updates: Arc<Mutex<BTreeMap<&'static str, u64>>>,
+    /// How long the updates took to handle.
+    update_durations: Arc<Mutex<Histogram>>,
+    /// How many requests were sent to AniList, by outcome.
+    anilist_requests: Arc<Mutex<BTreeMap<&'static str, u64>>>,
+    /// How many lookups each cache had, by cache and whether they found a value.
+    cache_lookups: Arc<Mutex<BTreeMap<(&'static str, bool), u64>>>,
+}
+
+/// A histogram, counting the observed values by bucket.
+#[derive(Clone, Debug)]
+pub struct Histogram {
+    /// The upper bounds of the buckets.
+    bounds: &'static [f64],
+    /// How many values fell in each bucket, not cumulative.
+    buckets: Vec<u64>,
+    /// The sum of the observed values.
+    sum: f64,
+    /// How many values were observed.
+    count: u64,
+}
+
+impl Default for Histogram {
+    fn default() -> Self {
+        Self::new(DURATION_BUCKETS)
+    }
+}
+
+impl Histogram {
+    /// Creates a new histogram.
+    ///
+    /// # Arguments
+    ///
+    /// * `bounds` - The upper bounds of the buckets, in ascending order.
+    pub fn new(bounds: &'static [f64]) -> Self {
+        Self {
+            bounds,
+            buckets: vec![0; bounds.len()],
+            sum: 0.0,
+            count: 0,
+        }
+    }
+
+    /// Observes a value.
+    ///
+    /// # Arguments
+    ///
+    /// * `value` - The value.
+    pub fn observe(&mut self, value: f64) {
+        if let Some(bucket) = self.bounds.iter().position(|bound| value <= *bound) {
+            self.buckets[bucket] += 1;
+        }
+        self.sum += value;
+        self.count += 1;
+    }
+
+    /// Gets how many values were at most each bound, as Prometheus expects them.
+    pub fn cumulative(&self) -> Vec<(f64, u64)> {
+        self.bounds
+            .iter()
+            .zip(self.buckets.iter())
+            .scan(0, |total, (bound, count)| {
+                *total += count;
+                Some((*bound, *total))
+            })
+            .collect()
+    }
+}
+
+impl Metrics {
+    /// Creates a new instance of the metrics.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Counts a callback query press.
+    ///
+    /// # Arguments
+    ///
+    /// * `data` - The data of the callback query.
+    pub fn record_callback(&self, data: &str) {
+        if let Some(verb) = callback_verb(data) {
+            *self.callbacks.lock().unwrap().entry(verb).or_default() += 1;
+        }
+    }
+
+    /// Takes the callback counts, resetting them.
+    pub fn take_callbacks(&self) -> HashMap<String, i64> {
+        std::mem::take(&mut *self.callbacks.lock().unwrap())
+    }
+
+    /// Adds counts back, e.g. after they failed to be flushed.
+    ///
+    /// # Arguments
+    ///
+    /// * `counts` - The counts to add.
+    pub fn restore_callbacks(&self, counts: HashMap<String, i64>) {
+        let mut callbacks = self.callbacks.lock().unwrap();
+
+        for (verb, count) in counts {
+            *callbacks.entry(verb).or_default() += count;
+        }
+    }
+
+    /// Counts a received update.
+    ///
+    /// # Arguments
+    ///
+    /// * `kind` - The kind of the update, e.g. `new_message`.
+    pub fn record_update(&self, kind: &'static str) {
+        *self.updates.lock().unwrap().entry(kind).or_default() += 1;
+    }
+
+    /// Records how long an update took to handle.
+    ///
+    /// # Arguments
+    ///
+    /// * `duration` - How long the update took.
+    pub fn record_update_duration(&self, duration: Duration) {
+        self.update_durations
+            .lock()
+            .unwrap()
+            .observe(duration.as_secs_f64());
+    }
+
+    /// Counts a request sent to AniList.
+    ///
+    /// # Arguments
+    ///
+    /// * `outcome` - How the request went, `ok`, `rate_limited` or `failed`.
+    pub fn record_anilist_request(&self, outcome: &'static str) {
+        *self
+            .anilist_requests
+            .lock()
+            .unwrap()
+            .entry(outcome)
+            .or_default() += 1;
+    }
+
+    /// Counts a cache lookup.
+    ///
+    /// # Arguments
+    ///
+    /// * `cache` - The name of the cache.
+    /// * `hit` - Whether the lookup found a value.
+    pub fn record_cache_lookup(&self, cache: &'static str, hit: bool) {
+        *self
+            .cache_lookups
+            .lock()
+            .unwrap()
+            .entry((cache, hit))
+            .or_default() += 1;
+    }
+
+    /// Renders the metrics in the Prometheus text format.
+    pub fn render(&self) -> String {
+        let mut text = String::new();
+
+        text.push_str("# HELP yamata_updates_total The updates received, by kind.\n");
+        text.push_str("# TYPE yamata_updates_total counter\n");
+        for (kind, count) in self.updates.lock().unwrap().iter() {
+            let _ = writeln!(text, "yamata_updates_total{{kind=\"{}\"}} {}", kind, count);
+        }
+
+        let durations = self.update_durations.lock().unwrap().clone();
+        text.push_str(
+            "# HELP yamata_update_duration_seconds How long the updates took to handle.\n",
+        );
+        text.push_str("# TYPE yamata_update_duration_seconds histogram\n");
+        for (bound, count) in durations.cumulative() {
+            let _ = writeln!(
+                text,
+                "yamata_update_duration_seconds_bucket{{le=\"{}\"}} {}",
+                bound, count
+            );
+        }
+        let _ = writeln!(
+            text,
+            "yamata_update_duration_seconds_bucket{{le=\"+Inf\"}} {}",
+            durations.count
+        );
+        let _ = writeln!(text, "yamata_update_duration_seconds_sum {}", durations.sum);
+        let _ = writeln!(
+            text,
+            "yamata_update_duration_seconds_count {}",
+            durations.count
+        );
+
+        text.push_str(
+            "# HELP yamata_anilist_requests_total The requests sent to AniList, by outcome.\n",
+        );
+        text.push_str("# TYPE yamata_anilist_requests_total counter\n");
+        for (outcome, count) in self.anilist_requests.lock().unwrap().iter() {
+            let _ = writeln!(
+                text,
+                "yamata_anilist_requests_total{{outcome=\"{}\"}} {}",
+                outcome, count
+            );
+        }
+
+        text.push_str(
+            "# HELP yamata_cache_lookups_total The lookups of the caches, by cache and result.\n",
+        );
+        text.push_str("# TYPE yamata_cache_lookups_total counter\n");
+        for ((cache, hit), count) in self.cache_lookups.lock().unwrap().iter() {
+            let _ = writeln!(
+                text,
+                "yamata_cache_lookups_total{{cache=\"{}\",result=\"{}\"}} {}",
+                cache,
+                if *hit { "hit" } else { "miss" },
+                count
+            );
+        }
+
+        text
+    }
+}
+
+/// Gets the verb of a callback query, i.e. the words before its arguments.
+///
+/// `anime tags 1 123` gives `anime tags` while `anime 1 123` gives `anime`.
+///
+/// # Arguments
+///
+/// * `data` - The data of the callback query.
+pub fn callback_verb(data: &str) -> Option<String> {
+    let mut words = data.split_whitespace();
+    let first = words.next()?;
+
+    match words.next() {
+        Some(second) if second.chars().all(|c| c.is_ascii_alphabetic() || c == '_') => {
+            Some(format!("{} {}", first, second))
+        }
+        _ => Some(first.to_string()),
+    }
+}
@@ -9,11 +9,49 @@
 //! Resources.
 
 pub mod anilist;
+pub mod bans;
 pub mod cache;
+pub mod callback_signer;
+pub mod cards;
+pub mod correlations;
 pub mod database;
+pub mod debounce;
+pub mod diagnostics;
+pub mod disabled_commands;
+pub mod favorites;
 pub mod i18n;
+pub mod maintenance;
+pub mod media_files;
+pub mod metrics;
+pub mod rate_limiter;
+pub mod search_stash;
+pub mod seeds;
+pub mod sessions;
+pub mod single_flight;
+pub mod stats;
+pub mod throttle;
+pub mod token_cipher;
 
 pub use anilist::AniList;
+pub use bans::Bans;
 pub use cache::Cache;
+pub use callback_signer::CallbackSigner;
+pub use cards::Cards;
+pub use correlations::Correlations;
 pub use database::Database;
+pub use debounce::Debounce;
+pub use diagnostics::Diagnostics;
+pub use disabled_commands::DisabledCommands;
+pub use favorites::Favorites;
 pub use i18n::I18n;
+pub use maintenance::Maintenance;
+pub use media_files::MediaFiles;
+pub use metrics::Metrics;
+pub use rate_limiter::RateLimiter;
+pub use search_stash::SearchStash;
+pub use seeds::Seeds;
+pub use sessions::Sessions;
+pub use single_flight::SingleFlight;
+pub use stats::Stats;
+pub use throttle::Throttle;
+pub use token_cipher::TokenCipher;
@@ -0,0 +1,135 @@
+// Copyright 2025 - Andriel Ferreira
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// https://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or https://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+//! The rate limiter resource.
+
+use std::{
+    sync::{Arc, Mutex},
+    time::Duration,
+};
+
+use tokio::time::Instant;
+
+/// How long a request may wait for its turn before giving up.
+const MAX_WAIT: Duration = Duration::from_secs(5);
+
+/// Rate limiter module.
+///
+/// A token bucket refilled evenly over a minute, which can also be blocked for a while,
+/// e.g. when the API answers that the limit was exceeded anyway.
+#[derive(Clone, Debug)]
+pub struct RateLimiter {
+    /// The state of the bucket.
+    state: Arc<Mutex<State>>,
+    /// How many requests are allowed per minute.
+    per_minute: u32,
+}
+
+/// The state of the bucket.
+#[derive(Debug)]
+struct State {
+    /// The requests available, negative when some are waiting for their turn.
+    tokens: f64,
+    /// When the tokens were last refilled.
+    refilled_at: Instant,
+    /// Until when no request is allowed.
+    blocked_until: Option<Instant>,
+}
+
+impl RateLimiter {
+    /// Creates a new instance of the rate limiter, with a full bucket.
+    ///
+    /// # Arguments
+    ///
+    /// * `per_minute` - How many requests are allowed per minute.
+    pub fn new(per_minute: u32) -> Self {
+        let per_minute = per_minute.max(1);
+
+        Self {
+            state: Arc::new(Mutex::new(State {
+                tokens: per_minute as f64,
+                refilled_at: Instant::now(),
+                blocked_until: None,
+            })),
+            per_minute,
+        }
+    }
+
+    /// Gets how many requests are allowed per minute.
+    pub fn per_minute(&self) -> u32 {
+        self.per_minute
+    }
+
+    /// Waits for the turn of a request.
+    ///
+    /// # Errors
+    ///
+    /// Returns how long to wait if the limiter is blocked or the turn is too far away.
+    pub async fn acquire(&self) -> Result<(), Duration> {
+        let wait = {
+            let mut state = self.state.lock().unwrap();
+            let now = Instant::now();
+
+            if let Some(blocked_until) = state.blocked_until {
+                if blocked_until > now {
+                    return Err(blocked_until - now);
+                }
+                state.blocked_until = None;
+            }
+
+            let rate = self.per_minute as f64 / 60.0;
+            state.tokens = (state.tokens + (now - state.refilled_at).as_secs_f64() * rate)
+                .min(self.per_minute as f64);
+            state.refilled_at = now;
+
+            let wait = Duration::from_secs_f64((1.0 - state.tokens).max(0.0) / rate);
+            if wait > MAX_WAIT {
+                return Err(wait);
+            }
+
+            // Take the token now, so the requests behind wait for the next ones.
+            state.tokens -= 1.0;
+            wait
+        };
+
+        if !wait.is_zero() {
+            tokio::time::sleep(wait).await;
+        }
+
+        Ok(())
+    }
+
+    /// Blocks the requests for a while.
+    ///
+    /// A shorter block does not shorten the current one.
+    ///
+    /// # Arguments
+    ///
+    /// * `duration` - How long to block the requests.
+    pub fn block_for(&self, duration: Duration) {
+        let mut state = self.state.lock().unwrap();
+        let until = Instant::now() + duration;
+
+        if state
+            .blocked_until
+            .is_none_or(|blocked_until| blocked_until < until)
+        {
+            state.blocked_until = Some(until);
+        }
+    }
+
+    /// Gets how long the requests are still blocked for, if they are.
+    pub fn retry_after(&self) -> Option<Duration> {
+        let state = self.state.lock().unwrap();
+
+        state
+            .blocked_until
+            .and_then(|blocked_until| blocked_until.checked_duration_since(Instant::now()))
+            .filter(|remaining| !remaining.is_zero())
+    }
+}
@@ -0,0 +1,87 @@
+// Copyright 2025 - Andriel Ferreira
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// https://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or https://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+//! The search stash resource.
+
+use crate::{resources::Cache, utils::SearchQuery};
+
+/// A search result, with just what is needed to render its button.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct SearchResult {
+    /// The media ID.
+    pub id: i64,
+    /// The media title.
+    pub title: String,
+    /// Whether the media is for adults.
+    pub is_adult: bool,
+}
+
+/// A stashed search.
+#[derive(Clone, Debug)]
+pub struct StashedSearch {
+    /// The search query.
+    pub query: SearchQuery,
+    /// The results of the search.
+    pub results: Vec<SearchResult>,
+}
+
+/// Search stash module.
+///
+/// Keeps the searches made through commands, so callbacks can re-run or expand them. Callback
+/// data is limited to 64 bytes, which is not enough to carry the search terms.
+#[derive(Clone)]
+pub struct SearchStash {
+    /// The stashed searches, keyed by their ID.
+    searches: Cache<String, StashedSearch>,
+}
+
+impl Default for SearchStash {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl SearchStash {
+    /// Creates a new instance of the search stash.
+    pub fn new() -> Self {
+        Self {
+            searches: Cache::with_capacity(200),
+        }
+    }
+
+    /// Stashes a search, returning its ID.
+    ///
+    /// # Arguments
+    ///
+    /// * `search` - The search to stash.
+    pub async fn insert(&self, search: StashedSearch) -> String {
+        let id = format!("{:08x}", rand::random::<u32>());
+        self.searches.insert(id.clone(), search).await;
+
+        id
+    }
+
+    /// Replaces a stashed search, e.g. after its results were re-sorted.
+    ///
+    /// # Arguments
+    ///
+    /// * `id` - The ID of the stashed search.
+    /// * `search` - The new search.
+    pub async fn replace(&self, id: &str, search: StashedSearch) {
+        self.searches.insert(id.to_string(), search).await;
+    }
+
+    /// Gets a stashed search.
+    ///
+    /// # Arguments
+    ///
+    /// * `id` - The ID of the stashed search.
+    pub async fn get(&self, id: &str) -> Option<StashedSearch> {
+        self.searches.get(&id.to_string()).await
+    }
+}
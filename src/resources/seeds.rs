@@ -0,0 +1,62 @@
+// Copyright 2025 - Andriel Ferreira
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// https://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or https://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+//! The seeds resource.
+
+use std::time::Duration;
+
+use tokio::time::Instant;
+
+use crate::resources::Cache;
+
+/// Seeds module.
+///
+/// Hands out random seeds that stay the same for a user for a short while, so repeated
+/// requests (e.g. Telegram retrying an inline query) roll the same results.
+#[derive(Clone, Debug)]
+pub struct Seeds {
+    /// The last seed of each user and purpose, and when it was rolled.
+    seeds: Cache<(i64, String), (Instant, u64)>,
+    /// How long a seed is reused.
+    ttl: Duration,
+}
+
+impl Seeds {
+    /// Creates a new instance of the seeds.
+    ///
+    /// # Arguments
+    ///
+    /// * `ttl` - How long a seed is reused.
+    pub fn new(ttl: Duration) -> Self {
+        Self {
+            seeds: Cache::with_capacity(1000),
+            ttl,
+        }
+    }
+
+    /// Gets the seed of a user for a purpose, rolling a new one if the last one expired.
+    ///
+    /// # Arguments
+    ///
+    /// * `user_id` - The user's ID.
+    /// * `purpose` - What the seed is for, e.g. `random` or the query text.
+    pub async fn seed(&self, user_id: i64, purpose: &str) -> u64 {
+        let key = (user_id, purpose.to_string());
+
+        if let Some((rolled_at, seed)) = self.seeds.get(&key).await {
+            if rolled_at.elapsed() < self.ttl {
+                return seed;
+            }
+        }
+
+        let seed = rand::random();
+        self.seeds.insert(key, (Instant::now(), seed)).await;
+
+        seed
+    }
+}
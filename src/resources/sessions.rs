@@ -0,0 +1,309 @@
+// Copyright 2025 - Andriel Ferreira
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// https://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or https://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+//! The sessions resource.
+
+use std::{
+    hash::{DefaultHasher, Hash, Hasher},
+    sync::Arc,
+    time::{Duration, Instant},
+};
+
+use base64::Engine;
+use chrono::{DateTime, Utc};
+use rand::{Rng, distr::Alphanumeric};
+use serde::Deserialize;
+
+use crate::resources::Cache;
+
+/// How long a pending authentication attempt stays valid.
+pub const AUTH_ATTEMPT_TTL: Duration = Duration::from_secs(10 * 60);
+
+/// The length of the OAuth `state` parameter.
+pub const STATE_LENGTH: usize = 32;
+
+/// A pending AniList authentication attempt.
+#[derive(Clone, Debug)]
+pub struct AuthAttempt {
+    /// The OAuth `state` parameter bound to the attempt.
+    pub state: String,
+    /// When the attempt was started.
+    pub started_at: Instant,
+}
+
+impl Default for AuthAttempt {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl AuthAttempt {
+    /// Creates a new authentication attempt with a random state.
+    pub fn new() -> Self {
+        Self {
+            state: gen_state(),
+            started_at: Instant::now(),
+        }
+    }
+
+    /// Whether the attempt is older than [`AUTH_ATTEMPT_TTL`].
+    pub fn is_expired(&self) -> bool {
+        self.started_at.elapsed() > AUTH_ATTEMPT_TTL
+    }
+
+    /// Whether the given state matches the one bound to the attempt.
+    ///
+    /// The comparison takes the same time regardless of where the states differ.
+    ///
+    /// # Arguments
+    ///
+    /// * `state` - The state received with the authorization code.
+    pub fn matches(&self, state: &str) -> bool {
+        if self.state.len() != state.len() {
+            return false;
+        }
+
+        self.state
+            .bytes()
+            .zip(state.bytes())
+            .fold(0, |acc, (a, b)| acc | (a ^ b))
+            == 0
+    }
+}
+
+/// Why an authentication attempt could not be verified.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum AttemptError {
+    /// There is no pending attempt, or it has expired.
+    Expired,
+    /// The received state does not match the pending attempt.
+    StateMismatch,
+}
+
+/// Generates a random OAuth `state` parameter.
+pub fn gen_state() -> String {
+    rand::rng()
+        .sample_iter(Alphanumeric)
+        .take(STATE_LENGTH)
+        .map(char::from)
+        .collect()
+}
+
+/// Gets a short fingerprint of a token, to tell tokens apart without revealing them.
+///
+/// # Arguments
+///
+/// * `token` - The token.
+pub fn token_fingerprint(token: &str) -> String {
+    let mut hasher = DefaultHasher::new();
+    token.hash(&mut hasher);
+
+    format!("{:016x}", hasher.finish())[..8].to_string()
+}
+
+/// Gets when an AniList token expires, from its JWT claims.
+///
+/// Returns `None` if the token is not a valid JWT.
+///
+/// # Arguments
+///
+/// * `token` - The AniList token.
+pub fn token_expiry(token: &str) -> Option<DateTime<Utc>> {
+    /// The claims of the token that matter here.
+    #[derive(Deserialize)]
+    struct Claims {
+        /// When the token expires, as a Unix timestamp.
+        exp: i64,
+    }
+
+    let body = token.split('.').nth(1)?;
+    let body = base64::engine::general_purpose::URL_SAFE_NO_PAD
+        .decode(body.trim_end_matches('='))
+        .ok()?;
+    let claims = serde_json::from_slice::<Claims>(&body).ok()?;
+
+    DateTime::from_timestamp(claims.exp, 0)
+}
+
+/// The timeout of the AniList clients.
+const CLIENT_TIMEOUT: Duration = Duration::from_secs(15);
+
+/// How long the AniList clients are cached before being built again.
+const CLIENT_TTL: Duration = Duration::from_secs(6 * 60 * 60);
+
+/// Sessions module.
+#[derive(Clone)]
+pub struct Sessions {
+    /// The pending authentication attempts, keyed by Telegram user ID.
+    attempts: Cache<i64, AuthAttempt>,
+    /// The AniList clients of the users, keyed by Telegram user ID.
+    clients: Cache<i64, Arc<rust_anilist::Client>>,
+}
+
+impl Default for Sessions {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Sessions {
+    /// Creates a new instance of the sessions resource.
+    pub fn new() -> Self {
+        Self {
+            attempts: Cache::with_capacity(500),
+            clients: Cache::with_capacity_and_ttl(50, CLIENT_TTL),
+        }
+    }
+
+    /// Removes the expired AniList clients, returning how many were removed.
+    pub async fn purge_expired(&self) -> usize {
+        self.clients.purge_expired().await
+    }
+
+    /// Gets the cached AniList client of a user.
+    ///
+    /// # Arguments
+    ///
+    /// * `user_id` - The Telegram user ID.
+    pub async fn client(&self, user_id: i64) -> Option<Arc<rust_anilist::Client>> {
+        self.clients.get(&user_id).await
+    }
+
+    /// Gets how long ago the cached AniList client of a user was created.
+    ///
+    /// Returns `None` if the user has no cached client.
+    ///
+    /// # Arguments
+    ///
+    /// * `user_id` - The Telegram user ID.
+    pub async fn client_age(&self, user_id: i64) -> Option<Duration> {
+        self.clients.age_of(&user_id).await
+    }
+
+    /// Creates and caches an AniList client for a user.
+    ///
+    /// The client is authenticated when a token is given, anonymous otherwise.
+    ///
+    /// # Arguments
+    ///
+    /// * `user_id` - The Telegram user ID.
+    /// * `token` - The user's AniList token, if any.
+    pub async fn authenticate(
+        &self,
+        user_id: i64,
+        token: Option<&str>,
+    ) -> Arc<rust_anilist::Client> {
+        log::debug!("creating a new Anilist client for user {:?}", user_id);
+
+        let client = Arc::new(if let Some(token) = token {
+            rust_anilist::Client::with_token(token).timeout(CLIENT_TIMEOUT)
+        } else {
+            rust_anilist::Client::with_timeout(CLIENT_TIMEOUT)
+        });
+        self.clients.insert(user_id, Arc::clone(&client)).await;
+
+        client
+    }
+
+    /// Removes the cached AniList client of a user.
+    ///
+    /// # Arguments
+    ///
+    /// * `user_id` - The Telegram user ID.
+    pub async fn remove_client(&self, user_id: i64) {
+        self.clients.remove(&user_id).await;
+    }
+
+    /// Gets the pending authentication attempt of a user.
+    ///
+    /// Expired attempts are not returned.
+    ///
+    /// # Arguments
+    ///
+    /// * `user_id` - The Telegram user ID.
+    pub async fn attempt(&self, user_id: i64) -> Option<AuthAttempt> {
+        self.attempts
+            .get(&user_id)
+            .await
+            .filter(|attempt| !attempt.is_expired())
+    }
+
+    /// Starts an authentication attempt for a user.
+    ///
+    /// If the user already has a pending attempt, it is returned instead of a new one.
+    ///
+    /// # Arguments
+    ///
+    /// * `user_id` - The Telegram user ID.
+    pub async fn start_attempt(&self, user_id: i64) -> AuthAttempt {
+        if let Some(attempt) = self.attempt(user_id).await {
+            attempt
+        } else {
+            let attempt = AuthAttempt::new();
+            self.attempts.insert(user_id, attempt.clone()).await;
+
+            attempt
+        }
+    }
+
+    /// Takes the pending authentication attempt of a user, removing it.
+    ///
+    /// Returns `None` if there is no attempt or if it has expired.
+    ///
+    /// # Arguments
+    ///
+    /// * `user_id` - The Telegram user ID.
+    pub async fn take_attempt(&self, user_id: i64) -> Option<AuthAttempt> {
+        self.attempts
+            .take(&user_id)
+            .await
+            .filter(|attempt| !attempt.is_expired())
+    }
+
+    /// Takes the pending authentication attempt of a user and verifies it against the
+    /// received state.
+    ///
+    /// The attempt is consumed even when the state does not match, so a state can't be
+    /// guessed by trying several times.
+    ///
+    /// # Arguments
+    ///
+    /// * `user_id` - The Telegram user ID.
+    /// * `state` - The state received with the authorization code.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if there is no valid pending attempt or if the state does not match.
+    pub async fn verify_attempt(
+        &self,
+        user_id: i64,
+        state: &str,
+    ) -> Result<AuthAttempt, AttemptError> {
+        let attempt = self
+            .take_attempt(user_id)
+            .await
+            .ok_or(AttemptError::Expired)?;
+
+        if attempt.matches(state) {
+            Ok(attempt)
+        } else {
+            Err(AttemptError::StateMismatch)
+        }
+    }
+
+    /// Cancels the pending authentication attempt of a user.
+    ///
+    /// Returns `true` if there was a pending attempt.
+    ///
+    /// # Arguments
+    ///
+    /// * `user_id` - The Telegram user ID.
+    pub async fn cancel_attempt(&self, user_id: i64) -> bool {
+        self.take_attempt(user_id).await.is_some()
+    }
+}
@@ -0,0 +1,96 @@
+// Copyright 2025 - Andriel Ferreira
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// https://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or https://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+//! The single-flight resource.
+
+use std::{
+    collections::HashMap,
+    fmt::{self, Debug},
+    hash::Hash,
+    sync::{Arc, Mutex},
+};
+
+use tokio::sync::OnceCell;
+
+/// Single-flight module.
+///
+/// Coalesces concurrent requests for the same key, so only the first one runs and the
+/// others wait for its result.
+pub struct SingleFlight<K, V> {
+    /// The requests in flight, by key.
+    flights: Arc<Mutex<HashMap<K, Arc<OnceCell<V>>>>>,
+}
+
+impl<K, V> Clone for SingleFlight<K, V> {
+    fn clone(&self) -> Self {
+        Self {
+            flights: Arc::clone(&self.flights),
+        }
+    }
+}
+
+impl<K, V> Debug for SingleFlight<K, V> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("SingleFlight")
+            .field("in_flight", &self.flights.lock().unwrap().len())
+            .finish()
+    }
+}
+
+impl<K, V> Default for SingleFlight<K, V> {
+    fn default() -> Self {
+        Self {
+            flights: Arc::new(Mutex::new(HashMap::new())),
+        }
+    }
+}
+
+impl<K, V> SingleFlight<K, V>
+where
+    K: Eq + Hash + Clone,
+    V: Clone,
+{
+    /// Creates a new instance of the single-flight.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Runs a request, or waits for the one already in flight for the same key.
+    ///
+    /// The result is not kept once the request lands, the next call runs it again. If the
+    /// running caller is cancelled, one of the waiting callers runs its own request instead.
+    ///
+    /// # Arguments
+    ///
+    /// * `key` - The key of the request.
+    /// * `request` - Runs the request.
+    pub async fn run<F, Fut>(&self, key: K, request: F) -> V
+    where
+        F: FnOnce() -> Fut,
+        Fut: Future<Output = V>,
+    {
+        let flight = Arc::clone(self.flights.lock().unwrap().entry(key.clone()).or_default());
+
+        let value = flight.get_or_init(request).await.clone();
+
+        let mut flights = self.flights.lock().unwrap();
+        if flights
+            .get(&key)
+            .is_some_and(|current| Arc::ptr_eq(current, &flight))
+        {
+            flights.remove(&key);
+        }
+
+        value
+    }
+
+    /// Gets the number of requests in flight.
+    pub fn in_flight(&self) -> usize {
+        self.flights.lock().unwrap().len()
+    }
+}
@@ -0,0 +1,133 @@
+// Copyright 2025 - Andriel Ferreira
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// https://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or https://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+//! The stats resource.
+
+use std::time::Duration;
+
+use tokio::time::Instant;
+
+use crate::{
+    models::{Group, User},
+    resources::{AniList, Database},
+};
+
+/// The runtime statistics of the bot at a moment.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct Snapshot {
+    /// The version of the bot.
+    pub version: &'static str,
+    /// How long the bot has been running.
+    pub uptime: Duration,
+    /// How many users are registered, `None` if the database is unavailable.
+    pub users: Option<i64>,
+    /// How many groups are registered, `None` if the database is unavailable.
+    pub groups: Option<i64>,
+    /// How long the database took to answer, `None` if it is unreachable.
+    pub database_latency: Option<Duration>,
+    /// How many lookups of the AniList caches found a value.
+    pub cache_hits: u64,
+    /// How many lookups of the AniList caches found nothing.
+    pub cache_misses: u64,
+    /// The resident memory of the process, in bytes, `None` if it could not be read.
+    pub memory: Option<u64>,
+}
+
+impl Snapshot {
+    /// Gets the percentage of the cache lookups that found a value, `None` before any lookup.
+    pub fn cache_hit_rate(&self) -> Option<u64> {
+        let lookups = self.cache_hits + self.cache_misses;
+
+        (lookups > 0).then(|| self.cache_hits * 100 / lookups)
+    }
+}
+
+/// Stats module.
+///
+/// Collects the runtime statistics of the bot, e.g. for `/about`.
+#[derive(Clone, Debug)]
+pub struct Stats {
+    /// When the bot started.
+    started_at: Instant,
+}
+
+impl Default for Stats {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Stats {
+    /// Creates a new instance of the stats, starting the uptime now.
+    pub fn new() -> Self {
+        Self {
+            started_at: Instant::now(),
+        }
+    }
+
+    /// Gets how long the bot has been running.
+    pub fn uptime(&self) -> Duration {
+        self.started_at.elapsed()
+    }
+
+    /// Collects the statistics of the bot.
+    ///
+    /// # Arguments
+    ///
+    /// * `db` - The database resource.
+    /// * `ani` - The AniList resource.
+    pub async fn collect(&self, db: &Database, ani: &AniList) -> Snapshot {
+        let database_latency = db.health_check().await;
+        let (users, groups) = if database_latency.is_some() {
+            (
+                db.report(User::count(db.pool()).await).ok(),
+                db.report(Group::count(db.pool()).await).ok(),
+            )
+        } else {
+            (None, None)
+        };
+        let (cache_hits, cache_misses) = ani.cache_counts();
+
+        Snapshot {
+            version: env!("CARGO_PKG_VERSION"),
+            uptime: self.uptime(),
+            users,
+            groups,
+            database_latency,
+            cache_hits,
+            cache_misses,
+            memory: process_memory(),
+        }
+    }
+}
+
+/// Reads the resident memory of the process, in bytes.
+///
+/// Only Linux exposes it, through `/proc`.
+pub fn process_memory() -> Option<u64> {
+    std::fs::read_to_string("/proc/self/status")
+        .ok()
+        .and_then(|status| parse_vm_rss(&status))
+}
+
+/// Parses the resident memory, in bytes, out of a `/proc/<pid>/status` file.
+///
+/// # Arguments
+///
+/// * `status` - The content of the file, with a line like `VmRSS:    10240 kB`.
+pub fn parse_vm_rss(status: &str) -> Option<u64> {
+    let line = status.lines().find(|line| line.starts_with("VmRSS:"))?;
+    let kilobytes = line
+        .trim_start_matches("VmRSS:")
+        .split_whitespace()
+        .next()?
+        .parse::<u64>()
+        .ok()?;
+
+    Some(kilobytes * 1024)
+}
@@ -0,0 +1,61 @@
+// Copyright 2025 - Andriel Ferreira
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// https://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or https://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+//! The throttle resource.
+
+use std::{hash::Hash, time::Duration};
+
+use tokio::time::Instant;
+
+use crate::resources::Cache;
+
+/// Throttle module.
+///
+/// Allows an action at most once per window for each key.
+#[derive(Clone, Debug)]
+pub struct Throttle<K> {
+    /// When the action was last allowed for each key.
+    last: Cache<K, Instant>,
+    /// The minimum time between two allowed actions for the same key.
+    window: Duration,
+}
+
+impl<K> Throttle<K>
+where
+    K: Eq + Hash + Clone,
+{
+    /// Creates a new instance of the throttle.
+    ///
+    /// # Arguments
+    ///
+    /// * `capacity` - The max number of keys to remember.
+    /// * `window` - The minimum time between two allowed actions for the same key.
+    pub fn new(capacity: usize, window: Duration) -> Self {
+        Self {
+            last: Cache::with_capacity(capacity),
+            window,
+        }
+    }
+
+    /// Checks whether the action is allowed for the key, recording it if so.
+    ///
+    /// # Arguments
+    ///
+    /// * `key` - The key to check.
+    pub async fn allow(&self, key: K) -> bool {
+        if let Some(last) = self.last.get(&key).await {
+            if last.elapsed() < self.window {
+                return false;
+            }
+        }
+
+        self.last.insert(key, Instant::now()).await;
+
+        true
+    }
+}
@@ -0,0 +1,155 @@
+// Copyright 2025 - Andriel Ferreira
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// https://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or https://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+//! The encryption of the AniList tokens stored in the database.
+
+use std::sync::OnceLock;
+
+use base64::{Engine, engine::general_purpose::STANDARD};
+use chacha20poly1305::{
+    XChaCha20Poly1305, XNonce,
+    aead::{Aead, AeadCore, KeyInit, OsRng},
+};
+
+/// The prefix of the encrypted tokens, the plain ones are JWTs and never start with it.
+pub const ENCRYPTED_PREFIX: &str = "enc:v1:";
+
+/// The length of the nonces, stored before the ciphertext.
+const NONCE_LENGTH: usize = 24;
+
+/// The cipher of the stored tokens, known once the config is loaded.
+static CIPHER: OnceLock<TokenCipher> = OnceLock::new();
+
+/// Sets the cipher of the stored tokens. Only the first call has an effect.
+///
+/// Without a cipher the tokens are stored in plain text.
+///
+/// # Arguments
+///
+/// * `cipher` - The token cipher.
+pub fn set_cipher(cipher: TokenCipher) {
+    let _ = CIPHER.set(cipher);
+}
+
+/// Gets the cipher of the stored tokens, `None` if it was not set.
+pub fn cipher() -> Option<&'static TokenCipher> {
+    CIPHER.get()
+}
+
+/// Whether a stored token is encrypted.
+///
+/// # Arguments
+///
+/// * `stored` - The token as stored in the database.
+pub fn is_encrypted(stored: &str) -> bool {
+    stored.starts_with(ENCRYPTED_PREFIX)
+}
+
+/// Encrypts a token to store it, with the cipher that was set.
+///
+/// Returns the token unchanged if no cipher was set.
+///
+/// # Arguments
+///
+/// * `token` - The AniList token.
+pub fn seal(token: &str) -> String {
+    match cipher() {
+        Some(cipher) => cipher.encrypt(token),
+        None => token.to_string(),
+    }
+}
+
+/// Decrypts a stored token, with the cipher that was set.
+///
+/// Tokens stored before they were encrypted are returned unchanged. Returns `None` if the
+/// token could not be decrypted, by a missing or another key.
+///
+/// # Arguments
+///
+/// * `stored` - The token as stored in the database.
+pub fn open(stored: &str) -> Option<String> {
+    if !is_encrypted(stored) {
+        return Some(stored.to_string());
+    }
+
+    let token = cipher().and_then(|cipher| cipher.decrypt(stored));
+    if token.is_none() {
+        log::warn!("failed to decrypt a stored token, is the token_encryption_key right?");
+    }
+
+    token
+}
+
+/// Encrypts the tokens with XChaCha20-Poly1305, a random nonce stored with each of them.
+pub struct TokenCipher {
+    /// The AEAD cipher.
+    aead: XChaCha20Poly1305,
+}
+
+impl TokenCipher {
+    /// Creates a cipher from a base64-encoded 32-byte key.
+    ///
+    /// Returns `None` if the key is not valid.
+    ///
+    /// # Arguments
+    ///
+    /// * `key` - The key, in base64.
+    pub fn from_base64(key: &str) -> Option<Self> {
+        let key = STANDARD.decode(key.trim()).ok()?;
+        let aead = XChaCha20Poly1305::new_from_slice(&key).ok()?;
+
+        Some(Self { aead })
+    }
+
+    /// Generates a random key, in base64.
+    pub fn gen_key() -> String {
+        STANDARD.encode(XChaCha20Poly1305::generate_key(&mut OsRng))
+    }
+
+    /// Encrypts a token, prefixing it with [`ENCRYPTED_PREFIX`].
+    ///
+    /// # Arguments
+    ///
+    /// * `token` - The AniList token.
+    pub fn encrypt(&self, token: &str) -> String {
+        let nonce = XChaCha20Poly1305::generate_nonce(&mut OsRng);
+        let ciphertext = self
+            .aead
+            .encrypt(&nonce, token.as_bytes())
+            .expect("failed to encrypt token");
+
+        let mut payload = nonce.to_vec();
+        payload.extend(ciphertext);
+
+        format!("{}{}", ENCRYPTED_PREFIX, STANDARD.encode(payload))
+    }
+
+    /// Decrypts a token encrypted by [`TokenCipher::encrypt`].
+    ///
+    /// Returns `None` if it is not an encrypted token, or was encrypted with another key.
+    ///
+    /// # Arguments
+    ///
+    /// * `stored` - The encrypted token.
+    pub fn decrypt(&self, stored: &str) -> Option<String> {
+        let payload = STANDARD
+            .decode(stored.strip_prefix(ENCRYPTED_PREFIX)?)
+            .ok()?;
+        if payload.len() < NONCE_LENGTH {
+            return None;
+        }
+
+        let (nonce, ciphertext) = payload.split_at(NONCE_LENGTH);
+        let token = self
+            .aead
+            .decrypt(XNonce::from_slice(nonce), ciphertext)
+            .ok()?;
+
+        String::from_utf8(token).ok()
+    }
+}
@@ -0,0 +1,168 @@
+// Copyright 2025 - Andriel Ferreira
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// https://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or https://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+//! The airing episode notifications.
+
+use std::time::Duration;
+
+use chrono::{DateTime, Utc};
+use ferogram::Result;
+use grammers_client::{Client, InputMessage, button, reply_markup};
+use maplit::hashmap;
+
+use crate::{
+    models::{ChatSettings, FollowedAnime, Follower, TitleLanguage},
+    resources::{AniList, Database, I18n, anilist::airing::AiredEpisode},
+    utils,
+};
+
+/// How often the airing schedule is checked.
+pub const PERIOD: Duration = Duration::from_secs(5 * 60);
+
+/// How far back the airing schedule is checked, so a short downtime does not skip episodes.
+const LOOKBACK: Duration = Duration::from_secs(6 * 60 * 60);
+
+/// The delay between two notifications, keeping well under the flood limits.
+const SEND_DELAY: Duration = Duration::from_millis(50);
+
+/// Notifies the followers of the animes whose episodes aired recently.
+///
+/// Each follower is notified once per episode, even if sending the notification fails.
+///
+/// # Arguments
+///
+/// * `client` - The Telegram client.
+/// * `db` - The database resource.
+/// * `i18n` - The i18n resource.
+/// * `ani` - The AniList resource.
+///
+/// # Errors
+///
+/// Returns an error if the follows could not be queried or updated.
+pub async fn run(client: Client, db: Database, i18n: I18n, ani: AniList) -> Result<()> {
+    if !db.is_healthy() {
+        return Ok(());
+    }
+
+    let pool = db.pool();
+
+    let ids = db.report(FollowedAnime::followed_ids(pool).await)?;
+    if ids.is_empty() {
+        return Ok(());
+    }
+
+    let now = Utc::now().timestamp();
+    let from = now - LOOKBACK.as_secs() as i64;
+    let Some(episodes) = ani.get_aired_episodes(&ids, from, now + 1).await else {
+        log::warn!("failed to get the airing schedule of {} animes", ids.len());
+        return Ok(());
+    };
+
+    for episode in episodes {
+        let aired_at = DateTime::from_timestamp(episode.airing_at, 0).unwrap_or_default();
+        let followers = db.report(
+            FollowedAnime::pending(pool, episode.media_id, episode.episode, aired_at).await,
+        )?;
+
+        for follower in followers {
+            let Some(chat) = follower.packed_chat.as_deref().and_then(utils::unpack_chat) else {
+                log::debug!(
+                    "skipping the notification of {}, their chat is unknown",
+                    follower.user_id
+                );
+                continue;
+            };
+            let i18n = i18n.for_locale(
+                follower
+                    .language_code
+                    .as_deref()
+                    .unwrap_or(i18n.default_locale()),
+            );
+
+            if let Err(e) = client
+                .send_message(chat, gen_notification(&episode, &follower, &i18n))
+                .await
+            {
+                log::warn!(
+                    "failed to notify {} about episode {} of {}: {:?}",
+                    follower.user_id,
+                    episode.episode,
+                    episode.media_id,
+                    e
+                );
+            }
+
+            db.report(
+                FollowedAnime::mark_notified(
+                    pool,
+                    follower.user_id,
+                    episode.media_id,
+                    episode.episode,
+                )
+                .await,
+            )?;
+
+            tokio::time::sleep(SEND_DELAY).await;
+        }
+    }
+
+    Ok(())
+}
+
+/// Generates the notification of an aired episode.
+///
+/// # Arguments
+///
+/// * `episode` - The aired episode.
+/// * `follower` - The follower to notify.
+/// * `i18n` - The i18n resource, in the follower's locale.
+fn gen_notification(episode: &AiredEpisode, follower: &Follower, i18n: &I18n) -> InputMessage {
+    let t = |key: &str| i18n.translate(key);
+
+    let settings = ChatSettings {
+        title_language: follower
+            .title_language
+            .as_deref()
+            .and_then(TitleLanguage::parse)
+            .unwrap_or_default(),
+        ..Default::default()
+    };
+
+    InputMessage::html(gen_text(episode, &settings, i18n)).reply_markup(&reply_markup::inline(
+        vec![vec![
+            button::inline(
+                t("load_more_btn"),
+                utils::sign_callback(&format!(
+                    "anime {0} {1}",
+                    episode.media_id, follower.user_id
+                )),
+            ),
+            button::inline(
+                t("unfollow_btn"),
+                format!("unfollow {0} {1}", episode.media_id, follower.user_id),
+            ),
+        ]],
+    ))
+}
+
+/// Generates the text of the notification of an aired episode.
+///
+/// # Arguments
+///
+/// * `episode` - The aired episode.
+/// * `settings` - The follower's settings, for the title language.
+/// * `i18n` - The i18n resource, in the follower's locale.
+pub fn gen_text(episode: &AiredEpisode, settings: &ChatSettings, i18n: &I18n) -> String {
+    i18n.translate_with_args(
+        "episode_aired_notification",
+        hashmap! {
+            "episode" => episode.episode.to_string(),
+            "title" => utils::escape_html(utils::preferred_title(&episode.media.title, settings)),
+        },
+    )
+}
@@ -0,0 +1,89 @@
+// Copyright 2025 - Andriel Ferreira
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// https://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or https://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+//! The daily character birthday announcements.
+
+use std::time::Duration;
+
+use chrono::{Timelike, Utc};
+use ferogram::Result;
+use grammers_client::{Client, InputMessage};
+
+use crate::{
+    models::Group,
+    resources::{AniList, Database, I18n},
+    utils,
+};
+
+/// How often the hour of the announcements is checked.
+pub const PERIOD: Duration = Duration::from_secs(60 * 60);
+
+/// The hour of the day the birthdays are announced at, in UTC.
+///
+/// The task runs once per hour, so it only announces once a day.
+const ANNOUNCE_HOUR: u32 = 12;
+
+/// The delay between two announcements, keeping well under the flood limits.
+const SEND_DELAY: Duration = Duration::from_millis(50);
+
+/// Posts the characters whose birthday is today to the groups that opted in.
+///
+/// # Arguments
+///
+/// * `client` - The Telegram client.
+/// * `db` - The database resource.
+/// * `i18n` - The i18n resource.
+/// * `ani` - The AniList resource.
+///
+/// # Errors
+///
+/// Returns an error if the groups could not be queried.
+pub async fn run(client: Client, db: Database, i18n: I18n, ani: AniList) -> Result<()> {
+    if Utc::now().hour() != ANNOUNCE_HOUR || !db.is_healthy() {
+        return Ok(());
+    }
+
+    let groups = db.report(Group::with_birthdays(db.pool()).await)?;
+    if groups.is_empty() {
+        return Ok(());
+    }
+
+    let Some(characters) = ani
+        .get_birthday_characters(1)
+        .await
+        .filter(|characters| !characters.characters.is_empty())
+    else {
+        log::warn!("failed to get the birthday characters");
+        return Ok(());
+    };
+
+    for group in groups {
+        let Some(chat) = group.chat() else {
+            log::debug!(
+                "skipping the birthdays of {}, its chat is unknown",
+                group.id
+            );
+            continue;
+        };
+        let i18n = i18n.for_locale(&group.language_code);
+
+        if let Err(e) = client
+            .send_message(
+                chat,
+                InputMessage::html(utils::gen_birthday_list(&characters.characters, &i18n)),
+            )
+            .await
+        {
+            log::warn!("failed to announce the birthdays to {}: {:?}", group.id, e);
+        }
+
+        tokio::time::sleep(SEND_DELAY).await;
+    }
+
+    Ok(())
+}
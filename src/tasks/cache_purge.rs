@@ -0,0 +1,34 @@
+// Copyright 2025 - Andriel Ferreira
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// https://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or https://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+//! The purge of the expired cache entries.
+
+use std::time::Duration;
+
+use ferogram::Result;
+
+use crate::resources::{AniList, Sessions};
+
+/// How often the expired entries are purged.
+pub const PERIOD: Duration = Duration::from_secs(5 * 60);
+
+/// Removes the expired entries of the caches, which otherwise linger until they are
+/// looked up or evicted.
+///
+/// # Arguments
+///
+/// * `ani` - The AniList resource.
+/// * `sessions` - The sessions resource.
+pub async fn run(ani: AniList, sessions: Sessions) -> Result<()> {
+    let purged = ani.purge_expired().await + sessions.purge_expired().await;
+    if purged > 0 {
+        log::debug!("purged {} expired cache entries", purged);
+    }
+
+    Ok(())
+}
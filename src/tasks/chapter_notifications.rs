@@ -0,0 +1,190 @@
+// Copyright 2025 - Andriel Ferreira
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// https://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or https://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+//! The new chapter notifications.
+//!
+//! Mangas have no airing schedule, so the reading lists of the users who opted in are
+//! checked periodically, and the chapters of each manga are compared with the last check.
+
+use std::{collections::HashMap, time::Duration};
+
+use ferogram::Result;
+use grammers_client::{Client, InputMessage, button, reply_markup};
+use maplit::hashmap;
+
+use crate::{
+    models::{MangaProgressSnapshot, User},
+    resources::{
+        AniList, Database, I18n,
+        anilist::{graphql::QueryError, lists::UserListEntry},
+    },
+    utils,
+};
+
+/// The delay between two notifications, keeping well under the flood limits.
+const SEND_DELAY: Duration = Duration::from_millis(50);
+
+/// Notifies the users who opted in about the new chapters of the mangas they are reading.
+///
+/// The users are checked one by one, spread across the period so the AniList requests do
+/// not burst. Users whose token expired are skipped.
+///
+/// # Arguments
+///
+/// * `client` - The Telegram client.
+/// * `db` - The database resource.
+/// * `i18n` - The i18n resource.
+/// * `ani` - The AniList resource.
+/// * `period` - How often the reading lists are checked.
+///
+/// # Errors
+///
+/// Returns an error if the users or the snapshots could not be queried or updated.
+pub async fn run(
+    client: Client,
+    db: Database,
+    i18n: I18n,
+    ani: AniList,
+    period: Duration,
+) -> Result<()> {
+    if !db.is_healthy() {
+        return Ok(());
+    }
+
+    let pool = db.pool();
+
+    let users = db
+        .report(User::chapter_subscribers(pool).await)?
+        .into_iter()
+        .filter(|user| !user.token_expired())
+        .collect::<Vec<_>>();
+    if users.is_empty() {
+        return Ok(());
+    }
+
+    let delay = stagger_delay(period, users.len());
+    for user in users {
+        // Without their chat, the snapshots are kept so the chapters are notified later.
+        let (Some(anilist_id), Some(token), Some(chat)) =
+            (user.anilist_id, user.token(), user.chat())
+        else {
+            continue;
+        };
+
+        let entries = match ani.get_reading_list(anilist_id, &token).await {
+            Ok(entries) => entries,
+            Err(QueryError::RateLimited(retry_after)) => {
+                log::warn!(
+                    "rate limited while checking the reading lists, retrying in {:?}",
+                    retry_after
+                );
+                return Ok(());
+            }
+            Err(e) => {
+                log::debug!("failed to get the reading list of {}: {:?}", user.id, e);
+                tokio::time::sleep(delay).await;
+                continue;
+            }
+        };
+
+        let snapshots = db
+            .report(MangaProgressSnapshot::get_by_user(pool, user.id).await)?
+            .into_iter()
+            .map(|snapshot| (snapshot.manga_id, snapshot.chapters))
+            .collect::<HashMap<_, _>>();
+
+        let i18n = i18n.for_locale(&user.language_code);
+        for (entry, previous) in new_chapters(&snapshots, &entries) {
+            if let Err(e) = client
+                .send_message(chat, gen_notification(entry, previous, user.id, &i18n))
+                .await
+            {
+                log::warn!(
+                    "failed to notify {} about the chapters of {}: {:?}",
+                    user.id,
+                    entry.media_id,
+                    e
+                );
+            }
+
+            tokio::time::sleep(SEND_DELAY).await;
+        }
+
+        let chapters = entries
+            .iter()
+            .filter_map(|entry| Some((entry.media_id, entry.total?)))
+            .collect::<Vec<_>>();
+        db.report(MangaProgressSnapshot::replace(pool, user.id, &chapters).await)?;
+
+        tokio::time::sleep(delay).await;
+    }
+
+    Ok(())
+}
+
+/// Gets how long to wait between two users, spreading them across the period and leaving
+/// the last slot free so a run ends before the next one starts.
+///
+/// # Arguments
+///
+/// * `period` - How often the reading lists are checked.
+/// * `users` - How many users are checked.
+pub fn stagger_delay(period: Duration, users: usize) -> Duration {
+    period / (users as u32).saturating_add(1)
+}
+
+/// Gets the entries of a reading list that gained chapters since the last check, with the
+/// chapters they had.
+///
+/// Mangas not checked before, or whose chapters are unknown, are not reported.
+///
+/// # Arguments
+///
+/// * `snapshots` - The chapters of each manga at the last check, by its AniList ID.
+/// * `entries` - The entries of the reading list.
+pub fn new_chapters<'a>(
+    snapshots: &HashMap<i64, i32>,
+    entries: &'a [UserListEntry],
+) -> Vec<(&'a UserListEntry, i32)> {
+    entries
+        .iter()
+        .filter_map(|entry| {
+            let previous = *snapshots.get(&entry.media_id)?;
+            (entry.total? > previous).then_some((entry, previous))
+        })
+        .collect()
+}
+
+/// Generates the notification of a manga that gained chapters.
+///
+/// # Arguments
+///
+/// * `entry` - The entry of the manga.
+/// * `previous` - The chapters the manga had at the last check.
+/// * `user_id` - The Telegram user ID.
+/// * `i18n` - The i18n resource, in the user's locale.
+fn gen_notification(
+    entry: &UserListEntry,
+    previous: i32,
+    user_id: i64,
+    i18n: &I18n,
+) -> InputMessage {
+    let text = i18n.translate_with_args(
+        "chapter_released_notification",
+        hashmap! {
+            "title" => utils::escape_html(&entry.title),
+            "from" => previous.to_string(),
+            "to" => entry.total.unwrap_or_default().to_string(),
+        },
+    );
+
+    InputMessage::html(text).reply_markup(&reply_markup::inline(vec![vec![button::inline(
+        i18n.translate("load_more_btn"),
+        utils::sign_callback(&format!("manga {0} {1}", entry.media_id, user_id)),
+    )]]))
+}
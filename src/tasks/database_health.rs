@@ -0,0 +1,30 @@
+// Copyright 2025 - Andriel Ferreira
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// https://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or https://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+//! The database health probe.
+
+use std::time::Duration;
+
+use ferogram::Result;
+
+use crate::resources::Database;
+
+/// How often the database is probed.
+pub const PERIOD: Duration = Duration::from_secs(15);
+
+/// Probes the database, so the health flag follows it even if no other query runs: it is
+/// logged when the database becomes unreachable, and when it is reachable again.
+///
+/// # Arguments
+///
+/// * `db` - The database resource.
+pub async fn run(db: Database) -> Result<()> {
+    db.health_check().await;
+
+    Ok(())
+}
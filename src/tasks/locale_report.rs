@@ -0,0 +1,155 @@
+// Copyright 2025 - Andriel Ferreira
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// https://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or https://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+//! The weekly locale usage report.
+
+use std::{collections::BTreeMap, time::Duration};
+
+use chrono::{Datelike, Timelike, Utc, Weekday};
+use ferogram::Result;
+use grammers_client::{Client, InputMessage};
+use maplit::hashmap;
+
+use crate::{
+    models::{Group, User},
+    resources::{Database, I18n},
+    utils,
+};
+
+/// How often the day and hour of the report are checked.
+pub const PERIOD: Duration = Duration::from_secs(60 * 60);
+
+/// The day of the week the report is posted on, in UTC.
+const REPORT_WEEKDAY: Weekday = Weekday::Mon;
+
+/// The hour of the day the report is posted at, in UTC.
+///
+/// The task runs once per hour, so it only posts once a week, however often the bot restarts.
+const REPORT_HOUR: u32 = 12;
+
+/// The usage of a language code.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct LocaleUsage {
+    /// The language code.
+    pub language_code: String,
+    /// The number of users using the language.
+    pub users: i64,
+    /// The number of groups using the language.
+    pub groups: i64,
+    /// How much of the locale is translated, if it exists.
+    pub completeness: Option<f32>,
+}
+
+/// Collects the usage of each language code, including loaded locales nobody uses.
+///
+/// The result is sorted by user count, then by group count, in descending order. Ties keep the
+/// alphabetical order of the language codes.
+///
+/// # Arguments
+///
+/// * `users` - The number of users per language code.
+/// * `groups` - The number of groups per language code.
+/// * `i18n` - The i18n resource.
+pub fn collect_usage(
+    users: &[(String, i64)],
+    groups: &[(String, i64)],
+    i18n: &I18n,
+) -> Vec<LocaleUsage> {
+    let mut usage = BTreeMap::<String, LocaleUsage>::new();
+
+    for locale in i18n.locales() {
+        usage.entry(locale.clone()).or_default().language_code = locale;
+    }
+    for (language_code, count) in users {
+        let entry = usage.entry(language_code.clone()).or_default();
+        entry.language_code = language_code.clone();
+        entry.users += count;
+    }
+    for (language_code, count) in groups {
+        let entry = usage.entry(language_code.clone()).or_default();
+        entry.language_code = language_code.clone();
+        entry.groups += count;
+    }
+
+    let mut usage = usage
+        .into_values()
+        .map(|mut entry| {
+            entry.completeness = i18n.completeness(&entry.language_code);
+            entry
+        })
+        .collect::<Vec<_>>();
+    usage.sort_by(|a, b| b.users.cmp(&a.users).then(b.groups.cmp(&a.groups)));
+
+    usage
+}
+
+/// Generates the report text.
+///
+/// # Arguments
+///
+/// * `usage` - The usage of each language code.
+/// * `i18n` - The i18n resource.
+pub fn gen_report(usage: &[LocaleUsage], i18n: &I18n) -> String {
+    let mut text = format!("🌐 <b>{}</b>\n\n", i18n.translate("locale_report"));
+
+    for entry in usage {
+        let line = i18n.translate_with_args(
+            "locale_report_entry",
+            hashmap! {
+                "language" => entry.language_code.clone(),
+                "users" => utils::format_thousands(entry.users),
+                "groups" => utils::format_thousands(entry.groups),
+            },
+        );
+        let status = match entry.completeness {
+            Some(completeness) => i18n.translate_with_args(
+                "locale_report_complete",
+                hashmap! { "completeness" => format!("{:.0}", completeness) },
+            ),
+            None => i18n.translate("locale_report_missing"),
+        };
+
+        text.push_str(&format!("• {0}, {1}\n", line, status));
+    }
+
+    text
+}
+
+/// Posts the report to the log chat, on [`REPORT_WEEKDAY`] at [`REPORT_HOUR`].
+///
+/// # Arguments
+///
+/// * `client` - The Telegram client.
+/// * `chat_id` - The log chat ID, in the Bot API format.
+/// * `db` - The database resource.
+/// * `i18n` - The i18n resource.
+///
+/// # Errors
+///
+/// Returns an error if the counts could not be queried or the report could not be sent.
+pub async fn run(client: Client, chat_id: i64, db: Database, i18n: I18n) -> Result<()> {
+    let now = Utc::now();
+    if now.weekday() != REPORT_WEEKDAY || now.hour() != REPORT_HOUR {
+        return Ok(());
+    }
+
+    let pool = db.pool();
+
+    let users = User::count_by_language(pool).await?;
+    let groups = Group::count_by_language(pool).await?;
+    let usage = collect_usage(&users, &groups, &i18n);
+
+    client
+        .send_message(
+            utils::packed_chat(chat_id),
+            InputMessage::html(gen_report(&usage, &i18n)),
+        )
+        .await?;
+
+    Ok(())
+}
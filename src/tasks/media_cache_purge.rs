@@ -0,0 +1,41 @@
+// Copyright 2025 - Andriel Ferreira
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// https://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or https://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+//! The purge of the old media stored in the database.
+
+use std::time::Duration;
+
+use chrono::Utc;
+use ferogram::Result;
+
+use crate::{models::MediaCache, resources::Database};
+
+/// How often the old media are purged.
+pub const PERIOD: Duration = Duration::from_secs(60 * 60);
+
+/// How long the media are kept, as a fallback for when AniList is unreachable.
+pub const MAX_AGE: Duration = Duration::from_secs(7 * 24 * 60 * 60);
+
+/// Deletes the media stored in the database more than a week ago.
+///
+/// # Arguments
+///
+/// * `db` - The database resource.
+pub async fn run(db: Database) -> Result<()> {
+    if !db.is_healthy() {
+        return Ok(());
+    }
+
+    let before = Utc::now() - MAX_AGE;
+    let purged = db.report(MediaCache::delete_older_than(db.pool(), before).await)?;
+    if purged > 0 {
+        log::debug!("purged {} old stored media", purged);
+    }
+
+    Ok(())
+}
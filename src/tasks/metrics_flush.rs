@@ -0,0 +1,55 @@
+// Copyright 2025 - Andriel Ferreira
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// https://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or https://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+//! The metrics flush.
+
+use std::time::Duration;
+
+use chrono::Utc;
+use ferogram::Result;
+
+use crate::{
+    models::{CommandStat, StatKind},
+    resources::{Database, Metrics},
+};
+
+/// How often the metrics are flushed.
+pub const PERIOD: Duration = Duration::from_secs(60);
+
+/// Persists the callback counts gathered since the last flush into today's stats.
+///
+/// Counts that fail to be persisted are kept for the next flush.
+///
+/// # Arguments
+///
+/// * `metrics` - The metrics resource.
+/// * `db` - The database resource.
+pub async fn run(metrics: Metrics, db: Database) -> Result<()> {
+    let mut counts = metrics.take_callbacks();
+    if counts.is_empty() {
+        return Ok(());
+    }
+
+    let day = Utc::now().date_naive();
+    let verbs = counts.keys().cloned().collect::<Vec<_>>();
+
+    for verb in verbs {
+        let count = counts[&verb];
+
+        let result = db
+            .report(CommandStat::increment(db.pool(), day, StatKind::Callback, &verb, count).await);
+        if let Err(e) = result {
+            metrics.restore_callbacks(counts);
+            return Err(e.into());
+        }
+
+        counts.remove(&verb);
+    }
+
+    Ok(())
+}
@@ -0,0 +1,116 @@
+// Copyright 2025 - Andriel Ferreira
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// https://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or https://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+//! The Prometheus metrics server.
+
+use std::{net::IpAddr, time::Duration};
+
+use tokio::{
+    io::{AsyncReadExt, AsyncWriteExt},
+    net::{TcpListener, TcpStream},
+};
+
+use crate::resources::Metrics;
+
+/// The largest request head that is read, only its first line matters.
+const MAX_REQUEST_SIZE: usize = 8 * 1024;
+
+/// How long a client has to send its request.
+const READ_TIMEOUT: Duration = Duration::from_secs(5);
+
+/// Serves the metrics in the Prometheus text format at `/metrics`, until the listener fails.
+///
+/// # Arguments
+///
+/// * `address` - The address to listen at.
+/// * `port` - The port to listen at.
+/// * `metrics` - The metrics resource.
+///
+/// # Errors
+///
+/// Returns an error if the address could not be bound.
+pub async fn run(address: IpAddr, port: u16, metrics: Metrics) -> std::io::Result<()> {
+    let listener = TcpListener::bind((address, port)).await?;
+    log::info!("serving the metrics at {}:{}", address, port);
+
+    loop {
+        let stream = match listener.accept().await {
+            Ok((stream, _)) => stream,
+            Err(e) => {
+                log::warn!("failed to accept a metrics connection: {:?}", e);
+                continue;
+            }
+        };
+
+        let metrics = metrics.clone();
+        tokio::spawn(async move {
+            if let Err(e) = serve(stream, &metrics).await {
+                log::debug!("failed to serve the metrics: {:?}", e);
+            }
+        });
+    }
+}
+
+/// Answers a single request, closing the connection afterwards.
+///
+/// # Arguments
+///
+/// * `stream` - The connection.
+/// * `metrics` - The metrics resource.
+async fn serve(mut stream: TcpStream, metrics: &Metrics) -> std::io::Result<()> {
+    let mut buffer = vec![0; MAX_REQUEST_SIZE];
+    let mut read = 0;
+
+    // The body, if any, is ignored, so reading stops at the end of the head.
+    while read < buffer.len() && !buffer[..read].windows(4).any(|bytes| bytes == b"\r\n\r\n") {
+        let count = tokio::time::timeout(READ_TIMEOUT, stream.read(&mut buffer[read..]))
+            .await
+            .map_err(|_| std::io::ErrorKind::TimedOut)??;
+        if count == 0 {
+            break;
+        }
+        read += count;
+    }
+
+    let request = String::from_utf8_lossy(&buffer[..read]);
+    stream
+        .write_all(gen_response(&request, metrics).as_bytes())
+        .await?;
+    stream.shutdown().await
+}
+
+/// Generates the HTTP response to a request.
+///
+/// # Arguments
+///
+/// * `request` - The request, only its first line is read.
+/// * `metrics` - The metrics resource.
+pub fn gen_response(request: &str, metrics: &Metrics) -> String {
+    let mut request_line = request
+        .lines()
+        .next()
+        .unwrap_or_default()
+        .split_whitespace();
+    let method = request_line.next();
+    let path = request_line
+        .next()
+        .and_then(|target| target.split('?').next());
+
+    let (status, body) = match (method, path) {
+        (Some("GET"), Some("/metrics")) => ("200 OK", metrics.render()),
+        (Some("GET"), _) => ("404 Not Found", "Not Found\n".to_string()),
+        _ => ("405 Method Not Allowed", "Method Not Allowed\n".to_string()),
+    };
+
+    format!(
+        "HTTP/1.1 {0}\r\nContent-Type: text/plain; version=0.0.4; charset=utf-8\r\nContent-Length: {1}\r\nConnection: close\r\n\r\n{2}",
+        status,
+        body.len(),
+        body
+    )
+}
@@ -0,0 +1,171 @@
+// Copyright 2025 - Andriel Ferreira
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// https://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or https://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+//! Background tasks.
+
+pub mod airing_notifications;
+pub mod birthday_announcements;
+pub mod cache_purge;
+pub mod chapter_notifications;
+pub mod database_health;
+pub mod locale_report;
+pub mod media_cache_purge;
+pub mod metrics_flush;
+pub mod metrics_server;
+
+use std::time::Duration;
+
+use ferogram::Result;
+use grammers_client::Client;
+use tokio::time::{Instant, MissedTickBehavior};
+
+use crate::{
+    Config,
+    resources::{AniList, Database, I18n, Metrics, Sessions},
+};
+
+/// The tasks setup.
+///
+/// # Arguments
+///
+/// * `client` - The Telegram client.
+/// * `config` - The bot configuration.
+/// * `db` - The database resource.
+/// * `i18n` - The i18n resource.
+/// * `metrics` - The metrics resource.
+/// * `ani` - The AniList resource.
+/// * `sessions` - The sessions resource.
+pub fn setup(
+    client: &Client,
+    config: &Config,
+    db: &Database,
+    i18n: &I18n,
+    metrics: &Metrics,
+    ani: &AniList,
+    sessions: &Sessions,
+) {
+    {
+        let db = db.clone();
+
+        spawn_periodic("database_health", database_health::PERIOD, move || {
+            database_health::run(db.clone())
+        });
+    }
+
+    {
+        let (metrics, db) = (metrics.clone(), db.clone());
+
+        spawn_periodic("metrics_flush", metrics_flush::PERIOD, move || {
+            metrics_flush::run(metrics.clone(), db.clone())
+        });
+    }
+
+    {
+        let (client, db, i18n, ani) = (client.clone(), db.clone(), i18n.clone(), ani.clone());
+
+        spawn_periodic(
+            "airing_notifications",
+            airing_notifications::PERIOD,
+            move || {
+                airing_notifications::run(client.clone(), db.clone(), i18n.clone(), ani.clone())
+            },
+        );
+    }
+
+    {
+        let (client, db, i18n, ani) = (client.clone(), db.clone(), i18n.clone(), ani.clone());
+
+        spawn_periodic(
+            "birthday_announcements",
+            birthday_announcements::PERIOD,
+            move || {
+                birthday_announcements::run(client.clone(), db.clone(), i18n.clone(), ani.clone())
+            },
+        );
+    }
+
+    {
+        let (client, db, i18n, ani) = (client.clone(), db.clone(), i18n.clone(), ani.clone());
+        let period = Duration::from_secs(config.anilist.chapter_check_minutes.max(1) * 60);
+
+        spawn_periodic("chapter_notifications", period, move || {
+            chapter_notifications::run(
+                client.clone(),
+                db.clone(),
+                i18n.clone(),
+                ani.clone(),
+                period,
+            )
+        });
+    }
+
+    {
+        let db = db.clone();
+
+        spawn_periodic("media_cache_purge", media_cache_purge::PERIOD, move || {
+            media_cache_purge::run(db.clone())
+        });
+    }
+
+    {
+        let (ani, sessions) = (ani.clone(), sessions.clone());
+
+        spawn_periodic("cache_purge", cache_purge::PERIOD, move || {
+            cache_purge::run(ani.clone(), sessions.clone())
+        });
+    }
+
+    if let Some(port) = config.app.metrics_port {
+        let (address, metrics) = (config.app.metrics_address, metrics.clone());
+
+        tokio::spawn(async move {
+            if let Err(e) = metrics_server::run(address, port, metrics).await {
+                log::error!("the metrics server stopped: {:?}", e);
+            }
+        });
+    }
+
+    if let Some(chat_id) = config.telegram.log_chat {
+        let (client, db, i18n) = (client.clone(), db.clone(), i18n.clone());
+
+        spawn_periodic("locale_report", locale_report::PERIOD, move || {
+            locale_report::run(client.clone(), chat_id, db.clone(), i18n.clone())
+        });
+    }
+}
+
+/// Spawns a task that runs `job` once every `period`, starting one period from now.
+///
+/// Errors returned by the job are logged and the task keeps running.
+///
+/// # Arguments
+///
+/// * `name` - The name of the task, used in the logs.
+/// * `period` - How often the job runs.
+/// * `job` - The job to run.
+pub fn spawn_periodic<F, Fut>(name: &'static str, period: Duration, job: F)
+where
+    F: Fn() -> Fut + Send + 'static,
+    Fut: Future<Output = Result<()>> + Send + 'static,
+{
+    log::debug!("spawning task {:?} every {:?}", name, period);
+
+    tokio::spawn(async move {
+        let mut interval = tokio::time::interval_at(Instant::now() + period, period);
+        interval.set_missed_tick_behavior(MissedTickBehavior::Delay);
+
+        loop {
+            interval.tick().await;
+
+            log::trace!("running task {:?}", name);
+            if let Err(e) = job().await {
+                log::error!("task {:?} failed: {:?}", name, e);
+            }
+        }
+    });
+}
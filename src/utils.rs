@@ -8,12 +8,53 @@
 
 //! Utility functions.
 
-use chrono::{DateTime, Local};
-use chrono_humanize::{Accuracy, HumanTime, Tense};
-use grammers_client::button::{self, Inline};
-use rust_anilist::models::{Anime, Character, Format, Gender, Manga, Status, User};
+use std::{fmt::Display, sync::OnceLock, time::Duration};
 
-use crate::resources::i18n::I18n;
+use async_trait::async_trait;
+use chrono::{DateTime, Datelike, TimeZone, Utc};
+use ferogram::Context;
+use grammers_client::{
+    InputMessage, InvocationError,
+    button::{self, Inline},
+    grammers_tl_types as tl, parsers, reply_markup,
+    session::{PackedChat, PackedType},
+    types::{CallbackQuery, Message, inline},
+};
+use maplit::hashmap;
+use rust_anilist::models::{Anime, Character, Date, Format, Manga, Status, Studio, Title, User};
+
+use crate::{
+    commands::{HelpCategory, HelpEntry},
+    models::{ChatSettings, MediaType, TitleLanguage},
+    resources::{
+        Cards,
+        anilist::{
+            AniList, FetchError,
+            appearances::Appearance,
+            birthdays::BirthdayCharacter,
+            chapters::ExternalLink,
+            characters::MediaCharacter,
+            episodes::Episode,
+            genres::GenreEntry,
+            graphql::QueryError,
+            links::MediaLinks,
+            lists::UserListEntry,
+            recommendations::Recommendation,
+            staff::{Staff, VoicedCharacter, Work},
+            studios::Studio as StudioInfo,
+            top::TopEntry,
+            user_favourites::{Favourite, Favourites},
+            user_stats::{MediaStats, StatCount, UserStats},
+            voice_actors::VoiceActor,
+        },
+        callback_signer::{self, CallbackError},
+        correlations::CorrelationId,
+        i18n::I18n,
+        media_files::{MediaFiles, PhotoFile},
+        search_stash::{SearchResult, StashedSearch},
+        stats::Snapshot,
+    },
+};
 
 /// Escapes special HTML characters in a given text to their corresponding HTML entities.
 ///
@@ -23,7 +64,6 @@ use crate::resources::i18n::I18n;
 /// - `>` to `&gt;`
 /// - `"` to `&quot;`
 /// - `'` to `&#x27;`
-/// - `/` to `&#x2F;`
 ///
 /// # Arguments
 ///
@@ -33,445 +73,4049 @@ pub fn escape_html(text: impl Into<String>) -> String {
         .replace("&", "&amp;")
         .replace("<", "&lt;")
         .replace(">", "&gt;")
-        .replace(r"\", "&quot;")
+        .replace('"', "&quot;")
         .replace("'", "&#x27;")
-        .replace("/", "&#x2F;")
         .trim()
         .to_string()
 }
 
-/// Removes specific HTML tags from the given text.
+/// Checks whether a text is valid Telegram HTML.
 ///
-/// This function takes a string input and removes the following HTML tags and chars:
-/// `<i>`, `</i>`, `<p>`, `</p>`, `<br>`, `<br/>`, `<br />`, `<em>`, `</em>`, `<li>`, `</li>`,
-/// `<ol>`, `</ol>`, `<ul>`, `</ul>`, `<center>`, `</center>`, `<strong>`, `</strong>`, `<`, `>`,
-/// `&quot;`, `&#x27;`, `&#x2F;`.
+/// Only the tags Telegram supports are allowed, they must be balanced and properly nested,
+/// and `<`, `>` and `&` outside of tags must be escaped.
 ///
 /// # Arguments
 ///
-/// * `text` - A value that can be converted into a `String`.
-pub fn remove_html(text: impl Into<String>) -> String {
-    text.into()
-        .replace("<i>", "")
-        .replace("</i>", "")
-        .replace("<p>", "")
-        .replace("</p>", "")
-        .replace("<br>", "")
-        .replace("<br/>", "")
-        .replace("<br />", "")
-        .replace("<em>", "")
-        .replace("</em>", "")
-        .replace("<li>", "• ")
-        .replace("</li>", "")
-        .replace("<ol>", "")
-        .replace("</ol>", "")
-        .replace("<ul>", "")
-        .replace("</ul>", "")
-        .replace("<center>", "")
-        .replace("</center>", "")
-        .replace("<strong>", "")
-        .replace("</strong>", "")
-        .replace("<", "&lt;")
-        .replace(">", "&gt;")
-        .replace("&quot;", "\"")
-        .replace("&#x27;", "'")
-        .replace("&#x2F;", "/")
-        .trim()
-        .to_string()
-}
-
-/// Shortens a given text to a specified maximum length, appending "..." if truncated.
+/// * `text` - The text to check.
 ///
-/// # Arguments
+/// # Errors
 ///
-/// * `text` - The text to be shortened. It can be any type that implements the `ToString` trait.
-/// * `max_length` - The maximum length of the resulting string, including the ellipsis.
-pub fn shorten_text<T: ToString>(text: T, mut max_length: usize) -> String {
-    let text = text.to_string();
-    max_length -= 3;
+/// Returns a description of the first problem found.
+pub fn validate_html(text: &str) -> Result<(), String> {
+    /// The tags supported by Telegram.
+    const TAGS: [&str; 15] = [
+        "b",
+        "strong",
+        "i",
+        "em",
+        "u",
+        "ins",
+        "s",
+        "strike",
+        "del",
+        "a",
+        "code",
+        "pre",
+        "blockquote",
+        "tg-spoiler",
+        "span",
+    ];
 
-    if text.len() > max_length {
-        format!("{}...", text.chars().take(max_length).collect::<String>())
-    } else {
-        text.to_string()
-    }
-}
+    let mut open = Vec::new();
+    let mut rest = text;
 
-/// Generates a formatted string containing detailed information about an anime.
-///
-/// # Arguments
-///
-/// * `anime` - A reference to an `Anime` struct containing the anime details.
-/// * `i18n` - A reference to an `I18n` struct containing the translations.
-pub fn gen_anime_info(anime: &Anime, i18n: &I18n) -> String {
-    let t = |key: &str| i18n.translate(key);
+    while let Some(i) = rest.find(['<', '>', '&']) {
+        let (before, after) = rest.split_at(i);
+        let offset = text.len() - rest.len() + before.len();
 
-    let mut text = format!(
-        "<code>{0}</code> | <b>{1}</b>\n\n",
-        anime.id,
-        anime.title.romaji(),
-    );
+        if after.starts_with('>') {
+            return Err(format!("unescaped `>` at {}", offset));
+        } else if after.starts_with('&') {
+            let end = after
+                .find(';')
+                .filter(|end| {
+                    let entity = &after[1..*end];
+                    matches!(entity, "lt" | "gt" | "amp" | "quot")
+                        || entity.strip_prefix('#').is_some_and(|code| {
+                            code.parse::<u32>().is_ok()
+                                || code
+                                    .strip_prefix('x')
+                                    .is_some_and(|hex| u32::from_str_radix(hex, 16).is_ok())
+                        })
+                })
+                .ok_or(format!("unescaped `&` at {}", offset))?;
 
-    if anime.start_date.is_some() || anime.end_date.is_some() {
-        if let Some(date) = anime.start_date.as_ref() {
-            if date.is_valid() {
-                text.push_str(&format!(
-                    "📅 | <b>{0}</b>: <i>{1}</i>",
-                    t("date"),
-                    date.format("{dd}/{mm}/{yyyy}")
-                ));
-            }
+            rest = &after[end + 1..];
+            continue;
         }
 
-        if !matches!(anime.format, Format::Movie | Format::Music)
-            && !anime.start_date.eq(&anime.end_date)
-        {
-            if let Some(date) = anime.end_date.as_ref() {
-                if date.is_valid() {
-                    text.push_str(&format!(" - <i>{}</i>", date.format("{dd}/{mm}/{yyyy}")));
+        let end = after
+            .find('>')
+            .ok_or(format!("unclosed tag at {}", offset))?;
+        let tag = &after[1..end];
+
+        if let Some(name) = tag.strip_prefix('/') {
+            match open.pop() {
+                Some(expected) if expected == name.trim() => {}
+                Some(expected) => {
+                    return Err(format!(
+                        "`</{0}>` at {1} closes `<{2}>`",
+                        name, offset, expected
+                    ));
                 }
+                None => return Err(format!("`</{0}>` at {1} closes nothing", name, offset)),
+            }
+        } else {
+            let name = tag.split_whitespace().next().unwrap_or_default();
+            if !TAGS.contains(&name) {
+                return Err(format!("unsupported tag `<{0}>` at {1}", name, offset));
             }
+
+            open.push(name);
         }
 
-        text.push_str("\n");
+        rest = &after[end + 1..];
     }
 
-    if let Some(score) = anime.average_score {
-        text.push_str(&format!(
-            "🌟 | <b>{0}</b>: <i>{1:02}%</i>\n",
-            t("score"),
-            score
-        ));
+    match open.pop() {
+        Some(name) => Err(format!("`<{}>` is never closed", name)),
+        None => Ok(()),
     }
+}
 
-    text.push_str(&format!(
-        "{0} | <b>{1}</b>: <i>{2}</i>",
-        match anime.status {
-            Status::Hiatus => "🕰",
-            Status::Paused => "⏸",
-            Status::Current => "✔",
-            Status::Dropped => "❌",
-            Status::Planning => "📅",
-            Status::Finished => "🏁",
-            Status::Cancelled => "❌",
-            Status::Completed => "🏁",
-            Status::Releasing => "📆",
-            Status::Repeating => "🔁",
-            Status::NotYetReleased => "🔜",
-        },
-        t("status"),
-        anime.status
-    ));
+/// The format [`sanitize_html`] produces.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum HtmlTarget {
+    /// Plain text, e.g. for inline query descriptions and callback alerts.
+    Plain,
+    /// Telegram HTML, e.g. for the descriptions in the cards.
+    Telegram,
+}
 
-    if let Some(next_airing) = anime.next_airing_episode.as_ref() {
-        let at = DateTime::from_timestamp(next_airing.at, 0)
-            .expect("invalid timestamp")
-            .time();
-        let now = Local::now().time();
-        let remaining = now - at;
-        let human_time = HumanTime::from(remaining);
-        text.push_str(&format!(
-            " (<i>E<b>{0}</b> in {1}</i>)",
-            next_airing.episode,
-            human_time.to_text_en(Accuracy::Rough, Tense::Present)
-        ));
+/// Sanitizes the HTML of AniList descriptions.
+///
+/// Line breaks and paragraphs become newlines, list items become bullets and runs of blank
+/// lines are collapsed. Every other tag is stripped, except the ones Telegram supports when
+/// targeting it. Links become `text (url)` in plain text, and AniList `~!spoilers!~` become
+/// `<tg-spoiler>` in Telegram HTML, while they are left out of plain text.
+///
+/// # Arguments
+///
+/// * `text` - The HTML to sanitize.
+/// * `target` - The format to produce.
+pub fn sanitize_html(text: &str, target: HtmlTarget) -> String {
+    let mut sanitizer = Sanitizer::new(target);
+    let mut rest = text;
+
+    while let Some(c) = rest.chars().next() {
+        if c == '<'
+            && let Some((tag, after)) = parse_tag(rest)
+        {
+            sanitizer.tag(&tag);
+            rest = after;
+        } else if c == '&'
+            && let Some((decoded, after)) = parse_entity(rest)
+        {
+            sanitizer.text(decoded);
+            rest = after;
+        } else if let Some(after) = rest.strip_prefix("~!") {
+            sanitizer.open_spoiler();
+            rest = after;
+        } else if let Some(after) = rest.strip_prefix("!~")
+            && sanitizer.in_spoiler()
+        {
+            sanitizer.close_spoiler();
+            rest = after;
+        } else {
+            sanitizer.text(c);
+            rest = &rest[c.len_utf8()..];
+        }
     }
 
-    text.push_str("\n");
+    sanitizer.finish()
+}
 
-    text.push_str(&format!(
-        "{0} | <b>{1}</b>: <i>{2}</i>\n",
-        match anime.format {
-            Format::Tv => "📺",
-            Format::Ona => "🎞",
-            Format::Ova => "🎞",
-            Format::Movie => "🎥",
-            Format::Music => "🎵",
-            Format::OneShot => "📖",
-            Format::Special => "🎌",
-            Format::TvShort => "📺",
-            _ => "📖",
-        },
-        t("format"),
-        anime.format
-    ));
+/// An HTML tag found by [`parse_tag`].
+#[derive(Debug)]
+struct Tag {
+    /// The name of the tag, in lowercase.
+    name: String,
+    /// Whether it is a closing tag.
+    closing: bool,
+    /// The `href` attribute of the tag, if any.
+    href: Option<String>,
+}
 
-    if let Some(genres) = anime.genres.as_ref() {
-        text.push_str(&format!(
-            "🎭 | <b>{0}</b>: <i>{1}</i>\n",
-            t("genres"),
-            genres
-                .iter()
-                .map(|genre| format!("#{}", genre.replace("-", "").replace(" ", "_")))
-                .collect::<Vec<_>>()
-                .join(" ")
-        ));
+/// Parses the tag at the start of the text, returning it and the text after it.
+///
+/// A `<` not followed by a tag name, e.g. `a < b` or `<3`, is not a tag.
+fn parse_tag(text: &str) -> Option<(Tag, &str)> {
+    let end = text.find('>')?;
+    let inner = &text[1..end];
+    if inner.contains('<') {
+        return None;
     }
 
-    if !matches!(anime.format, Format::Movie | Format::Music) {
-        if let Some(episodes) = anime.episodes {
-            text.push_str(&format!(
-                "🎞 | <b>{0}</b>: <i>{1}</i>\n",
-                t("episodes"),
-                episodes
-            ));
-        }
+    let (closing, body) = match inner.strip_prefix('/') {
+        Some(body) => (true, body),
+        None => (false, inner),
+    };
+    if !body.starts_with(|c: char| c.is_ascii_alphabetic()) {
+        return None;
     }
 
-    if !anime.description.is_empty() {
-        text.push_str(&format!(
-            "\n<blockquote expandable><i>{}</i></blockquote>\n",
-            shorten_text(remove_html(&anime.description), 500).as_str()
-        ));
-    }
+    let name_end = body
+        .find(|c: char| !c.is_ascii_alphanumeric() && c != '-')
+        .unwrap_or(body.len());
+    let tag = Tag {
+        name: body[..name_end].to_ascii_lowercase(),
+        closing,
+        href: parse_href(&body[name_end..]),
+    };
 
-    text
+    Some((tag, &text[end + 1..]))
 }
 
-/// Generates a formatted string containing detailed information about a manga.
-///
-/// # Arguments
-///
-/// * `manga` - A reference to an `Manga` struct containing the manga details.
-/// * `i18n` - A reference to an `I18n` struct containing the translations.
-pub fn gen_manga_info(manga: &Manga, i18n: &I18n) -> String {
-    let t = |key: &str| i18n.translate(key);
+/// Gets the `href` attribute out of the attributes of a tag.
+fn parse_href(attributes: &str) -> Option<String> {
+    let start = attributes.to_ascii_lowercase().find("href")?;
+    let value = attributes[start + 4..]
+        .trim_start()
+        .strip_prefix('=')?
+        .trim_start();
 
-    let mut text = format!(
-        "<code>{0}</code> | <b>{1}</b>\n\n",
-        manga.id,
-        manga.title.romaji(),
-    );
+    let value = match value.chars().next()? {
+        quote @ ('"' | '\'') => value[1..].split(quote).next()?,
+        _ => value.split_whitespace().next()?,
+    };
 
-    if manga.start_date.is_some() || manga.end_date.is_some() {
-        if let Some(date) = manga.start_date.as_ref() {
-            if date.is_valid() {
-                text.push_str(&format!(
-                    "📅 | <b>{0}</b>: <i>{1}</i>",
-                    t("date"),
-                    date.format("{dd}/{mm}/{yyyy}")
-                ));
-            }
+    let mut href = String::new();
+    let mut rest = value;
+    while let Some(c) = rest.chars().next() {
+        if c == '&'
+            && let Some((decoded, after)) = parse_entity(rest)
+        {
+            href.push(decoded);
+            rest = after;
+        } else {
+            href.push(c);
+            rest = &rest[c.len_utf8()..];
         }
+    }
 
-        if !matches!(manga.format, Format::Music) && !manga.start_date.eq(&manga.end_date) {
-            if let Some(date) = manga.end_date.as_ref() {
-                if date.is_valid() {
-                    text.push_str(&format!(" - <i>{}</i>", date.format("{dd}/{mm}/{yyyy}")));
-                }
-            }
+    Some(href.trim().to_string()).filter(|href| !href.is_empty())
+}
+
+/// Parses the entity at the start of the text, returning its char and the text after it.
+fn parse_entity(text: &str) -> Option<(char, &str)> {
+    let end = text.find(';').filter(|end| *end < 12)?;
+
+    let decoded = match &text[1..end] {
+        "amp" => '&',
+        "lt" => '<',
+        "gt" => '>',
+        "quot" => '"',
+        "apos" => '\'',
+        "nbsp" => ' ',
+        entity => {
+            let code = entity.strip_prefix('#')?;
+            let code = match code.strip_prefix(['x', 'X']) {
+                Some(hex) => u32::from_str_radix(hex, 16).ok()?,
+                None => code.parse::<u32>().ok()?,
+            };
+
+            char::from_u32(code)?
         }
+    };
 
-        text.push_str("\n");
-    }
+    Some((decoded, &text[end + 1..]))
+}
 
-    if let Some(average_score) = manga.average_score {
-        text.push_str(&format!(
-            "🌟 | <b>{0}</b>: <i>{1:02}%</i>\n",
-            t("score"),
-            average_score
-        ));
+/// The state of [`sanitize_html`].
+struct Sanitizer {
+    /// The format to produce.
+    target: HtmlTarget,
+    /// The text produced so far.
+    out: String,
+    /// The tags opened in the text produced, when targeting Telegram.
+    open: Vec<&'static str>,
+    /// The links being read and where their text starts, when targeting plain text.
+    links: Vec<(Option<String>, usize)>,
+    /// How many spoilers are being read.
+    spoilers: usize,
+    /// Whether a space right after a hidden spoiler is skipped, to avoid doubling it.
+    skip_space: bool,
+}
+
+impl Sanitizer {
+    /// Creates a new instance of the sanitizer.
+    fn new(target: HtmlTarget) -> Self {
+        Self {
+            target,
+            out: String::new(),
+            open: Vec::new(),
+            links: Vec::new(),
+            spoilers: 0,
+            skip_space: false,
+        }
     }
 
-    text.push_str(&format!(
-        "{0} | <b>{1}</b>: <i>{2}</i>\n",
-        match manga.status {
-            Status::Hiatus => "🕰",
-            Status::Paused => "⏸",
-            Status::Current => "✔",
-            Status::Dropped => "❌",
-            Status::Planning => "📅",
-            Status::Finished => "🏁",
-            Status::Cancelled => "❌",
-            Status::Completed => "🏁",
-            Status::Releasing => "📆",
-            Status::Repeating => "🔁",
-            Status::NotYetReleased => "🔜",
-        },
-        t("status"),
-        manga.status
-    ));
+    /// Whether the text is hidden, i.e. a spoiler in plain text.
+    fn hidden(&self) -> bool {
+        self.target == HtmlTarget::Plain && self.spoilers > 0
+    }
 
-    text.push_str(&format!(
-        "{0} | <b>{1}</b>: <i>{2}</i>\n",
-        match manga.format {
-            Format::Novel => "📖",
-            Format::Manga => "📚",
-            Format::Music => "🎵",
-            Format::OneShot => "📖",
-            Format::Special => "🎌",
-            _ => "🎥",
-        },
-        t("format"),
-        manga.format
-    ));
+    /// Adds a char of text.
+    fn text(&mut self, c: char) {
+        if self.hidden() || std::mem::take(&mut self.skip_space) && c == ' ' {
+            return;
+        }
 
-    if let Some(genres) = manga.genres.as_ref() {
-        text.push_str(&format!(
-            "🎭 | <b>{0}</b>: <i>{1}</i>\n",
-            t("genres"),
-            genres
-                .iter()
-                .map(|genre| format!("#{}", genre.replace("-", "").replace(" ", "_")))
-                .collect::<Vec<_>>()
-                .join(" ")
-        ));
+        match (self.target, c) {
+            (HtmlTarget::Telegram, '&') => self.out.push_str("&amp;"),
+            (HtmlTarget::Telegram, '<') => self.out.push_str("&lt;"),
+            (HtmlTarget::Telegram, '>') => self.out.push_str("&gt;"),
+            _ => self.out.push(c),
+        }
     }
 
-    if !matches!(manga.format, Format::Music) {
-        if let Some(chapters) = manga.chapters {
-            text.push_str(&format!(
-                "🔢 | <b>{0}</b>: <i>{1}</i>\n",
-                t("chapters"),
-                chapters
-            ));
+    /// Adds a line break.
+    fn newline(&mut self) {
+        if !self.hidden() {
+            self.out.push('\n');
         }
     }
 
-    if let Some(volumes) = manga.volumes {
-        text.push_str(&format!(
-            "📚 | <b>{0}</b>: <i>{1}</i>\n",
-            t("volumes"),
-            volumes
-        ));
-    }
+    /// Handles a tag.
+    fn tag(&mut self, tag: &Tag) {
+        match (tag.name.as_str(), tag.closing) {
+            ("br" | "ul" | "ol", _) => self.newline(),
+            ("p" | "div" | "h1" | "h2" | "h3" | "h4" | "h5" | "h6", true) => {
+                self.newline();
+                self.newline();
+            }
+            ("li", false) => {
+                if !self.out.is_empty() && !self.out.ends_with('\n') {
+                    self.newline();
+                }
+                if !self.hidden() {
+                    self.out.push_str("• ");
+                }
+            }
+            ("li", true) => self.newline(),
+            ("a", false) => self.open_link(tag.href.as_deref()),
+            ("a", true) => self.close_link(),
+            (name, closing) => {
+                let Some(name) = (match name {
+                    "b" | "strong" => Some("b"),
+                    "i" | "em" => Some("i"),
+                    "u" | "ins" => Some("u"),
+                    "s" | "strike" | "del" => Some("s"),
+                    "code" => Some("code"),
+                    _ => None,
+                }) else {
+                    return;
+                };
 
-    if !manga.description.is_empty() {
-        text.push_str(&format!(
-            "\n<blockquote expandable><i>{}</i></blockquote>\n",
-            shorten_text(remove_html(&manga.description), 350).as_str()
-        ));
+                if closing {
+                    self.close(name);
+                } else {
+                    self.open(name, None);
+                }
+            }
+        }
     }
 
-    text
-}
+    /// Opens a Telegram tag, when targeting Telegram.
+    fn open(&mut self, name: &'static str, attributes: Option<String>) {
+        if self.target != HtmlTarget::Telegram {
+            return;
+        }
 
-/// Generates a formatted string containing detailed information about a user.
-///
-/// # Arguments
-///
-/// * `user` - A reference to an `User` struct containing the user details.
-pub fn gen_user_info(user: &User) -> String {
-    let mut text = format!("<code>{0}</code> | <b>{1}</b>\n", user.id, user.name);
+        match attributes {
+            Some(attributes) => self.out.push_str(&format!("<{} {}>", name, attributes)),
+            None => self.out.push_str(&format!("<{}>", name)),
+        }
+        self.open.push(name);
+    }
 
-    if let Some(about) = user.about.as_ref() {
-        text.push_str(&format!(
-            "\n<blockquote expandable>{}</blockquote>\n",
-            shorten_text(remove_html(about), 300)
-        ));
+    /// Closes a Telegram tag, along with the tags opened inside it.
+    ///
+    /// Tags that are not open are ignored.
+    fn close(&mut self, name: &str) {
+        if let Some(position) = self.open.iter().rposition(|open| *open == name) {
+            for open in self.open.drain(position..).rev() {
+                self.out.push_str(&format!("</{}>", open));
+            }
+        }
     }
 
-    text.push_str(&format!(
-        "\n🔗 | <a href=\"https://anilist.co/user/{}\">AniList</a>",
-        user.id
-    ));
+    /// Opens a link.
+    fn open_link(&mut self, href: Option<&str>) {
+        let href = href.filter(|href| {
+            let href = href.to_ascii_lowercase();
+            href.starts_with("https://") || href.starts_with("http://")
+        });
+
+        match self.target {
+            HtmlTarget::Telegram => {
+                // Telegram does not allow nested links.
+                if let Some(href) = href
+                    && !self.open.contains(&"a")
+                {
+                    let href = href
+                        .replace('&', "&amp;")
+                        .replace('"', "&quot;")
+                        .replace('<', "&lt;")
+                        .replace('>', "&gt;");
+                    self.open("a", Some(format!("href=\"{}\"", href)));
+                }
+            }
+            HtmlTarget::Plain => self.links.push((href.map(str::to_string), self.out.len())),
+        }
+    }
+
+    /// Closes a link.
+    fn close_link(&mut self) {
+        match self.target {
+            HtmlTarget::Telegram => self.close("a"),
+            HtmlTarget::Plain => {
+                if let Some((Some(href), start)) = self.links.pop()
+                    && !self.hidden()
+                    && self.out[start..].trim() != href
+                {
+                    self.out.push_str(&format!(" ({})", href));
+                }
+            }
+        }
+    }
+
+    /// Whether a spoiler is being read.
+    fn in_spoiler(&self) -> bool {
+        self.spoilers > 0
+    }
+
+    /// Opens a spoiler.
+    fn open_spoiler(&mut self) {
+        if self.spoilers == 0 {
+            self.open("tg-spoiler", None);
+        }
+        self.spoilers += 1;
+    }
+
+    /// Closes a spoiler.
+    fn close_spoiler(&mut self) {
+        self.spoilers -= 1;
+        if self.spoilers == 0 {
+            self.close("tg-spoiler");
+            self.skip_space = self.target == HtmlTarget::Plain && self.out.ends_with(' ');
+        }
+    }
+
+    /// Closes the tags left open and tidies up the blank lines.
+    fn finish(mut self) -> String {
+        for open in self.open.drain(..).rev() {
+            self.out.push_str(&format!("</{}>", open));
+        }
+
+        let mut text = String::new();
+        let mut blank = false;
+        for line in self.out.lines().map(str::trim_end) {
+            if line.trim().is_empty() {
+                blank = !text.is_empty();
+                continue;
+            }
+
+            if !text.is_empty() {
+                text.push_str(if blank { "\n\n" } else { "\n" });
+            }
+            text.push_str(if text.is_empty() {
+                line.trim_start()
+            } else {
+                line
+            });
+            blank = false;
+        }
+
+        text
+    }
+}
+
+/// Shortens a given Telegram HTML to a specified maximum length, appending "..." if truncated.
+///
+/// Like [`shorten_text`], but only the visible chars are counted, and tags are never cut and
+/// are closed after the ellipsis.
+///
+/// # Arguments
+///
+/// * `html` - The Telegram HTML to be shortened.
+/// * `max_length` - The maximum length of the visible text, including the ellipsis.
+pub fn shorten_html(html: &str, max_length: usize) -> String {
+    /// The ellipsis appended to the shortened texts.
+    const ELLIPSIS: &str = "...";
+    /// How far back from the cut a word boundary is looked for.
+    const BOUNDARY_WINDOW: usize = 15;
+
+    /// Splits the next token, a tag, an entity or a char, off the HTML.
+    fn next_token(html: &str) -> Option<(&str, &str)> {
+        let c = html.chars().next()?;
+        let end = match c {
+            '<' => html.find('>').map_or(1, |end| end + 1),
+            '&' => html
+                .find(';')
+                .filter(|end| *end < 12)
+                .map_or(1, |end| end + 1),
+            _ => c.len_utf8(),
+        };
+
+        Some(html.split_at(end))
+    }
+
+    let visible = {
+        let mut visible = 0;
+        let mut rest = html;
+        while let Some((token, after)) = next_token(rest) {
+            if !token.starts_with('<') {
+                visible += 1;
+            }
+            rest = after;
+        }
+
+        visible
+    };
+    if visible <= max_length {
+        return html.to_string();
+    }
+
+    let (budget, ellipsis) = if max_length <= ELLIPSIS.len() {
+        (max_length, "")
+    } else {
+        (max_length - ELLIPSIS.len(), ELLIPSIS)
+    };
+
+    let mut out = String::new();
+    let mut open = Vec::<&str>::new();
+    let mut boundary = None;
+    let mut count = 0;
+    let mut rest = html;
+
+    while let Some((token, after)) = next_token(rest) {
+        if let Some(tag) = token.strip_prefix('<') {
+            let tag = tag.trim_end_matches('>');
+            match tag.strip_prefix('/') {
+                Some(name) => {
+                    if let Some(position) = open.iter().rposition(|open| *open == name.trim()) {
+                        open.truncate(position);
+                    }
+                }
+                None => open.push(tag.split_whitespace().next().unwrap_or_default()),
+            }
+
+            out.push_str(token);
+            rest = after;
+            continue;
+        }
+
+        let whitespace = token.chars().all(char::is_whitespace);
+        if count == budget {
+            // The cut falls inside a word, move it back to the start of that word.
+            if !whitespace && let Some((length, tags)) = boundary.take() {
+                out.truncate(length);
+                open = tags;
+            }
+            break;
+        }
+
+        if whitespace && count > 0 && count + BOUNDARY_WINDOW >= budget {
+            boundary = Some((out.len(), open.clone()));
+        }
+
+        out.push_str(token);
+        count += 1;
+        rest = after;
+    }
+
+    let mut shortened = out.trim_end().to_string() + ellipsis;
+    for name in open.into_iter().rev() {
+        shortened.push_str(&format!("</{}>", name));
+    }
+
+    shortened
+}
+
+/// Removes the HTML from the given text, leaving plain text.
+///
+/// See [`sanitize_html`] for how the tags are handled.
+///
+/// # Arguments
+///
+/// * `text` - A value that can be converted into a `String`.
+pub fn remove_html(text: impl Into<String>) -> String {
+    sanitize_html(&text.into(), HtmlTarget::Plain)
+}
+
+/// Shortens a given text to a specified maximum length, appending "..." if truncated.
+///
+/// The length is counted in chars. The text is cut at the last word boundary among its last
+/// 15 kept chars, if any. Limits too small for the ellipsis cut the text without it.
+///
+/// # Arguments
+///
+/// * `text` - The text to be shortened. It can be any type that implements the `ToString` trait.
+/// * `max_length` - The maximum length of the resulting string, including the ellipsis.
+pub fn shorten_text<T: ToString>(text: T, max_length: usize) -> String {
+    /// The ellipsis appended to the shortened texts.
+    const ELLIPSIS: &str = "...";
+    /// How far back from the cut a word boundary is looked for.
+    const BOUNDARY_WINDOW: usize = 15;
+
+    let text = text.to_string();
+    let chars = text.chars().collect::<Vec<_>>();
+
+    if chars.len() <= max_length {
+        return text;
+    } else if max_length <= ELLIPSIS.len() {
+        return chars[..max_length].iter().collect();
+    }
+
+    let budget = max_length - ELLIPSIS.len();
+    let mut end = budget;
+
+    // The cut falls inside a word, move it back to the start of that word.
+    let window_start = budget.saturating_sub(BOUNDARY_WINDOW);
+    if !chars[budget].is_whitespace()
+        && let Some(space) = chars[window_start..budget]
+            .iter()
+            .rposition(|c| c.is_whitespace())
+            .map(|space| window_start + space)
+        && chars[..space].iter().any(|c| !c.is_whitespace())
+    {
+        end = space;
+    }
+
+    let kept = chars[..end].iter().collect::<String>();
+    format!("{}{}", kept.trim_end(), ELLIPSIS)
+}
+
+/// The order of search results.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub enum SearchSort {
+    /// How well the title matches the search, the AniList default.
+    #[default]
+    Relevance,
+    /// The most popular first.
+    Popularity,
+    /// The best rated first.
+    Score,
+    /// The most recent first.
+    Newest,
+}
+
+impl SearchSort {
+    /// All the search orders, in the order they are displayed.
+    pub const ALL: [SearchSort; 4] = [
+        SearchSort::Relevance,
+        SearchSort::Popularity,
+        SearchSort::Score,
+        SearchSort::Newest,
+    ];
+
+    /// Parses a search order from its name.
+    ///
+    /// # Arguments
+    ///
+    /// * `name` - The name of the order, e.g. `popularity`.
+    pub fn parse(name: &str) -> Option<Self> {
+        match name.to_lowercase().as_str() {
+            "relevance" => Some(Self::Relevance),
+            "popularity" | "popular" => Some(Self::Popularity),
+            "score" => Some(Self::Score),
+            "newest" | "new" => Some(Self::Newest),
+            _ => None,
+        }
+    }
+
+    /// Gets the name of the order, as accepted by [`SearchSort::parse`].
+    pub fn name(&self) -> &'static str {
+        match self {
+            Self::Relevance => "relevance",
+            Self::Popularity => "popularity",
+            Self::Score => "score",
+            Self::Newest => "newest",
+        }
+    }
+
+    /// Gets the AniList `MediaSort` values of the order.
+    pub fn as_anilist(&self) -> &'static [&'static str] {
+        match self {
+            Self::Relevance => &["SEARCH_MATCH"],
+            Self::Popularity => &["POPULARITY_DESC", "SEARCH_MATCH"],
+            Self::Score => &["SCORE_DESC", "SEARCH_MATCH"],
+            Self::Newest => &["START_DATE_DESC", "SEARCH_MATCH"],
+        }
+    }
+}
+
+/// A season of the year, as AniList groups the anime releases.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum AnimeSeason {
+    /// December to February.
+    Winter,
+    /// March to May.
+    Spring,
+    /// June to August.
+    Summer,
+    /// September to November.
+    Fall,
+}
+
+impl AnimeSeason {
+    /// The keywords accepted by [`AnimeSeason::parse`], one per season.
+    pub const KEYWORDS: [&str; 4] = ["winter", "spring", "summer", "fall"];
+
+    /// Parses a season from its name, in English or Portuguese.
+    ///
+    /// # Arguments
+    ///
+    /// * `name` - The name of the season, e.g. `spring`.
+    pub fn parse(name: &str) -> Option<Self> {
+        match name.to_lowercase().as_str() {
+            "winter" | "inverno" => Some(Self::Winter),
+            "spring" | "primavera" => Some(Self::Spring),
+            "summer" | "verão" | "verao" => Some(Self::Summer),
+            "fall" | "autumn" | "outono" => Some(Self::Fall),
+            _ => None,
+        }
+    }
+
+    /// Gets the season and its year of a date.
+    ///
+    /// December belongs to the winter of the next year, as on AniList.
+    ///
+    /// # Arguments
+    ///
+    /// * `date` - The date.
+    pub fn of<Tz: TimeZone>(date: &DateTime<Tz>) -> (Self, i32) {
+        match date.month() {
+            12 => (Self::Winter, date.year() + 1),
+            1..=2 => (Self::Winter, date.year()),
+            3..=5 => (Self::Spring, date.year()),
+            6..=8 => (Self::Summer, date.year()),
+            _ => (Self::Fall, date.year()),
+        }
+    }
+
+    /// Gets the name of the season, as accepted by [`AnimeSeason::parse`].
+    pub fn name(&self) -> &'static str {
+        match self {
+            Self::Winter => "winter",
+            Self::Spring => "spring",
+            Self::Summer => "summer",
+            Self::Fall => "fall",
+        }
+    }
+
+    /// Gets the AniList `MediaSeason` value of the season.
+    pub fn as_anilist(&self) -> &'static str {
+        match self {
+            Self::Winter => "WINTER",
+            Self::Spring => "SPRING",
+            Self::Summer => "SUMMER",
+            Self::Fall => "FALL",
+        }
+    }
+}
+
+/// A ranking of the best scored media, as listed by `/top`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum TopKind {
+    /// All the animes.
+    Anime,
+    /// All the mangas.
+    Manga,
+    /// The anime movies.
+    Movie,
+    /// The animes releasing right now.
+    Airing,
+}
+
+impl TopKind {
+    /// The keywords accepted by [`TopKind::parse`], one per ranking.
+    pub const KEYWORDS: [&str; 4] = ["anime", "manga", "movie", "airing"];
+
+    /// Parses a ranking from its name, in English or Portuguese.
+    ///
+    /// # Arguments
+    ///
+    /// * `name` - The name of the ranking, e.g. `airing`.
+    pub fn parse(name: &str) -> Option<Self> {
+        match name.to_lowercase().as_str() {
+            "anime" | "animes" => Some(Self::Anime),
+            "manga" | "mangas" | "mangá" | "mangás" => Some(Self::Manga),
+            "movie" | "movies" | "filme" | "filmes" => Some(Self::Movie),
+            "airing" | "lançando" | "lancando" => Some(Self::Airing),
+            _ => None,
+        }
+    }
+
+    /// Gets the name of the ranking, as accepted by [`TopKind::parse`].
+    pub fn name(&self) -> &'static str {
+        match self {
+            Self::Anime => "anime",
+            Self::Manga => "manga",
+            Self::Movie => "movie",
+            Self::Airing => "airing",
+        }
+    }
+
+    /// Gets the media type of the ranking.
+    pub fn media_type(&self) -> MediaType {
+        match self {
+            Self::Manga => MediaType::Manga,
+            _ => MediaType::Anime,
+        }
+    }
+
+    /// Gets the AniList `MediaFormat` the ranking is restricted to, if any.
+    pub fn format(&self) -> Option<&'static str> {
+        match self {
+            Self::Movie => Some("MOVIE"),
+            _ => None,
+        }
+    }
+
+    /// Gets the AniList `MediaStatus` the ranking is restricted to, if any.
+    pub fn status(&self) -> Option<&'static str> {
+        match self {
+            Self::Airing => Some("RELEASING"),
+            _ => None,
+        }
+    }
+}
+
+/// A parsed search query.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct SearchQuery {
+    /// The search terms, without the filter tokens.
+    pub terms: String,
+    /// The order of the results.
+    pub sort: SearchSort,
+}
+
+/// Parses a search query, extracting the `sort:` token.
+///
+/// When no order is given, a single short word (e.g. `love`) is too ambiguous for the relevance
+/// order and is searched by popularity instead.
+///
+/// # Arguments
+///
+/// * `text` - The search text, e.g. `one piece sort:score`.
+pub fn parse_search_query(text: &str) -> SearchQuery {
+    let mut terms = Vec::new();
+    let mut sort = None;
+
+    for word in text.split_whitespace() {
+        if let Some(name) = word.strip_prefix("sort:") {
+            if let Some(parsed) = SearchSort::parse(name) {
+                sort = Some(parsed);
+                continue;
+            }
+        }
+
+        terms.push(word);
+    }
+
+    let sort = sort.unwrap_or_else(|| {
+        if is_common_word(&terms) {
+            SearchSort::Popularity
+        } else {
+            SearchSort::Relevance
+        }
+    });
+
+    SearchQuery {
+        terms: terms.join(" "),
+        sort,
+    }
+}
+
+/// Gets the search query of a command from the text of the message it replies to.
+///
+/// The whitespace is collapsed and the text is cut at a word boundary, up to 80 chars. The
+/// cards of the bot start with `<id> | <title>`, their ID is used instead.
+///
+/// Returns `None` for messages without text, e.g. media.
+///
+/// # Arguments
+///
+/// * `text` - The text of the replied message, without its entities.
+/// * `from_bot` - Whether the replied message was sent by the bot.
+pub fn reply_query(text: &str, from_bot: bool) -> Option<String> {
+    /// How long the queries taken from the replies can be, in chars.
+    const MAX_LENGTH: usize = 80;
+
+    if from_bot
+        && let Some((id, _)) = text.lines().next().and_then(|line| line.split_once(" | "))
+        && !id.is_empty()
+        && id.bytes().all(|byte| byte.is_ascii_digit())
+    {
+        return Some(id.to_string());
+    }
+
+    let mut query = String::new();
+    for word in text.split_whitespace() {
+        let length = query.chars().count();
+
+        if length == 0 {
+            query = word.chars().take(MAX_LENGTH).collect();
+        } else if length + 1 + word.chars().count() <= MAX_LENGTH {
+            query.push(' ');
+            query.push_str(word);
+        } else {
+            break;
+        }
+    }
+
+    (!query.is_empty()).then_some(query)
+}
+
+/// Checks whether an inline query is too short to be searched yet.
+///
+/// IDs are searched whatever their length, e.g. `!u 12`.
+///
+/// # Arguments
+///
+/// * `arg` - The search terms of the query.
+/// * `min_length` - How many characters a query needs.
+pub fn is_inline_query_too_short(arg: &str, min_length: usize) -> bool {
+    let arg = arg.trim();
+    if !arg.is_empty() && arg.chars().all(|c| c.is_ascii_digit()) {
+        return false;
+    }
+
+    arg.chars().count() < min_length
+}
+
+/// Interleaves two lists, taking one item of each in turn and the rest of the longest at the
+/// end, e.g. the animes and mangas of a mixed search.
+///
+/// # Arguments
+///
+/// * `first` - The list whose items come first.
+/// * `second` - The other list.
+pub fn interleave<T>(first: Vec<T>, second: Vec<T>) -> Vec<T> {
+    let mut items = Vec::with_capacity(first.len() + second.len());
+    let (mut first, mut second) = (first.into_iter(), second.into_iter());
+
+    loop {
+        match (first.next(), second.next()) {
+            (None, None) => break,
+            (a, b) => items.extend(a.into_iter().chain(b)),
+        }
+    }
+
+    items
+}
+
+/// Generates the text telling that AniList is rate limiting the bot.
+///
+/// # Arguments
+///
+/// * `retry_after` - How long until the requests are allowed again.
+/// * `i18n` - A reference to an `I18n` struct containing the translations.
+pub fn rate_limited_text(retry_after: Duration, i18n: &I18n) -> String {
+    let seconds = (retry_after.as_secs_f64().ceil() as u64).max(1);
+
+    i18n.translate_with_args("rate_limited", hashmap! { "seconds" => seconds })
+}
+
+/// Normalizes search terms, so searches differing only in case or spacing are the same.
+///
+/// # Arguments
+///
+/// * `terms` - The search terms, e.g. `  One   Piece`.
+pub fn normalize_search_terms(terms: &str) -> String {
+    terms
+        .split_whitespace()
+        .map(str::to_lowercase)
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+/// Generates shorter queries to retry a search that found nothing with.
+///
+/// The first drops the last word, which is often the misspelled one, and the second keeps only
+/// the first three words. Queries repeating the search or a previous suggestion are skipped.
+///
+/// # Arguments
+///
+/// * `terms` - The search terms, e.g. `naruto shipuden`.
+pub fn suggestion_queries(terms: &str) -> Vec<String> {
+    let words = terms.split_whitespace().collect::<Vec<_>>();
+    let original = words.join(" ");
+
+    let mut queries = Vec::new();
+    for candidate in [
+        &words[..words.len().saturating_sub(1)],
+        &words[..words.len().min(3)],
+    ] {
+        let candidate = candidate.join(" ");
+        if !candidate.is_empty() && candidate != original && !queries.contains(&candidate) {
+            queries.push(candidate);
+        }
+    }
+
+    queries
+}
+
+/// Retries a search that found nothing with the [`suggestion_queries`] of its terms.
+///
+/// Returns the first suggestion that found results, along with them. It stops at the first
+/// failed request, e.g. when rate limited. Not meant for inline queries, which already re-run on
+/// every keystroke.
+///
+/// # Arguments
+///
+/// * `terms` - The search terms that found nothing.
+/// * `search` - Runs the search for the given terms.
+pub async fn search_suggestion<T, F, Fut>(terms: &str, search: F) -> Option<(String, Vec<T>)>
+where
+    F: Fn(String) -> Fut,
+    Fut: Future<Output = Option<Vec<T>>>,
+{
+    for query in suggestion_queries(terms) {
+        match search(query.clone()).await {
+            Some(results) if !results.is_empty() => return Some((query, results)),
+            Some(_) => continue,
+            None => break,
+        }
+    }
+
+    None
+}
+
+/// Whether the search terms are a single common word, for which the relevance order tends to
+/// bury the obvious hit.
+///
+/// # Arguments
+///
+/// * `terms` - The search terms.
+fn is_common_word(terms: &[&str]) -> bool {
+    /// The max length of a word to be considered common.
+    const MAX_LENGTH: usize = 5;
+
+    match terms {
+        [word] => word.chars().all(char::is_alphabetic) && word.chars().count() <= MAX_LENGTH,
+        _ => false,
+    }
+}
+
+/// A group of search results from the same franchise.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct FranchiseGroup {
+    /// The title shared by the members of the group.
+    pub title: String,
+    /// The indexes of the members in the search results.
+    pub members: Vec<usize>,
+}
+
+/// Normalizes a genre to the way AniList writes it, e.g. `slice of life` to `Slice of Life`.
+///
+/// # Arguments
+///
+/// * `genre` - The genre, in any case.
+pub fn normalize_genre(genre: &str) -> String {
+    genre
+        .split_whitespace()
+        .enumerate()
+        .map(|(i, word)| {
+            let word = word.to_lowercase();
+
+            if i > 0 && word == "of" {
+                word
+            } else if word == "sci-fi" {
+                "Sci-Fi".to_string()
+            } else {
+                let mut chars = word.chars();
+                chars
+                    .next()
+                    .map(|first| first.to_uppercase().collect::<String>() + chars.as_str())
+                    .unwrap_or_default()
+            }
+        })
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+/// Gets the base title of a franchise entry, i.e. the title without the subtitle and the
+/// season/part markers, lowercased.
+///
+/// `Shingeki no Kyojin Season 3 Part 2` and `Shingeki no Kyojin: The Final Season` both have
+/// `shingeki no kyojin` as base title.
+///
+/// # Arguments
+///
+/// * `title` - The title of the entry.
+pub fn franchise_base(title: &str) -> String {
+    /// The words that only mark an entry inside a franchise.
+    const MARKERS: [&str; 10] = [
+        "season", "part", "movie", "movies", "ova", "ona", "special", "specials", "final", "the",
+    ];
+
+    let title = title
+        .split([':', '(', '[', '|'])
+        .next()
+        .unwrap_or_default()
+        .split(" - ")
+        .next()
+        .unwrap_or_default()
+        .to_lowercase();
+
+    let mut words = title.split_whitespace().collect::<Vec<_>>();
+    while let Some(word) = words.last() {
+        let is_ordinal = ["st", "nd", "rd", "th"].iter().any(|suffix| {
+            word.strip_suffix(suffix).is_some_and(|number| {
+                !number.is_empty() && number.chars().all(|c| c.is_ascii_digit())
+            })
+        });
+        let is_roman = word.chars().all(|c| matches!(c, 'i' | 'v' | 'x')) && word.len() <= 4;
+
+        if words.len() > 1
+            && (MARKERS.contains(word)
+                || is_ordinal
+                || is_roman
+                || word.chars().all(|c| c.is_ascii_digit()))
+        {
+            words.pop();
+        } else {
+            break;
+        }
+    }
+
+    words.join(" ")
+}
+
+/// Groups search results sharing the same [`franchise_base`].
+///
+/// The groups keep the order in which their first member appears, and so do the members.
+///
+/// # Arguments
+///
+/// * `titles` - The titles of the search results.
+pub fn group_by_franchise<T: AsRef<str>>(titles: &[T]) -> Vec<FranchiseGroup> {
+    let mut groups = Vec::<(String, FranchiseGroup)>::new();
+
+    for (i, title) in titles.iter().enumerate() {
+        let title = title.as_ref();
+        let base = franchise_base(title);
+
+        if let Some((_, group)) = groups.iter_mut().find(|(key, _)| *key == base) {
+            group.members.push(i);
+        } else {
+            let display = title
+                .split([':', '(', '[', '|'])
+                .next()
+                .unwrap_or(title)
+                .trim()
+                .to_string();
+
+            groups.push((
+                base,
+                FranchiseGroup {
+                    title: display,
+                    members: vec![i],
+                },
+            ));
+        }
+    }
+
+    groups.into_iter().map(|(_, group)| group).collect()
+}
+
+/// Generates the buttons of search results, followed by the sort and cancel buttons.
+///
+/// When `group` is set, results from the same franchise are collapsed into a single button
+/// that expands them through the `{callback} group {stash_id} {index} {sender_id}` callback.
+/// The callbacks are signed, see [`sign_callback`].
+///
+/// # Arguments
+///
+/// * `callback` - The callback prefix, e.g. `anime`.
+/// * `results` - The search results.
+/// * `stash_id` - The ID of the stashed search.
+/// * `sort` - The current order of the results.
+/// * `sender_id` - The ID of the user who searched.
+/// * `group` - Whether to group the results by franchise.
+/// * `i18n` - A reference to the `I18n` struct for internationalization.
+pub fn gen_search_buttons(
+    callback: &str,
+    results: &[SearchResult],
+    stash_id: &str,
+    sort: SearchSort,
+    sender_id: i64,
+    group: bool,
+    i18n: &I18n,
+) -> Vec<Vec<Inline>> {
+    let result_button = |result: &SearchResult| {
+        button::inline(
+            if result.is_adult { "🔞 " } else { "" }.to_string() + &result.title,
+            sign_callback(&format!("{0} {1} {2}", callback, result.id, sender_id)),
+        )
+    };
+
+    let mut buttons = if group {
+        let titles = results.iter().map(|r| r.title.as_str()).collect::<Vec<_>>();
+
+        group_by_franchise(&titles)
+            .into_iter()
+            .enumerate()
+            .map(|(i, group)| {
+                if let [member] = group.members.as_slice() {
+                    vec![result_button(&results[*member])]
+                } else {
+                    vec![button::inline(
+                        i18n.translate_with_args(
+                            "franchise_btn",
+                            maplit::hashmap! {
+                                "title" => group.title,
+                                "count" => group.members.len().to_string(),
+                            },
+                        ),
+                        sign_callback(&format!(
+                            "{0} group {1} {2} {3}",
+                            callback, stash_id, i, sender_id
+                        )),
+                    )]
+                }
+            })
+            .collect::<Vec<_>>()
+    } else {
+        results
+            .iter()
+            .map(|result| vec![result_button(result)])
+            .collect::<Vec<_>>()
+    };
+    buttons.push(gen_sort_buttons(
+        &format!("{} sort", callback),
+        stash_id,
+        sort,
+        sender_id,
+        i18n,
+    ));
+    buttons.push(vec![gen_cancel_button(sender_id, i18n)]);
+
+    buttons
+}
+
+/// Generates the button that closes a search list, only its sender can press it.
+///
+/// # Arguments
+///
+/// * `sender_id` - The ID of the user who searched.
+/// * `i18n` - A reference to the `I18n` struct for internationalization.
+pub fn gen_cancel_button(sender_id: i64, i18n: &I18n) -> Inline {
+    button::inline(
+        i18n.translate("cancel_btn"),
+        format!("cancel {}", sender_id),
+    )
+}
+
+/// Generates the buttons of the members of a franchise group from a stashed search, followed by
+/// a button back to the search results. The callbacks are signed, see [`sign_callback`].
+///
+/// Returns the title of the group and the buttons, or `None` if the group does not exist.
+///
+/// # Arguments
+///
+/// * `callback` - The callback prefix, e.g. `anime`.
+/// * `search` - The stashed search.
+/// * `stash_id` - The ID of the stashed search.
+/// * `index` - The index of the group, as given by [`group_by_franchise`].
+/// * `sender_id` - The ID of the user who searched.
+/// * `i18n` - A reference to the `I18n` struct for internationalization.
+pub fn gen_franchise_buttons(
+    callback: &str,
+    search: &StashedSearch,
+    stash_id: &str,
+    index: usize,
+    sender_id: i64,
+    i18n: &I18n,
+) -> Option<(String, Vec<Vec<Inline>>)> {
+    let titles = search
+        .results
+        .iter()
+        .map(|result| result.title.as_str())
+        .collect::<Vec<_>>();
+    let group = group_by_franchise(&titles).into_iter().nth(index)?;
+
+    let mut buttons = group
+        .members
+        .iter()
+        .map(|member| {
+            let result = &search.results[*member];
+
+            vec![button::inline(
+                if result.is_adult { "🔞 " } else { "" }.to_string() + &result.title,
+                sign_callback(&format!("{0} {1} {2}", callback, result.id, sender_id)),
+            )]
+        })
+        .collect::<Vec<_>>();
+    buttons.push(vec![button::inline(
+        i18n.translate("back_btn"),
+        sign_callback(&format!(
+            "{0} sort {1} {2} {3}",
+            callback,
+            stash_id,
+            search.query.sort.name(),
+            sender_id
+        )),
+    )]);
+
+    Some((group.title, buttons))
+}
+
+/// Formats a number with a space between each group of thousands, e.g. `1 240`.
+///
+/// # Arguments
+///
+/// * `number` - The number to be formatted.
+pub fn format_thousands(number: i64) -> String {
+    let digits = number.unsigned_abs().to_string();
+    let mut text = String::new();
+
+    for (i, digit) in digits.chars().enumerate() {
+        if i > 0 && (digits.len() - i) % 3 == 0 {
+            text.push(' ');
+        }
+        text.push(digit);
+    }
+
+    if number < 0 {
+        text.insert(0, '-');
+    }
+
+    text
+}
+
+/// Formats a duration in days, hours and minutes, e.g. `3d 4h 12m`.
+///
+/// # Arguments
+///
+/// * `duration` - The duration to be formatted.
+pub fn format_uptime(duration: Duration) -> String {
+    let minutes = duration.as_secs() / 60;
+    let (days, hours, minutes) = (minutes / (24 * 60), minutes / 60 % 24, minutes % 60);
+
+    if days > 0 {
+        format!("{}d {}h {}m", days, hours, minutes)
+    } else if hours > 0 {
+        format!("{}h {}m", hours, minutes)
+    } else {
+        format!("{}m", minutes)
+    }
+}
+
+/// Generates the runtime statistics section of `/about`.
+///
+/// # Arguments
+///
+/// * `snapshot` - The statistics of the bot.
+/// * `i18n` - A reference to the `I18n` struct for internationalization.
+pub fn gen_about_stats(snapshot: &Snapshot, i18n: &I18n) -> String {
+    let unknown = || i18n.translate("not_available");
+
+    i18n.translate_with_args(
+        "about_stats",
+        hashmap! {
+            "uptime" => format_uptime(snapshot.uptime),
+            "users" => snapshot.users.map(format_thousands).unwrap_or_else(unknown),
+            "groups" => snapshot.groups.map(format_thousands).unwrap_or_else(unknown),
+            "database" => snapshot
+                .database_latency
+                .map(|latency| format!("{} ms", latency.as_millis()))
+                .unwrap_or_else(|| i18n.translate("database_offline")),
+            "cache_hit_rate" => snapshot
+                .cache_hit_rate()
+                .map(|rate| format!("{}%", rate))
+                .unwrap_or_else(unknown),
+            "memory" => snapshot
+                .memory
+                .map(|bytes| format!("{:.1} MB", bytes as f64 / (1024.0 * 1024.0)))
+                .unwrap_or_else(unknown),
+        },
+    )
+}
+
+/// Describes how long ago, or how long from now, a moment is, e.g. `3 days ago` or `in 2 h`.
+///
+/// Moments more than a year away are shown as a date in the given time zone instead.
+///
+/// # Arguments
+///
+/// * `datetime` - The moment to describe.
+/// * `i18n` - A reference to the `I18n` struct for internationalization.
+/// * `tz` - The time zone of the dates.
+pub fn humanize_ago<Tz>(datetime: DateTime<Utc>, i18n: &I18n, tz: &Tz) -> String
+where
+    Tz: TimeZone,
+    Tz::Offset: Display,
+{
+    humanize_between(datetime, Utc::now(), i18n, tz)
+}
+
+/// Describes how far a moment is from `now`, see [`humanize_ago`].
+///
+/// # Arguments
+///
+/// * `datetime` - The moment to describe.
+/// * `now` - The moment to compare with.
+/// * `i18n` - A reference to the `I18n` struct for internationalization.
+/// * `tz` - The time zone of the dates.
+pub fn humanize_between<Tz>(
+    datetime: DateTime<Utc>,
+    now: DateTime<Utc>,
+    i18n: &I18n,
+    tz: &Tz,
+) -> String
+where
+    Tz: TimeZone,
+    Tz::Offset: Display,
+{
+    const MINUTE: i64 = 60;
+    const HOUR: i64 = 60 * MINUTE;
+    const DAY: i64 = 24 * HOUR;
+    const MONTH: i64 = 30 * DAY;
+    const YEAR: i64 = 365 * DAY;
+
+    let t = |key: &str| i18n.translate(key);
+    let t_a = |key: &str, args| i18n.translate_with_args(key, args);
+
+    let delta = (datetime - now).num_seconds();
+    let seconds = delta.abs();
+
+    if seconds < MINUTE {
+        return t("time_just_now");
+    } else if seconds >= YEAR {
+        let date = datetime
+            .with_timezone(tz)
+            .format(&t("time_date_format"))
+            .to_string();
+
+        return t_a("time_date", hashmap! { "date" => date });
+    }
+
+    let (key, count) = match seconds {
+        ..HOUR => ("time_minutes", seconds / MINUTE),
+        ..DAY => ("time_hours", seconds / HOUR),
+        ..MONTH => ("time_days", seconds / DAY),
+        _ => ("time_months", seconds / MONTH),
+    };
+    let key = if count == 1 {
+        key.trim_end_matches('s').to_string()
+    } else {
+        key.to_string()
+    };
+    let time = t_a(&key, hashmap! { "count" => count.to_string() });
+
+    if delta > 0 {
+        t_a("time_in", hashmap! { "time" => time })
+    } else {
+        t_a("time_ago", hashmap! { "time" => time })
+    }
+}
+
+/// Builds a packed chat from a Bot API chat ID, e.g. one from the config.
+///
+/// Positive IDs are users, IDs prefixed with `-100` are channels or supergroups and any other
+/// negative ID is a basic group. The chat has no access hash, the users and groups in the
+/// database are sent to with the chat recorded with their activity instead.
+///
+/// # Arguments
+///
+/// * `id` - The chat ID, in the Bot API format.
+pub fn packed_chat(id: i64) -> PackedChat {
+    const CHANNEL_OFFSET: i64 = 1_000_000_000_000;
+
+    let (ty, id) = if id > 0 {
+        (PackedType::User, id)
+    } else if id < -CHANNEL_OFFSET {
+        (PackedType::Megagroup, -id - CHANNEL_OFFSET)
+    } else {
+        (PackedType::Chat, -id)
+    };
+
+    PackedChat {
+        ty,
+        id,
+        access_hash: None,
+    }
+}
+
+/// Unpacks a chat stored with [`PackedChat::to_hex`].
+///
+/// Returns `None` if the hex is not a packed chat.
+///
+/// # Arguments
+///
+/// * `hex` - The packed chat, as hex.
+pub fn unpack_chat(hex: &str) -> Option<PackedChat> {
+    PackedChat::from_hex(hex).ok()
+}
+
+/// Gets the title in the language the chat prefers.
+///
+/// Falls back to the romaji title and then to the native one, so the title is never empty.
+///
+/// # Arguments
+///
+/// * `title` - The title of the media.
+/// * `settings` - The settings of the chat.
+pub fn preferred_title(title: &Title, settings: &ChatSettings) -> String {
+    let preferred = match settings.title_language {
+        TitleLanguage::Romaji => None,
+        TitleLanguage::English => title.english(),
+        TitleLanguage::Native => Some(title.native()),
+    };
+
+    [preferred, Some(title.romaji()), Some(title.native())]
+        .into_iter()
+        .flatten()
+        .find(|title| !title.trim().is_empty())
+        .unwrap_or_default()
+        .to_string()
+}
+
+/// Formats an AniList date with the pattern of the locale, e.g. `{dd}/{mm}/{yyyy}`.
+///
+/// # Arguments
+///
+/// * `date` - The date to format.
+/// * `i18n` - A reference to the `I18n` struct for internationalization.
+pub fn format_date(date: &Date, i18n: &I18n) -> String {
+    date.format(&i18n.translate("date_pattern"))
+}
+
+/// Translates the name of an AniList enum value, e.g. a status, a format or a genre.
+///
+/// The key is the prefix followed by the name in lowercase letters and digits only, e.g.
+/// `status_notyetreleased`. Names without a translation are kept as they are.
+///
+/// # Arguments
+///
+/// * `prefix` - The prefix of the key.
+/// * `value` - The value, either the enum or its AniList name.
+/// * `i18n` - A reference to the `I18n` struct for internationalization.
+pub fn translate_enum(prefix: &str, value: impl Display, i18n: &I18n) -> String {
+    let name = value.to_string();
+    let key = format!(
+        "{}_{}",
+        prefix,
+        name.chars()
+            .filter(char::is_ascii_alphanumeric)
+            .collect::<String>()
+            .to_ascii_lowercase()
+    );
+
+    i18n.lookup(&key, &i18n.locale())
+        .or_else(|| i18n.lookup(&key, i18n.default_locale()))
+        .unwrap_or(name)
+}
+
+/// Turns a genre into a hashtag in the language of the locale, e.g. `#Slice_of_Life`.
+///
+/// # Arguments
+///
+/// * `genre` - The AniList name of the genre.
+/// * `i18n` - A reference to the `I18n` struct for internationalization.
+pub fn genre_hashtag(genre: &str, i18n: &I18n) -> String {
+    format!(
+        "#{}",
+        translate_enum("genre", genre, i18n)
+            .replace("-", "")
+            .replace(" ", "_")
+    )
+}
+
+/// Generates a formatted string containing detailed information about an anime.
+///
+/// # Arguments
+///
+/// * `anime` - A reference to an `Anime` struct containing the anime details.
+/// * `i18n` - A reference to an `I18n` struct containing the translations.
+/// * `settings` - The settings of the chat, for the title language.
+pub fn gen_anime_info(anime: &Anime, i18n: &I18n, settings: &ChatSettings) -> String {
+    let t = |key: &str| i18n.translate(key);
+
+    let mut text = format!(
+        "<code>{0}</code> | <b>{1}</b>\n\n",
+        anime.id,
+        escape_html(preferred_title(&anime.title, settings)),
+    );
+
+    if anime.start_date.is_some() || anime.end_date.is_some() {
+        if let Some(date) = anime.start_date.as_ref() {
+            if date.is_valid() {
+                text.push_str(&format!(
+                    "📅 | <b>{0}</b>: <i>{1}</i>",
+                    t("date"),
+                    format_date(date, i18n)
+                ));
+            }
+        }
+
+        if !matches!(anime.format, Format::Movie | Format::Music)
+            && !anime.start_date.eq(&anime.end_date)
+        {
+            if let Some(date) = anime.end_date.as_ref() {
+                if date.is_valid() {
+                    text.push_str(&format!(" - <i>{}</i>", format_date(date, i18n)));
+                }
+            }
+        }
+
+        text.push_str("\n");
+    }
+
+    if let Some(score) = anime.average_score {
+        text.push_str(&format!(
+            "🌟 | <b>{0}</b>: <i>{1:02}%</i>\n",
+            t("score"),
+            score
+        ));
+    }
+
+    text.push_str(&format!(
+        "{0} | <b>{1}</b>: <i>{2}</i>",
+        match anime.status {
+            Status::Hiatus => "🕰",
+            Status::Paused => "⏸",
+            Status::Current => "✔",
+            Status::Dropped => "❌",
+            Status::Planning => "📅",
+            Status::Finished => "🏁",
+            Status::Cancelled => "❌",
+            Status::Completed => "🏁",
+            Status::Releasing => "📆",
+            Status::Repeating => "🔁",
+            Status::NotYetReleased => "🔜",
+        },
+        t("status"),
+        translate_enum("status", &anime.status, i18n)
+    ));
+
+    if let Some((episode, countdown)) = next_episode_countdown(anime, i18n) {
+        text.push_str(&format!(
+            " (<i>{}</i>)",
+            i18n.translate_with_args(
+                "next_episode",
+                hashmap! { "episode" => episode.to_string(), "countdown" => countdown },
+            )
+        ));
+    }
+
+    text.push_str("\n");
+
+    text.push_str(&format!(
+        "{0} | <b>{1}</b>: <i>{2}</i>\n",
+        match anime.format {
+            Format::Tv => "📺",
+            Format::Ona => "🎞",
+            Format::Ova => "🎞",
+            Format::Movie => "🎥",
+            Format::Music => "🎵",
+            Format::OneShot => "📖",
+            Format::Special => "🎌",
+            Format::TvShort => "📺",
+            _ => "📖",
+        },
+        t("format"),
+        translate_enum("format", &anime.format, i18n)
+    ));
+
+    if let Some(genres) = anime.genres.as_ref() {
+        text.push_str(&format!(
+            "🎭 | <b>{0}</b>: <i>{1}</i>\n",
+            t("genres"),
+            genres
+                .iter()
+                .map(|genre| genre_hashtag(genre, i18n))
+                .collect::<Vec<_>>()
+                .join(" ")
+        ));
+    }
+
+    if !matches!(anime.format, Format::Movie | Format::Music) {
+        if let Some(episodes) = anime.episodes {
+            text.push_str(&format!(
+                "🎞 | <b>{0}</b>: <i>{1}</i>\n",
+                t("episodes"),
+                episodes
+            ));
+        }
+    }
+
+    if !anime.description.is_empty() {
+        text.push_str(&format!(
+            "\n<blockquote expandable><i>{}</i></blockquote>\n",
+            shorten_html(
+                &sanitize_html(&anime.description, HtmlTarget::Telegram),
+                500
+            )
+        ));
+    }
+
+    text
+}
+
+/// Generates a formatted string containing detailed information about a manga.
+///
+/// # Arguments
+///
+/// * `manga` - A reference to an `Manga` struct containing the manga details.
+/// * `i18n` - A reference to an `I18n` struct containing the translations.
+/// * `settings` - The settings of the chat, for the title language.
+pub fn gen_manga_info(manga: &Manga, i18n: &I18n, settings: &ChatSettings) -> String {
+    let t = |key: &str| i18n.translate(key);
+
+    let mut text = format!(
+        "<code>{0}</code> | <b>{1}</b>\n\n",
+        manga.id,
+        escape_html(preferred_title(&manga.title, settings)),
+    );
+
+    if manga.start_date.is_some() || manga.end_date.is_some() {
+        if let Some(date) = manga.start_date.as_ref() {
+            if date.is_valid() {
+                text.push_str(&format!(
+                    "📅 | <b>{0}</b>: <i>{1}</i>",
+                    t("date"),
+                    format_date(date, i18n)
+                ));
+            }
+        }
+
+        if !matches!(manga.format, Format::Music) && !manga.start_date.eq(&manga.end_date) {
+            if let Some(date) = manga.end_date.as_ref() {
+                if date.is_valid() {
+                    text.push_str(&format!(" - <i>{}</i>", format_date(date, i18n)));
+                }
+            }
+        }
+
+        text.push_str("\n");
+    }
+
+    if let Some(average_score) = manga.average_score {
+        text.push_str(&format!(
+            "🌟 | <b>{0}</b>: <i>{1:02}%</i>\n",
+            t("score"),
+            average_score
+        ));
+    }
+
+    text.push_str(&format!(
+        "{0} | <b>{1}</b>: <i>{2}</i>\n",
+        match manga.status {
+            Status::Hiatus => "🕰",
+            Status::Paused => "⏸",
+            Status::Current => "✔",
+            Status::Dropped => "❌",
+            Status::Planning => "📅",
+            Status::Finished => "🏁",
+            Status::Cancelled => "❌",
+            Status::Completed => "🏁",
+            Status::Releasing => "📆",
+            Status::Repeating => "🔁",
+            Status::NotYetReleased => "🔜",
+        },
+        t("status"),
+        translate_enum("status", &manga.status, i18n)
+    ));
+
+    text.push_str(&format!(
+        "{0} | <b>{1}</b>: <i>{2}</i>\n",
+        match manga.format {
+            Format::Novel => "📖",
+            Format::Manga => "📚",
+            Format::Music => "🎵",
+            Format::OneShot => "📖",
+            Format::Special => "🎌",
+            _ => "🎥",
+        },
+        t("format"),
+        translate_enum("format", &manga.format, i18n)
+    ));
+
+    if let Some(genres) = manga.genres.as_ref() {
+        text.push_str(&format!(
+            "🎭 | <b>{0}</b>: <i>{1}</i>\n",
+            t("genres"),
+            genres
+                .iter()
+                .map(|genre| genre_hashtag(genre, i18n))
+                .collect::<Vec<_>>()
+                .join(" ")
+        ));
+    }
+
+    if !matches!(manga.format, Format::Music) {
+        if let Some(chapters) = manga.chapters {
+            text.push_str(&format!(
+                "🔢 | <b>{0}</b>: <i>{1}</i>\n",
+                t("chapters"),
+                chapters
+            ));
+        }
+    }
+
+    if let Some(volumes) = manga.volumes {
+        text.push_str(&format!(
+            "📚 | <b>{0}</b>: <i>{1}</i>\n",
+            t("volumes"),
+            volumes
+        ));
+    }
+
+    if !manga.description.is_empty() {
+        text.push_str(&format!(
+            "\n<blockquote expandable><i>{}</i></blockquote>\n",
+            shorten_html(
+                &sanitize_html(&manga.description, HtmlTarget::Telegram),
+                350
+            )
+        ));
+    }
+
+    text
+}
+
+/// Generates a formatted string containing detailed information about a user.
+///
+/// # Arguments
+///
+/// * `user` - A reference to an `User` struct containing the user details.
+pub fn gen_user_info(user: &User) -> String {
+    let mut text = format!("<code>{0}</code> | <b>{1}</b>\n", user.id, user.name);
+
+    if let Some(about) = user.about.as_ref() {
+        text.push_str(&format!(
+            "\n<blockquote expandable>{}</blockquote>\n",
+            shorten_html(&sanitize_html(about, HtmlTarget::Telegram), 300)
+        ));
+    }
+
+    text.push_str(&format!(
+        "\n🔗 | <a href=\"https://anilist.co/user/{}\">AniList</a>",
+        user.id
+    ));
+
+    text
+}
+
+/// Generates a page of a user's list, with the progress and score of each entry.
+///
+/// The titles are shortened, so a page always fits in a message.
+///
+/// # Arguments
+///
+/// * `title` - The title of the list, e.g. the status of its entries.
+/// * `entries` - The entries of the page.
+pub fn gen_user_list(title: &str, entries: &[UserListEntry]) -> String {
+    /// The maximum length of a title.
+    const MAX_TITLE_LENGTH: usize = 40;
+
+    let mut text = format!("📋 <b>{}</b>:\n\n", escape_html(title));
+
+    for entry in entries.iter() {
+        text.push_str(&format!(
+            "<code>{0}</code> | <b>{1}</b> — <i>{2}/{3}</i>",
+            entry.media_id,
+            escape_html(shorten_text(&entry.title, MAX_TITLE_LENGTH)),
+            entry.progress.unwrap_or_default(),
+            entry
+                .total
+                .map_or("?".to_string(), |total| total.to_string())
+        ));
+        if let Some(score) = entry.score.filter(|score| *score > 0.0) {
+            text.push_str(&format!(" · ⭐ <i>{}</i>", score));
+        }
+        text.push('\n');
+    }
+
+    text
+}
+
+/// Generates a bar showing how much a value is of a maximum, e.g. `■■■□□`.
+///
+/// Any value above zero fills at least one cell.
+///
+/// # Arguments
+///
+/// * `value` - The value.
+/// * `max` - The maximum value, a full bar.
+/// * `width` - How many cells the bar has.
+pub fn gen_bar(value: u32, max: u32, width: usize) -> String {
+    let filled = if max == 0 || value == 0 {
+        0
+    } else {
+        ((value.min(max) as f64 / max as f64 * width as f64).round() as usize).max(1)
+    };
+
+    "■".repeat(filled) + &"□".repeat(width - filled)
+}
+
+/// Generates the anime and manga statistics of a user, with the genres and statuses as bars.
+///
+/// Media types with no entries are skipped.
+///
+/// # Arguments
+///
+/// * `stats` - The statistics of the user.
+/// * `i18n` - A reference to the `I18n` struct for internationalization.
+pub fn gen_user_stats(stats: &UserStats, i18n: &I18n) -> String {
+    /// How many cells the bars have.
+    const BAR_WIDTH: usize = 5;
+
+    let t = |key: &str| i18n.translate(key);
+
+    let gen_counts = |title: &str, counts: &[StatCount], max: u32, prefix: &str| {
+        let mut text = format!("\n<b>{}</b>:\n", title);
+        for count in counts.iter() {
+            text.push_str(&format!(
+                "<code>{0}</code> {1} <i>({2})</i>\n",
+                gen_bar(count.count, max, BAR_WIDTH),
+                escape_html(translate_enum(prefix, &count.name, i18n)),
+                count.count
+            ));
+        }
+        text
+    };
+    let gen_section = |title: String, stats: &MediaStats, totals: Vec<(&str, String)>| {
+        let mut text = format!("\n<b>{}</b>\n", title);
+        for (label, value) in totals {
+            text.push_str(&format!("• <b>{0}</b>: <i>{1}</i>\n", t(label), value));
+        }
+        if !stats.genres.is_empty() {
+            let max = stats.genres.iter().map(|genre| genre.count).max();
+            text.push_str(&gen_counts(
+                &t("genres"),
+                &stats.genres,
+                max.unwrap_or_default(),
+                "genre",
+            ));
+        }
+        if !stats.statuses.is_empty() {
+            text.push_str(&gen_counts(
+                &t("status"),
+                &stats.statuses,
+                stats.count,
+                "status",
+            ));
+        }
+        text
+    };
+
+    let mut text = format!("📊 <b>{}</b>\n", t("stats"));
+
+    if stats.anime.count > 0 {
+        text.push_str(&gen_section(
+            t("anime_btn"),
+            &stats.anime,
+            vec![
+                ("stats_entries", format_thousands(stats.anime.count as i64)),
+                (
+                    "stats_days_watched",
+                    format!("{:.1}", stats.anime.minutes_watched as f64 / (24.0 * 60.0)),
+                ),
+                (
+                    "episodes",
+                    format_thousands(stats.anime.episodes_watched as i64),
+                ),
+                ("stats_mean_score", format!("{:.1}", stats.anime.mean_score)),
+            ],
+        ));
+    }
+    if stats.manga.count > 0 {
+        text.push_str(&gen_section(
+            t("manga_btn"),
+            &stats.manga,
+            vec![
+                ("stats_entries", format_thousands(stats.manga.count as i64)),
+                (
+                    "chapters",
+                    format_thousands(stats.manga.chapters_read as i64),
+                ),
+                ("volumes", format_thousands(stats.manga.volumes_read as i64)),
+                ("stats_mean_score", format!("{:.1}", stats.manga.mean_score)),
+            ],
+        ));
+    }
+
+    text
+}
+
+/// Generates the totals of the statistics of a user in a line per media type, without the bars
+/// of [`gen_user_stats`].
+///
+/// Media types with no entries are skipped.
+///
+/// # Arguments
+///
+/// * `stats` - The statistics of the user.
+/// * `i18n` - A reference to the `I18n` struct for internationalization.
+pub fn gen_user_stats_summary(stats: &UserStats, i18n: &I18n) -> String {
+    let t = |key: &str| i18n.translate(key);
+
+    let mut lines = Vec::new();
+    for (title, stats, (label, progress)) in [
+        (
+            t("anime_btn"),
+            &stats.anime,
+            ("episodes", stats.anime.episodes_watched),
+        ),
+        (
+            t("manga_btn"),
+            &stats.manga,
+            ("chapters", stats.manga.chapters_read),
+        ),
+    ] {
+        if stats.count == 0 {
+            continue;
+        }
+
+        lines.push(format!(
+            "<b>{0}</b>: <i>{1}</i> {2}, <i>{3}</i> {4}",
+            title,
+            format_thousands(stats.count as i64),
+            t("stats_entries").to_lowercase(),
+            format_thousands(progress as i64),
+            t(label).to_lowercase()
+        ));
+    }
+
+    lines.join("\n")
+}
+
+/// Generates the favourite animes, mangas and characters of a user.
+///
+/// Kinds with no favourites are skipped.
+///
+/// # Arguments
+///
+/// * `favourites` - The favourites of the user.
+/// * `i18n` - A reference to the `I18n` struct for internationalization.
+pub fn gen_user_favourites(favourites: &Favourites, i18n: &I18n) -> String {
+    let t = |key: &str| i18n.translate(key);
+
+    let mut text = format!("❤ <b>{}</b>\n", t("favourites"));
+
+    for (title, favourites) in [
+        (t("anime_btn"), &favourites.anime),
+        (t("manga_btn"), &favourites.manga),
+        (t("characters_btn"), &favourites.characters),
+    ] {
+        if favourites.is_empty() {
+            continue;
+        }
+
+        text.push_str(&format!("\n<b>{}</b>:\n", title));
+        for Favourite { id, name } in favourites.iter() {
+            text.push_str(&format!(
+                "<code>{0}</code> | <b>{1}</b>\n",
+                id,
+                escape_html(name)
+            ));
+        }
+    }
+
+    text
+}
+
+/// Generates the caption of the image of a character: its name and its ID.
+///
+/// # Arguments
+///
+/// * `char` - The character.
+pub fn gen_char_image_caption(char: &Character) -> String {
+    format!(
+        "<b>{0}</b> (<code>{1}</code>)",
+        escape_html(char.name.full()),
+        char.id
+    )
+}
+
+/// Generates a formatted string containing detailed information about a character.
+///
+/// # Arguments
+///
+/// * `char` - A reference to an `Character` struct containing the character details.
+/// * `i18n` - A reference to an `I18n` struct containing the translations.
+pub fn gen_char_info(char: &Character, i18n: &I18n) -> String {
+    let t = |key: &str| i18n.translate(key);
+
+    let mut text = format!(
+        "<code>{0}</code> | <b>{1}</b>\n",
+        char.id,
+        escape_html(char.name.full())
+    );
+
+    if let Some(age) = char.age.as_ref() {
+        text.push_str(&format!("\n🎂 | <b>{}</b>: <i>{}</i>", t("age"), age));
+    }
+
+    if let Some(blood_type) = char.blood_type.as_ref() {
+        text.push_str(&format!(
+            "\n🩸 | <b>{}</b>: <i>{}</i>\n",
+            t("blood_type"),
+            blood_type
+        ));
+    }
+
+    if let Some(date_of_birth) = char.date_of_birth.as_ref() {
+        if date_of_birth.is_valid() {
+            text.push_str(&format!(
+                "\n📅 | <b>{}</b>: <i>{}</i>\n",
+                t("date_of_birth"),
+                format_date(date_of_birth, i18n)
+            ));
+        }
+    }
+
+    if !char.description.is_empty() {
+        text.push_str(&format!(
+            "\n<blockquote expandable>{}</blockquote>\n",
+            shorten_html(&sanitize_html(&char.description, HtmlTarget::Telegram), 400)
+        ));
+    }
+
+    text
+}
+
+/// Gets the number of pages needed to list some items.
+///
+/// # Arguments
+///
+/// * `len` - The number of items.
+/// * `per_page` - The number of items per page.
+pub fn page_count(len: usize, per_page: usize) -> usize {
+    len.div_ceil(per_page)
+}
+
+/// How many characters a page can list with their voice actors, longer pages leave them out
+/// so the list fits in a message.
+pub const VOICE_ACTORS_MAX_CHARACTERS: usize = 25;
+/// How long a voice actor name can be in the character list.
+const VOICE_ACTOR_NAME_LENGTH: usize = 30;
+
+/// Generates a page of the characters of a media with internationalization support.
+///
+/// The voice actors are linked to their AniList page, up to
+/// [`VOICE_ACTORS_MAX_CHARACTERS`] characters.
+///
+/// # Arguments
+///
+/// * `characters` - The characters of the page, as AniList paged them.
+/// * `i18n` - A reference to the `I18n` struct for internationalization.
+pub fn gen_char_list(characters: &[MediaCharacter], i18n: &I18n) -> String {
+    let t = |key: &str| i18n.translate(key);
+    let t_a = |key: &str, args| i18n.translate_with_args(key, args);
+
+    let mut text = format!("👥 <b>{}</b>:\n", t("characters"));
+    let show_voice_actors = characters.len() <= VOICE_ACTORS_MAX_CHARACTERS;
+
+    for character in characters {
+        let voice_actor = match character.voice_actor.as_ref() {
+            Some(voice_actor) if show_voice_actors => format!(
+                " {}",
+                t_a(
+                    "char_voice_actor",
+                    hashmap! {
+                        "voice_actor" => format!(
+                            "<a href=\"https://anilist.co/staff/{0}\">{1}</a>",
+                            voice_actor.id,
+                            escape_html(shorten_text(&voice_actor.name, VOICE_ACTOR_NAME_LENGTH))
+                        )
+                    },
+                )
+            ),
+            _ => String::new(),
+        };
+
+        text.push_str(&format!(
+            "{0} | <code>{1}</code>. <b>{2}</b>{3}\n",
+            match character.gender.as_deref() {
+                Some("Male") => "👨",
+                Some("Female") => "👩",
+                Some("Non-binary") => "👨‍👧‍👦",
+                _ => "👨‍👩‍👧‍👦",
+            },
+            character.id,
+            escape_html(&character.name),
+            voice_actor
+        ));
+
+        if let Some(role) = character.role.as_ref() {
+            text.push_str(&format!(
+                "🎭 | <i>{}</i>\n",
+                t(&format!("role_{}", role.to_lowercase()))
+            ));
+        }
+    }
+
+    text
+}
+
+/// Gets the flag of the language of a site, e.g. 🇯🇵 for `Japanese`.
+///
+/// # Arguments
+///
+/// * `language` - The language, as AniList names it.
+pub fn language_flag(language: &str) -> Option<&'static str> {
+    match language {
+        "Japanese" => Some("🇯🇵"),
+        "English" => Some("🇺🇸"),
+        "Portuguese" => Some("🇧🇷"),
+        "Spanish" => Some("🇪🇸"),
+        "French" => Some("🇫🇷"),
+        "German" => Some("🇩🇪"),
+        "Italian" => Some("🇮🇹"),
+        "Korean" => Some("🇰🇷"),
+        "Chinese" => Some("🇨🇳"),
+        _ => None,
+    }
+}
+
+/// Generates the links of a media with internationalization support.
+///
+/// The streaming sites, where an anime is watched or a manga is read, are listed apart from
+/// the others, and the disabled links are left out.
+///
+/// # Arguments
+///
+/// * `links` - The links of the media.
+/// * `media_type` - The media type.
+/// * `i18n` - A reference to the `I18n` struct for internationalization.
+pub fn gen_links_list(links: &MediaLinks, media_type: MediaType, i18n: &I18n) -> String {
+    let t = |key: &str| i18n.translate(key);
+
+    let link_line = |link: &ExternalLink| {
+        format!(
+            "🔗 | {0}<a href=\"{1}\">{2}</a>\n",
+            link.language
+                .as_deref()
+                .and_then(language_flag)
+                .map(|flag| format!("{} ", flag))
+                .unwrap_or_default(),
+            escape_html(&link.url),
+            escape_html(&link.site)
+        )
+    };
+    let (streaming, others): (Vec<_>, Vec<_>) = links
+        .enabled_links()
+        .partition(|link| link.link_type.as_deref() == Some("STREAMING"));
+
+    let mut text = format!("🖇 <b>{}</b>:\n", t("links"));
+    text.push_str(&format!(
+        "🔗 | <a href=\"{}\">AniList</a>\n",
+        escape_html(&links.site_url)
+    ));
+    if let Some(id) = links.id_mal {
+        text.push_str(&format!(
+            "🔗 | <a href=\"https://myanimelist.net/{0}/{1}\">MyAnimeList</a>\n",
+            media_type.as_str().to_lowercase(),
+            id
+        ));
+    }
+    for link in others {
+        text.push_str(&link_line(link));
+    }
+
+    if !streaming.is_empty() {
+        text.push_str(&format!(
+            "\n{0} <b>{1}</b>:\n",
+            match media_type {
+                MediaType::Anime => "📺",
+                MediaType::Manga => "📖",
+            },
+            t(match media_type {
+                MediaType::Anime => "watch_at",
+                MediaType::Manga => "read_at",
+            })
+        ));
+        for link in streaming {
+            text.push_str(&link_line(link));
+        }
+    }
+
+    text
+}
+
+/// Gets the number of the next episode of an anime and how long until it airs.
+///
+/// Returns `None` if no episode is scheduled.
+///
+/// # Arguments
+///
+/// * `anime` - The anime.
+/// * `i18n` - A reference to the `I18n` struct for internationalization.
+pub fn next_episode_countdown(anime: &Anime, i18n: &I18n) -> Option<(i64, String)> {
+    let next_airing = anime.next_airing_episode.as_ref()?;
+    let at = DateTime::from_timestamp(next_airing.at, 0)?;
+
+    Some((next_airing.episode, format_countdown(at, Utc::now(), i18n)))
+}
+
+/// Describes when an episode airs relative to `now`, e.g. `airs in 2 h` or `aired 3 days ago`.
+///
+/// # Arguments
+///
+/// * `airing_at` - When the episode airs.
+/// * `now` - The moment to compare with.
+/// * `i18n` - A reference to the `I18n` struct for internationalization.
+pub fn format_countdown(airing_at: DateTime<Utc>, now: DateTime<Utc>, i18n: &I18n) -> String {
+    i18n.translate_with_args(
+        if airing_at > now {
+            "episode_airs"
+        } else {
+            "episode_aired"
+        },
+        hashmap! { "time" => humanize_between(airing_at, now, i18n, &Utc) },
+    )
+}
+
+/// Generates a list of episodes with pagination and internationalization support.
+///
+/// # Arguments
+///
+/// * `episodes` - A slice of `Episode` structs to be displayed.
+/// * `next` - The number of the next episode and its countdown, see [`next_episode_countdown`].
+/// * `page` - The current page number for pagination.
+/// * `per_page` - The number of episodes per page.
+/// * `i18n` - A reference to the `I18n` struct for internationalization.
+pub fn gen_episode_list(
+    episodes: &[Episode],
+    next: Option<&(i64, String)>,
+    page: usize,
+    per_page: usize,
+    i18n: &I18n,
+) -> String {
+    let t = |key: &str| i18n.translate(key);
+    let t_a = |key: &str, args| i18n.translate_with_args(key, args);
+
+    let mut text = format!("📺 <b>{}</b>:\n", t("episodes"));
+
+    let offset = (page - 1) * per_page;
+
+    for episode in episodes.iter().skip(offset).take(per_page) {
+        let title = episode.title.clone().unwrap_or_else(|| {
+            t_a(
+                "episode_number",
+                hashmap! { "number" => episode.number.to_string() },
+            )
+        });
+
+        match next.filter(|(number, _)| *number == episode.number) {
+            Some((_, countdown)) => text.push_str(&format!(
+                "⏳ | <code>{0}</code>. <b>{1}</b> — <i>{2}</i>\n",
+                episode.number,
+                escape_html(&title),
+                countdown
+            )),
+            None => {
+                text.push_str(&format!(
+                    "🎞 | <code>{0}</code>. <b>{1}</b>",
+                    episode.number,
+                    escape_html(&title)
+                ));
+
+                if let Some(airing_at) = episode.airing_at {
+                    text.push_str(&format!(
+                        " — <i>{}</i>",
+                        format_countdown(airing_at, Utc::now(), i18n)
+                    ));
+                }
+
+                text.push('\n');
+            }
+        }
+    }
+
+    text
+}
+
+/// Generates a list of studios with pagination and internationalization support.
+///
+/// # Arguments
+///
+/// * `studios` - A slice of `Studio` structs to be displayed.
+/// * `page` - The current page number for pagination.
+/// * `per_page` - The number of studios per page.
+/// * `i18n` - A reference to the `I18n` struct for internationalization.
+pub fn gen_studio_list(studios: &[Studio], page: usize, per_page: usize, i18n: &I18n) -> String {
+    let t = |key: &str| i18n.translate(key);
+
+    let mut text = format!("🎬 <b>{}</b>:\n", t("studios"));
+
+    let offset = (page - 1) * per_page;
+
+    for studio in studios.iter().skip(offset).take(per_page) {
+        text.push_str(&format!(
+            "🏢 | <a href=\"{0}\">{1}</a>",
+            escape_html(&studio.url),
+            escape_html(&studio.name)
+        ));
+
+        if studio.is_animation_studio {
+            text.push_str(&format!(" — <i>{}</i>", t("animation_studio")));
+        }
+
+        text.push('\n');
+    }
+
+    text
+}
+
+/// Generates the info of a studio, with a page of its productions.
+///
+/// # Arguments
+///
+/// * `studio` - The studio.
+/// * `page` - The current page number for pagination.
+/// * `per_page` - The number of productions per page.
+/// * `i18n` - A reference to the `I18n` struct for internationalization.
+pub fn gen_studio_info(studio: &StudioInfo, page: usize, per_page: usize, i18n: &I18n) -> String {
+    let t = |key: &str| i18n.translate(key);
+
+    let mut text = format!(
+        "<code>{0}</code> | <a href=\"{1}\"><b>{2}</b></a>\n",
+        studio.id,
+        escape_html(&studio.site_url),
+        escape_html(&studio.name)
+    );
+
+    if studio.is_animation_studio {
+        text.push_str(&format!("🎨 | <i>{}</i>\n", t("animation_studio")));
+    }
+
+    if let Some(favourites) = studio.favourites {
+        text.push_str(&format!(
+            "❤️ | <b>{0}</b>: <i>{1}</i>\n",
+            t("favourites"),
+            favourites
+        ));
+    }
+
+    if !studio.media.nodes.is_empty() {
+        text.push_str(&format!("\n🎬 <b>{}</b>:\n", t("productions")));
+    }
+
+    let offset = (page - 1) * per_page;
+
+    for production in studio.media.nodes.iter().skip(offset).take(per_page) {
+        text.push_str(&format!(
+            "<code>{0}</code> | <b>{1}</b>",
+            production.id,
+            escape_html(production.title.romaji.as_deref().unwrap_or_default())
+        ));
+
+        if let Some(format) = production.format.as_ref() {
+            text.push_str(&format!(
+                " · <i>{}</i>",
+                translate_enum("format", format, i18n)
+            ));
+        }
+        if let Some(score) = production.average_score {
+            text.push_str(&format!(" · 🌟 <i>{:02}%</i>", score));
+        }
+
+        text.push('\n');
+    }
+
+    text
+}
+
+/// Generates the info of a staff member.
+///
+/// # Arguments
+///
+/// * `staff` - The staff member.
+/// * `i18n` - A reference to the `I18n` struct for internationalization.
+pub fn gen_staff_info(staff: &Staff, i18n: &I18n) -> String {
+    let t = |key: &str| i18n.translate(key);
+
+    let mut text = format!(
+        "<code>{0}</code> | <b>{1}</b>\n",
+        staff.id,
+        escape_html(&staff.name.full)
+    );
+
+    if let Some(native) = staff.name.native.as_ref() {
+        text.push_str(&format!("<i>{}</i>\n", native));
+    }
+
+    if let Some(language) = staff.language.as_ref() {
+        text.push_str(&format!(
+            "\n🗣 | <b>{}</b>: <i>{}</i>",
+            t("language_label"),
+            language
+        ));
+    }
+
+    if let Some(age) = staff.age {
+        text.push_str(&format!("\n🎂 | <b>{}</b>: <i>{}</i>", t("age"), age));
+    }
+
+    if let Some(home_town) = staff.home_town.as_ref() {
+        text.push_str(&format!(
+            "\n🏠 | <b>{}</b>: <i>{}</i>",
+            t("home_town"),
+            home_town
+        ));
+    }
+
+    if !staff.primary_occupations.is_empty() {
+        text.push_str(&format!(
+            "\n💼 | <b>{}</b>: <i>{}</i>",
+            t("occupations"),
+            staff.primary_occupations.join(", ")
+        ));
+    }
+
+    text.push('\n');
+
+    if let Some(description) = staff.description.as_ref().filter(|d| !d.is_empty()) {
+        text.push_str(&format!(
+            "\n<blockquote expandable>{}</blockquote>\n",
+            shorten_html(&sanitize_html(description, HtmlTarget::Telegram), 400)
+        ));
+    }
+
+    text
+}
+
+/// Generates a list of the characters voiced by a staff member, with pagination and
+/// internationalization support.
+///
+/// # Arguments
+///
+/// * `characters` - A slice of `VoicedCharacter` structs to be displayed.
+/// * `page` - The current page number for pagination.
+/// * `per_page` - The number of characters per page.
+/// * `i18n` - A reference to the `I18n` struct for internationalization.
+pub fn gen_voiced_list(
+    characters: &[VoicedCharacter],
+    page: usize,
+    per_page: usize,
+    i18n: &I18n,
+) -> String {
+    let t = |key: &str| i18n.translate(key);
+
+    let mut text = format!("🎙 <b>{}</b>:\n", t("characters_voiced"));
+
+    let offset = (page - 1) * per_page;
+
+    for character in characters.iter().skip(offset).take(per_page) {
+        text.push_str(&format!(
+            "👤 | <code>{0}</code>. <b>{1}</b>\n",
+            character.id,
+            escape_html(&character.name.full)
+        ));
+    }
+
+    text
+}
+
+/// Generates a list of the works of a staff member, with pagination and internationalization
+/// support.
+///
+/// # Arguments
+///
+/// * `works` - A slice of `Work` structs to be displayed.
+/// * `page` - The current page number for pagination.
+/// * `per_page` - The number of works per page.
+/// * `i18n` - A reference to the `I18n` struct for internationalization.
+pub fn gen_work_list(works: &[Work], page: usize, per_page: usize, i18n: &I18n) -> String {
+    let t = |key: &str| i18n.translate(key);
+
+    let mut text = format!("🎬 <b>{}</b>:\n", t("works"));
+
+    let offset = (page - 1) * per_page;
+
+    for work in works.iter().skip(offset).take(per_page) {
+        text.push_str(&format!(
+            "{0} | <code>{1}</code>. <b>{2}</b>\n",
+            if work.node.media_type.as_deref() == Some("MANGA") {
+                "📖"
+            } else {
+                "📺"
+            },
+            work.node.id,
+            escape_html(work.node.title.romaji.as_deref().unwrap_or_default())
+        ));
+
+        if let Some(role) = work.staff_role.as_ref() {
+            text.push_str(&format!("🎭 | <i>{}</i>\n", role));
+        }
+    }
+
+    text
+}
+
+/// Generates a list of voice actors with pagination and internationalization support.
+///
+/// # Arguments
+///
+/// * `actors` - A slice of `VoiceActor` structs to be displayed.
+/// * `page` - The current page number for pagination.
+/// * `per_page` - The number of voice actors per page.
+/// * `i18n` - A reference to the `I18n` struct for internationalization.
+pub fn gen_voice_actor_list(
+    actors: &[VoiceActor],
+    page: usize,
+    per_page: usize,
+    i18n: &I18n,
+) -> String {
+    let t = |key: &str| i18n.translate(key);
+
+    let mut text = format!("🎙 <b>{}</b>:\n", t("voice_actors"));
+
+    let offset = (page - 1) * per_page;
+
+    for actor in actors.iter().skip(offset).take(per_page) {
+        text.push_str(&format!(
+            "🗣 | <code>{0}</code>. <a href=\"{1}\">{2}</a>",
+            actor.id,
+            escape_html(&actor.site_url),
+            escape_html(&actor.name.full)
+        ));
+
+        if let Some(language) = actor.language.as_ref() {
+            text.push_str(&format!(" — <i>{}</i>", language));
+        }
+
+        text.push('\n');
+    }
+
+    text
+}
+
+/// Generates a list of the media a character appears in, with pagination and
+/// internationalization support.
+///
+/// # Arguments
+///
+/// * `appearances` - A slice of `Appearance` structs to be displayed.
+/// * `page` - The current page number for pagination.
+/// * `per_page` - The number of appearances per page.
+/// * `i18n` - A reference to the `I18n` struct for internationalization.
+pub fn gen_appearance_list(
+    appearances: &[Appearance],
+    page: usize,
+    per_page: usize,
+    i18n: &I18n,
+) -> String {
+    let t = |key: &str| i18n.translate(key);
+
+    let mut text = format!("🎬 <b>{}</b>:\n", t("medias"));
+
+    let offset = (page - 1) * per_page;
+
+    for appearance in appearances.iter().skip(offset).take(per_page) {
+        text.push_str(&format!(
+            "{0} | <code>{1}</code>. <b>{2}</b>\n",
+            match appearance.media_type {
+                MediaType::Anime => "📺",
+                MediaType::Manga => "📖",
+            },
+            appearance.id,
+            escape_html(&appearance.title)
+        ));
+
+        if let Some(role) = appearance.role.as_ref() {
+            text.push_str(&format!(
+                "🎭 | <i>{}</i>\n",
+                t(&format!("role_{}", role.to_lowercase()))
+            ));
+        }
+    }
+
+    text
+}
+
+/// Generates a list of the recommendations of a media, with their ratings.
+///
+/// # Arguments
+///
+/// * `recommendations` - The recommendations of the page.
+/// * `i18n` - A reference to the `I18n` struct for internationalization.
+pub fn gen_recommendation_list(recommendations: &[Recommendation], i18n: &I18n) -> String {
+    let t = |key: &str| i18n.translate(key);
+
+    let mut text = format!("💡 <b>{}</b>:\n", t("recommendations"));
+
+    for recommendation in recommendations.iter() {
+        text.push_str(&format!(
+            "{0} | <code>{1}</code>. <b>{2}</b> — 👍 <i>{3}</i>\n",
+            match recommendation.media_type {
+                MediaType::Anime => "📺",
+                MediaType::Manga => "📖",
+            },
+            recommendation.id,
+            escape_html(&recommendation.title),
+            recommendation.rating
+        ));
+    }
+
+    text
+}
+
+/// Generates the list of commands of `/help`, grouped by category.
+///
+/// # Arguments
+///
+/// * `entries` - The help entries of the plugins.
+/// * `i18n` - A reference to the `I18n` struct for internationalization.
+pub fn gen_help_list(entries: &[HelpEntry], i18n: &I18n) -> String {
+    let mut text = i18n.translate("help");
+
+    for category in HelpCategory::ALL {
+        let commands = entries
+            .iter()
+            .filter(|entry| entry.category == category)
+            .collect::<Vec<_>>();
+        if commands.is_empty() {
+            continue;
+        }
+
+        text.push_str(&format!(
+            "\n\n<b>{}</b>",
+            i18n.translate(format!("help_category_{}", category.name()))
+        ));
+        for entry in commands {
+            text.push_str(&format!(
+                "\n/{0} — {1}",
+                entry.command,
+                i18n.translate(format!("command_{}", entry.command))
+            ));
+        }
+    }
+
+    text
+}
+
+/// Generates the details of a command of `/help`, with its aliases and inline prefix.
+///
+/// # Arguments
+///
+/// * `entry` - The help entry of the command.
+/// * `i18n` - A reference to the `I18n` struct for internationalization.
+pub fn gen_help_entry(entry: &HelpEntry, i18n: &I18n) -> String {
+    let mut text = format!(
+        "<b>/{0}</b>\n\n{1}\n",
+        entry.command,
+        i18n.translate(format!("help_{}", entry.command))
+    );
+
+    if !entry.aliases.is_empty() {
+        let aliases = entry
+            .aliases
+            .iter()
+            .map(|alias| format!("<code>/{}</code>", alias))
+            .collect::<Vec<_>>()
+            .join(", ");
+        text.push_str(&format!(
+            "\n{}",
+            i18n.translate_with_args("help_aliases", hashmap! { "aliases" => aliases })
+        ));
+    }
+    if let Some(prefix) = entry.inline {
+        text.push_str(&format!(
+            "\n{}",
+            i18n.translate_with_args("help_inline", hashmap! { "prefix" => prefix })
+        ));
+    }
+
+    text
+}
+
+/// Generates a list of the characters whose birthday is today, with their ages and the media
+/// they are best known for.
+///
+/// # Arguments
+///
+/// * `characters` - The characters of the page.
+/// * `i18n` - A reference to the `I18n` struct for internationalization.
+pub fn gen_birthday_list(characters: &[BirthdayCharacter], i18n: &I18n) -> String {
+    let t = |key: &str| i18n.translate(key);
+
+    let mut text = format!("🎂 <b>{}</b>:\n\n", t("birthdays"));
+
+    for character in characters.iter() {
+        text.push_str(&format!(
+            "<code>{0}</code> | <b>{1}</b>",
+            character.id,
+            escape_html(&character.name)
+        ));
+        if let Some(age) = character.age.as_ref() {
+            text.push_str(&format!(" — {0}: <i>{1}</i>", t("age"), escape_html(age)));
+        }
+        if let Some(media) = character.known_for.as_ref() {
+            text.push_str(&format!(
+                "\n{0} <i>{1}</i>",
+                match media.media_type {
+                    MediaType::Anime => "📺",
+                    MediaType::Manga => "📖",
+                },
+                escape_html(&media.title)
+            ));
+        }
+        text.push('\n');
+    }
+
+    text
+}
+
+/// Generates a list of the media of a genre or tag, with their scores.
+///
+/// # Arguments
+///
+/// * `genre` - The AniList name of the genre or tag.
+/// * `entries` - The media of the page.
+/// * `i18n` - A reference to the `I18n` struct for internationalization.
+pub fn gen_genre_list(genre: &str, entries: &[GenreEntry], i18n: &I18n) -> String {
+    let mut text = format!(
+        "🎭 <b>{}</b>:\n\n",
+        escape_html(translate_enum("genre", genre, i18n))
+    );
+
+    for entry in entries.iter() {
+        text.push_str(&format!(
+            "<code>{0}</code> | <b>{1}</b>",
+            entry.id,
+            escape_html(&entry.title)
+        ));
+        if let Some(score) = entry.average_score {
+            text.push_str(&format!(" · 🌟 <i>{:02}%</i>", score));
+        }
+        text.push('\n');
+    }
+
+    text
+}
+
+/// Generates a numbered list of a page of a ranking, with the scores.
+///
+/// # Arguments
+///
+/// * `kind` - The ranking.
+/// * `entries` - The media of the page, the best scored first.
+/// * `offset` - How many media were ranked in the previous pages.
+/// * `i18n` - A reference to the `I18n` struct for internationalization.
+pub fn gen_top_list(kind: TopKind, entries: &[TopEntry], offset: usize, i18n: &I18n) -> String {
+    let mut text = format!(
+        "🏆 <b>{}</b>:\n\n",
+        i18n.translate(&format!("top_{}", kind.name()))
+    );
+
+    for (i, entry) in entries.iter().enumerate() {
+        text.push_str(&format!(
+            "<b>{0}.</b> <code>{1}</code> | <b>{2}</b>",
+            offset + i + 1,
+            entry.id,
+            escape_html(&entry.title)
+        ));
+        if let Some(score) = entry.average_score {
+            text.push_str(&format!(" · 🌟 <i>{:02}%</i>", score));
+        }
+        text.push('\n');
+    }
 
     text
 }
 
-/// Generates a formatted string containing detailed information about a character.
+/// Generates the buttons to change the order of stashed search results, with signed callbacks.
+///
+/// # Arguments
+///
+/// * `callback` - The callback prefix, e.g. `anime sort`.
+/// * `stash_id` - The ID of the stashed search.
+/// * `current` - The current order.
+/// * `sender_id` - The ID of the user who searched.
+/// * `i18n` - A reference to the `I18n` struct for internationalization.
+pub fn gen_sort_buttons(
+    callback: &str,
+    stash_id: &str,
+    current: SearchSort,
+    sender_id: i64,
+    i18n: &I18n,
+) -> Vec<Inline> {
+    SearchSort::ALL
+        .iter()
+        .map(|sort| {
+            let label = i18n.translate(format!("sort_{}_btn", sort.name()));
+
+            button::inline(
+                if *sort == current {
+                    format!("· {} ·", label)
+                } else {
+                    label
+                },
+                sign_callback(&format!(
+                    "{0} {1} {2} {3}",
+                    callback,
+                    stash_id,
+                    sort.name(),
+                    sender_id
+                )),
+            )
+        })
+        .collect()
+}
+
+/// Generates the pagination buttons, see [`gen_pagination_entries`].
+///
+/// # Arguments
+///
+/// * `callback` - The callback data the page number is appended to.
+/// * `page` - The current page number.
+/// * `max_pages` - The number of pages.
+pub fn gen_pagination_buttons(callback: &str, page: usize, max_pages: usize) -> Vec<Inline> {
+    gen_pagination_buttons_with(page, max_pages, |i| format!("{0} {1}", callback, i))
+}
+
+/// Generates the pagination buttons of a signed callback, see [`gen_pagination_buttons`].
+///
+/// # Arguments
+///
+/// * `callback` - The callback data the page number is appended to, before it is signed.
+/// * `page` - The current page number.
+/// * `max_pages` - The number of pages.
+pub fn gen_signed_pagination_buttons(callback: &str, page: usize, max_pages: usize) -> Vec<Inline> {
+    gen_pagination_buttons_with(page, max_pages, |i| {
+        sign_callback(&format!("{0} {1}", callback, i))
+    })
+}
+
+/// Generates the pagination buttons, for callbacks that do not end with the page number.
+///
+/// # Arguments
+///
+/// * `page` - The current page number.
+/// * `max_pages` - The number of pages.
+/// * `callback` - Builds the callback data of a page.
+pub fn gen_pagination_buttons_with<F>(page: usize, max_pages: usize, callback: F) -> Vec<Inline>
+where
+    F: Fn(usize) -> String,
+{
+    gen_pagination_entries(page, max_pages, callback)
+        .into_iter()
+        .map(|(label, data)| button::inline(label, data))
+        .collect()
+}
+
+/// Generates the labels and callback data of the pagination buttons.
+///
+/// Shows up to two pages around the current one, plus jumps to the first and last pages
+/// when they are out of that window.
+///
+/// # Arguments
+///
+/// * `page` - The current page number.
+/// * `max_pages` - The number of pages.
+/// * `callback` - Builds the callback data of a page.
+pub fn gen_pagination_entries<F>(
+    page: usize,
+    max_pages: usize,
+    callback: F,
+) -> Vec<(String, String)>
+where
+    F: Fn(usize) -> String,
+{
+    /// How many pages are shown on each side of the current one.
+    const RADIUS: usize = 2;
+
+    if max_pages == 0 {
+        return Vec::new();
+    }
+
+    let page = page.clamp(1, max_pages);
+    let first = page.saturating_sub(RADIUS).max(1);
+    let last = (page + RADIUS).min(max_pages);
+
+    let mut entries = Vec::new();
+
+    if first > 1 {
+        entries.push(("« 1".to_string(), callback(1)));
+    }
+
+    for i in first..=last {
+        let label = if i < page {
+            format!("⬅️ {0}", i)
+        } else if i > page {
+            format!("{0} ➡️", i)
+        } else {
+            format!("· {0} ·", i)
+        };
+
+        entries.push((label, callback(i)));
+    }
+
+    if last < max_pages {
+        entries.push((format!("{0} »", max_pages), callback(max_pages)));
+    }
+
+    entries
+}
+
+/// Parses a media link of a site, e.g. `https://myanimelist.net/anime/5114/Title`.
+///
+/// Returns the media type and ID, or `None` if the link is not a media link of the site.
+///
+/// # Arguments
+///
+/// * `url` - The link, with or without the scheme.
+/// * `host` - The host of the site, e.g. `myanimelist.net`.
+fn parse_media_url(url: &str, host: &str) -> Option<(MediaType, i64)> {
+    let url = url
+        .trim()
+        .trim_start_matches("https://")
+        .trim_start_matches("http://")
+        .trim_start_matches("www.");
+
+    let mut segments = url
+        .strip_prefix(host)?
+        .strip_prefix('/')?
+        .split(['/', '?', '#']);
+    let media_type = match segments.next()? {
+        "anime" => MediaType::Anime,
+        "manga" => MediaType::Manga,
+        _ => return None,
+    };
+    let id = segments.next()?.parse::<i64>().ok()?;
+
+    Some((media_type, id))
+}
+
+/// Parses a MyAnimeList media link, e.g. `https://myanimelist.net/anime/5114/Title`.
+///
+/// Returns the media type and the MyAnimeList ID.
+///
+/// # Arguments
+///
+/// * `url` - The link, with or without the scheme.
+pub fn parse_mal_url(url: &str) -> Option<(MediaType, i64)> {
+    parse_media_url(url, "myanimelist.net")
+}
+
+/// Parses a MyAnimeList reference: a media link, or `mal:` followed by the ID of a media of the
+/// given type, e.g. `mal:21`.
+///
+/// Returns the media type and the MyAnimeList ID.
+///
+/// # Arguments
+///
+/// * `text` - The reference.
+/// * `media_type` - The media type of a `mal:` reference.
+pub fn parse_mal_reference(text: &str, media_type: MediaType) -> Option<(MediaType, i64)> {
+    match text.trim().strip_prefix("mal:") {
+        Some(id) => Some((media_type, id.parse::<i64>().ok().filter(|id| *id > 0)?)),
+        None => parse_mal_url(text),
+    }
+}
+
+/// A media typed after a command, by its ID.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum MediaReference {
+    /// An AniList ID, e.g. `21`.
+    AniList(i64),
+    /// A MyAnimeList ID, from a link or a `mal:` reference.
+    MyAnimeList(i64),
+}
+
+/// Parses the media typed after a command: an AniList ID, or a MyAnimeList reference of the
+/// given type, see [`parse_mal_reference`].
+///
+/// Returns `None` for anything else, which is searched by title.
+///
+/// # Arguments
+///
+/// * `text` - The first argument of the command.
+/// * `media_type` - The media type of the command.
+pub fn parse_media_reference(text: &str, media_type: MediaType) -> Option<MediaReference> {
+    match parse_mal_reference(text, media_type) {
+        Some((found, mal_id)) => {
+            (found == media_type).then_some(MediaReference::MyAnimeList(mal_id))
+        }
+        None => text.parse::<i64>().ok().map(MediaReference::AniList),
+    }
+}
+
+/// Parses an AniList media link, e.g. `https://anilist.co/anime/5114/Title`.
+///
+/// Returns the media type and the AniList ID.
+///
+/// # Arguments
+///
+/// * `url` - The link, with or without the scheme.
+pub fn parse_anilist_url(url: &str) -> Option<(MediaType, i64)> {
+    parse_media_url(url, "anilist.co")
+}
+
+/// An AniList link found in a message.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum AniListLink {
+    /// An anime, by its ID.
+    Anime(i64),
+    /// A manga, by its ID.
+    Manga(i64),
+    /// A character, by its ID.
+    Character(i64),
+    /// A user, by their name.
+    User(String),
+}
+
+/// Finds the first AniList link of a message, e.g. `https://anilist.co/character/1/Spike`.
+///
+/// # Arguments
+///
+/// * `text` - The text of the message.
+pub fn find_anilist_link(text: &str) -> Option<AniListLink> {
+    const HOST: &str = "anilist.co/";
+
+    text.split_whitespace().find_map(|word| {
+        let start = word.find(HOST)?;
+        // Only the host itself, not e.g. `notanilist.co`.
+        let before = &word[..start];
+        if !(before.is_empty() || before.ends_with('/') || before.ends_with('.')) {
+            return None;
+        }
+
+        let mut segments = word[start + HOST.len()..].split(['/', '?', '#']);
+        let kind = segments.next()?;
+        let value = segments.next()?;
+        match kind {
+            "anime" => value.parse().ok().map(AniListLink::Anime),
+            "manga" => value.parse().ok().map(AniListLink::Manga),
+            "character" => value.parse().ok().map(AniListLink::Character),
+            "user" if !value.is_empty() && value.chars().all(|c| c.is_ascii_alphanumeric()) => {
+                Some(AniListLink::User(value.to_string()))
+            }
+            _ => None,
+        }
+    })
+}
+
+/// The username of the bot, known once it is connected.
+static BOT_USERNAME: OnceLock<String> = OnceLock::new();
+
+/// Sets the username of the bot, for the deep links. Only the first call has an effect.
+///
+/// # Arguments
+///
+/// * `username` - The username of the bot, without the `@`.
+pub fn set_bot_username(username: &str) {
+    let _ = BOT_USERNAME.set(username.to_string());
+}
+
+/// Gets the username of the bot, `None` until it is connected.
+pub fn bot_username() -> Option<&'static str> {
+    BOT_USERNAME.get().map(String::as_str)
+}
+
+/// Signs the data of a callback, see [`callback_signer`].
+///
+/// # Arguments
+///
+/// * `parts` - The callback data, its parts separated by spaces.
+pub fn sign_callback(parts: &str) -> String {
+    callback_signer::signer().sign(parts, Utc::now())
+}
+
+/// Verifies the data of a callback signed by [`sign_callback`].
+///
+/// Returns the data without its token.
+///
+/// # Arguments
+///
+/// * `data` - The signed callback data.
+pub fn verify_callback(data: &str) -> Result<&str, CallbackError> {
+    callback_signer::signer().verify(data, Utc::now())
+}
+
+/// Gets the data of a callback query, without the token of a signed callback.
+///
+/// The tokens were already checked by the `VerifyCallbacks` middleware.
+///
+/// # Arguments
+///
+/// * `query` - The callback query.
+pub fn callback_data(query: &CallbackQuery) -> String {
+    let data = String::from_utf8_lossy(query.data());
+
+    callback_signer::strip_token(&data).to_string()
+}
+
+/// A card opened by a `/start` deep link, e.g. `t.me/bot?start=anime_1735`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum StartPayload {
+    /// An anime, by its ID.
+    Anime(i64),
+    /// A manga, by its ID.
+    Manga(i64),
+    /// A character, by its ID.
+    Character(i64),
+    /// A user, by their ID.
+    User(i32),
+}
+
+impl StartPayload {
+    /// Parses a payload, `None` if it is not a card the bot can open.
+    ///
+    /// # Arguments
+    ///
+    /// * `payload` - The payload of the `/start` command, e.g. `anime_1735`.
+    pub fn parse(payload: &str) -> Option<Self> {
+        let (kind, id) = payload.split_once('_')?;
+        // Only plain numbers, not e.g. `+1` or `1 2`.
+        if id.is_empty() || !id.bytes().all(|byte| byte.is_ascii_digit()) {
+            return None;
+        }
+
+        match kind {
+            "anime" => id.parse().ok().filter(|id| *id > 0).map(Self::Anime),
+            "manga" => id.parse().ok().filter(|id| *id > 0).map(Self::Manga),
+            "char" => id.parse().ok().filter(|id| *id > 0).map(Self::Character),
+            "user" => id.parse().ok().filter(|id| *id > 0).map(Self::User),
+            _ => None,
+        }
+    }
+
+    /// Gets the payload of the `/start` command, e.g. `anime_1735`.
+    pub fn as_payload(&self) -> String {
+        match self {
+            Self::Anime(id) => format!("anime_{}", id),
+            Self::Manga(id) => format!("manga_{}", id),
+            Self::Character(id) => format!("char_{}", id),
+            Self::User(id) => format!("user_{}", id),
+        }
+    }
+}
+
+/// Generates the deep link that opens a card in the bot.
 ///
 /// # Arguments
 ///
-/// * `char` - A reference to an `Character` struct containing the character details.
-/// * `i18n` - A reference to an `I18n` struct containing the translations.
-pub fn gen_char_info(char: &Character, i18n: &I18n) -> String {
-    let t = |key: &str| i18n.translate(key);
+/// * `username` - The username of the bot.
+/// * `payload` - The card to open.
+pub fn gen_deep_link(username: &str, payload: StartPayload) -> String {
+    format!("https://t.me/{0}?start={1}", username, payload.as_payload())
+}
+
+/// Generates the button that shares a card to another chat, as a deep link.
+///
+/// Returns `None` until the username of the bot is known.
+///
+/// # Arguments
+///
+/// * `payload` - The card to share.
+/// * `i18n` - The i18n resource.
+pub fn gen_share_button(payload: StartPayload, i18n: &I18n) -> Option<Inline> {
+    let link = gen_deep_link(BOT_USERNAME.get()?, payload);
 
-    let mut text = format!("<code>{0}</code> | <b>{1}</b>\n", char.id, char.name.full());
+    Some(button::url(
+        i18n.translate("share_btn"),
+        format!(
+            "https://t.me/share/url?url={}",
+            link.replace(':', "%3A")
+                .replace('/', "%2F")
+                .replace('?', "%3F")
+                .replace('=', "%3D")
+        ),
+    ))
+}
 
-    if let Some(age) = char.age.as_ref() {
-        text.push_str(&format!("\n🎂 | <b>{}</b>: <i>{}</i>", t("age"), age));
+/// What went wrong in a handler, as told to the user.
+///
+/// The error itself is only logged, it may carry queries, paths or tokens.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ErrorClass {
+    /// Telegram asked the bot to slow down.
+    FloodWait,
+    /// AniList could not be reached or is rate limiting the bot.
+    AniListUnavailable,
+    /// The message was too long to be sent.
+    MessageTooLong,
+    /// Telegram refused the media of the message.
+    MediaInvalid,
+    /// The query was answered too late, e.g. after a slow AniList request.
+    QueryTooOld,
+    /// Anything else, worth a report.
+    Unknown,
+}
+
+impl ErrorClass {
+    /// Classifies an error, looking through its sources.
+    ///
+    /// # Arguments
+    ///
+    /// * `error` - The error returned by a handler.
+    pub fn of(error: &(dyn std::error::Error + 'static)) -> Self {
+        let mut source = Some(error);
+
+        while let Some(error) = source {
+            let rpc_class = match error.downcast_ref::<InvocationError>() {
+                Some(InvocationError::Rpc(rpc)) => Self::from_rpc_error(&rpc.name),
+                _ => None,
+            };
+            if let Some(class) = rpc_class {
+                return class;
+            }
+            if error.is::<FetchError>() || error.is::<QueryError>() {
+                return Self::AniListUnavailable;
+            }
+
+            source = error.source();
+        }
+
+        Self::Unknown
     }
 
-    if let Some(blood_type) = char.blood_type.as_ref() {
-        text.push_str(&format!(
-            "\n🩸 | <b>{}</b>: <i>{}</i>\n",
-            t("blood_type"),
-            blood_type
-        ));
+    /// Classifies an error answered by Telegram, `None` if it is not a well-known one.
+    ///
+    /// # Arguments
+    ///
+    /// * `name` - The name of the error, e.g. `MESSAGE_TOO_LONG`.
+    pub fn from_rpc_error(name: &str) -> Option<Self> {
+        match name {
+            _ if is_flood_error(name) => Some(Self::FloodWait),
+            "MESSAGE_TOO_LONG" | "MEDIA_CAPTION_TOO_LONG" => Some(Self::MessageTooLong),
+            "MEDIA_INVALID"
+            | "MEDIA_EMPTY"
+            | "PHOTO_INVALID_DIMENSIONS"
+            | "WEBPAGE_CURL_FAILED"
+            | "WEBPAGE_MEDIA_EMPTY" => Some(Self::MediaInvalid),
+            "QUERY_ID_INVALID" => Some(Self::QueryTooOld),
+            _ => None,
+        }
     }
 
-    if let Some(date_of_birth) = char.date_of_birth.as_ref() {
-        if date_of_birth.is_valid() {
-            text.push_str(&format!(
-                "\n📅 | <b>{}</b>: <i>{}</i>\n",
-                t("date_of_birth"),
-                date_of_birth.format("{dd}/{mm}/{yyyy}")
-            ));
+    /// Gets the key of the text telling the user about it, the alerts add `_alert` to it.
+    pub fn key(self) -> &'static str {
+        match self {
+            Self::FloodWait => "slow_down",
+            Self::AniListUnavailable => "anilist_unavailable",
+            Self::MessageTooLong => "message_too_long",
+            Self::MediaInvalid => "media_invalid",
+            Self::QueryTooOld => "query_too_old",
+            Self::Unknown => "error_report",
         }
     }
+}
 
-    if !char.description.is_empty() {
-        text.push_str(&format!(
-            "\n<blockquote expandable>{}</blockquote>\n",
-            shorten_text(remove_html(&char.description), 400)
-        ));
+/// Whether an error answered by Telegram asks the bot to slow down.
+///
+/// # Arguments
+///
+/// * `name` - The name of the error, e.g. `FLOOD_WAIT`.
+pub fn is_flood_error(name: &str) -> bool {
+    name.starts_with("FLOOD_") || name == "SLOWMODE_WAIT"
+}
+
+/// How many times a request to Telegram is retried before giving up.
+pub const SEND_RETRIES: u32 = 3;
+
+/// The longest flood wait honored by [`send_with_retry`], longer ones fail right away.
+pub const MAX_FLOOD_WAIT: Duration = Duration::from_secs(30);
+
+/// How long to wait before retrying a request that timed out.
+const TIMEOUT_RETRY_DELAY: Duration = Duration::from_secs(1);
+
+/// How long to wait before retrying a request Telegram failed, `None` if it is not worth it.
+///
+/// # Arguments
+///
+/// * `name` - The name of the error, e.g. `FLOOD_WAIT`.
+/// * `value` - The value of the error, the seconds to wait for the flood waits.
+pub fn retry_delay(name: &str, value: Option<u32>) -> Option<Duration> {
+    if is_flood_error(name) {
+        let wait = Duration::from_secs(value.unwrap_or(1).into());
+
+        (wait <= MAX_FLOOD_WAIT).then_some(wait)
+    } else if name == "Timeout" {
+        Some(TIMEOUT_RETRY_DELAY)
+    } else {
+        None
     }
+}
 
-    text
+/// Sends a request to Telegram, e.g. a reply or an edit, retrying the flood waits and the
+/// timeouts.
+///
+/// Returns `None` when an edit did not change the message, which is not worth an error.
+///
+/// # Arguments
+///
+/// * `send` - Sends the request, called again for every retry.
+pub async fn send_with_retry<T, F, Fut>(mut send: F) -> Result<Option<T>, InvocationError>
+where
+    F: FnMut() -> Fut,
+    Fut: Future<Output = Result<T, InvocationError>>,
+{
+    let mut attempt = 0;
+
+    loop {
+        match send().await {
+            Ok(value) => return Ok(Some(value)),
+            Err(InvocationError::Rpc(rpc)) if rpc.name == "MESSAGE_NOT_MODIFIED" => {
+                return Ok(None);
+            }
+            Err(InvocationError::Rpc(rpc)) => {
+                let delay = retry_delay(&rpc.name, rpc.value).filter(|_| attempt < SEND_RETRIES);
+                let Some(delay) = delay else {
+                    return Err(InvocationError::Rpc(rpc));
+                };
+
+                attempt += 1;
+                log::debug!(
+                    "retrying a request in {:?} after {} (attempt {})",
+                    delay,
+                    rpc.name,
+                    attempt
+                );
+                tokio::time::sleep(delay).await;
+            }
+            Err(e) => return Err(e),
+        }
+    }
 }
 
-/// Generates a list of characters with pagination and internationalization support.
+/// The longest caption Telegram accepts on a photo, in UTF-16 code units.
+pub const MAX_CAPTION_LENGTH: usize = 1024;
+
+/// Whether a card text fits in the caption of a photo.
+///
+/// Only the visible text counts, the HTML tags do not.
 ///
 /// # Arguments
 ///
-/// * `characters` - A slice of `Character` structs to be displayed.
-/// * `page` - The current page number for pagination.
-/// * `per_page` - The number of characters per page.
-/// * `i18n` - A reference to the `I18n` struct for internationalization.
-pub fn gen_char_list(
-    characters: &[Character],
-    page: usize,
-    per_page: usize,
+/// * `text` - The HTML text of the card.
+pub fn fits_in_caption(text: &str) -> bool {
+    remove_html(text).encode_utf16().count() <= MAX_CAPTION_LENGTH
+}
+
+/// Shortens a page of a card, e.g. its episodes, to fit in the caption of a photo.
+///
+/// The last lines of the page are dropped and replaced by an ellipsis, so the entries left
+/// keep their tags. Pages that already fit are returned as they are.
+///
+/// # Arguments
+///
+/// * `text` - The HTML text of the page.
+pub fn fit_in_caption(text: &str) -> String {
+    if fits_in_caption(text) {
+        return text.to_string();
+    }
+
+    let mut lines = text.trim_end().lines().collect::<Vec<_>>();
+    while lines.len() > 1 {
+        lines.pop();
+
+        let shortened = format!("{}\n...", lines.join("\n").trim_end());
+        if fits_in_caption(&shortened) {
+            return shortened;
+        }
+    }
+
+    // A single line does not fit, every char takes at most two code units.
+    shorten_html(text, MAX_CAPTION_LENGTH / 2)
+}
+
+/// Generates the message of a card: a photo with the text as its caption, or only the text
+/// when there is no image or the text does not fit in a caption.
+///
+/// # Arguments
+///
+/// * `text` - The HTML text of the card.
+/// * `image_url` - The link of the image of the card, if any.
+pub fn gen_card_message(text: &str, image_url: Option<&str>) -> InputMessage {
+    match image_url {
+        Some(image_url) if fits_in_caption(text) => InputMessage::html(text).photo_url(image_url),
+        _ => InputMessage::html(text),
+    }
+}
+
+/// Whether Telegram rejected an uploaded image sent again, e.g. because its file reference
+/// expired, so it has to be uploaded from its link again.
+///
+/// # Arguments
+///
+/// * `name` - The name of the error, e.g. `FILE_REFERENCE_EXPIRED`.
+pub fn is_stale_file_error(name: &str) -> bool {
+    name.starts_with("FILE_REFERENCE_")
+        || ErrorClass::from_rpc_error(name) == Some(ErrorClass::MediaInvalid)
+}
+
+/// Generates the inline query result of a card: a photo with the text as its caption, or an
+/// article when there is no image or the text does not fit in a caption.
+///
+/// Articles show the image through the link preview, if any.
+///
+/// # Arguments
+///
+/// * `title` - The title of the result.
+/// * `description` - The description of the result.
+/// * `text` - The HTML text of the card.
+/// * `image_url` - The link of the image of the card, if any.
+/// * `thumb_url` - The link of the thumbnail of the result, if any.
+/// * `button` - The label and the callback data of the button of the card.
+pub fn gen_card_result(
+    title: String,
+    description: String,
+    text: &str,
+    image_url: Option<&str>,
+    thumb_url: Option<&str>,
+    button: (String, String),
+) -> tl::enums::InputBotInlineResult {
+    let (label, data) = button;
+
+    match image_url {
+        Some(image_url) if fits_in_caption(text) => {
+            let web_document = |url: &str| {
+                tl::types::InputWebDocument {
+                    url: url.to_string(),
+                    size: 0,
+                    mime_type: "image/jpeg".to_string(),
+                    attributes: Vec::new(),
+                }
+                .into()
+            };
+            let (message, entities) = parsers::parse_html_message(text);
+            let markup = tl::types::ReplyInlineMarkup {
+                rows: vec![
+                    tl::types::KeyboardButtonRow {
+                        buttons: vec![
+                            tl::types::KeyboardButtonCallback {
+                                requires_password: false,
+                                text: label,
+                                data: data.into_bytes(),
+                            }
+                            .into(),
+                        ],
+                    }
+                    .into(),
+                ],
+            };
+
+            tl::types::InputBotInlineResult {
+                id: format!("{:x}", rand::random::<u64>()),
+                r#type: "photo".to_string(),
+                title: Some(title),
+                description: Some(description),
+                url: None,
+                thumb: Some(web_document(thumb_url.unwrap_or(image_url))),
+                content: Some(web_document(image_url)),
+                send_message: tl::types::InputBotInlineMessageMediaAuto {
+                    invert_media: false,
+                    message,
+                    entities: Some(entities),
+                    reply_markup: Some(markup.into()),
+                }
+                .into(),
+            }
+            .into()
+        }
+        _ => {
+            let text = match image_url {
+                Some(image_url) => format!("<a href=\"{}\">⁠</a>", image_url) + text,
+                None => text.to_string(),
+            };
+            let message =
+                InputMessage::html(text)
+                    .link_preview(true)
+                    .reply_markup(&reply_markup::inline(vec![vec![button::inline(
+                        label, data,
+                    )]]));
+
+            let mut article = inline::query::Article::new(title, message).description(description);
+            if let Some(thumb_url) = thumb_url {
+                article = article.thumb_url(thumb_url);
+            }
+
+            article.into()
+        }
+    }
+}
+
+/// The resources the cards are sent with, borrowed from the ones injected into the handler.
+#[derive(Clone, Copy)]
+pub struct CardResources<'a> {
+    /// The cards resource.
+    pub cards: &'a Cards,
+    /// The i18n resource.
+    pub i18n: &'a I18n,
+    /// The AniList resource.
+    pub ani: &'a AniList,
+}
+
+/// Sends a card, editing the message of a callback query and replying otherwise.
+///
+/// Editing replaces the photo, so the picture follows the card, e.g. when navigating to a
+/// sequel. Images already uploaded from the same link are sent again without uploading them,
+/// and cards whose image Telegram could not fetch are sent as a text.
+///
+/// # Arguments
+///
+/// * `ctx` - The context of the update.
+/// * `files` - Where the images uploaded by the cards are remembered.
+/// * `text` - The HTML text of the card.
+/// * `image_url` - The link of the image of the card, if any.
+/// * `buttons` - The buttons of the card.
+/// * `reuse_image` - Whether the uploaded image can be sent again, `false` for images that
+///   change behind the same link.
+pub async fn send_card(
+    ctx: &Context,
+    files: &MediaFiles,
+    text: &str,
+    image_url: Option<&str>,
+    buttons: Vec<Vec<Inline>>,
+    reuse_image: bool,
+) -> Result<(), InvocationError> {
+    let markup = reply_markup::inline(buttons);
+    let files = Some(files).filter(|_| reuse_image);
+
+    let uploaded = match (image_url, files) {
+        (Some(image_url), Some(files)) if fits_in_caption(text) => files
+            .get(image_url)
+            .await
+            .and_then(|photo| photo.to_media()),
+        _ => None,
+    };
+    if let (Some(media), Some(image_url), Some(files)) = (uploaded, image_url, files) {
+        let message = InputMessage::html(text)
+            .copy_media(&media)
+            .reply_markup(&markup);
+        match send_card_message(ctx, message).await {
+            Err(InvocationError::Rpc(rpc)) if is_stale_file_error(&rpc.name) => {
+                log::debug!(
+                    "the uploaded image of {} was rejected, uploading it again: {}",
+                    image_url,
+                    rpc.name
+                );
+                files.forget(image_url).await;
+            }
+            result => return result.map(|_| ()),
+        }
+    }
+
+    let message = gen_card_message(text, image_url).reply_markup(&markup);
+    match send_card_message(ctx, message).await {
+        Ok(sent) => {
+            let photo = sent
+                .and_then(|message| message.media())
+                .and_then(|media| PhotoFile::from_media(&media));
+            if let (Some(photo), Some(image_url), Some(files)) = (photo, image_url, files) {
+                files.store(image_url, photo).await;
+            }
+
+            Ok(())
+        }
+        Err(InvocationError::Rpc(rpc))
+            if image_url.is_some()
+                && ErrorClass::from_rpc_error(&rpc.name) == Some(ErrorClass::MediaInvalid) =>
+        {
+            log::warn!(
+                "failed to send the image {:?} of a card, sending it as a text: {}",
+                image_url,
+                rpc.name
+            );
+
+            send_card_message(ctx, InputMessage::html(text).reply_markup(&markup))
+                .await
+                .map(|_| ())
+        }
+        Err(e) => Err(e),
+    }
+}
+
+/// Tells the user an entry could not be fetched, with an alert for callbacks and a reply for
+/// commands.
+///
+/// # Arguments
+///
+/// * `ctx` - The context of the update.
+/// * `command` - The message of the command, `None` for callbacks.
+/// * `error` - Why the entry could not be fetched.
+/// * `i18n` - The i18n resource.
+/// * `clean_after` - How long the reply stays, `None` to keep it, see [`reply_ephemeral`].
+pub async fn send_fetch_error(
+    ctx: &Context,
+    command: Option<&Message>,
+    error: FetchError,
     i18n: &I18n,
-) -> String {
+    clean_after: Option<Duration>,
+) -> Result<(), InvocationError> {
+    let text = match error {
+        FetchError::RateLimited(retry_after) => rate_limited_text(retry_after, i18n),
+        // The buttons of old messages may point to entries that no longer exist.
+        FetchError::NotFound if ctx.is_callback_query() => i18n.translate("entry_unavailable"),
+        FetchError::NotFound => i18n.translate("not_found"),
+    };
+
+    if let Some(query) = ctx.callback_query() {
+        send_with_retry(|| query.answer().alert(remove_html(&text)).send()).await?;
+    } else {
+        reply_ephemeral(ctx, command, InputMessage::html(text), clean_after).await?;
+    }
+
+    Ok(())
+}
+
+/// Replies to a command with a message that is deleted after a while, along with the command.
+///
+/// Keeps the usage and error replies from cluttering the groups, private chats keep them. The
+/// command is only deleted when the bot can delete the messages of others, the failed
+/// deletions are logged and ignored.
+///
+/// # Arguments
+///
+/// * `ctx` - The context of the update.
+/// * `command` - The message of the command, `None` for callbacks, whose message is the
+///   bot's own and is kept.
+/// * `message` - The reply.
+/// * `ttl` - How long the messages stay, `None` to keep them.
+pub async fn reply_ephemeral(
+    ctx: &Context,
+    command: Option<&Message>,
+    message: InputMessage,
+    ttl: Option<Duration>,
+) -> Result<(), InvocationError> {
+    let reply = send_with_retry(|| ctx.reply(message.clone())).await?;
+
+    let (Some(reply), Some(ttl)) = (reply, ttl.filter(|_| !ctx.is_private())) else {
+        return Ok(());
+    };
+    let command = command.cloned();
+
+    tokio::spawn(async move {
+        tokio::time::sleep(ttl).await;
+
+        if let Err(e) = reply.delete().await {
+            log::debug!("failed to delete an ephemeral reply: {:?}", e);
+        }
+        if let Some(command) = command
+            && let Err(e) = command.delete().await
+        {
+            log::debug!(
+                "failed to delete the command of an ephemeral reply: {:?}",
+                e
+            );
+        }
+    });
+
+    Ok(())
+}
+
+/// The entries of a search command, found by a reference or by searching, see
+/// [`resolve_or_disambiguate`].
+#[async_trait]
+pub trait Resolver: Sync {
+    /// The entries found.
+    type Entry: Send;
+
+    /// Gets the key of the usage of the command.
+    fn usage_key(&self) -> &'static str;
+
+    /// Gets the inline query that searches the same entries, e.g. `!a`.
+    fn inline_query(&self) -> &'static str;
+
+    /// Fetches the entry an argument refers to, e.g. by its ID.
+    ///
+    /// Returns `None` if the argument does not refer to an entry, so it is searched.
+    ///
+    /// # Arguments
+    ///
+    /// * `arg` - The first argument of the command.
+    async fn fetch(&self, arg: &str) -> Option<Result<Self::Entry, FetchError>>;
+
+    /// Fetches the whole entry of a search result, which may lack some of its fields.
+    ///
+    /// Returns the result itself if it could not be fetched.
+    ///
+    /// # Arguments
+    ///
+    /// * `entry` - The search result.
+    async fn refetch(&self, entry: Self::Entry) -> Self::Entry;
+
+    /// Searches the entries, `None` if the request failed.
+    ///
+    /// # Arguments
+    ///
+    /// * `query` - The search query.
+    async fn search(&self, query: &SearchQuery) -> Option<Vec<Self::Entry>>;
+
+    /// Sends an entry.
+    ///
+    /// # Arguments
+    ///
+    /// * `entry` - The entry to send.
+    /// * `ctx` - The context of the update.
+    async fn send(&self, entry: Self::Entry, ctx: Context) -> ferogram::Result<()>;
+
+    /// Generates the buttons that open the search results, followed by the cancel button.
+    ///
+    /// # Arguments
+    ///
+    /// * `results` - The search results.
+    /// * `query` - The search query that found them.
+    /// * `sender_id` - The ID of the user who searched.
+    async fn gen_result_buttons(
+        &self,
+        results: Vec<Self::Entry>,
+        query: &SearchQuery,
+        sender_id: i64,
+    ) -> Vec<Vec<Inline>>;
+}
+
+/// Finds the entry a search command, or a button of its results, asks for and sends it.
+///
+/// An argument that refers to an entry is fetched, anything else is searched. Without
+/// arguments, the text of the message the command replies to is used, see [`reply_query`].
+/// A single result is sent right away and several are listed as buttons. When nothing is
+/// found, the [`search_suggestion`] results are listed instead. The callbacks end with the ID
+/// of the user who searched, only they can press the buttons.
+///
+/// The usage and not found replies are deleted after `clean_after`, see [`reply_ephemeral`].
+///
+/// # Arguments
+///
+/// * `ctx` - The context of the update.
+/// * `command` - The message of the command, `None` for the buttons of the results.
+/// * `resolver` - Finds and sends the entries.
+/// * `ani` - The AniList resource, to tell when the searches are rate limited.
+/// * `i18n` - The i18n resource.
+/// * `clean_after` - How long the usage and not found replies stay, `None` to keep them.
+pub async fn resolve_or_disambiguate<R: Resolver>(
+    ctx: Context,
+    command: Option<&Message>,
+    resolver: &R,
+    ani: &AniList,
+    i18n: &I18n,
+    clean_after: Option<Duration>,
+) -> ferogram::Result<()> {
     let t = |key: &str| i18n.translate(key);
 
-    let mut text = format!("👥 <b>{}</b>:\n", t("characters"));
+    let text = match ctx.callback_query() {
+        Some(query) => callback_data(&query),
+        None => ctx.text().unwrap(),
+    };
+    let mut args = text.split_whitespace().skip(1).collect::<Vec<_>>();
 
-    let offset = (page - 1) * per_page;
+    let sender = ctx.sender().unwrap();
 
-    for character in characters.iter().skip(offset).take(per_page) {
-        text.push_str(&format!(
-            "{0} | <code>{1}</code>. <b>{2}</b>\n",
-            match character.gender.clone().unwrap_or_default() {
-                Gender::Male => "👨",
-                Gender::Female => "👩",
-                Gender::NonBinary => "👨‍👧‍👦",
-                Gender::Other(_) => "👨‍👩‍👧‍👦",
-            },
-            character.id,
-            character.name.full()
-        ));
+    if let Some(query) = ctx.callback_query() {
+        let sender_id = args.pop().and_then(|id| id.parse::<i64>().ok());
 
-        if let Some(role) = character.role.as_ref() {
-            text.push_str(&format!("🎭 | <i>{}</i>\n", role));
+        if sender_id != Some(sender.id()) {
+            send_with_retry(|| {
+                query
+                    .answer()
+                    .cache_time(Duration::from_secs(120))
+                    .alert(t("not_allowed"))
+                    .send()
+            })
+            .await?;
+            return Ok(());
         }
     }
 
-    text
+    let mut terms = args.join(" ");
+    if terms.is_empty()
+        && let Some(command) = command
+        && let Some(reply) = replied_query(command).await
+    {
+        terms = reply;
+    }
+    let args = terms.split_whitespace().collect::<Vec<_>>();
+
+    let mut query = parse_search_query(&terms);
+    if query.terms.is_empty() {
+        let message =
+            InputMessage::html(t(resolver.usage_key())).reply_markup(&reply_markup::inline(vec![
+                vec![button::switch_inline(
+                    t("search_btn"),
+                    format!("{} ", resolver.inline_query()),
+                )],
+            ]));
+        reply_ephemeral(&ctx, command, message, clean_after).await?;
+        return Ok(());
+    }
+
+    if let Some(entry) = resolver.fetch(args[0]).await {
+        match entry {
+            Ok(entry) => resolver.send(entry, ctx).await?,
+            Err(e) => send_fetch_error(&ctx, command, e, i18n, clean_after).await?,
+        }
+        return Ok(());
+    }
+
+    let title = query.terms.clone();
+    let no_results =
+        InputMessage::html(t("no_results_text")).reply_markup(&reply_markup::inline(vec![vec![
+            button::switch_inline(
+                t("search_again_btn"),
+                format!("{0} {1}", resolver.inline_query(), title),
+            ),
+        ]]));
+
+    let Some(mut results) = resolver.search(&query).await else {
+        let message = match ani.retry_after() {
+            Some(retry_after) => InputMessage::html(rate_limited_text(retry_after, i18n)),
+            None => no_results,
+        };
+        reply_ephemeral(&ctx, command, message, clean_after).await?;
+        return Ok(());
+    };
+
+    let mut header = "search_results";
+    if results.is_empty() {
+        let sort = query.sort;
+        let search =
+            |terms: String| async move { resolver.search(&SearchQuery { terms, sort }).await };
+
+        let Some((terms, suggested)) = search_suggestion(&title, search).await else {
+            reply_ephemeral(&ctx, command, no_results, clean_after).await?;
+            return Ok(());
+        };
+
+        query.terms = terms;
+        results = suggested;
+        header = "search_suggestion";
+    } else if results.len() == 1 {
+        let entry = resolver.refetch(results.swap_remove(0)).await;
+        return resolver.send(entry, ctx).await;
+    }
+
+    let buttons = resolver
+        .gen_result_buttons(results, &query, sender.id())
+        .await;
+    let message = InputMessage::html(i18n.translate_with_args(
+        header,
+        hashmap! {
+            "search" => escape_html(&title),
+            "suggestion" => escape_html(&query.terms),
+        },
+    ))
+    .reply_markup(&reply_markup::inline(buttons));
+    send_with_retry(|| ctx.reply(message.clone())).await?;
+
+    Ok(())
 }
 
-pub fn gen_pagination_buttons(callback: &str, page: usize, max_pages: usize) -> Vec<Inline> {
-    let mut buttons = Vec::new();
+/// Gets the search query of a command from the message it replies to, see [`reply_query`].
+///
+/// # Arguments
+///
+/// * `command` - The message of the command.
+async fn replied_query(command: &Message) -> Option<String> {
+    let reply = match command.get_reply().await {
+        Ok(reply) => reply?,
+        Err(e) => {
+            log::warn!("failed to get the replied message: {:?}", e);
+            return None;
+        }
+    };
 
-    for i in 1..=max_pages {
-        if (page > 1 && i < (page - 2)) || i > (page + 2) {
-            continue;
+    reply_query(reply.text(), reply.outgoing())
+}
+
+/// Edits the message of a callback query into a page of a card, e.g. its episodes.
+///
+/// Cards sent as a photo take the page as their caption, so pages too long for a caption are
+/// shortened with [`fit_in_caption`] when Telegram rejects them.
+///
+/// # Arguments
+///
+/// * `query` - The callback query of the card.
+/// * `text` - The HTML text of the page.
+/// * `buttons` - The buttons of the page.
+pub async fn edit_card_page(
+    query: &CallbackQuery,
+    text: &str,
+    buttons: Vec<Vec<Inline>>,
+) -> Result<(), InvocationError> {
+    let markup = reply_markup::inline(buttons);
+
+    let message = InputMessage::html(text).reply_markup(&markup);
+    match send_with_retry(|| query.answer().edit(message.clone())).await {
+        Err(InvocationError::Rpc(rpc))
+            if !fits_in_caption(text)
+                && ErrorClass::from_rpc_error(&rpc.name) == Some(ErrorClass::MessageTooLong) =>
+        {
+            log::debug!("a page of a card does not fit in its caption, shortening it");
+
+            let message = InputMessage::html(fit_in_caption(text)).reply_markup(&markup);
+            send_with_retry(|| query.answer().edit(message.clone()))
+                .await
+                .map(|_| ())
         }
+        result => result.map(|_| ()),
+    }
+}
 
-        buttons.push(button::inline(
-            if i < page {
-                format!("⬅️ {0}", i)
-            } else if i > page {
-                format!("{0} ➡️", i)
-            } else {
-                format!("· {0} ·", i)
-            },
-            format!("{0} {1}", callback, i),
-        ));
+/// Sends the message of a card, editing the message of a callback query and replying
+/// otherwise.
+///
+/// Returns the reply, edits return `None`.
+///
+/// # Arguments
+///
+/// * `ctx` - The context of the update.
+/// * `message` - The message of the card.
+async fn send_card_message(
+    ctx: &Context,
+    message: InputMessage,
+) -> Result<Option<Message>, InvocationError> {
+    if ctx.is_callback_query() {
+        send_with_retry(|| ctx.edit(message.clone())).await?;
+
+        Ok(None)
+    } else {
+        send_with_retry(|| ctx.reply(message.clone())).await
     }
+}
 
-    buttons
+/// Generates the message telling the user a handler failed.
+///
+/// # Arguments
+///
+/// * `class` - What went wrong.
+/// * `id` - The correlation ID of the update, to find it in the logs.
+/// * `support_chat` - Where the errors are reported.
+/// * `i18n` - The i18n resource.
+pub fn gen_error_text(
+    class: ErrorClass,
+    id: &CorrelationId,
+    support_chat: &str,
+    i18n: &I18n,
+) -> String {
+    i18n.translate_with_args(
+        class.key(),
+        hashmap! {
+            "id" => id.to_string(),
+            "support_chat" => escape_html(support_chat),
+        },
+    )
+}
+
+/// Generates the alert telling the user a handler failed, in plain text.
+///
+/// # Arguments
+///
+/// * `class` - What went wrong.
+/// * `id` - The correlation ID of the update, to find it in the logs.
+/// * `support_chat` - Where the errors are reported.
+/// * `i18n` - The i18n resource.
+pub fn gen_error_alert(
+    class: ErrorClass,
+    id: &CorrelationId,
+    support_chat: &str,
+    i18n: &I18n,
+) -> String {
+    i18n.translate_with_args(
+        &format!("{}_alert", class.key()),
+        hashmap! {
+            "id" => id.to_string(),
+            "support_chat" => support_chat.to_string(),
+        },
+    )
 }
@@ -0,0 +1,64 @@
+// Copyright 2025 - Andriel Ferreira
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// https://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or https://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+//! Tests for the airing episode notifications.
+
+mod fixtures;
+
+use serde_json::json;
+use yamata_no_orochi::{
+    models::{ChatSettings, TitleLanguage},
+    resources::anilist::airing::{AiredEpisode, AiredPage},
+    tasks::airing_notifications::gen_text,
+};
+
+fn episode() -> AiredEpisode {
+    serde_json::from_value(json!({
+        "mediaId": 21,
+        "episode": 1100,
+        "airingAt": 1_700_000_000,
+        "media": {
+            "title": {
+                "romaji": "ONE PIECE",
+                "english": "ONE PIECE",
+                "native": "ONE PIECE <ワンピース>",
+                "userPreferred": "ONE PIECE"
+            }
+        }
+    }))
+    .expect("invalid aired episode")
+}
+
+#[test]
+fn pages_are_parsed() {
+    let page: AiredPage = serde_json::from_value(json!({
+        "pageInfo": { "hasNextPage": false },
+        "airingSchedules": [],
+    }))
+    .expect("invalid page");
+
+    assert!(!page.page_info.has_next_page);
+    assert!(page.airing_schedules.is_empty());
+}
+
+#[test]
+fn notifications_use_the_follower_preferences() {
+    let settings = ChatSettings {
+        title_language: TitleLanguage::Native,
+        ..Default::default()
+    };
+
+    assert_eq!(
+        gen_text(&episode(), &ChatSettings::default(), &fixtures::i18n("en")),
+        "🔔 Episode <b>1100</b> of <b>ONE PIECE</b> just aired!"
+    );
+    assert_eq!(
+        gen_text(&episode(), &settings, &fixtures::i18n("pt")),
+        "🔔 O episódio <b>1100</b> de <b>ONE PIECE &lt;ワンピース&gt;</b> acabou de ir ao ar!"
+    );
+}
@@ -0,0 +1,34 @@
+// Copyright 2025 - Andriel Ferreira
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// https://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or https://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+//! Tests for the media appearances of a character.
+
+use yamata_no_orochi::{
+    models::MediaType,
+    resources::anilist::appearances::{MediaEdge, collect},
+};
+
+#[test]
+fn appearances_keep_their_type_and_role() {
+    let edges: Vec<MediaEdge> = serde_json::from_str(
+        r#"[
+            { "characterRole": "MAIN", "node": { "id": 20, "type": "ANIME", "title": { "romaji": "Naruto" } } },
+            { "characterRole": "SUPPORTING", "node": { "id": 30011, "type": "MANGA", "title": { "romaji": "NARUTO" } } },
+            { "characterRole": null, "node": { "id": 1, "type": null, "title": { "romaji": null } } }
+        ]"#,
+    )
+    .unwrap();
+
+    let appearances = collect(edges);
+
+    assert_eq!(appearances.len(), 2);
+    assert_eq!(appearances[0].media_type, MediaType::Anime);
+    assert_eq!(appearances[0].role.as_deref(), Some("MAIN"));
+    assert_eq!(appearances[1].media_type, MediaType::Manga);
+    assert_eq!(appearances[1].title, "NARUTO");
+}
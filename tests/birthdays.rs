@@ -0,0 +1,68 @@
+// Copyright 2025 - Andriel Ferreira
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// https://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or https://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+//! Tests for the characters whose birthday is today.
+
+mod fixtures;
+
+use yamata_no_orochi::{
+    models::MediaType,
+    resources::anilist::birthdays::{CharacterPage, collect},
+    utils::{gen_birthday_list, validate_html},
+};
+
+fn characters() -> CharacterPage {
+    serde_json::from_str(
+        r#"{
+            "Page": {
+                "pageInfo": { "lastPage": 2 },
+                "characters": [
+                    { "id": 40, "name": { "full": "Luffy Monkey D." }, "age": " 19 ", "media": { "nodes": [
+                        { "id": 21, "type": "ANIME", "title": { "romaji": "ONE PIECE" } }
+                    ] } },
+                    { "id": 7, "name": { "full": "Guts" }, "age": "", "media": { "nodes": [
+                        { "id": 30002, "type": "MANGA", "title": { "romaji": "Berserk" } }
+                    ] } },
+                    { "id": 9, "name": { "full": "Mob <3" }, "age": null, "media": null },
+                    { "id": 1, "name": { "full": null }, "age": "17", "media": null }
+                ]
+            }
+        }"#,
+    )
+    .unwrap()
+}
+
+#[test]
+fn characters_without_a_name_are_skipped() {
+    let page = collect(characters().page);
+
+    assert_eq!(page.last_page, 2);
+    assert_eq!(page.characters.len(), 3);
+    assert_eq!(page.characters[0].age.as_deref(), Some("19"));
+    assert_eq!(page.characters[1].age, None);
+    assert_eq!(
+        page.characters[1].known_for.as_ref().unwrap().media_type,
+        MediaType::Manga
+    );
+    assert_eq!(page.characters[2].known_for, None);
+}
+
+#[test]
+fn birthday_list_shows_ages_and_media() {
+    let i18n = fixtures::i18n("en");
+    let page = collect(characters().page);
+
+    let text = gen_birthday_list(&page.characters, &i18n);
+
+    assert!(text.contains(
+        "<code>40</code> | <b>Luffy Monkey D.</b> — Age: <i>19</i>\n📺 <i>ONE PIECE</i>"
+    ));
+    assert!(text.contains("<code>7</code> | <b>Guts</b>\n📖 <i>Berserk</i>"));
+    assert!(text.contains("<b>Mob &lt;3</b>\n"));
+    assert_eq!(validate_html(&text), Ok(()));
+}
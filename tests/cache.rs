@@ -0,0 +1,210 @@
+// Copyright 2025 - Andriel Ferreira
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// https://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or https://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+//! Tests for the cache resource.
+
+use std::time::Duration;
+
+use yamata_no_orochi::resources::Cache;
+
+#[tokio::test]
+async fn inspection_reflects_the_entries() {
+    let cache = Cache::with_capacity(10);
+    assert!(cache.is_empty().await);
+    assert!(!cache.contains(&1).await);
+    assert_eq!(cache.age_of(&1).await, None);
+
+    cache.insert(1, "one").await;
+    tokio::time::sleep(Duration::from_millis(10)).await;
+
+    assert!(cache.contains(&1).await);
+    assert_eq!(cache.len().await, 1);
+    assert!(cache.age_of(&1).await.unwrap() >= Duration::from_millis(10));
+
+    assert_eq!(cache.take(&1).await, Some("one"));
+    assert!(!cache.contains(&1).await);
+    assert_eq!(cache.len().await, 0);
+}
+
+#[tokio::test]
+async fn stale_entries_are_not_fresh() {
+    let cache = Cache::with_capacity(10);
+    cache.insert(1, "one").await;
+
+    assert_eq!(
+        cache.get_fresh(&1, Duration::from_secs(60)).await,
+        Some("one")
+    );
+
+    tokio::time::sleep(Duration::from_millis(20)).await;
+
+    assert_eq!(cache.get_fresh(&1, Duration::from_millis(10)).await, None);
+    assert_eq!(cache.get(&1).await, Some("one"));
+}
+
+#[tokio::test]
+async fn only_the_least_recently_used_entry_is_evicted() {
+    let cache = Cache::with_capacity(3);
+    cache.insert(1, "one").await;
+    cache.insert(2, "two").await;
+    cache.insert(3, "three").await;
+
+    cache.insert(4, "four").await;
+
+    assert_eq!(cache.len().await, 3);
+    assert!(!cache.contains(&1).await);
+    assert!(cache.contains(&2).await);
+    assert!(cache.contains(&3).await);
+    assert!(cache.contains(&4).await);
+}
+
+#[tokio::test]
+async fn get_refreshes_the_recency() {
+    let cache = Cache::with_capacity(3);
+    cache.insert(1, "one").await;
+    cache.insert(2, "two").await;
+    cache.insert(3, "three").await;
+
+    assert_eq!(cache.get(&1).await, Some("one"));
+    cache.insert(4, "four").await;
+
+    assert!(cache.contains(&1).await);
+    assert!(!cache.contains(&2).await);
+
+    assert_eq!(
+        cache.get_fresh(&3, Duration::from_secs(60)).await,
+        Some("three")
+    );
+    cache.insert(5, "five").await;
+
+    assert!(cache.contains(&3).await);
+    assert!(!cache.contains(&1).await);
+}
+
+#[tokio::test]
+async fn replacing_an_entry_does_not_evict() {
+    let cache = Cache::with_capacity(2);
+    cache.insert(1, "one").await;
+    cache.insert(2, "two").await;
+
+    cache.insert(1, "uno").await;
+    assert_eq!(cache.len().await, 2);
+
+    // The replaced entry became the most recently used.
+    cache.insert(3, "three").await;
+    assert_eq!(cache.get(&1).await, Some("uno"));
+    assert!(!cache.contains(&2).await);
+    assert!(cache.contains(&3).await);
+}
+
+#[tokio::test]
+async fn expired_entries_are_misses() {
+    let cache = Cache::with_capacity_and_ttl(10, Duration::from_millis(20));
+    assert_eq!(cache.ttl(), Some(Duration::from_millis(20)));
+
+    cache.insert(1, "one").await;
+    assert_eq!(cache.get(&1).await, Some("one"));
+    assert!(cache.contains(&1).await);
+
+    tokio::time::sleep(Duration::from_millis(30)).await;
+
+    assert!(!cache.contains(&1).await);
+    assert_eq!(cache.age_of(&1).await, None);
+    assert_eq!(cache.get_fresh(&1, Duration::from_secs(60)).await, None);
+
+    // The lookup removed the expired entry.
+    assert!(cache.is_empty().await);
+}
+
+#[tokio::test]
+async fn purging_removes_only_the_expired_entries() {
+    let cache = Cache::with_capacity_and_ttl(10, Duration::from_millis(20));
+    cache.insert(1, "one").await;
+    cache.insert(2, "two").await;
+
+    tokio::time::sleep(Duration::from_millis(30)).await;
+    cache.insert(3, "three").await;
+
+    assert_eq!(cache.len().await, 3);
+    assert_eq!(cache.purge_expired().await, 2);
+    assert_eq!(cache.len().await, 1);
+    assert_eq!(cache.get(&3).await, Some("three"));
+    assert_eq!(cache.take(&1).await, None);
+}
+
+#[tokio::test]
+async fn caches_without_ttl_never_expire() {
+    let cache = Cache::with_capacity(10);
+    assert_eq!(cache.ttl(), None);
+
+    cache.insert(1, "one").await;
+    tokio::time::sleep(Duration::from_millis(10)).await;
+
+    assert_eq!(cache.purge_expired().await, 0);
+    assert_eq!(cache.get(&1).await, Some("one"));
+}
+
+#[tokio::test]
+async fn retain_removes_the_other_keys() {
+    let cache = Cache::with_capacity(10);
+    cache.insert((1, 'a'), "one a").await;
+    cache.insert((1, 'b'), "one b").await;
+    cache.insert((2, 'a'), "two a").await;
+
+    assert_eq!(cache.retain(|(user, _)| *user != 1).await, 2);
+    assert_eq!(cache.len().await, 1);
+    assert_eq!(cache.get(&(1, 'a')).await, None);
+    assert_eq!(cache.get(&(2, 'a')).await, Some("two a"));
+}
+
+#[tokio::test]
+async fn concurrent_access_does_not_panic() {
+    let cache = Cache::with_capacity(16);
+
+    let tasks = (0..64)
+        .map(|task| {
+            let cache = cache.clone();
+
+            tokio::spawn(async move {
+                for i in 0..200 {
+                    let key = (task * i) % 32;
+
+                    if i % 3 == 0 {
+                        cache.insert(key, i).await;
+                    } else {
+                        cache.get(&key).await;
+                    }
+                    if i % 7 == 0 {
+                        cache.take(&key).await;
+                    }
+
+                    tokio::task::yield_now().await;
+                }
+            })
+        })
+        .collect::<Vec<_>>();
+
+    for task in tasks {
+        task.await.expect("a cache task panicked");
+    }
+
+    assert!(cache.len().await <= cache.capacity());
+}
+
+#[tokio::test]
+async fn lookups_are_counted() {
+    let cache = Cache::with_capacity(10);
+    cache.insert(1, "one").await;
+
+    assert_eq!(cache.get(&1).await, Some("one"));
+    assert_eq!(cache.get(&2).await, None);
+    assert_eq!(cache.get_fresh(&1, Duration::ZERO).await, None);
+
+    assert_eq!(cache.hits(), 1);
+    assert_eq!(cache.misses(), 2);
+}
@@ -0,0 +1,124 @@
+// Copyright 2025 - Andriel Ferreira
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// https://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or https://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+//! Tests for the signatures of the callback data.
+
+use chrono::{Duration, TimeZone, Utc};
+use yamata_no_orochi::{
+    resources::{
+        CallbackSigner,
+        callback_signer::{self, CALLBACK_LIFETIME, CallbackError},
+    },
+    utils,
+};
+
+/// Telegram's limit of the callback data, in bytes.
+const MAX_CALLBACK_LENGTH: usize = 64;
+
+#[test]
+fn callbacks_round_trip() {
+    let signer = CallbackSigner::from_bot_token("123456:token");
+    let now = Utc.with_ymd_and_hms(2026, 10, 16, 12, 0, 0).unwrap();
+
+    let data = signer.sign("anime 21 7", now);
+    assert!(data.starts_with("anime 21 7 "));
+    assert_eq!(signer.verify(&data, now), Ok("anime 21 7"));
+    assert_eq!(callback_signer::strip_token(&data), "anime 21 7");
+
+    // The same token signs the same data.
+    let other = CallbackSigner::from_bot_token("123456:token");
+    assert_eq!(other.verify(&data, now), Ok("anime 21 7"));
+
+    assert_eq!(
+        utils::verify_callback(&utils::sign_callback("language set pt")),
+        Ok("language set pt")
+    );
+}
+
+#[test]
+fn forged_callbacks_are_rejected() {
+    let signer = CallbackSigner::from_bot_token("123456:token");
+    let now = Utc.with_ymd_and_hms(2026, 10, 16, 12, 0, 0).unwrap();
+
+    let data = signer.sign("anime 21 7", now);
+    let (_, token) = data.rsplit_once(' ').unwrap();
+
+    // Another sender, with the token of the original one.
+    let forged = format!("anime 21 8 {}", token);
+    assert_eq!(
+        signer.verify(&forged, now),
+        Err(CallbackError::BadSignature)
+    );
+
+    let other = CallbackSigner::from_bot_token("654321:token");
+    assert_eq!(other.verify(&data, now), Err(CallbackError::BadSignature));
+
+    assert_eq!(
+        signer.verify("anime 21 7", now),
+        Err(CallbackError::Unsigned)
+    );
+    assert_eq!(callback_signer::strip_token("anime 21 7"), "anime 21 7");
+}
+
+#[test]
+fn old_callbacks_expire() {
+    let signer = CallbackSigner::from_bot_token("123456:token");
+    let issued_at = Utc.with_ymd_and_hms(2026, 10, 16, 12, 0, 0).unwrap();
+    let lifetime = Duration::from_std(CALLBACK_LIFETIME).unwrap();
+
+    let data = signer.sign("manga 30013 7", issued_at);
+    assert_eq!(
+        signer.verify(&data, issued_at + lifetime),
+        Ok("manga 30013 7")
+    );
+    assert_eq!(
+        signer.verify(&data, issued_at + lifetime + Duration::seconds(1)),
+        Err(CallbackError::Expired)
+    );
+}
+
+#[test]
+fn only_the_card_callbacks_are_signed() {
+    assert!(callback_signer::is_signed("anime 21 7"));
+    assert!(callback_signer::is_signed("charimg 40 7"));
+    assert!(callback_signer::is_signed("language"));
+    assert!(callback_signer::is_signed("user list 1 anime CURRENT 1 7"));
+
+    assert!(!callback_signer::is_signed("cancel 7"));
+    assert!(!callback_signer::is_signed("me 7"));
+    assert!(!callback_signer::is_signed("settings commands"));
+}
+
+#[test]
+fn longest_callbacks_fit_in_telegram() {
+    // The longest callbacks the bot sends, with the largest IDs of AniList and Telegram.
+    let callbacks = [
+        "user list 99999999 manga REPEATING 999 9999999999",
+        "user favourites 99999999 9999999999",
+        "char voice_actors 9999999 9999999999 999",
+        "anime episodes 9999999 9999999999 999",
+        "manga sort ffffffff popularity 9999999999",
+        "anime group ffffffff 99 9999999999",
+        "language set pt",
+    ];
+
+    for callback in callbacks {
+        let data = utils::sign_callback(callback);
+
+        assert!(
+            data.len() <= MAX_CALLBACK_LENGTH,
+            "{:?} is {} bytes",
+            data,
+            data.len()
+        );
+        assert_eq!(
+            data.len(),
+            callback.len() + 1 + callback_signer::TOKEN_LENGTH
+        );
+    }
+}
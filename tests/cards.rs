@@ -0,0 +1,253 @@
+// Copyright 2025 - Andriel Ferreira
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// https://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or https://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+//! Snapshot tests for the card formatters.
+
+mod fixtures;
+
+use fixtures::*;
+use yamata_no_orochi::{
+    models::{ChatSettings, TitleLanguage, VoiceActorLanguage},
+    resources::anilist::episodes::Episode,
+    utils::{
+        MAX_CAPTION_LENGTH, fit_in_caption, fits_in_caption, gen_anime_info,
+        gen_char_image_caption, gen_char_info, gen_episode_list, gen_manga_info, gen_user_info,
+        genre_hashtag, preferred_title, translate_enum, validate_html,
+    },
+};
+
+/// The locales every card is checked in.
+const LOCALES: [&str; 2] = ["en", "pt"];
+
+#[test]
+fn anime_cards() {
+    for locale in LOCALES {
+        let i18n = i18n(locale);
+
+        assert_snapshot(
+            &format!("anime_full_{}", locale),
+            &gen_anime_info(&full_anime(), &i18n, &ChatSettings::default()),
+        );
+        assert_snapshot(
+            &format!("anime_minimal_{}", locale),
+            &gen_anime_info(&minimal_anime(), &i18n, &ChatSettings::default()),
+        );
+    }
+}
+
+#[test]
+fn manga_cards() {
+    for locale in LOCALES {
+        let i18n = i18n(locale);
+
+        assert_snapshot(
+            &format!("manga_full_{}", locale),
+            &gen_manga_info(&full_manga(), &i18n, &ChatSettings::default()),
+        );
+        assert_snapshot(
+            &format!("manga_minimal_{}", locale),
+            &gen_manga_info(&minimal_manga(), &i18n, &ChatSettings::default()),
+        );
+    }
+}
+
+#[test]
+fn char_cards() {
+    for locale in LOCALES {
+        let i18n = i18n(locale);
+
+        assert_snapshot(
+            &format!("char_full_{}", locale),
+            &gen_char_info(&full_char(), &i18n),
+        );
+        assert_snapshot(
+            &format!("char_minimal_{}", locale),
+            &gen_char_info(&minimal_char(), &i18n),
+        );
+    }
+}
+
+#[test]
+fn char_image_captions() {
+    assert_eq!(
+        gen_char_image_caption(&full_char()),
+        "<b>Spike Spiegel</b> (<code>1</code>)"
+    );
+    assert_eq!(
+        validate_html(&gen_char_image_caption(&minimal_char())),
+        Ok(())
+    );
+}
+
+#[test]
+fn user_cards() {
+    assert_snapshot("user_full", &gen_user_info(&full_user()));
+    assert_snapshot("user_minimal", &gen_user_info(&minimal_user()));
+}
+
+#[test]
+fn cards_are_valid_telegram_html() {
+    for locale in LOCALES {
+        let i18n = i18n(locale);
+
+        for card in [
+            gen_anime_info(&full_anime(), &i18n, &ChatSettings::default()),
+            gen_anime_info(&minimal_anime(), &i18n, &ChatSettings::default()),
+            gen_manga_info(&full_manga(), &i18n, &ChatSettings::default()),
+            gen_manga_info(&minimal_manga(), &i18n, &ChatSettings::default()),
+            gen_char_info(&full_char(), &i18n),
+            gen_char_info(&minimal_char(), &i18n),
+            gen_user_info(&full_user()),
+            gen_user_info(&minimal_user()),
+        ] {
+            assert_eq!(validate_html(&card), Ok(()), "invalid card:\n{}", card);
+        }
+    }
+}
+
+#[test]
+fn validate_html_rejects_broken_markup() {
+    assert_eq!(validate_html("<b>bold</b> &amp; <i>it</i>"), Ok(()));
+    assert_eq!(
+        validate_html("<a href=\"https://anilist.co\">AniList</a>"),
+        Ok(())
+    );
+    assert_eq!(
+        validate_html("<blockquote expandable>x</blockquote>"),
+        Ok(())
+    );
+    assert!(validate_html("<b>unclosed").is_err());
+    assert!(validate_html("<b><i>crossed</b></i>").is_err());
+    assert!(validate_html("</b>").is_err());
+    assert!(validate_html("<br>").is_err());
+    assert!(validate_html("1 < 2").is_err());
+    assert!(validate_html("Tom & Jerry").is_err());
+}
+
+#[test]
+fn titles_follow_the_preference() {
+    let title = full_anime().title;
+    let settings = |title_language| ChatSettings {
+        title_language,
+        ..Default::default()
+    };
+
+    assert_eq!(
+        preferred_title(&title, &settings(TitleLanguage::Romaji)),
+        "Cowboy Bebop"
+    );
+    assert_eq!(
+        preferred_title(&title, &settings(TitleLanguage::English)),
+        "Cowboy Bebop"
+    );
+    assert_eq!(
+        preferred_title(&title, &settings(TitleLanguage::Native)),
+        "カウボーイビバップ"
+    );
+}
+
+#[test]
+fn title_languages_cycle() {
+    for language in TitleLanguage::ALL {
+        assert_eq!(TitleLanguage::parse(language.as_str()), Some(language));
+    }
+
+    assert_eq!(TitleLanguage::Native.next(), TitleLanguage::Romaji);
+    assert_eq!(TitleLanguage::parse("klingon"), None);
+}
+
+#[test]
+fn voice_actor_languages_cycle() {
+    for language in VoiceActorLanguage::ALL {
+        assert_eq!(VoiceActorLanguage::parse(language.as_str()), Some(language));
+        assert_eq!(language.as_anilist(), language.as_str().to_uppercase());
+    }
+
+    assert_eq!(
+        VoiceActorLanguage::Spanish.next(),
+        VoiceActorLanguage::Japanese
+    );
+    assert_eq!(VoiceActorLanguage::parse("klingon"), None);
+}
+
+#[test]
+fn cards_are_localized() {
+    let (en, pt) = (i18n("en"), i18n("pt"));
+
+    let card = gen_anime_info(&full_anime(), &en, &ChatSettings::default());
+    assert!(
+        card.contains("<i>04/03/1998</i> - <i>04/24/1999</i>"),
+        "{}",
+        card
+    );
+    assert!(card.contains("<i>Finished</i>"), "{}", card);
+    assert!(
+        card.contains("#Action #Adventure #Drama #SciFi"),
+        "{}",
+        card
+    );
+
+    let card = gen_anime_info(&full_anime(), &pt, &ChatSettings::default());
+    assert!(
+        card.contains("<i>03/04/1998</i> - <i>24/04/1999</i>"),
+        "{}",
+        card
+    );
+    assert!(card.contains("<i>Finalizado</i>"), "{}", card);
+    assert!(
+        card.contains("#Ação #Aventura #Drama #Ficção_Científica"),
+        "{}",
+        card
+    );
+}
+
+#[test]
+fn enum_names_are_translated() {
+    let pt = i18n("pt");
+
+    assert_eq!(
+        translate_enum("status", "NOT_YET_RELEASED", &pt),
+        "Não lançado"
+    );
+    assert_eq!(translate_enum("format", "TV_SHORT", &pt), "TV Curta");
+    assert_eq!(translate_enum("format", "Not Listed", &pt), "Not Listed");
+    assert_eq!(genre_hashtag("Slice of Life", &pt), "#Slice_of_Life");
+}
+
+#[test]
+fn captions_count_only_the_visible_text() {
+    let visible = "a".repeat(MAX_CAPTION_LENGTH);
+
+    assert!(fits_in_caption(&format!("<b>{}</b>", visible)));
+    assert!(!fits_in_caption(&format!("{}b", visible)));
+    // Emoji outside the basic plane take two code units.
+    assert!(!fits_in_caption(&"🎬".repeat(MAX_CAPTION_LENGTH / 2 + 1)));
+}
+
+#[test]
+fn long_pages_are_shortened_to_fit_in_a_caption() {
+    let episodes = (1..=15)
+        .map(|number| Episode {
+            number,
+            title: Some(format!("The {} Episode With A Rather Long Title", number).repeat(2)),
+            airing_at: None,
+        })
+        .collect::<Vec<_>>();
+    let page = gen_episode_list(&episodes, None, 1, 15, &i18n("en"));
+    assert!(!fits_in_caption(&page));
+
+    let shortened = fit_in_caption(&page);
+    assert!(fits_in_caption(&shortened));
+    assert_eq!(validate_html(&shortened), Ok(()));
+    assert!(shortened.ends_with("..."));
+    // Only whole entries are kept.
+    assert!(page.starts_with(shortened.trim_end_matches("...")));
+
+    let short_page = gen_episode_list(&episodes[..2], None, 1, 15, &i18n("en"));
+    assert_eq!(fit_in_caption(&short_page), short_page);
+}
@@ -0,0 +1,62 @@
+// Copyright 2025 - Andriel Ferreira
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// https://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or https://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+//! Tests for the new chapter notifications.
+
+use std::{collections::HashMap, time::Duration};
+
+use yamata_no_orochi::{
+    resources::anilist::lists::UserListEntry,
+    tasks::chapter_notifications::{new_chapters, stagger_delay},
+};
+
+fn entry(media_id: i64, total: Option<i32>) -> UserListEntry {
+    UserListEntry {
+        media_id,
+        title: format!("Manga {}", media_id),
+        progress: Some(1),
+        total,
+        score: None,
+    }
+}
+
+#[test]
+fn only_gained_chapters_are_reported() {
+    let snapshots = HashMap::from([(1, 100), (2, 50), (3, 20), (4, 10)]);
+    let entries = vec![
+        entry(1, Some(102)),
+        entry(2, Some(50)),
+        entry(3, Some(18)),
+        entry(4, None),
+        entry(5, Some(7)),
+    ];
+
+    let gained = new_chapters(&snapshots, &entries)
+        .into_iter()
+        .map(|(entry, previous)| (entry.media_id, previous))
+        .collect::<Vec<_>>();
+
+    assert_eq!(gained, vec![(1, 100)]);
+}
+
+#[test]
+fn first_checks_report_nothing() {
+    let entries = vec![entry(1, Some(102))];
+
+    assert!(new_chapters(&HashMap::new(), &entries).is_empty());
+}
+
+#[test]
+fn users_are_spread_across_the_period() {
+    let period = Duration::from_secs(60 * 60);
+
+    assert_eq!(stagger_delay(period, 0), period);
+    assert_eq!(stagger_delay(period, 1), Duration::from_secs(30 * 60));
+    assert_eq!(stagger_delay(period, 59), Duration::from_secs(60));
+    assert!(stagger_delay(period, 10_000) * 10_000 < period);
+}
@@ -0,0 +1,39 @@
+// Copyright 2025 - Andriel Ferreira
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// https://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or https://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+//! Tests for the manga chapter data.
+
+use yamata_no_orochi::resources::anilist::chapters::ChapterData;
+
+#[test]
+fn only_enabled_streaming_links_are_reading_links() {
+    let data: ChapterData = serde_json::from_str(
+        r#"{
+            "chapters": 100,
+            "volumes": 10,
+            "externalLinks": [
+                { "site": "MANGA Plus", "url": "https://mangaplus.shueisha.co.jp/titles/1", "type": "STREAMING", "isDisabled": false },
+                { "site": "Twitter", "url": "https://twitter.com/example", "type": "SOCIAL", "isDisabled": false },
+                { "site": "Old Reader", "url": "https://example.com", "type": "STREAMING", "isDisabled": true }
+            ]
+        }"#,
+    )
+    .unwrap();
+
+    let links = data.reading_links();
+    assert_eq!(links.len(), 1);
+    assert_eq!(links[0].site, "MANGA Plus");
+}
+
+#[test]
+fn missing_links_are_empty() {
+    let data: ChapterData =
+        serde_json::from_str(r#"{ "chapters": null, "volumes": null }"#).unwrap();
+
+    assert!(data.reading_links().is_empty());
+}
@@ -0,0 +1,83 @@
+// Copyright 2025 - Andriel Ferreira
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// https://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or https://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+//! Tests for the characters of a media.
+
+mod fixtures;
+
+use yamata_no_orochi::{
+    resources::anilist::characters::{MediaCharacters, collect},
+    utils::{VOICE_ACTORS_MAX_CHARACTERS, gen_char_list, validate_html},
+};
+
+fn characters() -> MediaCharacters {
+    serde_json::from_str(
+        r#"{
+            "Media": { "characters": {
+                "pageInfo": { "lastPage": 4, "total": 38 },
+                "edges": [
+                    { "role": "MAIN", "node": { "id": 1, "gender": "Male", "name": { "full": "Spike Spiegel" } }, "voiceActors": [{ "id": 95011, "name": { "full": "Kouichi Yamadera" } }] },
+                    { "role": "SUPPORTING", "node": { "id": 2, "gender": "Female", "name": { "full": "Faye Valentine" } }, "voiceActors": [] },
+                    { "role": "BACKGROUND", "node": null }
+                ]
+            } }
+        }"#,
+    )
+    .unwrap()
+}
+
+#[test]
+fn deleted_characters_are_skipped() {
+    let page = collect(characters().media.characters);
+
+    assert_eq!(page.last_page, 4);
+    assert_eq!(page.total, 38);
+    assert_eq!(page.characters.len(), 2);
+    assert_eq!(page.characters[1].name, "Faye Valentine");
+    assert_eq!(page.characters[1].role.as_deref(), Some("SUPPORTING"));
+}
+
+#[test]
+fn empty_connections_have_one_page() {
+    let characters: MediaCharacters = serde_json::from_str(
+        r#"{ "Media": { "characters": { "pageInfo": { "lastPage": null }, "edges": [] } } }"#,
+    )
+    .unwrap();
+
+    let page = collect(characters.media.characters);
+
+    assert!(page.characters.is_empty());
+    assert_eq!(page.last_page, 1);
+    assert_eq!(page.total, 0);
+}
+
+#[test]
+fn char_list_translates_the_roles() {
+    let i18n = fixtures::i18n("en");
+    let page = collect(characters().media.characters);
+
+    let text = gen_char_list(&page.characters, &i18n);
+
+    assert!(text.contains(
+        "👨 | <code>1</code>. <b>Spike Spiegel</b> – CV: <a href=\"https://anilist.co/staff/95011\">Kouichi Yamadera</a>\n🎭 | <i>Main</i>"
+    ));
+    assert!(text.contains("👩 | <code>2</code>. <b>Faye Valentine</b>\n🎭 | <i>Supporting</i>"));
+    assert_eq!(validate_html(&text), Ok(()));
+}
+
+#[test]
+fn long_char_lists_leave_out_the_voice_actors() {
+    let i18n = fixtures::i18n("en");
+    let page = collect(characters().media.characters);
+    let characters = page.characters[..1].repeat(VOICE_ACTORS_MAX_CHARACTERS + 1);
+
+    let text = gen_char_list(&characters, &i18n);
+
+    assert!(!text.contains("CV:"));
+    assert!(gen_char_list(&characters[1..], &i18n).contains("CV:"));
+}
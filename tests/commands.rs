@@ -0,0 +1,165 @@
+// Copyright 2025 - Andriel Ferreira
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// https://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or https://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+//! Tests for the command registry.
+
+mod fixtures;
+
+use yamata_no_orochi::{
+    commands::{
+        ALWAYS_ENABLED, COMMANDS, command_list, gateable_commands, parse_command, resolve_command,
+    },
+    plugins::help_entries,
+    utils::{gen_help_entry, gen_help_list, validate_html},
+};
+
+#[test]
+fn global_list_uses_default_descriptions() {
+    let i18n = fixtures::i18n("en");
+    let list = command_list(&i18n, None).unwrap();
+
+    assert_eq!(list.len(), COMMANDS.len());
+    for ((name, description), command) in list.iter().zip(COMMANDS) {
+        assert_eq!(name, command.name);
+        assert_eq!(description, command.description);
+    }
+}
+
+#[test]
+fn locale_list_uses_translations() {
+    let i18n = fixtures::i18n("en");
+    let list = command_list(&i18n, Some("pt")).unwrap();
+
+    assert_eq!(list.len(), COMMANDS.len());
+    assert!(list.contains(&("start".to_string(), "Inicia o bot.".to_string())));
+}
+
+#[test]
+fn every_command_is_translated() {
+    let i18n = fixtures::i18n("en");
+
+    for locale in i18n.locales() {
+        for command in COMMANDS {
+            assert!(
+                i18n.lookup(format!("command_{}", command.name), &locale)
+                    .is_some(),
+                "{} has no description for /{}",
+                locale,
+                command.name
+            );
+        }
+    }
+}
+
+#[test]
+fn unknown_locale_has_no_list() {
+    let i18n = fixtures::i18n("en");
+
+    assert!(command_list(&i18n, Some("xx")).is_none());
+}
+
+#[test]
+fn every_command_has_help() {
+    let entries = help_entries();
+
+    for command in COMMANDS {
+        assert_eq!(
+            entries
+                .iter()
+                .filter(|entry| entry.command == command.name)
+                .count(),
+            1,
+            "/{} has no single help entry",
+            command.name
+        );
+    }
+    for entry in &entries {
+        assert!(
+            COMMANDS.iter().any(|command| command.name == entry.command),
+            "/{} is not in the command list",
+            entry.command
+        );
+    }
+}
+
+#[test]
+fn every_help_entry_is_translated() {
+    let i18n = fixtures::i18n("en");
+
+    for locale in i18n.locales() {
+        for entry in help_entries() {
+            assert!(
+                i18n.lookup(format!("help_{}", entry.command), &locale)
+                    .is_some(),
+                "{} has no help for /{}",
+                locale,
+                entry.command
+            );
+        }
+    }
+}
+
+#[test]
+fn help_shows_aliases_and_inline_prefix() {
+    let i18n = fixtures::i18n("en");
+    let entries = help_entries();
+    let anime = entries
+        .iter()
+        .find(|entry| entry.command == "anime")
+        .unwrap();
+
+    let list = gen_help_list(&entries, &i18n);
+    let text = gen_help_entry(anime, &i18n);
+
+    assert!(list.contains("\n/anime — Search for animes."));
+    assert!(text.starts_with("<b>/anime</b>"));
+    assert!(text.contains("<code>/a</code>"));
+    assert!(text.contains("<code>!a title</code>"));
+    assert_eq!(validate_html(&list), Ok(()));
+    assert_eq!(validate_html(&text), Ok(()));
+}
+
+#[test]
+fn commands_are_parsed_from_messages() {
+    assert_eq!(
+        parse_command("/anime naruto", None),
+        Some("anime".to_string())
+    );
+    assert_eq!(
+        parse_command("/Anime@OrochiBot naruto", Some("orochibot")),
+        Some("anime".to_string())
+    );
+    assert_eq!(parse_command("/anime@OtherBot", Some("OrochiBot")), None);
+    assert_eq!(
+        parse_command("/anime@OtherBot", None),
+        Some("anime".to_string())
+    );
+    assert_eq!(parse_command("anime", None), None);
+    assert_eq!(parse_command("/ anime", None), None);
+    assert_eq!(parse_command("/@OrochiBot", Some("OrochiBot")), None);
+}
+
+#[test]
+fn aliases_resolve_to_their_command() {
+    assert_eq!(resolve_command("anime"), Some("anime"));
+    assert_eq!(resolve_command("a"), Some("anime"));
+    assert_eq!(resolve_command("lang"), Some("language"));
+    assert_eq!(resolve_command("gstats"), None);
+}
+
+#[test]
+fn the_settings_can_not_be_turned_off() {
+    let gateable = gateable_commands();
+    let entries = help_entries();
+
+    for command in ALWAYS_ENABLED {
+        assert!(entries.iter().any(|entry| entry.command == *command));
+        assert!(!gateable.iter().any(|entry| entry.command == *command));
+    }
+    assert!(gateable.iter().any(|entry| entry.command == "anime"));
+}
@@ -0,0 +1,182 @@
+// Copyright 2025 - Andriel Ferreira
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// https://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or https://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+//! Tests for the configuration fallbacks.
+
+use std::{
+    net::{IpAddr, Ipv4Addr},
+    time::Duration,
+};
+
+use log::LevelFilter;
+use yamata_no_orochi::{
+    Config,
+    config::parse_log_level,
+    resources::{
+        I18n,
+        database::{MAX_RETRY_DELAY, retry_delay},
+        i18n::fallback_locale,
+    },
+};
+
+#[test]
+fn log_level_is_case_insensitive() {
+    assert_eq!(parse_log_level("debug"), Some(LevelFilter::Debug));
+    assert_eq!(parse_log_level("WARN"), Some(LevelFilter::Warn));
+    assert_eq!(parse_log_level(" off "), Some(LevelFilter::Off));
+}
+
+#[test]
+fn invalid_log_level_is_rejected() {
+    assert_eq!(parse_log_level("debgu"), None);
+    assert_eq!(parse_log_level(""), None);
+}
+
+#[test]
+fn fallback_locale_prefers_english() {
+    let locales = vec!["pt".to_string(), "en".to_string(), "es".to_string()];
+
+    assert_eq!(fallback_locale(&locales), Some("en".to_string()));
+}
+
+#[test]
+fn fallback_locale_without_english_picks_the_first() {
+    let locales = vec!["pt".to_string(), "es".to_string()];
+
+    assert_eq!(fallback_locale(&locales), Some("es".to_string()));
+    assert_eq!(fallback_locale(&[]), None);
+}
+
+#[test]
+fn unknown_default_locale_falls_back() {
+    let mut i18n = I18n::with_locale("xx");
+    i18n.load().expect("failed to load the locales");
+
+    assert_eq!(i18n.ensure_default_locale(), "en");
+    assert_eq!(i18n.locale(), "en");
+    assert!(!i18n.translate("start").starts_with("[[missing"));
+}
+
+#[test]
+fn known_default_locale_is_kept() {
+    let mut i18n = I18n::with_locale("pt");
+    i18n.load().expect("failed to load the locales");
+
+    assert_eq!(i18n.ensure_default_locale(), "pt");
+}
+
+#[test]
+fn update_logging_is_off_by_default() {
+    let content = r#"
+        [app]
+        log_level = "info"
+        database_url = "postgres://localhost/bot"
+        session_file = "bot.session"
+
+        [anilist]
+        client_id = 1
+        client_secret = "secret"
+
+        [telegram]
+        api_id = 1
+        api_hash = "hash"
+        bot_token = "token"
+        catch_up = false
+        flood_sleep_threshold = 180
+    "#;
+
+    let config = toml::from_str::<Config>(content).expect("invalid config");
+    assert!(!config.app.debug_updates);
+
+    let config = toml::from_str::<Config>(&content.replace(
+        "session_file = \"bot.session\"",
+        "session_file = \"bot.session\"\ndebug_updates = true",
+    ))
+    .expect("invalid config");
+    assert!(config.app.debug_updates);
+}
+
+#[test]
+fn owners_are_always_sudoers() {
+    let content = r#"
+        [app]
+        log_level = "info"
+        database_url = "postgres://localhost/bot"
+        session_file = "bot.session"
+
+        [anilist]
+        client_id = 1
+        client_secret = "secret"
+
+        [telegram]
+        api_id = 1
+        api_hash = "hash"
+        bot_token = "token"
+        catch_up = false
+        flood_sleep_threshold = 180
+        owners = [1]
+        sudoers = [2]
+    "#;
+
+    let config = toml::from_str::<Config>(content).expect("invalid config");
+
+    assert!(config.telegram.is_sudoer(1));
+    assert!(config.telegram.is_sudoer(2));
+    assert!(!config.telegram.is_sudoer(3));
+    assert!(!config.telegram.is_owner(2));
+    assert_eq!(config.telegram.support_chat, "@Yonorochi");
+    assert_eq!(config.telegram.inline_min_query_length, 3);
+    assert_eq!(config.anilist.chapter_check_minutes, 60);
+    assert_eq!(config.app.metrics_address, IpAddr::V4(Ipv4Addr::LOCALHOST));
+
+    let pool = config.app.pool_settings();
+    assert_eq!(pool.max_connections, 10);
+    assert_eq!(pool.acquire_timeout, Duration::from_secs(5));
+    assert_eq!(pool.connect_retry, Duration::from_secs(60));
+}
+
+#[test]
+fn invalid_token_encryption_key_is_an_error() {
+    let content = r#"
+        [app]
+        log_level = "loud"
+        database_url = "postgres://localhost/bot"
+        session_file = "bot.session"
+
+        [anilist]
+        client_id = 1
+        client_secret = "secret"
+
+        [telegram]
+        api_id = 1
+        api_hash = "hash"
+        bot_token = "token"
+        catch_up = false
+        flood_sleep_threshold = 180
+    "#;
+
+    let mut config = toml::from_str::<Config>(content).expect("invalid config");
+    assert!(config.validate().is_ok());
+    assert_eq!(config.app.log_level, "info");
+
+    let mut config = toml::from_str::<Config>(&content.replace(
+        "session_file = \"bot.session\"",
+        "session_file = \"bot.session\"\ntoken_encryption_key = \"short\"",
+    ))
+    .expect("invalid config");
+    assert!(config.validate().is_err());
+}
+
+#[test]
+fn database_connection_retries_back_off() {
+    assert_eq!(retry_delay(0), Duration::from_secs(1));
+    assert_eq!(retry_delay(1), Duration::from_secs(2));
+    assert_eq!(retry_delay(4), Duration::from_secs(16));
+    assert_eq!(retry_delay(5), MAX_RETRY_DELAY);
+    assert_eq!(retry_delay(u32::MAX), MAX_RETRY_DELAY);
+}
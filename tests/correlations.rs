@@ -0,0 +1,68 @@
+// Copyright 2025 - Andriel Ferreira
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// https://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or https://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+//! Tests for the correlations resource.
+
+use yamata_no_orochi::resources::{
+    Correlations,
+    correlations::{Correlation, CorrelationId, UpdateKey},
+};
+
+fn message_key(message_id: i32) -> UpdateKey {
+    UpdateKey::Message {
+        chat_id: -100123,
+        message_id,
+        edited: false,
+    }
+}
+
+#[test]
+fn correlation_ids_are_short_and_hexadecimal() {
+    let id = CorrelationId::new();
+
+    assert_eq!(id.0.len(), 6);
+    assert!(id.0.chars().all(|c| c.is_ascii_hexdigit()));
+    assert_eq!(id.to_string(), id.0);
+}
+
+#[tokio::test]
+async fn ids_are_remembered_until_taken() {
+    let correlations = Correlations::new();
+
+    let first = correlations.assign(Some(message_key(1))).await;
+    let second = correlations.assign(Some(message_key(2))).await;
+    correlations.assign(None).await;
+
+    assert_eq!(
+        correlations.take(&message_key(2)).await.map(|c| c.id),
+        Some(second)
+    );
+    assert_eq!(
+        correlations.take(&message_key(1)).await.map(|c| c.id),
+        Some(first)
+    );
+    assert_eq!(correlations.take(&message_key(1)).await, None);
+}
+
+#[tokio::test]
+async fn locales_are_only_set_on_assigned_updates() {
+    let correlations = Correlations::new();
+
+    let id = correlations.assign(Some(message_key(1))).await;
+    correlations.set_locale(&message_key(1), "en").await;
+    correlations.set_locale(&message_key(2), "pt").await;
+
+    assert_eq!(
+        correlations.take(&message_key(1)).await,
+        Some(Correlation {
+            id,
+            locale: Some("en".to_string())
+        })
+    );
+    assert_eq!(correlations.take(&message_key(2)).await, None);
+}
@@ -0,0 +1,49 @@
+// Copyright 2025 - Andriel Ferreira
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// https://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or https://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+//! Tests for the debounce resource.
+
+use std::time::Duration;
+
+use yamata_no_orochi::resources::Debounce;
+
+#[tokio::test]
+async fn lone_action_goes_through() {
+    let debounce = Debounce::new(10, Duration::from_millis(10));
+
+    assert!(debounce.settle(1).await);
+    assert!(debounce.settle(1).await);
+}
+
+#[tokio::test]
+async fn only_the_last_of_a_burst_goes_through() {
+    let debounce = Debounce::new(10, Duration::from_millis(50));
+
+    let first = tokio::spawn({
+        let debounce = debounce.clone();
+        async move { debounce.settle(1).await }
+    });
+    tokio::time::sleep(Duration::from_millis(10)).await;
+    let second = tokio::spawn({
+        let debounce = debounce.clone();
+        async move { debounce.settle(1).await }
+    });
+
+    assert!(!first.await.unwrap());
+    assert!(second.await.unwrap());
+}
+
+#[tokio::test]
+async fn keys_are_independent() {
+    let debounce = Debounce::new(10, Duration::from_millis(20));
+
+    let (first, second) = tokio::join!(debounce.settle(1), debounce.settle(2));
+
+    assert!(first);
+    assert!(second);
+}
@@ -0,0 +1,53 @@
+// Copyright 2025 - Andriel Ferreira
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// https://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or https://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+//! Tests for the diagnostics resource.
+
+use yamata_no_orochi::resources::{
+    Diagnostics,
+    diagnostics::{BotPermissions, ChatActivity},
+};
+
+#[tokio::test]
+async fn privacy_mode_is_inferred_from_the_messages() {
+    let diagnostics = Diagnostics::new();
+
+    assert_eq!(diagnostics.activity(1).await.privacy_mode(), None);
+
+    for _ in 0..3 {
+        diagnostics.record_message(1, true).await;
+    }
+    assert_eq!(diagnostics.activity(1).await.privacy_mode(), Some(true));
+
+    diagnostics.record_message(1, false).await;
+    assert_eq!(
+        diagnostics.activity(1).await,
+        ChatActivity {
+            commands: 3,
+            plain_messages: 1,
+        }
+    );
+    assert_eq!(diagnostics.activity(1).await.privacy_mode(), Some(false));
+    assert_eq!(diagnostics.activity(2).await, ChatActivity::default());
+}
+
+#[tokio::test]
+async fn permission_probes_are_cached_per_chat() {
+    let diagnostics = Diagnostics::new();
+    let permissions = BotPermissions {
+        send_messages: Some(true),
+        send_media: Some(false),
+        embed_links: None,
+    };
+
+    assert_eq!(diagnostics.permissions(1).await, None);
+
+    diagnostics.store_permissions(1, permissions).await;
+    assert_eq!(diagnostics.permissions(1).await, Some(permissions));
+    assert_eq!(diagnostics.permissions(2).await, None);
+}
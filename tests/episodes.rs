@@ -0,0 +1,84 @@
+// Copyright 2025 - Andriel Ferreira
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// https://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or https://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+//! Tests for the episode list.
+
+use yamata_no_orochi::resources::anilist::episodes::{
+    AiringEpisode, StreamingEpisode, merge, parse_streaming_title,
+};
+
+fn streaming(title: &str) -> StreamingEpisode {
+    StreamingEpisode {
+        title: Some(title.to_string()),
+    }
+}
+
+#[test]
+fn streaming_titles_are_split() {
+    assert_eq!(
+        parse_streaming_title("Episode 12 - The Final Battle"),
+        (Some(12), "The Final Battle".to_string())
+    );
+    assert_eq!(parse_streaming_title("Episode 3"), (Some(3), String::new()));
+    assert_eq!(
+        parse_streaming_title("A Special Day"),
+        (None, "A Special Day".to_string())
+    );
+}
+
+#[test]
+fn titles_and_schedule_are_merged() {
+    let episodes = merge(
+        Some(3),
+        &[
+            streaming("Episode 2 - Second"),
+            streaming("Episode 1 - First"),
+        ],
+        &[
+            AiringEpisode {
+                episode: 1,
+                airing_at: 1_700_000_000,
+            },
+            AiringEpisode {
+                episode: 3,
+                airing_at: 1_700_604_800,
+            },
+        ],
+    );
+
+    let numbers = episodes.iter().map(|e| e.number).collect::<Vec<_>>();
+    assert_eq!(numbers, vec![1, 2, 3]);
+
+    assert_eq!(episodes[0].title.as_deref(), Some("First"));
+    assert!(episodes[0].airing_at.is_some());
+    assert_eq!(episodes[1].title.as_deref(), Some("Second"));
+    assert!(episodes[1].airing_at.is_none());
+    assert!(episodes[2].title.is_none());
+    assert!(episodes[2].airing_at.is_some());
+}
+
+#[test]
+fn unnumbered_titles_use_their_position() {
+    let episodes = merge(None, &[streaming("Pilot"), streaming("Return")], &[]);
+
+    assert_eq!(episodes.len(), 2);
+    assert_eq!(episodes[1].number, 2);
+    assert_eq!(episodes[1].title.as_deref(), Some("Return"));
+}
+
+#[test]
+fn unknown_episodes_are_listed_up_to_the_total() {
+    let episodes = merge(Some(4), &[], &[]);
+
+    assert_eq!(episodes.len(), 4);
+    assert!(
+        episodes
+            .iter()
+            .all(|e| e.title.is_none() && e.airing_at.is_none())
+    );
+}
@@ -0,0 +1,132 @@
+// Copyright 2025 - Andriel Ferreira
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// https://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or https://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+//! Tests for the replies to the errors of the handlers.
+
+mod fixtures;
+
+use std::{io, time::Duration};
+
+use yamata_no_orochi::{
+    resources::{
+        anilist::{FetchError, graphql::QueryError},
+        correlations::CorrelationId,
+    },
+    utils::{
+        ErrorClass, MAX_FLOOD_WAIT, gen_error_alert, gen_error_text, is_flood_error, retry_delay,
+        validate_html,
+    },
+};
+
+#[test]
+fn anilist_errors_are_told_apart_from_unknown_ones() {
+    assert_eq!(
+        ErrorClass::of(&FetchError::NotFound),
+        ErrorClass::AniListUnavailable
+    );
+    assert_eq!(
+        ErrorClass::of(&QueryError::RateLimited(Duration::from_secs(30))),
+        ErrorClass::AniListUnavailable
+    );
+    assert_eq!(
+        ErrorClass::of(&io::Error::other("connection reset")),
+        ErrorClass::Unknown
+    );
+}
+
+#[test]
+fn telegram_errors_are_mapped_to_their_texts() {
+    let cases = [
+        ("FLOOD_WAIT", Some(ErrorClass::FloodWait), "slow_down"),
+        ("SLOWMODE_WAIT", Some(ErrorClass::FloodWait), "slow_down"),
+        (
+            "MESSAGE_TOO_LONG",
+            Some(ErrorClass::MessageTooLong),
+            "message_too_long",
+        ),
+        (
+            "MEDIA_CAPTION_TOO_LONG",
+            Some(ErrorClass::MessageTooLong),
+            "message_too_long",
+        ),
+        (
+            "WEBPAGE_CURL_FAILED",
+            Some(ErrorClass::MediaInvalid),
+            "media_invalid",
+        ),
+        (
+            "QUERY_ID_INVALID",
+            Some(ErrorClass::QueryTooOld),
+            "query_too_old",
+        ),
+        ("CHAT_WRITE_FORBIDDEN", None, "error_report"),
+    ];
+
+    for (name, class, key) in cases {
+        assert_eq!(ErrorClass::from_rpc_error(name), class, "{}", name);
+        assert_eq!(class.unwrap_or(ErrorClass::Unknown).key(), key, "{}", name);
+    }
+    assert!(!is_flood_error("MESSAGE_NOT_MODIFIED"));
+}
+
+#[test]
+fn only_short_flood_waits_and_timeouts_are_retried() {
+    assert_eq!(
+        retry_delay("FLOOD_WAIT", Some(5)),
+        Some(Duration::from_secs(5))
+    );
+    assert_eq!(
+        retry_delay("FLOOD_WAIT", Some(MAX_FLOOD_WAIT.as_secs() as u32)),
+        Some(MAX_FLOOD_WAIT)
+    );
+    assert_eq!(retry_delay("FLOOD_WAIT", Some(3600)), None);
+    assert_eq!(
+        retry_delay("SLOWMODE_WAIT", None),
+        Some(Duration::from_secs(1))
+    );
+    assert!(retry_delay("Timeout", None).is_some());
+    assert_eq!(retry_delay("MESSAGE_TOO_LONG", None), None);
+}
+
+#[test]
+fn errors_are_never_shown_to_the_users() {
+    let i18n = fixtures::i18n("en");
+    let id = CorrelationId("a1b2c3".to_string());
+
+    let text = gen_error_text(ErrorClass::Unknown, &id, "@Support", &i18n);
+    let alert = gen_error_alert(ErrorClass::Unknown, &id, "@Support", &i18n);
+
+    assert!(!text.contains("<blockquote>"));
+    assert!(text.contains("@Support"));
+    assert!(text.contains("<code>a1b2c3</code>"));
+    assert_eq!(validate_html(&text), Ok(()));
+    assert!(alert.contains("a1b2c3"));
+    assert!(!alert.contains('<'));
+}
+
+#[test]
+fn known_errors_do_not_ask_for_a_report() {
+    let i18n = fixtures::i18n("en");
+    let id = CorrelationId::default();
+
+    for class in [
+        ErrorClass::FloodWait,
+        ErrorClass::AniListUnavailable,
+        ErrorClass::MessageTooLong,
+        ErrorClass::MediaInvalid,
+        ErrorClass::QueryTooOld,
+    ] {
+        let text = gen_error_text(class, &id, "@Support", &i18n);
+        let alert = gen_error_alert(class, &id, "@Support", &i18n);
+
+        assert!(!text.contains("@Support"));
+        assert_eq!(validate_html(&text), Ok(()));
+        assert!(!alert.is_empty());
+        assert!(!alert.contains('<'));
+    }
+}
@@ -0,0 +1,52 @@
+// Copyright 2025 - Andriel Ferreira
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// https://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or https://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+//! Tests for the favorites.
+
+use chrono::Utc;
+use yamata_no_orochi::{
+    models::{EntryKind, Favorite},
+    plugins::favorites::group_favorites,
+};
+
+fn favorite(media_type: &str, media_id: i64) -> Favorite {
+    Favorite {
+        user_id: 1,
+        media_type: media_type.to_string(),
+        media_id,
+        title: format!("{} {}", media_type, media_id),
+        created_at: Utc::now(),
+    }
+}
+
+#[test]
+fn favorites_are_grouped_by_kind_in_order() {
+    let grouped = group_favorites(vec![
+        favorite("character", 1),
+        favorite("manga", 2),
+        favorite("anime", 3),
+        favorite("staff", 4),
+        favorite("manga", 5),
+        favorite("anime", 6),
+    ]);
+
+    let ids = grouped
+        .iter()
+        .map(|(kind, favorite)| (*kind, favorite.media_id))
+        .collect::<Vec<_>>();
+    assert_eq!(
+        ids,
+        vec![
+            (EntryKind::Anime, 3),
+            (EntryKind::Anime, 6),
+            (EntryKind::Manga, 2),
+            (EntryKind::Manga, 5),
+            (EntryKind::Character, 1),
+        ]
+    );
+}
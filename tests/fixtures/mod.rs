@@ -0,0 +1,117 @@
+// Copyright 2025 - Andriel Ferreira
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// https://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or https://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+//! Fixtures and snapshot helpers shared by the tests.
+//!
+//! The full fixtures are AniList API responses checked in next to this file, the minimal ones
+//! only have the defaults.
+
+#![allow(dead_code)]
+
+use std::path::PathBuf;
+
+use rust_anilist::models::{Anime, Character, Manga, User};
+use yamata_no_orochi::resources::I18n;
+
+/// Loads the locales, using the given one.
+///
+/// # Arguments
+///
+/// * `locale` - The locale to use.
+pub fn i18n(locale: &str) -> I18n {
+    let mut i18n = I18n::with_locale("en");
+    i18n.load().expect("failed to load the locales");
+    i18n.set_locale(locale);
+
+    i18n
+}
+
+/// An anime with every field the cards show.
+pub fn full_anime() -> Anime {
+    serde_json::from_str(include_str!("anime.json")).expect("invalid anime fixture")
+}
+
+/// An anime with only the defaults.
+pub fn minimal_anime() -> Anime {
+    Anime {
+        id: 2,
+        ..Default::default()
+    }
+}
+
+/// A manga with every field the cards show.
+pub fn full_manga() -> Manga {
+    serde_json::from_str(include_str!("manga.json")).expect("invalid manga fixture")
+}
+
+/// A manga with only the defaults.
+pub fn minimal_manga() -> Manga {
+    Manga {
+        id: 2,
+        ..Default::default()
+    }
+}
+
+/// A character with every field the cards show.
+pub fn full_char() -> Character {
+    serde_json::from_str(include_str!("character.json")).expect("invalid character fixture")
+}
+
+/// A character with only the defaults.
+pub fn minimal_char() -> Character {
+    Character {
+        id: 2,
+        ..Default::default()
+    }
+}
+
+/// A user with every field the cards show.
+pub fn full_user() -> User {
+    serde_json::from_str(include_str!("user.json")).expect("invalid user fixture")
+}
+
+/// A user with only the defaults.
+pub fn minimal_user() -> User {
+    User {
+        id: 2,
+        ..Default::default()
+    }
+}
+
+/// Compares a text with its snapshot in `tests/snapshots`.
+///
+/// Missing snapshots are written instead, and so are all of them when `UPDATE_SNAPSHOTS` is
+/// set, so changing a formatter shows up as a snapshot diff in review.
+///
+/// # Arguments
+///
+/// * `name` - The name of the snapshot.
+/// * `actual` - The text to compare.
+pub fn assert_snapshot(name: &str, actual: &str) {
+    let path = PathBuf::from(env!("CARGO_MANIFEST_DIR"))
+        .join("tests")
+        .join("snapshots")
+        .join(format!("{}.html", name));
+
+    if std::env::var_os("UPDATE_SNAPSHOTS").is_some() || !path.exists() {
+        std::fs::create_dir_all(path.parent().unwrap()).expect("failed to create snapshots dir");
+        std::fs::write(&path, actual).expect("failed to write the snapshot");
+        eprintln!("wrote snapshot {:?}", path);
+
+        return;
+    }
+
+    let expected = std::fs::read_to_string(&path).expect("failed to read the snapshot");
+    assert!(
+        expected == actual,
+        "snapshot {0:?} changed, rerun with UPDATE_SNAPSHOTS=1 if it is expected\n\n--- expected\n{1}\n--- actual\n{2}",
+        name,
+        expected,
+        actual
+    );
+}
@@ -0,0 +1,64 @@
+// Copyright 2025 - Andriel Ferreira
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// https://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or https://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+//! Tests for the genre and tag browsing.
+
+mod fixtures;
+
+use yamata_no_orochi::{
+    models::MediaType,
+    plugins::genre::gen_tag_buttons,
+    resources::anilist::genres::{MediaPage, collect, decode, encode},
+    utils::{gen_genre_list, validate_html},
+};
+
+#[test]
+fn genre_list_shows_the_scores() {
+    let i18n = fixtures::i18n("pt");
+    let page: MediaPage = serde_json::from_str(
+        r#"{
+            "Page": {
+                "pageInfo": { "lastPage": 40 },
+                "media": [
+                    { "id": 1, "title": { "romaji": "Cowboy Bebop" }, "averageScore": 86 },
+                    { "id": 2, "title": { "romaji": "Tom & Jerry" }, "averageScore": null }
+                ]
+            }
+        }"#,
+    )
+    .unwrap();
+    let page = collect(page.page);
+
+    let text = gen_genre_list("Fantasy", &page.media, &i18n);
+
+    assert_eq!(page.last_page, 40);
+    assert!(text.starts_with("🎭 <b>Fantasia</b>:"));
+    assert!(text.contains("<code>1</code> | <b>Cowboy Bebop</b> · 🌟 <i>86%</i>\n"));
+    assert!(text.contains("<code>2</code> | <b>Tom &amp; Jerry</b>\n"));
+    assert_eq!(validate_html(&text), Ok(()));
+}
+
+#[test]
+fn names_survive_the_callback() {
+    assert_eq!(encode("Slice of Life"), "Slice_of_Life");
+    assert_eq!(decode(&encode("Slice of Life")), "Slice of Life");
+    assert_eq!(decode(&encode("Sci-Fi")), "Sci-Fi");
+}
+
+#[test]
+fn long_tags_are_not_buttons() {
+    let tags = [
+        "Time Skip",
+        "A Tag Name So Long It Would Not Fit In A Callback",
+    ];
+
+    let buttons = gen_tag_buttons(tags, MediaType::Manga, 123456789);
+
+    assert_eq!(buttons.len(), 1);
+    assert_eq!(buttons[0].len(), 1);
+}
@@ -0,0 +1,32 @@
+// Copyright 2025 - Andriel Ferreira
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// https://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or https://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+//! Tests for the view history.
+
+use yamata_no_orochi::{models::EntryKind, utils};
+
+#[test]
+fn kinds_round_trip() {
+    for kind in [EntryKind::Anime, EntryKind::Manga, EntryKind::Character] {
+        assert_eq!(EntryKind::parse(kind.as_str()), Some(kind));
+    }
+
+    assert_eq!(EntryKind::parse("staff"), None);
+}
+
+#[test]
+fn entries_open_their_cards() {
+    let anime = EntryKind::Anime.callback_data(21, 7);
+    assert_eq!(utils::verify_callback(&anime), Ok("anime 21 7"));
+
+    let manga = EntryKind::Manga.callback_data(30013, 7);
+    assert_eq!(utils::verify_callback(&manga), Ok("manga 30013 7"));
+
+    let character = EntryKind::Character.callback_data(40, 7);
+    assert_eq!(utils::verify_callback(&character), Ok("char 40 7"));
+}
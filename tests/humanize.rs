@@ -0,0 +1,128 @@
+// Copyright 2025 - Andriel Ferreira
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// https://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or https://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+//! Tests for the relative timestamps.
+
+use chrono::{DateTime, Duration, FixedOffset, TimeZone, Utc};
+use yamata_no_orochi::{
+    resources::I18n,
+    utils::{format_countdown, humanize_between},
+};
+
+fn i18n(locale: &str) -> I18n {
+    let mut i18n = I18n::with_locale("en");
+    i18n.load().expect("failed to load the locales");
+    i18n.set_locale(locale);
+
+    i18n
+}
+
+fn now() -> DateTime<Utc> {
+    Utc.with_ymd_and_hms(2025, 3, 10, 12, 0, 0).unwrap()
+}
+
+#[test]
+fn past_moments() {
+    let (en, pt) = (i18n("en"), i18n("pt"));
+    let at = now() - Duration::days(3);
+
+    assert_eq!(humanize_between(at, now(), &en, &Utc), "3 days ago");
+    assert_eq!(humanize_between(at, now(), &pt, &Utc), "há 3 dias");
+    assert_eq!(
+        humanize_between(now() - Duration::hours(1), now(), &pt, &Utc),
+        "há 1 h"
+    );
+}
+
+#[test]
+fn future_moments() {
+    let (en, pt) = (i18n("en"), i18n("pt"));
+    let at = now() + Duration::hours(2) + Duration::minutes(5);
+
+    assert_eq!(humanize_between(at, now(), &en, &Utc), "in 2 h");
+    assert_eq!(humanize_between(at, now(), &pt, &Utc), "em 2 h");
+    assert_eq!(
+        humanize_between(now() + Duration::days(1), now(), &en, &Utc),
+        "in 1 day"
+    );
+}
+
+#[test]
+fn sub_minute_moments() {
+    let (en, pt) = (i18n("en"), i18n("pt"));
+
+    assert_eq!(
+        humanize_between(now() - Duration::seconds(59), now(), &en, &Utc),
+        "just now"
+    );
+    assert_eq!(
+        humanize_between(now() + Duration::seconds(10), now(), &pt, &Utc),
+        "agora mesmo"
+    );
+}
+
+#[test]
+fn multi_month_moments() {
+    let (en, pt) = (i18n("en"), i18n("pt"));
+    let at = now() - Duration::days(95);
+
+    assert_eq!(humanize_between(at, now(), &en, &Utc), "3 months ago");
+    assert_eq!(humanize_between(at, now(), &pt, &Utc), "há 3 meses");
+    assert_eq!(
+        humanize_between(now() - Duration::days(31), now(), &pt, &Utc),
+        "há 1 mês"
+    );
+}
+
+#[test]
+fn distant_moments_are_dates_in_the_time_zone() {
+    let (en, pt) = (i18n("en"), i18n("pt"));
+    let at = Utc.with_ymd_and_hms(2023, 1, 1, 1, 0, 0).unwrap();
+    let tz = FixedOffset::west_opt(3 * 60 * 60).unwrap();
+
+    assert_eq!(humanize_between(at, now(), &en, &tz), "on Dec 31, 2022");
+    assert_eq!(humanize_between(at, now(), &pt, &tz), "em 31/12/2022");
+}
+
+#[test]
+fn countdowns_use_the_full_date() {
+    let (en, pt) = (i18n("en"), i18n("pt"));
+
+    assert_eq!(
+        format_countdown(now() + Duration::minutes(5), now(), &en),
+        "airs in 5 min"
+    );
+    // Earlier in the day, but tomorrow.
+    assert_eq!(
+        format_countdown(
+            now() + Duration::hours(23) + Duration::minutes(55),
+            now(),
+            &en
+        ),
+        "airs in 23 h"
+    );
+    assert_eq!(
+        format_countdown(now() + Duration::days(3), now(), &pt),
+        "estreia em 3 dias"
+    );
+}
+
+#[test]
+fn countdowns_of_aired_episodes() {
+    let (en, pt) = (i18n("en"), i18n("pt"));
+
+    assert_eq!(
+        format_countdown(now() - Duration::days(2), now(), &en),
+        "aired 2 days ago"
+    );
+    assert_eq!(
+        format_countdown(now() - Duration::hours(1), now(), &pt),
+        "exibido há 1 h"
+    );
+    assert_eq!(format_countdown(now(), now(), &en), "aired just now");
+}
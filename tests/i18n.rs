@@ -0,0 +1,125 @@
+// Copyright 2025 - Andriel Ferreira
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// https://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or https://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+//! Tests for the i18n resource.
+
+use std::time::Duration;
+
+use yamata_no_orochi::resources::I18n;
+
+fn loaded() -> I18n {
+    let mut i18n = I18n::with_locale("en");
+    i18n.load().expect("failed to load the locales");
+
+    i18n
+}
+
+#[test]
+fn views_do_not_change_the_resource() {
+    let i18n = loaded();
+    let view = i18n.for_locale("pt");
+
+    assert_eq!(view.locale(), "pt");
+    assert_eq!(i18n.locale(), "en");
+    assert_eq!(view.translate("back_btn"), "⬅️ Voltar");
+    assert_eq!(i18n.translate("back_btn"), "⬅️ Back");
+}
+
+#[tokio::test]
+async fn concurrent_updates_keep_their_locale() {
+    let i18n = loaded();
+
+    // Simulates two updates from chats with different languages, interleaving their
+    // translations the way a busy bot would.
+    let handles = ["en", "pt", "en", "pt"].map(|locale| {
+        let view = i18n.for_locale(locale);
+
+        tokio::spawn(async move {
+            let mut translations = Vec::new();
+            for _ in 0..10 {
+                translations.push(view.translate("back_btn"));
+                tokio::time::sleep(Duration::from_millis(1)).await;
+            }
+
+            (locale, translations)
+        })
+    });
+
+    for handle in handles {
+        let (locale, translations) = handle.await.unwrap();
+        let expected = i18n.translate_from_locale("back_btn", locale);
+
+        assert!(
+            translations
+                .iter()
+                .all(|translation| *translation == expected)
+        );
+    }
+}
+
+/// Writes locale files to a temporary directory and loads them, with `en` as the default.
+fn partial(name: &str, locales: &[(&str, &str)]) -> I18n {
+    let dir = std::env::temp_dir().join(format!("yamata-i18n-{}-{}", name, std::process::id()));
+    std::fs::create_dir_all(&dir).expect("failed to create the locales dir");
+    for (locale, content) in locales {
+        std::fs::write(dir.join(format!("{}.json", locale)), content)
+            .expect("failed to write the locale");
+    }
+
+    let mut i18n = I18n::with_locale("en");
+    i18n.load_from(&dir).expect("failed to load the locales");
+    std::fs::remove_dir_all(&dir).ok();
+
+    i18n
+}
+
+#[test]
+fn missing_keys_fall_back_to_the_default_locale() {
+    let i18n = partial(
+        "fallback",
+        &[
+            ("en", r#"{ "hello": "Hello", "bye": "Bye" }"#),
+            ("pt", r#"{ "hello": "Olá" }"#),
+        ],
+    );
+    let pt = i18n.for_locale("pt");
+
+    assert_eq!(pt.translate("hello"), "Olá");
+    assert_eq!(pt.translate("bye"), "Bye");
+    assert_eq!(pt.translate("unknown"), "[[missing: unknown]]");
+    assert_eq!(i18n.for_locale("xx").translate("hello"), "Hello");
+}
+
+#[test]
+fn missing_keys_are_listed() {
+    let i18n = partial(
+        "listing",
+        &[
+            ("en", r#"{ "a": "A", "b": "B", "c": "C" }"#),
+            ("pt", r#"{ "b": "B" }"#),
+        ],
+    );
+
+    assert_eq!(i18n.missing_keys("pt"), vec!["a", "c"]);
+    assert!(i18n.missing_keys("en").is_empty());
+    assert_eq!(i18n.missing_keys("xx"), vec!["a", "b", "c"]);
+}
+
+#[test]
+fn shipped_locales_are_complete() {
+    let i18n = loaded();
+
+    for locale in i18n.locales() {
+        assert_eq!(
+            i18n.missing_keys(&locale),
+            Vec::<String>::new(),
+            "{}",
+            locale
+        );
+    }
+}
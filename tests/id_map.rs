@@ -0,0 +1,131 @@
+// Copyright 2025 - Andriel Ferreira
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// https://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or https://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+//! Tests for the MyAnimeList ID mappings.
+
+use std::sync::atomic::{AtomicBool, Ordering};
+
+use yamata_no_orochi::{
+    models::MediaType,
+    resources::anilist::{Resolution, resolve_with},
+    utils::{
+        MediaReference, parse_anilist_url, parse_mal_reference, parse_mal_url,
+        parse_media_reference,
+    },
+};
+
+#[tokio::test]
+async fn stored_mappings_take_precedence() {
+    let fetched = AtomicBool::new(false);
+    let flag = &fetched;
+
+    let resolution = resolve_with(
+        || async { Some(1) },
+        || async move {
+            flag.store(true, Ordering::SeqCst);
+            Some(2)
+        },
+    )
+    .await;
+
+    assert_eq!(resolution, Some(Resolution::Stored(1)));
+    assert!(!fetched.load(Ordering::SeqCst));
+}
+
+#[tokio::test]
+async fn unknown_mappings_are_fetched() {
+    let resolution = resolve_with(|| async { None }, || async { Some(2) }).await;
+
+    assert_eq!(resolution, Some(Resolution::Fetched(2)));
+    assert_eq!(resolution.map(|resolution| resolution.id()), Some(2));
+}
+
+#[tokio::test]
+async fn unresolvable_mappings_are_none() {
+    let resolution = resolve_with(|| async { None }, || async { None }).await;
+
+    assert_eq!(resolution, None);
+}
+
+#[test]
+fn parse_mal_url_accepts_media_links() {
+    assert_eq!(
+        parse_mal_url("https://myanimelist.net/anime/5114/Fullmetal_Alchemist__Brotherhood"),
+        Some((MediaType::Anime, 5114))
+    );
+    assert_eq!(
+        parse_mal_url("www.myanimelist.net/manga/2"),
+        Some((MediaType::Manga, 2))
+    );
+    assert_eq!(
+        parse_mal_url("https://myanimelist.net/anime/5114?q=x"),
+        Some((MediaType::Anime, 5114))
+    );
+    assert_eq!(parse_mal_url("https://myanimelist.net/character/11"), None);
+    assert_eq!(parse_mal_url("https://anilist.co/anime/5114"), None);
+    assert_eq!(parse_mal_url("5114"), None);
+}
+
+#[test]
+fn parse_mal_reference_accepts_prefixes_and_links() {
+    assert_eq!(
+        parse_mal_reference("mal:21", MediaType::Anime),
+        Some((MediaType::Anime, 21))
+    );
+    assert_eq!(
+        parse_mal_reference("mal:2", MediaType::Manga),
+        Some((MediaType::Manga, 2))
+    );
+    assert_eq!(
+        parse_mal_reference("https://myanimelist.net/anime/21/", MediaType::Manga),
+        Some((MediaType::Anime, 21))
+    );
+    assert_eq!(
+        parse_mal_reference(
+            "https://myanimelist.net/manga/13/One_Piece?utm_source=x",
+            MediaType::Anime
+        ),
+        Some((MediaType::Manga, 13))
+    );
+    assert_eq!(parse_mal_reference("mal:", MediaType::Anime), None);
+    assert_eq!(parse_mal_reference("mal:one", MediaType::Anime), None);
+    assert_eq!(parse_mal_reference("21", MediaType::Anime), None);
+}
+
+#[test]
+fn parse_media_reference_accepts_ids() {
+    assert_eq!(
+        parse_media_reference("21", MediaType::Anime),
+        Some(MediaReference::AniList(21))
+    );
+    assert_eq!(
+        parse_media_reference("mal:13", MediaType::Manga),
+        Some(MediaReference::MyAnimeList(13))
+    );
+    assert_eq!(
+        parse_media_reference("https://myanimelist.net/anime/21/", MediaType::Anime),
+        Some(MediaReference::MyAnimeList(21))
+    );
+    assert_eq!(
+        parse_media_reference("https://myanimelist.net/anime/21/", MediaType::Manga),
+        None
+    );
+    assert_eq!(parse_media_reference("Frieren", MediaType::Anime), None);
+}
+
+#[test]
+fn parse_anilist_url_accepts_media_links() {
+    assert_eq!(
+        parse_anilist_url("https://anilist.co/manga/30002/Berserk/"),
+        Some((MediaType::Manga, 30002))
+    );
+    assert_eq!(
+        parse_anilist_url("https://myanimelist.net/anime/5114"),
+        None
+    );
+}
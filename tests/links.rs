@@ -0,0 +1,95 @@
+// Copyright 2025 - Andriel Ferreira
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// https://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or https://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+//! Tests for the links of a media.
+
+mod fixtures;
+
+use yamata_no_orochi::{
+    models::MediaType,
+    resources::anilist::{chapters::ExternalLink, links::MediaLinks},
+    utils::{gen_links_list, validate_html},
+};
+
+/// An external link.
+///
+/// # Arguments
+///
+/// * `site` - The site name.
+/// * `link_type` - The link type.
+/// * `language` - The language of the site.
+/// * `is_disabled` - Whether the link is disabled.
+fn link(
+    site: &str,
+    link_type: &str,
+    language: Option<&str>,
+    is_disabled: Option<bool>,
+) -> ExternalLink {
+    ExternalLink {
+        site: site.to_string(),
+        url: format!("https://example.com/{}", site.to_lowercase()),
+        link_type: Some(link_type.to_string()),
+        language: language.map(String::from),
+        is_disabled,
+    }
+}
+
+fn links() -> MediaLinks {
+    MediaLinks {
+        id_mal: Some(1),
+        site_url: "https://anilist.co/anime/1".to_string(),
+        external_links: vec![
+            link("Crunchyroll", "STREAMING", Some("English"), Some(false)),
+            link("Official", "INFO", Some("Japanese"), None),
+            link("Funimation", "STREAMING", Some("English"), Some(true)),
+            link("Twitter", "SOCIAL", None, None),
+        ],
+    }
+}
+
+#[test]
+fn only_disabled_links_are_hidden() {
+    let links = links();
+
+    let sites = links
+        .enabled_links()
+        .map(|link| link.site.as_str())
+        .collect::<Vec<_>>();
+
+    assert_eq!(sites, ["Crunchyroll", "Official", "Twitter"]);
+}
+
+#[test]
+fn streaming_sites_are_listed_apart() {
+    let i18n = fixtures::i18n("en");
+
+    let text = gen_links_list(&links(), MediaType::Anime, &i18n);
+
+    let (others, streaming) = text.split_once("📺 <b>Watch at</b>:").unwrap();
+    assert!(others.contains("🔗 | 🇯🇵 <a href=\"https://example.com/official\">Official</a>"));
+    assert!(others.contains("🔗 | <a href=\"https://example.com/twitter\">Twitter</a>"));
+    assert!(
+        streaming.contains("🔗 | 🇺🇸 <a href=\"https://example.com/crunchyroll\">Crunchyroll</a>")
+    );
+    assert!(!text.contains("Funimation"));
+    assert_eq!(validate_html(&text), Ok(()));
+}
+
+#[test]
+fn myanimelist_links_follow_the_media_type() {
+    let i18n = fixtures::i18n("en");
+
+    assert!(
+        gen_links_list(&links(), MediaType::Anime, &i18n)
+            .contains("https://myanimelist.net/anime/1")
+    );
+    assert!(
+        gen_links_list(&links(), MediaType::Manga, &i18n)
+            .contains("https://myanimelist.net/manga/1")
+    );
+}
@@ -0,0 +1,164 @@
+// Copyright 2025 - Andriel Ferreira
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// https://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or https://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+//! Tests for the AniList list entries.
+
+mod fixtures;
+
+use serde_json::json;
+use yamata_no_orochi::{
+    models::MediaType,
+    plugins::list::{gen_list_buttons, gen_list_status, status_label},
+    resources::anilist::{
+        graphql::QueryError,
+        lists::{
+            ListEntry, ListStatus, MediaListEntry, MediaListPage, SavedEntry, collect, is_private,
+        },
+    },
+    utils::{gen_user_list, validate_html},
+};
+
+#[test]
+fn statuses_round_trip() {
+    for status in ListStatus::ALL {
+        assert_eq!(ListStatus::parse(status.as_str()), Some(status));
+    }
+
+    assert_eq!(ListStatus::parse("current"), Some(ListStatus::Current));
+    assert_eq!(ListStatus::parse("WATCHING"), None);
+}
+
+#[test]
+fn saved_entries_are_parsed() {
+    let saved: SavedEntry = serde_json::from_value(json!({
+        "SaveMediaListEntry": { "id": 42, "status": "COMPLETED" }
+    }))
+    .expect("invalid saved entry");
+
+    assert_eq!(saved.entry.id, 42);
+    assert_eq!(saved.entry.status, Some(ListStatus::Completed));
+}
+
+#[test]
+fn statuses_are_translated() {
+    for locale in ["en", "pt"] {
+        let i18n = fixtures::i18n(locale);
+
+        for status in ListStatus::ALL {
+            for media_type in [MediaType::Anime, MediaType::Manga] {
+                let label = status_label(status, media_type, &i18n);
+                assert!(!label.starts_with("[[missing"), "{}: {}", locale, label);
+            }
+        }
+    }
+
+    let i18n = fixtures::i18n("en");
+    assert_eq!(
+        status_label(ListStatus::Current, MediaType::Anime, &i18n),
+        "Watching"
+    );
+    assert_eq!(
+        status_label(ListStatus::Current, MediaType::Manga, &i18n),
+        "Reading"
+    );
+}
+
+#[test]
+fn list_buttons_cover_the_main_statuses() {
+    let i18n = fixtures::i18n("en");
+
+    for media_type in [MediaType::Anime, MediaType::Manga] {
+        let buttons = gen_list_buttons(media_type, i64::MAX, i64::MAX, &i18n);
+        assert_eq!(buttons.len(), 3);
+    }
+}
+
+#[test]
+fn list_status_lines() {
+    let i18n = fixtures::i18n("en");
+    let entry = |progress, score| ListEntry {
+        id: 1,
+        user_id: Some(1),
+        status: Some(ListStatus::Current),
+        progress,
+        score,
+    };
+
+    assert_eq!(
+        gen_list_status(
+            &entry(Some(7), Some(8.0)),
+            MediaType::Anime,
+            Some(12),
+            &i18n
+        )
+        .as_deref(),
+        Some("📝 | <b>Your status</b>: <i>Watching — 7/12, score 8</i>\n")
+    );
+    assert_eq!(
+        gen_list_status(&entry(Some(30), Some(7.5)), MediaType::Manga, None, &i18n).as_deref(),
+        Some("📝 | <b>Your status</b>: <i>Reading — 30, score 7.5</i>\n")
+    );
+    assert_eq!(
+        gen_list_status(
+            &entry(Some(0), Some(0.0)),
+            MediaType::Anime,
+            Some(12),
+            &i18n
+        )
+        .as_deref(),
+        Some("📝 | <b>Your status</b>: <i>Watching</i>\n")
+    );
+
+    let mut unknown = entry(None, None);
+    unknown.status = None;
+    assert_eq!(
+        gen_list_status(&unknown, MediaType::Anime, None, &i18n),
+        None
+    );
+}
+
+#[test]
+fn missing_list_entries_are_parsed() {
+    let missing: MediaListEntry =
+        serde_json::from_value(json!({ "MediaList": null })).expect("invalid list entry");
+
+    assert!(missing.entry.is_none());
+}
+
+#[test]
+fn user_lists_show_progress_and_score() {
+    let page: MediaListPage = serde_json::from_value(json!({
+        "Page": {
+            "pageInfo": { "lastPage": 34 },
+            "mediaList": [
+                { "progress": 7, "score": 8.5, "media": { "id": 1, "title": { "romaji": "Cowboy Bebop" }, "episodes": 26 } },
+                { "progress": 120, "score": 0, "media": { "id": 30013, "title": { "romaji": "ONE PIECE" }, "chapters": null } },
+                { "progress": 1, "score": 0, "media": null },
+                { "progress": 3, "score": 0, "media": { "id": 2, "title": { "romaji": "Kono Subarashii Sekai ni Shukufuku wo! Kurenai Densetsu" }, "episodes": 1 } }
+            ]
+        }
+    }))
+    .expect("invalid list page");
+    let page = collect(page.page);
+
+    let text = gen_user_list("Watching", &page.entries);
+
+    assert_eq!(page.last_page, 34);
+    assert_eq!(page.entries.len(), 3);
+    assert!(text.contains("<code>1</code> | <b>Cowboy Bebop</b> — <i>7/26</i> · ⭐ <i>8.5</i>\n"));
+    assert!(text.contains("<code>30013</code> | <b>ONE PIECE</b> — <i>120/?</i>\n"));
+    assert!(text.contains("<b>Kono Subarashii Sekai ni Shukufuku...</b>"));
+    assert_eq!(validate_html(&text), Ok(()));
+}
+
+#[test]
+fn private_lists_are_detected() {
+    assert!(is_private(&QueryError::Api("Private User".to_string())));
+    assert!(!is_private(&QueryError::Api("Not Found.".to_string())));
+    assert!(!is_private(&QueryError::Request));
+}
@@ -0,0 +1,44 @@
+// Copyright 2025 - Andriel Ferreira
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// https://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or https://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+//! Tests for the images uploaded by the cards.
+
+use yamata_no_orochi::{resources::media_files::PhotoFile, utils::is_stale_file_error};
+
+#[test]
+fn file_ids_round_trip() {
+    let photo = PhotoFile {
+        id: 5042,
+        access_hash: -77,
+        file_reference: vec![1, 2, 3, 255],
+        dc_id: 2,
+    };
+
+    let file_id = photo.to_file_id();
+
+    assert_eq!(file_id, "5042:-77:2:AQID_w");
+    assert_eq!(PhotoFile::parse(&file_id), Some(photo));
+}
+
+#[test]
+fn malformed_file_ids_are_rejected() {
+    assert_eq!(PhotoFile::parse(""), None);
+    assert_eq!(PhotoFile::parse("5042:-77:2"), None);
+    assert_eq!(PhotoFile::parse("5042:-77:2:AQID:extra"), None);
+    assert_eq!(PhotoFile::parse("photo:-77:2:AQID"), None);
+    assert_eq!(PhotoFile::parse("5042:-77:2:not base64!"), None);
+}
+
+#[test]
+fn rejected_files_are_uploaded_again() {
+    assert!(is_stale_file_error("FILE_REFERENCE_EXPIRED"));
+    assert!(is_stale_file_error("FILE_REFERENCE_INVALID"));
+    assert!(is_stale_file_error("MEDIA_EMPTY"));
+    assert!(!is_stale_file_error("MESSAGE_TOO_LONG"));
+    assert!(!is_stale_file_error("FLOOD_WAIT"));
+}
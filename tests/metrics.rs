@@ -0,0 +1,111 @@
+// Copyright 2025 - Andriel Ferreira
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// https://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or https://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+//! Tests for the metrics resource.
+
+use std::time::Duration;
+
+use yamata_no_orochi::{
+    resources::{
+        Metrics,
+        metrics::{Histogram, callback_verb},
+    },
+    tasks::metrics_server::gen_response,
+};
+
+#[test]
+fn callback_verb_keeps_the_view_name() {
+    assert_eq!(
+        callback_verb("anime tags 1 123"),
+        Some("anime tags".to_string())
+    );
+    assert_eq!(
+        callback_verb("char medias 5 123"),
+        Some("char medias".to_string())
+    );
+    assert_eq!(callback_verb("anime 1 123"), Some("anime".to_string()));
+    assert_eq!(callback_verb("language"), Some("language".to_string()));
+    assert_eq!(callback_verb(""), None);
+}
+
+#[test]
+fn callbacks_are_batched_until_taken() {
+    let metrics = Metrics::new();
+
+    metrics.record_callback("anime tags 1 123");
+    metrics.record_callback("anime tags 2 456");
+    metrics.record_callback("manga chars 3 123");
+
+    let counts = metrics.take_callbacks();
+    assert_eq!(counts.get("anime tags"), Some(&2));
+    assert_eq!(counts.get("manga chars"), Some(&1));
+    assert!(metrics.take_callbacks().is_empty());
+
+    metrics.record_callback("anime tags 1 123");
+    metrics.restore_callbacks(counts);
+    assert_eq!(metrics.take_callbacks().get("anime tags"), Some(&3));
+}
+
+#[test]
+fn histogram_buckets_are_cumulative() {
+    let mut histogram = Histogram::new(&[0.1, 1.0, 10.0]);
+
+    histogram.observe(0.05);
+    histogram.observe(0.5);
+    histogram.observe(0.7);
+    histogram.observe(30.0);
+
+    assert_eq!(histogram.cumulative(), vec![(0.1, 1), (1.0, 3), (10.0, 3)]);
+}
+
+#[test]
+fn metrics_are_rendered_for_prometheus() {
+    let metrics = Metrics::new();
+
+    metrics.record_update("new_message");
+    metrics.record_update("new_message");
+    metrics.record_update("callback_query");
+    metrics.record_update_duration(Duration::from_millis(20));
+    metrics.record_anilist_request("ok");
+    metrics.record_anilist_request("rate_limited");
+    metrics.record_cache_lookup("anime", true);
+    metrics.record_cache_lookup("anime", false);
+    metrics.record_cache_lookup("anime", true);
+
+    let text = metrics.render();
+
+    assert!(text.contains("# TYPE yamata_updates_total counter\n"));
+    assert!(text.contains("yamata_updates_total{kind=\"new_message\"} 2\n"));
+    assert!(text.contains("yamata_updates_total{kind=\"callback_query\"} 1\n"));
+    assert!(text.contains("yamata_update_duration_seconds_bucket{le=\"0.01\"} 0\n"));
+    assert!(text.contains("yamata_update_duration_seconds_bucket{le=\"0.025\"} 1\n"));
+    assert!(text.contains("yamata_update_duration_seconds_bucket{le=\"+Inf\"} 1\n"));
+    assert!(text.contains("yamata_update_duration_seconds_count 1\n"));
+    assert!(text.contains("yamata_anilist_requests_total{outcome=\"rate_limited\"} 1\n"));
+    assert!(text.contains("yamata_cache_lookups_total{cache=\"anime\",result=\"hit\"} 2\n"));
+    assert!(text.contains("yamata_cache_lookups_total{cache=\"anime\",result=\"miss\"} 1\n"));
+}
+
+#[test]
+fn only_the_metrics_path_is_served() {
+    let metrics = Metrics::new();
+    metrics.record_update("inline_query");
+
+    let response = gen_response("GET /metrics HTTP/1.1\r\nHost: localhost\r\n\r\n", &metrics);
+    let (head, body) = response.split_once("\r\n\r\n").unwrap();
+    assert!(head.starts_with("HTTP/1.1 200 OK\r\n"));
+    assert!(head.contains(&format!("Content-Length: {}", body.len())));
+    assert!(body.contains("yamata_updates_total{kind=\"inline_query\"} 1"));
+
+    assert!(
+        gen_response("GET /metrics?x=1 HTTP/1.1\r\n\r\n", &metrics).starts_with("HTTP/1.1 200")
+    );
+    assert!(gen_response("GET / HTTP/1.1\r\n\r\n", &metrics).starts_with("HTTP/1.1 404"));
+    assert!(gen_response("POST /metrics HTTP/1.1\r\n\r\n", &metrics).starts_with("HTTP/1.1 405"));
+    assert!(gen_response("", &metrics).starts_with("HTTP/1.1 405"));
+}
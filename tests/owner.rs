@@ -0,0 +1,91 @@
+// Copyright 2025 - Andriel Ferreira
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// https://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or https://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+//! Tests for the admin commands.
+
+mod fixtures;
+
+use chrono::NaiveDate;
+use yamata_no_orochi::{
+    plugins::owner::{
+        BroadcastProgress, Growth, gen_broadcast_report, gen_growth_report, is_blocked_error,
+        parse_ban_args,
+    },
+    utils::validate_html,
+};
+
+fn day(day: u32) -> NaiveDate {
+    NaiveDate::from_ymd_opt(2026, 10, day).unwrap()
+}
+
+#[test]
+fn blocked_users_are_told_apart_from_failures() {
+    assert!(is_blocked_error("USER_IS_BLOCKED"));
+    assert!(is_blocked_error("INPUT_USER_DEACTIVATED"));
+    assert!(!is_blocked_error("FLOOD_WAIT"));
+    assert!(!is_blocked_error("PEER_ID_INVALID"));
+}
+
+#[test]
+fn broadcast_report_counts_each_outcome() {
+    let i18n = fixtures::i18n("en");
+    let progress = BroadcastProgress {
+        total: 1500,
+        sent: 1200,
+        blocked: 290,
+        failed: 10,
+    };
+
+    let text = gen_broadcast_report(&progress, &i18n);
+
+    assert_eq!(progress.done(), 1500);
+    assert!(text.contains("<b>Sent</b>: 1 200"));
+    assert!(text.contains("<b>Blocked</b>: 290"));
+    assert!(text.contains("<b>Failed</b>: 10"));
+    assert_eq!(validate_html(&text), Ok(()));
+}
+
+#[test]
+fn growth_report_lists_every_day() {
+    let i18n = fixtures::i18n("en");
+    let users = Growth {
+        total: 12_480,
+        daily: vec![(day(10), 5), (day(16), 30)],
+        active: 2_051,
+    };
+    let groups = Growth {
+        total: 310,
+        daily: vec![(day(12), 2)],
+        active: 87,
+    };
+
+    let text = gen_growth_report(&users, &groups, day(16), &i18n);
+
+    assert!(text.contains("<b>Users</b>: 12 480 (+35)"));
+    assert!(text.contains("<b>Groups</b>: 310 (+2)"));
+    assert!(text.contains("<b>Active</b>: 2 051 users, 87 groups"));
+    assert!(text.contains("<code>2026-10-10</code>: +5 users, +0 groups"));
+    assert!(text.contains("<code>2026-10-12</code>: +0 users, +2 groups"));
+    assert!(text.contains("<code>2026-10-16</code>: +30 users, +0 groups"));
+    assert!(!text.contains("2026-10-09"));
+    assert_eq!(text.matches("<code>2026-10-").count(), 7);
+    assert_eq!(validate_html(&text), Ok(()));
+}
+
+#[test]
+fn ban_arguments_take_an_id_and_an_optional_reason() {
+    assert_eq!(parse_ban_args("/ban 123456"), Some((123456, None)));
+    assert_eq!(
+        parse_ban_args("/ban 123456  spamming inline queries "),
+        Some((123456, Some("spamming inline queries")))
+    );
+    assert_eq!(parse_ban_args("/unban 42"), Some((42, None)));
+    assert_eq!(parse_ban_args("/ban"), None);
+    assert_eq!(parse_ban_args("/ban someone spam"), None);
+    assert_eq!(parse_ban_args("/ban -5"), None);
+}
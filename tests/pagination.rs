@@ -0,0 +1,97 @@
+// Copyright 2025 - Andriel Ferreira
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// https://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or https://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+//! Tests for the paginated lists.
+
+use yamata_no_orochi::utils::{gen_pagination_entries, page_count};
+
+#[test]
+fn page_count_rounds_up() {
+    assert_eq!(page_count(0, 10), 0);
+    assert_eq!(page_count(9, 10), 1);
+    assert_eq!(page_count(10, 10), 1);
+    assert_eq!(page_count(11, 10), 2);
+    assert_eq!(page_count(150, 10), 15);
+}
+
+/// Gets the labels and callback data of the pagination buttons of a list.
+///
+/// # Arguments
+///
+/// * `page` - The current page number.
+/// * `max_pages` - The number of pages.
+fn entries(page: usize, max_pages: usize) -> Vec<(String, String)> {
+    gen_pagination_entries(page, max_pages, |i| format!("anime chars 1 2 {}", i))
+}
+
+/// Gets the labels of the pagination buttons of a list.
+///
+/// # Arguments
+///
+/// * `page` - The current page number.
+/// * `max_pages` - The number of pages.
+fn labels(page: usize, max_pages: usize) -> Vec<String> {
+    entries(page, max_pages)
+        .into_iter()
+        .map(|(label, _)| label)
+        .collect()
+}
+
+#[test]
+fn pagination_on_the_first_page() {
+    assert_eq!(labels(1, 1), ["· 1 ·"]);
+    assert_eq!(labels(1, 3), ["· 1 ·", "2 ➡️", "3 ➡️"]);
+    assert_eq!(labels(1, 30), ["· 1 ·", "2 ➡️", "3 ➡️", "30 »"]);
+}
+
+#[test]
+fn pagination_on_the_second_page() {
+    assert_eq!(labels(2, 3), ["⬅️ 1", "· 2 ·", "3 ➡️"]);
+    assert_eq!(labels(2, 30), ["⬅️ 1", "· 2 ·", "3 ➡️", "4 ➡️", "30 »"]);
+}
+
+#[test]
+fn pagination_in_the_middle() {
+    assert_eq!(
+        labels(15, 30),
+        ["« 1", "⬅️ 13", "⬅️ 14", "· 15 ·", "16 ➡️", "17 ➡️", "30 »"]
+    );
+    // A last page inside the window needs no jump.
+    assert_eq!(
+        labels(4, 6),
+        ["« 1", "⬅️ 2", "⬅️ 3", "· 4 ·", "5 ➡️", "6 ➡️"]
+    );
+    assert_eq!(labels(3, 5), ["⬅️ 1", "⬅️ 2", "· 3 ·", "4 ➡️", "5 ➡️"]);
+}
+
+#[test]
+fn pagination_on_the_last_page() {
+    assert_eq!(labels(3, 3), ["⬅️ 1", "⬅️ 2", "· 3 ·"]);
+    assert_eq!(labels(30, 30), ["« 1", "⬅️ 28", "⬅️ 29", "· 30 ·"]);
+}
+
+#[test]
+fn pagination_callbacks_point_to_their_pages() {
+    assert_eq!(
+        entries(15, 30),
+        [
+            ("« 1".to_string(), "anime chars 1 2 1".to_string()),
+            ("⬅️ 13".to_string(), "anime chars 1 2 13".to_string()),
+            ("⬅️ 14".to_string(), "anime chars 1 2 14".to_string()),
+            ("· 15 ·".to_string(), "anime chars 1 2 15".to_string()),
+            ("16 ➡️".to_string(), "anime chars 1 2 16".to_string()),
+            ("17 ➡️".to_string(), "anime chars 1 2 17".to_string()),
+            ("30 »".to_string(), "anime chars 1 2 30".to_string()),
+        ]
+    );
+}
+
+#[test]
+fn pagination_without_pages_is_empty() {
+    assert!(entries(1, 0).is_empty());
+}
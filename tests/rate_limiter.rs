@@ -0,0 +1,71 @@
+// Copyright 2025 - Andriel Ferreira
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// https://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or https://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+//! Tests for the rate limiter resource.
+
+use std::time::Duration;
+
+use yamata_no_orochi::resources::RateLimiter;
+
+#[tokio::test]
+async fn allows_the_budget_at_once() {
+    let limiter = RateLimiter::new(3);
+
+    for _ in 0..3 {
+        assert_eq!(limiter.acquire().await, Ok(()));
+    }
+}
+
+#[tokio::test]
+async fn refuses_turns_too_far_away() {
+    // One request every 20 seconds, beyond the max wait.
+    let limiter = RateLimiter::new(3);
+    for _ in 0..3 {
+        limiter.acquire().await.unwrap();
+    }
+
+    let wait = limiter.acquire().await.unwrap_err();
+    assert!(wait > Duration::from_secs(15));
+}
+
+#[tokio::test]
+async fn waits_for_close_turns() {
+    // One request every 50 milliseconds.
+    let limiter = RateLimiter::new(1200);
+    for _ in 0..1200 {
+        limiter.acquire().await.unwrap();
+    }
+
+    let started_at = std::time::Instant::now();
+    assert_eq!(limiter.acquire().await, Ok(()));
+    assert!(started_at.elapsed() >= Duration::from_millis(25));
+}
+
+#[tokio::test]
+async fn blocks_until_the_retry_after() {
+    let limiter = RateLimiter::new(60);
+    assert_eq!(limiter.retry_after(), None);
+
+    limiter.block_for(Duration::from_secs(30));
+    limiter.block_for(Duration::from_secs(10));
+
+    let remaining = limiter.retry_after().unwrap();
+    assert!(remaining > Duration::from_secs(25));
+    assert!(limiter.acquire().await.unwrap_err() > Duration::from_secs(25));
+}
+
+#[tokio::test]
+async fn unblocks_once_the_retry_after_passes() {
+    let limiter = RateLimiter::new(60);
+    limiter.block_for(Duration::from_millis(10));
+
+    tokio::time::sleep(Duration::from_millis(20)).await;
+
+    assert_eq!(limiter.retry_after(), None);
+    assert_eq!(limiter.acquire().await, Ok(()));
+}
@@ -0,0 +1,55 @@
+// Copyright 2025 - Andriel Ferreira
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// https://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or https://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+//! Tests for the recommendations of a media.
+
+mod fixtures;
+
+use yamata_no_orochi::{
+    models::MediaType,
+    resources::anilist::recommendations::{MediaRecommendations, collect},
+    utils::{gen_recommendation_list, validate_html},
+};
+
+fn recommendations() -> MediaRecommendations {
+    serde_json::from_str(
+        r#"{
+            "Media": { "recommendations": {
+                "pageInfo": { "lastPage": 3 },
+                "nodes": [
+                    { "rating": 512, "mediaRecommendation": { "id": 205, "type": "ANIME", "title": { "romaji": "Samurai Champloo" } } },
+                    { "rating": 88, "mediaRecommendation": { "id": 30002, "type": "MANGA", "title": { "romaji": "Berserk" } } },
+                    { "rating": 10, "mediaRecommendation": null }
+                ]
+            } }
+        }"#,
+    )
+    .unwrap()
+}
+
+#[test]
+fn deleted_media_are_skipped() {
+    let page = collect(recommendations().media.recommendations);
+
+    assert_eq!(page.last_page, 3);
+    assert_eq!(page.recommendations.len(), 2);
+    assert_eq!(page.recommendations[0].rating, 512);
+    assert_eq!(page.recommendations[1].media_type, MediaType::Manga);
+}
+
+#[test]
+fn recommendation_list_shows_the_ratings() {
+    let i18n = fixtures::i18n("en");
+    let page = collect(recommendations().media.recommendations);
+
+    let text = gen_recommendation_list(&page.recommendations, &i18n);
+
+    assert!(text.contains("📺 | <code>205</code>. <b>Samurai Champloo</b> — 👍 <i>512</i>"));
+    assert!(text.contains("📖 | <code>30002</code>. <b>Berserk</b> — 👍 <i>88</i>"));
+    assert_eq!(validate_html(&text), Ok(()));
+}
@@ -0,0 +1,32 @@
+// Copyright 2025 - Andriel Ferreira
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// https://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or https://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+//! Tests for the seeds resource.
+
+use std::time::Duration;
+
+use yamata_no_orochi::resources::Seeds;
+
+#[tokio::test]
+async fn seeds_are_stable_within_the_ttl() {
+    let seeds = Seeds::new(Duration::from_secs(60));
+
+    let seed = seeds.seed(1, "random").await;
+    assert_eq!(seeds.seed(1, "random").await, seed);
+    assert_eq!(seeds.seed(1, "random").await, seed);
+}
+
+#[tokio::test]
+async fn seeds_are_rolled_again_after_the_ttl() {
+    let seeds = Seeds::new(Duration::from_millis(10));
+
+    let seed = seeds.seed(1, "random").await;
+    tokio::time::sleep(Duration::from_millis(20)).await;
+
+    assert_ne!(seeds.seed(1, "random").await, seed);
+}
@@ -0,0 +1,178 @@
+// Copyright 2025 - Andriel Ferreira
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// https://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or https://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+//! Tests for the sessions resource.
+
+use std::time::{Duration, Instant};
+
+use chrono::Utc;
+use yamata_no_orochi::{
+    models::{AniListAuth, User},
+    resources::{
+        Sessions,
+        sessions::{
+            AUTH_ATTEMPT_TTL, AttemptError, AuthAttempt, STATE_LENGTH, gen_state, token_expiry,
+            token_fingerprint,
+        },
+    },
+};
+
+#[test]
+fn gen_state_is_random_and_alphanumeric() {
+    let a = gen_state();
+    let b = gen_state();
+
+    assert_eq!(a.len(), STATE_LENGTH);
+    assert!(a.chars().all(|c| c.is_ascii_alphanumeric()));
+    assert_ne!(a, b);
+}
+
+#[test]
+fn attempt_matches_only_its_state() {
+    let attempt = AuthAttempt::new();
+
+    assert!(attempt.matches(&attempt.state.clone()));
+    assert!(!attempt.matches(&gen_state()));
+    assert!(!attempt.matches(""));
+    assert!(!attempt.matches(&attempt.state[1..]));
+}
+
+#[test]
+fn attempt_expires_after_ttl() {
+    let mut attempt = AuthAttempt::new();
+    assert!(!attempt.is_expired());
+
+    if let Some(started_at) = Instant::now().checked_sub(AUTH_ATTEMPT_TTL + Duration::from_secs(1))
+    {
+        attempt.started_at = started_at;
+        assert!(attempt.is_expired());
+    }
+}
+
+#[tokio::test]
+async fn start_attempt_reuses_pending_state() {
+    let sessions = Sessions::new();
+
+    let first = sessions.start_attempt(1).await;
+    let second = sessions.start_attempt(1).await;
+    let other = sessions.start_attempt(2).await;
+
+    assert_eq!(first.state, second.state);
+    assert_ne!(first.state, other.state);
+}
+
+#[tokio::test]
+async fn verify_attempt_accepts_matching_state() {
+    let sessions = Sessions::new();
+    let attempt = sessions.start_attempt(1).await;
+
+    assert!(sessions.verify_attempt(1, &attempt.state).await.is_ok());
+    assert_eq!(
+        sessions
+            .verify_attempt(1, &attempt.state)
+            .await
+            .unwrap_err(),
+        AttemptError::Expired
+    );
+}
+
+#[tokio::test]
+async fn verify_attempt_rejects_other_users_state() {
+    let sessions = Sessions::new();
+    let attempt = sessions.start_attempt(1).await;
+    sessions.start_attempt(2).await;
+
+    assert_eq!(
+        sessions
+            .verify_attempt(2, &attempt.state)
+            .await
+            .unwrap_err(),
+        AttemptError::StateMismatch
+    );
+    assert!(sessions.attempt(2).await.is_none());
+}
+
+#[tokio::test]
+async fn verify_attempt_without_pending_attempt_expires() {
+    let sessions = Sessions::new();
+
+    assert_eq!(
+        sessions.verify_attempt(1, "state").await.unwrap_err(),
+        AttemptError::Expired
+    );
+}
+
+#[test]
+fn token_expiry_reads_the_jwt_claims() {
+    // {"alg":"none"}.{"sub":"1","exp":1767225600}.
+    let token = "eyJhbGciOiJub25lIn0.eyJzdWIiOiIxIiwiZXhwIjoxNzY3MjI1NjAwfQ.";
+
+    assert_eq!(
+        token_expiry(token).map(|expiry| expiry.timestamp()),
+        Some(1767225600)
+    );
+    assert_eq!(token_expiry("not a token"), None);
+}
+
+#[test]
+fn token_fingerprint_does_not_reveal_the_token() {
+    let fingerprint = token_fingerprint("secret-token");
+
+    assert_eq!(fingerprint.len(), 8);
+    assert_eq!(fingerprint, token_fingerprint("secret-token"));
+    assert_ne!(fingerprint, token_fingerprint("other-token"));
+    assert!(!"secret-token".contains(&fingerprint));
+}
+
+#[test]
+fn expired_tokens_need_a_new_authentication() {
+    let user = |token: Option<&str>, expires_at| User {
+        id: 1,
+        anilist_id: token.map(|_| 1),
+        anilist_token: token.map(String::from),
+        anilist_token_expires_at: expires_at,
+        language_code: "en".to_string(),
+        group_results: true,
+        allow_adult: false,
+        title_language: "romaji".to_string(),
+        voice_actor_language: "japanese".to_string(),
+        track_history: true,
+        chapter_notifications: false,
+        last_seen_at: None,
+        interaction_count: 0,
+        packed_chat: None,
+        created_at: Utc::now(),
+        updated_at: Utc::now(),
+    };
+    let hour = chrono::Duration::hours(1);
+
+    assert_eq!(AniListAuth::of(None), AniListAuth::Anonymous);
+    assert_eq!(
+        AniListAuth::of(Some(&user(None, None))),
+        AniListAuth::Anonymous
+    );
+    assert_eq!(
+        AniListAuth::of(Some(&user(Some("token"), Some(Utc::now() + hour)))),
+        AniListAuth::Authenticated
+    );
+    assert_eq!(
+        AniListAuth::of(Some(&user(Some("token"), Some(Utc::now() - hour)))),
+        AniListAuth::Expired
+    );
+
+    // Tokens stored before their expiry was tracked fall back on their claims.
+    let expired = "eyJhbGciOiJub25lIn0.eyJzdWIiOiIxIiwiZXhwIjoxNzY3MjI1NjAwfQ.";
+    assert_eq!(
+        AniListAuth::of(Some(&user(Some(expired), None))),
+        AniListAuth::Expired
+    );
+    assert_eq!(
+        AniListAuth::of(Some(&user(Some("not a token"), None))),
+        AniListAuth::Authenticated
+    );
+}
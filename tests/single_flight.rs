@@ -0,0 +1,109 @@
+// Copyright 2025 - Andriel Ferreira
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// https://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or https://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+//! Tests for the single-flight resource.
+
+use std::{
+    sync::{
+        Arc,
+        atomic::{AtomicUsize, Ordering},
+    },
+    time::Duration,
+};
+
+use yamata_no_orochi::resources::{Cache, SingleFlight};
+
+/// A slow client standing in for AniList, counting the requests it receives.
+#[derive(Clone, Default)]
+struct SlowClient {
+    /// How many requests were made.
+    calls: Arc<AtomicUsize>,
+}
+
+impl SlowClient {
+    async fn get_anime(&self, id: i64) -> Option<String> {
+        self.calls.fetch_add(1, Ordering::SeqCst);
+        tokio::time::sleep(Duration::from_millis(50)).await;
+
+        Some(format!("anime {}", id))
+    }
+}
+
+/// Mirrors `AniList::get_anime`: cache first, then a coalesced request.
+async fn get_anime(
+    client: &SlowClient,
+    cache: &Cache<i64, String>,
+    flights: &SingleFlight<i64, Option<String>>,
+    id: i64,
+) -> Option<String> {
+    if let Some(anime) = cache.get(&id).await {
+        return Some(anime);
+    }
+
+    flights
+        .run(id, || async {
+            let anime = client.get_anime(id).await?;
+            cache.insert(id, anime.clone()).await;
+
+            Some(anime)
+        })
+        .await
+}
+
+#[tokio::test]
+async fn concurrent_calls_make_a_single_request() {
+    let client = SlowClient::default();
+    let cache = Cache::with_capacity(10);
+    let flights = SingleFlight::new();
+
+    let results = tokio::join!(
+        get_anime(&client, &cache, &flights, 1),
+        get_anime(&client, &cache, &flights, 1),
+        get_anime(&client, &cache, &flights, 1),
+        get_anime(&client, &cache, &flights, 1),
+        get_anime(&client, &cache, &flights, 1),
+    );
+
+    assert_eq!(client.calls.load(Ordering::SeqCst), 1);
+    assert_eq!(results.0, Some("anime 1".to_string()));
+    assert_eq!(results.0, results.4);
+    assert_eq!(flights.in_flight(), 0);
+}
+
+#[tokio::test]
+async fn different_keys_are_not_coalesced() {
+    let client = SlowClient::default();
+    let cache = Cache::with_capacity(10);
+    let flights = SingleFlight::new();
+
+    let (first, second) = tokio::join!(
+        get_anime(&client, &cache, &flights, 1),
+        get_anime(&client, &cache, &flights, 2),
+    );
+
+    assert_eq!(client.calls.load(Ordering::SeqCst), 2);
+    assert_eq!(first, Some("anime 1".to_string()));
+    assert_eq!(second, Some("anime 2".to_string()));
+}
+
+#[tokio::test]
+async fn landed_requests_run_again() {
+    let flights = SingleFlight::new();
+    let calls = AtomicUsize::new(0);
+
+    for _ in 0..2 {
+        flights
+            .run(1, || async {
+                calls.fetch_add(1, Ordering::SeqCst);
+                None::<String>
+            })
+            .await;
+    }
+
+    assert_eq!(calls.load(Ordering::SeqCst), 2);
+}
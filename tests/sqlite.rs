@@ -0,0 +1,250 @@
+// Copyright 2025 - Andriel Ferreira
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// https://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or https://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+//! Tests for the models on an in-memory SQLite database.
+
+use std::time::Duration;
+
+use chrono::{Days, Utc};
+use grammers_client::session::{PackedChat, PackedType};
+use yamata_no_orochi::{
+    models::{
+        EntryKind, Group, MangaProgressSnapshot, NewGroup, NewUser, UpdateGroup, UpdateUser, User,
+        UserData, ViewHistory,
+    },
+    resources::{
+        Database, DisabledCommands,
+        database::{Backend, PoolSettings},
+    },
+};
+
+async fn connect() -> Database {
+    let settings = PoolSettings {
+        max_connections: 1,
+        acquire_timeout: Duration::from_secs(5),
+        connect_retry: Duration::ZERO,
+    };
+
+    let db = Database::connect("sqlite::memory:", &settings)
+        .await
+        .expect("failed to open the database");
+    db.migrate(None).await.expect("failed to migrate");
+
+    db
+}
+
+#[tokio::test]
+async fn users_are_created_and_updated() {
+    let db = connect().await;
+    let pool = db.pool();
+    assert_eq!(pool.backend(), Backend::Sqlite);
+
+    assert!(User::get_by_id(pool, &1).await.unwrap().is_none());
+
+    let user = NewUser::new(1, "pt".to_string())
+        .create(pool)
+        .await
+        .unwrap();
+    assert_eq!(user.language_code, "pt");
+    assert!(user.group_results);
+    assert!(!user.allow_adult);
+    assert_eq!(user.title_language, "romaji");
+
+    let mut update_user: UpdateUser = user.into();
+    update_user.language_code = "en".to_string();
+    update_user.set_token(Some("token"));
+    update_user.update(pool).await.unwrap();
+
+    let user = User::get_by_id(pool, &1).await.unwrap().unwrap();
+    assert_eq!(user.language_code, "en");
+    assert_eq!(user.token().as_deref(), Some("token"));
+    assert!(user.updated_at >= user.created_at);
+
+    assert!(
+        NewUser::new(1, "pt".to_string())
+            .create(pool)
+            .await
+            .is_err()
+    );
+
+    NewUser::new(2, "pt".to_string())
+        .create(pool)
+        .await
+        .unwrap();
+    assert_eq!(User::count(pool).await.unwrap(), 2);
+    let mut languages = User::count_by_language(pool).await.unwrap();
+    languages.sort();
+    assert_eq!(
+        languages,
+        vec![("en".to_string(), 1), ("pt".to_string(), 1)]
+    );
+
+    let yesterday = Utc::now().checked_sub_days(Days::new(1)).unwrap();
+    assert_eq!(
+        User::count_by_day(pool, yesterday).await.unwrap(),
+        vec![(Utc::now().date_naive(), 2)]
+    );
+
+    User::set_blocked(pool, 2, true).await.unwrap();
+    assert_eq!(User::reachable(pool).await.unwrap(), vec![(1, None)]);
+}
+
+#[tokio::test]
+async fn activity_is_recorded() {
+    let db = connect().await;
+    let pool = db.pool();
+
+    let user = NewUser::new(1, "pt".to_string())
+        .create(pool)
+        .await
+        .unwrap();
+    assert!(user.last_seen_at.is_none());
+    assert_eq!(user.interaction_count, 0);
+
+    let before = Utc::now();
+    let chat = PackedChat {
+        ty: PackedType::User,
+        id: 1,
+        access_hash: Some(42),
+    };
+    User::touch(pool, 1, &chat.to_hex()).await.unwrap();
+    User::touch(pool, 1, &chat.to_hex()).await.unwrap();
+
+    let user = User::get_by_id(pool, &1).await.unwrap().unwrap();
+    assert!(user.last_seen_at.is_some_and(|seen| seen >= before));
+    assert_eq!(user.interaction_count, 2);
+    assert_eq!(user.chat(), Some(chat));
+    assert_eq!(User::active_since(pool, before).await.unwrap(), 1);
+    assert_eq!(User::active_since(pool, Utc::now()).await.unwrap(), 0);
+
+    NewGroup::new(-100, "pt".to_string())
+        .create(pool)
+        .await
+        .unwrap();
+    Group::touch(pool, -100, "not a chat").await.unwrap();
+    assert_eq!(Group::active_since(pool, before).await.unwrap(), 1);
+
+    let group = Group::get_by_id(pool, &-100).await.unwrap().unwrap();
+    assert_eq!(group.chat(), None);
+}
+
+#[tokio::test]
+async fn groups_are_created_and_updated() {
+    let db = connect().await;
+    let pool = db.pool();
+
+    let group = NewGroup::new(-100, "pt".to_string())
+        .create(pool)
+        .await
+        .unwrap();
+    assert!(!group.birthdays);
+    assert_eq!(group.clean_service_messages, 0);
+
+    let mut update_group: UpdateGroup = group.into();
+    update_group.language_code = "en".to_string();
+    update_group.birthdays = true;
+    update_group.clean_service_messages = 60;
+    update_group.update(pool).await.unwrap();
+
+    let group = Group::get_by_id(pool, &-100).await.unwrap().unwrap();
+    assert_eq!(group.language_code, "en");
+    assert_eq!(group.clean_service_messages, 60);
+    assert_eq!(Group::count(pool).await.unwrap(), 1);
+    assert_eq!(Group::with_birthdays(pool).await.unwrap().len(), 1);
+}
+
+#[tokio::test]
+async fn user_data_is_deleted() {
+    let db = connect().await;
+    let pool = db.pool();
+
+    NewUser::new(1, "pt".to_string())
+        .create(pool)
+        .await
+        .unwrap();
+    ViewHistory::record(pool, 1, EntryKind::Anime, 20, "Naruto")
+        .await
+        .unwrap();
+    MangaProgressSnapshot::replace(pool, 1, &[(30, 10), (31, 5)])
+        .await
+        .unwrap();
+    MangaProgressSnapshot::replace(pool, 1, &[(30, 12)])
+        .await
+        .unwrap();
+
+    let data = UserData::collect(pool, 1).await.unwrap();
+    assert!(data.user.is_some());
+    assert_eq!(data.history.len(), 1);
+    assert_eq!(data.manga_progress.len(), 1);
+    assert_eq!(data.manga_progress[0].chapters, 12);
+
+    UserData::delete(pool, 1).await.unwrap();
+
+    let data = UserData::collect(pool, 1).await.unwrap();
+    assert!(data.user.is_none());
+    assert!(data.history.is_empty());
+    assert!(data.manga_progress.is_empty());
+}
+
+#[tokio::test]
+async fn commands_are_turned_off_per_group() {
+    let db = connect().await;
+    let disabled_commands = DisabledCommands::new();
+
+    assert_eq!(
+        disabled_commands
+            .set(&db, -100, "char", false)
+            .await
+            .unwrap(),
+        None
+    );
+
+    NewGroup::new(-100, "pt".to_string())
+        .create(db.pool())
+        .await
+        .unwrap();
+    NewGroup::new(-200, "pt".to_string())
+        .create(db.pool())
+        .await
+        .unwrap();
+    assert!(!disabled_commands.is_disabled(&db, -100, "char").await);
+
+    disabled_commands
+        .set(&db, -100, "char", false)
+        .await
+        .unwrap();
+    let disabled = disabled_commands
+        .set(&db, -100, "anime", false)
+        .await
+        .unwrap();
+    assert_eq!(
+        disabled,
+        Some(vec!["anime".to_string(), "char".to_string()])
+    );
+    assert!(disabled_commands.is_disabled(&db, -100, "char").await);
+    assert!(!disabled_commands.is_disabled(&db, -200, "char").await);
+
+    // A fresh cache reads the same commands back from the database.
+    let group = Group::get_by_id(db.pool(), &-100).await.unwrap().unwrap();
+    assert_eq!(group.disabled_commands, "anime,char");
+    assert!(
+        DisabledCommands::new()
+            .is_disabled(&db, -100, "anime")
+            .await
+    );
+
+    disabled_commands
+        .set(&db, -100, "char", true)
+        .await
+        .unwrap();
+    assert!(!disabled_commands.is_disabled(&db, -100, "char").await);
+    assert_eq!(
+        disabled_commands.get(&db, -100).await,
+        vec!["anime".to_string()]
+    );
+}
@@ -0,0 +1,72 @@
+// Copyright 2025 - Andriel Ferreira
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// https://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or https://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+//! Tests for the staff info.
+
+mod fixtures;
+
+use yamata_no_orochi::{
+    resources::anilist::staff::Staff,
+    utils::{gen_staff_info, gen_work_list, validate_html},
+};
+
+fn staff() -> Staff {
+    serde_json::from_str(
+        r#"{
+            "id": 95185,
+            "name": { "full": "Kana Hanazawa", "native": "花澤香菜" },
+            "languageV2": "Japanese",
+            "age": 36,
+            "homeTown": "Tokyo, Japan",
+            "primaryOccupations": ["Voice Actor", "Singer"],
+            "description": "A <b>voice actress</b> and singer.",
+            "siteUrl": "https://anilist.co/staff/95185",
+            "image": { "large": "https://example.com/kana.png" },
+            "characters": { "nodes": [{ "id": 1, "name": { "full": "Mayuri Shiina", "native": null } }] },
+            "staffMedia": { "edges": [
+                { "staffRole": "Theme Song Performance", "node": { "id": 9253, "type": "ANIME", "title": { "romaji": "Steins;Gate" } } }
+            ] }
+        }"#,
+    )
+    .unwrap()
+}
+
+#[test]
+fn staff_info_is_valid_html() {
+    for locale in ["en", "pt"] {
+        let i18n = fixtures::i18n(locale);
+        let text = gen_staff_info(&staff(), &i18n);
+
+        assert!(text.contains("Kana Hanazawa"));
+        assert!(text.contains("Voice Actor, Singer"));
+        assert_eq!(validate_html(&text), Ok(()));
+    }
+}
+
+#[test]
+fn works_show_their_role() {
+    let i18n = fixtures::i18n("en");
+    let staff = staff();
+
+    let text = gen_work_list(&staff.staff_media.edges, 1, 10, &i18n);
+
+    assert!(text.contains("Steins;Gate"));
+    assert!(text.contains("Theme Song Performance"));
+}
+
+#[test]
+fn minimal_staff_has_no_roles() {
+    let staff: Staff = serde_json::from_str(
+        r#"{ "id": 1, "name": { "full": "Someone", "native": null }, "siteUrl": "https://anilist.co/staff/1" }"#,
+    )
+    .unwrap();
+
+    assert!(staff.characters.nodes.is_empty());
+    assert!(staff.staff_media.edges.is_empty());
+    assert_eq!(staff.image_url(), "");
+}
@@ -0,0 +1,67 @@
+// Copyright 2025 - Andriel Ferreira
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// https://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or https://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+//! Tests for the runtime statistics of the bot.
+
+mod fixtures;
+
+use std::time::Duration;
+
+use yamata_no_orochi::{
+    resources::stats::{Snapshot, parse_vm_rss},
+    utils::{format_uptime, gen_about_stats, validate_html},
+};
+
+#[test]
+fn vm_rss_is_read_in_bytes() {
+    let status = "Name:\tyamata\nVmPeak:\t  20480 kB\nVmRSS:\t   10240 kB\nThreads:\t4\n";
+
+    assert_eq!(parse_vm_rss(status), Some(10 * 1024 * 1024));
+    assert_eq!(parse_vm_rss("Name:\tyamata\n"), None);
+    assert_eq!(parse_vm_rss("VmRSS:\tlots kB\n"), None);
+}
+
+#[test]
+fn uptime_is_shown_in_days_hours_and_minutes() {
+    assert_eq!(format_uptime(Duration::from_secs(59)), "0m");
+    assert_eq!(
+        format_uptime(Duration::from_secs(2 * 3600 + 5 * 60)),
+        "2h 5m"
+    );
+    assert_eq!(
+        format_uptime(Duration::from_secs(3 * 86400 + 4 * 3600 + 12 * 60)),
+        "3d 4h 12m"
+    );
+}
+
+#[test]
+fn stats_show_unavailable_numbers() {
+    let i18n = fixtures::i18n("en");
+    let snapshot = Snapshot {
+        uptime: Duration::from_secs(3600),
+        users: Some(12_480),
+        groups: None,
+        database_latency: Some(Duration::from_millis(4)),
+        cache_hits: 3,
+        cache_misses: 1,
+        memory: Some(52_428_800),
+        ..Default::default()
+    };
+
+    let text = gen_about_stats(&snapshot, &i18n);
+
+    assert_eq!(snapshot.cache_hit_rate(), Some(75));
+    assert_eq!(Snapshot::default().cache_hit_rate(), None);
+    assert!(text.contains("<b>Uptime</b>: <code>1h 0m</code>"));
+    assert!(text.contains("<b>Users</b>: <code>12 480</code>"));
+    assert!(text.contains("<b>Groups</b>: <code>Not available.</code>"));
+    assert!(text.contains("<b>Database</b>: <code>4 ms</code>"));
+    assert!(text.contains("<b>Cache hit rate</b>: <code>75%</code>"));
+    assert!(text.contains("<b>Memory</b>: <code>50.0 MB</code>"));
+    assert_eq!(validate_html(&text), Ok(()));
+}
@@ -0,0 +1,53 @@
+// Copyright 2025 - Andriel Ferreira
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// https://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or https://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+//! Tests for the studio info.
+
+mod fixtures;
+
+use yamata_no_orochi::{resources::anilist::studios::Studio, utils::gen_studio_info};
+
+fn studio() -> Studio {
+    serde_json::from_str(
+        r#"{
+            "id": 11,
+            "name": "Trigger",
+            "isAnimationStudio": true,
+            "siteUrl": "https://anilist.co/studio/11",
+            "favourites": 1234,
+            "media": { "nodes": [
+                { "id": 1, "type": "ANIME", "format": "TV", "averageScore": 80, "title": { "romaji": "First" } },
+                { "id": 2, "type": "ANIME", "format": "MOVIE", "averageScore": null, "title": { "romaji": "Second" } },
+                { "id": 3, "type": "ANIME", "format": "TV", "averageScore": 70, "title": { "romaji": "Third" } }
+            ] }
+        }"#,
+    )
+    .unwrap()
+}
+
+#[test]
+fn studio_info_lists_a_page_of_productions() {
+    let i18n = fixtures::i18n("en");
+
+    let text = gen_studio_info(&studio(), 2, 2, &i18n);
+
+    assert!(text.contains("Trigger"));
+    assert!(text.contains("1234"));
+    assert!(text.contains("Third"));
+    assert!(!text.contains("First"));
+}
+
+#[test]
+fn missing_productions_are_empty() {
+    let studio: Studio = serde_json::from_str(
+        r#"{ "id": 1, "name": "Aniplex", "isAnimationStudio": false, "siteUrl": "https://anilist.co/studio/1", "favourites": null }"#,
+    )
+    .unwrap();
+
+    assert!(studio.media.nodes.is_empty());
+}
@@ -0,0 +1,44 @@
+// Copyright 2025 - Andriel Ferreira
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// https://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or https://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+//! Tests for the throttle resource.
+
+use std::time::Duration;
+
+use yamata_no_orochi::resources::Throttle;
+
+#[tokio::test]
+async fn allows_once_per_window() {
+    let throttle = Throttle::new(10, Duration::from_secs(60));
+
+    assert!(throttle.allow(1).await);
+    assert!(!throttle.allow(1).await);
+    assert!(!throttle.allow(1).await);
+}
+
+#[tokio::test]
+async fn keys_are_independent() {
+    let throttle = Throttle::new(10, Duration::from_secs(60));
+
+    assert!(throttle.allow(1).await);
+    assert!(throttle.allow(2).await);
+    assert!(!throttle.allow(1).await);
+    assert!(!throttle.allow(2).await);
+}
+
+#[tokio::test]
+async fn allows_again_after_window() {
+    let throttle = Throttle::new(10, Duration::from_millis(20));
+
+    assert!(throttle.allow(1).await);
+    assert!(!throttle.allow(1).await);
+
+    tokio::time::sleep(Duration::from_millis(30)).await;
+
+    assert!(throttle.allow(1).await);
+}
@@ -0,0 +1,56 @@
+// Copyright 2025 - Andriel Ferreira
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// https://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or https://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+//! Tests for the encryption of the stored tokens.
+
+use yamata_no_orochi::resources::{
+    TokenCipher,
+    token_cipher::{self, ENCRYPTED_PREFIX},
+};
+
+#[test]
+fn tokens_round_trip() {
+    let cipher = TokenCipher::from_base64(&TokenCipher::gen_key()).unwrap();
+
+    let encrypted = cipher.encrypt("eyJhbGciOi.token.signature");
+    assert!(encrypted.starts_with(ENCRYPTED_PREFIX));
+    assert!(!encrypted.contains("token"));
+    assert_eq!(
+        cipher.decrypt(&encrypted).as_deref(),
+        Some("eyJhbGciOi.token.signature")
+    );
+
+    // Every encryption has its own nonce.
+    assert_ne!(encrypted, cipher.encrypt("eyJhbGciOi.token.signature"));
+}
+
+#[test]
+fn tokens_of_another_key_are_not_decrypted() {
+    let cipher = TokenCipher::from_base64(&TokenCipher::gen_key()).unwrap();
+    let other = TokenCipher::from_base64(&TokenCipher::gen_key()).unwrap();
+
+    let encrypted = cipher.encrypt("token");
+    assert_eq!(other.decrypt(&encrypted), None);
+    assert_eq!(cipher.decrypt(&format!("{}AAAA", ENCRYPTED_PREFIX)), None);
+    assert_eq!(cipher.decrypt("token"), None);
+}
+
+#[test]
+fn invalid_keys_are_rejected() {
+    assert!(TokenCipher::from_base64("not base64!").is_none());
+    assert!(TokenCipher::from_base64("c2hvcnQ=").is_none());
+}
+
+#[test]
+fn plain_tokens_are_read_as_they_are() {
+    assert!(!token_cipher::is_encrypted("eyJhbGciOi.token.signature"));
+    assert_eq!(
+        token_cipher::open("eyJhbGciOi.token.signature").as_deref(),
+        Some("eyJhbGciOi.token.signature")
+    );
+}
@@ -0,0 +1,42 @@
+// Copyright 2025 - Andriel Ferreira
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// https://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or https://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+//! Tests for the rankings of the best scored media.
+
+mod fixtures;
+
+use yamata_no_orochi::{
+    resources::anilist::top::{MediaPage, collect},
+    utils::{TopKind, gen_top_list, validate_html},
+};
+
+#[test]
+fn ranks_continue_across_pages() {
+    let i18n = fixtures::i18n("en");
+    let page: MediaPage = serde_json::from_str(
+        r#"{
+            "Page": {
+                "pageInfo": { "lastPage": 500 },
+                "media": [
+                    { "id": 5114, "title": { "romaji": "Hagane no Renkinjutsushi: FULLMETAL ALCHEMIST" }, "averageScore": 90 },
+                    { "id": 9253, "title": { "romaji": "Steins;Gate" }, "averageScore": null }
+                ]
+            }
+        }"#,
+    )
+    .unwrap();
+    let page = collect(page.page);
+
+    let text = gen_top_list(TopKind::Anime, &page.media, 10, &i18n);
+
+    assert_eq!(page.last_page, 500);
+    assert!(text.starts_with("🏆 <b>Top animes</b>:"));
+    assert!(text.contains("<b>11.</b> <code>5114</code> | <b>Hagane no Renkinjutsushi: FULLMETAL ALCHEMIST</b> · 🌟 <i>90%</i>\n"));
+    assert!(text.contains("<b>12.</b> <code>9253</code> | <b>Steins;Gate</b>\n"));
+    assert_eq!(validate_html(&text), Ok(()));
+}
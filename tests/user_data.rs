@@ -0,0 +1,58 @@
+// Copyright 2025 - Andriel Ferreira
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// https://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or https://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+//! Tests for the export of the users' data.
+
+use chrono::Utc;
+use yamata_no_orochi::models::{User, UserData, user_data::USER_TABLES};
+
+#[test]
+fn export_leaves_the_token_out() {
+    let data = UserData {
+        user: Some(User {
+            id: 1,
+            anilist_id: Some(2),
+            anilist_token: Some("secret-token".to_string()),
+            anilist_token_expires_at: Some(Utc::now()),
+            language_code: "en".to_string(),
+            group_results: true,
+            allow_adult: false,
+            title_language: "romaji".to_string(),
+            voice_actor_language: "japanese".to_string(),
+            track_history: true,
+            chapter_notifications: false,
+            last_seen_at: None,
+            interaction_count: 0,
+            packed_chat: None,
+            created_at: Utc::now(),
+            updated_at: Utc::now(),
+        }),
+        ..Default::default()
+    };
+
+    let json = data.to_json();
+    assert_eq!(json["user"]["anilist_authenticated"], true);
+    assert_eq!(json["user"]["anilist_id"], 2);
+    assert!(json["user"].get("anilist_token").is_none());
+    assert!(!json.to_string().contains("secret-token"));
+}
+
+#[test]
+fn export_of_unknown_user_is_empty() {
+    let json = UserData::default().to_json();
+
+    assert!(json["user"].is_null());
+    assert!(json["ban"].is_null());
+    assert_eq!(json["favorites"].as_array().map(Vec::len), Some(0));
+}
+
+#[test]
+fn deletion_keeps_the_bans() {
+    assert!(!USER_TABLES.contains(&"banned_users"));
+    assert!(!USER_TABLES.contains(&"users"));
+}
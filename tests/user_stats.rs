@@ -0,0 +1,89 @@
+// Copyright 2025 - Andriel Ferreira
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// https://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or https://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+//! Tests for the statistics and favourites of a user.
+
+mod fixtures;
+
+use yamata_no_orochi::{
+    resources::anilist::{
+        user_favourites::{UserFavourites, collect},
+        user_stats::UserStatistics,
+    },
+    utils::{gen_bar, gen_user_favourites, gen_user_stats, gen_user_stats_summary, validate_html},
+};
+
+#[test]
+fn bars_fill_proportionally() {
+    assert_eq!(gen_bar(10, 10, 5), "■■■■■");
+    assert_eq!(gen_bar(6, 10, 5), "■■■□□");
+    assert_eq!(gen_bar(1, 100, 5), "■□□□□");
+    assert_eq!(gen_bar(0, 100, 5), "□□□□□");
+    assert_eq!(gen_bar(3, 0, 5), "□□□□□");
+    assert_eq!(gen_bar(20, 10, 5), "■■■■■");
+}
+
+#[test]
+fn stats_skip_empty_media_types() {
+    let i18n = fixtures::i18n("en");
+    let stats: UserStatistics = serde_json::from_str(
+        r#"{
+            "User": { "statistics": {
+                "anime": {
+                    "count": 120, "meanScore": 78.5, "minutesWatched": 72000, "episodesWatched": 2900,
+                    "genres": [ { "genre": "Action", "count": 80 }, { "genre": "Slice of Life", "count": 40 } ],
+                    "statuses": [ { "status": "COMPLETED", "count": 96 }, { "status": "DROPPED", "count": 24 } ]
+                },
+                "manga": { "count": 0, "meanScore": 0, "chaptersRead": 0, "volumesRead": 0, "genres": [], "statuses": [] }
+            } }
+        }"#,
+    )
+    .unwrap();
+
+    let text = gen_user_stats(&stats.user.statistics, &i18n);
+
+    assert!(text.contains("• <b>Days watched</b>: <i>50.0</i>"));
+    assert!(text.contains("• <b>Mean score</b>: <i>78.5</i>"));
+    assert!(text.contains("<code>■■■■■</code> Action <i>(80)</i>"));
+    assert!(text.contains("<code>■■■□□</code> Slice of Life <i>(40)</i>"));
+    assert!(text.contains("<code>■■■■□</code> Completed <i>(96)</i>"));
+    assert!(!text.contains("📚 Manga"));
+    assert_eq!(validate_html(&text), Ok(()));
+
+    let summary = gen_user_stats_summary(&stats.user.statistics, &i18n);
+
+    assert_eq!(
+        summary,
+        "<b>📺 Anime</b>: <i>120</i> entries, <i>2 900</i> episodes"
+    );
+    assert_eq!(validate_html(&summary), Ok(()));
+}
+
+#[test]
+fn favourites_use_titles_and_names() {
+    let i18n = fixtures::i18n("en");
+    let favourites: UserFavourites = serde_json::from_str(
+        r#"{
+            "User": { "favourites": {
+                "anime": { "nodes": [ { "id": 1, "title": { "romaji": "Cowboy Bebop" } } ] },
+                "manga": { "nodes": [] },
+                "characters": { "nodes": [ { "id": 1, "name": { "full": "Spike Spiegel" } } ] }
+            } }
+        }"#,
+    )
+    .unwrap();
+    let favourites = collect(favourites.user.favourites);
+
+    let text = gen_user_favourites(&favourites, &i18n);
+
+    assert_eq!(favourites.anime[0].name, "Cowboy Bebop");
+    assert_eq!(favourites.characters[0].name, "Spike Spiegel");
+    assert!(!text.contains("📚 Manga"));
+    assert!(text.contains("<code>1</code> | <b>Spike Spiegel</b>"));
+    assert_eq!(validate_html(&text), Ok(()));
+}
@@ -0,0 +1,457 @@
+// Copyright 2025 - Andriel Ferreira
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// https://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or https://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+//! Tests for the utility functions.
+
+use chrono::{TimeZone, Utc};
+use yamata_no_orochi::utils::{
+    AniListLink, AnimeSeason, FranchiseGroup, HtmlTarget, SearchSort, StartPayload, TopKind,
+    escape_html, find_anilist_link, franchise_base, gen_deep_link, group_by_franchise, interleave,
+    is_inline_query_too_short, normalize_genre, normalize_search_terms, parse_search_query,
+    remove_html, reply_query, sanitize_html, shorten_html, shorten_text, suggestion_queries,
+    validate_html,
+};
+
+#[test]
+fn remove_html_strips_known_tags() {
+    assert_eq!(
+        remove_html("<p><i>Hello</i><br>world</p>"),
+        "Hello\nworld".to_string()
+    );
+    assert_eq!(remove_html("<ul><li>one</li></ul>"), "• one".to_string());
+}
+
+#[test]
+fn remove_html_keeps_stray_brackets() {
+    assert_eq!(remove_html("a < b <3"), "a < b <3".to_string());
+}
+
+/// Real AniList descriptions, with their plain text and Telegram HTML versions.
+const DESCRIPTIONS: [(&str, &str, &str); 8] = [
+    (
+        "Enter a world in the distant future, where Bounty Hunters roam the solar system.<br><br>\n(Source: Crunchyroll)",
+        "Enter a world in the distant future, where Bounty Hunters roam the solar system.\n\n(Source: Crunchyroll)",
+        "Enter a world in the distant future, where Bounty Hunters roam the solar system.\n\n(Source: Crunchyroll)",
+    ),
+    (
+        "Kenzou Tenma is a renowned neurosurgeon.<br>\n<br>\n<br>\n<I>(Source: Anime News Network)</I>",
+        "Kenzou Tenma is a renowned neurosurgeon.\n\n(Source: Anime News Network)",
+        "Kenzou Tenma is a renowned neurosurgeon.\n\n<i>(Source: Anime News Network)</i>",
+    ),
+    (
+        "Based on the <a href=\"https://anilist.co/manga/30002/\">manga</a> by Kentarou Miura.",
+        "Based on the manga (https://anilist.co/manga/30002/) by Kentarou Miura.",
+        "Based on the <a href=\"https://anilist.co/manga/30002/\">manga</a> by Kentarou Miura.",
+    ),
+    (
+        "<b>Note:</b> Eren ~!becomes the Attack Titan!~ in episode 8.",
+        "Note: Eren in episode 8.",
+        "<b>Note:</b> Eren <tg-spoiler>becomes the Attack Titan</tg-spoiler> in episode 8.",
+    ),
+    (
+        "Taken from &quot;Shounen Jump&quot; &amp; <spoiler>Shounen Magazine</spoiler>.",
+        "Taken from \"Shounen Jump\" & Shounen Magazine.",
+        "Taken from \"Shounen Jump\" &amp; Shounen Magazine.",
+    ),
+    (
+        "Seasons:<ul><li>Part 1</li><li>Part 2</li></ul>",
+        "Seasons:\n• Part 1\n• Part 2",
+        "Seasons:\n• Part 1\n• Part 2",
+    ),
+    (
+        "<p>First paragraph.</p><p>Second <em>paragraph</em>.</p>",
+        "First paragraph.\n\nSecond paragraph.",
+        "First paragraph.\n\nSecond <i>paragraph</i>.",
+    ),
+    (
+        "<strong>Unclosed bold and <a href=\"javascript:alert(1)\">a bad link</a>",
+        "Unclosed bold and a bad link",
+        "<b>Unclosed bold and a bad link</b>",
+    ),
+];
+
+#[test]
+fn sanitize_html_handles_anilist_descriptions() {
+    for (description, plain, telegram) in DESCRIPTIONS {
+        assert_eq!(sanitize_html(description, HtmlTarget::Plain), plain);
+        assert_eq!(remove_html(description), plain);
+        assert_eq!(sanitize_html(description, HtmlTarget::Telegram), telegram);
+        assert_eq!(validate_html(telegram), Ok(()));
+    }
+}
+
+#[test]
+fn sanitize_html_closes_crossed_tags() {
+    let html = sanitize_html("<b>bold <i>both</b> italic</i>", HtmlTarget::Telegram);
+
+    assert_eq!(html, "<b>bold <i>both</i></b> italic");
+    assert_eq!(validate_html(&html), Ok(()));
+}
+
+#[test]
+fn shorten_html_keeps_tags_balanced() {
+    let html = "<b>Shingeki</b> no <i>Kyojin &amp; more</i>";
+
+    assert_eq!(shorten_html(html, 40), html);
+    assert_eq!(shorten_html(html, 14), "<b>Shingeki</b> no...");
+    assert_eq!(shorten_html(html, 18), "<b>Shingeki</b> no...");
+    assert_eq!(
+        shorten_html(html, 22),
+        "<b>Shingeki</b> no <i>Kyojin...</i>"
+    );
+    assert_eq!(
+        shorten_html(html, 24),
+        "<b>Shingeki</b> no <i>Kyojin &amp;...</i>"
+    );
+    assert_eq!(shorten_html(html, 2), "<b>Sh</b>");
+
+    for max_length in 0..30 {
+        assert_eq!(validate_html(&shorten_html(html, max_length)), Ok(()));
+    }
+}
+
+#[test]
+fn escape_html_escapes_special_characters() {
+    let cases = [
+        ("Tom & Jerry", "Tom &amp; Jerry"),
+        ("a < b > c", "a &lt; b &gt; c"),
+        ("\"Oshi no Ko\"", "&quot;Oshi no Ko&quot;"),
+        ("JoJo's", "JoJo&#x27;s"),
+        ("&lt;", "&amp;lt;"),
+        ("<b>Title</b>", "&lt;b&gt;Title&lt;/b&gt;"),
+    ];
+
+    for (text, expected) in cases {
+        assert_eq!(escape_html(text), expected);
+    }
+}
+
+#[test]
+fn escape_html_keeps_slashes_and_backslashes() {
+    assert_eq!(
+        escape_html("https://anilist.co/anime/1"),
+        "https://anilist.co/anime/1".to_string()
+    );
+    assert_eq!(escape_html(r"Fate\Zero"), r"Fate\Zero".to_string());
+}
+
+#[test]
+fn shorten_text_keeps_short_text() {
+    assert_eq!(shorten_text("Naruto", 10), "Naruto".to_string());
+}
+
+#[test]
+fn shorten_text_appends_ellipsis() {
+    assert_eq!(
+        shorten_text("Shingeki no Kyojin", 10),
+        "Shingek...".to_string()
+    );
+}
+
+#[test]
+fn shorten_text_prefers_word_boundaries() {
+    assert_eq!(
+        shorten_text("Shingeki no Kyojin", 15),
+        "Shingeki no...".to_string()
+    );
+    assert_eq!(
+        shorten_text("Shingeki no Kyojin", 14),
+        "Shingeki no...".to_string()
+    );
+}
+
+#[test]
+fn shorten_text_counts_chars() {
+    assert_eq!(shorten_text("🍥🍥🍥🍥🍥🍥", 5), "🍥🍥...".to_string());
+    assert_eq!(shorten_text("🍥🍥🍥🍥🍥", 5), "🍥🍥🍥🍥🍥".to_string());
+    assert_eq!(shorten_text("進撃の巨人", 4), "進...".to_string());
+    assert_eq!(shorten_text("進撃の巨人", 5), "進撃の巨人".to_string());
+}
+
+#[test]
+fn shorten_text_handles_tiny_limits() {
+    let expected = ["", "N", "Na", "Nar", "N...", "Na..."];
+
+    for (max_length, expected) in expected.into_iter().enumerate() {
+        let shortened = shorten_text("Naruto Shippuden", max_length);
+
+        assert_eq!(shortened, expected);
+        assert!(shortened.chars().count() <= max_length);
+    }
+}
+
+#[test]
+fn parse_search_query_extracts_sort() {
+    let query = parse_search_query("one piece sort:score");
+
+    assert_eq!(query.terms, "one piece");
+    assert_eq!(query.sort, SearchSort::Score);
+}
+
+#[test]
+fn parse_search_query_keeps_unknown_sort_as_terms() {
+    let query = parse_search_query("sort:nonsense naruto shippuden");
+
+    assert_eq!(query.terms, "sort:nonsense naruto shippuden");
+    assert_eq!(query.sort, SearchSort::Relevance);
+}
+
+#[test]
+fn parse_search_query_defaults_to_relevance() {
+    assert_eq!(
+        parse_search_query("shingeki no kyojin").sort,
+        SearchSort::Relevance
+    );
+    assert_eq!(parse_search_query("naruto").sort, SearchSort::Relevance);
+}
+
+#[test]
+fn parse_search_query_sorts_common_words_by_popularity() {
+    assert_eq!(parse_search_query("love").sort, SearchSort::Popularity);
+    assert_eq!(parse_search_query("k-on").sort, SearchSort::Relevance);
+    assert_eq!(
+        parse_search_query("love sort:newest").sort,
+        SearchSort::Newest
+    );
+}
+
+#[test]
+fn reply_query_uses_the_replied_text() {
+    assert_eq!(
+        reply_query("  have you watched\n frieren? ", false),
+        Some("have you watched frieren?".to_string())
+    );
+    assert_eq!(reply_query("", false), None);
+    assert_eq!(reply_query(" \n ", false), None);
+}
+
+#[test]
+fn reply_query_is_cut_at_a_word_boundary() {
+    let query = reply_query(&"naruto ".repeat(20), false).unwrap();
+    assert_eq!(query.chars().count(), 76);
+    assert!(query.ends_with("naruto"));
+
+    assert_eq!(reply_query(&"a".repeat(100), false), Some("a".repeat(80)));
+}
+
+#[test]
+fn reply_query_uses_the_id_of_the_cards() {
+    assert_eq!(
+        reply_query("21 | One Piece\n\nType: TV", true),
+        Some("21".to_string())
+    );
+    // Only the cards of the bot have their ID used.
+    assert_eq!(
+        reply_query("21 | One Piece", false),
+        Some("21 | One Piece".to_string())
+    );
+    assert_eq!(
+        reply_query("Airing | One Piece", true),
+        Some("Airing | One Piece".to_string())
+    );
+}
+
+#[test]
+fn normalize_search_terms_ignores_case_and_spacing() {
+    assert_eq!(normalize_search_terms("  One   Piece "), "one piece");
+    assert_eq!(
+        normalize_search_terms("Shingeki no Kyojin"),
+        normalize_search_terms("shingeki  no kyojin")
+    );
+}
+
+#[test]
+fn suggestion_queries_drop_the_last_word_then_keep_three() {
+    assert_eq!(suggestion_queries("naruto shipuden"), vec!["naruto"]);
+    assert_eq!(
+        suggestion_queries("kimetsu no yaiba yuukaku hen"),
+        vec!["kimetsu no yaiba yuukaku", "kimetsu no yaiba"]
+    );
+    assert_eq!(suggestion_queries("one piece film"), vec!["one piece"]);
+    assert!(suggestion_queries("naruto").is_empty());
+}
+
+#[test]
+fn search_sort_round_trips_names() {
+    for sort in SearchSort::ALL {
+        assert_eq!(SearchSort::parse(sort.name()), Some(sort));
+    }
+}
+
+#[test]
+fn franchise_base_strips_subtitles_and_markers() {
+    assert_eq!(franchise_base("Shingeki no Kyojin"), "shingeki no kyojin");
+    assert_eq!(
+        franchise_base("Shingeki no Kyojin Season 3 Part 2"),
+        "shingeki no kyojin"
+    );
+    assert_eq!(
+        franchise_base("Shingeki no Kyojin: The Final Season"),
+        "shingeki no kyojin"
+    );
+    assert_eq!(franchise_base("Mob Psycho 100 II"), "mob psycho");
+    assert_eq!(franchise_base("Overlord IV"), "overlord");
+    assert_eq!(franchise_base("Dr. Stone 2nd Season"), "dr. stone");
+    assert_eq!(franchise_base("86"), "86");
+}
+
+#[test]
+fn group_by_franchise_keeps_unrelated_titles_apart() {
+    let groups = group_by_franchise(&[
+        "One Piece",
+        "One Punch Man",
+        "One Piece Film: Red",
+        "One Piece: Stampede",
+        "One Punch Man 2nd Season",
+        "Monster",
+    ]);
+
+    assert_eq!(
+        groups,
+        vec![
+            FranchiseGroup {
+                title: "One Piece".to_string(),
+                members: vec![0, 3],
+            },
+            FranchiseGroup {
+                title: "One Punch Man".to_string(),
+                members: vec![1, 4],
+            },
+            FranchiseGroup {
+                title: "One Piece Film".to_string(),
+                members: vec![2],
+            },
+            FranchiseGroup {
+                title: "Monster".to_string(),
+                members: vec![5],
+            },
+        ]
+    );
+}
+
+#[test]
+fn normalize_genre_matches_anilist() {
+    assert_eq!(normalize_genre("action"), "Action");
+    assert_eq!(normalize_genre("SLICE OF LIFE"), "Slice of Life");
+    assert_eq!(normalize_genre("sci-fi"), "Sci-Fi");
+    assert_eq!(normalize_genre("  mahou   shoujo "), "Mahou Shoujo");
+}
+
+#[test]
+fn season_keywords_are_parsed() {
+    for keyword in AnimeSeason::KEYWORDS {
+        assert_eq!(AnimeSeason::parse(keyword).unwrap().name(), keyword);
+    }
+
+    assert_eq!(AnimeSeason::parse("Outono"), Some(AnimeSeason::Fall));
+    assert_eq!(AnimeSeason::parse("monsoon"), None);
+}
+
+#[test]
+fn top_keywords_are_parsed() {
+    for keyword in TopKind::KEYWORDS {
+        assert_eq!(TopKind::parse(keyword).unwrap().name(), keyword);
+    }
+
+    assert_eq!(TopKind::parse("Filmes"), Some(TopKind::Movie));
+    assert_eq!(TopKind::parse("ova"), None);
+    assert_eq!(TopKind::Movie.format(), Some("MOVIE"));
+    assert_eq!(TopKind::Airing.status(), Some("RELEASING"));
+}
+
+#[test]
+fn december_belongs_to_the_next_winter() {
+    let date = |month| Utc.with_ymd_and_hms(2024, month, 15, 0, 0, 0).unwrap();
+
+    assert_eq!(AnimeSeason::of(&date(12)), (AnimeSeason::Winter, 2025));
+    assert_eq!(AnimeSeason::of(&date(1)), (AnimeSeason::Winter, 2024));
+    assert_eq!(AnimeSeason::of(&date(4)), (AnimeSeason::Spring, 2024));
+    assert_eq!(AnimeSeason::of(&date(7)), (AnimeSeason::Summer, 2024));
+    assert_eq!(AnimeSeason::of(&date(10)), (AnimeSeason::Fall, 2024));
+}
+
+#[test]
+fn find_anilist_link_finds_the_first_link() {
+    assert_eq!(
+        find_anilist_link("have you seen https://anilist.co/anime/1/Cowboy-Bebop/ yet?"),
+        Some(AniListLink::Anime(1))
+    );
+    assert_eq!(
+        find_anilist_link("anilist.co/manga/30002?ref=share and anilist.co/anime/1"),
+        Some(AniListLink::Manga(30002))
+    );
+    assert_eq!(
+        find_anilist_link("https://www.anilist.co/character/1/Spike-Spiegel"),
+        Some(AniListLink::Character(1))
+    );
+    assert_eq!(
+        find_anilist_link("https://anilist.co/user/Andriel/animelist"),
+        Some(AniListLink::User("Andriel".to_string()))
+    );
+    assert_eq!(find_anilist_link("https://notanilist.co/anime/1"), None);
+    assert_eq!(find_anilist_link("https://anilist.co/studio/4"), None);
+    assert_eq!(find_anilist_link("https://anilist.co/anime/"), None);
+}
+
+#[test]
+fn start_payloads_round_trip() {
+    for payload in [
+        StartPayload::Anime(1735),
+        StartPayload::Manga(30002),
+        StartPayload::Character(1),
+        StartPayload::User(5_000_000),
+    ] {
+        assert_eq!(StartPayload::parse(&payload.as_payload()), Some(payload));
+    }
+    assert_eq!(
+        gen_deep_link("yonorochibot", StartPayload::Anime(1735)),
+        "https://t.me/yonorochibot?start=anime_1735"
+    );
+}
+
+#[test]
+fn malformed_start_payloads_are_ignored() {
+    for payload in [
+        "",
+        "anime",
+        "anime_",
+        "anime_0",
+        "anime_-1",
+        "anime_+1",
+        "anime_1x",
+        "anime_1 2",
+        "anime_1_2",
+        "anime_99999999999999999999",
+        "user_99999999999",
+        "studio_1",
+        "error_report",
+        "ANIME_1",
+    ] {
+        assert_eq!(StartPayload::parse(payload), None, "{:?}", payload);
+    }
+}
+
+#[test]
+fn short_inline_queries_wait_for_more_text() {
+    assert!(is_inline_query_too_short("", 3));
+    assert!(is_inline_query_too_short("a", 3));
+    assert!(is_inline_query_too_short(" ab ", 3));
+    assert!(!is_inline_query_too_short("abc", 3));
+    assert!(!is_inline_query_too_short("ゆるキャン", 3));
+    assert!(!is_inline_query_too_short("7", 3));
+}
+
+#[test]
+fn interleave_alternates_and_keeps_the_rest() {
+    assert_eq!(
+        interleave(vec![1, 3, 5, 7], vec![2, 4]),
+        vec![1, 2, 3, 4, 5, 7]
+    );
+    assert_eq!(interleave(vec![1], vec![2, 4, 6]), vec![1, 2, 4, 6]);
+    assert_eq!(interleave(Vec::<u8>::new(), Vec::new()), Vec::<u8>::new());
+}
@@ -0,0 +1,34 @@
+// Copyright 2025 - Andriel Ferreira
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// https://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or https://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+//! Tests for the voice actors of a character.
+
+use yamata_no_orochi::resources::anilist::voice_actors::{MediaEdge, collect};
+
+#[test]
+fn voice_actors_are_not_repeated() {
+    let edges: Vec<MediaEdge> = serde_json::from_str(
+        r#"[
+            { "voiceActors": [
+                { "id": 1, "name": { "full": "Junko Takeuchi" }, "languageV2": "Japanese", "siteUrl": "https://anilist.co/staff/1" },
+                { "id": 2, "name": { "full": "Maile Flanagan" }, "languageV2": "English", "siteUrl": "https://anilist.co/staff/2" }
+            ] },
+            { "voiceActors": [
+                { "id": 1, "name": { "full": "Junko Takeuchi" }, "languageV2": "Japanese", "siteUrl": "https://anilist.co/staff/1" }
+            ] },
+            { }
+        ]"#,
+    )
+    .unwrap();
+
+    let actors = collect(edges);
+    let ids = actors.iter().map(|actor| actor.id).collect::<Vec<_>>();
+
+    assert_eq!(ids, vec![1, 2]);
+    assert_eq!(actors[1].language.as_deref(), Some("English"));
+}
@@ -0,0 +1,52 @@
+// Copyright 2025 - Andriel Ferreira
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// https://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or https://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+//! Tests for the watch party polls.
+
+use grammers_client::grammers_tl_types as tl;
+use yamata_no_orochi::plugins::watchparty::tally_votes;
+
+fn results(voters: Option<Vec<(&[u8], i32)>>) -> tl::types::PollResults {
+    tl::types::PollResults {
+        min: false,
+        results: voters.map(|voters| {
+            voters
+                .into_iter()
+                .map(|(option, voters)| {
+                    tl::enums::PollAnswerVoters::Voters(tl::types::PollAnswerVoters {
+                        chosen: false,
+                        correct: false,
+                        option: option.to_vec(),
+                        voters,
+                    })
+                })
+                .collect()
+        }),
+        total_voters: None,
+        recent_voters: None,
+        solution: None,
+        solution_entities: None,
+    }
+}
+
+#[test]
+fn votes_are_counted_by_option() {
+    assert_eq!(
+        tally_votes(&results(Some(vec![(&b"0"[..], 5), (&b"1"[..], 2)]))),
+        (5, 2)
+    );
+    assert_eq!(
+        tally_votes(&results(Some(vec![(&b"1"[..], 3), (&b"9"[..], 4)]))),
+        (0, 3)
+    );
+}
+
+#[test]
+fn polls_without_votes_are_a_tie() {
+    assert_eq!(tally_votes(&results(None)), (0, 0));
+}